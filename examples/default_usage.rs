@@ -37,6 +37,7 @@ fn main() {
         min_p: None,
         top_a: None,
         seed: None,
+        n: None,
         stop: None,
         logit_bias: None,
         logprobs: None,
@@ -47,6 +48,7 @@ fn main() {
         debug: None,
         plugins: None,
         reasoning: None,
+        extra_body: None,
     };
 
     // After Default implementation - concise:
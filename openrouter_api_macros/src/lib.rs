@@ -0,0 +1,206 @@
+//! Procedural macros for `openrouter_api`.
+//!
+//! Use them through the `macros` feature of `openrouter_api`, which
+//! re-exports them; the generated code refers to `::openrouter_api`.
+
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, Attribute, Expr, FnArg, ItemFn, Lit, Meta, Pat};
+
+/// Generates an agent tool from a function.
+///
+/// Next to the function, `#[openrouter_tool]` adds `<name>_tool()`, which
+/// returns an `openrouter_api::agent::AgentTool` for `Agent::with_tool`. The
+/// tool's `FunctionDescription` is built from the function:
+///
+/// - the name is the function's name;
+/// - the description is the doc comment up to its first `#` heading;
+/// - each parameter becomes a property whose schema comes from
+///   `openrouter_api::agent::ToolParameter`, and is required unless it is an
+///   `Option`. Parameters are described by list items such as
+///   ``- `city`: The city name`` under a `# Arguments` heading.
+///
+/// The function may be `async`. Its parameters must be owned types that
+/// implement `Deserialize`, and it must return `Result<T, E>` with
+/// `T: Serialize` and `E: Into<openrouter_api::Error>`.
+///
+/// ```ignore
+/// use openrouter_api::openrouter_tool;
+///
+/// /// Looks up the current weather.
+/// ///
+/// /// # Arguments
+/// ///
+/// /// - `city`: The city name.
+/// /// - `days`: Forecast days, defaults to 1.
+/// #[openrouter_tool]
+/// async fn get_weather(city: String, days: Option<u32>) -> openrouter_api::Result<String> {
+///     Ok(format!("Sunny in {city} for {} day(s)", days.unwrap_or(1)))
+/// }
+///
+/// let agent = agent.with_tool(get_weather_tool());
+/// ```
+#[proc_macro_attribute]
+pub fn openrouter_tool(attr: TokenStream, item: TokenStream) -> TokenStream {
+    if !attr.is_empty() {
+        return syn::Error::new(Span::call_site(), "#[openrouter_tool] takes no arguments")
+            .to_compile_error()
+            .into();
+    }
+    let function = parse_macro_input!(item as ItemFn);
+    match expand(&function) {
+        Ok(tool) => quote!(#function #tool).into(),
+        Err(error) => {
+            let error = error.to_compile_error();
+            quote!(#function #error).into()
+        }
+    }
+}
+
+fn expand(function: &ItemFn) -> syn::Result<proc_macro2::TokenStream> {
+    let signature = &function.sig;
+    if !signature.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &signature.generics,
+            "#[openrouter_tool] functions cannot be generic",
+        ));
+    }
+
+    let (description, argument_docs) = parse_docs(&function.attrs);
+    let mut names = Vec::new();
+    let mut types = Vec::new();
+    for input in &signature.inputs {
+        let FnArg::Typed(argument) = input else {
+            return Err(syn::Error::new_spanned(
+                input,
+                "#[openrouter_tool] functions cannot take `self`",
+            ));
+        };
+        let Pat::Ident(pattern) = &*argument.pat else {
+            return Err(syn::Error::new_spanned(
+                &argument.pat,
+                "#[openrouter_tool] parameters must be plain identifiers",
+            ));
+        };
+        if let syn::Type::Reference(reference) = &*argument.ty {
+            return Err(syn::Error::new_spanned(
+                reference,
+                "#[openrouter_tool] parameters must be owned types",
+            ));
+        }
+        names.push(pattern.ident.clone());
+        types.push((*argument.ty).clone());
+    }
+
+    let vis = &function.vis;
+    let ident = &signature.ident;
+    let tool_ident = format_ident!("{}_tool", ident);
+    let name = ident.to_string().trim_start_matches("r#").to_string();
+    let keys: Vec<String> = names
+        .iter()
+        .map(|name| name.to_string().trim_start_matches("r#").to_string())
+        .collect();
+    let key_docs = keys.iter().map(|key| match argument_docs.get(key) {
+        Some(doc) => quote!(::core::option::Option::Some(#doc)),
+        None => quote!(::core::option::Option::None),
+    });
+    let description = match description {
+        Some(text) => quote!(::core::option::Option::Some(#text.to_string())),
+        None => quote!(::core::option::Option::None),
+    };
+    let await_call = signature.asyncness.map(|_| quote!(.await));
+    let doc = format!("Returns [`{name}`] as an agent tool.");
+
+    Ok(quote! {
+        #[doc = #doc]
+        #vis fn #tool_ident() -> impl ::openrouter_api::agent::AgentTool {
+            ::openrouter_api::agent::FnTool::new(
+                ::openrouter_api::models::tool::FunctionDescription {
+                    name: #name.to_string(),
+                    description: #description,
+                    parameters: ::openrouter_api::agent::tool_parameters(::std::vec![
+                        #(::openrouter_api::agent::ToolParameterSchema {
+                            name: #keys,
+                            description: #key_docs,
+                            schema: <#types as ::openrouter_api::agent::ToolParameter>::schema(),
+                            required: <#types as ::openrouter_api::agent::ToolParameter>::REQUIRED,
+                        },)*
+                    ]),
+                    strict: ::core::option::Option::None,
+                },
+                |mut arguments| async move {
+                    #(
+                        let #names: #types =
+                            ::openrouter_api::agent::tool_argument(&mut arguments, #keys)?;
+                    )*
+                    ::openrouter_api::agent::tool_output(#ident(#(#names),*) #await_call)
+                },
+            )
+        }
+    })
+}
+
+/// Splits the doc comment into the description before the first heading and
+/// the parameter descriptions listed under `# Arguments`.
+fn parse_docs(attrs: &[Attribute]) -> (Option<String>, std::collections::HashMap<String, String>) {
+    let lines: Vec<String> = attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("doc"))
+        .filter_map(|attr| match &attr.meta {
+            Meta::NameValue(meta) => match &meta.value {
+                Expr::Lit(expr) => match &expr.lit {
+                    Lit::Str(text) => Some(text.value()),
+                    _ => None,
+                },
+                _ => None,
+            },
+            _ => None,
+        })
+        .flat_map(|text| {
+            text.split('\n')
+                .map(|line| line.strip_prefix(' ').unwrap_or(line).to_string())
+                .collect::<Vec<_>>()
+        })
+        .collect();
+
+    let mut description = Vec::new();
+    let mut arguments = std::collections::HashMap::new();
+    let mut section = None;
+    for line in &lines {
+        let trimmed = line.trim();
+        if let Some(heading) = trimmed.strip_prefix('#') {
+            section = Some(heading.trim_start_matches('#').trim().to_ascii_lowercase());
+            continue;
+        }
+        match section.as_deref() {
+            None => description.push(trimmed),
+            Some("arguments" | "parameters") => {
+                if let Some((name, text)) = parse_argument_item(trimmed) {
+                    arguments.insert(name, text);
+                }
+            }
+            Some(_) => {}
+        }
+    }
+
+    let description = description
+        .split(|line| line.is_empty())
+        .filter(|paragraph| !paragraph.is_empty())
+        .map(|paragraph| paragraph.join(" "))
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    ((!description.is_empty()).then_some(description), arguments)
+}
+
+/// Parses ``- `name`: text`` or ``* `name` - text``.
+fn parse_argument_item(line: &str) -> Option<(String, String)> {
+    let rest = line
+        .strip_prefix('-')
+        .or_else(|| line.strip_prefix('*'))?
+        .trim_start()
+        .strip_prefix('`')?;
+    let (name, text) = rest.split_once('`')?;
+    let text = text.trim_start().trim_start_matches([':', '-', '—']).trim();
+    Some((name.to_string(), text.to_string()))
+}
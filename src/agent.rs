@@ -0,0 +1,893 @@
+//! A minimal tool-using agent loop.
+//!
+//! An [`Agent`] sends a conversation to a model with its registered tools,
+//! runs any tool calls in the response, appends the results and repeats until
+//! the model answers without calling a tool or `max_steps` is reached — a
+//! ReAct-style loop.
+//!
+//! Tools are closures ([`FnTool`]), functions annotated with
+//! `#[openrouter_tool]` (`macros` feature), tools served by an MCP server
+//! ([`McpTool`]) or any other [`AgentTool`]. Tool failures are reported back
+//! to the model as the tool's result so it can recover; request failures end
+//! the run. [`AgentHooks`] observe every message, tool call and error.
+
+use crate::api::chat::ChatApi;
+use crate::error::{Error, Result};
+use crate::mcp::client::MCPClient;
+use crate::mcp::types::ToolCallParams;
+use crate::models::tool::{FunctionDescription, Tool, ToolCall};
+use crate::types::chat::{ChatCompletionRequest, ChatRole, Message, ToolResult};
+use crate::types::conversation::Conversation;
+use crate::utils::budget::{self, BudgetGuard, RequestSize};
+use futures::stream::{self, StreamExt};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Default limit on model calls per run.
+const DEFAULT_MAX_STEPS: usize = 10;
+
+/// Default limit on tool calls running at once.
+const DEFAULT_TOOL_CONCURRENCY: usize = 4;
+
+/// Future returned by [`AgentTool::call`].
+pub type ToolFuture<'a> = Pin<Box<dyn Future<Output = Result<Value>> + Send + 'a>>;
+
+/// A tool the agent can offer to the model.
+pub trait AgentTool: Send + Sync {
+    /// The name, description and parameter schema sent to the model.
+    fn definition(&self) -> &FunctionDescription;
+
+    /// Runs the tool with the model's parsed arguments.
+    ///
+    /// The output reaches the model through `ToolResult::from`: strings as
+    /// text, an `image_url` content part as an image and anything else as
+    /// JSON.
+    fn call<'a>(&'a self, arguments: Value) -> ToolFuture<'a>;
+}
+
+/// Adapts an async closure into an [`AgentTool`].
+pub struct FnTool<F> {
+    definition: FunctionDescription,
+    f: F,
+}
+
+impl<F, Fut> FnTool<F>
+where
+    F: Fn(Value) -> Fut + Send + Sync,
+    Fut: Future<Output = Result<Value>> + Send + 'static,
+{
+    /// Wraps `f` as the tool described by `definition`.
+    pub fn new(definition: FunctionDescription, f: F) -> Self {
+        Self { definition, f }
+    }
+}
+
+impl<F, Fut> AgentTool for FnTool<F>
+where
+    F: Fn(Value) -> Fut + Send + Sync,
+    Fut: Future<Output = Result<Value>> + Send + 'static,
+{
+    fn definition(&self) -> &FunctionDescription {
+        &self.definition
+    }
+
+    fn call<'a>(&'a self, arguments: Value) -> ToolFuture<'a> {
+        Box::pin((self.f)(arguments))
+    }
+}
+
+/// Maps a Rust parameter type to the JSON Schema sent to the model.
+///
+/// Used by the `#[openrouter_tool]` macro (`macros` feature); implement it
+/// for your own types to use them as tool parameters.
+pub trait ToolParameter {
+    /// Whether the model must supply the parameter.
+    const REQUIRED: bool = true;
+
+    /// The JSON Schema of the parameter.
+    fn schema() -> Value;
+}
+
+macro_rules! tool_parameter {
+    ($kind:literal: $($ty:ty),*) => {
+        $(impl ToolParameter for $ty {
+            fn schema() -> Value {
+                serde_json::json!({"type": $kind})
+            }
+        })*
+    };
+}
+
+tool_parameter!("string": String, char);
+tool_parameter!("boolean": bool);
+tool_parameter!("integer": i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+tool_parameter!("number": f32, f64);
+
+impl<T: ToolParameter> ToolParameter for Option<T> {
+    const REQUIRED: bool = false;
+
+    fn schema() -> Value {
+        T::schema()
+    }
+}
+
+impl<T: ToolParameter> ToolParameter for Vec<T> {
+    fn schema() -> Value {
+        serde_json::json!({"type": "array", "items": T::schema()})
+    }
+}
+
+impl<T: ToolParameter> ToolParameter for std::collections::HashMap<String, T> {
+    fn schema() -> Value {
+        serde_json::json!({"type": "object", "additionalProperties": T::schema()})
+    }
+}
+
+impl<T: ToolParameter> ToolParameter for std::collections::BTreeMap<String, T> {
+    fn schema() -> Value {
+        serde_json::json!({"type": "object", "additionalProperties": T::schema()})
+    }
+}
+
+impl ToolParameter for Value {
+    fn schema() -> Value {
+        serde_json::json!({})
+    }
+}
+
+/// One parameter of a tool, as passed to [`tool_parameters`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ToolParameterSchema {
+    pub name: &'static str,
+    pub description: Option<&'static str>,
+    pub schema: Value,
+    pub required: bool,
+}
+
+/// Builds the object schema of a tool's parameters.
+pub fn tool_parameters(parameters: Vec<ToolParameterSchema>) -> Value {
+    let mut properties = serde_json::Map::new();
+    let mut required = Vec::new();
+    for parameter in parameters {
+        let mut schema = parameter.schema;
+        if let (Some(description), Some(object)) = (parameter.description, schema.as_object_mut()) {
+            object.insert("description".into(), description.into());
+        }
+        if parameter.required {
+            required.push(Value::from(parameter.name));
+        }
+        properties.insert(parameter.name.to_string(), schema);
+    }
+    serde_json::json!({
+        "type": "object",
+        "properties": properties,
+        "required": required,
+    })
+}
+
+/// Takes the argument `name` out of the model's arguments object. A missing
+/// argument reads as `null`, so optional parameters become `None`.
+#[doc(hidden)]
+pub fn tool_argument<T: serde::de::DeserializeOwned>(
+    arguments: &mut Value,
+    name: &str,
+) -> Result<T> {
+    let value = arguments
+        .as_object_mut()
+        .and_then(|object| object.remove(name))
+        .unwrap_or(Value::Null);
+    serde_json::from_value(value)
+        .map_err(|e| Error::ValidationError(format!("Invalid argument '{name}': {e}")))
+}
+
+/// Converts a tool function's result into the tool's output.
+#[doc(hidden)]
+pub fn tool_output<T: serde::Serialize, E: Into<Error>>(
+    result: std::result::Result<T, E>,
+) -> Result<Value> {
+    serde_json::to_value(result.map_err(Into::into)?).map_err(Error::SerializationError)
+}
+
+/// A tool served by an MCP server, called by its function name.
+pub struct McpTool {
+    client: Arc<MCPClient>,
+    definition: FunctionDescription,
+}
+
+impl McpTool {
+    /// Exposes the MCP tool named `definition.name` to the model.
+    pub fn new(client: Arc<MCPClient>, definition: FunctionDescription) -> Self {
+        Self { client, definition }
+    }
+}
+
+impl AgentTool for McpTool {
+    fn definition(&self) -> &FunctionDescription {
+        &self.definition
+    }
+
+    fn call<'a>(&'a self, arguments: Value) -> ToolFuture<'a> {
+        Box::pin(async move {
+            let response = self
+                .client
+                .tool_call(ToolCallParams {
+                    id: self.definition.name.clone(),
+                    parameters: arguments,
+                })
+                .await?;
+            Ok(response.result)
+        })
+    }
+}
+
+/// Observes an agent run. Every method has a no-op default.
+///
+/// `step` counts model calls, starting at 1.
+pub trait AgentHooks: Send + Sync {
+    /// Called for each message added to the conversation: the model's replies
+    /// and tool results.
+    fn on_message(&self, step: usize, message: &Message) {
+        let _ = (step, message);
+    }
+
+    /// Called before a tool call is run.
+    fn on_tool_call(&self, step: usize, call: &ToolCall) {
+        let _ = (step, call);
+    }
+
+    /// Called for request errors, which end the run, and for tool errors,
+    /// which are reported to the model.
+    fn on_error(&self, step: usize, error: &Error) {
+        let _ = (step, error);
+    }
+}
+
+/// Why an agent run stopped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AgentStop {
+    /// The model answered without calling a tool.
+    Finished,
+    /// The run used `max_steps` model calls without a final answer.
+    MaxSteps,
+}
+
+/// The result of an agent run.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct AgentRun {
+    /// Text of the model's last reply.
+    pub output: String,
+    /// The full conversation, including tool calls, results and usage.
+    pub conversation: Conversation,
+    /// Number of model calls made.
+    pub steps: usize,
+    pub stop: AgentStop,
+}
+
+/// How an [`Agent`] runs the tool calls of one response.
+///
+/// Calls run concurrently up to `max_concurrency`; their results are always
+/// appended in the order of the calls. A call that fails or times out gets a
+/// result of the form `{"error": {"type": ..., "message": ...}}`, with type
+/// `timeout`, `invalid_call` (unknown tool or malformed arguments) or
+/// `tool_error`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ToolExecutionPolicy {
+    /// Maximum tool calls running at once (minimum 1). Defaults to 4.
+    pub max_concurrency: usize,
+    /// Time limit for each call, unless overridden in `tool_timeouts`.
+    pub timeout: Option<Duration>,
+    /// Time limits by tool name.
+    pub tool_timeouts: HashMap<String, Duration>,
+}
+
+impl Default for ToolExecutionPolicy {
+    fn default() -> Self {
+        Self {
+            max_concurrency: DEFAULT_TOOL_CONCURRENCY,
+            timeout: None,
+            tool_timeouts: HashMap::new(),
+        }
+    }
+}
+
+impl ToolExecutionPolicy {
+    /// Runs calls one at a time.
+    pub fn sequential() -> Self {
+        Self::default().with_max_concurrency(1)
+    }
+
+    /// Sets the maximum tool calls running at once (minimum 1).
+    pub fn with_max_concurrency(mut self, max_concurrency: usize) -> Self {
+        self.max_concurrency = max_concurrency.max(1);
+        self
+    }
+
+    /// Sets the time limit for every call.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the time limit for calls to the tool `name`.
+    pub fn with_tool_timeout(mut self, name: impl Into<String>, timeout: Duration) -> Self {
+        self.tool_timeouts.insert(name.into(), timeout);
+        self
+    }
+
+    /// The time limit for calls to `name`.
+    pub fn timeout_for(&self, name: &str) -> Option<Duration> {
+        self.tool_timeouts.get(name).copied().or(self.timeout)
+    }
+}
+
+/// The result sent to the model for a tool call that failed with `error`.
+fn tool_error_result(error: &Error) -> String {
+    let kind = match error {
+        Error::TimeoutError(_) => "timeout",
+        Error::ValidationError(_) => "invalid_call",
+        _ => "tool_error",
+    };
+    serde_json::json!({"error": {"type": kind, "message": error.to_string()}}).to_string()
+}
+
+/// A model, system prompt and set of tools run in a loop.
+///
+/// # Example
+///
+/// ```no_run
+/// use openrouter_api::agent::FnTool;
+/// use openrouter_api::models::tool::FunctionDescription;
+/// use openrouter_api::OpenRouterClient;
+/// use serde_json::json;
+///
+/// # async fn run() -> openrouter_api::Result<()> {
+/// let client = OpenRouterClient::from_env()?;
+/// let agent = client
+///     .agent("openai/gpt-4o")?
+///     .with_system_prompt("You are a weather assistant.")
+///     .with_tool(FnTool::new(
+///         FunctionDescription {
+///             name: "get_weather".into(),
+///             description: Some("Current weather for a city".into()),
+///             parameters: json!({
+///                 "type": "object",
+///                 "properties": {"city": {"type": "string"}},
+///                 "required": ["city"]
+///             }),
+///             strict: None,
+///         },
+///         |args| async move { Ok(json!({"city": args["city"], "forecast": "sunny"})) },
+///     ));
+///
+/// let run = agent.run("What's the weather in Oslo?").await?;
+/// println!("{}", run.output);
+/// # Ok(())
+/// # }
+/// ```
+pub struct Agent {
+    chat: Arc<ChatApi>,
+    model: String,
+    system_prompt: Option<String>,
+    tools: Vec<Arc<dyn AgentTool>>,
+    hooks: Vec<Arc<dyn AgentHooks>>,
+    max_steps: usize,
+    budget_guard: Option<Arc<BudgetGuard>>,
+    request_template: ChatCompletionRequest,
+    tool_execution: ToolExecutionPolicy,
+}
+
+impl Agent {
+    /// Creates an agent that calls `model` through `chat`.
+    pub fn new(chat: Arc<ChatApi>, model: impl Into<String>) -> Self {
+        Self {
+            chat,
+            model: model.into(),
+            system_prompt: None,
+            tools: Vec::new(),
+            hooks: Vec::new(),
+            max_steps: DEFAULT_MAX_STEPS,
+            budget_guard: None,
+            request_template: ChatCompletionRequest::default(),
+            tool_execution: ToolExecutionPolicy::default(),
+        }
+    }
+
+    /// Sets the system prompt that starts every run.
+    pub fn with_system_prompt(mut self, prompt: impl Into<String>) -> Self {
+        self.system_prompt = Some(prompt.into());
+        self
+    }
+
+    /// Registers a tool. A later tool with the same name replaces it.
+    pub fn with_tool(mut self, tool: impl AgentTool + 'static) -> Self {
+        let name = &tool.definition().name;
+        self.tools.retain(|t| &t.definition().name != name);
+        self.tools.push(Arc::new(tool));
+        self
+    }
+
+    /// Adds hooks; every registered hook sees every event.
+    pub fn with_hooks(mut self, hooks: impl AgentHooks + 'static) -> Self {
+        self.hooks.push(Arc::new(hooks));
+        self
+    }
+
+    /// Sets the maximum number of model calls per run (minimum 1).
+    pub fn with_max_steps(mut self, max_steps: usize) -> Self {
+        self.max_steps = max_steps.max(1);
+        self
+    }
+
+    /// Caps this agent's spend, in addition to any guard on the client.
+    ///
+    /// Passing the client's own guard is allowed: the client already
+    /// reserves and records each step against it, so the agent skips its
+    /// reservation rather than counting the step twice.
+    pub fn with_budget_guard(mut self, guard: Arc<BudgetGuard>) -> Self {
+        self.budget_guard = Some(guard);
+        self
+    }
+
+    /// Sets request parameters (temperature, max tokens, ...) for every step.
+    /// The template's `model`, `messages` and `tools` are replaced.
+    pub fn with_request_template(mut self, template: ChatCompletionRequest) -> Self {
+        self.request_template = template;
+        self
+    }
+
+    /// Sets how tool calls are run: concurrency and time limits.
+    pub fn with_tool_execution(mut self, policy: ToolExecutionPolicy) -> Self {
+        self.tool_execution = policy;
+        self
+    }
+
+    /// Runs the loop on a new conversation starting with `prompt`.
+    pub async fn run(&self, prompt: impl Into<String>) -> Result<AgentRun> {
+        let mut conversation = Conversation::new();
+        if let Some(system) = &self.system_prompt {
+            conversation.push(Message::text(ChatRole::System, system.clone()));
+        }
+        conversation.push(Message::text(ChatRole::User, prompt));
+        self.resume(conversation).await
+    }
+
+    /// Runs the loop on an existing conversation, e.g. one restored with
+    /// `Conversation::load` and extended with a new user message.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first request error, including `Error::BudgetExceeded`
+    /// when a budget guard blocks the next step.
+    pub async fn resume(&self, mut conversation: Conversation) -> Result<AgentRun> {
+        let tools: Option<Vec<Tool>> = (!self.tools.is_empty()).then(|| {
+            self.tools
+                .iter()
+                .map(|tool| Tool::Function {
+                    function: tool.definition().clone(),
+                })
+                .collect()
+        });
+
+        // The client reserves against its own guard; don't count it twice.
+        let budget_guard = self.budget_guard.as_ref().filter(|guard| {
+            !self
+                .chat
+                .config
+                .budget_guard
+                .as_ref()
+                .is_some_and(|client_guard| Arc::ptr_eq(guard, client_guard))
+        });
+
+        for step in 1..=self.max_steps {
+            let request = ChatCompletionRequest {
+                model: self.model.clone(),
+                messages: conversation.messages().to_vec(),
+                tools: tools.clone(),
+                ..self.request_template.clone()
+            };
+            let response = match budget::guarded(
+                budget_guard.map(Arc::as_ref),
+                RequestSize::of_chat(&request),
+                self.chat.chat_completion(request),
+                |response| response.usage.as_ref().and_then(|usage| usage.cost),
+            )
+            .await
+            {
+                Ok(response) => response,
+                Err(e) => {
+                    self.each_hook(|hooks| hooks.on_error(step, &e));
+                    return Err(e);
+                }
+            };
+
+            conversation.record_response(&response);
+            if let Some(message) = response.first_choice().map(|choice| &choice.message) {
+                self.each_hook(|hooks| hooks.on_message(step, message));
+            }
+
+            let calls = response.tool_calls();
+            if calls.is_empty() {
+                return Ok(AgentRun {
+                    output: response.text().unwrap_or_default().to_string(),
+                    conversation,
+                    steps: step,
+                    stop: AgentStop::Finished,
+                });
+            }
+
+            let results: Vec<ToolResult> = stream::iter(calls.iter().map(|call| async move {
+                self.each_hook(|hooks| hooks.on_tool_call(step, call));
+                match self.call_tool(call).await {
+                    Ok(value) => ToolResult::from(value),
+                    Err(e) => {
+                        self.each_hook(|hooks| hooks.on_error(step, &e));
+                        ToolResult::Text(tool_error_result(&e))
+                    }
+                }
+            }))
+            .buffered(self.tool_execution.max_concurrency.max(1))
+            .collect()
+            .await;
+            for (call, result) in calls.iter().zip(results) {
+                let message = Message::tool_result(result, call.id.clone());
+                self.each_hook(|hooks| hooks.on_message(step, &message));
+                conversation.push(message);
+            }
+        }
+
+        let output = conversation
+            .messages()
+            .iter()
+            .rev()
+            .find(|message| message.role == ChatRole::Assistant)
+            .map(|message| message.content.to_text())
+            .unwrap_or_default();
+        Ok(AgentRun {
+            output,
+            conversation,
+            steps: self.max_steps,
+            stop: AgentStop::MaxSteps,
+        })
+    }
+
+    async fn call_tool(&self, call: &ToolCall) -> Result<Value> {
+        let name = &call.function_call.name;
+        let tool = self
+            .tools
+            .iter()
+            .find(|tool| &tool.definition().name == name)
+            .ok_or_else(|| Error::ValidationError(format!("Unknown tool '{name}'")))?;
+        let arguments = match call.function_call.arguments.trim() {
+            "" => Value::Object(Default::default()),
+            raw => serde_json::from_str(raw).map_err(|e| {
+                Error::ValidationError(format!("Invalid arguments for tool '{name}': {e}"))
+            })?,
+        };
+        match self.tool_execution.timeout_for(name) {
+            Some(limit) => tokio::time::timeout(limit, tool.call(arguments))
+                .await
+                .map_err(|_| {
+                    Error::TimeoutError(format!("Tool '{name}' timed out after {limit:?}"))
+                })?,
+            None => tool.call(arguments).await,
+        }
+    }
+
+    fn each_hook(&self, f: impl Fn(&dyn AgentHooks)) {
+        for hooks in &self.hooks {
+            f(hooks.as_ref());
+        }
+    }
+}
+
+impl std::fmt::Debug for Agent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Agent")
+            .field("model", &self.model)
+            .field(
+                "tools",
+                &self
+                    .tools
+                    .iter()
+                    .map(|tool| tool.definition().name.as_str())
+                    .collect::<Vec<_>>(),
+            )
+            .field("hooks", &self.hooks.len())
+            .field("max_steps", &self.max_steps)
+            .field("tool_execution", &self.tool_execution)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::test_helpers::test_client_config;
+    use serde_json::json;
+    use std::sync::Mutex;
+    use wiremock::{matchers, Mock, MockServer, ResponseTemplate};
+
+    fn reply(message: Value) -> ResponseTemplate {
+        ResponseTemplate::new(200).set_body_json(json!({
+            "id": "gen-1",
+            "choices": [{"message": message}],
+            "created": 0,
+            "model": "openai/gpt-4o",
+            "object": "chat.completion",
+            "usage": {"prompt_tokens": 10, "completion_tokens": 5, "total_tokens": 15, "cost": 0.01}
+        }))
+    }
+
+    fn tool_call_reply(name: &str, arguments: &str) -> ResponseTemplate {
+        reply(json!({
+            "role": "assistant",
+            "content": null,
+            "tool_calls": [{
+                "id": "call-1",
+                "type": "function",
+                "function": {"name": name, "arguments": arguments}
+            }]
+        }))
+    }
+
+    fn agent_for(server: &MockServer) -> Agent {
+        let mut config = test_client_config();
+        config.base_url = format!("{}/", server.uri()).parse().unwrap();
+        let chat = ChatApi::new(reqwest::Client::new(), &config).unwrap();
+        Agent::new(Arc::new(chat), "openai/gpt-4o")
+    }
+
+    fn weather_tool() -> FnTool<impl Fn(Value) -> futures::future::Ready<Result<Value>>> {
+        FnTool::new(
+            FunctionDescription {
+                name: "get_weather".into(),
+                description: None,
+                parameters: json!({"type": "object"}),
+                strict: None,
+            },
+            |args: Value| {
+                futures::future::ready(Ok(json!(format!(
+                    "Sunny in {}",
+                    args["city"].as_str().unwrap_or("?")
+                ))))
+            },
+        )
+    }
+
+    #[derive(Default)]
+    struct Events(Mutex<Vec<String>>);
+
+    impl AgentHooks for Arc<Events> {
+        fn on_message(&self, step: usize, message: &Message) {
+            self.0
+                .lock()
+                .unwrap()
+                .push(format!("{step}:message:{}", message.role));
+        }
+
+        fn on_tool_call(&self, step: usize, call: &ToolCall) {
+            self.0
+                .lock()
+                .unwrap()
+                .push(format!("{step}:tool:{}", call.function_call.name));
+        }
+
+        fn on_error(&self, step: usize, _error: &Error) {
+            self.0.lock().unwrap().push(format!("{step}:error"));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_agent_runs_tools_until_final_answer() {
+        let server = MockServer::start().await;
+        // The second request carries the tool result.
+        Mock::given(matchers::method("POST"))
+            .and(matchers::body_string_contains("Sunny in Oslo"))
+            .respond_with(reply(
+                json!({"role": "assistant", "content": "It is sunny."}),
+            ))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(matchers::method("POST"))
+            .respond_with(tool_call_reply("get_weather", r#"{"city":"Oslo"}"#))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let events = Arc::new(Events::default());
+        let agent = agent_for(&server)
+            .with_system_prompt("Answer briefly.")
+            .with_tool(weather_tool())
+            .with_hooks(events.clone());
+        let run = agent.run("Weather in Oslo?").await.unwrap();
+
+        assert_eq!(run.stop, AgentStop::Finished);
+        assert_eq!(run.steps, 2);
+        assert_eq!(run.output, "It is sunny.");
+        assert_eq!(run.conversation.messages().len(), 5);
+        assert_eq!(run.conversation.usage().total_tokens, 30);
+        assert_eq!(
+            *events.0.lock().unwrap(),
+            [
+                "1:message:assistant",
+                "1:tool:get_weather",
+                "1:message:tool",
+                "2:message:assistant"
+            ]
+        );
+
+        let first: Value =
+            serde_json::from_slice(&server.received_requests().await.unwrap()[0].body).unwrap();
+        assert_eq!(first["tools"][0]["function"]["name"], "get_weather");
+        assert_eq!(first["messages"][0]["role"], "system");
+    }
+
+    #[tokio::test]
+    async fn test_agent_reports_tool_errors_and_stops_at_max_steps() {
+        let server = MockServer::start().await;
+        Mock::given(matchers::method("POST"))
+            .respond_with(tool_call_reply("missing_tool", "{}"))
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let events = Arc::new(Events::default());
+        let agent = agent_for(&server)
+            .with_tool(weather_tool())
+            .with_hooks(events.clone())
+            .with_max_steps(2);
+        let run = agent.run("Go").await.unwrap();
+
+        assert_eq!(run.stop, AgentStop::MaxSteps);
+        assert_eq!(run.steps, 2);
+        let result = &run.conversation.messages()[2];
+        assert_eq!(result.role, ChatRole::Tool);
+        assert!(result
+            .content
+            .to_text()
+            .contains("Unknown tool 'missing_tool'"));
+        assert_eq!(
+            events
+                .0
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|e| e.ends_with("error"))
+                .count(),
+            2
+        );
+    }
+
+    #[tokio::test]
+    async fn test_agent_budget_guard_ends_run() {
+        let server = MockServer::start().await;
+        Mock::given(matchers::method("POST"))
+            .respond_with(tool_call_reply("get_weather", r#"{"city":"Oslo"}"#))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let guard = Arc::new(
+            BudgetGuard::new()
+                .with_hourly_limit(0.015)
+                .with_request_estimate(0.01),
+        );
+        let events = Arc::new(Events::default());
+        let agent = agent_for(&server)
+            .with_tool(weather_tool())
+            .with_hooks(events.clone())
+            .with_budget_guard(guard);
+
+        let result = agent.run("Weather?").await;
+        assert!(matches!(result, Err(Error::BudgetExceeded { .. })));
+        assert_eq!(events.0.lock().unwrap().last().unwrap(), "2:error");
+    }
+
+    #[tokio::test]
+    async fn test_agent_budget_guard_shared_with_client_counts_once() {
+        use crate::utils::budget::BudgetWindow;
+
+        let server = MockServer::start().await;
+        Mock::given(matchers::method("POST"))
+            .respond_with(reply(json!({"role": "assistant", "content": "Sunny."})))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        // Room for one step's estimate, but not for two reservations.
+        let guard = Arc::new(
+            BudgetGuard::new()
+                .with_hourly_limit(0.015)
+                .with_request_estimate(0.01),
+        );
+        let mut config = test_client_config();
+        config.base_url = format!("{}/", server.uri()).parse().unwrap();
+        config.budget_guard = Some(guard.clone());
+        let chat = ChatApi::new(reqwest::Client::new(), &config).unwrap();
+        let agent = Agent::new(Arc::new(chat), "openai/gpt-4o").with_budget_guard(guard.clone());
+
+        agent.run("Weather?").await.unwrap();
+        assert!((guard.spent(BudgetWindow::Hour) - 0.01).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_agent_runs_tool_calls_concurrently_in_order_with_timeouts() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let server = MockServer::start().await;
+        Mock::given(matchers::method("POST"))
+            .and(matchers::body_string_contains("\"role\":\"tool\""))
+            .respond_with(reply(json!({"role": "assistant", "content": "Done."})))
+            .mount(&server)
+            .await;
+        let call = |id: &str, name: &str| json!({"id": id, "type": "function", "function": {"name": name, "arguments": "{}"}});
+        Mock::given(matchers::method("POST"))
+            .respond_with(reply(json!({
+                "role": "assistant",
+                "content": null,
+                "tool_calls": [call("call-1", "slow"), call("call-2", "fast"), call("call-3", "stuck")]
+            })))
+            .mount(&server)
+            .await;
+
+        let running = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        let sleeper = |name: &str, delay: Duration| {
+            let (running, peak) = (running.clone(), peak.clone());
+            let output = name.to_string();
+            FnTool::new(
+                FunctionDescription {
+                    name: name.into(),
+                    description: None,
+                    parameters: json!({"type": "object"}),
+                    strict: None,
+                },
+                move |_| {
+                    let (running, peak, output) = (running.clone(), peak.clone(), output.clone());
+                    async move {
+                        let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                        peak.fetch_max(now, Ordering::SeqCst);
+                        tokio::time::sleep(delay).await;
+                        running.fetch_sub(1, Ordering::SeqCst);
+                        Ok(json!(output))
+                    }
+                },
+            )
+        };
+
+        let agent = agent_for(&server)
+            .with_tool(sleeper("slow", Duration::from_millis(100)))
+            .with_tool(sleeper("fast", Duration::from_millis(1)))
+            .with_tool(sleeper("stuck", Duration::from_secs(30)))
+            .with_tool_execution(
+                ToolExecutionPolicy::default()
+                    .with_max_concurrency(2)
+                    .with_timeout(Duration::from_secs(5))
+                    .with_tool_timeout("stuck", Duration::from_millis(50)),
+            );
+        let run = agent.run("Go").await.unwrap();
+
+        let results: Vec<_> = run.conversation.messages()[2..5]
+            .iter()
+            .map(|message| {
+                (
+                    message.tool_call_id.as_ref().unwrap().as_str().to_string(),
+                    message.content.to_text(),
+                )
+            })
+            .collect();
+        assert_eq!(results[0], ("call-1".to_string(), "slow".to_string()));
+        assert_eq!(results[1], ("call-2".to_string(), "fast".to_string()));
+        assert_eq!(results[2].0, "call-3");
+        let error: Value = serde_json::from_str(&results[2].1).unwrap();
+        assert_eq!(error["error"]["type"], "timeout");
+        assert_eq!(peak.load(Ordering::SeqCst), 2);
+        assert_eq!(run.output, "Done.");
+    }
+}
@@ -1,10 +1,20 @@
 #![allow(dead_code)]
+use crate::api::core::ApiCore;
+use crate::api::options::RequestOptions;
 use crate::error::{Error, Result};
-use crate::types::analytics::{ActivityRequest, ActivityResponse, SortField, SortOrder};
+use crate::types::analytics::{
+    ActivityData, ActivityRequest, ActivityResponse, BucketGranularity, SortField, SortOrder,
+    UsageBucket, UsageBucketer,
+};
 use crate::utils::retry::operations::GET_ACTIVITY;
-use crate::utils::{retry::execute_with_retry_builder, retry::handle_response_json};
+use async_stream::try_stream;
+use chrono::NaiveDate;
+use futures::stream::Stream;
+use futures::StreamExt;
 use reqwest::Client;
-use urlencoding::encode;
+use std::ops::RangeInclusive;
+use std::pin::Pin;
+use tokio::io::{AsyncWrite, AsyncWriteExt};
 
 /// API endpoint for analytics and activity data.
 pub struct AnalyticsApi {
@@ -92,73 +102,204 @@ impl AnalyticsApi {
     /// ```
     #[must_use = "returns an activity response that should be processed"]
     pub async fn get_activity(&self, request: ActivityRequest) -> Result<ActivityResponse> {
+        self.get_activity_with_options(request, &RequestOptions::default())
+            .await
+    }
+
+    /// Like `get_activity`, with per-call options.
+    #[must_use = "returns an activity response that should be processed"]
+    pub async fn get_activity_with_options(
+        &self,
+        request: ActivityRequest,
+        options: &RequestOptions,
+    ) -> Result<ActivityResponse> {
         // Validate the request parameters
         request.validate().map_err(Error::ConfigError)?;
 
-        // Build the URL with query parameters
-        let url = self
-            .config
-            .base_url
-            .join("activity")
-            .map_err(|e| Error::ApiError {
-                code: 400,
-                message: format!("Invalid URL for activity endpoint: {e}"),
-                metadata: None,
-            })?;
-
-        // Build query parameters
-        let mut query_params = Vec::new();
-
-        if let Some(start_date) = &request.start_date {
-            query_params.push(("start_date", encode(start_date).to_string()));
-        }
-
-        if let Some(end_date) = &request.end_date {
-            query_params.push(("end_date", encode(end_date).to_string()));
-        }
-
-        if let Some(model) = &request.model {
-            query_params.push(("model", encode(model).to_string()));
-        }
-
-        if let Some(provider) = &request.provider {
-            query_params.push(("provider", encode(provider).to_string()));
-        }
+        ApiCore::new(&self.client, &self.config)
+            .get_with_options(GET_ACTIVITY, "activity", &activity_query(&request), options)
+            .await
+    }
 
-        if let Some(sort) = &request.sort {
-            query_params.push(("sort", encode(sort.as_str()).to_string()));
-        }
+    /// Streams every activity entry matching `request`, following pagination.
+    ///
+    /// Pages are fetched lazily as the stream is polled. The request's `offset`
+    /// is used as the starting point and `limit` as the page size (defaulting
+    /// to `DEFAULT_LIMIT`). Pagination stops when the server reports
+    /// `has_more: false`, or — if `has_more` is absent — when a page comes back
+    /// shorter than the page size.
+    ///
+    /// Each page goes through the client's retry configuration, so a
+    /// rate-limited page is refetched after the response's `Retry-After` (or
+    /// the client's backoff when absent). Once those retries are exhausted
+    /// the stream yields the error.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use futures::TryStreamExt;
+    /// use openrouter_api::OpenRouterClient;
+    /// use openrouter_api::types::analytics::ActivityRequest;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = OpenRouterClient::from_env()?;
+    ///     let analytics = client.analytics()?;
+    ///
+    ///     let request = ActivityRequest::new().with_start_date("2024-01-01");
+    ///     let all: Vec<_> = analytics.get_activity_paged(request).try_collect().await?;
+    ///
+    ///     println!("Fetched {} activity entries", all.len());
+    ///     Ok(())
+    /// }
+    /// ```
+    #[must_use = "returns a stream that should be consumed to receive activity entries"]
+    pub fn get_activity_paged(
+        &self,
+        request: ActivityRequest,
+    ) -> Pin<Box<dyn Stream<Item = Result<ActivityData>> + Send + '_>> {
+        let page_size = request
+            .limit
+            .unwrap_or(crate::types::analytics::constants::DEFAULT_LIMIT)
+            .max(1);
+        let mut offset = request.offset.unwrap_or(0);
+
+        let stream = try_stream! {
+            loop {
+                let page_request = request
+                    .clone()
+                    .with_limit(page_size)
+                    .with_offset(offset);
+                let page = self.get_activity(page_request).await?;
+
+                let received = page.data.len() as u32;
+                let has_more = page.has_more.unwrap_or(received >= page_size);
+
+                for activity in page.data {
+                    yield activity;
+                }
+
+                if received == 0 || !has_more {
+                    break;
+                }
+                offset = offset.saturating_add(received);
+            }
+        };
 
-        if let Some(order) = &request.order {
-            query_params.push(("order", encode(order.as_str()).to_string()));
-        }
+        Box::pin(stream)
+    }
 
-        if let Some(limit) = request.limit {
-            query_params.push(("limit", limit.to_string()));
-        }
+    /// Fetches all activity in `range` and aggregates it into cost buckets.
+    ///
+    /// Both ends of the range are inclusive. Pages are fetched with
+    /// [`get_activity_paged`](Self::get_activity_paged) and folded into the
+    /// buckets as they arrive, so memory does not grow with the range;
+    /// periods with no activity are omitted from the report.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use chrono::NaiveDate;
+    /// use openrouter_api::OpenRouterClient;
+    /// use openrouter_api::types::analytics::BucketGranularity;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = OpenRouterClient::from_env()?;
+    ///     let start = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+    ///     let end = NaiveDate::from_ymd_opt(2024, 1, 31).unwrap();
+    ///
+    ///     let report = client
+    ///         .analytics()?
+    ///         .cost_report(start..=end, BucketGranularity::Week)
+    ///         .await?;
+    ///     for bucket in report {
+    ///         println!("{}: ${:.4} over {} requests", bucket.period, bucket.cost, bucket.request_count);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn cost_report(
+        &self,
+        range: RangeInclusive<NaiveDate>,
+        granularity: BucketGranularity,
+    ) -> Result<Vec<UsageBucket>> {
+        let request = ActivityRequest::new()
+            .with_start_date(range.start().format("%Y-%m-%d").to_string())
+            .with_end_date(range.end().format("%Y-%m-%d").to_string())
+            .with_sort(SortField::CreatedAt)
+            .with_order(SortOrder::Ascending);
 
-        if let Some(offset) = request.offset {
-            query_params.push(("offset", offset.to_string()));
+        // Entries are folded in as pages arrive rather than kept around.
+        let mut bucketer = UsageBucketer::new(granularity);
+        let mut stream = self.get_activity_paged(request);
+        while let Some(activity) = stream.next().await {
+            bucketer.add(&activity?);
         }
 
-        // Execute request with retry logic
-        let response = execute_with_retry_builder(&self.config.retry_config, GET_ACTIVITY, || {
-            let mut req_builder = self
-                .client
-                .get(url.clone())
-                .headers((*self.config.headers).clone());
+        Ok(bucketer.finish())
+    }
 
-            // Add query parameters if any
-            if !query_params.is_empty() {
-                req_builder = req_builder.query(&query_params);
-            }
+    /// Streams all activity matching `request` into `writer` as CSV.
+    ///
+    /// Pages are written as they arrive, so arbitrarily long histories can be
+    /// exported without holding them in memory. The header row and column
+    /// order match [`ActivityResponse::write_csv`]. Returns the number of data
+    /// rows written.
+    pub async fn export_activity_csv<W>(&self, request: ActivityRequest, writer: W) -> Result<usize>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        let mut header = Vec::new();
+        crate::types::analytics::write_activity_csv_header(&mut header)?;
+        self.export_activity(request, writer, header, |activity, buf| {
+            activity.write_csv_row(buf)
+        })
+        .await
+    }
 
-            req_builder
+    /// Streams all activity matching `request` into `writer` as JSON Lines.
+    ///
+    /// Returns the number of lines written.
+    pub async fn export_activity_jsonl<W>(
+        &self,
+        request: ActivityRequest,
+        writer: W,
+    ) -> Result<usize>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        self.export_activity(request, writer, Vec::new(), |activity, buf| {
+            activity.write_jsonl_row(buf)
         })
-        .await?;
+        .await
+    }
 
-        // Handle response with consistent error parsing
-        handle_response_json::<ActivityResponse>(response, GET_ACTIVITY).await
+    async fn export_activity<W, F>(
+        &self,
+        request: ActivityRequest,
+        mut writer: W,
+        preamble: Vec<u8>,
+        mut encode_row: F,
+    ) -> Result<usize>
+    where
+        W: AsyncWrite + Unpin,
+        F: FnMut(&ActivityData, &mut Vec<u8>) -> std::io::Result<()>,
+    {
+        writer.write_all(&preamble).await?;
+
+        let mut rows = 0usize;
+        let mut buf = Vec::new();
+        let mut activities = self.get_activity_paged(request);
+        while let Some(activity) = activities.next().await {
+            buf.clear();
+            encode_row(&activity?, &mut buf)?;
+            writer.write_all(&buf).await?;
+            rows += 1;
+        }
+
+        writer.flush().await?;
+        Ok(rows)
     }
 
     /// Retrieves activity data for a specific date range with default parameters.
@@ -373,6 +514,45 @@ impl AnalyticsApi {
     }
 }
 
+/// Query parameters for `GET /activity`.
+fn activity_query(request: &ActivityRequest) -> Vec<(&'static str, String)> {
+    let mut query_params = Vec::new();
+
+    if let Some(start_date) = &request.start_date {
+        query_params.push(("start_date", start_date.to_string()));
+    }
+
+    if let Some(end_date) = &request.end_date {
+        query_params.push(("end_date", end_date.to_string()));
+    }
+
+    if let Some(model) = &request.model {
+        query_params.push(("model", model.to_string()));
+    }
+
+    if let Some(provider) = &request.provider {
+        query_params.push(("provider", provider.to_string()));
+    }
+
+    if let Some(sort) = &request.sort {
+        query_params.push(("sort", sort.as_str().to_string()));
+    }
+
+    if let Some(order) = &request.order {
+        query_params.push(("order", order.as_str().to_string()));
+    }
+
+    if let Some(limit) = request.limit {
+        query_params.push(("limit", limit.to_string()));
+    }
+
+    if let Some(offset) = request.offset {
+        query_params.push(("offset", offset.to_string()));
+    }
+
+    query_params
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -449,4 +629,192 @@ mod tests {
             url.path()
         );
     }
+
+    #[tokio::test]
+    async fn test_get_activity_paged_follows_offsets() {
+        use crate::tests::test_helpers::test_client_config;
+        use futures::TryStreamExt;
+        use serde_json::json;
+        use wiremock::{matchers, Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+        let mut config = test_client_config();
+        config.base_url = url::Url::parse(&format!("{}/", mock_server.uri())).unwrap();
+        let analytics_api = AnalyticsApi::new(Client::new(), &config).unwrap();
+
+        let entry = |id: &str| {
+            json!({
+                "id": id,
+                "created_at": "2024-01-01T00:00:00Z",
+                "model": "test-model",
+                "total_cost": 0.01,
+                "streamed": false,
+                "cancelled": false
+            })
+        };
+
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/activity"))
+            .and(matchers::query_param("offset", "0"))
+            .and(matchers::query_param("limit", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "data": [entry("a-1"), entry("a-2")],
+                "total_count": 3,
+                "has_more": true
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/activity"))
+            .and(matchers::query_param("offset", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "data": [entry("a-3")],
+                "total_count": 3,
+                "has_more": false
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let request = ActivityRequest::new().with_limit(2);
+        let all: Vec<ActivityData> = analytics_api
+            .get_activity_paged(request)
+            .try_collect()
+            .await
+            .unwrap();
+
+        let ids: Vec<&str> = all.iter().map(|a| a.id.as_str()).collect();
+        assert_eq!(ids, vec!["a-1", "a-2", "a-3"]);
+    }
+
+    #[tokio::test]
+    async fn test_get_activity_paged_honours_retry_after() {
+        use crate::tests::test_helpers::test_client_config;
+        use futures::TryStreamExt;
+        use serde_json::json;
+        use std::time::Duration;
+        use wiremock::{matchers, Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+        let mut config = test_client_config();
+        config.base_url = url::Url::parse(&format!("{}/", mock_server.uri())).unwrap();
+        let analytics_api = AnalyticsApi::new(Client::new(), &config).unwrap();
+
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/activity"))
+            .respond_with(ResponseTemplate::new(429).insert_header("retry-after", "1"))
+            .up_to_n_times(1)
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/activity"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "data": [],
+                "has_more": false
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let started = std::time::Instant::now();
+        let all: Vec<ActivityData> = analytics_api
+            .get_activity_paged(ActivityRequest::new())
+            .try_collect()
+            .await
+            .unwrap();
+        assert!(all.is_empty());
+        // Retry-After of one second, jittered down by at most a quarter.
+        assert!(started.elapsed() >= Duration::from_millis(700));
+    }
+
+    #[tokio::test]
+    async fn test_get_activity_paged_retries_a_rate_limited_page_once_per_attempt() {
+        use crate::tests::test_helpers::test_client_config;
+        use futures::StreamExt;
+        use wiremock::{matchers, Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+        let mut config = test_client_config();
+        config.base_url = url::Url::parse(&format!("{}/", mock_server.uri())).unwrap();
+        let max_retries = config.retry_config.max_retries;
+        let analytics_api = AnalyticsApi::new(Client::new(), &config).unwrap();
+
+        // The default retry config handles the 429s; nothing retries on top.
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/activity"))
+            .respond_with(ResponseTemplate::new(429).insert_header("retry-after", "0"))
+            .expect(u64::from(max_retries) + 1)
+            .mount(&mock_server)
+            .await;
+
+        let mut stream = analytics_api.get_activity_paged(ActivityRequest::new());
+        assert!(matches!(stream.next().await, Some(Err(_))));
+        assert!(stream.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_get_activity_paged_surfaces_validation_errors() {
+        use crate::tests::test_helpers::test_client_config;
+        use futures::StreamExt;
+
+        let config = test_client_config();
+        let analytics_api = AnalyticsApi::new(Client::new(), &config).unwrap();
+
+        let request = ActivityRequest::new().with_start_date("not-a-date");
+        let mut stream = analytics_api.get_activity_paged(request);
+        assert!(matches!(
+            stream.next().await,
+            Some(Err(Error::ConfigError(_)))
+        ));
+        assert!(stream.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_export_activity_csv_streams_rows() {
+        use crate::tests::test_helpers::test_client_config;
+        use crate::types::analytics::ACTIVITY_CSV_COLUMNS;
+        use serde_json::json;
+        use wiremock::{matchers, Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+        let mut config = test_client_config();
+        config.base_url = url::Url::parse(&format!("{}/", mock_server.uri())).unwrap();
+        let analytics_api = AnalyticsApi::new(Client::new(), &config).unwrap();
+
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/activity"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "data": [{
+                    "id": "a-1",
+                    "created_at": "2024-01-01T00:00:00Z",
+                    "model": "test-model",
+                    "streamed": true,
+                    "cancelled": false
+                }],
+                "has_more": false
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let mut csv = Vec::new();
+        let rows = analytics_api
+            .export_activity_csv(ActivityRequest::new(), &mut csv)
+            .await
+            .unwrap();
+        assert_eq!(rows, 1);
+        let text = String::from_utf8(csv).unwrap();
+        assert!(text.starts_with(&ACTIVITY_CSV_COLUMNS.join(",")));
+        assert!(text.contains("a-1,2024-01-01T00:00:00+00:00,test-model"));
+
+        let mut jsonl = Vec::new();
+        let rows = analytics_api
+            .export_activity_jsonl(ActivityRequest::new(), &mut jsonl)
+            .await
+            .unwrap();
+        assert_eq!(rows, 1);
+        assert_eq!(String::from_utf8(jsonl).unwrap().lines().count(), 1);
+    }
 }
@@ -1,29 +1,49 @@
+use crate::api::core::ApiCore;
+use crate::api::generation::{GenerationApi, TrackedResponse};
+use crate::api::models::ModelsApi;
+use crate::api::options::RequestOptions;
 use crate::error::{Error, Result};
 use crate::models::tool::ToolType;
 use crate::types::chat::{
-    ChatCompletionChunk, ChatCompletionRequest, ChatCompletionResponse, ChatRole, Message,
-    MessageContent,
+    ChatCompletionAccumulator, ChatCompletionChunk, ChatCompletionRequest, ChatCompletionResponse,
+    ChatRole, Message, StreamEvent, StreamProgress, StreamStats,
 };
+use crate::types::comparison::{ModelComparison, ModelRun};
+use crate::types::normalization::{self, NormalizationRules};
+use crate::types::response_meta::WithMeta;
+use crate::types::tool_events::{ToolCallTracker, ToolStreamEvent};
+use crate::types::truncation::{self, TruncationPolicy, TruncationReport};
 use crate::utils::{
-    retry::execute_with_retry_builder, retry::handle_response_json,
-    retry::operations::CHAT_COMPLETION, security::create_safe_error_message, validation,
+    budget::{self, BudgetGuard, PendingReservation, RequestSize},
+    diagnostics::Diagnostic,
+    retry::{operations::CHAT_COMPLETION, read_body_limited},
+    security::create_safe_error_message,
+    validation,
 };
 use async_stream::try_stream;
 use futures::stream::Stream;
 use futures::StreamExt;
 use futures::TryStreamExt;
+use reqwest::header::HeaderMap;
 use reqwest::Client;
 use serde_json;
 use std::pin::Pin;
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
-use tokio_util::codec::{FramedRead, LinesCodec};
+use std::time::{Duration, Instant};
+use tokio_util::codec::{FramedRead, LinesCodec, LinesCodecError};
 use tokio_util::io::StreamReader;
 
 // Streaming safety limits to prevent memory exhaustion
 const MAX_LINE_LENGTH: usize = 64 * 1024; // 64KB per line
 const MAX_TOTAL_CHUNKS: usize = 10_000; // Maximum chunks per stream
 
+/// Output cap for summaries written by `TruncationPolicy::SummarizeOldest`.
+const SUMMARY_MAX_TOKENS: u32 = 256;
+
+/// Most model runs `compare_models` keeps in flight at once.
+const COMPARE_MODELS_CONCURRENCY: usize = 4;
+
 /// API endpoint for chat completions.
 pub struct ChatApi {
     pub(crate) client: Client,
@@ -46,37 +66,256 @@ impl ChatApi {
         &self,
         request: ChatCompletionRequest,
     ) -> Result<ChatCompletionResponse> {
+        self.chat_completion_with_options(request, &RequestOptions::default())
+            .await
+    }
+
+    /// Sends a chat completion request with per-call options such as extra
+    /// headers or a timeout.
+    #[must_use = "returns the chat completion response that should be processed"]
+    pub async fn chat_completion_with_options(
+        &self,
+        request: ChatCompletionRequest,
+        options: &RequestOptions,
+    ) -> Result<ChatCompletionResponse> {
+        self.chat_completion_with_meta(request, options)
+            .await
+            .map(WithMeta::into_response)
+    }
+
+    /// Like [`chat_completion_with_options`](Self::chat_completion_with_options),
+    /// also returning the request ID, timing and rate limit state from the
+    /// response headers.
+    #[must_use = "returns the chat completion response that should be processed"]
+    pub async fn chat_completion_with_meta(
+        &self,
+        mut request: ChatCompletionRequest,
+        options: &RequestOptions,
+    ) -> Result<WithMeta<ChatCompletionResponse>> {
+        options.apply_to_chat(&mut request);
+        self.apply_defaults(&mut request);
+        self.normalize_messages(&mut request);
+
         // Validate the request
-        validation::validate_chat_request(&request)?;
-        validation::check_token_limits(&request)?;
-
-        // Build the complete URL for the chat completions endpoint.
-        let url = self
-            .config
-            .base_url
-            .join("chat/completions")
-            .map_err(|e| Error::ApiError {
-                code: 400,
-                message: format!("Invalid URL: {e}"),
-                metadata: None,
-            })?;
+        let mode = options.validation_mode(self.config.validation_mode);
+        validation::repair_chat_request(&mut request, &self.config.validation_policy);
+        mode.apply_with(CHAT_COMPLETION, self.config.diagnostics(), || {
+            validation::validate_chat_request_with_policy(&request, &self.config.validation_policy)
+        })?;
+        let truncation = self.truncate(&mut request, options).await?;
+        mode.apply_with(CHAT_COMPLETION, self.config.diagnostics(), || {
+            validation::check_token_limits(&request)
+        })?;
+        let headers = options.idempotent_headers(&self.config.headers)?;
+
+        let mut response = budget::guarded(
+            self.config.budget_guard.as_deref(),
+            RequestSize::of_chat(&request),
+            self.send_chat_completion(&request, &headers, options),
+            |response| {
+                response
+                    .response
+                    .usage
+                    .as_ref()
+                    .and_then(|usage| usage.cost)
+            },
+        )
+        .await?;
+        response.response.truncation = truncation;
+        Ok(response)
+    }
+
+    /// Shortens the request per the call's or client's `TruncationConfig`
+    /// if it exceeds the model's context window.
+    async fn truncate(
+        &self,
+        request: &mut ChatCompletionRequest,
+        options: &RequestOptions,
+    ) -> Result<Option<TruncationReport>> {
+        let Some(config) = options
+            .truncation
+            .as_ref()
+            .or(self.config.truncation.as_ref())
+            .filter(|config| config.policy != TruncationPolicy::None)
+        else {
+            return Ok(None);
+        };
+
+        let context_window = match config.context_window {
+            Some(tokens) => tokens,
+            None => self.context_window(&request.model).await?,
+        };
+        let budget = truncation::prompt_budget(request, context_window);
+        let estimated_tokens_before = validation::chat::estimate_request_tokens(request);
+        if estimated_tokens_before <= budget {
+            return Ok(None);
+        }
+
+        let mut report = TruncationReport {
+            estimated_tokens_before,
+            ..Default::default()
+        };
+        match &config.policy {
+            TruncationPolicy::None => return Ok(None),
+            TruncationPolicy::DropOldestMessages => {
+                report.removed_messages = truncation::drop_oldest(request, budget).1;
+            }
+            TruncationPolicy::SummarizeOldest { model } => {
+                let (index, removed) =
+                    truncation::drop_oldest(request, budget.saturating_sub(SUMMARY_MAX_TOKENS));
+                if !removed.is_empty() {
+                    let summary = self.summarize(model, &removed).await?;
+                    request.messages.insert(
+                        index,
+                        Message::text(
+                            ChatRole::System,
+                            format!("Summary of the earlier conversation: {summary}"),
+                        ),
+                    );
+                    report.summary = Some(summary);
+                }
+                report.removed_messages = removed;
+            }
+            TruncationPolicy::TruncateMiddle => {
+                report.truncated_bytes = truncation::truncate_middle(request, budget);
+            }
+        }
+
+        report.estimated_tokens_after = validation::chat::estimate_request_tokens(request);
+        if report.estimated_tokens_after > budget {
+            return Err(Error::ContextLengthExceeded {
+                model: request.model.clone(),
+                message: format!(
+                    "Estimated token count ({}) still exceeds the prompt budget ({budget}) after truncation",
+                    report.estimated_tokens_after
+                ),
+            });
+        }
+        Ok(Some(report))
+    }
+
+    /// The model's context window from the models endpoint, cached per model.
+    async fn context_window(&self, model: &str) -> Result<u32> {
+        if let Some(tokens) = self.cached_context_window(model) {
+            return Ok(tokens);
+        }
+
+        let models = ModelsApi {
+            client: self.client.clone(),
+            config: self.config.clone(),
+        }
+        .list_models(None)
+        .await?;
+        if let Ok(mut windows) = self.config.context_windows.lock() {
+            windows.extend(
+                models
+                    .data
+                    .iter()
+                    .map(|info| (info.id.as_str().to_string(), info.context_length)),
+            );
+        }
+
+        self.cached_context_window(model).ok_or_else(|| {
+            Error::ConfigError(format!(
+                "Unknown context window for model '{model}'; set TruncationConfig::context_window"
+            ))
+        })
+    }
+
+    fn cached_context_window(&self, model: &str) -> Option<u32> {
+        self.config
+            .context_windows
+            .lock()
+            .ok()
+            .and_then(|windows| windows.get(model).copied())
+    }
 
-        // Execute request with retry logic
-        let response =
-            execute_with_retry_builder(&self.config.retry_config, CHAT_COMPLETION, || {
-                self.client
-                    .post(url.clone())
-                    .headers((*self.config.headers).clone())
-                    .json(&request)
+    /// Asks `model` for a short summary of `messages`.
+    async fn summarize(&self, model: &str, messages: &[Message]) -> Result<String> {
+        let transcript = messages
+            .iter()
+            .map(|message| format!("{}: {}", message.role, message.content.to_text()))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let request = ChatCompletionRequest {
+            model: model.to_string(),
+            messages: vec![
+                Message::text(
+                    ChatRole::System,
+                    "Summarize this conversation excerpt in a few sentences, keeping facts, \
+                     decisions and open questions.",
+                ),
+                Message::text(ChatRole::User, transcript),
+            ],
+            max_tokens: Some(SUMMARY_MAX_TOKENS),
+            ..Default::default()
+        };
+        let headers = RequestOptions::default().idempotent_headers(&self.config.headers)?;
+        let response = budget::guarded(
+            self.config.budget_guard.as_deref(),
+            RequestSize::of_chat(&request),
+            self.send_chat_completion(&request, &headers, &RequestOptions::default()),
+            |response| {
+                response
+                    .response
+                    .usage
+                    .as_ref()
+                    .and_then(|usage| usage.cost)
+            },
+        )
+        .await?;
+        response
+            .response
+            .text()
+            .map(str::to_string)
+            .ok_or_else(|| Error::ApiError {
+                code: 500,
+                message: "Summarization returned no text".into(),
+                metadata: None,
             })
-            .await?;
+    }
+
+    /// Sends a chat completion request and reconciles its cost in the background.
+    ///
+    /// The response is returned as soon as it arrives. When the client was
+    /// built with `with_cost_tracking()`, the generation stats for the
+    /// response are fetched asynchronously and delivered through
+    /// `TrackedResponse::generation`; otherwise the receiver resolves to an
+    /// error immediately.
+    #[must_use = "returns the tracked chat completion response that should be processed"]
+    pub async fn chat_completion_tracked(
+        &self,
+        request: ChatCompletionRequest,
+    ) -> Result<TrackedResponse<ChatCompletionResponse>> {
+        let response = self.chat_completion(request).await?;
+        let id = response.id.clone();
+        Ok(GenerationApi::track(
+            &self.client,
+            &self.config,
+            Some(&id),
+            response,
+        ))
+    }
 
-        // Handle response with consistent error parsing
-        let chat_response: ChatCompletionResponse =
-            handle_response_json::<ChatCompletionResponse>(response, CHAT_COMPLETION).await?;
+    async fn send_chat_completion(
+        &self,
+        request: &ChatCompletionRequest,
+        headers: &HeaderMap,
+        options: &RequestOptions,
+    ) -> Result<WithMeta<ChatCompletionResponse>> {
+        let mut chat_response: WithMeta<ChatCompletionResponse> =
+            ApiCore::new(&self.client, &self.config)
+                .post_with_meta(
+                    CHAT_COMPLETION,
+                    "chat/completions",
+                    request,
+                    headers,
+                    options,
+                )
+                .await?;
 
         // Validate any tool calls in the response
-        for choice in &chat_response.choices {
+        for choice in &chat_response.response.choices {
             if let Some(tool_calls) = &choice.message.tool_calls {
                 for tc in tool_calls {
                     if tc.kind != ToolType::Function {
@@ -89,9 +328,99 @@ impl ChatApi {
             }
         }
 
+        let served = &chat_response.response.model;
+        if served != &request.model
+            && request
+                .models
+                .as_ref()
+                .is_some_and(|models| models.contains(served))
+        {
+            self.config.diagnostics().emit(&Diagnostic::ModelFallback {
+                requested: &request.model,
+                served,
+            });
+        }
+
+        if self.config.strip_reasoning {
+            chat_response.response.strip_reasoning();
+        }
+
         Ok(chat_response)
     }
 
+    /// Sends the same request to several models concurrently, at most
+    /// `COMPARE_MODELS_CONCURRENCY` at a time.
+    ///
+    /// The request's `model` is replaced for each run and any fallback
+    /// `models` list is cleared so every run is served by the model under
+    /// test. Individual failures are recorded in the returned
+    /// [`ModelComparison`] rather than failing the whole comparison.
+    ///
+    /// Responses without a reported cost get an estimate from the model
+    /// catalog's pricing, if the catalog can be fetched.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::ValidationError` if `models` is empty.
+    #[must_use = "returns the comparison results that should be processed"]
+    pub async fn compare_models(
+        &self,
+        request: ChatCompletionRequest,
+        models: &[&str],
+    ) -> Result<ModelComparison> {
+        if models.is_empty() {
+            return Err(Error::ValidationError(
+                "compare_models requires at least one model".into(),
+            ));
+        }
+
+        let runs = futures::stream::iter(models.iter().map(|model| {
+            let request = ChatCompletionRequest {
+                model: (*model).to_string(),
+                models: None,
+                ..request.clone()
+            };
+            async move {
+                let started = Instant::now();
+                let result = self.chat_completion(request).await;
+                ModelRun {
+                    model: (*model).to_string(),
+                    result,
+                    latency: started.elapsed(),
+                    estimated_cost: None,
+                }
+            }
+        }));
+        // `buffered` keeps the runs in the order the models were given.
+        let mut runs: Vec<ModelRun> = runs.buffered(COMPARE_MODELS_CONCURRENCY).collect().await;
+
+        let unpriced = |run: &ModelRun| run.is_success() && run.reported_cost().is_none();
+        if runs.iter().any(unpriced) {
+            let catalog = ModelsApi {
+                client: self.client.clone(),
+                config: self.config.clone(),
+            }
+            .list_models(None)
+            .await;
+            if let Ok(catalog) = catalog {
+                for run in runs.iter_mut().filter(|run| unpriced(run)) {
+                    let usage = run.response().and_then(|r| r.usage.as_ref());
+                    let pricing = catalog
+                        .data
+                        .iter()
+                        .find(|info| info.id.as_str() == run.model)
+                        .map(|info| &info.pricing);
+                    if let (Some(usage), Some(pricing)) = (usage, pricing) {
+                        run.estimated_cost =
+                            pricing.estimate_cost(usage.prompt_tokens, usage.completion_tokens);
+                    }
+                }
+            }
+        }
+
+        Ok(ModelComparison { runs })
+    }
+
     /// Returns a stream for a chat completion request.
     /// Each yielded item is a ChatCompletionChunk.
     #[must_use = "returns a stream that should be consumed to receive completion chunks"]
@@ -99,58 +428,100 @@ impl ChatApi {
         &self,
         request: ChatCompletionRequest,
     ) -> Pin<Box<dyn Stream<Item = Result<ChatCompletionChunk>> + Send + '_>> {
+        self.chat_completion_stream_with_options(request, &RequestOptions::default())
+    }
+
+    /// Returns a stream for a chat completion request with per-call options.
+    ///
+    /// `RequestOptions::timeout` bounds the whole stream and
+    /// `RequestOptions::first_token_timeout` the wait for the first chunk;
+    /// either yields `Error::TimeoutError` and ends the stream.
+    ///
+    /// With a `MetricsRecorder` configured, the stream's `StreamSummary` is
+    /// passed to `MetricsRecorder::record_stream` when it completes.
+    #[must_use = "returns a stream that should be consumed to receive completion chunks"]
+    pub fn chat_completion_stream_with_options(
+        &self,
+        mut request: ChatCompletionRequest,
+        options: &RequestOptions,
+    ) -> Pin<Box<dyn Stream<Item = Result<ChatCompletionChunk>> + Send + '_>> {
+        options.apply_to_chat(&mut request);
+        self.apply_defaults(&mut request);
+        self.normalize_messages(&mut request);
         let client = self.client.clone();
-        let headers = Arc::clone(&self.config.headers);
+        let headers = match options.idempotent_headers(&self.config.headers) {
+            Ok(headers) => headers,
+            Err(e) => return Box::pin(futures::stream::once(async { Err(e) })),
+        };
+        let mut deadlines = StreamDeadlines::new(options);
 
         // Validate the request before streaming
-        if let Err(e) = validation::validate_chat_request(&request) {
-            return Box::pin(futures::stream::once(async { Err(e) }));
-        }
-
-        if let Err(e) = validation::check_token_limits(&request) {
+        let mode = options.validation_mode(self.config.validation_mode);
+        validation::repair_chat_request(&mut request, &self.config.validation_policy);
+        if let Err(e) = mode.apply_with(CHAT_COMPLETION, self.config.diagnostics(), || {
+            validation::validate_chat_request_with_policy(&request, &self.config.validation_policy)
+        }) {
             return Box::pin(futures::stream::once(async { Err(e) }));
         }
 
         let chunk_count = AtomicUsize::new(0);
+        let max_response_bytes = self.config.max_response_bytes;
+        let metrics_recorder = self.config.metrics_recorder.clone();
+        let diagnostics = self.config.diagnostics();
+        let strip_reasoning = self.config.strip_reasoning;
+        let failover = self.config.failover.clone();
+        let options = options.clone();
 
-        // Build the URL for the chat completions endpoint.
-        let url = match self.config.base_url.join("chat/completions") {
-            Ok(url) => url,
-            Err(e) => {
-                return Box::pin(futures::stream::once(async move {
-                    Err(Error::ApiError {
-                        code: 400,
-                        message: format!("Invalid URL: {e}"),
-                        metadata: None,
-                    })
-                }));
-            }
-        };
+        let stream = try_stream! {
+            // Truncation may fetch the context window or summarize, so it
+            // runs once the stream is polled.
+            let mut truncation = self.truncate(&mut request, &options).await?;
+            mode.apply_with(CHAT_COMPLETION, diagnostics, || {
+                validation::check_token_limits(&request)
+            })?;
+            let model = request.model.clone();
 
-        // Serialize the request with streaming enabled.
-        let mut req_body = match serde_json::to_value(&request) {
-            Ok(body) => body,
-            Err(e) => {
-                return Box::pin(futures::stream::once(async move {
-                    Err(Error::ApiError {
-                        code: 500,
-                        message: format!("Request serialization error: {e}"),
-                        metadata: None,
-                    })
-                }));
-            }
-        };
-        req_body["stream"] = serde_json::Value::Bool(true);
+            // Released if the stream fails or is dropped before the first chunk;
+            // after that the estimate is kept unless usage reports the cost.
+            let mut reservation = match self.config.budget_guard.clone() {
+                Some(guard) => Some(PendingReservation::reserve(guard, RequestSize::of_chat(&request))?),
+                None => None,
+            };
+
+            // Build the URL for the chat completions endpoint.
+            let url = self.config.url_builder().build("chat/completions")?;
+
+            // Serialize the request with streaming enabled.
+            let mut req_body = serde_json::to_value(&request).map_err(|e| Error::ApiError {
+                code: 500,
+                message: format!("Request serialization error: {e}"),
+                metadata: None,
+            })?;
+            req_body["stream"] = serde_json::Value::Bool(true);
+
+            let request_builder = self.config.compress(
+                CHAT_COMPLETION,
+                options.apply_timeout(
+                    self.config.authorize(
+                        self.config
+                            .route(client.post(url).headers(headers).json(&req_body)),
+                    ),
+                ),
+            );
+
+            deadlines.start();
+            let mut stats = metrics_recorder.as_ref().map(|_| StreamStats::new());
 
-        let stream = try_stream! {
             // Issue the POST request
-            let response = client
-                .post(url)
-                .headers((*headers).clone())
-                .json(&req_body)
-                .send()
-                .await
+            let sent = deadlines.run(request_builder.send()).await?;
+            if let Some(failover) = &failover {
+                failover.observe_send(&sent);
+            }
+            let response = sent
                 .map_err(|e| {
+                    if e.is_timeout() {
+                        return deadlines.timed_out();
+                    }
                     Error::ApiError {
                         code: 500,
                         message: format!("Request failed: {e}"),
@@ -158,21 +529,35 @@ impl ChatApi {
                     }
                 })?;
 
-            let response = response.error_for_status().map_err(|e| {
-                Error::ApiError {
-                    code: e.status().map(|s| s.as_u16()).unwrap_or(500),
-                    message: e.to_string(),
-                    metadata: None,
+            // Keep the error body, as for non-streaming requests, so
+            // `raw_body` and `provider_error` are available.
+            let response = match response.status() {
+                status if status.is_success() => response,
+                status => {
+                    let body = read_body_limited(response, max_response_bytes).await?;
+                    Err(Error::from_response_text(status.as_u16(), &body))?
                 }
-            })?;
+            };
 
             // Process the bytes stream as an asynchronous line stream.
             let byte_stream = response.bytes_stream().map_err(std::io::Error::other);
             let stream_reader = StreamReader::new(byte_stream);
             let mut lines = FramedRead::new(stream_reader, LinesCodec::new_with_max_length(MAX_LINE_LENGTH));
+            let mut total_bytes = 0usize;
 
-            while let Some(line_result) = lines.next().await {
-                let line = line_result.map_err(|e| Error::StreamingError(format!("Failed to read stream line: {e}")))?;
+            while let Some(line_result) = deadlines.run(lines.next()).await? {
+                let line = line_result.map_err(|e| {
+                    if is_body_timeout(&e) {
+                        return deadlines.timed_out();
+                    }
+                    Error::StreamingError(format!("Failed to read stream line: {e}"))
+                })?;
+
+                // Safety check: total size of the stream, line terminators included
+                total_bytes += line.len() + 1;
+                if total_bytes > max_response_bytes {
+                    Err(Error::ResponseTooLarge(total_bytes, max_response_bytes))?;
+                }
 
                 // Skip empty lines before incurring chunk budget or backpressure cost
                 if line.trim().is_empty() {
@@ -194,7 +579,16 @@ impl ChatApi {
                     }
 
                     match serde_json::from_str::<ChatCompletionChunk>(data_part) {
-                        Ok(chunk) => {
+                        Ok(mut chunk) => {
+                            track_chunk_cost(&mut reservation, &chunk);
+                            deadlines.first_chunk_received();
+                            if let Some(stats) = &mut stats {
+                                stats.push(&chunk);
+                            }
+                            if strip_reasoning {
+                                chunk.strip_reasoning();
+                            }
+                            chunk.truncation = truncation.take();
                             yield chunk;
                         },
                         Err(e) => {
@@ -203,12 +597,11 @@ impl ChatApi {
                                 "Streaming chunk parse error"
                             );
 
-                            // Log via tracing if available; otherwise silently skip
-                            // malformed chunks (library crates must not write to stderr).
-                            #[cfg(feature = "tracing")]
-                            tracing::error!("Streaming parse error: {}", error_msg);
-
-                            let _ = error_msg; // suppress unused warning when tracing is off
+                            // Report and skip malformed chunks.
+                            diagnostics.emit(&Diagnostic::StreamChunkSkipped {
+                                operation: CHAT_COMPLETION,
+                                error: &error_msg,
+                            });
                             continue;
                         }
                     }
@@ -218,16 +611,53 @@ impl ChatApi {
                 } else {
                     // Try to parse as a regular JSON message (non-SSE format)
                     match serde_json::from_str::<ChatCompletionChunk>(&line) {
-                        Ok(chunk) => {
+                        Ok(mut chunk) => {
+                            track_chunk_cost(&mut reservation, &chunk);
+                            deadlines.first_chunk_received();
+                            if let Some(stats) = &mut stats {
+                                stats.push(&chunk);
+                            }
+                            if strip_reasoning {
+                                chunk.strip_reasoning();
+                            }
+                            chunk.truncation = truncation.take();
                             yield chunk;
                         },
                         Err(_) => continue,
                     }
                 }
             }
+
+            // Completed without a reported cost: keep the estimate.
+            if let Some(reservation) = reservation.take() {
+                reservation.settle(None);
+            }
+            if let (Some(recorder), Some(stats)) = (&metrics_recorder, &stats) {
+                recorder.record_stream(&model, &stats.summary());
+            }
         };
 
-        Box::pin(stream)
+        Box::pin(self.config.in_flight.track_stream(CHAT_COMPLETION, stream))
+    }
+
+    /// Stamps the client's default user and default parameters onto
+    /// requests that don't set them.
+    fn apply_defaults(&self, request: &mut ChatCompletionRequest) {
+        if request.user.is_none() {
+            request.user.clone_from(&self.config.default_user);
+        }
+        self.config.default_params.apply_to_chat(request);
+    }
+
+    /// Normalizes the messages for the request's provider when the client
+    /// enables message normalization.
+    fn normalize_messages(&self, request: &mut ChatCompletionRequest) {
+        if self.config.message_normalization {
+            normalization::normalize_messages(
+                &mut request.messages,
+                NormalizationRules::for_model(&request.model),
+            );
+        }
     }
 
     /// Simple function to complete a chat with a single user message
@@ -240,17 +670,253 @@ impl ChatApi {
 
         let response = self.chat_completion(request).await?;
 
-        let choice = response.choices.first().ok_or_else(|| Error::ApiError {
+        let choice = response.first_choice().ok_or_else(|| Error::ApiError {
             code: 500,
             message: "API returned no choices".into(),
             metadata: None,
         })?;
 
-        match &choice.message.content {
-            MessageContent::Text(content) => Ok(content.clone()),
-            MessageContent::Parts(_) => Err(Error::ConfigError(
-                "Unexpected multimodal content in simple completion response".into(),
-            )),
+        choice
+            .message
+            .content
+            .as_text()
+            .map(str::to_string)
+            .ok_or_else(|| {
+                Error::ConfigError(
+                    "Unexpected multimodal content in simple completion response".into(),
+                )
+            })
+    }
+}
+
+/// Time limits for a single chat completion stream.
+struct StreamDeadlines {
+    first_token_timeout: Option<Duration>,
+    timeout: Option<Duration>,
+    started: Instant,
+    first_chunk_received: bool,
+}
+
+impl StreamDeadlines {
+    fn new(options: &RequestOptions) -> Self {
+        Self {
+            first_token_timeout: options.first_token_timeout,
+            timeout: options.timeout,
+            started: Instant::now(),
+            first_chunk_received: false,
+        }
+    }
+
+    /// Restarts the clock; called when the stream is first polled.
+    fn start(&mut self) {
+        self.started = Instant::now();
+    }
+
+    fn first_chunk_received(&mut self) {
+        self.first_chunk_received = true;
+    }
+
+    /// The earliest applicable limit and a description of it.
+    fn current_limit(&self) -> Option<(Duration, &'static str)> {
+        let first_token = self
+            .first_token_timeout
+            .filter(|_| !self.first_chunk_received)
+            .map(|limit| (limit, "waiting for the first chunk"));
+        let total = self.timeout.map(|limit| (limit, "(total stream deadline)"));
+        first_token
+            .into_iter()
+            .chain(total)
+            .min_by_key(|(limit, _)| *limit)
+    }
+
+    /// The error reported when a deadline passes, including when the HTTP
+    /// client's own timer (set from the same `timeout`) fires first.
+    fn timed_out(&self) -> Error {
+        match self.current_limit() {
+            Some((limit, what)) => {
+                Error::TimeoutError(format!("Stream timed out after {limit:?} {what}"))
+            }
+            None => Error::TimeoutError("Stream request timed out".into()),
+        }
+    }
+
+    /// Awaits `future`, failing with `Error::TimeoutError` once the earliest
+    /// applicable deadline passes.
+    async fn run<F: std::future::Future>(&self, future: F) -> Result<F::Output> {
+        let Some((limit, _)) = self.current_limit() else {
+            return Ok(future.await);
+        };
+
+        let deadline = tokio::time::Instant::from_std(self.started + limit);
+        tokio::time::timeout_at(deadline, future)
+            .await
+            .map_err(|_| self.timed_out())
+    }
+}
+
+/// Marks a stream's reservation billable once a chunk arrives, and settles
+/// it with the actual cost when the chunk carries usage (the final one).
+fn track_chunk_cost(
+    reservation: &mut Option<PendingReservation<Arc<BudgetGuard>>>,
+    chunk: &ChatCompletionChunk,
+) {
+    if let Some(pending) = reservation.as_mut() {
+        pending.mark_billable();
+    }
+    if let Some(cost) = chunk.usage.as_ref().and_then(|usage| usage.cost) {
+        if let Some(pending) = reservation.take() {
+            pending.settle(Some(cost));
+        }
+    }
+}
+
+/// Whether a stream read failed because the HTTP client's timeout elapsed.
+fn is_body_timeout(error: &LinesCodecError) -> bool {
+    match error {
+        LinesCodecError::Io(e) => e
+            .get_ref()
+            .and_then(|inner| inner.downcast_ref::<reqwest::Error>())
+            .is_some_and(reqwest::Error::is_timeout),
+        _ => false,
+    }
+}
+
+/// Extension methods for streams of chat completion chunks.
+///
+/// Implemented for every `Stream<Item = Result<ChatCompletionChunk>>`,
+/// including the stream returned by [`ChatApi::chat_completion_stream`].
+pub trait ChatCompletionStream: Stream<Item = Result<ChatCompletionChunk>> + Send + Sized {
+    /// Drains the stream and assembles the deltas (content, tool calls,
+    /// usage and finish reason) into a single `ChatCompletionResponse`.
+    ///
+    /// Stops at the first stream error and returns it.
+    fn collect_full(
+        self,
+    ) -> impl std::future::Future<Output = Result<ChatCompletionResponse>> + Send;
+
+    /// Wraps each chunk in `StreamEvent::Chunk` while tracking `StreamStats`,
+    /// then yields a final `StreamEvent::Summary` once the stream ends.
+    ///
+    /// Timing starts when the stream is first polled. No summary is emitted
+    /// if the stream fails.
+    fn with_stats(self) -> impl Stream<Item = Result<StreamEvent>> + Send;
+
+    /// Turns the stream into typed content and tool call events; see
+    /// [`ToolCallTracker`].
+    ///
+    /// Stops at the first stream error, or when a tool call's arguments
+    /// are not JSON.
+    fn tool_call_events(self) -> impl Stream<Item = Result<ToolStreamEvent>> + Send;
+
+    /// Passes chunks through until `predicate` returns true, then yields
+    /// that chunk and ends.
+    ///
+    /// The underlying stream is dropped as soon as the predicate fires,
+    /// which for [`ChatApi::chat_completion_stream`] closes the connection
+    /// and stops the generation, so the rest of the output is not billed.
+    ///
+    /// With `futures::StreamExt` also in scope, call it as
+    /// `ChatCompletionStream::take_until(stream, predicate)`.
+    ///
+    /// ```rust,no_run
+    /// # use openrouter_api::api::chat::{ChatApi, ChatCompletionStream};
+    /// # use openrouter_api::types::chat::ChatCompletionRequest;
+    /// # async fn run(api: ChatApi, request: ChatCompletionRequest) -> openrouter_api::Result<()> {
+    /// // Stop once a JSON object has closed, or after about 500 tokens.
+    /// let response = api
+    ///     .chat_completion_stream(request)
+    ///     .take_until(|progress| {
+    ///         progress.text.trim_end().ends_with('}') || progress.estimated_tokens >= 500
+    ///     })
+    ///     .collect_full()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn take_until<P>(self, predicate: P) -> impl Stream<Item = Result<ChatCompletionChunk>> + Send
+    where
+        P: FnMut(&StreamProgress<'_>) -> bool + Send;
+}
+
+impl<S> ChatCompletionStream for S
+where
+    S: Stream<Item = Result<ChatCompletionChunk>> + Send + Sized,
+{
+    async fn collect_full(self) -> Result<ChatCompletionResponse> {
+        self.try_fold(
+            ChatCompletionAccumulator::new(),
+            |mut accumulator, chunk| async move {
+                accumulator.push(chunk);
+                Ok(accumulator)
+            },
+        )
+        .await?
+        .finish()
+    }
+
+    fn with_stats(self) -> impl Stream<Item = Result<StreamEvent>> + Send {
+        try_stream! {
+            let mut stats = StreamStats::new();
+            let mut chunks = Box::pin(self);
+            while let Some(chunk) = chunks.next().await {
+                let chunk = chunk?;
+                stats.push(&chunk);
+                yield StreamEvent::Chunk(chunk);
+            }
+            yield StreamEvent::Summary(stats.summary());
+        }
+    }
+
+    fn tool_call_events(self) -> impl Stream<Item = Result<ToolStreamEvent>> + Send {
+        try_stream! {
+            let mut tracker = ToolCallTracker::new();
+            let mut chunks = Box::pin(self);
+            while let Some(chunk) = chunks.next().await {
+                for event in tracker.push(chunk?)? {
+                    yield event;
+                }
+            }
+            for event in tracker.finish()? {
+                yield event;
+            }
+        }
+    }
+
+    fn take_until<P>(
+        self,
+        mut predicate: P,
+    ) -> impl Stream<Item = Result<ChatCompletionChunk>> + Send
+    where
+        P: FnMut(&StreamProgress<'_>) -> bool + Send,
+    {
+        try_stream! {
+            let mut stats = StreamStats::new();
+            let mut text = String::new();
+            let mut chunks = Box::pin(self);
+            while let Some(chunk) = chunks.next().await {
+                let chunk = chunk?;
+                stats.push(&chunk);
+                let content = chunk
+                    .choices
+                    .iter()
+                    .find(|choice| choice.index == 0)
+                    .and_then(|choice| choice.delta.content.as_ref());
+                if let Some(content) = content {
+                    text.push_str(&content.to_text());
+                }
+                let stop = predicate(&StreamProgress {
+                    chunk: &chunk,
+                    text: &text,
+                    estimated_tokens: stats.estimated_tokens(),
+                });
+                if stop {
+                    // Cancel the request before handing over the last chunk.
+                    drop(chunks);
+                    yield chunk;
+                    break;
+                }
+                yield chunk;
+            }
         }
     }
 }
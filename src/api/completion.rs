@@ -1,10 +1,17 @@
 // api/completion.rs
-use crate::error::{Error, Result};
+use crate::api::core::ApiCore;
+use crate::api::generation::{GenerationApi, TrackedResponse};
+use crate::api::options::RequestOptions;
+use crate::error::Result;
 use crate::types::completion::{CompletionRequest, CompletionResponse};
+use crate::types::response_meta::WithMeta;
 use crate::utils::{
-    retry::execute_with_retry_builder, retry::handle_response_json,
-    retry::operations::TEXT_COMPLETION, validation::validate_completion_request,
+    budget::{self, RequestSize},
+    diagnostics::Diagnostic,
+    retry::operations::TEXT_COMPLETION,
+    validation::{completion::supports_suffix, validate_completion_request},
 };
+use reqwest::header::HeaderMap;
 use reqwest::Client;
 
 /// API endpoint for text completions.
@@ -27,32 +34,105 @@ impl CompletionApi {
     /// along with any additional generation parameters (temperature, top_p, and so on).
     #[must_use = "returns a completion response that should be processed"]
     pub async fn text_completion(&self, request: CompletionRequest) -> Result<CompletionResponse> {
+        self.text_completion_with_options(request, &RequestOptions::default())
+            .await
+    }
+
+    /// Calls the completions endpoint with per-call options such as extra
+    /// headers, a timeout or provider overrides.
+    #[must_use = "returns a completion response that should be processed"]
+    pub async fn text_completion_with_options(
+        &self,
+        request: CompletionRequest,
+        options: &RequestOptions,
+    ) -> Result<CompletionResponse> {
+        self.text_completion_with_meta(request, options)
+            .await
+            .map(WithMeta::into_response)
+    }
+
+    /// Like [`text_completion_with_options`](Self::text_completion_with_options),
+    /// also returning the request ID, timing and rate limit state from the
+    /// response headers.
+    #[must_use = "returns a completion response that should be processed"]
+    pub async fn text_completion_with_meta(
+        &self,
+        mut request: CompletionRequest,
+        options: &RequestOptions,
+    ) -> Result<WithMeta<CompletionResponse>> {
+        options.apply_to_completion(&mut request)?;
+        if let Some(user) = &self.config.default_user {
+            if request.extra_params.is_null() {
+                request.extra_params = serde_json::json!({});
+            }
+            if let Some(params) = request.extra_params.as_object_mut() {
+                params
+                    .entry("user")
+                    .or_insert_with(|| serde_json::Value::String(user.clone()));
+            }
+        }
+        self.config
+            .default_params
+            .apply_to_completion(&mut request)?;
+
         // Validate the request using the new validation module
-        validate_completion_request(&request)?;
-
-        // Build the URL.
-        let url = self
-            .config
-            .base_url
-            .join("completions")
-            .map_err(|e| Error::ApiError {
-                code: 400,
-                message: format!("Invalid URL for completions: {e}"),
-                metadata: None,
+        options
+            .validation_mode(self.config.validation_mode)
+            .apply_with(TEXT_COMPLETION, self.config.diagnostics(), || {
+                validate_completion_request(&request)
             })?;
+        let has_suffix = request.suffix.is_some()
+            || request
+                .extra_params
+                .get("suffix")
+                .is_some_and(|suffix| suffix.is_string());
+        if has_suffix && !supports_suffix(&request.model) {
+            self.config
+                .diagnostics()
+                .emit(&Diagnostic::UnsupportedParameter {
+                    model: &request.model,
+                    parameter: "suffix",
+                });
+        }
+        let headers = options.idempotent_headers(&self.config.headers)?;
+
+        budget::guarded(
+            self.config.budget_guard.as_deref(),
+            RequestSize::of_completion(&request),
+            self.send_text_completion(&request, &headers, options),
+            |_| None,
+        )
+        .await
+    }
+
+    /// Calls the completions endpoint and reconciles the cost in the background.
+    ///
+    /// See `ChatApi::chat_completion_tracked`; stats are only fetched when
+    /// cost tracking is enabled and the response carries a generation ID.
+    #[must_use = "returns the tracked completion response that should be processed"]
+    pub async fn text_completion_tracked(
+        &self,
+        request: CompletionRequest,
+    ) -> Result<TrackedResponse<CompletionResponse>> {
+        let response = self.text_completion(request).await?;
+        let id = response.id.clone();
+        Ok(GenerationApi::track(
+            &self.client,
+            &self.config,
+            id.as_deref(),
+            response,
+        ))
+    }
 
-        // Execute request with retry logic
-        let response =
-            execute_with_retry_builder(&self.config.retry_config, TEXT_COMPLETION, || {
-                self.client
-                    .post(url.clone())
-                    .headers((*self.config.headers).clone())
-                    .json(&request)
-            })
-            .await?;
-
-        // Handle response with consistent error parsing
-        handle_response_json::<CompletionResponse>(response, TEXT_COMPLETION).await
+    async fn send_text_completion(
+        &self,
+        request: &CompletionRequest,
+        headers: &HeaderMap,
+        options: &RequestOptions,
+    ) -> Result<WithMeta<CompletionResponse>> {
+        ApiCore::new(&self.client, &self.config)
+            .post_with_meta(TEXT_COMPLETION, "completions", request, headers, options)
+            .await
     }
 }
 
@@ -0,0 +1,310 @@
+//! Request plumbing shared by the API modules.
+//!
+//! Every JSON endpoint goes through [`ApiCore`], so URL building, headers,
+//! retries, per-call options and the response size limit behave the same
+//! everywhere.
+
+use crate::api::options::RequestOptions;
+use crate::client::ApiConfig;
+use crate::error::Result;
+use crate::types::response_meta::{ResponseMeta, WithMeta};
+use crate::utils::diagnostics::Diagnostic;
+use crate::utils::retry::handle_response_json_limited;
+use reqwest::header::HeaderMap;
+use reqwest::{Client, Method, Response};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// Sends JSON requests for an API module with its client and configuration.
+pub(crate) struct ApiCore<'a> {
+    client: &'a Client,
+    config: &'a ApiConfig,
+}
+
+impl<'a> ApiCore<'a> {
+    pub(crate) fn new(client: &'a Client, config: &'a ApiConfig) -> Self {
+        Self { client, config }
+    }
+
+    /// GETs `path` with the query parameters `query` serializes to.
+    pub(crate) async fn get<T, Q>(&self, operation: &str, path: &str, query: &Q) -> Result<T>
+    where
+        T: DeserializeOwned,
+        Q: Serialize + ?Sized,
+    {
+        self.get_with_options(operation, path, query, &RequestOptions::default())
+            .await
+    }
+
+    /// Like [`get`](Self::get), with per-call `options`.
+    pub(crate) async fn get_with_options<T, Q>(
+        &self,
+        operation: &str,
+        path: &str,
+        query: &Q,
+        options: &RequestOptions,
+    ) -> Result<T>
+    where
+        T: DeserializeOwned,
+        Q: Serialize + ?Sized,
+    {
+        let url = self.config.url_builder().build_with_query(path, query)?;
+        let headers = options.headers(&self.config.headers)?;
+        self.send(operation, Method::GET, url, &headers, None::<&()>, options)
+            .await
+    }
+
+    /// Like [`get`](Self::get), answering from the catalog cache while a
+    /// response for the same URL is fresh.
+    pub(crate) async fn get_cached<T, Q>(&self, operation: &str, path: &str, query: &Q) -> Result<T>
+    where
+        T: DeserializeOwned + Clone + Send + Sync + 'static,
+        Q: Serialize + ?Sized,
+    {
+        let url = self.config.url_builder().build_with_query(path, query)?;
+        let diagnostics = self.config.diagnostics();
+        if let Some(cached) = self.config.catalog_cache.get(url.as_str()) {
+            diagnostics.emit(&Diagnostic::CacheHit { key: url.as_str() });
+            return Ok(cached);
+        }
+        diagnostics.emit(&Diagnostic::CacheMiss { key: url.as_str() });
+        let response: T = self.get_url(operation, url.clone()).await?;
+        self.config
+            .catalog_cache
+            .insert(url.into(), response.clone());
+        Ok(response)
+    }
+
+    /// Fetches `path` and replaces its response in the catalog cache.
+    pub(crate) async fn refresh<T, Q>(&self, operation: &str, path: &str, query: &Q) -> Result<T>
+    where
+        T: DeserializeOwned + Clone + Send + Sync + 'static,
+        Q: Serialize + ?Sized,
+    {
+        let url = self.config.url_builder().build_with_query(path, query)?;
+        let response: T = self.get_url(operation, url.clone()).await?;
+        self.config
+            .catalog_cache
+            .insert(url.into(), response.clone());
+        Ok(response)
+    }
+
+    async fn get_url<T: DeserializeOwned>(&self, operation: &str, url: url::Url) -> Result<T> {
+        self.send(
+            operation,
+            Method::GET,
+            url,
+            &self.config.headers,
+            None::<&()>,
+            &RequestOptions::default(),
+        )
+        .await
+    }
+
+    /// POSTs `body` to `path` with the per-call `options`.
+    pub(crate) async fn post<T, B>(
+        &self,
+        operation: &str,
+        path: &str,
+        body: &B,
+        options: &RequestOptions,
+    ) -> Result<T>
+    where
+        T: DeserializeOwned,
+        B: Serialize + ?Sized,
+    {
+        let headers = options.headers(&self.config.headers)?;
+        self.post_with_headers(operation, path, body, &headers, options)
+            .await
+    }
+
+    /// Like [`post`](Self::post), with headers already merged from the
+    /// options, e.g. to keep an idempotency key across calls.
+    pub(crate) async fn post_with_headers<T, B>(
+        &self,
+        operation: &str,
+        path: &str,
+        body: &B,
+        headers: &HeaderMap,
+        options: &RequestOptions,
+    ) -> Result<T>
+    where
+        T: DeserializeOwned,
+        B: Serialize + ?Sized,
+    {
+        let url = self.config.url_builder().build(path)?;
+        self.send(operation, Method::POST, url, headers, Some(body), options)
+            .await
+    }
+
+    /// Like [`post_with_headers`](Self::post_with_headers), also returning
+    /// the metadata from the response headers.
+    pub(crate) async fn post_with_meta<T, B>(
+        &self,
+        operation: &str,
+        path: &str,
+        body: &B,
+        headers: &HeaderMap,
+        options: &RequestOptions,
+    ) -> Result<WithMeta<T>>
+    where
+        T: DeserializeOwned,
+        B: Serialize + ?Sized,
+    {
+        let url = self.config.url_builder().build(path)?;
+        self.send_with_meta(operation, Method::POST, url, headers, Some(body), options)
+            .await
+    }
+
+    /// PATCHes `path` with `body`.
+    pub(crate) async fn patch<T, B>(&self, operation: &str, path: &str, body: &B) -> Result<T>
+    where
+        T: DeserializeOwned,
+        B: Serialize + ?Sized,
+    {
+        let url = self.config.url_builder().build(path)?;
+        self.send(
+            operation,
+            Method::PATCH,
+            url,
+            &self.config.headers,
+            Some(body),
+            &RequestOptions::default(),
+        )
+        .await
+    }
+
+    /// DELETEs `path`.
+    pub(crate) async fn delete<T: DeserializeOwned>(
+        &self,
+        operation: &str,
+        path: &str,
+    ) -> Result<T> {
+        let url = self.config.url_builder().build(path)?;
+        self.send(
+            operation,
+            Method::DELETE,
+            url,
+            &self.config.headers,
+            None::<&()>,
+            &RequestOptions::default(),
+        )
+        .await
+    }
+
+    async fn send<T, B>(
+        &self,
+        operation: &str,
+        method: Method,
+        url: url::Url,
+        headers: &HeaderMap,
+        body: Option<&B>,
+        options: &RequestOptions,
+    ) -> Result<T>
+    where
+        T: DeserializeOwned,
+        B: Serialize + ?Sized,
+    {
+        let response = self
+            .execute(operation, method, url, headers, body, options)
+            .await?;
+        self.decode(response, operation).await
+    }
+
+    async fn send_with_meta<T, B>(
+        &self,
+        operation: &str,
+        method: Method,
+        url: url::Url,
+        headers: &HeaderMap,
+        body: Option<&B>,
+        options: &RequestOptions,
+    ) -> Result<WithMeta<T>>
+    where
+        T: DeserializeOwned,
+        B: Serialize + ?Sized,
+    {
+        let response = self
+            .execute(operation, method, url, headers, body, options)
+            .await?;
+        let meta = ResponseMeta::from_headers(response.headers());
+        Ok(WithMeta {
+            response: self.decode(response, operation).await?,
+            meta,
+        })
+    }
+
+    async fn execute<B: Serialize + ?Sized>(
+        &self,
+        operation: &str,
+        method: Method,
+        url: url::Url,
+        headers: &HeaderMap,
+        body: Option<&B>,
+        options: &RequestOptions,
+    ) -> Result<Response> {
+        self.config
+            .execute_with_options(operation, options, || {
+                let request = self
+                    .client
+                    .request(method.clone(), url.clone())
+                    .headers(headers.clone());
+                match body {
+                    Some(body) => request.json(body),
+                    None => request,
+                }
+            })
+            .await
+    }
+
+    /// Decodes a JSON response, reading at most `max_response_bytes`.
+    pub(crate) async fn decode<T: DeserializeOwned>(
+        &self,
+        response: Response,
+        operation: &str,
+    ) -> Result<T> {
+        handle_response_json_limited(response, operation, self.config.max_response_bytes).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Error;
+    use crate::tests::test_helpers::test_client_config;
+    use wiremock::{matchers, Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn test_core_enforces_response_size_limit() {
+        let server = MockServer::start().await;
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/models"))
+            .and(matchers::query_param("provider", "openai"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({"data": ["x".repeat(64)]})),
+            )
+            .mount(&server)
+            .await;
+
+        let mut config = test_client_config();
+        config.base_url = format!("{}/", server.uri()).parse().unwrap();
+        let mut config = config.to_api_config().unwrap();
+        let client = Client::new();
+
+        let core = ApiCore::new(&client, &config);
+        let body: serde_json::Value = core
+            .get("list_models", "models", &[("provider", "openai")])
+            .await
+            .unwrap();
+        assert_eq!(body["data"][0].as_str().unwrap().len(), 64);
+
+        config.max_response_bytes = 32;
+        let core = ApiCore::new(&client, &config);
+        let error = core
+            .get::<serde_json::Value, _>("list_models", "models", &[("provider", "openai")])
+            .await
+            .unwrap_err();
+        assert!(matches!(error, Error::ResponseTooLarge(_, 32)));
+    }
+}
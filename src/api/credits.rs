@@ -1,10 +1,17 @@
+use crate::api::core::ApiCore;
+use crate::api::options::RequestOptions;
+use crate::client::RetryConfig;
 use crate::error::{Error, Result};
-use crate::types::credits::CreditsResponse;
-use crate::utils::retry::operations::GET_BALANCE;
-use crate::utils::{retry::execute_with_retry_builder, retry::handle_response_json};
+use crate::types::credits::{CoinbaseChargeRequest, CoinbaseChargeResponse, CreditsResponse};
+use crate::utils::retry::operations::{CREATE_COINBASE_CHARGE, GET_BALANCE};
 use reqwest::Client;
 
 /// API endpoint for credits management.
+///
+/// Covers the balance and Coinbase credit purchases. OpenRouter documents no
+/// endpoint for querying a charge's status, so there is no status query;
+/// watch the on-chain transfer or poll `get_balance` instead. Stripe
+/// purchases are not exposed by the API either.
 pub struct CreditsApi {
     pub(crate) client: Client,
     pub(crate) config: crate::client::ApiConfig,
@@ -61,27 +68,105 @@ impl CreditsApi {
     /// }
     /// ```
     pub async fn get_balance(&self) -> Result<CreditsResponse> {
-        // Build the URL.
-        let url = self
-            .config
-            .base_url
-            .join("credits")
-            .map_err(|e| Error::ApiError {
-                code: 400,
-                message: format!("Invalid URL for credits endpoint: {e}"),
-                metadata: None,
-            })?;
-
-        // Execute request with retry logic
-        let response = execute_with_retry_builder(&self.config.retry_config, GET_BALANCE, || {
-            self.client
-                .get(url.clone())
-                .headers((*self.config.headers).clone())
-        })
-        .await?;
+        self.core().get(GET_BALANCE, "credits", &()).await
+    }
+
+    /// Like `get_balance`, with per-call options.
+    pub async fn get_balance_with_options(
+        &self,
+        options: &RequestOptions,
+    ) -> Result<CreditsResponse> {
+        self.core()
+            .get_with_options(GET_BALANCE, "credits", &(), options)
+            .await
+    }
+
+    /// Creates a Coinbase purchase intent for buying credits with crypto
+    /// (`POST /credits/coinbase`, see
+    /// <https://openrouter.ai/docs/api-reference/create-coinbase-charge>).
+    ///
+    /// The returned `web3_data` contains the on-chain transfer intent that the
+    /// sender wallet must execute before `expires_at`.
+    ///
+    /// The request is never retried: a retry after a timeout or 5xx that the
+    /// server had already accepted would create a second charge.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::ValidationError` if the amount is not a positive number,
+    /// the sender address is empty, or the chain ID is zero.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use openrouter_api::OpenRouterClient;
+    /// use openrouter_api::types::credits::{chains, CoinbaseChargeRequest};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = OpenRouterClient::from_env()?;
+    ///     let request = CoinbaseChargeRequest::new(50.0, "0x1234...", chains::BASE);
+    ///     let charge = client.credits()?.create_coinbase_charge(&request).await?;
+    ///
+    ///     println!("Charge {} expires at {}", charge.data.id, charge.data.expires_at);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn create_coinbase_charge(
+        &self,
+        request: &CoinbaseChargeRequest,
+    ) -> Result<CoinbaseChargeResponse> {
+        self.create_coinbase_charge_with_options(request, &RequestOptions::default())
+            .await
+    }
+
+    /// Like `create_coinbase_charge`, with per-call options.
+    ///
+    /// The charge is still not retried unless `options.retry_override` asks
+    /// for it, which should go with an `idempotency_key`.
+    pub async fn create_coinbase_charge_with_options(
+        &self,
+        request: &CoinbaseChargeRequest,
+        options: &RequestOptions,
+    ) -> Result<CoinbaseChargeResponse> {
+        Self::validate_amount(request.amount)?;
+        if request.sender.trim().is_empty() {
+            return Err(Error::ValidationError(
+                "Coinbase sender address cannot be empty".to_string(),
+            ));
+        }
+        if request.chain_id == 0 {
+            return Err(Error::ValidationError(
+                "Coinbase chain_id must be non-zero".to_string(),
+            ));
+        }
 
-        // Handle response with consistent error parsing
-        handle_response_json::<CreditsResponse>(response, GET_BALANCE).await
+        let mut options = options.clone();
+        options.retry_override.get_or_insert_with(|| RetryConfig {
+            max_retries: 0,
+            ..(*self.config.retry_config).clone()
+        });
+        self.core()
+            .post(
+                CREATE_COINBASE_CHARGE,
+                "credits/coinbase",
+                request,
+                &options,
+            )
+            .await
+    }
+
+    fn core(&self) -> ApiCore<'_> {
+        ApiCore::new(&self.client, &self.config)
+    }
+
+    fn validate_amount(amount: f64) -> Result<()> {
+        if !amount.is_finite() || amount <= 0.0 {
+            return Err(Error::ValidationError(
+                "Credit purchase amount must be a positive number".to_string(),
+            ));
+        }
+        Ok(())
     }
 }
 
@@ -124,4 +209,83 @@ mod tests {
             url.path()
         );
     }
+
+    #[test]
+    fn test_purchase_validation_rejects_bad_inputs() {
+        assert!(CreditsApi::validate_amount(0.0).is_err());
+        assert!(CreditsApi::validate_amount(-5.0).is_err());
+        assert!(CreditsApi::validate_amount(f64::NAN).is_err());
+        assert!(CreditsApi::validate_amount(10.0).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_coinbase_charge_rejects_empty_sender() {
+        use crate::tests::test_helpers::test_client_config;
+
+        let config = test_client_config();
+        let credits_api = CreditsApi::new(Client::new(), &config).unwrap();
+        let request = CoinbaseChargeRequest::new(10.0, " ", 1);
+
+        let result = credits_api.create_coinbase_charge(&request).await;
+        assert!(matches!(result, Err(Error::ValidationError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_coinbase_charge_wiremock() {
+        use crate::tests::test_helpers::test_client_config;
+        use serde_json::json;
+        use wiremock::{matchers, Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+        let mut config = test_client_config();
+        config.base_url = url::Url::parse(&format!("{}/", mock_server.uri())).unwrap();
+        let credits_api = CreditsApi::new(Client::new(), &config).unwrap();
+
+        Mock::given(matchers::method("POST"))
+            .and(matchers::path("/credits/coinbase"))
+            .and(matchers::body_json(
+                json!({"amount": 25.0, "sender": "0xabc", "chain_id": 8453}),
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "data": {
+                    "id": "ch_123",
+                    "created_at": "2025-01-01T00:00:00Z",
+                    "expires_at": "2025-01-01T01:00:00Z",
+                    "web3_data": {"transfer_intent": {"metadata": {"chain_id": 8453}}}
+                }
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let charge = credits_api
+            .create_coinbase_charge(&CoinbaseChargeRequest::new(25.0, "0xabc", 8453))
+            .await
+            .unwrap();
+        assert_eq!(charge.data.id, "ch_123");
+        assert!(charge.data.web3_data.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_coinbase_charge_is_not_retried() {
+        use crate::tests::test_helpers::test_client_config;
+        use wiremock::{matchers, Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+        let mut config = test_client_config();
+        config.base_url = url::Url::parse(&format!("{}/", mock_server.uri())).unwrap();
+        config.retry_config.initial_backoff_ms = 1;
+        let credits_api = CreditsApi::new(Client::new(), &config).unwrap();
+
+        Mock::given(matchers::method("POST"))
+            .and(matchers::path("/credits/coinbase"))
+            .respond_with(ResponseTemplate::new(502))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let result = credits_api
+            .create_coinbase_charge(&CoinbaseChargeRequest::new(25.0, "0xabc", 8453))
+            .await;
+        assert!(result.is_err());
+    }
 }
@@ -1,7 +1,8 @@
+use crate::api::core::ApiCore;
+use crate::api::options::RequestOptions;
 use crate::error::{Error, Result};
 use crate::types::embeddings::{EmbeddingInput, EmbeddingRequest, EmbeddingResponse};
 use crate::utils::retry::operations::GET_EMBEDDINGS;
-use crate::utils::{retry::execute_with_retry_builder, retry::handle_response_json};
 use reqwest::Client;
 
 /// API endpoint for embeddings.
@@ -42,6 +43,16 @@ impl EmbeddingsApi {
     /// }
     /// ```
     pub async fn create(&self, request: EmbeddingRequest) -> Result<EmbeddingResponse> {
+        self.create_with_options(request, &RequestOptions::default())
+            .await
+    }
+
+    /// Like `create`, with per-call options.
+    pub async fn create_with_options(
+        &self,
+        request: EmbeddingRequest,
+        options: &RequestOptions,
+    ) -> Result<EmbeddingResponse> {
         // Validate model ID
         crate::utils::validation::validate_model_id(&request.model)?;
 
@@ -68,26 +79,9 @@ impl EmbeddingsApi {
             }
         }
 
-        let url = self
-            .config
-            .base_url
-            .join("embeddings")
-            .map_err(|e| Error::ApiError {
-                code: 400,
-                message: format!("Invalid URL for embeddings endpoint: {e}"),
-                metadata: None,
-            })?;
-
-        let response =
-            execute_with_retry_builder(&self.config.retry_config, GET_EMBEDDINGS, || {
-                self.client
-                    .post(url.clone())
-                    .headers((*self.config.headers).clone())
-                    .json(&request)
-            })
-            .await?;
-
-        handle_response_json::<EmbeddingResponse>(response, GET_EMBEDDINGS).await
+        ApiCore::new(&self.client, &self.config)
+            .post(GET_EMBEDDINGS, "embeddings", &request, options)
+            .await
     }
 
     /// Convenience method to embed a single string.
@@ -1,10 +1,74 @@
+use crate::api::core::ApiCore;
+use crate::api::options::RequestOptions;
 use crate::error::{Error, Result};
-use crate::types::generation::GenerationResponse;
-use crate::utils::{
-    retry::execute_with_retry_builder, retry::handle_response_json,
-    retry::operations::GET_GENERATION,
-};
+use crate::types::generation::{GenerationData, GenerationResponse};
+use crate::utils::retry::operations::GET_GENERATION;
 use reqwest::Client;
+use std::time::{Duration, Instant};
+use tokio::sync::oneshot;
+
+/// Polling schedule for [`GenerationApi::get_generation_when_ready`].
+///
+/// Generation stats are usually available within a second or two of a
+/// completion finishing. Until then the endpoint answers 404, which the
+/// poller treats as "not ready yet".
+#[derive(Debug, Clone)]
+pub struct PollPolicy {
+    /// Delay before the second attempt (the first is made immediately).
+    pub initial_delay: Duration,
+    /// Upper bound for the delay between attempts.
+    pub max_delay: Duration,
+    /// Factor the delay grows by after each attempt.
+    pub multiplier: f64,
+    /// Total time after which polling gives up.
+    pub deadline: Duration,
+}
+
+impl Default for PollPolicy {
+    fn default() -> Self {
+        Self {
+            initial_delay: Duration::from_millis(250),
+            max_delay: Duration::from_secs(5),
+            multiplier: 2.0,
+            deadline: Duration::from_secs(30),
+        }
+    }
+}
+
+impl PollPolicy {
+    /// Sets the delay before the second attempt.
+    pub fn with_initial_delay(mut self, delay: Duration) -> Self {
+        self.initial_delay = delay;
+        self
+    }
+
+    /// Sets the maximum delay between attempts.
+    pub fn with_max_delay(mut self, delay: Duration) -> Self {
+        self.max_delay = delay;
+        self
+    }
+
+    /// Sets the backoff multiplier (values below 1.0, and NaN, are treated
+    /// as 1.0).
+    pub fn with_multiplier(mut self, multiplier: f64) -> Self {
+        self.multiplier = multiplier;
+        self
+    }
+
+    /// Sets the overall polling deadline.
+    pub fn with_deadline(mut self, deadline: Duration) -> Self {
+        self.deadline = deadline;
+        self
+    }
+
+    fn next_delay(&self, current: Duration) -> Duration {
+        // An infinite or overflowing product saturates at `max_delay` rather
+        // than panicking like `Duration::mul_f64`.
+        Duration::try_from_secs_f64(current.as_secs_f64() * self.multiplier.max(1.0))
+            .unwrap_or(self.max_delay)
+            .min(self.max_delay)
+    }
+}
 
 /// API endpoint for generation management.
 /// API endpoint for generation information.
@@ -88,6 +152,16 @@ impl GenerationApi {
     /// }
     /// ```
     pub async fn get_generation(&self, id: &str) -> Result<GenerationResponse> {
+        self.get_generation_with_options(id, &RequestOptions::default())
+            .await
+    }
+
+    /// Like `get_generation`, with per-call options.
+    pub async fn get_generation_with_options(
+        &self,
+        id: &str,
+        options: &RequestOptions,
+    ) -> Result<GenerationResponse> {
         // Validate the generation ID
         if id.trim().is_empty() {
             return Err(Error::ConfigError(
@@ -95,29 +169,119 @@ impl GenerationApi {
             ));
         }
 
-        // Build the URL with query parameter.
-        let url = self
-            .config
-            .base_url
-            .join("generation")
-            .map_err(|e| Error::ApiError {
-                code: 400,
-                message: format!("Invalid URL for generation endpoint: {e}"),
-                metadata: None,
-            })?;
-
-        // Execute request with retry logic
-        let response =
-            execute_with_retry_builder(&self.config.retry_config, GET_GENERATION, || {
-                self.client
-                    .get(url.clone())
-                    .query(&[("id", id)])
-                    .headers((*self.config.headers).clone())
-            })
-            .await?;
-
-        // Handle response with consistent error parsing
-        handle_response_json::<GenerationResponse>(response, GET_GENERATION).await
+        ApiCore::new(&self.client, &self.config)
+            .get_with_options(GET_GENERATION, "generation", &[("id", id)], options)
+            .await
+    }
+}
+
+/// A response paired with its generation stats, fetched in the background.
+///
+/// Returned by the `*_tracked` methods on `ChatApi` and `CompletionApi`. The
+/// `generation` receiver resolves once OpenRouter has recorded the final
+/// cost. If cost tracking is disabled on the client, the response carries no
+/// generation ID, or the stats cannot be fetched before the polling deadline,
+/// the sender is dropped and the receiver yields an error.
+#[derive(Debug)]
+pub struct TrackedResponse<T> {
+    /// The API response, available immediately.
+    pub response: T,
+    /// Generation stats for the response, delivered when ready.
+    pub generation: oneshot::Receiver<GenerationData>,
+}
+
+impl<T> TrackedResponse<T> {
+    /// Waits for the generation stats, returning `None` if they never arrive.
+    pub async fn generation_data(self) -> Option<GenerationData> {
+        self.generation.await.ok()
+    }
+}
+
+impl GenerationApi {
+    /// Spawns a background fetch of the generation stats for `id` if cost
+    /// tracking is enabled, wrapping `response` in a [`TrackedResponse`].
+    pub(crate) fn track<T>(
+        client: &Client,
+        config: &crate::client::ApiConfig,
+        id: Option<&str>,
+        response: T,
+    ) -> TrackedResponse<T> {
+        let (tx, rx) = oneshot::channel();
+
+        if let (Some(policy), Some(id)) = (config.cost_tracking.clone(), id) {
+            let api = GenerationApi {
+                client: client.clone(),
+                config: config.clone(),
+            };
+            let id = id.to_string();
+            tokio::spawn(async move {
+                if let Ok(generation) = api.get_generation_when_ready(&id, policy).await {
+                    // The receiver may already have been dropped; nothing to do then.
+                    let _ = tx.send(generation.data);
+                }
+            });
+        }
+
+        TrackedResponse {
+            response,
+            generation: rx,
+        }
+    }
+
+    /// Polls the generation endpoint until stats for `id` are available.
+    ///
+    /// A 404 response means the stats have not been recorded yet; the call is
+    /// retried with exponential backoff according to `policy`. Any other
+    /// error is returned immediately.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::TimeoutError` if the stats are still unavailable when
+    /// the policy's deadline passes.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use openrouter_api::api::generation::PollPolicy;
+    /// use openrouter_api::OpenRouterClient;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = OpenRouterClient::from_env()?;
+    ///     let generation = client
+    ///         .generation()?
+    ///         .get_generation_when_ready("gen-123456789", PollPolicy::default())
+    ///         .await?;
+    ///
+    ///     println!("Total cost: ${:.6}", generation.total_cost());
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn get_generation_when_ready(
+        &self,
+        id: &str,
+        policy: PollPolicy,
+    ) -> Result<GenerationResponse> {
+        let started = Instant::now();
+        let mut delay = policy.initial_delay;
+
+        loop {
+            match self.get_generation(id).await {
+                Err(Error::ApiError { code: 404, .. }) => {}
+                result => return result,
+            }
+
+            let remaining = policy.deadline.saturating_sub(started.elapsed());
+            if remaining.is_zero() {
+                return Err(Error::TimeoutError(format!(
+                    "Generation {id} was not ready after {:?}",
+                    policy.deadline
+                )));
+            }
+
+            tokio::time::sleep(delay.min(remaining)).await;
+            delay = policy.next_delay(delay);
+        }
     }
 }
 
@@ -183,4 +347,169 @@ mod tests {
             url.path()
         );
     }
+
+    #[test]
+    fn test_poll_policy_backoff_is_capped() {
+        let policy = PollPolicy::default()
+            .with_initial_delay(Duration::from_millis(100))
+            .with_max_delay(Duration::from_millis(300));
+
+        let d1 = policy.next_delay(policy.initial_delay);
+        let d2 = policy.next_delay(d1);
+        assert_eq!(d1, Duration::from_millis(200));
+        assert_eq!(d2, Duration::from_millis(300));
+
+        let flat = policy.clone().with_multiplier(0.5);
+        assert_eq!(
+            flat.next_delay(Duration::from_millis(100)),
+            Duration::from_millis(100)
+        );
+
+        for multiplier in [f64::INFINITY, f64::MAX, f64::NAN] {
+            let policy = policy.clone().with_multiplier(multiplier);
+            assert!(policy.next_delay(Duration::from_millis(100)) <= Duration::from_millis(300));
+        }
+        let unbounded = policy
+            .with_multiplier(f64::MAX)
+            .with_max_delay(Duration::MAX);
+        assert_eq!(unbounded.next_delay(Duration::from_secs(1)), Duration::MAX);
+    }
+
+    #[tokio::test]
+    async fn test_get_generation_when_ready_polls_until_available() {
+        use crate::tests::test_helpers::test_client_config;
+        use serde_json::json;
+        use wiremock::{matchers, Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/generation"))
+            .respond_with(ResponseTemplate::new(404).set_body_string("not found"))
+            .up_to_n_times(2)
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/generation"))
+            .and(matchers::query_param("id", "gen-1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "data": {
+                    "id": "gen-1",
+                    "total_cost": 0.002,
+                    "created_at": "2024-01-01T00:00:00Z",
+                    "model": "openai/gpt-4o",
+                    "origin": "",
+                    "usage": 0.002,
+                    "is_byok": false,
+                    "streamed": false,
+                    "cancelled": false
+                }
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let mut config = test_client_config();
+        config.base_url = url::Url::parse(&format!("{}/", mock_server.uri())).unwrap();
+        let api = GenerationApi::new(Client::new(), &config).unwrap();
+
+        let policy = PollPolicy::default().with_initial_delay(Duration::from_millis(10));
+        let generation = api
+            .get_generation_when_ready("gen-1", policy)
+            .await
+            .unwrap();
+        assert_eq!(generation.id(), "gen-1");
+    }
+
+    #[tokio::test]
+    async fn test_get_generation_when_ready_times_out() {
+        use crate::tests::test_helpers::test_client_config;
+        use wiremock::{matchers, Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+        Mock::given(matchers::method("GET"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&mock_server)
+            .await;
+
+        let mut config = test_client_config();
+        config.base_url = url::Url::parse(&format!("{}/", mock_server.uri())).unwrap();
+        let api = GenerationApi::new(Client::new(), &config).unwrap();
+
+        let policy = PollPolicy::default()
+            .with_initial_delay(Duration::from_millis(10))
+            .with_deadline(Duration::from_millis(50));
+        let result = api.get_generation_when_ready("gen-1", policy).await;
+        assert!(matches!(result, Err(Error::TimeoutError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_chat_completion_tracked_delivers_generation() {
+        use crate::api::chat::ChatApi;
+        use crate::tests::test_helpers::test_client_config;
+        use crate::types::chat::{ChatCompletionRequest, ChatRole, Message};
+        use serde_json::json;
+        use wiremock::{matchers, Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+        Mock::given(matchers::method("POST"))
+            .and(matchers::path("/chat/completions"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": "gen-7",
+                "choices": [{
+                    "message": {"role": "assistant", "content": "hi"},
+                    "finish_reason": "stop"
+                }],
+                "created": 0,
+                "model": "openai/gpt-4o",
+                "object": "chat.completion"
+            })))
+            .mount(&mock_server)
+            .await;
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/generation"))
+            .and(matchers::query_param("id", "gen-7"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "data": {
+                    "id": "gen-7",
+                    "total_cost": 0.004,
+                    "created_at": "2024-01-01T00:00:00Z",
+                    "model": "openai/gpt-4o",
+                    "origin": "",
+                    "usage": 0.004,
+                    "is_byok": false,
+                    "streamed": false,
+                    "cancelled": false
+                }
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let mut config = test_client_config();
+        config.base_url = url::Url::parse(&format!("{}/", mock_server.uri())).unwrap();
+        config.cost_tracking =
+            Some(PollPolicy::default().with_initial_delay(Duration::from_millis(10)));
+        let chat = ChatApi::new(Client::new(), &config).unwrap();
+
+        let request = ChatCompletionRequest {
+            model: "openai/gpt-4o".to_string(),
+            messages: vec![Message::text(ChatRole::User, "hello")],
+            ..Default::default()
+        };
+        let tracked = chat.chat_completion_tracked(request).await.unwrap();
+        assert_eq!(tracked.response.id, "gen-7");
+
+        let generation = tracked.generation_data().await.unwrap();
+        assert_eq!(generation.id.as_str(), "gen-7");
+        assert!((generation.total_cost - 0.004).abs() < f64::EPSILON);
+    }
+
+    #[tokio::test]
+    async fn test_tracked_response_without_cost_tracking_closes_channel() {
+        use crate::tests::test_helpers::test_client_config;
+
+        let config = test_client_config().to_api_config().unwrap();
+        let tracked = GenerationApi::track(&Client::new(), &config, Some("gen-1"), ());
+        assert!(tracked.generation_data().await.is_none());
+    }
 }
@@ -1,3 +1,5 @@
+use crate::api::core::ApiCore;
+use crate::api::options::RequestOptions;
 use crate::error::{Error, Result};
 use crate::types::guardrails::{
     BulkAssignKeysRequest, BulkAssignMembersRequest, BulkAssignResponse, BulkUnassignResponse,
@@ -5,10 +7,8 @@ use crate::types::guardrails::{
     GuardrailMemberAssignmentsResponse, GuardrailResponse, GuardrailUpdateRequest,
     GuardrailsListResponse,
 };
-use crate::utils::{retry::execute_with_retry_builder, retry::handle_response_json};
+use crate::utils::url_builder::UrlBuilder;
 use reqwest::Client;
-use serde::Serialize;
-use url::Url;
 
 const LIST_GUARDRAILS: &str = "list_guardrails";
 const CREATE_GUARDRAIL: &str = "create_guardrail";
@@ -62,7 +62,13 @@ impl GuardrailsApi {
     /// Management API key required by OpenRouter.
     pub async fn create(&self, request: &GuardrailCreateRequest) -> Result<GuardrailResponse> {
         Self::validate_create_request(request)?;
-        self.post_json("guardrails", CREATE_GUARDRAIL, request)
+        self.core()
+            .post(
+                CREATE_GUARDRAIL,
+                "guardrails",
+                request,
+                &RequestOptions::default(),
+            )
             .await
     }
 
@@ -70,8 +76,8 @@ impl GuardrailsApi {
     ///
     /// Management API key required by OpenRouter.
     pub async fn get(&self, id: &str) -> Result<GuardrailResponse> {
-        let path = Self::guardrail_path(id)?;
-        self.get_json(&path, GET_GUARDRAIL).await
+        let path = Self::guardrail_path(id, "")?;
+        self.core().get(GET_GUARDRAIL, &path, &()).await
     }
 
     /// Updates an existing guardrail by ID.
@@ -83,16 +89,16 @@ impl GuardrailsApi {
         request: &GuardrailUpdateRequest,
     ) -> Result<GuardrailResponse> {
         Self::validate_update_request(request)?;
-        let path = Self::guardrail_path(id)?;
-        self.patch_json(&path, UPDATE_GUARDRAIL, request).await
+        let path = Self::guardrail_path(id, "")?;
+        self.core().patch(UPDATE_GUARDRAIL, &path, request).await
     }
 
     /// Deletes an existing guardrail by ID.
     ///
     /// Management API key required by OpenRouter.
     pub async fn delete(&self, id: &str) -> Result<GuardrailDeleteResponse> {
-        let path = Self::guardrail_path(id)?;
-        self.delete_json(&path, DELETE_GUARDRAIL).await
+        let path = Self::guardrail_path(id, "")?;
+        self.core().delete(DELETE_GUARDRAIL, &path).await
     }
 
     /// Lists all API key assignments across the authenticated user's guardrails.
@@ -163,7 +169,7 @@ impl GuardrailsApi {
         offset: Option<u32>,
         limit: Option<u32>,
     ) -> Result<GuardrailKeyAssignmentsResponse> {
-        let path = format!("{}/assignments/keys", Self::guardrail_path(id)?);
+        let path = Self::guardrail_path(id, "/assignments/keys")?;
         self.get_paginated(&path, LIST_KEY_ASSIGNMENTS, offset, limit)
             .await
     }
@@ -177,8 +183,10 @@ impl GuardrailsApi {
         request: &BulkAssignKeysRequest,
     ) -> Result<BulkAssignResponse> {
         Self::validate_key_assignment_request(request)?;
-        let path = format!("{}/assignments/keys", Self::guardrail_path(id)?);
-        self.post_json(&path, BULK_ASSIGN_KEYS, request).await
+        let path = Self::guardrail_path(id, "/assignments/keys")?;
+        self.core()
+            .post(BULK_ASSIGN_KEYS, &path, request, &RequestOptions::default())
+            .await
     }
 
     /// Lists member assignments for a specific guardrail.
@@ -201,7 +209,7 @@ impl GuardrailsApi {
         offset: Option<u32>,
         limit: Option<u32>,
     ) -> Result<GuardrailMemberAssignmentsResponse> {
-        let path = format!("{}/assignments/members", Self::guardrail_path(id)?);
+        let path = Self::guardrail_path(id, "/assignments/members")?;
         self.get_paginated(&path, LIST_MEMBER_ASSIGNMENTS, offset, limit)
             .await
     }
@@ -215,8 +223,15 @@ impl GuardrailsApi {
         request: &BulkAssignMembersRequest,
     ) -> Result<BulkAssignResponse> {
         Self::validate_member_assignment_request(request)?;
-        let path = format!("{}/assignments/members", Self::guardrail_path(id)?);
-        self.post_json(&path, BULK_ASSIGN_MEMBERS, request).await
+        let path = Self::guardrail_path(id, "/assignments/members")?;
+        self.core()
+            .post(
+                BULK_ASSIGN_MEMBERS,
+                &path,
+                request,
+                &RequestOptions::default(),
+            )
+            .await
     }
 
     /// Unassigns multiple API keys from a guardrail.
@@ -228,8 +243,15 @@ impl GuardrailsApi {
         request: &BulkAssignKeysRequest,
     ) -> Result<BulkUnassignResponse> {
         Self::validate_key_assignment_request(request)?;
-        let path = format!("{}/assignments/keys/remove", Self::guardrail_path(id)?);
-        self.post_json(&path, BULK_UNASSIGN_KEYS, request).await
+        let path = Self::guardrail_path(id, "/assignments/keys/remove")?;
+        self.core()
+            .post(
+                BULK_UNASSIGN_KEYS,
+                &path,
+                request,
+                &RequestOptions::default(),
+            )
+            .await
     }
 
     /// Unassigns multiple organization members from a guardrail.
@@ -241,23 +263,15 @@ impl GuardrailsApi {
         request: &BulkAssignMembersRequest,
     ) -> Result<BulkUnassignResponse> {
         Self::validate_member_assignment_request(request)?;
-        let path = format!("{}/assignments/members/remove", Self::guardrail_path(id)?);
-        self.post_json(&path, BULK_UNASSIGN_MEMBERS, request).await
-    }
-
-    async fn get_json<T>(&self, path: &str, operation: &str) -> Result<T>
-    where
-        T: serde::de::DeserializeOwned,
-    {
-        let url = self.endpoint(path)?;
-        let response = execute_with_retry_builder(&self.config.retry_config, operation, || {
-            self.client
-                .get(url.clone())
-                .headers((*self.config.headers).clone())
-        })
-        .await?;
-
-        handle_response_json::<T>(response, operation).await
+        let path = Self::guardrail_path(id, "/assignments/members/remove")?;
+        self.core()
+            .post(
+                BULK_UNASSIGN_MEMBERS,
+                &path,
+                request,
+                &RequestOptions::default(),
+            )
+            .await
     }
 
     async fn get_paginated<T>(
@@ -270,87 +284,18 @@ impl GuardrailsApi {
     where
         T: serde::de::DeserializeOwned,
     {
-        let url = self.endpoint(path)?;
         let query = Self::pagination_query(offset, limit)?;
-
-        let response = execute_with_retry_builder(&self.config.retry_config, operation, || {
-            let mut request = self
-                .client
-                .get(url.clone())
-                .headers((*self.config.headers).clone());
-            if !query.is_empty() {
-                request = request.query(&query);
-            }
-            request
-        })
-        .await?;
-
-        handle_response_json::<T>(response, operation).await
+        self.core().get(operation, path, &query).await
     }
 
-    async fn post_json<B, T>(&self, path: &str, operation: &str, body: &B) -> Result<T>
-    where
-        B: Serialize + ?Sized,
-        T: serde::de::DeserializeOwned,
-    {
-        let url = self.endpoint(path)?;
-        let response = execute_with_retry_builder(&self.config.retry_config, operation, || {
-            self.client
-                .post(url.clone())
-                .headers((*self.config.headers).clone())
-                .json(body)
-        })
-        .await?;
-
-        handle_response_json::<T>(response, operation).await
-    }
-
-    async fn patch_json<B, T>(&self, path: &str, operation: &str, body: &B) -> Result<T>
-    where
-        B: Serialize + ?Sized,
-        T: serde::de::DeserializeOwned,
-    {
-        let url = self.endpoint(path)?;
-        let response = execute_with_retry_builder(&self.config.retry_config, operation, || {
-            self.client
-                .patch(url.clone())
-                .headers((*self.config.headers).clone())
-                .json(body)
-        })
-        .await?;
-
-        handle_response_json::<T>(response, operation).await
-    }
-
-    async fn delete_json<T>(&self, path: &str, operation: &str) -> Result<T>
-    where
-        T: serde::de::DeserializeOwned,
-    {
-        let url = self.endpoint(path)?;
-        let response = execute_with_retry_builder(&self.config.retry_config, operation, || {
-            self.client
-                .delete(url.clone())
-                .headers((*self.config.headers).clone())
-        })
-        .await?;
-
-        handle_response_json::<T>(response, operation).await
-    }
-
-    fn endpoint(&self, path: &str) -> Result<Url> {
-        self.config
-            .base_url
-            .join(path)
-            .map_err(|e| Error::ApiError {
-                code: 400,
-                message: format!("Invalid URL for guardrails endpoint '{path}': {e}"),
-                metadata: None,
-            })
+    fn core(&self) -> ApiCore<'_> {
+        ApiCore::new(&self.client, &self.config)
     }
 
-    fn guardrail_path(id: &str) -> Result<String> {
+    /// The path of guardrail `id`, followed by `rest`.
+    fn guardrail_path(id: &str, rest: &str) -> Result<String> {
         let id = Self::validate_guardrail_id(id)?;
-        Ok(format!("guardrails/{id}"))
+        UrlBuilder::expand(&format!("guardrails/{{id}}{rest}"), &[("id", id)])
     }
 
     fn validate_guardrail_id(id: &str) -> Result<&str> {
@@ -499,7 +444,7 @@ mod tests {
         let config = test_client_config();
         let client = Client::new();
         let api = GuardrailsApi::new(client, &config).unwrap();
-        let url = api.endpoint("guardrails").unwrap();
+        let url = api.config.url_builder().build("guardrails").unwrap();
 
         assert!(
             url.path().ends_with("/guardrails"),
@@ -1,7 +1,8 @@
-use crate::error::{Error, Result};
-use crate::types::key_info::KeyInfoResponse;
+use crate::api::core::ApiCore;
+use crate::api::options::RequestOptions;
+use crate::error::Result;
+use crate::types::key_info::{KeyInfo, KeyInfoResponse};
 use crate::utils::retry::operations::GET_KEY_INFO;
-use crate::utils::{retry::execute_with_retry_builder, retry::handle_response_json};
 use reqwest::Client;
 
 /// API endpoint for key information.
@@ -42,24 +43,45 @@ impl KeyInfoApi {
     /// }
     /// ```
     pub async fn get_key_info(&self) -> Result<KeyInfoResponse> {
-        let url = self
-            .config
-            .base_url
-            .join("auth/key")
-            .map_err(|e| Error::ApiError {
-                code: 400,
-                message: format!("Invalid URL for key info endpoint: {e}"),
-                metadata: None,
-            })?;
+        self.get_key_info_with_options(&RequestOptions::default())
+            .await
+    }
 
-        let response = execute_with_retry_builder(&self.config.retry_config, GET_KEY_INFO, || {
-            self.client
-                .get(url.clone())
-                .headers((*self.config.headers).clone())
-        })
-        .await?;
+    /// Like `get_key_info`, with per-call options.
+    pub async fn get_key_info_with_options(
+        &self,
+        options: &RequestOptions,
+    ) -> Result<KeyInfoResponse> {
+        ApiCore::new(&self.client, &self.config)
+            .get_with_options(GET_KEY_INFO, "auth/key", &(), options)
+            .await
+    }
+
+    /// Retrieves the current API key's label, usage (total and for the
+    /// current day, week and month), limit and rate limit from `GET /key`.
+    ///
+    /// ```rust,no_run
+    /// # async fn run(client: openrouter_api::OpenRouterClient<openrouter_api::Ready>)
+    /// #     -> openrouter_api::Result<()> {
+    /// let key = client.key_info()?.current_key().await?;
+    /// match key.remaining() {
+    ///     Some(remaining) => println!("{remaining:.2} credits left"),
+    ///     None => println!("No limit"),
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn current_key(&self) -> Result<KeyInfo> {
+        self.current_key_with_options(&RequestOptions::default())
+            .await
+    }
 
-        handle_response_json::<KeyInfoResponse>(response, GET_KEY_INFO).await
+    /// Like `current_key`, with per-call options.
+    pub async fn current_key_with_options(&self, options: &RequestOptions) -> Result<KeyInfo> {
+        let response: KeyInfoResponse = ApiCore::new(&self.client, &self.config)
+            .get_with_options(GET_KEY_INFO, "key", &(), options)
+            .await?;
+        Ok(response.data)
     }
 }
 
@@ -1,12 +1,15 @@
 pub mod analytics;
 pub mod chat;
 pub mod completion;
+pub(crate) mod core;
 pub mod credits;
 pub mod embeddings;
 pub mod generation;
 pub mod guardrails;
 pub mod key_info;
 pub mod models;
+pub mod mux;
+pub mod options;
 pub mod providers;
 pub mod request;
 pub mod structured;
@@ -14,14 +17,16 @@ pub mod web_search;
 
 // Re-export commonly used API types
 pub use analytics::AnalyticsApi;
-pub use chat::ChatApi;
+pub use chat::{ChatApi, ChatCompletionStream};
 pub use completion::CompletionApi;
 pub use credits::CreditsApi;
 pub use embeddings::EmbeddingsApi;
-pub use generation::GenerationApi;
+pub use generation::{GenerationApi, PollPolicy, TrackedResponse};
 pub use guardrails::GuardrailsApi;
 pub use key_info::KeyInfoApi;
 pub use models::ModelsApi;
+pub use mux::{MuxEvent, StreamMux};
+pub use options::{DefaultParams, RequestOptions};
 pub use providers::ProvidersApi;
-pub use structured::StructuredApi;
+pub use structured::{RepairAttempt, StructuredApi, StructuredOutput};
 pub use web_search::WebSearchApi;
@@ -1,7 +1,9 @@
+use crate::api::core::ApiCore;
+use crate::api::options::RequestOptions;
 use crate::error::{Error, Result};
-use crate::types::models::{ModelsRequest, ModelsResponse};
-use crate::utils::retry::operations::LIST_MODELS;
-use crate::utils::{retry::execute_with_retry_builder, retry::handle_response_json};
+use crate::types::models::{ModelEndpointsResponse, ModelsRequest, ModelsResponse};
+use crate::utils::retry::operations::{LIST_MODELS, LIST_MODEL_ENDPOINTS};
+use crate::utils::url_builder::UrlBuilder;
 use reqwest::Client;
 
 /// API endpoint for model management.
@@ -22,34 +24,70 @@ impl ModelsApi {
     }
 
     /// Lists available models, optionally filtered by capability or provider.
+    ///
+    /// Responses come from the shared catalog cache while fresh. With the
+    /// `offline` feature, the bundled snapshot is returned when the API can't
+    /// be reached.
     pub async fn list_models(&self, request: Option<ModelsRequest>) -> Result<ModelsResponse> {
-        // Build the URL.
-        let url = self
-            .config
-            .base_url
-            .join("models")
-            .map_err(|e| Error::ApiError {
-                code: 400,
-                message: format!("Invalid URL for models endpoint: {e}"),
-                metadata: None,
-            })?;
-
-        // Execute request with retry logic
-        let response = execute_with_retry_builder(&self.config.retry_config, LIST_MODELS, || {
-            let mut req_builder = self
-                .client
-                .get(url.clone())
-                .headers((*self.config.headers).clone());
-
-            if let Some(ref req) = request {
-                req_builder = req_builder.query(req);
+        let result = ApiCore::new(&self.client, &self.config)
+            .get_cached(LIST_MODELS, "models", &request)
+            .await;
+        #[cfg(feature = "offline")]
+        if let Err(error) = &result {
+            if crate::offline::is_unreachable(error) {
+                return Ok(crate::offline::models());
             }
+        }
+        result
+    }
 
-            req_builder
-        })
-        .await?;
+    /// Like `list_models`, with per-call options. The catalog cache is
+    /// bypassed, since the options may change the response.
+    pub async fn list_models_with_options(
+        &self,
+        request: Option<ModelsRequest>,
+        options: &RequestOptions,
+    ) -> Result<ModelsResponse> {
+        ApiCore::new(&self.client, &self.config)
+            .get_with_options(LIST_MODELS, "models", &request, options)
+            .await
+    }
+
+    /// Like `list_models`, always fetching the list and updating the cache.
+    pub async fn refresh_models(&self, request: Option<ModelsRequest>) -> Result<ModelsResponse> {
+        ApiCore::new(&self.client, &self.config)
+            .refresh(LIST_MODELS, "models", &request)
+            .await
+    }
+
+    /// Lists the provider endpoints serving `model` (e.g. `"openai/gpt-4o"`),
+    /// including their pricing, limits and recent uptime.
+    ///
+    /// Not cached, as uptime routing needs current stats.
+    pub async fn list_endpoints(&self, model: &str) -> Result<ModelEndpointsResponse> {
+        self.list_endpoints_with_options(model, &RequestOptions::default())
+            .await
+    }
+
+    /// Like `list_endpoints`, with per-call options.
+    pub async fn list_endpoints_with_options(
+        &self,
+        model: &str,
+        options: &RequestOptions,
+    ) -> Result<ModelEndpointsResponse> {
+        if model.split('/').count() != 2 || model.split('/').any(str::is_empty) {
+            return Err(Error::ValidationError(format!(
+                "Model ID '{model}' must have the form 'author/slug'"
+            )));
+        }
 
-        // Handle response with consistent error parsing
-        handle_response_json::<ModelsResponse>(response, LIST_MODELS).await
+        let (author, slug) = model.split_once('/').unwrap_or_default();
+        let path = UrlBuilder::expand(
+            "models/{author}/{slug}/endpoints",
+            &[("author", author), ("slug", slug)],
+        )?;
+        ApiCore::new(&self.client, &self.config)
+            .get_with_options(LIST_MODEL_ENDPOINTS, &path, &(), options)
+            .await
     }
 }
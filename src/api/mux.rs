@@ -0,0 +1,154 @@
+//! Runs several chat completion streams and merges them into one.
+
+use crate::api::chat::ChatApi;
+use crate::error::Error;
+use crate::types::chat::{ChatCompletionChunk, ChatCompletionRequest};
+use futures::stream::{self, SelectAll, Stream, StreamExt};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// An event from one of the streams of a [`StreamMux`].
+#[derive(Debug)]
+pub enum MuxEvent {
+    /// The next chunk of the stream.
+    Chunk(Box<ChatCompletionChunk>),
+    /// The stream failed. The other streams keep running.
+    Error(Error),
+    /// The stream ended; no more events follow for its ID.
+    Finished,
+}
+
+type TaggedStream<'a, K> = Pin<Box<dyn Stream<Item = (K, MuxEvent)> + Send + 'a>>;
+
+/// Merges chat completion streams into one stream of `(id, MuxEvent)`
+/// pairs, for apps that drive several generations at once.
+///
+/// Events of one stream stay in order, and each stream ends with
+/// [`MuxEvent::Finished`]. The streams are only polled when the mux is, so a
+/// slow consumer holds back the requests instead of buffering their output.
+/// The mux ends once every stream has finished; streams can be added at any
+/// time, including after that.
+///
+/// ```no_run
+/// use futures::StreamExt;
+/// use openrouter_api::api::mux::{MuxEvent, StreamMux};
+/// # async fn run(chat: &openrouter_api::api::ChatApi,
+/// #     a: openrouter_api::types::chat::ChatCompletionRequest,
+/// #     b: openrouter_api::types::chat::ChatCompletionRequest) {
+/// let mut mux = StreamMux::new();
+/// mux.add_request(chat, "left", a);
+/// mux.add_request(chat, "right", b);
+/// while let Some((id, event)) = mux.next().await {
+///     if let MuxEvent::Chunk(chunk) = event {
+///         println!("{id}: {:?}", chunk.choices.first().map(|c| &c.delta.content));
+///     }
+/// }
+/// # }
+/// ```
+pub struct StreamMux<'a, K> {
+    streams: SelectAll<TaggedStream<'a, K>>,
+}
+
+impl<K> Default for StreamMux<'_, K> {
+    fn default() -> Self {
+        Self {
+            streams: SelectAll::new(),
+        }
+    }
+}
+
+impl<K> std::fmt::Debug for StreamMux<'_, K> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StreamMux")
+            .field("streams", &self.streams.len())
+            .finish()
+    }
+}
+
+impl<'a, K: Clone + Send + 'a> StreamMux<'a, K> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a stream whose events are tagged with `id`.
+    pub fn add<S>(&mut self, id: K, chunks: S)
+    where
+        S: Stream<Item = crate::error::Result<ChatCompletionChunk>> + Send + 'a,
+    {
+        let finished = id.clone();
+        let events = chunks
+            .map(move |chunk| {
+                let event = match chunk {
+                    Ok(chunk) => MuxEvent::Chunk(Box::new(chunk)),
+                    Err(error) => MuxEvent::Error(error),
+                };
+                (id.clone(), event)
+            })
+            .chain(stream::once(async move { (finished, MuxEvent::Finished) }));
+        self.streams.push(Box::pin(events));
+    }
+
+    /// Starts streaming `request` with `chat` and adds it under `id`.
+    pub fn add_request(&mut self, chat: &'a ChatApi, id: K, request: ChatCompletionRequest) {
+        self.add(id, chat.chat_completion_stream(request));
+    }
+
+    /// Number of streams that have not finished.
+    pub fn len(&self) -> usize {
+        self.streams.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.streams.is_empty()
+    }
+}
+
+impl<K> Stream for StreamMux<'_, K> {
+    type Item = (K, MuxEvent);
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.streams.poll_next_unpin(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn chunk(text: &str) -> crate::error::Result<ChatCompletionChunk> {
+        Ok(serde_json::from_value(json!({
+            "id": "c1", "object": "chat.completion.chunk", "created": 0, "model": "m",
+            "choices": [{"index": 0, "delta": {"content": text}}]
+        }))
+        .unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_mux_tags_events_and_keeps_per_stream_order() {
+        let mut mux = StreamMux::new();
+        mux.add(1, stream::iter(vec![chunk("a1"), chunk("a2")]));
+        mux.add(
+            2,
+            stream::iter(vec![chunk("b1"), Err(Error::ConfigError("boom".into()))]),
+        );
+        assert_eq!(mux.len(), 2);
+
+        let events: Vec<_> = mux.collect().await;
+        let of = |id: u32| -> Vec<String> {
+            events
+                .iter()
+                .filter(|(event_id, _)| *event_id == id)
+                .map(|(_, event)| match event {
+                    MuxEvent::Chunk(chunk) => {
+                        chunk.choices[0].delta.content.as_ref().unwrap().to_text()
+                    }
+                    MuxEvent::Error(_) => "error".to_string(),
+                    MuxEvent::Finished => "finished".to_string(),
+                })
+                .collect()
+        };
+        assert_eq!(of(1), ["a1", "a2", "finished"]);
+        assert_eq!(of(2), ["b1", "error", "finished"]);
+    }
+}
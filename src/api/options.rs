@@ -0,0 +1,463 @@
+//! Per-call request options.
+
+use crate::client::{parse_custom_header, RetryConfig};
+use crate::error::{Error, Result};
+use crate::models::provider_preferences::ProviderPreferences;
+use crate::types::chat::ChatCompletionRequest;
+use crate::types::completion::CompletionRequest;
+use crate::types::sampling::{Temperature, TopP};
+use crate::types::truncation::TruncationConfig;
+use crate::utils::validation::ValidationMode;
+use reqwest::header::HeaderMap;
+use reqwest::RequestBuilder;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Header carrying `RequestOptions::idempotency_key`.
+pub const IDEMPOTENCY_KEY_HEADER: &str = "Idempotency-Key";
+
+/// Request values a client applies to every chat and completion request
+/// that leaves them unset.
+///
+/// Set with `OpenRouterClient::with_default_params`. Values on the request
+/// always win, as do `RequestOptions::provider_overrides` and
+/// `RequestOptions::user`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DefaultParams {
+    /// Sampling temperature.
+    pub temperature: Option<Temperature>,
+    /// Maximum number of tokens to generate.
+    pub max_tokens: Option<u32>,
+    /// Nucleus sampling threshold.
+    pub top_p: Option<TopP>,
+    /// Provider routing preferences.
+    pub provider: Option<ProviderPreferences>,
+    /// End-user identifier; the client's default user takes precedence.
+    pub user: Option<String>,
+}
+
+impl DefaultParams {
+    /// Creates empty defaults; requests are sent as they are.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the default sampling temperature.
+    pub fn with_temperature(mut self, temperature: Temperature) -> Self {
+        self.temperature = Some(temperature);
+        self
+    }
+
+    /// Sets the default maximum number of tokens to generate.
+    pub fn with_max_tokens(mut self, max_tokens: u32) -> Self {
+        self.max_tokens = Some(max_tokens);
+        self
+    }
+
+    /// Sets the default nucleus sampling threshold.
+    pub fn with_top_p(mut self, top_p: TopP) -> Self {
+        self.top_p = Some(top_p);
+        self
+    }
+
+    /// Sets the default provider routing preferences.
+    pub fn with_provider(mut self, provider: ProviderPreferences) -> Self {
+        self.provider = Some(provider);
+        self
+    }
+
+    /// Sets the default end-user identifier.
+    pub fn with_user(mut self, user: impl Into<String>) -> Self {
+        self.user = Some(user.into());
+        self
+    }
+
+    /// Fills the fields of a chat request that it leaves unset.
+    pub(crate) fn apply_to_chat(&self, request: &mut ChatCompletionRequest) {
+        if request.temperature.is_none() {
+            request.temperature = self.temperature.map(|t| t.as_f32());
+        }
+        if request.max_tokens.is_none() {
+            request.max_tokens = self.max_tokens;
+        }
+        if request.top_p.is_none() {
+            request.top_p = self.top_p.map(|p| p.as_f32());
+        }
+        if request.provider.is_none() {
+            request.provider.clone_from(&self.provider);
+        }
+        if request.user.is_none() {
+            request.user.clone_from(&self.user);
+        }
+    }
+
+    /// Fills the parameters of a text completion request that it leaves
+    /// unset.
+    pub(crate) fn apply_to_completion(&self, request: &mut CompletionRequest) -> Result<()> {
+        if *self == Self::default() {
+            return Ok(());
+        }
+        if request.extra_params.is_null() {
+            request.extra_params = serde_json::json!({});
+        }
+        let Some(params) = request.extra_params.as_object_mut() else {
+            return Ok(());
+        };
+        let defaults = [
+            // Sent as the exact f64, so 0.2 stays 0.2 on the wire.
+            ("temperature", self.temperature.map(|t| t.as_f64().into())),
+            ("max_tokens", self.max_tokens.map(serde_json::Value::from)),
+            ("top_p", self.top_p.map(|p| p.as_f64().into())),
+            ("user", self.user.clone().map(serde_json::Value::from)),
+        ];
+        for (name, value) in defaults {
+            if let Some(value) = value {
+                params.entry(name).or_insert(value);
+            }
+        }
+        if let Some(provider) = &self.provider {
+            if !params.contains_key("provider") {
+                params.insert(
+                    "provider".into(),
+                    serde_json::to_value(provider).map_err(Error::SerializationError)?,
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Options that tune a single API call without changing the client.
+///
+/// Pass them to the `*_with_options` methods of the API modules, e.g.
+/// `ChatApi::chat_completion_with_options` or
+/// `ModelsApi::list_models_with_options`. Headers, timeouts, retries and the
+/// idempotency key apply to every call; the other options only affect calls
+/// that send a chat or completion request.
+#[derive(Debug, Clone, Default)]
+pub struct RequestOptions {
+    /// Extra headers sent with this call only, added on top of (and
+    /// overriding) the client's default headers. `Authorization` and
+    /// `Content-Type` cannot be overridden.
+    pub extra_headers: Option<HashMap<String, String>>,
+    /// Deadline for this call, overriding the client's timeout.
+    ///
+    /// For regular calls it bounds each attempt and all retries together;
+    /// for streams it bounds the whole stream, from sending the request to
+    /// the last chunk.
+    pub timeout: Option<Duration>,
+    /// For streams, how long to wait for the first chunk (time to first
+    /// token). Ignored by non-streaming calls.
+    pub first_token_timeout: Option<Duration>,
+    /// Provider routing preferences for this call, replacing any set on the
+    /// request. Ignored by web search.
+    pub provider_overrides: Option<ProviderPreferences>,
+    /// Retry behaviour for this call instead of the client's `RetryConfig`.
+    pub retry_override: Option<RetryConfig>,
+    /// Sent as the `Idempotency-Key` header on every attempt of this call.
+    /// Chat and completion calls generate a random key when unset.
+    pub idempotency_key: Option<String>,
+    /// End-user identifier for this call, used instead of the client's
+    /// default user when the request doesn't set one. Ignored by web search.
+    pub user: Option<String>,
+    /// Truncation settings for this call instead of the client's.
+    pub truncation: Option<TruncationConfig>,
+    /// Validation mode for this call instead of the client's.
+    pub validation_mode: Option<ValidationMode>,
+}
+
+impl RequestOptions {
+    /// Creates empty options; calls behave exactly like their plain variants.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a header sent with this call only.
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.extra_headers
+            .get_or_insert_with(HashMap::new)
+            .insert(name.into(), value.into());
+        self
+    }
+
+    /// Sets the deadline for this call.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets how long a stream may take to deliver its first chunk.
+    pub fn with_first_token_timeout(mut self, timeout: Duration) -> Self {
+        self.first_token_timeout = Some(timeout);
+        self
+    }
+
+    /// Overrides provider routing for this call.
+    pub fn with_provider_overrides(mut self, provider: ProviderPreferences) -> Self {
+        self.provider_overrides = Some(provider);
+        self
+    }
+
+    /// Uses a different retry configuration for this call.
+    pub fn with_retry_override(mut self, retry_config: RetryConfig) -> Self {
+        self.retry_override = Some(retry_config);
+        self
+    }
+
+    /// Sets the idempotency key for this call.
+    pub fn with_idempotency_key(mut self, key: impl Into<String>) -> Self {
+        self.idempotency_key = Some(key.into());
+        self
+    }
+
+    /// Sets the end-user identifier for this call.
+    pub fn with_user(mut self, user: impl Into<String>) -> Self {
+        self.user = Some(user.into());
+        self
+    }
+
+    /// Uses different truncation settings for this call.
+    pub fn with_truncation(mut self, truncation: TruncationConfig) -> Self {
+        self.truncation = Some(truncation);
+        self
+    }
+
+    /// Validates this call with `mode` instead of the client's mode.
+    pub fn with_validation_mode(mut self, mode: ValidationMode) -> Self {
+        self.validation_mode = Some(mode);
+        self
+    }
+
+    /// The mode this call is validated with.
+    pub(crate) fn validation_mode(&self, default: ValidationMode) -> ValidationMode {
+        self.validation_mode.unwrap_or(default)
+    }
+
+    /// Applies the provider override and user to a chat request.
+    pub(crate) fn apply_to_chat(&self, request: &mut ChatCompletionRequest) {
+        if let Some(provider) = &self.provider_overrides {
+            request.provider = Some(provider.clone());
+        }
+        if request.user.is_none() {
+            request.user.clone_from(&self.user);
+        }
+    }
+
+    /// Applies the provider override and user to a text completion request.
+    pub(crate) fn apply_to_completion(&self, request: &mut CompletionRequest) -> Result<()> {
+        if self.provider_overrides.is_none() && self.user.is_none() {
+            return Ok(());
+        }
+        if request.extra_params.is_null() {
+            request.extra_params = serde_json::json!({});
+        }
+        let Some(params) = request.extra_params.as_object_mut() else {
+            return Ok(());
+        };
+        if let Some(provider) = &self.provider_overrides {
+            params.insert(
+                "provider".into(),
+                serde_json::to_value(provider).map_err(Error::SerializationError)?,
+            );
+        }
+        if let Some(user) = &self.user {
+            params
+                .entry("user")
+                .or_insert_with(|| serde_json::Value::String(user.clone()));
+        }
+        Ok(())
+    }
+
+    /// Applies the per-call timeout, if any, to a request.
+    pub(crate) fn apply_timeout(&self, builder: RequestBuilder) -> RequestBuilder {
+        match self.timeout {
+            Some(timeout) => builder.timeout(timeout),
+            None => builder,
+        }
+    }
+
+    /// Merges the per-call headers over the client's headers.
+    ///
+    /// Returns `Error::ConfigError` for invalid or reserved headers.
+    pub(crate) fn headers(&self, base: &HeaderMap) -> Result<HeaderMap> {
+        let mut headers = base.clone();
+        for (name, value) in self.extra_headers.iter().flatten() {
+            let (name, value) = parse_custom_header(name, value)?;
+            headers.insert(name, value);
+        }
+        if let Some(key) = &self.idempotency_key {
+            let (name, value) = parse_custom_header(IDEMPOTENCY_KEY_HEADER, key)?;
+            headers.insert(name, value);
+        }
+        Ok(headers)
+    }
+
+    /// Like `headers`, generating an `Idempotency-Key` when neither the
+    /// options nor the client supply one.
+    ///
+    /// The headers are built once per call, so every retry attempt carries
+    /// the same key and the server can deduplicate them.
+    pub(crate) fn idempotent_headers(&self, base: &HeaderMap) -> Result<HeaderMap> {
+        let mut headers = self.headers(base)?;
+        if !headers.contains_key(IDEMPOTENCY_KEY_HEADER) {
+            let key = uuid::Uuid::new_v4().to_string();
+            let (name, value) = parse_custom_header(IDEMPOTENCY_KEY_HEADER, &key)?;
+            headers.insert(name, value);
+        }
+        Ok(headers)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::{HeaderValue, AUTHORIZATION};
+
+    #[test]
+    fn test_headers_merge_and_override() {
+        let mut base = HeaderMap::new();
+        base.insert("x-gateway", HeaderValue::from_static("default"));
+        base.insert(AUTHORIZATION, HeaderValue::from_static("Bearer sk-x"));
+
+        let headers = RequestOptions::new()
+            .with_header("X-Gateway", "override")
+            .with_header("X-Trace-Id", "abc")
+            .headers(&base)
+            .unwrap();
+
+        assert_eq!(headers.get("x-gateway").unwrap(), "override");
+        assert_eq!(headers.get("x-trace-id").unwrap(), "abc");
+        assert_eq!(headers.get(AUTHORIZATION).unwrap(), "Bearer sk-x");
+        assert_eq!(RequestOptions::new().headers(&base).unwrap(), base);
+    }
+
+    #[test]
+    fn test_reserved_and_invalid_headers_are_rejected() {
+        let base = HeaderMap::new();
+        for (name, value) in [
+            ("Authorization", "Bearer other"),
+            ("content-type", "text/plain"),
+            ("bad header", "x"),
+            ("x-ok", "line\nbreak"),
+        ] {
+            let result = RequestOptions::new()
+                .with_header(name, value)
+                .headers(&base);
+            assert!(matches!(result, Err(Error::ConfigError(_))), "{name}");
+        }
+    }
+
+    #[test]
+    fn test_idempotency_key_header() {
+        let headers = RequestOptions::new()
+            .with_idempotency_key("req-123")
+            .headers(&HeaderMap::new())
+            .unwrap();
+        assert_eq!(headers.get(IDEMPOTENCY_KEY_HEADER).unwrap(), "req-123");
+    }
+
+    #[test]
+    fn test_idempotent_headers_generate_key_unless_supplied() {
+        let base = HeaderMap::new();
+        let options = RequestOptions::new();
+        let first = options.idempotent_headers(&base).unwrap();
+        let second = options.idempotent_headers(&base).unwrap();
+        assert!(first.contains_key(IDEMPOTENCY_KEY_HEADER));
+        assert_ne!(
+            first.get(IDEMPOTENCY_KEY_HEADER),
+            second.get(IDEMPOTENCY_KEY_HEADER)
+        );
+
+        let headers = RequestOptions::new()
+            .with_idempotency_key("req-123")
+            .idempotent_headers(&base)
+            .unwrap();
+        assert_eq!(headers.get(IDEMPOTENCY_KEY_HEADER).unwrap(), "req-123");
+
+        let headers = RequestOptions::new()
+            .with_header("idempotency-key", "from-header")
+            .idempotent_headers(&base)
+            .unwrap();
+        assert_eq!(headers.get(IDEMPOTENCY_KEY_HEADER).unwrap(), "from-header");
+    }
+
+    #[test]
+    fn test_apply_to_chat_overrides_provider_and_keeps_request_user() {
+        let options = RequestOptions::new()
+            .with_provider_overrides(ProviderPreferences::new().with_order(vec!["openai".into()]))
+            .with_user("options-user");
+
+        let mut request = ChatCompletionRequest::default();
+        options.apply_to_chat(&mut request);
+        assert_eq!(request.provider, options.provider_overrides);
+        assert_eq!(request.user.as_deref(), Some("options-user"));
+
+        let mut request = ChatCompletionRequest {
+            user: Some("request-user".into()),
+            ..Default::default()
+        };
+        options.apply_to_chat(&mut request);
+        assert_eq!(request.user.as_deref(), Some("request-user"));
+    }
+
+    #[test]
+    fn test_apply_to_completion_sets_extra_params() {
+        let options = RequestOptions::new()
+            .with_provider_overrides(ProviderPreferences::new().with_order(vec!["openai".into()]))
+            .with_user("options-user");
+        let mut request = CompletionRequest {
+            model: "openai/gpt-3.5-turbo-instruct".into(),
+            prompt: "Hello".into(),
+            extra_params: serde_json::Value::Null,
+            ..Default::default()
+        };
+        options.apply_to_completion(&mut request).unwrap();
+
+        assert_eq!(request.extra_params["user"], "options-user");
+        assert_eq!(request.extra_params["provider"]["order"][0], "openai");
+    }
+
+    #[test]
+    fn test_default_params_fill_only_unset_fields() {
+        let defaults = DefaultParams::new()
+            .with_temperature(Temperature::try_from(0.2).unwrap())
+            .with_max_tokens(256)
+            .with_provider(ProviderPreferences::new().with_order(vec!["openai".into()]))
+            .with_user("default-user");
+
+        let mut request = ChatCompletionRequest {
+            temperature: Some(0.9),
+            ..Default::default()
+        };
+        defaults.apply_to_chat(&mut request);
+        assert_eq!(request.temperature, Some(0.9));
+        assert_eq!(request.max_tokens, Some(256));
+        assert_eq!(request.top_p, None);
+        assert_eq!(request.provider, defaults.provider);
+        assert_eq!(request.user.as_deref(), Some("default-user"));
+
+        let mut request = CompletionRequest {
+            model: "openai/gpt-3.5-turbo-instruct".into(),
+            prompt: "Hello".into(),
+            extra_params: serde_json::json!({"max_tokens": 8}),
+            ..Default::default()
+        };
+        defaults.apply_to_completion(&mut request).unwrap();
+        assert_eq!(request.extra_params["max_tokens"], 8);
+        assert_eq!(request.extra_params["user"], "default-user");
+        assert_eq!(request.extra_params["provider"]["order"][0], "openai");
+        assert_eq!(request.extra_params["temperature"], 0.2);
+        assert!(request.extra_params.get("top_p").is_none());
+
+        let mut request = CompletionRequest {
+            model: "openai/gpt-3.5-turbo-instruct".into(),
+            prompt: "Hello".into(),
+            ..Default::default()
+        };
+        DefaultParams::new()
+            .with_top_p(TopP::try_from(0.9f32).unwrap())
+            .apply_to_completion(&mut request)
+            .unwrap();
+        assert_eq!(request.extra_params["top_p"], 0.9);
+    }
+}
@@ -1,36 +1,26 @@
+use crate::api::core::ApiCore;
 use crate::error::{Error, Result};
+use crate::models::provider_preferences::ProviderPreferences;
 use crate::types::{Provider, ProvidersResponse};
-use crate::utils::cache::Cache;
-use crate::utils::{
-    retry::execute_with_retry_builder, retry::handle_response_json,
-    retry::operations::GET_PROVIDERS,
-};
+use crate::utils::retry::operations::GET_PROVIDERS;
 use reqwest::Client;
-use std::sync::{Arc, Mutex};
 
 /// API client for provider-related operations
 pub struct ProvidersApi {
     pub(crate) client: Client,
     pub(crate) config: crate::client::ApiConfig,
-    pub(crate) cache: Arc<Mutex<Cache<String, ProvidersResponse>>>,
 }
 
 impl ProvidersApi {
-    /// Creates a new ProvidersApi with the given reqwest client, configuration, and shared cache.
+    /// Creates a new ProvidersApi with the given reqwest client and configuration.
     ///
-    /// The cache is shared across calls so that repeated requests hit the cache
-    /// instead of the network. Callers should retain the same `Arc<Mutex<Cache<...>>>`
-    /// instance across multiple `ProvidersApi` lifetimes.
+    /// The instance gets its own catalog cache; handles from
+    /// `OpenRouterClient::providers` share the client's.
     #[must_use = "returns an API client that should be used for API calls"]
-    pub fn new(
-        client: Client,
-        config: &crate::client::ClientConfig,
-        cache: Arc<Mutex<Cache<String, ProvidersResponse>>>,
-    ) -> Result<Self> {
+    pub fn new(client: Client, config: &crate::client::ClientConfig) -> Result<Self> {
         Ok(Self {
             client,
             config: config.to_api_config()?,
-            cache,
         })
     }
 
@@ -42,7 +32,8 @@ impl ProvidersApi {
     /// # Returns
     ///
     /// Returns a `Result` containing the `ProvidersResponse` with provider information
-    /// or an `Error` if the request fails.
+    /// or an `Error` if the request fails. With the `offline` feature, the
+    /// bundled snapshot is returned when the API can't be reached.
     ///
     /// # Examples
     ///
@@ -76,43 +67,23 @@ impl ProvidersApi {
     /// }
     /// ```
     pub async fn get_providers(&self) -> Result<ProvidersResponse> {
-        // Check cache first
-        let cache_key = "providers".to_string();
-        if let Ok(mut cache) = self.cache.lock() {
-            if let Some(cached_response) = cache.get(&cache_key) {
-                return Ok(cached_response);
+        let result = ApiCore::new(&self.client, &self.config)
+            .get_cached(GET_PROVIDERS, "providers", &())
+            .await;
+        #[cfg(feature = "offline")]
+        if let Err(error) = &result {
+            if crate::offline::is_unreachable(error) {
+                return Ok(crate::offline::providers());
             }
         }
+        result
+    }
 
-        // Build the URL for the providers endpoint
-        let url = self
-            .config
-            .base_url
-            .join("providers")
-            .map_err(|e| Error::ApiError {
-                code: 400,
-                message: format!("Invalid URL: {e}"),
-                metadata: None,
-            })?;
-
-        // Execute request with retry logic
-        let response = execute_with_retry_builder(&self.config.retry_config, GET_PROVIDERS, || {
-            self.client
-                .get(url.clone())
-                .headers((*self.config.headers).clone())
-        })
-        .await?;
-
-        // Handle response with consistent error parsing
-        let providers_response =
-            handle_response_json::<ProvidersResponse>(response, GET_PROVIDERS).await?;
-
-        // Cache the response
-        if let Ok(mut cache) = self.cache.lock() {
-            cache.insert(cache_key, providers_response.clone());
-        }
-
-        Ok(providers_response)
+    /// Like `get_providers`, always fetching the list and updating the cache.
+    pub async fn refresh_providers(&self) -> Result<ProvidersResponse> {
+        ApiCore::new(&self.client, &self.config)
+            .refresh(GET_PROVIDERS, "providers", &())
+            .await
     }
 
     /// Retrieves a specific provider by slug
@@ -407,29 +378,53 @@ impl ProvidersApi {
         let providers_response = self.get_providers().await?;
         Ok(providers_response.sorted_names())
     }
+
+    /// Checks the provider slugs in `preferences` against the providers list
+    ///
+    /// Uses the cached providers list when available. Returns
+    /// `Error::UnknownProviderSlugs` with a suggestion for each likely typo;
+    /// see `ProviderPreferences::validate_slugs`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use openrouter_api::client::OpenRouterClient;
+    /// use openrouter_api::models::provider_preferences::ProviderPreferences;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = OpenRouterClient::from_env()?;
+    ///     let preferences = ProviderPreferences::new().with_order(vec!["opnai".into()]);
+    ///
+    ///     if let Err(error) = client.providers()?.validate_preferences(&preferences).await {
+    ///         eprintln!("{error}"); // ... 'opnai' in order (did you mean 'openai'?)
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn validate_preferences(&self, preferences: &ProviderPreferences) -> Result<()> {
+        let providers_response = self.get_providers().await?;
+        preferences.validate_slugs(&providers_response)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::client::{ClientConfig, RetryConfig, SecureApiKey};
+    use crate::client::{ClientConfig, SecureApiKey};
     use crate::tests::test_helpers::test_client_config;
     use reqwest::Client;
-    use std::time::Duration;
-
-    fn default_providers_cache() -> Arc<Mutex<Cache<String, ProvidersResponse>>> {
-        Arc::new(Mutex::new(Cache::new(Duration::from_secs(300))))
-    }
 
     #[test]
     fn test_providers_api_new() {
         let config = test_client_config();
         let http_client = Client::new();
 
-        let _providers_api = ProvidersApi::new(http_client, &config, default_providers_cache());
+        let _providers_api = ProvidersApi::new(http_client, &config);
     }
 
     #[tokio::test]
+    #[cfg(not(feature = "offline"))]
     async fn test_providers_api_network_error() {
         let config = ClientConfig {
             api_key: Some(SecureApiKey::new("sk-test123456789012345678901234567890").unwrap()),
@@ -439,12 +434,11 @@ mod tests {
             http_referer: None,
             site_title: None,
             user_id: None,
-            retry_config: RetryConfig::default(),
             max_response_bytes: 10 * 1024 * 1024,
+            ..Default::default()
         };
         let http_client = Client::new();
-        let providers_api =
-            ProvidersApi::new(http_client, &config, default_providers_cache()).unwrap();
+        let providers_api = ProvidersApi::new(http_client, &config).unwrap();
 
         // Test that network errors are properly handled
         let result = providers_api.get_providers().await;
@@ -466,6 +460,7 @@ mod tests {
     }
 
     #[tokio::test]
+    #[cfg(not(feature = "offline"))]
     async fn test_provider_convenience_methods_with_empty_response() {
         let config = ClientConfig {
             api_key: Some(SecureApiKey::new("sk-test123456789012345678901234567890").unwrap()),
@@ -474,8 +469,7 @@ mod tests {
             ..test_client_config()
         };
         let http_client = Client::new();
-        let providers_api =
-            ProvidersApi::new(http_client, &config, default_providers_cache()).unwrap();
+        let providers_api = ProvidersApi::new(http_client, &config).unwrap();
 
         // All convenience methods should handle network errors gracefully
         assert!(providers_api.get_provider_by_slug("openai").await.is_err());
@@ -497,7 +491,7 @@ mod tests {
     }
 
     /// E2E test: verifies that a shared cache actually prevents duplicate network calls.
-    /// Two ProvidersApi instances sharing the same Arc<Mutex<Cache>> should only hit
+    /// Two ProvidersApi instances sharing the same ApiConfig should only hit
     /// the server once — the second call returns the cached value.
     #[tokio::test]
     async fn test_shared_cache_prevents_duplicate_network_calls() {
@@ -531,14 +525,12 @@ mod tests {
             ..test_client_config()
         };
 
-        // Shared cache
-        let cache = default_providers_cache();
-
-        let http_client_1 = Client::new();
-        let api1 = ProvidersApi::new(http_client_1, &config, cache.clone()).unwrap();
-
-        let http_client_2 = Client::new();
-        let api2 = ProvidersApi::new(http_client_2, &config, cache).unwrap();
+        let api1 = ProvidersApi::new(Client::new(), &config).unwrap();
+        // Shares the catalog cache through the config
+        let api2 = ProvidersApi {
+            client: Client::new(),
+            config: api1.config.clone(),
+        };
 
         // First call hits the server
         let result1 = api1.get_providers().await;
@@ -556,4 +548,37 @@ mod tests {
 
         // MockServer's .expect(1) will panic on drop if the mock was called more than once
     }
+
+    #[tokio::test]
+    async fn test_validate_preferences_uses_providers_list() {
+        use wiremock::{matchers, Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/providers"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": [
+                    {"name": "OpenAI", "slug": "openai"},
+                    {"name": "Together", "slug": "together"}
+                ]
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let mut config = test_client_config();
+        config.base_url = format!("{}/", mock_server.uri()).parse().unwrap();
+        let api = ProvidersApi::new(Client::new(), &config).unwrap();
+
+        let valid = ProviderPreferences::new().with_order(vec!["together".into()]);
+        assert!(api.validate_preferences(&valid).await.is_ok());
+
+        let typo = ProviderPreferences::new().with_ignore(vec!["togther".into()]);
+        match api.validate_preferences(&typo).await {
+            Err(Error::UnknownProviderSlugs(unknown)) => {
+                assert_eq!(unknown[0].suggestion.as_deref(), Some("together"));
+            }
+            other => panic!("expected unknown slugs, got {other:?}"),
+        }
+    }
 }
@@ -11,16 +11,20 @@
      This enables the model to suggest or invoke external functions via the API.
 
    - **Provider Preferences:** Clients can attach routing options using our first‑class provider preferences.
+
+   - **Assistant Prefill:** Clients can end the conversation with a partial assistant message for the
+     model to continue.
 */
 
 use crate::models::structured::JsonSchemaConfig;
 use crate::models::tool::Tool;
-use crate::types::chat::Message;
+use crate::types::chat::{ChatRole, Message};
 use serde::Serialize;
 use serde_json::Value;
 
 /// Representation for enabling structured outputs in the request payload.
 #[derive(Debug, Clone, Serialize, PartialEq)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ResponseFormatConfig {
     /// Fixed string indicating the type of response format.
@@ -55,6 +59,10 @@ pub struct RequestPayload<T: Serialize> {
     /// Additional parameters merged into the request payload.
     #[serde(flatten)]
     pub extra_params: T,
+    /// The assistant prefill ending `messages`, if any. The response only
+    /// contains the continuation; prepend this to get the full text.
+    #[serde(skip)]
+    pub assistant_prefill: Option<String>,
 }
 
 /// A unified request builder for non‑interactive endpoints.
@@ -63,6 +71,7 @@ pub struct RequestPayload<T: Serialize> {
 /// - **Structured Outputs:** By invoking `with_structured_output`, clients can enable a specific JSON Schema for responses.
 /// - **Tool Calling:** By invoking `with_tools`, clients can supply a list of callable tools.
 /// - **Provider Preferences:** By invoking `with_provider_preferences`, clients can configure provider routing.
+/// - **Assistant Prefill:** By invoking `with_assistant_prefill`, clients can start the model's reply.
 pub struct RequestBuilder<T: Serialize> {
     model: String,
     messages: Vec<Message>,
@@ -70,6 +79,7 @@ pub struct RequestBuilder<T: Serialize> {
     structured_output: Option<ResponseFormatConfig>,
     /// Optional list of tools for tool calling.
     tools: Option<Vec<Tool>>,
+    assistant_prefill: Option<String>,
     /// Whether to perform JSON Schema validation on the response.
     pub validate_structured: bool,
     /// If true, fallback to an unstructured response on validation failure.
@@ -91,6 +101,7 @@ impl<T: Serialize> RequestBuilder<T> {
             extra_params,
             structured_output: None,
             tools: None,
+            assistant_prefill: None,
             validate_structured: true,
             fallback_on_failure: false,
         }
@@ -130,6 +141,51 @@ impl<T: Serialize> RequestBuilder<T> {
         self
     }
 
+    /// Ends the conversation with a partial assistant message that the model
+    /// continues, e.g. `{` to force JSON or the start of a required format.
+    ///
+    /// Replaces any prefill set earlier. Stream consumers can restore the
+    /// full text with `ChatCompletionAccumulator::with_prefill`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::ConfigError` if the model is not known to support
+    /// prefill, or the text is empty or ends with whitespace.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use openrouter_api::api::request::RequestBuilder;
+    /// use openrouter_api::types::chat::{ChatRole, Message};
+    /// use serde_json::json;
+    ///
+    /// let messages = vec![Message::text(ChatRole::User, "List three colors as JSON.")];
+    /// let payload = RequestBuilder::new("anthropic/claude-sonnet-4", messages, json!({}))
+    ///     .with_assistant_prefill("{\"colors\": [")?
+    ///     .build();
+    /// assert_eq!(payload.messages.len(), 2);
+    /// # Ok::<(), openrouter_api::Error>(())
+    /// ```
+    pub fn with_assistant_prefill(
+        mut self,
+        text: impl Into<String>,
+    ) -> Result<Self, crate::error::Error> {
+        let text = text.into();
+        crate::utils::validation::validate_assistant_prefill(&self.model, &text)?;
+        if self.assistant_prefill.is_some() {
+            self.messages.pop();
+        }
+        self.messages
+            .push(Message::text(ChatRole::Assistant, text.clone()));
+        self.assistant_prefill = Some(text);
+        Ok(self)
+    }
+
+    /// The assistant prefill set with `with_assistant_prefill`, if any.
+    pub fn assistant_prefill(&self) -> Option<&str> {
+        self.assistant_prefill.as_deref()
+    }
+
     /// Consumes the builder and returns the complete request payload.
     ///
     /// # Returns
@@ -144,6 +200,7 @@ impl<T: Serialize> RequestBuilder<T> {
             response_format: self.structured_output,
             tools: self.tools,
             extra_params: self.extra_params,
+            assistant_prefill: self.assistant_prefill,
         }
     }
 }
@@ -202,4 +259,52 @@ impl RequestBuilder<Value> {
 
         Ok(self)
     }
+
+    /// Adds a provider-specific parameter, such as `thinking` or
+    /// `safe_prompt`, to the top level of the payload.
+    ///
+    /// Returns an error if `key` is already set, or names a field the
+    /// builder manages itself (`model`, `messages`, `response_format`,
+    /// `tools`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use openrouter_api::api::request::RequestBuilder;
+    /// use serde_json::json;
+    ///
+    /// let request = RequestBuilder::new("mistralai/mistral-large", vec![], json!({}))
+    ///     .with_extra_param("safe_prompt", true)
+    ///     .expect("Unused key")
+    ///     .build();
+    /// assert_eq!(request.extra_params["safe_prompt"], json!(true));
+    /// ```
+    #[must_use = "returns the updated builder that should be used for constructing the request"]
+    pub fn with_extra_param(
+        mut self,
+        key: impl Into<String>,
+        value: impl Into<Value>,
+    ) -> Result<Self, crate::error::Error> {
+        let key = key.into();
+        if matches!(
+            key.as_str(),
+            "model" | "messages" | "response_format" | "responseFormat" | "tools"
+        ) {
+            return Err(crate::error::Error::ConfigError(format!(
+                "Extra parameter '{key}' conflicts with a field set by the builder"
+            )));
+        }
+        let Value::Object(ref mut map) = self.extra_params else {
+            return Err(crate::error::Error::ConfigError(
+                "Extra parameters must be a JSON object".to_string(),
+            ));
+        };
+        if map.contains_key(&key) {
+            return Err(crate::error::Error::ConfigError(format!(
+                "Extra parameter '{key}' is already set"
+            )));
+        }
+        map.insert(key, value.into());
+        Ok(self)
+    }
 }
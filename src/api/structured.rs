@@ -1,21 +1,50 @@
 //! Structured output API module for handling JSON schema-based responses
 
+use crate::api::chat::ChatApi;
+use crate::api::core::ApiCore;
+use crate::api::options::RequestOptions;
 use crate::error::{Error, Result};
 use crate::models::structured::{JsonSchemaConfig, JsonSchemaDefinition};
-use crate::types::chat::{ChatCompletionRequest, ChatCompletionResponse, Message, MessageContent};
-use crate::types::status::StreamingStatus;
-use crate::utils::{
-    retry::execute_with_retry_builder, retry::handle_response_json,
-    retry::operations::STRUCTURED_GENERATE,
+use crate::types::chat::{
+    ChatCompletionRequest, ChatCompletionResponse, ChatRole, Message, MessageContent,
 };
+use crate::types::status::StreamingStatus;
+use crate::utils::partial_json::{PartialJsonParser, PartialValue};
+use crate::utils::retry::operations::STRUCTURED_GENERATE;
+use async_stream::try_stream;
+use futures::{Stream, StreamExt};
 use reqwest::Client;
 use serde::de::DeserializeOwned;
 use serde_json::Value;
+use std::pin::Pin;
+
+/// Prompt appended after an output that failed validation; `{error}` is
+/// replaced with the validation error.
+pub const REPAIR_PROMPT: &str = "Your previous response did not match the required JSON schema: \
+{error}\nReply again with only the corrected JSON.";
+
+/// An output rejected during [`StructuredApi::generate_with_history`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct RepairAttempt {
+    /// The model's raw output.
+    pub content: String,
+    /// Why it failed to parse or validate.
+    pub error: String,
+}
+
+/// A structured result together with the outputs rejected before it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StructuredOutput<T> {
+    pub value: T,
+    /// Rejected outputs, oldest first; empty if the first output was valid.
+    pub attempts: Vec<RepairAttempt>,
+}
 
 /// API endpoint for structured output generation.
 pub struct StructuredApi {
     pub(crate) client: Client,
     pub(crate) config: crate::client::ApiConfig,
+    pub(crate) repair_attempts: u32,
 }
 
 impl StructuredApi {
@@ -25,9 +54,19 @@ impl StructuredApi {
         Ok(Self {
             client,
             config: config.to_api_config()?,
+            repair_attempts: 0,
         })
     }
 
+    /// Re-prompts the model up to `attempts` times when its output fails to
+    /// parse or validate, appending the rejected output and the error (see
+    /// [`REPAIR_PROMPT`]) to the conversation. Defaults to 0.
+    #[must_use = "returns the API with repair configured"]
+    pub fn with_repair_attempts(mut self, attempts: u32) -> Self {
+        self.repair_attempts = attempts;
+        self
+    }
+
     /// Generates a structured output that conforms to the provided JSON schema.
     /// Returns the parsed response deserialized into the specified type T.
     pub async fn generate<T>(
@@ -39,8 +78,109 @@ impl StructuredApi {
     where
         T: DeserializeOwned,
     {
+        self.generate_with_options(model, messages, schema_config, &RequestOptions::default())
+            .await
+    }
+
+    /// Like `generate`, with per-call options such as extra headers, a
+    /// timeout or provider overrides.
+    pub async fn generate_with_options<T>(
+        &self,
+        model: &str,
+        messages: Vec<Message>,
+        schema_config: JsonSchemaConfig,
+        options: &RequestOptions,
+    ) -> Result<T>
+    where
+        T: DeserializeOwned,
+    {
+        self.generate_with_history(model, messages, schema_config, options)
+            .await
+            .map(|output| output.value)
+    }
+
+    /// Like `generate_with_options`, also returning the outputs rejected
+    /// along the way by the repair loop (see
+    /// [`with_repair_attempts`](Self::with_repair_attempts)).
+    ///
+    /// An `idempotency_key` in `options` is sent with the first attempt
+    /// only; repair attempt `n` sends `{key}-repair-{n}`.
+    ///
+    /// When every attempt fails, returns `Error::SchemaValidationError`
+    /// listing each attempt's error.
+    pub async fn generate_with_history<T>(
+        &self,
+        model: &str,
+        mut messages: Vec<Message>,
+        schema_config: JsonSchemaConfig,
+        options: &RequestOptions,
+    ) -> Result<StructuredOutput<T>>
+    where
+        T: DeserializeOwned,
+    {
+        let mut attempts = Vec::new();
+        loop {
+            // A repair is a new request, so it must not reuse the caller's
+            // idempotency key; the server would replay the rejected output.
+            let repair_options;
+            let attempt_options = match &options.idempotency_key {
+                Some(key) if !attempts.is_empty() => {
+                    repair_options = options
+                        .clone()
+                        .with_idempotency_key(format!("{key}-repair-{}", attempts.len()));
+                    &repair_options
+                }
+                _ => options,
+            };
+            let content = self
+                .complete(model, messages.clone(), &schema_config, attempt_options)
+                .await?;
+            let error = match self.parse_structured(&content, &schema_config) {
+                Ok(value) => return Ok(StructuredOutput { value, attempts }),
+                Err(Error::SchemaValidationError(error)) => error,
+                Err(e) => return Err(e),
+            };
+
+            attempts.push(RepairAttempt {
+                content: content.clone(),
+                error: error.clone(),
+            });
+            if attempts.len() > self.repair_attempts as usize {
+                if attempts.len() == 1 {
+                    return Err(Error::SchemaValidationError(error));
+                }
+                let errors: Vec<String> = attempts
+                    .iter()
+                    .enumerate()
+                    .map(|(i, attempt)| format!("attempt {}: {}", i + 1, attempt.error))
+                    .collect();
+                return Err(Error::SchemaValidationError(format!(
+                    "No valid output after {} attempts ({})",
+                    attempts.len(),
+                    errors.join("; ")
+                )));
+            }
+            messages.push(Message::text(ChatRole::Assistant, content));
+            messages.push(Message::text(
+                ChatRole::User,
+                REPAIR_PROMPT.replace("{error}", &error),
+            ));
+        }
+    }
+
+    /// Sends one structured output request and returns the text of the
+    /// first choice.
+    async fn complete(
+        &self,
+        model: &str,
+        messages: Vec<Message>,
+        schema_config: &JsonSchemaConfig,
+        options: &RequestOptions,
+    ) -> Result<String> {
+        let headers = options.idempotent_headers(&self.config.headers)?;
+
         // Build the request with structured output configuration
-        let request = ChatCompletionRequest {
+        let mut request = ChatCompletionRequest {
             model: model.to_string(),
             messages,
             stream: Some(StreamingStatus::NotStarted),
@@ -59,17 +199,8 @@ impl StructuredApi {
             }),
             ..Default::default()
         };
-
-        // Build the complete URL for the chat completions endpoint.
-        let url = self
-            .config
-            .base_url
-            .join("chat/completions")
-            .map_err(|e| Error::ApiError {
-                code: 400,
-                message: format!("Invalid URL: {e}"),
-                metadata: None,
-            })?;
+        options.apply_to_chat(&mut request);
+        self.config.default_params.apply_to_chat(&mut request);
 
         // Build the request body with the structured output schema
         let mut body = serde_json::to_value(&request).map_err(Error::SerializationError)?;
@@ -82,43 +213,116 @@ impl StructuredApi {
             },
         });
 
-        // Execute request with retry logic
-        let response =
-            execute_with_retry_builder(&self.config.retry_config, STRUCTURED_GENERATE, || {
-                self.client
-                    .post(url.clone())
-                    .headers((*self.config.headers).clone())
-                    .json(&body)
-            })
+        let chat_response: ChatCompletionResponse = ApiCore::new(&self.client, &self.config)
+            .post_with_headers(
+                STRUCTURED_GENERATE,
+                "chat/completions",
+                &body,
+                &headers,
+                options,
+            )
             .await?;
 
-        // Handle response with consistent error parsing
-        let chat_response: ChatCompletionResponse =
-            handle_response_json::<ChatCompletionResponse>(response, STRUCTURED_GENERATE).await?;
-
         // Extract the content from the response
         let choice = chat_response
             .choices
-            .first()
+            .into_iter()
+            .next()
             .ok_or_else(|| Error::ApiError {
                 code: 500,
                 message: "No choices returned in response".into(),
                 metadata: None,
             })?;
 
-        let content_str = match &choice.message.content {
-            MessageContent::Text(content) => content,
-            MessageContent::Parts(_) => {
-                return Err(Error::ApiError {
-                    code: 500,
-                    message: "Unexpected multimodal content in structured response".into(),
-                    metadata: None,
-                });
-            }
+        match choice.message.content {
+            MessageContent::Text(content) => Ok(content),
+            MessageContent::Parts(_) => Err(Error::ApiError {
+                code: 500,
+                message: "Unexpected multimodal content in structured response".into(),
+                metadata: None,
+            }),
+        }
+    }
+
+    /// Streams a structured output, yielding the object parsed so far each
+    /// time a chunk completes one or more of its values.
+    ///
+    /// The last item has `is_complete` set. The stream fails with
+    /// `Error::SchemaValidationError` if the output is not JSON, ends early,
+    /// or (in strict mode) misses required fields.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use futures::StreamExt;
+    /// use openrouter_api::models::structured::JsonSchemaConfig;
+    /// use openrouter_api::types::chat::{ChatRole, Message};
+    /// use openrouter_api::OpenRouterClient;
+    ///
+    /// # async fn run(schema: JsonSchemaConfig) -> openrouter_api::Result<()> {
+    /// let client = OpenRouterClient::from_env()?;
+    /// let structured = client.structured()?;
+    /// let messages = vec![Message::text(ChatRole::User, "Describe Rust as a JSON profile.")];
+    /// let mut updates = structured.generate_stream("openai/gpt-4o", messages, schema);
+    /// while let Some(update) = updates.next().await {
+    ///     let update = update?;
+    ///     println!("{} ({:?} done)", update.value, update.completed);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn generate_stream(
+        &self,
+        model: &str,
+        messages: Vec<Message>,
+        schema_config: JsonSchemaConfig,
+    ) -> Pin<Box<dyn Stream<Item = Result<PartialValue>> + Send + '_>> {
+        let request = ChatCompletionRequest {
+            model: model.to_string(),
+            messages,
+            response_format: Some(crate::api::request::ResponseFormatConfig {
+                format_type: "json_schema".to_string(),
+                json_schema: schema_config.clone(),
+            }),
+            ..Default::default()
+        };
+        let chat = ChatApi {
+            client: self.client.clone(),
+            config: self.config.clone(),
         };
 
+        Box::pin(try_stream! {
+            let mut chunks = chat.chat_completion_stream(request);
+            let mut parser = PartialJsonParser::new();
+            while let Some(chunk) = chunks.next().await {
+                let chunk = chunk?;
+                let Some(content) = chunk.choices.first().and_then(|c| c.delta.content.as_ref())
+                else {
+                    continue;
+                };
+                if let Some(update) = parser.push(&content.to_text())? {
+                    yield update;
+                }
+            }
+
+            let value = parser.finish()?;
+            if schema_config.strict {
+                let schema = serde_json::to_value(&schema_config.schema)
+                    .map_err(Error::SerializationError)?;
+                basic_schema_validation(&schema, &value)?;
+            }
+        })
+    }
+
+    /// Parses a structured output and, in strict mode, validates it against the
+    /// schema before deserializing it into `T`.
+    fn parse_structured<T: DeserializeOwned>(
+        &self,
+        content: &str,
+        schema_config: &JsonSchemaConfig,
+    ) -> Result<T> {
         // Parse the content as JSON
-        let json_result: Value = serde_json::from_str(content_str).map_err(|e| {
+        let json_result: Value = serde_json::from_str(content).map_err(|e| {
             Error::SchemaValidationError(format!("Failed to parse response as JSON: {}", e))
         })?;
 
@@ -141,72 +345,79 @@ impl StructuredApi {
 
     /// Simple schema validation for required fields and top-level type checking
     fn basic_schema_validation(&self, schema: &Value, data: &Value) -> Result<()> {
-        // Check if schema is an object and extract it in one operation
-        let schema_obj = match schema.as_object() {
-            Some(obj) => obj,
-            None => {
-                return Err(Error::SchemaValidationError(
-                    "Schema must be an object".into(),
-                ));
-            }
-        };
+        basic_schema_validation(schema, data)
+    }
+}
 
-        // Check type
-        if let Some(type_val) = schema_obj.get("type") {
-            if let Some(type_str) = type_val.as_str() {
-                match type_str {
-                    "object" if !data.is_object() => {
-                        return Err(Error::SchemaValidationError(
-                            "Expected an object but received a different type".into(),
-                        ));
-                    }
-                    "array" if !data.is_array() => {
-                        return Err(Error::SchemaValidationError(
-                            "Expected an array but received a different type".into(),
-                        ));
-                    }
-                    "string" if !data.is_string() => {
-                        return Err(Error::SchemaValidationError(
-                            "Expected a string but received a different type".into(),
-                        ));
-                    }
-                    "number" | "integer" if !data.is_number() => {
-                        return Err(Error::SchemaValidationError(
-                            "Expected a number but received a different type".into(),
-                        ));
-                    }
-                    "boolean" if !data.is_boolean() => {
-                        return Err(Error::SchemaValidationError(
-                            "Expected a boolean but received a different type".into(),
-                        ));
-                    }
-                    _ => {}
+/// Simple schema validation for required fields and top-level type checking.
+///
+/// Shared with the JSON-schema scorer in `evals`.
+pub(crate) fn basic_schema_validation(schema: &Value, data: &Value) -> Result<()> {
+    // Check if schema is an object and extract it in one operation
+    let schema_obj = match schema.as_object() {
+        Some(obj) => obj,
+        None => {
+            return Err(Error::SchemaValidationError(
+                "Schema must be an object".into(),
+            ));
+        }
+    };
+
+    // Check type
+    if let Some(type_val) = schema_obj.get("type") {
+        if let Some(type_str) = type_val.as_str() {
+            match type_str {
+                "object" if !data.is_object() => {
+                    return Err(Error::SchemaValidationError(
+                        "Expected an object but received a different type".into(),
+                    ));
+                }
+                "array" if !data.is_array() => {
+                    return Err(Error::SchemaValidationError(
+                        "Expected an array but received a different type".into(),
+                    ));
+                }
+                "string" if !data.is_string() => {
+                    return Err(Error::SchemaValidationError(
+                        "Expected a string but received a different type".into(),
+                    ));
+                }
+                "number" | "integer" if !data.is_number() => {
+                    return Err(Error::SchemaValidationError(
+                        "Expected a number but received a different type".into(),
+                    ));
                 }
+                "boolean" if !data.is_boolean() => {
+                    return Err(Error::SchemaValidationError(
+                        "Expected a boolean but received a different type".into(),
+                    ));
+                }
+                _ => {}
             }
         }
+    }
 
-        // Check required fields
-        if let Some(required) = schema_obj.get("required") {
-            if let Some(required_arr) = required.as_array() {
-                let data_obj = match data.as_object() {
-                    Some(obj) => obj,
-                    None => return Ok(()), // Skip if not an object
-                };
-
-                for field in required_arr {
-                    if let Some(field_str) = field.as_str() {
-                        if !data_obj.contains_key(field_str) {
-                            return Err(Error::SchemaValidationError(format!(
-                                "Required field '{field_str}' is missing"
-                            )));
-                        }
+    // Check required fields
+    if let Some(required) = schema_obj.get("required") {
+        if let Some(required_arr) = required.as_array() {
+            let data_obj = match data.as_object() {
+                Some(obj) => obj,
+                None => return Ok(()), // Skip if not an object
+            };
+
+            for field in required_arr {
+                if let Some(field_str) = field.as_str() {
+                    if !data_obj.contains_key(field_str) {
+                        return Err(Error::SchemaValidationError(format!(
+                            "Required field '{field_str}' is missing"
+                        )));
                     }
                 }
             }
         }
-
-        Ok(())
     }
+
+    Ok(())
 }
 
 #[cfg(test)]
@@ -447,4 +658,241 @@ mod tests {
             _ => panic!("Expected SchemaValidationError"),
         }
     }
+
+    #[tokio::test]
+    async fn test_generate_stream_yields_partial_objects() {
+        use futures::StreamExt;
+        use wiremock::{matchers, Mock, MockServer, ResponseTemplate};
+
+        let fragments = [
+            r#"{"title": "Ru"#,
+            r#"st", "year": 20"#,
+            r#"15, "tags": ["fast""#,
+            r#", "safe"]}"#,
+        ];
+        let sse_body: String = fragments
+            .iter()
+            .map(|fragment| {
+                let chunk = json!({
+                    "id": "gen-1",
+                    "object": "chat.completion.chunk",
+                    "created": 0,
+                    "model": "openai/gpt-4o",
+                    "choices": [{"index": 0, "delta": {"content": fragment}}]
+                });
+                format!("data: {chunk}\n\n")
+            })
+            .chain(std::iter::once("data: [DONE]\n\n".to_string()))
+            .collect();
+
+        let server = MockServer::start().await;
+        Mock::given(matchers::method("POST"))
+            .and(matchers::path("/chat/completions"))
+            .and(matchers::body_partial_json(json!({
+                "stream": true,
+                "response_format": {"type": "json_schema", "json_schema": {"name": "language"}}
+            })))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-type", "text/event-stream")
+                    .set_body_string(sse_body),
+            )
+            .mount(&server)
+            .await;
+
+        let mut config = crate::tests::test_helpers::test_client_config();
+        config.base_url = format!("{}/", server.uri()).parse().unwrap();
+        let api = StructuredApi::new(reqwest::Client::new(), &config).unwrap();
+        let schema = JsonSchemaConfig {
+            name: "language".into(),
+            strict: true,
+            schema: JsonSchemaDefinition {
+                schema_type: "object".into(),
+                properties: serde_json::Map::new(),
+                required: Some(vec!["title".into(), "tags".into()]),
+                additional_properties: None,
+            },
+        };
+
+        let updates: Vec<_> = api
+            .generate_stream(
+                "openai/gpt-4o",
+                vec![Message::text(
+                    crate::types::chat::ChatRole::User,
+                    "Describe Rust as JSON.",
+                )],
+                schema,
+            )
+            .collect()
+            .await;
+        let updates: Vec<PartialValue> = updates.into_iter().map(Result::unwrap).collect();
+
+        let values: Vec<_> = updates.iter().map(|u| u.value.clone()).collect();
+        assert_eq!(
+            values,
+            [
+                json!({"title": "Rust"}),
+                json!({"title": "Rust", "year": 2015, "tags": ["fast"]}),
+                json!({"title": "Rust", "year": 2015, "tags": ["fast", "safe"]}),
+            ]
+        );
+        assert_eq!(updates[2].completed, ["/tags/1", "/tags", ""]);
+        assert!(updates[2].is_complete);
+    }
+
+    fn completion(content: &str) -> Value {
+        json!({
+            "id": "gen-1",
+            "object": "chat.completion",
+            "created": 0,
+            "model": "openai/gpt-4o",
+            "choices": [{
+                "index": 0,
+                "message": {"role": "assistant", "content": content},
+                "finish_reason": "stop"
+            }]
+        })
+    }
+
+    #[tokio::test]
+    async fn test_generate_with_history_repairs_invalid_output() {
+        use wiremock::{matchers, Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(matchers::method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(completion(r#"{"year": 2015}"#)))
+            .up_to_n_times(1)
+            .with_priority(1)
+            .mount(&server)
+            .await;
+        Mock::given(matchers::method("POST"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(completion(r#"{"title": "Rust"}"#)),
+            )
+            .mount(&server)
+            .await;
+
+        let mut config = crate::tests::test_helpers::test_client_config();
+        config.base_url = format!("{}/", server.uri()).parse().unwrap();
+        let schema = JsonSchemaConfig {
+            name: "language".into(),
+            strict: true,
+            schema: JsonSchemaDefinition {
+                schema_type: "object".into(),
+                properties: serde_json::Map::new(),
+                required: Some(vec!["title".into()]),
+                additional_properties: None,
+            },
+        };
+        let messages = vec![Message::text(ChatRole::User, "Describe Rust as JSON.")];
+
+        let api = StructuredApi::new(reqwest::Client::new(), &config)
+            .unwrap()
+            .with_repair_attempts(1);
+        let output: StructuredOutput<Value> = api
+            .generate_with_history(
+                "openai/gpt-4o",
+                messages.clone(),
+                schema.clone(),
+                &RequestOptions::default(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(output.value, json!({"title": "Rust"}));
+        assert_eq!(output.attempts.len(), 1);
+        assert_eq!(output.attempts[0].content, r#"{"year": 2015}"#);
+        assert!(output.attempts[0].error.contains("'title' is missing"));
+
+        // The retry carries the rejected output and the error.
+        let requests = server.received_requests().await.unwrap();
+        let retry: Value = requests[1].body_json().unwrap();
+        let retry_messages = retry["messages"].as_array().unwrap();
+        assert_eq!(retry_messages.len(), 3);
+        assert_eq!(retry_messages[1]["content"], r#"{"year": 2015}"#);
+        assert!(retry_messages[2]["content"]
+            .as_str()
+            .unwrap()
+            .contains("'title' is missing"));
+
+        // Without repair, the first invalid output fails.
+        server.reset().await;
+        Mock::given(matchers::method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(completion("not json")))
+            .mount(&server)
+            .await;
+        let api = StructuredApi::new(reqwest::Client::new(), &config).unwrap();
+        let result: Result<Value> = api
+            .generate("openai/gpt-4o", messages.clone(), schema.clone())
+            .await;
+        assert!(matches!(result, Err(Error::SchemaValidationError(_))));
+        assert_eq!(server.received_requests().await.unwrap().len(), 1);
+
+        let api = api.with_repair_attempts(2);
+        match api
+            .generate::<Value>("openai/gpt-4o", messages, schema)
+            .await
+        {
+            Err(Error::SchemaValidationError(message)) => {
+                assert!(message.contains("after 3 attempts"), "{message}");
+            }
+            other => panic!("expected SchemaValidationError, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_repair_attempts_use_distinct_idempotency_keys() {
+        use wiremock::{matchers, Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(matchers::method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(completion("not json")))
+            .up_to_n_times(2)
+            .with_priority(1)
+            .mount(&server)
+            .await;
+        Mock::given(matchers::method("POST"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(completion(r#"{"title": "Rust"}"#)),
+            )
+            .mount(&server)
+            .await;
+
+        let mut config = crate::tests::test_helpers::test_client_config();
+        config.base_url = format!("{}/", server.uri()).parse().unwrap();
+        let schema = JsonSchemaConfig {
+            name: "language".into(),
+            strict: true,
+            schema: JsonSchemaDefinition {
+                schema_type: "object".into(),
+                properties: serde_json::Map::new(),
+                required: Some(vec!["title".into()]),
+                additional_properties: None,
+            },
+        };
+        let messages = vec![Message::text(ChatRole::User, "Describe Rust as JSON.")];
+
+        let api = StructuredApi::new(reqwest::Client::new(), &config)
+            .unwrap()
+            .with_repair_attempts(2);
+        let options = RequestOptions::new().with_idempotency_key("job-42");
+        let output: StructuredOutput<Value> = api
+            .generate_with_history("openai/gpt-4o", messages, schema, &options)
+            .await
+            .unwrap();
+        assert_eq!(output.attempts.len(), 2);
+
+        let keys: Vec<_> = server
+            .received_requests()
+            .await
+            .unwrap()
+            .iter()
+            .map(|request| {
+                request.headers["idempotency-key"]
+                    .to_str()
+                    .unwrap()
+                    .to_string()
+            })
+            .collect();
+        assert_eq!(keys, ["job-42", "job-42-repair-1", "job-42-repair-2"]);
+    }
 }
@@ -1,14 +1,16 @@
 /// Web Search API implementation
 use crate::{
-    error::{Error, Result},
-    types::web_search::{WebSearchRequest, WebSearchResponse},
+    api::core::ApiCore,
+    api::options::RequestOptions,
+    error::Result,
+    types::web_search::{WebSearchRequest, WebSearchResponse, WebSearchResult},
     utils::retry::operations::WEB_SEARCH,
-    utils::{
-        retry::execute_with_retry_builder, retry::handle_response_json,
-        validation::validate_web_search_request,
-    },
+    utils::validation::validate_web_search_request,
 };
+use async_stream::try_stream;
+use futures::stream::Stream;
 use reqwest::Client;
+use std::pin::Pin;
 
 /// API endpoint for web search integration.
 pub struct WebSearchApi {
@@ -28,35 +30,190 @@ impl WebSearchApi {
 
     /// Performs a web search with the given request and returns a structured response.
     pub async fn search(&self, request: WebSearchRequest) -> Result<WebSearchResponse> {
+        self.search_with_options(request, &RequestOptions::default())
+            .await
+    }
+
+    /// Performs a web search with per-call options such as extra headers or
+    /// a timeout.
+    pub async fn search_with_options(
+        &self,
+        request: WebSearchRequest,
+        options: &RequestOptions,
+    ) -> Result<WebSearchResponse> {
         // Validate the request using the validation module
-        validate_web_search_request(&request)?;
-
-        // Join the base URL with the relative path "web/search".
-        let url = self
-            .config
-            .base_url
-            .join("web/search")
-            .map_err(|e| Error::ApiError {
-                code: 400,
-                message: format!("Invalid URL for web search: {e}"),
-                metadata: None,
+        options
+            .validation_mode(self.config.validation_mode)
+            .apply_with(WEB_SEARCH, self.config.diagnostics(), || {
+                validate_web_search_request(&request)
             })?;
 
-        // Execute request with retry logic
-        let response = execute_with_retry_builder(&self.config.retry_config, WEB_SEARCH, || {
-            self.client
-                .post(url.clone())
-                .headers((*self.config.headers).clone())
-                .json(&request)
-        })
-        .await?;
+        ApiCore::new(&self.client, &self.config)
+            .post(WEB_SEARCH, "web/search", &request, options)
+            .await
+    }
+
+    /// Returns a stream of results across all pages of a search.
+    ///
+    /// Pages are fetched lazily as the stream is polled, following each
+    /// response's `next_cursor`; the request's `cursor` (if any) is the
+    /// starting point and `num_results` the page size. Pagination stops when
+    /// the server returns no cursor or an empty page.
+    ///
+    /// To handle pages yourself, call [`search`](Self::search) and pass the
+    /// response's `next_cursor` to `WebSearchRequest::with_cursor`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use futures::{StreamExt, TryStreamExt};
+    /// use openrouter_api::types::web_search::WebSearchRequest;
+    /// use openrouter_api::OpenRouterClient;
+    ///
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = OpenRouterClient::from_env()?;
+    /// let web_search = client.web_search()?;
+    ///
+    /// let request = WebSearchRequest::new("rust async runtimes").with_num_results(20);
+    /// let first_fifty: Vec<_> = web_search
+    ///     .search_paged(request)
+    ///     .take(50)
+    ///     .try_collect()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use = "returns a stream that should be consumed to receive search results"]
+    pub fn search_paged(
+        &self,
+        request: WebSearchRequest,
+    ) -> Pin<Box<dyn Stream<Item = Result<WebSearchResult>> + Send + '_>> {
+        let stream = try_stream! {
+            let mut request = request;
+            loop {
+                let page = self.search(request.clone()).await?;
+                let received = page.results.len();
+                for result in page.results {
+                    yield result;
+                }
 
-        // Handle response with consistent error parsing
-        let search_response: WebSearchResponse =
-            handle_response_json::<WebSearchResponse>(response, WEB_SEARCH).await?;
-        Ok(search_response)
+                match page.next_cursor {
+                    Some(cursor) if received > 0 && request.cursor.as_ref() != Some(&cursor) => {
+                        request.cursor = Some(cursor);
+                    }
+                    _ => break,
+                }
+            }
+        };
+
+        Box::pin(stream)
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Error;
+    use crate::tests::test_helpers::test_client_config;
+    use crate::types::web_search::SafeSearch;
+    use serde_json::json;
+    use wiremock::{matchers, Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn test_search_sends_filter_options() {
+        let server = MockServer::start().await;
+        Mock::given(matchers::method("POST"))
+            .and(matchers::path("/web/search"))
+            .and(matchers::body_json(json!({
+                "query": "rust async runtimes",
+                "include_domains": ["docs.rs"],
+                "exclude_domains": ["example.com"],
+                "start_date": "2024-01-01",
+                "end_date": "2024-12-31",
+                "country": "US",
+                "language": "en",
+                "safe_search": "strict"
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "query": "rust async runtimes",
+                "results": [{"title": "tokio", "url": "https://docs.rs/tokio"}],
+                "total_results": 1
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let mut config = test_client_config();
+        config.base_url = format!("{}/", server.uri()).parse().unwrap();
+        let api = WebSearchApi::new(Client::new(), &config).unwrap();
+        let request = WebSearchRequest::new("rust async runtimes")
+            .with_included_domains(["docs.rs"])
+            .with_excluded_domains(["example.com"])
+            .with_date_range("2024-01-01", "2024-12-31")
+            .with_country("US")
+            .with_language("en")
+            .with_safe_search(SafeSearch::Strict);
 
-    // Note: The handle_response method has been replaced by the centralized
-    // handle_response_json utility in utils::retry for consistency across all endpoints.
+        let response = api.search(request).await.unwrap();
+        assert_eq!(response.results[0].url, "https://docs.rs/tokio");
+    }
+
+    #[tokio::test]
+    async fn test_search_paged_follows_cursors() {
+        use futures::TryStreamExt;
+
+        let server = MockServer::start().await;
+        for (cursor, titles, next) in [
+            (None, vec!["a", "b"], Some("page-2")),
+            (Some("page-2"), vec!["c", "d"], Some("page-3")),
+            (Some("page-3"), vec!["e"], None),
+        ] {
+            let results: Vec<_> = titles
+                .iter()
+                .map(|title| json!({"title": title, "url": format!("https://{title}.example.com")}))
+                .collect();
+            let mut body = json!({"query": "rust", "num_results": 2});
+            if let Some(cursor) = cursor {
+                body["cursor"] = json!(cursor);
+            }
+            Mock::given(matchers::method("POST"))
+                .and(matchers::body_json(body))
+                .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                    "query": "rust",
+                    "results": results,
+                    "total_results": 5,
+                    "next_cursor": next
+                })))
+                .expect(1)
+                .mount(&server)
+                .await;
+        }
+
+        let mut config = test_client_config();
+        config.base_url = format!("{}/", server.uri()).parse().unwrap();
+        let api = WebSearchApi::new(Client::new(), &config).unwrap();
+
+        let results: Vec<WebSearchResult> = api
+            .search_paged(WebSearchRequest::new("rust").with_num_results(2))
+            .try_collect()
+            .await
+            .unwrap();
+        let titles: Vec<_> = results.iter().map(|r| r.title.as_str()).collect();
+        assert_eq!(titles, ["a", "b", "c", "d", "e"]);
+    }
+
+    #[tokio::test]
+    async fn test_search_rejects_invalid_options_before_sending() {
+        let server = MockServer::start().await;
+        let mut config = test_client_config();
+        config.base_url = format!("{}/", server.uri()).parse().unwrap();
+        let api = WebSearchApi::new(Client::new(), &config).unwrap();
+
+        let request = WebSearchRequest::new("rust").with_country("USA");
+        assert!(matches!(
+            api.search(request).await,
+            Err(Error::ConfigError(_))
+        ));
+        assert!(server.received_requests().await.unwrap().is_empty());
+    }
 }
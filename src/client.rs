@@ -1,15 +1,40 @@
 // openrouter_api/src/client.rs
 
+use crate::api::generation::PollPolicy;
+use crate::api::options::DefaultParams;
 use crate::error::{Error, Result};
 
 /// Note: These imports are used to implement the client builder pattern.
-use crate::types::routing::{PredefinedModelCoverageProfile, RouterConfig};
+use crate::types::routing::{
+    ModelCandidate, ModelSelection, ModelSelector, PredefinedModelCoverageProfile, ProviderHealth,
+    RouterConfig, UptimeRouting,
+};
+use crate::types::truncation::TruncationConfig;
+use crate::utils::auth::ApiKeySource;
+use crate::utils::budget::BudgetGuard;
+use crate::utils::cache::CatalogCache;
+use crate::utils::diagnostics::{default_diagnostics, Diagnostic, DiagnosticsSink};
+use crate::utils::metrics::MetricsRecorder;
+use crate::utils::validation::{ValidationMode, ValidationPolicy};
 use std::marker::PhantomData;
+use std::sync::Arc;
 use std::time::Duration;
 use url::Url;
 
+pub mod compression;
 pub mod config;
+pub mod failover;
+pub mod keys;
+pub mod shutdown;
+pub mod tls;
+pub use compression::{CompressionConfig, CompressionStats, ContentEncoding};
 pub use config::*;
+pub use failover::{BaseUrlFailover, FailoverConfig};
+pub use keys::*;
+pub use shutdown::InFlight;
+#[cfg(feature = "cert-pinning")]
+pub use tls::Sha256Pin;
+pub use tls::TlsVersion;
 
 /// Routing shortcut for high-throughput.
 pub const ROUTING_NITRO: &str = ":nitro";
@@ -27,21 +52,55 @@ pub struct NoAuth;
 pub struct Ready;
 
 /// Main OpenRouter client using a type‑state builder pattern.
+///
+/// A ready client is a handle to shared state: cloning it is cheap, and
+/// clones share the HTTP connection pool, the providers cache and the
+/// routing state, so one client can be handed to many tasks.
 #[derive(Debug)]
 pub struct OpenRouterClient<State = Unconfigured> {
+    pub(crate) inner: Arc<ClientInner>,
+    pub(crate) _state: PhantomData<State>,
+}
+
+/// State shared by all clones of a client.
+#[derive(Debug)]
+pub(crate) struct ClientInner {
     pub(crate) config: ClientConfig,
     pub(crate) http_client: Option<reqwest::Client>,
-    pub(crate) _state: PhantomData<State>,
     pub(crate) router_config: Option<RouterConfig>,
     pub(crate) cached_api_config: Option<ApiConfig>,
-    /// Shared providers cache persisted across `.providers()` calls
-    pub(crate) providers_cache: Option<
-        std::sync::Arc<
-            std::sync::Mutex<
-                crate::utils::cache::Cache<String, crate::types::providers::ProvidersResponse>,
-            >,
-        >,
-    >,
+    /// Models and providers responses, shared by every API handle.
+    pub(crate) catalog_cache: CatalogCache,
+    /// Candidate data and ranking from the router's `ModelSelector`.
+    pub(crate) model_selection: Arc<std::sync::Mutex<ModelSelection>>,
+    /// Endpoint uptime tracked for `UptimeRouting`.
+    pub(crate) provider_health: Arc<std::sync::Mutex<ProviderHealth>>,
+}
+
+impl Clone for OpenRouterClient<Ready> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: Arc::clone(&self.inner),
+            _state: PhantomData,
+        }
+    }
+}
+
+impl<State> OpenRouterClient<State> {
+    /// Mutable access to the state while building.
+    ///
+    /// Only ready clients can be cloned, so a builder always holds the only
+    /// reference.
+    fn inner_mut(&mut self) -> &mut ClientInner {
+        Arc::get_mut(&mut self.inner).expect("client builders are never shared")
+    }
+
+    fn into_state<Next>(self) -> OpenRouterClient<Next> {
+        OpenRouterClient {
+            inner: self.inner,
+            _state: PhantomData,
+        }
+    }
 }
 
 impl Default for OpenRouterClient<Unconfigured> {
@@ -53,7 +112,8 @@ impl Default for OpenRouterClient<Unconfigured> {
 // Convenience constructors and methods
 impl OpenRouterClient<Ready> {
     /// Creates a ready-to-use client from environment variables.
-    /// Looks for OPENROUTER_API_KEY or OR_API_KEY in the environment.
+    /// Looks for OPENROUTER_API_KEY or OR_API_KEY in the environment, then the
+    /// file in OPENROUTER_API_KEY_FILE (see `ApiKeySource::default_chain`).
     ///
     /// # Examples
     /// ```rust,no_run
@@ -65,7 +125,15 @@ impl OpenRouterClient<Ready> {
     /// ```
     #[must_use = "returns a configured client that should be used for API calls"]
     pub fn from_env() -> Result<Self> {
-        let api_key = crate::utils::auth::load_api_key_from_env()?;
+        Self::from_key_sources(&ApiKeySource::default_chain())
+    }
+
+    /// Creates a client using the first configured key source.
+    ///
+    /// See `ApiKeySource` for how sources are tried.
+    #[must_use = "returns a configured client that should be used for API calls"]
+    pub fn from_key_sources(sources: &[ApiKeySource]) -> Result<Self> {
+        let api_key = ApiKeySource::load_first(sources)?;
         OpenRouterClient::from_api_key(api_key)
     }
 
@@ -88,7 +156,7 @@ impl OpenRouterClient<Ready> {
         referer: Option<impl Into<String>>,
         title: Option<impl Into<String>>,
     ) -> Result<Self> {
-        let api_key = crate::utils::auth::load_api_key_from_env()?;
+        let api_key = ApiKeySource::load_first(&ApiKeySource::default_chain())?;
         OpenRouterClient::new()
             .skip_url_configuration()
             .configure(api_key, referer, title)
@@ -123,23 +191,44 @@ impl OpenRouterClient<Unconfigured> {
     #[must_use = "returns a client builder that should be configured and used for API calls"]
     pub fn new() -> Self {
         Self {
-            config: ClientConfig {
-                api_key: None,
-                // Default base URL; can be overridden with with_base_url().
-                base_url: "https://openrouter.ai/api/v1/".parse().unwrap(),
-                http_referer: None,
-                site_title: None,
-                user_id: None,
-                timeout: Duration::from_secs(30),
-                retry_config: RetryConfig::default(),
-                // Default to 10MB limit
-                max_response_bytes: 10 * 1024 * 1024,
-            },
-            http_client: None,
+            inner: Arc::new(ClientInner {
+                config: ClientConfig {
+                    api_key: None,
+                    // Default base URL; can be overridden with with_base_url().
+                    base_url: "https://openrouter.ai/api/v1/".parse().unwrap(),
+                    fallback_base_urls: Vec::new(),
+                    failover: FailoverConfig::default(),
+                    http_referer: None,
+                    site_title: None,
+                    user_id: None,
+                    default_user: None,
+                    default_params: Default::default(),
+                    timeout: Duration::from_secs(30),
+                    retry_config: RetryConfig::default(),
+                    // Default to 10MB limit
+                    max_response_bytes: 10 * 1024 * 1024,
+                    budget_guard: None,
+                    cost_tracking: None,
+                    connection: ConnectionConfig::default(),
+                    compression: CompressionConfig::default(),
+                    default_headers: reqwest::header::HeaderMap::new(),
+                    key_provider: None,
+                    metrics_recorder: None,
+                    diagnostics: None,
+                    truncation: None,
+                    message_normalization: false,
+                    strip_reasoning: false,
+                    validation_policy: Default::default(),
+                    validation_mode: Default::default(),
+                },
+                http_client: None,
+                router_config: None,
+                cached_api_config: None,
+                catalog_cache: CatalogCache::default(),
+                model_selection: Arc::default(),
+                provider_health: Arc::default(),
+            }),
             _state: PhantomData,
-            router_config: None,
-            cached_api_config: None,
-            providers_cache: None,
         }
     }
 
@@ -184,31 +273,73 @@ impl OpenRouterClient<Unconfigured> {
         base_url: impl Into<String>,
     ) -> Result<OpenRouterClient<NoAuth>> {
         let url_str = base_url.into();
-        self.config.base_url = Url::parse(&url_str).map_err(|e| {
+        self.inner_mut().config.base_url = Url::parse(&url_str).map_err(|e| {
             Error::ConfigError(format!(
                 "Invalid base URL '{url_str}': {e}. Expected format: 'https://api.example.com/v1/'"
             ))
         })?;
-        crate::utils::https::enforce_https(&self.config.base_url)?;
+        crate::utils::https::enforce_https(&self.inner.config.base_url)?;
         Ok(self.transition_to_no_auth())
     }
 
+    /// Sets a primary base URL followed by backups, in order of preference,
+    /// and transitions to the NoAuth state.
+    ///
+    /// Requests go to the primary until it fails repeatedly (connection
+    /// errors, timeouts or 5xx statuses), then to the first healthy backup;
+    /// the primary is health-checked and used again once it recovers. See
+    /// `with_failover_config` and `client::failover`.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use openrouter_api::OpenRouterClient;
+    ///
+    /// let client = OpenRouterClient::new().with_base_urls([
+    ///     "https://openrouter.ai/api/v1/",
+    ///     "https://gateway.example.com/openrouter/v1/",
+    /// ])?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use = "returns the updated client that should be used for API calls"]
+    pub fn with_base_urls<I>(self, base_urls: I) -> Result<OpenRouterClient<NoAuth>>
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        let mut base_urls = base_urls.into_iter().map(|url| {
+            let url_str = url.into();
+            let url = Url::parse(&url_str).map_err(|e| {
+                Error::ConfigError(format!(
+                    "Invalid base URL '{url_str}': {e}. Expected format: 'https://api.example.com/v1/'"
+                ))
+            })?;
+            crate::utils::https::enforce_https(&url)?;
+            Ok(url)
+        });
+        let primary = base_urls
+            .next()
+            .ok_or_else(|| Error::ConfigError("At least one base URL is required".into()))??;
+        let fallbacks = base_urls.collect::<Result<Vec<_>>>()?;
+
+        let mut client = self.transition_to_no_auth();
+        let config = &mut client.inner_mut().config;
+        config.base_url = primary;
+        config.fallback_base_urls = fallbacks;
+        Ok(client)
+    }
+
     fn transition_to_no_auth(self) -> OpenRouterClient<NoAuth> {
-        OpenRouterClient {
-            config: self.config,
-            http_client: None,
-            _state: PhantomData,
-            router_config: self.router_config,
-            cached_api_config: None,
-            providers_cache: None,
-        }
+        self.into_state()
     }
 
     /// Sets maximum response size in bytes.
     /// Defaults to 10MB (10 * 1024 * 1024 bytes).
+    ///
+    /// Applies to every endpoint; for streams it caps the total size of
+    /// the event stream.
     #[must_use = "returns the updated client that should be used for API calls"]
     pub fn with_max_response_bytes(mut self, bytes: usize) -> Self {
-        self.config.max_response_bytes = bytes;
+        self.inner_mut().config.max_response_bytes = bytes;
         self
     }
 
@@ -217,6 +348,69 @@ impl OpenRouterClient<Unconfigured> {
     pub fn model_with_shortcut(model: &str, shortcut: &str) -> String {
         format!("{}{}", model, shortcut)
     }
+
+    /// Routes all requests through an HTTP(S) or SOCKS5 proxy.
+    ///
+    /// Accepts `http://`, `https://`, `socks5://` and `socks5h://` URLs,
+    /// optionally with `user:pass@` credentials.
+    pub fn with_proxy(mut self, proxy_url: &str) -> Result<Self> {
+        self.inner_mut().config.connection.proxy = ProxyConfig::from_url(proxy_url)?;
+        Ok(self)
+    }
+
+    /// Disables proxies, including those configured via environment variables.
+    #[must_use = "returns the updated client that should be used for API calls"]
+    pub fn with_no_proxy(mut self) -> Self {
+        self.inner_mut().config.connection.proxy = ProxyConfig::Disabled;
+        self
+    }
+
+    /// Trusts an additional PEM-encoded root certificate, e.g. a private CA.
+    ///
+    /// Malformed certificates are rejected here or, at the latest, when the
+    /// API key is supplied and the HTTP client is built.
+    pub fn with_added_root_certificate(mut self, pem: &[u8]) -> Result<Self> {
+        let certificate = reqwest::Certificate::from_pem(pem)
+            .map_err(|e| Error::ConfigError(format!("Invalid root certificate: {e}")))?;
+        self.inner_mut()
+            .config
+            .connection
+            .root_certificates
+            .push(certificate);
+        Ok(self)
+    }
+
+    /// Sets request body compression and response decompression.
+    ///
+    /// Encodings whose Cargo feature is disabled are rejected when the API
+    /// key is supplied and the HTTP client is built.
+    #[must_use = "returns the updated client that should be used for API calls"]
+    pub fn with_compression(mut self, compression: CompressionConfig) -> Self {
+        self.inner_mut().config.compression = compression;
+        self
+    }
+
+    /// Refuses connections using a TLS version older than `version`.
+    ///
+    /// `TlsVersion::Tls13` needs the `tls-rustls` backend; with
+    /// `tls-native-tls` the HTTP client fails to build.
+    #[must_use = "returns the updated client that should be used for API calls"]
+    pub fn with_min_tls_version(mut self, version: TlsVersion) -> Self {
+        self.inner_mut().config.connection.min_tls_version = Some(version);
+        self
+    }
+
+    /// Only talks to servers whose certificate matches one of `pins`.
+    ///
+    /// The certificate is checked right after the handshake, and a mismatch
+    /// fails the request with `Error::CertificatePinMismatch` before anything
+    /// is sent. Pin every certificate the server may rotate to.
+    #[cfg(feature = "cert-pinning")]
+    #[must_use = "returns the updated client that should be used for API calls"]
+    pub fn with_pinned_certificates(mut self, pins: Vec<Sha256Pin>) -> Self {
+        self.inner_mut().config.connection.pinned_certificates = pins;
+        self
+    }
 }
 
 impl OpenRouterClient<NoAuth> {
@@ -233,7 +427,7 @@ impl OpenRouterClient<NoAuth> {
     /// ```
     #[must_use = "returns the updated client that should be used for API calls"]
     pub fn with_api_key(mut self, api_key: impl Into<String>) -> Result<OpenRouterClient<Ready>> {
-        self.config.api_key = Some(SecureApiKey::new(api_key)?);
+        self.inner_mut().config.api_key = Some(SecureApiKey::new(api_key)?);
         self.transition_to_ready()
     }
 
@@ -241,7 +435,30 @@ impl OpenRouterClient<NoAuth> {
     /// This is useful when you already have a SecureApiKey instance.
     #[must_use = "returns the updated client that should be used for API calls"]
     pub fn with_secure_api_key(mut self, api_key: SecureApiKey) -> Result<OpenRouterClient<Ready>> {
-        self.config.api_key = Some(api_key);
+        self.inner_mut().config.api_key = Some(api_key);
+        self.transition_to_ready()
+    }
+
+    /// Uses a `KeyProvider` to supply the API key for each request and
+    /// transitions to the Ready state.
+    ///
+    /// Unlike a fixed key, the provider is consulted before every attempt, so
+    /// keys can be rotated or reloaded without rebuilding the client.
+    #[must_use = "returns the updated client that should be used for API calls"]
+    pub fn with_key_provider(
+        self,
+        provider: impl KeyProvider + 'static,
+    ) -> Result<OpenRouterClient<Ready>> {
+        self.with_shared_key_provider(Arc::new(provider))
+    }
+
+    /// Like `with_key_provider`, sharing one provider across several clients.
+    #[must_use = "returns the updated client that should be used for API calls"]
+    pub fn with_shared_key_provider(
+        mut self,
+        provider: Arc<dyn KeyProvider>,
+    ) -> Result<OpenRouterClient<Ready>> {
+        self.inner_mut().config.key_provider = Some(provider);
         self.transition_to_ready()
     }
 
@@ -255,12 +472,12 @@ impl OpenRouterClient<NoAuth> {
         title: Option<impl Into<String>>,
     ) -> Result<OpenRouterClient<Ready>> {
         if let Some(ref_val) = referer {
-            self.config.http_referer = Some(ref_val.into());
+            self.inner_mut().config.http_referer = Some(ref_val.into());
         }
         if let Some(title_val) = title {
-            self.config.site_title = Some(title_val.into());
+            self.inner_mut().config.site_title = Some(title_val.into());
         }
-        self.config.api_key = Some(SecureApiKey::new(api_key)?);
+        self.inner_mut().config.api_key = Some(SecureApiKey::new(api_key)?);
         self.transition_to_ready()
     }
 
@@ -279,50 +496,312 @@ impl OpenRouterClient<NoAuth> {
     /// ```
     #[must_use = "returns the updated client that should be used for API calls"]
     pub fn with_timeout(mut self, timeout: Duration) -> Self {
-        self.config.timeout = timeout;
+        self.inner_mut().config.timeout = timeout;
         self
     }
 
     /// Sets request timeout in seconds (convenience method).
     #[must_use = "returns the updated client that should be used for API calls"]
     pub fn with_timeout_secs(mut self, seconds: u64) -> Self {
-        self.config.timeout = Duration::from_secs(seconds);
+        self.inner_mut().config.timeout = Duration::from_secs(seconds);
         self
     }
 
     /// Optionally sets HTTP referer header.
     #[must_use = "returns the updated client that should be used for API calls"]
     pub fn with_http_referer(mut self, referer: impl Into<String>) -> Self {
-        self.config.http_referer = Some(referer.into());
+        self.inner_mut().config.http_referer = Some(referer.into());
         self
     }
 
     /// Optionally sets site title header.
     #[must_use = "returns the updated client that should be used for API calls"]
     pub fn with_site_title(mut self, title: impl Into<String>) -> Self {
-        self.config.site_title = Some(title.into());
+        self.inner_mut().config.site_title = Some(title.into());
         self
     }
 
     /// Optionally sets user ID header for tracking specific users.
     #[must_use = "returns the updated client that should be used for API calls"]
     pub fn with_user_id(mut self, user_id: impl Into<String>) -> Self {
-        self.config.user_id = Some(user_id.into());
+        self.inner_mut().config.user_id = Some(user_id.into());
+        self
+    }
+
+    /// Sets a default `user` for chat and completion requests.
+    ///
+    /// Requests that already carry a `user` are left untouched. Combined with
+    /// `ActivityResponse::group_by_user()`, this lets spend be attributed to
+    /// the end users of your application.
+    #[must_use = "returns the updated client that should be used for API calls"]
+    pub fn with_default_user(mut self, user_id: impl Into<String>) -> Self {
+        self.inner_mut().config.default_user = Some(user_id.into());
+        self
+    }
+
+    /// Sets values, such as a temperature or `max_tokens` preset, applied to
+    /// chat and completion requests that leave them unset.
+    ///
+    /// Values set on a request, or through `RequestOptions`, always win.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use openrouter_api::api::DefaultParams;
+    /// use openrouter_api::types::sampling::Temperature;
+    /// use openrouter_api::OpenRouterClient;
+    ///
+    /// let defaults = DefaultParams::new()
+    ///     .with_temperature(Temperature::try_from(0.2)?)
+    ///     .with_max_tokens(512);
+    /// let client = OpenRouterClient::new()
+    ///     .skip_url_configuration()
+    ///     .with_default_params(defaults)
+    ///     .with_api_key("sk-...")?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use = "returns the updated client that should be used for API calls"]
+    pub fn with_default_params(mut self, params: DefaultParams) -> Self {
+        self.inner_mut().config.default_params = params;
+        self
+    }
+
+    /// Enforces spending caps on chat and completion requests.
+    ///
+    /// Requests that would push spend over a cap fail with
+    /// `Error::BudgetExceeded` before anything is sent. Pass an
+    /// `Arc<BudgetGuard>` to share one budget across several clients.
+    #[must_use = "returns the updated client that should be used for API calls"]
+    pub fn with_budget_guard(mut self, guard: impl Into<Arc<BudgetGuard>>) -> Self {
+        self.inner_mut().config.budget_guard = Some(guard.into());
+        self
+    }
+
+    /// Shortens chat requests that exceed the model's context window.
+    ///
+    /// `RequestOptions::with_truncation` overrides this per call. The
+    /// response's `truncation` field reports what was removed.
+    #[must_use = "returns the updated client that should be used for API calls"]
+    pub fn with_truncation(mut self, truncation: TruncationConfig) -> Self {
+        self.inner_mut().config.truncation = Some(truncation);
+        self
+    }
+
+    /// Normalizes chat request messages for the target provider before
+    /// sending, e.g. merging consecutive user messages for Anthropic.
+    ///
+    /// See `NormalizationRules::for_model` for the rules applied.
+    #[must_use = "returns the updated client that should be used for API calls"]
+    pub fn with_message_normalization(mut self, enabled: bool) -> Self {
+        self.inner_mut().config.message_normalization = enabled;
+        self
+    }
+
+    /// Drops the reasoning channel (`reasoning` and `reasoning_details`)
+    /// from chat responses and stream chunks, so model thinking is never
+    /// handed to application code or logs.
+    ///
+    /// Reasoning tokens are still generated and billed; to stop the model
+    /// reasoning, set `ReasoningConfig` on the request instead.
+    #[must_use = "returns the updated client that should be used for API calls"]
+    pub fn with_strip_reasoning(mut self, enabled: bool) -> Self {
+        self.inner_mut().config.strip_reasoning = enabled;
+        self
+    }
+
+    /// Applies extra validation rules to chat requests, such as merging or
+    /// rejecting multiple system messages.
+    #[must_use = "returns the updated client that should be used for API calls"]
+    pub fn with_validation_policy(mut self, policy: ValidationPolicy) -> Self {
+        self.inner_mut().config.validation_policy = policy;
+        self
+    }
+
+    /// Sets whether requests are validated before they are sent; see
+    /// `RequestOptions::validation_mode` to change it for a single call.
+    #[must_use = "returns the updated client that should be used for API calls"]
+    pub fn with_validation_mode(mut self, mode: ValidationMode) -> Self {
+        self.inner_mut().config.validation_mode = mode;
+        self
+    }
+
+    /// Reports latency, throughput and usage of API calls to `recorder`.
+    ///
+    /// Chat completion streams are instrumented whenever a recorder is set.
+    #[must_use = "returns the updated client that should be used for API calls"]
+    pub fn with_metrics_recorder(self, recorder: impl MetricsRecorder + 'static) -> Self {
+        self.with_shared_metrics_recorder(Arc::new(recorder))
+    }
+
+    /// Like `with_metrics_recorder`, sharing one recorder across several clients.
+    #[must_use = "returns the updated client that should be used for API calls"]
+    pub fn with_shared_metrics_recorder(mut self, recorder: Arc<dyn MetricsRecorder>) -> Self {
+        self.inner_mut().config.metrics_recorder = Some(recorder);
+        self
+    }
+
+    /// Reports retries, model fallbacks, skipped values and catalog cache
+    /// lookups to `sink`. Without one they go to `tracing` when that feature
+    /// is enabled; `StderrDiagnostics` prints them instead.
+    #[must_use = "returns the updated client that should be used for API calls"]
+    pub fn with_diagnostics(self, sink: impl DiagnosticsSink + 'static) -> Self {
+        self.with_shared_diagnostics(Arc::new(sink))
+    }
+
+    /// Like `with_diagnostics`, sharing one sink across several clients.
+    #[must_use = "returns the updated client that should be used for API calls"]
+    pub fn with_shared_diagnostics(mut self, sink: Arc<dyn DiagnosticsSink>) -> Self {
+        self.inner_mut().config.diagnostics = Some(sink);
+        self
+    }
+
+    /// Enables background cost reconciliation for tracked requests.
+    ///
+    /// With cost tracking on, `ChatApi::chat_completion_tracked` and
+    /// `CompletionApi::text_completion_tracked` fetch the generation stats for
+    /// each response in the background (using the default `PollPolicy`) and
+    /// deliver them through `TrackedResponse::generation`.
+    #[must_use = "returns the updated client that should be used for API calls"]
+    pub fn with_cost_tracking(self) -> Self {
+        self.with_cost_tracking_policy(PollPolicy::default())
+    }
+
+    /// Enables background cost reconciliation with a custom polling schedule.
+    #[must_use = "returns the updated client that should be used for API calls"]
+    pub fn with_cost_tracking_policy(mut self, policy: PollPolicy) -> Self {
+        self.inner_mut().config.cost_tracking = Some(policy);
+        self
+    }
+
+    /// Replaces all connection pool and transport settings.
+    #[must_use = "returns the updated client that should be used for API calls"]
+    pub fn with_connection_config(mut self, connection: ConnectionConfig) -> Self {
+        self.inner_mut().config.connection = connection;
+        self
+    }
+
+    /// Sets the maximum number of idle connections kept per host.
+    #[must_use = "returns the updated client that should be used for API calls"]
+    pub fn with_pool_max_idle_per_host(mut self, max: usize) -> Self {
+        self.inner_mut().config.connection.pool_max_idle_per_host = Some(max);
+        self
+    }
+
+    /// Sets how long idle pooled connections are kept open.
+    #[must_use = "returns the updated client that should be used for API calls"]
+    pub fn with_pool_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.inner_mut().config.connection.pool_idle_timeout = Some(timeout);
+        self
+    }
+
+    /// Enables HTTP/2 keep-alive pings at `interval`, closing the connection
+    /// if a ping is not acknowledged within `timeout`.
+    #[must_use = "returns the updated client that should be used for API calls"]
+    pub fn with_http2_keep_alive(mut self, interval: Duration, timeout: Duration) -> Self {
+        self.inner_mut().config.connection.http2_keep_alive_interval = Some(interval);
+        self.inner_mut().config.connection.http2_keep_alive_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets whether `TCP_NODELAY` is enabled (on by default).
+    #[must_use = "returns the updated client that should be used for API calls"]
+    pub fn with_tcp_nodelay(mut self, enabled: bool) -> Self {
+        self.inner_mut().config.connection.tcp_nodelay = enabled;
+        self
+    }
+
+    /// Routes all requests through an HTTP(S) or SOCKS5 proxy.
+    ///
+    /// Accepts `http://`, `https://`, `socks5://` and `socks5h://` URLs,
+    /// optionally with `user:pass@` credentials.
+    pub fn with_proxy(mut self, proxy_url: &str) -> Result<Self> {
+        self.inner_mut().config.connection.proxy = ProxyConfig::from_url(proxy_url)?;
+        Ok(self)
+    }
+
+    /// Disables proxies, including those configured via environment variables.
+    #[must_use = "returns the updated client that should be used for API calls"]
+    pub fn with_no_proxy(mut self) -> Self {
+        self.inner_mut().config.connection.proxy = ProxyConfig::Disabled;
+        self
+    }
+
+    /// Trusts an additional PEM-encoded root certificate, e.g. a private CA.
+    ///
+    /// Malformed certificates are rejected here or, at the latest, when the
+    /// API key is supplied and the HTTP client is built.
+    pub fn with_added_root_certificate(mut self, pem: &[u8]) -> Result<Self> {
+        let certificate = reqwest::Certificate::from_pem(pem)
+            .map_err(|e| Error::ConfigError(format!("Invalid root certificate: {e}")))?;
+        self.inner_mut()
+            .config
+            .connection
+            .root_certificates
+            .push(certificate);
+        Ok(self)
+    }
+
+    /// Sets request body compression and response decompression.
+    ///
+    /// Encodings whose Cargo feature is disabled are rejected when the API
+    /// key is supplied and the HTTP client is built.
+    #[must_use = "returns the updated client that should be used for API calls"]
+    pub fn with_compression(mut self, compression: CompressionConfig) -> Self {
+        self.inner_mut().config.compression = compression;
+        self
+    }
+
+    /// Refuses connections using a TLS version older than `version`.
+    ///
+    /// `TlsVersion::Tls13` needs the `tls-rustls` backend; with
+    /// `tls-native-tls` the HTTP client fails to build.
+    #[must_use = "returns the updated client that should be used for API calls"]
+    pub fn with_min_tls_version(mut self, version: TlsVersion) -> Self {
+        self.inner_mut().config.connection.min_tls_version = Some(version);
         self
     }
 
+    /// Only talks to servers whose certificate matches one of `pins`.
+    ///
+    /// The certificate is checked right after the handshake, and a mismatch
+    /// fails the request with `Error::CertificatePinMismatch` before anything
+    /// is sent. Pin every certificate the server may rotate to.
+    #[cfg(feature = "cert-pinning")]
+    #[must_use = "returns the updated client that should be used for API calls"]
+    pub fn with_pinned_certificates(mut self, pins: Vec<Sha256Pin>) -> Self {
+        self.inner_mut().config.connection.pinned_certificates = pins;
+        self
+    }
+
+    /// Adds a header sent with every request, e.g. for an API gateway.
+    ///
+    /// Returns an error for invalid header names or values, or when trying to
+    /// override `Authorization` or `Content-Type`.
+    pub fn with_default_header(mut self, name: &str, value: &str) -> Result<Self> {
+        let (name, value) = parse_custom_header(name, value)?;
+        self.inner_mut().config.default_headers.insert(name, value);
+        Ok(self)
+    }
+
     /// Configures retry behavior with a complete RetryConfig.
     #[must_use = "returns the updated client that should be used for API calls"]
     pub fn with_retry_config(mut self, retry_config: RetryConfig) -> Self {
-        self.config.retry_config = retry_config;
+        self.inner_mut().config.retry_config = retry_config;
+        self
+    }
+
+    /// Sets when base URLs given to `with_base_urls` are failed over and
+    /// brought back.
+    #[must_use = "returns the updated client that should be used for API calls"]
+    pub fn with_failover_config(mut self, failover: FailoverConfig) -> Self {
+        self.inner_mut().config.failover = failover;
         self
     }
 
     /// Configures basic retry settings (convenience method).
     #[must_use = "returns updated client that should be used for API calls"]
     pub fn with_retries(mut self, max_retries: u32, initial_backoff_ms: u64) -> Self {
-        self.config.retry_config.max_retries = max_retries;
-        self.config.retry_config.initial_backoff_ms = initial_backoff_ms;
+        self.inner_mut().config.retry_config.max_retries = max_retries;
+        self.inner_mut().config.retry_config.initial_backoff_ms = initial_backoff_ms;
         self
     }
 
@@ -330,24 +809,63 @@ impl OpenRouterClient<NoAuth> {
     /// Defaults to 10MB (10 * 1024 * 1024 bytes).
     #[must_use = "returns updated client that should be used for API calls"]
     pub fn with_max_response_bytes(mut self, bytes: usize) -> Self {
-        self.config.max_response_bytes = bytes;
+        self.inner_mut().config.max_response_bytes = bytes;
         self
     }
 
     /// Disables automatic retries.
     #[must_use = "returns updated client that should be used for API calls"]
     pub fn without_retries(mut self) -> Self {
-        self.config.retry_config.max_retries = 0;
+        self.inner_mut().config.retry_config.max_retries = 0;
         self
     }
 
     /// Configures Model Coverage Profile for model selection and routing.
     #[must_use = "returns updated client that should be used for API calls"]
     pub fn with_model_coverage_profile(mut self, profile: PredefinedModelCoverageProfile) -> Self {
-        self.router_config = Some(RouterConfig {
-            profile,
-            provider_preferences: None,
-        });
+        let (selector, uptime_routing) = self
+            .inner_mut()
+            .router_config
+            .take()
+            .map(|config| (config.selector, config.uptime_routing))
+            .unwrap_or_default();
+        let mut router_config = RouterConfig::new(profile);
+        router_config.selector = selector;
+        router_config.uptime_routing = uptime_routing;
+        self.inner_mut().router_config = Some(router_config);
+        self
+    }
+
+    /// Returns the router configuration, creating a lowest-cost one if unset.
+    fn router_config_mut(&mut self) -> &mut RouterConfig {
+        self.inner_mut()
+            .router_config
+            .get_or_insert_with(|| RouterConfig::new(PredefinedModelCoverageProfile::LowestCost))
+    }
+
+    /// Ranks models with a user-defined `ModelSelector`.
+    ///
+    /// Call `select_models` on the ready client to fetch candidate data and
+    /// rank it; until then requests follow the coverage profile.
+    #[must_use = "returns updated client that should be used for API calls"]
+    pub fn with_model_selector(self, selector: impl ModelSelector + 'static) -> Self {
+        self.with_shared_model_selector(Arc::new(selector))
+    }
+
+    /// Ranks models with a `ModelSelector` shared with other clients.
+    #[must_use = "returns updated client that should be used for API calls"]
+    pub fn with_shared_model_selector(mut self, selector: Arc<dyn ModelSelector>) -> Self {
+        self.router_config_mut().selector = Some(selector);
+        self
+    }
+
+    /// Moves providers with degraded uptime to the end of `provider.order`.
+    ///
+    /// Endpoint stats are fetched by `refresh_provider_uptime`, or in the
+    /// background after `spawn_uptime_refresh`.
+    #[must_use = "returns updated client that should be used for API calls"]
+    pub fn with_uptime_routing(mut self, routing: UptimeRouting) -> Self {
+        self.router_config_mut().uptime_routing = Some(routing);
         self
     }
 
@@ -355,44 +873,37 @@ impl OpenRouterClient<NoAuth> {
     #[must_use = "returns updated client that should be used for API calls"]
     pub fn with_zdr(mut self) -> Self {
         use crate::models::provider_preferences::{DataCollection, ProviderPreferences};
-        let router_config = self.router_config.get_or_insert(RouterConfig {
-            profile: PredefinedModelCoverageProfile::LowestCost,
-            provider_preferences: Some(ProviderPreferences::new()),
-        });
-
-        let prefs = router_config
+        let prefs = self
+            .router_config_mut()
             .provider_preferences
             .get_or_insert(ProviderPreferences::new());
         prefs.data_collection = Some(DataCollection::Deny);
         self
     }
 
-    fn transition_to_ready(self) -> Result<OpenRouterClient<Ready>> {
-        let headers = self.config.build_headers()?;
+    fn transition_to_ready(mut self) -> Result<OpenRouterClient<Ready>> {
+        let inner = self.inner_mut();
+        let headers = inner.config.build_headers()?;
 
         // Build a client with retry capabilities
-        let client_builder = reqwest::Client::builder()
-            .timeout(self.config.timeout)
-            .tcp_keepalive(Duration::from_secs(60))
-            .default_headers(headers);
+        let client_builder = inner.config.compression.apply(
+            inner.config.connection.apply(
+                reqwest::Client::builder()
+                    .timeout(inner.config.timeout)
+                    .default_headers(headers),
+            )?,
+        )?;
 
         let http_client = client_builder
             .build()
             .map_err(|e| Error::ConfigError(format!("Failed to create HTTP client: {e}")))?;
 
         // Cache the ApiConfig so accessor methods don't rebuild it each time
-        let api_config = self.config.to_api_config()?;
-
-        Ok(OpenRouterClient {
-            config: self.config,
-            http_client: Some(http_client),
-            _state: PhantomData,
-            router_config: self.router_config,
-            cached_api_config: Some(api_config),
-            providers_cache: Some(std::sync::Arc::new(std::sync::Mutex::new(
-                crate::utils::cache::Cache::new(std::time::Duration::from_secs(300)),
-            ))),
-        })
+        let mut api_config = inner.config.to_api_config()?;
+        api_config.catalog_cache = inner.catalog_cache.clone();
+        inner.cached_api_config = Some(api_config);
+        inner.http_client = Some(http_client);
+        Ok(self.into_state())
     }
 }
 
@@ -400,22 +911,86 @@ impl OpenRouterClient<Ready> {
     /// Returns the cached HTTP client, or an error if missing.
     fn get_client_and_config(&self) -> Result<(reqwest::Client, ApiConfig)> {
         let client = self
+            .inner
             .http_client
             .clone()
             .ok_or_else(|| Error::ConfigError("HTTP client is missing".into()))?;
         let api_config = self
+            .inner
             .cached_api_config
             .clone()
             .ok_or_else(|| Error::ConfigError("API config is missing".into()))?;
         Ok((client, api_config))
     }
 
+    /// Returns the budget guard configured on this client, if any.
+    ///
+    /// Use it to inspect remaining budget or to record costs that arrive
+    /// later, such as those reported by the generation endpoint.
+    pub fn budget_guard(&self) -> Option<&Arc<BudgetGuard>> {
+        self.inner.config.budget_guard.as_ref()
+    }
+
+    /// Shuts the client down gracefully.
+    ///
+    /// Requests made afterwards through this client or the API handles it
+    /// created fail with `Error::Cancelled`. Requests and streams already in
+    /// flight get up to `grace` to finish; the rest are then cancelled, and
+    /// cancelled streams yield `Error::Cancelled` and close their connection.
+    /// Returns how many were cancelled.
+    pub async fn shutdown(&self, grace: Duration) -> usize {
+        match &self.inner.cached_api_config {
+            Some(config) => config.in_flight.shutdown(grace).await,
+            None => 0,
+        }
+    }
+
+    /// Requests and streams in flight on this client's API handles.
+    pub fn in_flight(&self) -> usize {
+        self.inner
+            .cached_api_config
+            .as_ref()
+            .map_or(0, |config| config.in_flight.len())
+    }
+
     /// Provides access to the chat endpoint.
     pub fn chat(&self) -> Result<crate::api::chat::ChatApi> {
         let (client, config) = self.get_client_and_config()?;
         Ok(crate::api::chat::ChatApi { client, config })
     }
 
+    /// Creates a `SensitiveSession` with `model`, whose message contents are
+    /// zeroed on drop and kept out of errors and debug output.
+    pub fn sensitive_session(
+        &self,
+        model: impl Into<String>,
+    ) -> Result<crate::sensitive::SensitiveSession> {
+        Ok(crate::sensitive::SensitiveSession::new(self.chat()?, model))
+    }
+
+    /// Creates an `Agent` that runs a tool-calling loop against `model`.
+    pub fn agent(&self, model: impl Into<String>) -> Result<crate::agent::Agent> {
+        Ok(crate::agent::Agent::new(Arc::new(self.chat()?), model))
+    }
+
+    /// Creates an `OpenRouterSampler` that answers MCP sampling requests,
+    /// using `default_model` unless the server's preferences select another.
+    pub fn mcp_sampler(
+        &self,
+        default_model: impl Into<String>,
+    ) -> Result<crate::mcp::sampling::OpenRouterSampler> {
+        Ok(crate::mcp::sampling::OpenRouterSampler::new(
+            Arc::new(self.chat()?),
+            default_model,
+        ))
+    }
+
+    /// Creates a `TaskPool` that runs chat requests in the background, at
+    /// most `max_concurrency` at a time.
+    pub fn task_pool(&self, max_concurrency: usize) -> Result<crate::tasks::TaskPool> {
+        Ok(crate::tasks::TaskPool::new(self.chat()?, max_concurrency))
+    }
+
     /// Provides access to the completions endpoint.
     pub fn completions(&self) -> Result<crate::api::completion::CompletionApi> {
         let (client, config) = self.get_client_and_config()?;
@@ -431,7 +1006,11 @@ impl OpenRouterClient<Ready> {
     /// Provides access to the structured output endpoint.
     pub fn structured(&self) -> Result<crate::api::structured::StructuredApi> {
         let (client, config) = self.get_client_and_config()?;
-        Ok(crate::api::structured::StructuredApi { client, config })
+        Ok(crate::api::structured::StructuredApi {
+            client,
+            config,
+            repair_attempts: 0,
+        })
     }
 
     /// Provides access to the web search endpoint.
@@ -453,17 +1032,16 @@ impl OpenRouterClient<Ready> {
     }
 
     /// Provides access to the providers endpoint.
-    /// The cache is shared across calls so repeated `.providers()?.get_providers()` hits cache.
+    /// The catalog cache is shared, so repeated `.providers()?.get_providers()` hits cache.
     pub fn providers(&self) -> Result<crate::api::providers::ProvidersApi> {
         let (client, config) = self.get_client_and_config()?;
-        let cache = self.providers_cache.clone().ok_or_else(|| {
-            crate::error::Error::ConfigError("Providers cache not initialized".into())
-        })?;
-        Ok(crate::api::providers::ProvidersApi {
-            client,
-            config,
-            cache,
-        })
+        Ok(crate::api::providers::ProvidersApi { client, config })
+    }
+
+    /// The models and providers responses shared by every API handle and
+    /// the router. Call `clear` to fetch a fresh catalog.
+    pub fn catalog_cache(&self) -> &CatalogCache {
+        &self.inner.catalog_cache
     }
 
     /// Provides access to the key info endpoint.
@@ -490,42 +1068,367 @@ impl OpenRouterClient<Ready> {
         Ok(crate::api::guardrails::GuardrailsApi { client, config })
     }
 
-    /// Returns a new request builder for chat completions that supports MCP.
-    pub fn chat_request_builder(
+    /// Searches the web for `query` and has `options.model` answer it from
+    /// the top results, citing them.
+    ///
+    /// `query` is a string or a `WebSearchRequest` carrying search filters.
+    /// The answer's `[n]` markers are resolved to the numbered results in
+    /// `SearchAnswer::citations`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use openrouter_api::types::search_answer::SummarizeOptions;
+    /// use openrouter_api::OpenRouterClient;
+    ///
+    /// # async fn run() -> openrouter_api::Result<()> {
+    /// let client = OpenRouterClient::from_env()?;
+    /// let result = client
+    ///     .search_and_answer(
+    ///         "What is the latest stable Rust release?",
+    ///         SummarizeOptions::new("openai/gpt-4o-mini").with_max_sources(3),
+    ///     )
+    ///     .await?;
+    ///
+    /// println!("{}", result.answer);
+    /// for citation in &result.citations {
+    ///     println!("[{}] {}", citation.index, citation.url);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn search_and_answer(
         &self,
-        messages: Vec<crate::types::chat::Message>,
-    ) -> crate::api::request::RequestBuilder<serde_json::Value> {
-        // Apply the model coverage profile if available
-        let primary_model = if let Some(router_config) = &self.router_config {
-            match &router_config.profile {
-                PredefinedModelCoverageProfile::Custom(profile) => profile.primary.clone(),
-                PredefinedModelCoverageProfile::LowestLatency => "openai/gpt-3.5-turbo".to_string(),
-                PredefinedModelCoverageProfile::LowestCost => "openai/gpt-3.5-turbo".to_string(),
-                PredefinedModelCoverageProfile::HighestQuality => {
-                    "anthropic/claude-3-opus-20240229".to_string()
+        query: impl Into<crate::types::web_search::WebSearchRequest>,
+        options: crate::types::search_answer::SummarizeOptions,
+    ) -> Result<crate::types::search_answer::SearchAnswer> {
+        let mut search = query.into();
+        // Only fetch the results the prompt will use; a smaller
+        // `num_results` on the request is kept.
+        let max_sources = u32::try_from(options.max_sources)
+            .unwrap_or(u32::MAX)
+            .min(crate::utils::validation::web_search::MAX_RESULTS);
+        search.num_results = Some(search.num_results.unwrap_or(u32::MAX).min(max_sources));
+        let question = search.query.clone();
+        let mut results = self.web_search()?.search(search).await?.results;
+        // In case the server returns more than asked for.
+        results.truncate(options.max_sources);
+
+        let request = crate::types::search_answer::build_request(&question, &results, &options);
+        let response = self.chat()?.chat_completion(request).await?;
+        Ok(crate::types::search_answer::SearchAnswer::new(
+            results, response,
+        ))
+    }
+
+    /// Polls the model catalog every `interval` and yields the changes
+    /// whenever it differs from the previous poll.
+    ///
+    /// The first poll only records a baseline. A failed poll yields its error
+    /// and the stream carries on comparing against the last successful
+    /// snapshot, so the caller decides whether to stop.
+    ///
+    /// A zero `interval` yields `Error::ConfigError` and ends the stream.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use futures::StreamExt;
+    /// use openrouter_api::OpenRouterClient;
+    /// use std::time::Duration;
+    ///
+    /// # async fn run() -> openrouter_api::Result<()> {
+    /// let client = OpenRouterClient::from_env()?;
+    /// let mut changes = client.watch_models(Duration::from_secs(3600));
+    /// while let Some(diff) = changes.next().await {
+    ///     let diff = diff?;
+    ///     for model in &diff.added {
+    ///         println!("new model: {}", model.id);
+    ///     }
+    ///     for update in &diff.changed {
+    ///         println!("{} changed: {:?}", update.id, update.changes);
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use = "returns a stream that should be polled to receive catalog changes"]
+    pub fn watch_models(
+        &self,
+        interval: Duration,
+    ) -> std::pin::Pin<
+        Box<dyn futures::Stream<Item = Result<crate::types::catalog::ModelsDiff>> + Send + '_>,
+    > {
+        use crate::types::catalog::ModelsSnapshot;
+
+        Box::pin(async_stream::stream! {
+            if interval.is_zero() {
+                yield Err(Error::ConfigError(
+                    "watch_models interval must be greater than zero".into(),
+                ));
+                return;
+            }
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            let mut previous: Option<ModelsSnapshot> = None;
+            loop {
+                ticker.tick().await;
+                let snapshot = match self.models() {
+                    Ok(models) => models.refresh_models(None).await.map(ModelsSnapshot::from),
+                    Err(e) => Err(e),
+                };
+                match (snapshot, previous.take()) {
+                    (Ok(snapshot), Some(old)) => {
+                        let diff = ModelsSnapshot::diff(&old, &snapshot);
+                        previous = Some(snapshot);
+                        if !diff.is_empty() {
+                            yield Ok(diff);
+                        }
+                    }
+                    (Ok(snapshot), None) => previous = Some(snapshot),
+                    (Err(e), old) => {
+                        previous = old;
+                        yield Err(e);
+                    }
                 }
             }
+        })
+    }
+
+    /// Ranks the configured `ModelSelector`'s candidates and remembers the
+    /// result for `chat_request_builder`.
+    ///
+    /// Model and endpoint data is fetched for candidates not seen in the last
+    /// five minutes. Returns the ranking, best first.
+    pub async fn select_models(&self) -> Result<Vec<String>> {
+        let selector = self
+            .inner
+            .router_config
+            .as_ref()
+            .and_then(|config| config.selector.clone())
+            .ok_or_else(|| Error::ConfigError("No model selector configured".into()))?;
+        let wanted = selector.candidates();
+
+        let mut candidates = Vec::with_capacity(wanted.len());
+        let mut missing = Vec::new();
+        {
+            let mut selection = self
+                .inner
+                .model_selection
+                .lock()
+                .map_err(|_| Error::ConfigError("Model selection lock poisoned".into()))?;
+            for id in &wanted {
+                match selection.candidates.get(id) {
+                    Some(candidate) => candidates.push(candidate),
+                    None => missing.push(id.as_str()),
+                }
+            }
+        }
+
+        if !missing.is_empty() {
+            let models_api = self.models()?;
+            let models = models_api.list_models(None).await?;
+            let mut fetched = Vec::new();
+            for model in models
+                .data
+                .into_iter()
+                .filter(|model| missing.contains(&model.id.as_str()))
+            {
+                let endpoints = models_api.list_endpoints(model.id.as_str()).await?;
+                fetched.push(ModelCandidate {
+                    model,
+                    endpoints: endpoints.data.endpoints,
+                });
+            }
+            if let Ok(mut selection) = self.inner.model_selection.lock() {
+                for candidate in &fetched {
+                    selection
+                        .candidates
+                        .insert(candidate.model.id.as_str().to_string(), candidate.clone());
+                }
+            }
+            candidates.extend(fetched);
+        }
+
+        let ranking = selector.rank(&candidates);
+        if ranking.is_empty() {
+            return Err(Error::ConfigError("Model selector ranked no models".into()));
+        }
+        if let Ok(mut selection) = self.inner.model_selection.lock() {
+            selection.ranking = Some(ranking.clone());
+        }
+        Ok(ranking)
+    }
+
+    /// Fetches endpoint uptime for the models monitored by `UptimeRouting`
+    /// whose stats are older than the refresh interval.
+    pub async fn refresh_provider_uptime(&self) -> Result<()> {
+        let (routing, models) = self.uptime_targets()?;
+        refresh_uptime(
+            &self.models()?,
+            &self.inner.provider_health,
+            &routing,
+            &models,
+        )
+        .await
+    }
+
+    /// Refreshes endpoint uptime in a background task every refresh
+    /// interval until the returned handle is aborted.
+    ///
+    /// Failed refreshes keep the previous stats. Must be called within a
+    /// Tokio runtime.
+    pub fn spawn_uptime_refresh(&self) -> Result<tokio::task::JoinHandle<()>> {
+        let (routing, models) = self.uptime_targets()?;
+        let models_api = self.models()?;
+        let health = Arc::clone(&self.inner.provider_health);
+        Ok(tokio::spawn(async move {
+            loop {
+                // Errors are retried on the next tick.
+                let _ = refresh_uptime(&models_api, &health, &routing, &models).await;
+                tokio::time::sleep(routing.refresh_interval).await;
+            }
+        }))
+    }
+
+    /// Fetches the model and provider catalog into the shared cache and
+    /// checks the key against the credits endpoint, so the first requests
+    /// neither wait for the catalog nor for a new connection.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use openrouter_api::OpenRouterClient;
+    ///
+    /// # async fn run() -> openrouter_api::Result<()> {
+    /// let client = OpenRouterClient::from_env()?;
+    /// client.warm_up().await?;
+    /// assert!(client.catalog_ready());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn warm_up(&self) -> Result<()> {
+        warm_up_catalog(&self.models()?, &self.providers()?, &self.credits()?).await
+    }
+
+    /// Whether the model and provider lists are cached and fresh.
+    pub fn catalog_ready(&self) -> bool {
+        let Some(config) = &self.inner.cached_api_config else {
+            return false;
+        };
+        let urls = config.url_builder();
+        ["models", "providers"].iter().all(|path| {
+            urls.build(path)
+                .is_ok_and(|url| self.inner.catalog_cache.contains(url.as_str()))
+        })
+    }
+
+    /// Warms up the catalog, then refreshes it in a background task about
+    /// every `interval` until the returned handle is aborted.
+    ///
+    /// Each wait is jittered by up to 25% so that many clients don't refresh
+    /// in lockstep. Keep `interval` below the five minute cache lifetime for
+    /// `catalog_ready` to stay true; failed refreshes keep the cached catalog
+    /// until it expires. Must be called within a Tokio runtime.
+    pub fn spawn_catalog_refresh(&self, interval: Duration) -> Result<tokio::task::JoinHandle<()>> {
+        let models_api = self.models()?;
+        let providers_api = self.providers()?;
+        let credits_api = self.credits()?;
+        Ok(tokio::spawn(async move {
+            let mut rng = fastrand::Rng::new();
+            loop {
+                // Errors are retried on the next tick.
+                let _ = warm_up_catalog(&models_api, &providers_api, &credits_api).await;
+                let jitter = rng.f64() * 0.5 + 0.75;
+                tokio::time::sleep(interval.mul_f64(jitter)).await;
+            }
+        }))
+    }
+
+    /// Providers currently demoted by uptime routing for `model`.
+    pub fn degraded_providers(&self, model: &str) -> Vec<String> {
+        self.inner
+            .provider_health
+            .lock()
+            .map(|health| health.degraded(model))
+            .unwrap_or_default()
+    }
+
+    /// The uptime routing settings and the models they monitor.
+    fn uptime_targets(&self) -> Result<(UptimeRouting, Vec<String>)> {
+        let routing = self
+            .inner
+            .router_config
+            .as_ref()
+            .and_then(|config| config.uptime_routing.clone())
+            .ok_or_else(|| Error::ConfigError("Uptime routing is not configured".into()))?;
+        let models = if routing.models.is_empty() {
+            let ranking = self
+                .inner
+                .model_selection
+                .lock()
+                .ok()
+                .and_then(|selection| selection.ranking.clone());
+            vec![self.primary_model(ranking.as_deref())]
         } else {
-            "openai/gpt-4o".to_string()
+            routing.models.clone()
         };
+        Ok((routing, models))
+    }
+
+    /// Returns a new request builder for chat completions that supports MCP.
+    ///
+    /// After `select_models` has run, the selector's top model is the primary
+    /// and the rest are sent as fallbacks.
+    pub fn chat_request_builder(
+        &self,
+        messages: Vec<crate::types::chat::Message>,
+    ) -> crate::api::request::RequestBuilder<serde_json::Value> {
+        let ranking = self
+            .inner
+            .model_selection
+            .lock()
+            .ok()
+            .and_then(|selection| selection.ranking.clone());
+        let primary_model = self.primary_model(ranking.as_deref());
 
         // Set up basic params
         let mut extra_params = serde_json::json!({});
 
         // Add provider preferences if set
-        if let Some(router_config) = &self.router_config {
+        if let Some(router_config) = &self.inner.router_config {
+            let preferred = router_config
+                .provider_preferences
+                .as_ref()
+                .and_then(|prefs| prefs.order.as_deref());
+            let uptime_order = router_config.uptime_routing.as_ref().and_then(|_| {
+                self.inner
+                    .provider_health
+                    .lock()
+                    .ok()?
+                    .order(&primary_model, preferred)
+            });
+
             if let Some(provider_prefs) = &router_config.provider_preferences {
-                // Convert to Value and handle errors
-                if let Ok(prefs_value) = serde_json::to_value(provider_prefs) {
-                    extra_params["provider"] = prefs_value;
+                match serde_json::to_value(provider_prefs) {
+                    Ok(prefs_value) => extra_params["provider"] = prefs_value,
+                    Err(e) => self.skipped("provider", &e),
                 }
             }
+            if let Some(order) = uptime_order {
+                extra_params["provider"]["order"] = serde_json::json!(order);
+            }
 
             // Add fallback models if present in custom profile
-            if let PredefinedModelCoverageProfile::Custom(profile) = &router_config.profile {
+            if let Some(ranking) = &ranking {
+                if ranking.len() > 1 {
+                    extra_params["models"] = serde_json::json!(ranking[1..]);
+                }
+            } else if let PredefinedModelCoverageProfile::Custom(profile) = &router_config.profile {
                 if let Some(fallbacks) = &profile.fallbacks {
-                    if let Ok(fallbacks_value) = serde_json::to_value(fallbacks) {
-                        extra_params["models"] = fallbacks_value;
+                    match serde_json::to_value(fallbacks) {
+                        Ok(fallbacks_value) => extra_params["models"] = fallbacks_value,
+                        Err(e) => self.skipped("models", &e),
                     }
                 }
             }
@@ -534,6 +1437,37 @@ impl OpenRouterClient<Ready> {
         crate::api::request::RequestBuilder::new(primary_model, messages, extra_params)
     }
 
+    /// Reports a router setting that could not be serialized.
+    fn skipped(&self, field: &str, error: &serde_json::Error) {
+        let diagnostic = Diagnostic::SerializationSkipped {
+            field,
+            error: &error.to_string(),
+        };
+        match &self.inner.config.diagnostics {
+            Some(sink) => sink.emit(&diagnostic),
+            None => default_diagnostics().emit(&diagnostic),
+        }
+    }
+
+    /// The model requests are routed to: the selector's top-ranked model,
+    /// else the coverage profile's primary.
+    fn primary_model(&self, ranking: Option<&[String]>) -> String {
+        if let Some(top) = ranking.and_then(|r| r.first()) {
+            top.clone()
+        } else if let Some(router_config) = &self.inner.router_config {
+            match &router_config.profile {
+                PredefinedModelCoverageProfile::Custom(profile) => profile.primary.clone(),
+                PredefinedModelCoverageProfile::LowestLatency => "openai/gpt-3.5-turbo".to_string(),
+                PredefinedModelCoverageProfile::LowestCost => "openai/gpt-3.5-turbo".to_string(),
+                PredefinedModelCoverageProfile::HighestQuality => {
+                    "anthropic/claude-3-opus-20240229".to_string()
+                }
+            }
+        } else {
+            "openai/gpt-4o".to_string()
+        }
+    }
+
     /// Validates tool calls in a chat completion response.
     ///
     /// Note: With the introduction of `ToolType` enum, tool call kind is now
@@ -549,5 +1483,42 @@ impl OpenRouterClient<Ready> {
     }
 }
 
+/// Refreshes the cached model and provider lists and fetches the balance.
+async fn warm_up_catalog(
+    models_api: &crate::api::models::ModelsApi,
+    providers_api: &crate::api::providers::ProvidersApi,
+    credits_api: &crate::api::credits::CreditsApi,
+) -> Result<()> {
+    futures::try_join!(
+        models_api.refresh_models(None),
+        providers_api.refresh_providers(),
+        credits_api.get_balance(),
+    )?;
+    Ok(())
+}
+
+/// Fetches endpoint stats for each of `models` whose stats are stale.
+async fn refresh_uptime(
+    models_api: &crate::api::models::ModelsApi,
+    health: &std::sync::Mutex<ProviderHealth>,
+    routing: &UptimeRouting,
+    models: &[String],
+) -> Result<()> {
+    for model in models {
+        let stale = health
+            .lock()
+            .map_err(|_| Error::ConfigError("Provider health lock poisoned".into()))?
+            .is_stale(model, routing);
+        if !stale {
+            continue;
+        }
+        let endpoints = models_api.list_endpoints(model).await?;
+        if let Ok(mut health) = health.lock() {
+            health.update(model, &endpoints.data.endpoints, routing);
+        }
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,236 @@
+//! Ordered failover between base URLs.
+//!
+//! With backup base URLs configured (`OpenRouterClient::with_base_urls`),
+//! each request attempt goes to the first base URL that is healthy. A base
+//! URL becomes unhealthy after `FailoverConfig::failure_threshold`
+//! consecutive attempts that failed to connect, timed out or returned a 5xx
+//! status; later attempts, including retries of the same request, go to the
+//! next one. Once `FailoverConfig::recovery_interval` has passed, a health
+//! check (`GET` of `FailoverConfig::health_check_path`) runs in the
+//! background, and traffic returns to the base URL when it answers without a
+//! 5xx status.
+
+use crate::error::Error;
+use crate::utils::diagnostics::{Diagnostic, DiagnosticsSink};
+use crate::utils::retry::RetryTrigger;
+use crate::utils::url_builder::UrlBuilder;
+use reqwest::{Client, Response};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use url::Url;
+
+/// When a base URL is taken out of rotation and how it is brought back.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct FailoverConfig {
+    /// Consecutive failed attempts after which a base URL is skipped.
+    pub failure_threshold: u32,
+    /// How long a failed base URL is skipped before it is health-checked.
+    pub recovery_interval: Duration,
+    /// Path below the base URL requested by the health check.
+    pub health_check_path: String,
+}
+
+impl Default for FailoverConfig {
+    fn default() -> Self {
+        Self {
+            failure_threshold: 3,
+            recovery_interval: Duration::from_secs(30),
+            health_check_path: "models".to_string(),
+        }
+    }
+}
+
+/// Health of one base URL.
+#[derive(Debug, Default)]
+struct Health {
+    consecutive_failures: u32,
+    /// When the base URL was taken out of rotation, or last failed its
+    /// health check.
+    down_since: Option<Instant>,
+    probing: bool,
+}
+
+#[derive(Debug)]
+struct State {
+    health: Vec<Health>,
+    /// The base URL the last attempt was routed to.
+    active: usize,
+}
+
+/// The base URLs of one client, in order of preference, and their health.
+///
+/// Shared by every API handle the client creates.
+#[derive(Debug, Clone)]
+pub struct BaseUrlFailover {
+    urls: Arc<[Url]>,
+    config: Arc<FailoverConfig>,
+    state: Arc<Mutex<State>>,
+}
+
+impl BaseUrlFailover {
+    /// Creates the failover set; `urls` must not be empty.
+    pub(crate) fn new(urls: Vec<Url>, config: FailoverConfig) -> Self {
+        // Match the trailing slash `UrlBuilder` gives the URLs it builds.
+        let urls: Vec<Url> = urls
+            .into_iter()
+            .map(|url| UrlBuilder::new(url).base_url().clone())
+            .collect();
+        let health = urls.iter().map(|_| Health::default()).collect();
+        Self {
+            urls: urls.into(),
+            config: Arc::new(config),
+            state: Arc::new(Mutex::new(State { health, active: 0 })),
+        }
+    }
+
+    /// The base URLs, primary first.
+    pub fn urls(&self) -> &[Url] {
+        &self.urls
+    }
+
+    /// The base URL requests are currently sent to.
+    pub fn active(&self) -> &Url {
+        let state = self.lock();
+        &self.urls[Self::first_healthy(&state)]
+    }
+
+    /// Whether the base URL at `index` is in rotation.
+    pub fn is_healthy(&self, index: usize) -> bool {
+        self.lock()
+            .health
+            .get(index)
+            .is_some_and(|health| health.down_since.is_none())
+    }
+
+    /// Moves `url`, built below any of the base URLs, to the base URL the
+    /// next attempt should use, starting health checks of failed base URLs
+    /// that are due one.
+    pub(crate) fn route(
+        &self,
+        client: &Client,
+        url: &Url,
+        diagnostics: &dyn DiagnosticsSink,
+    ) -> Url {
+        let mut state = self.lock();
+        for index in 0..self.urls.len() {
+            let health = &mut state.health[index];
+            let due = health
+                .down_since
+                .is_some_and(|since| since.elapsed() >= self.config.recovery_interval);
+            if due && !health.probing {
+                if let Ok(runtime) = tokio::runtime::Handle::try_current() {
+                    health.probing = true;
+                    runtime.spawn(self.clone().health_check(client.clone(), index));
+                }
+            }
+        }
+
+        let target = Self::first_healthy(&state);
+        if target != state.active {
+            diagnostics.emit(&Diagnostic::BaseUrlFailover {
+                from: self.urls[state.active].as_str(),
+                to: self.urls[target].as_str(),
+            });
+            state.active = target;
+        }
+        drop(state);
+
+        match self.base_of(url) {
+            Some(base) if base != target => {
+                let rest = &url.as_str()[self.urls[base].as_str().len()..];
+                Url::parse(&format!("{}{rest}", self.urls[target])).unwrap_or_else(|_| url.clone())
+            }
+            _ => url.clone(),
+        }
+    }
+
+    /// Records the outcome of an attempt against the base URL it was sent
+    /// to. Outcomes that say nothing about reachability, such as a 4xx
+    /// status, count as successes.
+    pub(crate) fn observe(&self, trigger: RetryTrigger<'_>) {
+        match trigger {
+            RetryTrigger::Response(response) => self.observe_response(response),
+            RetryTrigger::Error(Error::HttpError(error)) => self.observe_error(error),
+            RetryTrigger::Error(_) => {}
+        }
+    }
+
+    /// Like `observe`, for a request sent without the retry executor.
+    pub(crate) fn observe_send(&self, result: &reqwest::Result<Response>) {
+        match result {
+            Ok(response) => self.observe_response(response),
+            Err(error) => self.observe_error(error),
+        }
+    }
+
+    fn observe_response(&self, response: &Response) {
+        if let Some(index) = self.base_of(response.url()) {
+            self.record(index, !response.status().is_server_error());
+        }
+    }
+
+    fn observe_error(&self, error: &reqwest::Error) {
+        if let Some(index) = error.url().and_then(|url| self.base_of(url)) {
+            self.record(index, !(error.is_connect() || error.is_timeout()));
+        }
+    }
+
+    fn record(&self, index: usize, success: bool) {
+        let mut state = self.lock();
+        let health = &mut state.health[index];
+        if success {
+            health.consecutive_failures = 0;
+            health.down_since = None;
+        } else {
+            health.consecutive_failures = health.consecutive_failures.saturating_add(1);
+            if health.consecutive_failures >= self.config.failure_threshold.max(1) {
+                health.down_since.get_or_insert_with(Instant::now);
+            }
+        }
+    }
+
+    async fn health_check(self, client: Client, index: usize) {
+        let url = UrlBuilder::new(self.urls[index].clone()).build(&self.config.health_check_path);
+        let healthy = match url {
+            Ok(url) => client
+                .get(url)
+                .send()
+                .await
+                .is_ok_and(|response| !response.status().is_server_error()),
+            Err(_) => false,
+        };
+        let mut state = self.lock();
+        let health = &mut state.health[index];
+        health.probing = false;
+        if healthy {
+            health.consecutive_failures = 0;
+            health.down_since = None;
+        } else {
+            health.down_since = Some(Instant::now());
+        }
+    }
+
+    /// Index of the longest base URL that `url` lies below.
+    fn base_of(&self, url: &Url) -> Option<usize> {
+        self.urls
+            .iter()
+            .enumerate()
+            .filter(|(_, base)| url.as_str().starts_with(base.as_str()))
+            .max_by_key(|(_, base)| base.as_str().len())
+            .map(|(index, _)| index)
+    }
+
+    /// The first base URL in rotation; the primary if none is.
+    fn first_healthy(state: &State) -> usize {
+        state
+            .health
+            .iter()
+            .position(|health| health.down_since.is_none())
+            .unwrap_or(0)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
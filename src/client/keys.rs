@@ -0,0 +1,295 @@
+//! Pluggable API key sources consulted on every request.
+//!
+//! A [`KeyProvider`] lets a long-lived client rotate or reload credentials
+//! without being rebuilt. The client asks the provider for a key before each
+//! attempt (including retries) and reports the HTTP status it received, so
+//! providers can steer traffic away from keys that are revoked or throttled.
+
+use super::SecureApiKey;
+use crate::error::{Error, Result};
+use reqwest::StatusCode;
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Supplies the API key for each request.
+pub trait KeyProvider: fmt::Debug + Send + Sync {
+    /// Returns the key to authenticate the next request with.
+    fn api_key(&self) -> Arc<SecureApiKey>;
+
+    /// Called with the status of every non-streaming response received
+    /// with `key`, including responses that are retried.
+    ///
+    /// The default implementation ignores it.
+    fn report(&self, key: &SecureApiKey, status: StatusCode) {
+        let _ = (key, status);
+    }
+}
+
+/// A single fixed key.
+#[derive(Debug)]
+pub struct StaticKey(Arc<SecureApiKey>);
+
+impl StaticKey {
+    /// Validates and wraps the key.
+    pub fn new(key: impl Into<String>) -> Result<Self> {
+        Ok(Self(Arc::new(SecureApiKey::new(key)?)))
+    }
+}
+
+impl From<SecureApiKey> for StaticKey {
+    fn from(key: SecureApiKey) -> Self {
+        Self(Arc::new(key))
+    }
+}
+
+impl KeyProvider for StaticKey {
+    fn api_key(&self) -> Arc<SecureApiKey> {
+        Arc::clone(&self.0)
+    }
+}
+
+/// Round-robins across several keys.
+///
+/// A key that receives `401 Unauthorized` or `429 Too Many Requests` is
+/// quarantined (skipped) for the quarantine period, 60 seconds by default.
+/// If every key is quarantined, the one released soonest is used.
+#[derive(Debug)]
+pub struct RotatingKeys {
+    keys: Vec<Arc<SecureApiKey>>,
+    next: AtomicUsize,
+    quarantined_until: Mutex<Vec<Option<Instant>>>,
+    quarantine: Duration,
+}
+
+impl RotatingKeys {
+    /// Validates the keys; at least one is required.
+    pub fn new<I, S>(keys: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let keys = keys
+            .into_iter()
+            .map(|key| SecureApiKey::new(key).map(Arc::new))
+            .collect::<Result<Vec<_>>>()?;
+        if keys.is_empty() {
+            return Err(Error::ConfigError(
+                "RotatingKeys requires at least one API key".into(),
+            ));
+        }
+        Ok(Self {
+            quarantined_until: Mutex::new(vec![None; keys.len()]),
+            keys,
+            next: AtomicUsize::new(0),
+            quarantine: Duration::from_secs(60),
+        })
+    }
+
+    /// Sets how long a rejected or throttled key is skipped.
+    pub fn with_quarantine(mut self, quarantine: Duration) -> Self {
+        self.quarantine = quarantine;
+        self
+    }
+
+    /// Number of keys in the rotation.
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Always `false`; construction requires at least one key.
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Number of keys currently quarantined.
+    pub fn quarantined(&self) -> usize {
+        let now = Instant::now();
+        self.lock_quarantine()
+            .iter()
+            .filter(|until| until.is_some_and(|until| until > now))
+            .count()
+    }
+
+    fn lock_quarantine(&self) -> std::sync::MutexGuard<'_, Vec<Option<Instant>>> {
+        self.quarantined_until
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl KeyProvider for RotatingKeys {
+    fn api_key(&self) -> Arc<SecureApiKey> {
+        let start = self.next.fetch_add(1, Ordering::Relaxed);
+        let now = Instant::now();
+        let quarantined_until = self.lock_quarantine();
+
+        let count = self.keys.len();
+        let index = (0..count)
+            .map(|offset| (start + offset) % count)
+            .find(|&i| quarantined_until[i].is_none_or(|until| until <= now))
+            .unwrap_or_else(|| {
+                (0..count)
+                    .min_by_key(|&i| quarantined_until[i])
+                    .unwrap_or_default()
+            });
+        Arc::clone(&self.keys[index])
+    }
+
+    fn report(&self, key: &SecureApiKey, status: StatusCode) {
+        if status != StatusCode::UNAUTHORIZED && status != StatusCode::TOO_MANY_REQUESTS {
+            return;
+        }
+        if let Some(index) = self
+            .keys
+            .iter()
+            .position(|candidate| std::ptr::eq(candidate.as_ref(), key))
+        {
+            self.lock_quarantine()[index] = Some(Instant::now() + self.quarantine);
+        }
+    }
+}
+
+/// Reads the key from an environment variable and re-reads it periodically,
+/// every 30 seconds by default.
+///
+/// If the variable is later unset or holds an invalid key, the last valid
+/// key keeps being used.
+#[derive(Debug)]
+pub struct EnvReloadingKey {
+    var: String,
+    reload_interval: Duration,
+    state: Mutex<(Arc<SecureApiKey>, Instant)>,
+}
+
+impl EnvReloadingKey {
+    /// Loads the key from `var`, failing if it is unset or invalid.
+    pub fn new(var: impl Into<String>) -> Result<Self> {
+        let var = var.into();
+        let key = Self::load(&var)?;
+        Ok(Self {
+            var,
+            reload_interval: Duration::from_secs(30),
+            state: Mutex::new((Arc::new(key), Instant::now())),
+        })
+    }
+
+    /// Sets how often the variable is re-read.
+    pub fn with_reload_interval(mut self, reload_interval: Duration) -> Self {
+        self.reload_interval = reload_interval;
+        self
+    }
+
+    fn load(var: &str) -> Result<SecureApiKey> {
+        let value = std::env::var(var).map_err(|_| {
+            Error::MissingCredential(format!("environment variable {var} is not set"))
+        })?;
+        SecureApiKey::new(value.trim())
+    }
+}
+
+impl KeyProvider for EnvReloadingKey {
+    fn api_key(&self) -> Arc<SecureApiKey> {
+        let mut state = self
+            .state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if state.1.elapsed() >= self.reload_interval {
+            if let Ok(key) = Self::load(&self.var) {
+                if key.as_str() != state.0.as_str() {
+                    state.0 = Arc::new(key);
+                }
+            }
+            state.1 = Instant::now();
+        }
+        Arc::clone(&state.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY_A: &str = "sk-aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
+    const KEY_B: &str = "sk-bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb";
+    const KEY_C: &str = "sk-cccccccccccccccccccccccccccccccc";
+
+    #[test]
+    fn test_static_key() {
+        let provider = StaticKey::new(KEY_A).unwrap();
+        assert_eq!(provider.api_key().as_str(), KEY_A);
+        assert!(StaticKey::new("short").is_err());
+    }
+
+    #[test]
+    fn test_rotating_keys_round_robin() {
+        let provider = RotatingKeys::new([KEY_A, KEY_B, KEY_C]).unwrap();
+        let keys: Vec<_> = (0..4)
+            .map(|_| provider.api_key().as_str().to_string())
+            .collect();
+        assert_eq!(keys, [KEY_A, KEY_B, KEY_C, KEY_A]);
+
+        assert!(RotatingKeys::new(Vec::<String>::new()).is_err());
+        assert!(RotatingKeys::new([KEY_A, "bad"]).is_err());
+    }
+
+    #[test]
+    fn test_rotating_keys_quarantine() {
+        let provider = RotatingKeys::new([KEY_A, KEY_B]).unwrap();
+
+        let a = provider.api_key();
+        provider.report(&a, StatusCode::OK);
+        provider.report(&a, StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(provider.quarantined(), 0);
+
+        provider.report(&a, StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(provider.quarantined(), 1);
+        for _ in 0..3 {
+            assert_eq!(provider.api_key().as_str(), KEY_B);
+        }
+
+        // With every key quarantined, the one released first is used.
+        let b = provider.api_key();
+        provider.report(&b, StatusCode::UNAUTHORIZED);
+        assert_eq!(provider.quarantined(), 2);
+        assert_eq!(provider.api_key().as_str(), KEY_A);
+    }
+
+    #[test]
+    fn test_rotating_keys_quarantine_expires() {
+        let provider = RotatingKeys::new([KEY_A, KEY_B])
+            .unwrap()
+            .with_quarantine(Duration::ZERO);
+        let a = provider.api_key();
+        provider.report(&a, StatusCode::UNAUTHORIZED);
+        assert_eq!(provider.quarantined(), 0);
+        assert_eq!(provider.api_key().as_str(), KEY_B);
+        assert_eq!(provider.api_key().as_str(), KEY_A);
+    }
+
+    #[test]
+    fn test_env_reloading_key() {
+        let var = "OPENROUTER_TEST_ENV_RELOADING_KEY";
+        std::env::remove_var(var);
+        assert!(matches!(
+            EnvReloadingKey::new(var),
+            Err(Error::MissingCredential(_))
+        ));
+
+        std::env::set_var(var, KEY_A);
+        let provider = EnvReloadingKey::new(var)
+            .unwrap()
+            .with_reload_interval(Duration::ZERO);
+        assert_eq!(provider.api_key().as_str(), KEY_A);
+
+        std::env::set_var(var, KEY_B);
+        assert_eq!(provider.api_key().as_str(), KEY_B);
+
+        // An invalid or missing value keeps the last good key.
+        std::env::set_var(var, "invalid");
+        assert_eq!(provider.api_key().as_str(), KEY_B);
+        std::env::remove_var(var);
+        assert_eq!(provider.api_key().as_str(), KEY_B);
+    }
+}
@@ -0,0 +1,176 @@
+//! Tracking of in-flight requests for graceful shutdown.
+
+use crate::error::{Error, Result};
+use async_stream::try_stream;
+use futures::{Stream, StreamExt};
+use std::future::Future;
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+use tokio_util::task::TaskTracker;
+
+/// The requests and streams in flight on the API handles of one client.
+///
+/// Shared by every handle the client creates; see
+/// [`OpenRouterClient::shutdown`](crate::client::OpenRouterClient::shutdown).
+#[derive(Debug, Clone, Default)]
+pub struct InFlight {
+    tracker: TaskTracker,
+    cancel: CancellationToken,
+}
+
+impl InFlight {
+    /// Number of requests and open streams.
+    pub fn len(&self) -> usize {
+        self.tracker.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tracker.is_empty()
+    }
+
+    /// Whether shutdown has started.
+    pub fn is_closed(&self) -> bool {
+        self.tracker.is_closed()
+    }
+
+    /// Stops accepting requests and waits up to `grace` for those in flight
+    /// to finish, then cancels the rest. Returns how many were cancelled.
+    pub async fn shutdown(&self, grace: Duration) -> usize {
+        self.tracker.close();
+        if tokio::time::timeout(grace, self.tracker.wait())
+            .await
+            .is_ok()
+        {
+            return 0;
+        }
+        let cancelled = self.tracker.len();
+        self.cancel.cancel();
+        cancelled
+    }
+
+    /// Runs `request` as an in-flight request, failing with
+    /// `Error::Cancelled` after shutdown.
+    pub(crate) async fn run<T>(
+        &self,
+        operation: &str,
+        request: impl Future<Output = Result<T>>,
+    ) -> Result<T> {
+        if self.tracker.is_closed() {
+            return Err(rejected(operation));
+        }
+        let _token = self.tracker.token();
+        tokio::select! {
+            biased;
+            () = self.cancel.cancelled() => Err(cancelled(operation)),
+            result = request => result,
+        }
+    }
+
+    /// Tracks `stream` until it ends or is dropped. Once cancelled, the
+    /// stream yields `Error::Cancelled` and drops the underlying stream,
+    /// closing its connection.
+    pub(crate) fn track_stream<'a, T: Send + 'a>(
+        &self,
+        operation: &str,
+        stream: impl Stream<Item = Result<T>> + Send + 'a,
+    ) -> impl Stream<Item = Result<T>> + Send + 'a {
+        let closed = self.tracker.is_closed();
+        let token = (!closed).then(|| self.tracker.token());
+        let cancel = self.cancel.clone();
+        let operation = operation.to_string();
+        try_stream! {
+            let _token = token;
+            if closed {
+                Err(rejected(&operation))?;
+            }
+            let mut stream = Box::pin(stream);
+            loop {
+                let item = tokio::select! {
+                    biased;
+                    () = cancel.cancelled() => Some(Err(cancelled(&operation))),
+                    item = stream.next() => item,
+                };
+                match item {
+                    Some(item) => yield item?,
+                    None => break,
+                }
+            }
+        }
+    }
+}
+
+fn rejected(operation: &str) -> Error {
+    Error::Cancelled(format!("{operation} requested after client shutdown"))
+}
+
+fn cancelled(operation: &str) -> Error {
+    Error::Cancelled(format!("{operation} cancelled by client shutdown"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_shutdown_drains_then_cancels_stragglers() {
+        let in_flight = InFlight::default();
+        let quick = tokio::spawn({
+            let in_flight = in_flight.clone();
+            async move {
+                in_flight
+                    .run("quick", async {
+                        tokio::time::sleep(Duration::from_millis(10)).await;
+                        Ok(1)
+                    })
+                    .await
+            }
+        });
+        let slow = tokio::spawn({
+            let in_flight = in_flight.clone();
+            async move {
+                in_flight
+                    .run("slow", async {
+                        tokio::time::sleep(Duration::from_secs(60)).await;
+                        Ok(2)
+                    })
+                    .await
+            }
+        });
+        tokio::task::yield_now().await;
+        while in_flight.len() < 2 {
+            tokio::task::yield_now().await;
+        }
+
+        assert_eq!(in_flight.shutdown(Duration::from_millis(200)).await, 1);
+        assert_eq!(quick.await.unwrap().unwrap(), 1);
+        assert!(matches!(slow.await.unwrap(), Err(Error::Cancelled(_))));
+        assert!(matches!(
+            in_flight.run("late", async { Ok(3) }).await,
+            Err(Error::Cancelled(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_tracked_stream_counts_until_dropped_and_is_cancelled() {
+        let in_flight = InFlight::default();
+        let mut stream = Box::pin(in_flight.track_stream(
+            "stream",
+            futures::stream::iter(vec![Ok(1)]).chain(futures::stream::pending()),
+        ));
+        assert_eq!(in_flight.len(), 1);
+        assert_eq!(stream.next().await.unwrap().unwrap(), 1);
+
+        assert_eq!(in_flight.shutdown(Duration::from_millis(10)).await, 1);
+        assert!(matches!(
+            stream.next().await,
+            Some(Err(Error::Cancelled(_)))
+        ));
+        assert!(stream.next().await.is_none());
+        drop(stream);
+        assert!(in_flight.is_empty());
+
+        let mut late =
+            Box::pin(in_flight.track_stream("stream", futures::stream::iter(vec![Ok(1)])));
+        assert!(matches!(late.next().await, Some(Err(Error::Cancelled(_)))));
+    }
+}
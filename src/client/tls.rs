@@ -0,0 +1,28 @@
+//! TLS version floor and, with the `cert-pinning` feature, certificate
+//! pinning.
+
+#[cfg(feature = "cert-pinning")]
+mod pinning;
+
+#[cfg(feature = "cert-pinning")]
+pub use pinning::Sha256Pin;
+#[cfg(feature = "cert-pinning")]
+pub(crate) use pinning::{PinMismatch, PinningLayer};
+
+/// Minimum TLS protocol version accepted by the client.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TlsVersion {
+    Tls12,
+    /// Only supported by the `tls-rustls` backend; with `tls-native-tls` the
+    /// HTTP client fails to build.
+    Tls13,
+}
+
+impl From<TlsVersion> for reqwest::tls::Version {
+    fn from(version: TlsVersion) -> Self {
+        match version {
+            TlsVersion::Tls12 => reqwest::tls::Version::TLS_1_2,
+            TlsVersion::Tls13 => reqwest::tls::Version::TLS_1_3,
+        }
+    }
+}
@@ -0,0 +1,192 @@
+//! Certificate pinning, behind the `cert-pinning` feature.
+//!
+//! Pins are checked by a connector layer right after the TLS handshake, so a
+//! connection to a server presenting an unpinned certificate is dropped
+//! before any request (or API key) is written to it. The check only relies on
+//! reqwest's `TlsInfo`, which both TLS backends provide.
+
+use crate::error::{Error, Result};
+use hyper_util::client::legacy::connect::Connection;
+use ring::digest::{digest, SHA256};
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tower_layer::Layer;
+use tower_service::Service;
+
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// SHA-256 fingerprint of a DER-encoded server (leaf) certificate.
+///
+/// Compute one with
+/// `openssl x509 -in cert.pem -outform der | openssl dgst -sha256`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Sha256Pin([u8; 32]);
+
+impl Sha256Pin {
+    /// Parses a hex fingerprint, with or without `:` separators.
+    pub fn from_hex(hex: &str) -> Result<Self> {
+        let digits: Vec<u8> = hex.bytes().filter(|b| *b != b':').collect();
+        let invalid = || {
+            Error::ConfigError(format!(
+                "Invalid SHA-256 pin '{hex}': expected 64 hex digits"
+            ))
+        };
+        if digits.len() != 64 {
+            return Err(invalid());
+        }
+        let mut bytes = [0u8; 32];
+        for (byte, pair) in bytes.iter_mut().zip(digits.chunks(2)) {
+            let pair = std::str::from_utf8(pair).map_err(|_| invalid())?;
+            *byte = u8::from_str_radix(pair, 16).map_err(|_| invalid())?;
+        }
+        Ok(Self(bytes))
+    }
+
+    /// Fingerprints a DER-encoded certificate.
+    pub fn from_certificate_der(der: &[u8]) -> Self {
+        let mut bytes = [0u8; 32];
+        bytes.copy_from_slice(digest(&SHA256, der).as_ref());
+        Self(bytes)
+    }
+
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+}
+
+impl FromStr for Sha256Pin {
+    type Err = Error;
+
+    fn from_str(hex: &str) -> Result<Self> {
+        Self::from_hex(hex)
+    }
+}
+
+impl fmt::Display for Sha256Pin {
+    /// Lowercase hex without separators.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.iter().try_for_each(|byte| write!(f, "{byte:02x}"))
+    }
+}
+
+impl fmt::Debug for Sha256Pin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Sha256Pin({self})")
+    }
+}
+
+/// Raised inside the connector when a certificate doesn't match; converted to
+/// `Error::CertificatePinMismatch` when the reqwest error surfaces.
+#[derive(Debug)]
+pub(crate) struct PinMismatch {
+    /// Fingerprint of the presented certificate, or `None` when the
+    /// connection had no certificate (e.g. plain HTTP).
+    pub(crate) presented: Option<Sha256Pin>,
+}
+
+impl fmt::Display for PinMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.presented {
+            Some(pin) => write!(f, "certificate {pin} does not match any pinned certificate"),
+            None => f.write_str("connection presented no certificate to check against pins"),
+        }
+    }
+}
+
+impl std::error::Error for PinMismatch {}
+
+/// Connector layer rejecting connections whose certificate is not pinned.
+///
+/// Requires `ClientBuilder::tls_info(true)` so the certificate is exposed.
+#[derive(Clone)]
+pub(crate) struct PinningLayer {
+    pins: Arc<[Sha256Pin]>,
+}
+
+impl PinningLayer {
+    pub(crate) fn new(pins: &[Sha256Pin]) -> Self {
+        Self { pins: pins.into() }
+    }
+}
+
+impl<S> Layer<S> for PinningLayer {
+    type Service = PinningService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        PinningService {
+            inner,
+            pins: Arc::clone(&self.pins),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct PinningService<S> {
+    inner: S,
+    pins: Arc<[Sha256Pin]>,
+}
+
+impl<S, R> Service<R> for PinningService<S>
+where
+    S: Service<R, Error = BoxError>,
+    S::Response: Connection + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = BoxError;
+    type Future = Pin<Box<dyn Future<Output = std::result::Result<S::Response, BoxError>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<std::result::Result<(), BoxError>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: R) -> Self::Future {
+        let connecting = self.inner.call(request);
+        let pins = Arc::clone(&self.pins);
+        Box::pin(async move {
+            let connection = connecting.await?;
+            let mut extensions = http::Extensions::new();
+            connection.connected().get_extras(&mut extensions);
+            let presented = extensions
+                .get::<reqwest::tls::TlsInfo>()
+                .and_then(|info| info.peer_certificate())
+                .map(Sha256Pin::from_certificate_der);
+            match presented {
+                Some(pin) if pins.contains(&pin) => Ok(connection),
+                presented => Err(Box::new(PinMismatch { presented }) as BoxError),
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pin_parsing_and_fingerprints() {
+        // SHA-256 of the empty input.
+        let empty = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+        assert_eq!(Sha256Pin::from_certificate_der(b"").to_string(), empty);
+
+        let colons = empty
+            .to_uppercase()
+            .as_bytes()
+            .chunks(2)
+            .map(|pair| std::str::from_utf8(pair).unwrap())
+            .collect::<Vec<_>>()
+            .join(":");
+        assert_eq!(
+            colons.parse::<Sha256Pin>().unwrap(),
+            Sha256Pin::from_hex(empty).unwrap()
+        );
+
+        assert!(Sha256Pin::from_hex("abcd").is_err());
+        assert!(Sha256Pin::from_hex(&"zz".repeat(32)).is_err());
+        assert!(Sha256Pin::from_hex(&"é".repeat(32)).is_err());
+    }
+}
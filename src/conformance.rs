@@ -0,0 +1,274 @@
+//! Conformance checks against captured OpenRouter payloads.
+//!
+//! [`check_dir`] parses every payload in a directory with the same types the
+//! client uses, so a deserialization regression, or a proxy in front of
+//! OpenRouter that rewrites responses, shows up as a failing payload rather
+//! than as an error at runtime. Payloads are grouped by subdirectory:
+//!
+//! | Directory | Contents | Parsed as |
+//! |-----------|----------|-----------|
+//! | `chat/`   | `/chat/completions` response bodies | [`ChatCompletionResponse`] |
+//! | `stream/` | raw SSE bodies of streamed completions | [`ChatCompletionChunk`] per `data:` line |
+//! | `errors/` | error response bodies | [`Error::from_response_text`] |
+//! | `models/` | `/models` response bodies | [`ModelsResponse`] |
+//!
+//! Other subdirectories and files are ignored. The crate checks its own
+//! captures in `tests/data/conformance`; point [`check_dir`] at captures
+//! taken through a gateway to run the same suite against it.
+//!
+//! ```rust,no_run
+//! let report = openrouter_api::conformance::check_dir("captures/gateway")?;
+//! for failure in report.failures() {
+//!     eprintln!("{}: {}", failure.path.display(), failure.error.as_deref().unwrap_or(""));
+//! }
+//! assert!(report.is_success());
+//! # Ok::<(), openrouter_api::Error>(())
+//! ```
+
+use crate::error::{Error, Result};
+use crate::types::chat::{ChatCompletionChunk, ChatCompletionResponse};
+use crate::types::models::ModelsResponse;
+use serde_json::Value;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// The kind of payload a capture holds, determined by its subdirectory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PayloadKind {
+    /// A non-streaming chat completion response body.
+    ChatCompletion,
+    /// A streamed chat completion, as the raw SSE body.
+    Stream,
+    /// An error response body.
+    Error,
+    /// A models list response body.
+    Models,
+}
+
+impl PayloadKind {
+    /// All kinds, in the order [`check_dir`] visits them.
+    pub const ALL: [PayloadKind; 4] = [
+        PayloadKind::ChatCompletion,
+        PayloadKind::Stream,
+        PayloadKind::Error,
+        PayloadKind::Models,
+    ];
+
+    /// The subdirectory holding captures of this kind.
+    pub fn dir_name(self) -> &'static str {
+        match self {
+            PayloadKind::ChatCompletion => "chat",
+            PayloadKind::Stream => "stream",
+            PayloadKind::Error => "errors",
+            PayloadKind::Models => "models",
+        }
+    }
+
+    /// Checks one captured payload of this kind.
+    ///
+    /// Returns a description of the first problem found.
+    pub fn check(self, payload: &str) -> std::result::Result<(), String> {
+        match self {
+            PayloadKind::ChatCompletion => check_chat_completion(payload),
+            PayloadKind::Stream => check_stream(payload),
+            PayloadKind::Error => check_error(payload),
+            PayloadKind::Models => check_models(payload),
+        }
+    }
+}
+
+impl fmt::Display for PayloadKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.dir_name())
+    }
+}
+
+/// The outcome of checking one captured payload.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PayloadResult {
+    pub path: PathBuf,
+    pub kind: PayloadKind,
+    /// Why the payload failed; `None` if it passed.
+    pub error: Option<String>,
+}
+
+impl PayloadResult {
+    /// Whether the payload passed.
+    pub fn passed(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// Results of [`check_dir`], one per payload checked.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ConformanceReport {
+    pub results: Vec<PayloadResult>,
+}
+
+impl ConformanceReport {
+    /// Whether every payload passed. An empty report is a success.
+    pub fn is_success(&self) -> bool {
+        self.results.iter().all(PayloadResult::passed)
+    }
+
+    /// The payloads that failed.
+    pub fn failures(&self) -> impl Iterator<Item = &PayloadResult> {
+        self.results.iter().filter(|result| !result.passed())
+    }
+
+    /// Number of payloads checked of the given kind.
+    pub fn count(&self, kind: PayloadKind) -> usize {
+        self.results.iter().filter(|r| r.kind == kind).count()
+    }
+}
+
+/// Checks every capture under `dir`, visiting files in name order.
+///
+/// Returns an error only if a directory or file cannot be read; payloads
+/// that fail to parse are reported in the [`ConformanceReport`].
+pub fn check_dir(dir: impl AsRef<Path>) -> Result<ConformanceReport> {
+    let dir = dir.as_ref();
+    if !dir.is_dir() {
+        return Err(Error::ConfigError(format!(
+            "Conformance directory not found: {}",
+            dir.display()
+        )));
+    }
+
+    let mut report = ConformanceReport::default();
+    for kind in PayloadKind::ALL {
+        let kind_dir = dir.join(kind.dir_name());
+        if !kind_dir.is_dir() {
+            continue;
+        }
+        let mut paths = std::fs::read_dir(&kind_dir)?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<std::io::Result<Vec<_>>>()?;
+        paths.retain(|path| path.is_file());
+        paths.sort();
+
+        for path in paths {
+            let payload = std::fs::read_to_string(&path)?;
+            let error = kind.check(&payload).err();
+            report.results.push(PayloadResult { path, kind, error });
+        }
+    }
+    Ok(report)
+}
+
+fn check_chat_completion(payload: &str) -> std::result::Result<(), String> {
+    let response: ChatCompletionResponse =
+        serde_json::from_str(payload).map_err(|e| format!("not a chat completion: {e}"))?;
+    if response.choices.is_empty() {
+        return Err("chat completion has no choices".to_string());
+    }
+    Ok(())
+}
+
+fn check_stream(payload: &str) -> std::result::Result<(), String> {
+    let mut chunks = 0;
+    for (number, line) in payload.lines().enumerate() {
+        // Comments (`: OPENROUTER PROCESSING`) and other SSE fields carry no chunk.
+        let Some(data) = line.strip_prefix("data:") else {
+            continue;
+        };
+        let data = data.trim();
+        if data == "[DONE]" {
+            break;
+        }
+        serde_json::from_str::<ChatCompletionChunk>(data)
+            .map_err(|e| format!("line {}: not a stream chunk: {e}", number + 1))?;
+        chunks += 1;
+    }
+    if chunks == 0 {
+        return Err("stream has no chunks".to_string());
+    }
+    Ok(())
+}
+
+fn check_error(payload: &str) -> std::result::Result<(), String> {
+    let body: Value =
+        serde_json::from_str(payload).map_err(|e| format!("error body is not JSON: {e}"))?;
+    let error = body
+        .get("error")
+        .ok_or_else(|| "error body has no `error` object".to_string())?;
+    if !error.get("message").is_some_and(Value::is_string) {
+        return Err("error body has no `error.message` string".to_string());
+    }
+    let status = error
+        .get("code")
+        .and_then(Value::as_u64)
+        .and_then(|code| u16::try_from(code).ok())
+        .ok_or_else(|| "error body has no numeric `error.code`".to_string())?;
+
+    match Error::from_response_text(status, payload) {
+        ref parsed @ Error::ApiError { .. } if parsed.raw_body().is_some() => Ok(()),
+        Error::ApiError { .. } => Err("error body was not kept as raw JSON".to_string()),
+        other => Err(format!("error body parsed as {}", other.code())),
+    }
+}
+
+fn check_models(payload: &str) -> std::result::Result<(), String> {
+    let response: ModelsResponse =
+        serde_json::from_str(payload).map_err(|e| format!("not a models list: {e}"))?;
+    if response.data.is_empty() {
+        return Err("models list is empty".to_string());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_stream_reports_bad_chunk_line() {
+        let stream = ": OPENROUTER PROCESSING\n\n\
+            data: {\"id\":\"gen-1\",\"object\":\"chat.completion.chunk\",\"created\":1,\"model\":\"m\",\"choices\":[]}\n\n\
+            data: {\"id\":\"gen-1\"}\n\n\
+            data: [DONE]\n\n";
+        let error = PayloadKind::Stream.check(stream).unwrap_err();
+        assert!(error.starts_with("line 5: not a stream chunk"), "{error}");
+        assert_eq!(
+            PayloadKind::Stream.check("data: [DONE]\n\n").unwrap_err(),
+            "stream has no chunks"
+        );
+    }
+
+    #[test]
+    fn test_check_error_requires_documented_shape() {
+        let body = r#"{"error": {"code": 402, "message": "Insufficient credits"}}"#;
+        assert!(PayloadKind::Error.check(body).is_ok());
+        assert_eq!(
+            PayloadKind::Error
+                .check(r#"{"error": {"message": "missing code"}}"#)
+                .unwrap_err(),
+            "error body has no numeric `error.code`"
+        );
+        assert!(PayloadKind::Error.check(r#"{"message": "flat"}"#).is_err());
+    }
+
+    #[test]
+    fn test_check_dir_collects_failures() {
+        let dir = std::env::temp_dir().join(format!("conformance-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(dir.join("chat")).unwrap();
+        std::fs::create_dir_all(dir.join("notes")).unwrap();
+        std::fs::write(dir.join("chat/bad.json"), r#"{"id": "gen-1"}"#).unwrap();
+        std::fs::write(dir.join("notes/readme.txt"), "ignored").unwrap();
+
+        let report = check_dir(&dir).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert!(!report.is_success());
+        assert_eq!(report.results.len(), 1);
+        assert_eq!(report.count(PayloadKind::ChatCompletion), 1);
+        let failure = report.failures().next().unwrap();
+        assert!(failure.path.ends_with("chat/bad.json"));
+        assert!(failure
+            .error
+            .as_deref()
+            .unwrap()
+            .starts_with("not a chat completion"));
+        assert!(check_dir(dir.join("missing")).is_err());
+    }
+}
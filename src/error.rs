@@ -3,7 +3,10 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use thiserror::Error;
 
-use crate::utils::security::create_safe_error_message;
+use crate::utils::security::{create_safe_error_message, Redactor};
+
+/// Error bodies larger than this are not kept as raw JSON.
+pub const MAX_RAW_ERROR_BODY_BYTES: usize = 16 * 1024;
 
 /// OpenRouter API error details
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -24,14 +27,18 @@ pub struct ApiErrorDetails {
 
 /// Centralized error type for the OpenRouter client library.
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum Error {
     #[error("HTTP error: {0}")]
-    HttpError(#[from] reqwest::Error),
+    HttpError(#[source] reqwest::Error),
 
     #[error("API error (status {code}): {message}")]
     ApiError {
         code: u16,
+        /// Sanitized summary, safe to log.
         message: String,
+        /// For errors built from a response, includes the redacted body;
+        /// see [`Error::raw_body`] and [`Error::provider_error`].
         metadata: Option<Value>,
     },
 
@@ -75,7 +82,57 @@ pub enum Error {
     ResourceExhausted(String),
 
     #[error("Deserialization error (status {status_code}): {message}")]
-    DeserializationError { message: String, status_code: u16 },
+    DeserializationError {
+        message: String,
+        status_code: u16,
+        /// The underlying decode error, unless its details were withheld.
+        #[source]
+        source: Option<serde_json::Error>,
+    },
+
+    #[error("I/O error: {0}")]
+    IoError(#[from] std::io::Error),
+
+    #[error("Budget exceeded: {window} spend ${spent:.4} has reached the ${limit:.4} cap")]
+    BudgetExceeded {
+        window: crate::utils::budget::BudgetWindow,
+        limit: f64,
+        spent: f64,
+    },
+
+    #[error("Cancelled: {0}")]
+    Cancelled(String),
+
+    #[error(
+        "Unknown provider slugs: {}",
+        crate::models::provider_preferences::describe_unknown_slugs(.0)
+    )]
+    UnknownProviderSlugs(Vec<crate::models::provider_preferences::UnknownProviderSlug>),
+
+    /// The server's certificate did not match any pin configured with
+    /// `with_pinned_certificates` (`cert-pinning` feature); no request was
+    /// sent over the connection.
+    #[error(
+        "Certificate for {host} does not match any pinned certificate (presented: {})",
+        .presented.as_deref().unwrap_or("none")
+    )]
+    CertificatePinMismatch {
+        host: String,
+        /// Hex SHA-256 of the presented certificate, if there was one.
+        presented: Option<String>,
+    },
+
+    /// An MCP server answered with a JSON-RPC error.
+    #[error("MCP error ({code}): {message}")]
+    McpError {
+        code: crate::mcp::types::McpErrorCode,
+        message: String,
+        data: Option<Value>,
+    },
+
+    /// The MCP server did not advertise the capability a method needs.
+    #[error("MCP server does not support {capability} (required by {method})")]
+    CapabilityNotSupported { capability: String, method: String },
 
     #[error("Unknown error")]
     Unknown,
@@ -83,6 +140,30 @@ pub enum Error {
 
 pub type Result<T> = std::result::Result<T, Error>;
 
+impl From<reqwest::Error> for Error {
+    fn from(error: reqwest::Error) -> Self {
+        // Pin mismatches are raised inside the connector.
+        #[cfg(feature = "cert-pinning")]
+        {
+            let mut source = std::error::Error::source(&error);
+            while let Some(cause) = source {
+                if let Some(mismatch) = cause.downcast_ref::<crate::client::tls::PinMismatch>() {
+                    return Error::CertificatePinMismatch {
+                        host: error
+                            .url()
+                            .and_then(|url| url.host_str())
+                            .unwrap_or("unknown host")
+                            .to_string(),
+                        presented: mismatch.presented.map(|pin| pin.to_string()),
+                    };
+                }
+                source = cause.source();
+            }
+        }
+        Error::HttpError(error)
+    }
+}
+
 impl Error {
     /// Creates an API error from a given HTTP response.
     pub async fn from_response(response: Response) -> Self {
@@ -95,11 +176,15 @@ impl Error {
     pub fn from_response_text(status: u16, text: &str) -> Self {
         // Try to parse structured API error response
         if let Ok(api_error) = serde_json::from_str::<ApiErrorDetails>(text) {
+            let raw_body = raw_error_body(text);
+            let provider_error = raw_body.as_ref().and_then(provider_error_from_body);
             return Error::ApiError {
                 code: status,
                 message: create_safe_error_message(text, "API error occurred"),
                 metadata: Some(serde_json::json!({
                     "original_response": api_error,
+                    "raw_body": raw_body,
+                    "provider_error": provider_error,
                     "response_text_length": text.len(),
                     "timestamp": chrono::Utc::now().to_rfc3339()
                 })),
@@ -126,5 +211,117 @@ impl Error {
     }
 }
 
+impl Error {
+    /// A stable, machine-readable code for the kind of failure, for mapping
+    /// errors onto an application's own taxonomy or telemetry.
+    ///
+    /// Codes are `snake_case` and never change once released. API errors
+    /// are refined by status, and HTTP errors by whether they timed out or
+    /// failed to connect.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Error::HttpError(e) if e.is_timeout() => "http_timeout",
+            Error::HttpError(e) if e.is_connect() => "http_connect",
+            Error::HttpError(_) => "http_error",
+            Error::ApiError { code, .. } => match code {
+                400 => "api_bad_request",
+                401 => "api_unauthorized",
+                402 => "api_insufficient_credits",
+                403 => "api_forbidden",
+                404 => "api_not_found",
+                408 => "api_timeout",
+                429 => "api_rate_limited",
+                500..=599 => "api_server_error",
+                _ => "api_error",
+            },
+            Error::RateLimitExceeded(_) => "rate_limited",
+            Error::ConfigError(_) => "config_error",
+            Error::StructuredOutputNotSupported => "structured_output_unsupported",
+            Error::SchemaValidationError(_) => "schema_validation_error",
+            Error::ValidationError(_) => "validation_error",
+            Error::SerializationError(_) => "serialization_error",
+            Error::ModelNotAvailable(_) => "model_not_available",
+            Error::MissingCredential(_) => "missing_credential",
+            Error::StreamingError(_) => "streaming_error",
+            Error::ContextLengthExceeded { .. } => "context_length_exceeded",
+            Error::TimeoutError(_) => "timeout",
+            Error::ResponseTooLarge(..) => "response_too_large",
+            Error::ResourceExhausted(_) => "resource_exhausted",
+            Error::DeserializationError { .. } => "deserialization_error",
+            Error::IoError(_) => "io_error",
+            Error::BudgetExceeded { .. } => "budget_exceeded",
+            Error::Cancelled(_) => "cancelled",
+            Error::UnknownProviderSlugs(_) => "unknown_provider_slugs",
+            Error::CertificatePinMismatch { .. } => "certificate_pin_mismatch",
+            Error::McpError { code, .. } => match code {
+                crate::mcp::types::McpErrorCode::ParseError => "mcp_parse_error",
+                crate::mcp::types::McpErrorCode::InvalidRequest => "mcp_invalid_request",
+                crate::mcp::types::McpErrorCode::MethodNotFound => "mcp_method_not_found",
+                crate::mcp::types::McpErrorCode::InvalidParams => "mcp_invalid_params",
+                crate::mcp::types::McpErrorCode::Internal => "mcp_internal_error",
+                crate::mcp::types::McpErrorCode::ServerDefined(_) => "mcp_error",
+            },
+            Error::CapabilityNotSupported { .. } => "capability_not_supported",
+            Error::Unknown => "unknown",
+        }
+    }
+
+    /// The JSON body of the error response, with secrets and API keys
+    /// redacted. Only kept for bodies up to [`MAX_RAW_ERROR_BODY_BYTES`].
+    pub fn raw_body(&self) -> Option<&Value> {
+        self.metadata_field("raw_body")
+    }
+
+    /// The upstream provider's error details (`error.metadata` in
+    /// OpenRouter's error format), with a JSON `raw` payload parsed.
+    ///
+    /// Useful when debugging provider-specific failures; the shape varies
+    /// by provider.
+    pub fn provider_error(&self) -> Option<&Value> {
+        self.metadata_field("provider_error")
+    }
+
+    fn metadata_field(&self, key: &str) -> Option<&Value> {
+        match self {
+            Error::ApiError {
+                metadata: Some(metadata),
+                ..
+            } => metadata.get(key).filter(|value| !value.is_null()),
+            _ => None,
+        }
+    }
+}
+
+/// Parses and redacts an error body small enough to keep.
+fn raw_error_body(text: &str) -> Option<Value> {
+    if text.len() > MAX_RAW_ERROR_BODY_BYTES {
+        return None;
+    }
+    let body = serde_json::from_str::<Value>(text).ok()?;
+    Some(error_body_redactor().redact(&body))
+}
+
+/// Extracts `error.metadata` from a redacted body, parsing a provider's
+/// `raw` payload when it is JSON.
+fn provider_error_from_body(body: &Value) -> Option<Value> {
+    let mut provider_error = body.pointer("/error/metadata")?.clone();
+    let parsed = provider_error
+        .get("raw")
+        .and_then(Value::as_str)
+        .and_then(|raw| serde_json::from_str::<Value>(raw).ok());
+    if let Some(parsed) = parsed {
+        provider_error["raw"] = error_body_redactor().redact(&parsed);
+    }
+    Some(provider_error)
+}
+
+/// Masks secrets but keeps message text, which error bodies rarely echo and
+/// which is usually what explains the error.
+fn error_body_redactor() -> Redactor {
+    Redactor::new()
+        .mask_message_content(false)
+        .mask_tool_arguments(false)
+}
+
 #[cfg(test)]
 mod tests;
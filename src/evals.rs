@@ -0,0 +1,795 @@
+//! Evaluation harness for running prompt suites against models.
+//!
+//! Define a set of [`EvalCase`]s, attach one or more [`Scorer`]s and run the
+//! suite against any number of models with [`EvalSuite::run`]. Each case is
+//! sent through [`ChatApi::chat_completion`], scored, and collected into an
+//! [`EvalReport`].
+//!
+//! Built-in scorers cover exact matches, regular expressions, JSON-schema
+//! validity and LLM-as-judge grading; closures can be used via [`FnScorer`].
+
+use crate::api::chat::ChatApi;
+use crate::error::{Error, Result};
+use crate::types::chat::{ChatCompletionRequest, ChatRole, Message};
+use futures::stream::{self, StreamExt};
+use regex::Regex;
+use serde_json::Value;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, LazyLock};
+use std::time::{Duration, Instant};
+
+/// Default number of cases run concurrently.
+const DEFAULT_CONCURRENCY: usize = 4;
+
+/// A `Score: N` line anywhere in a judge reply.
+static SCORE_LINE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?im)^\W*score\W*?[:=][\s*_]*(\d+(?:\.\d+)?)").expect("static regex is valid")
+});
+
+/// A bare number opening a judge reply.
+static LEADING_SCORE_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\s*(\d+(?:\.\d+)?)\b").expect("static regex is valid"));
+
+/// Future returned by [`Scorer::score`].
+pub type ScoreFuture<'a> = Pin<Box<dyn Future<Output = Result<Score>> + Send + 'a>>;
+
+/// A single evaluation input with an optional expected answer.
+#[derive(Debug, Clone)]
+pub struct EvalCase {
+    pub name: String,
+    pub messages: Vec<Message>,
+    pub expected: Option<String>,
+}
+
+impl EvalCase {
+    /// Creates a case from a conversation.
+    pub fn new(name: impl Into<String>, messages: Vec<Message>) -> Self {
+        Self {
+            name: name.into(),
+            messages,
+            expected: None,
+        }
+    }
+
+    /// Creates a case from a single user prompt.
+    pub fn prompt(name: impl Into<String>, prompt: impl Into<String>) -> Self {
+        Self::new(name, vec![Message::text(ChatRole::User, prompt)])
+    }
+
+    /// Sets the expected answer used by scorers such as [`ExactMatch`].
+    pub fn with_expected(mut self, expected: impl Into<String>) -> Self {
+        self.expected = Some(expected.into());
+        self
+    }
+}
+
+/// The result of scoring one model output.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Score {
+    /// Normalized score between 0.0 and 1.0.
+    pub value: f64,
+    /// Whether the output is considered a pass.
+    pub passed: bool,
+    /// Optional explanation, e.g. a judge's rationale.
+    pub reason: Option<String>,
+}
+
+impl Score {
+    /// A passing score of 1.0.
+    pub fn pass() -> Self {
+        Self {
+            value: 1.0,
+            passed: true,
+            reason: None,
+        }
+    }
+
+    /// A failing score of 0.0 with a reason.
+    pub fn fail(reason: impl Into<String>) -> Self {
+        Self {
+            value: 0.0,
+            passed: false,
+            reason: Some(reason.into()),
+        }
+    }
+
+    /// A score from a boolean outcome.
+    pub fn from_bool(passed: bool) -> Self {
+        if passed {
+            Self::pass()
+        } else {
+            Self {
+                value: 0.0,
+                passed: false,
+                reason: None,
+            }
+        }
+    }
+}
+
+/// Scores a model output for a given case.
+pub trait Scorer: Send + Sync {
+    /// Short name used as the key in reports.
+    fn name(&self) -> &str;
+
+    /// Scores `output` produced for `case`.
+    fn score<'a>(&'a self, case: &'a EvalCase, output: &'a str) -> ScoreFuture<'a>;
+}
+
+/// Passes when the output equals the case's expected answer.
+///
+/// Leading and trailing whitespace is ignored. Cases without an expected
+/// answer fail.
+#[derive(Debug, Clone, Default)]
+pub struct ExactMatch {
+    case_insensitive: bool,
+}
+
+impl ExactMatch {
+    /// Creates a case-sensitive exact-match scorer.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Compares outputs case-insensitively.
+    pub fn case_insensitive(mut self) -> Self {
+        self.case_insensitive = true;
+        self
+    }
+}
+
+impl Scorer for ExactMatch {
+    fn name(&self) -> &str {
+        "exact_match"
+    }
+
+    fn score<'a>(&'a self, case: &'a EvalCase, output: &'a str) -> ScoreFuture<'a> {
+        let score = match &case.expected {
+            None => Score::fail("case has no expected answer"),
+            Some(expected) if self.case_insensitive => {
+                Score::from_bool(expected.trim().to_lowercase() == output.trim().to_lowercase())
+            }
+            Some(expected) => Score::from_bool(expected.trim() == output.trim()),
+        };
+        Box::pin(async move { Ok(score) })
+    }
+}
+
+/// Passes when the output matches a regular expression.
+#[derive(Debug, Clone)]
+pub struct RegexMatch {
+    pattern: Regex,
+}
+
+impl RegexMatch {
+    /// Compiles `pattern`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::ConfigError` if the pattern is invalid.
+    pub fn new(pattern: &str) -> Result<Self> {
+        let pattern = Regex::new(pattern)
+            .map_err(|e| Error::ConfigError(format!("Invalid eval regex: {e}")))?;
+        Ok(Self { pattern })
+    }
+}
+
+impl Scorer for RegexMatch {
+    fn name(&self) -> &str {
+        "regex"
+    }
+
+    fn score<'a>(&'a self, _case: &'a EvalCase, output: &'a str) -> ScoreFuture<'a> {
+        let score = Score::from_bool(self.pattern.is_match(output));
+        Box::pin(async move { Ok(score) })
+    }
+}
+
+/// Passes when the output parses as JSON and satisfies a schema.
+///
+/// Validation covers the top-level `type` and `required` fields, matching
+/// the checks used for structured outputs.
+#[derive(Debug, Clone)]
+pub struct JsonSchemaValid {
+    schema: Value,
+}
+
+impl JsonSchemaValid {
+    /// Creates a scorer validating against `schema`.
+    pub fn new(schema: Value) -> Self {
+        Self { schema }
+    }
+}
+
+impl Scorer for JsonSchemaValid {
+    fn name(&self) -> &str {
+        "json_schema"
+    }
+
+    fn score<'a>(&'a self, _case: &'a EvalCase, output: &'a str) -> ScoreFuture<'a> {
+        let score = match serde_json::from_str::<Value>(output.trim()) {
+            Err(e) => Score::fail(format!("output is not valid JSON: {e}")),
+            Ok(value) => {
+                match crate::api::structured::basic_schema_validation(&self.schema, &value) {
+                    Ok(()) => Score::pass(),
+                    Err(e) => Score::fail(e.to_string()),
+                }
+            }
+        };
+        Box::pin(async move { Ok(score) })
+    }
+}
+
+/// Grades outputs with a second chat call to a judge model.
+///
+/// The judge is asked to answer with `Score: N` (0 to 10) on the first line,
+/// followed by a short justification. Scores at or above the threshold
+/// (7 by default) pass.
+pub struct LlmJudge {
+    chat: Arc<ChatApi>,
+    model: String,
+    rubric: String,
+    pass_threshold: f64,
+}
+
+impl LlmJudge {
+    /// Creates a judge using `model` and a grading rubric.
+    pub fn new(chat: Arc<ChatApi>, model: impl Into<String>, rubric: impl Into<String>) -> Self {
+        Self {
+            chat,
+            model: model.into(),
+            rubric: rubric.into(),
+            pass_threshold: 0.7,
+        }
+    }
+
+    /// Sets the normalized score (0.0 to 1.0) required to pass.
+    pub fn with_pass_threshold(mut self, threshold: f64) -> Self {
+        self.pass_threshold = threshold.clamp(0.0, 1.0);
+        self
+    }
+
+    fn judge_messages(&self, case: &EvalCase, output: &str) -> Vec<Message> {
+        let conversation = case
+            .messages
+            .iter()
+            .map(|m| format!("{}: {}", m.role, m.content.to_text()))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let expected = case
+            .expected
+            .as_deref()
+            .map(|e| format!("\n\nReference answer:\n{e}"))
+            .unwrap_or_default();
+
+        vec![
+            Message::text(
+                ChatRole::System,
+                format!(
+                    "You are grading an AI assistant's answer.\n{}\n\nReply with `Score: N` \
+                     on the first line, where N is an integer from 0 to 10, then a \
+                     one-sentence justification.",
+                    self.rubric
+                ),
+            ),
+            Message::text(
+                ChatRole::User,
+                format!("Conversation:\n{conversation}{expected}\n\nAnswer to grade:\n{output}"),
+            ),
+        ]
+    }
+}
+
+impl std::fmt::Debug for LlmJudge {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LlmJudge")
+            .field("model", &self.model)
+            .field("rubric", &self.rubric)
+            .field("pass_threshold", &self.pass_threshold)
+            .finish()
+    }
+}
+
+impl Scorer for LlmJudge {
+    fn name(&self) -> &str {
+        "llm_judge"
+    }
+
+    fn score<'a>(&'a self, case: &'a EvalCase, output: &'a str) -> ScoreFuture<'a> {
+        Box::pin(async move {
+            let request = ChatCompletionRequest {
+                model: self.model.clone(),
+                messages: self.judge_messages(case, output),
+                temperature: Some(0.0),
+                ..Default::default()
+            };
+            let response = self.chat.chat_completion(request).await?;
+            let verdict = response.text().unwrap_or_default();
+            let raw = parse_judge_score(verdict).ok_or_else(|| {
+                Error::ValidationError(format!("Judge reply has no 0-10 score: {verdict:?}"))
+            })?;
+            let value = raw / 10.0;
+            Ok(Score {
+                value,
+                passed: value >= self.pass_threshold,
+                reason: Some(verdict.trim().to_string()),
+            })
+        })
+    }
+}
+
+/// Extracts the 0-10 score from a judge reply.
+///
+/// Looks for a `Score: N` line first and otherwise accepts a bare number
+/// opening the reply; numbers elsewhere in the justification are ignored, as
+/// are scores outside 0..=10.
+fn parse_judge_score(reply: &str) -> Option<f64> {
+    SCORE_LINE_REGEX
+        .captures(reply)
+        .or_else(|| LEADING_SCORE_REGEX.captures(reply))
+        .and_then(|captures| captures[1].parse::<f64>().ok())
+        .filter(|score| (0.0..=10.0).contains(score))
+}
+
+/// Adapts a closure into a [`Scorer`].
+pub struct FnScorer<F> {
+    name: String,
+    f: F,
+}
+
+impl<F> FnScorer<F>
+where
+    F: Fn(&EvalCase, &str) -> Score + Send + Sync,
+{
+    /// Wraps `f` under the given scorer name.
+    pub fn new(name: impl Into<String>, f: F) -> Self {
+        Self {
+            name: name.into(),
+            f,
+        }
+    }
+}
+
+impl<F> Scorer for FnScorer<F>
+where
+    F: Fn(&EvalCase, &str) -> Score + Send + Sync,
+{
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn score<'a>(&'a self, case: &'a EvalCase, output: &'a str) -> ScoreFuture<'a> {
+        let score = (self.f)(case, output);
+        Box::pin(async move { Ok(score) })
+    }
+}
+
+/// The outcome of one case run against one model.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct EvalResult {
+    pub model: String,
+    pub case: String,
+    /// The model output, if the request succeeded.
+    pub output: Option<String>,
+    /// The request or scoring error, if any.
+    pub error: Option<String>,
+    /// Scores keyed by scorer name, in scorer order.
+    pub scores: Vec<(String, Score)>,
+    pub latency: Duration,
+    /// Cost reported in the response usage.
+    pub cost: Option<f64>,
+}
+
+impl EvalResult {
+    /// A case passes when the request succeeded and every scorer passed.
+    pub fn passed(&self) -> bool {
+        self.error.is_none() && self.scores.iter().all(|(_, score)| score.passed)
+    }
+
+    /// Mean of the scorer values, or 0.0 if the case errored.
+    pub fn mean_score(&self) -> f64 {
+        if self.error.is_some() || self.scores.is_empty() {
+            return 0.0;
+        }
+        self.scores.iter().map(|(_, s)| s.value).sum::<f64>() / self.scores.len() as f64
+    }
+}
+
+/// Aggregate results for one model.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct ModelSummary {
+    pub model: String,
+    pub cases: usize,
+    pub passed: usize,
+    pub errors: usize,
+    pub mean_score: f64,
+    pub total_cost: f64,
+    pub mean_latency: Duration,
+}
+
+impl ModelSummary {
+    /// Fraction of cases that passed.
+    pub fn pass_rate(&self) -> f64 {
+        if self.cases == 0 {
+            0.0
+        } else {
+            self.passed as f64 / self.cases as f64
+        }
+    }
+}
+
+/// Results of running an [`EvalSuite`].
+#[derive(Debug, Clone, Default)]
+#[non_exhaustive]
+pub struct EvalReport {
+    /// Results ordered by model, then by case.
+    pub results: Vec<EvalResult>,
+}
+
+impl EvalReport {
+    /// Results for a single model.
+    pub fn for_model<'a>(&'a self, model: &'a str) -> impl Iterator<Item = &'a EvalResult> {
+        self.results.iter().filter(move |r| r.model == model)
+    }
+
+    /// Per-model summaries in the order the models were run.
+    pub fn summaries(&self) -> Vec<ModelSummary> {
+        let mut models: Vec<&str> = Vec::new();
+        for result in &self.results {
+            if !models.contains(&result.model.as_str()) {
+                models.push(&result.model);
+            }
+        }
+
+        models
+            .into_iter()
+            .map(|model| {
+                let results: Vec<_> = self.for_model(model).collect();
+                let cases = results.len();
+                let total_latency: Duration = results.iter().map(|r| r.latency).sum();
+                ModelSummary {
+                    model: model.to_string(),
+                    cases,
+                    passed: results.iter().filter(|r| r.passed()).count(),
+                    errors: results.iter().filter(|r| r.error.is_some()).count(),
+                    mean_score: results.iter().map(|r| r.mean_score()).sum::<f64>()
+                        / cases.max(1) as f64,
+                    total_cost: results.iter().filter_map(|r| r.cost).sum(),
+                    mean_latency: total_latency / cases.max(1) as u32,
+                }
+            })
+            .collect()
+    }
+
+    /// The summary for a single model.
+    pub fn summary(&self, model: &str) -> Option<ModelSummary> {
+        self.summaries().into_iter().find(|s| s.model == model)
+    }
+}
+
+/// A set of cases and scorers that can be run against models.
+pub struct EvalSuite {
+    cases: Vec<EvalCase>,
+    scorers: Vec<Arc<dyn Scorer>>,
+    concurrency: usize,
+    request_template: ChatCompletionRequest,
+}
+
+impl EvalSuite {
+    /// Creates a suite from cases.
+    pub fn new(cases: Vec<EvalCase>) -> Self {
+        Self {
+            cases,
+            scorers: Vec::new(),
+            concurrency: DEFAULT_CONCURRENCY,
+            request_template: ChatCompletionRequest::default(),
+        }
+    }
+
+    /// Adds a scorer; every case is scored by every scorer.
+    pub fn with_scorer(mut self, scorer: impl Scorer + 'static) -> Self {
+        self.scorers.push(Arc::new(scorer));
+        self
+    }
+
+    /// Sets how many requests may be in flight at once (minimum 1).
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Sets request parameters (temperature, max tokens, ...) applied to every
+    /// case. The template's `model` and `messages` are replaced per run.
+    pub fn with_request_template(mut self, template: ChatCompletionRequest) -> Self {
+        self.request_template = template;
+        self
+    }
+
+    /// Returns the cases in the suite.
+    pub fn cases(&self) -> &[EvalCase] {
+        &self.cases
+    }
+
+    /// Runs every case against every model and scores the outputs.
+    ///
+    /// Request failures are recorded per result rather than aborting the run.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::ValidationError` if there are no cases, models or
+    /// scorers.
+    pub async fn run(&self, chat: &ChatApi, models: &[&str]) -> Result<EvalReport> {
+        if self.cases.is_empty() || models.is_empty() || self.scorers.is_empty() {
+            return Err(Error::ValidationError(
+                "An eval run needs at least one case, model and scorer".into(),
+            ));
+        }
+
+        let jobs = models
+            .iter()
+            .flat_map(|model| self.cases.iter().map(move |case| (*model, case)))
+            .enumerate();
+
+        let mut results: Vec<(usize, EvalResult)> =
+            stream::iter(jobs)
+                .map(|(order, (model, case))| async move {
+                    (order, self.run_case(chat, model, case).await)
+                })
+                .buffer_unordered(self.concurrency)
+                .collect()
+                .await;
+        results.sort_by_key(|(order, _)| *order);
+
+        Ok(EvalReport {
+            results: results.into_iter().map(|(_, result)| result).collect(),
+        })
+    }
+
+    async fn run_case(&self, chat: &ChatApi, model: &str, case: &EvalCase) -> EvalResult {
+        let request = ChatCompletionRequest {
+            model: model.to_string(),
+            messages: case.messages.clone(),
+            ..self.request_template.clone()
+        };
+
+        let started = Instant::now();
+        let response = chat.chat_completion(request).await;
+        let latency = started.elapsed();
+
+        let mut result = EvalResult {
+            model: model.to_string(),
+            case: case.name.clone(),
+            output: None,
+            error: None,
+            scores: Vec::new(),
+            latency,
+            cost: None,
+        };
+
+        let response = match response {
+            Ok(response) => response,
+            Err(e) => {
+                result.error = Some(e.to_string());
+                return result;
+            }
+        };
+        result.cost = response.usage.as_ref().and_then(|u| u.cost);
+        let output = response
+            .first_choice()
+            .map(|choice| choice.message.content.to_text())
+            .unwrap_or_default();
+
+        for scorer in &self.scorers {
+            match scorer.score(case, &output).await {
+                Ok(score) => result.scores.push((scorer.name().to_string(), score)),
+                Err(e) => {
+                    result.error = Some(format!("scorer '{}' failed: {e}", scorer.name()));
+                    break;
+                }
+            }
+        }
+        result.output = Some(output);
+        result
+    }
+}
+
+impl std::fmt::Debug for EvalSuite {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EvalSuite")
+            .field("cases", &self.cases.len())
+            .field(
+                "scorers",
+                &self.scorers.iter().map(|s| s.name()).collect::<Vec<_>>(),
+            )
+            .field("concurrency", &self.concurrency)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::test_helpers::test_client_config;
+    use serde_json::json;
+    use wiremock::{matchers, Mock, MockServer, ResponseTemplate};
+
+    fn chat_response(content: &str) -> Value {
+        json!({
+            "id": "gen-1",
+            "choices": [{
+                "message": {"role": "assistant", "content": content},
+                "finish_reason": "stop"
+            }],
+            "created": 0,
+            "model": "m",
+            "object": "chat.completion",
+            "usage": {"prompt_tokens": 1, "completion_tokens": 1, "total_tokens": 2, "cost": 0.001}
+        })
+    }
+
+    async fn mock_chat(server: &MockServer, model: &str, content: &str) {
+        Mock::given(matchers::method("POST"))
+            .and(matchers::path("/chat/completions"))
+            .and(matchers::body_partial_json(json!({"model": model})))
+            .respond_with(ResponseTemplate::new(200).set_body_json(chat_response(content)))
+            .mount(server)
+            .await;
+    }
+
+    fn chat_for(server: &MockServer) -> ChatApi {
+        let mut config = test_client_config();
+        config.base_url = format!("{}/", server.uri()).parse().unwrap();
+        ChatApi::new(reqwest::Client::new(), &config).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_builtin_scorers() {
+        let case = EvalCase::prompt("capital", "Capital of France?").with_expected("Paris");
+
+        assert!(
+            ExactMatch::new()
+                .score(&case, " Paris\n")
+                .await
+                .unwrap()
+                .passed
+        );
+        assert!(
+            !ExactMatch::new()
+                .score(&case, "paris")
+                .await
+                .unwrap()
+                .passed
+        );
+        assert!(
+            ExactMatch::new()
+                .case_insensitive()
+                .score(&case, "paris")
+                .await
+                .unwrap()
+                .passed
+        );
+
+        let regex = RegexMatch::new(r"(?i)\bparis\b").unwrap();
+        assert!(regex.score(&case, "It is Paris.").await.unwrap().passed);
+        assert!(matches!(RegexMatch::new("("), Err(Error::ConfigError(_))));
+
+        let schema = JsonSchemaValid::new(json!({"type": "object", "required": ["city"]}));
+        assert!(
+            schema
+                .score(&case, r#"{"city": "Paris"}"#)
+                .await
+                .unwrap()
+                .passed
+        );
+        assert!(
+            !schema
+                .score(&case, r#"{"town": "Paris"}"#)
+                .await
+                .unwrap()
+                .passed
+        );
+        assert!(!schema.score(&case, "Paris").await.unwrap().passed);
+    }
+
+    #[test]
+    fn test_parse_judge_score() {
+        assert_eq!(parse_judge_score("8\nGood answer."), Some(8.0));
+        assert_eq!(parse_judge_score("Score: 7.5/10"), Some(7.5));
+        assert_eq!(
+            parse_judge_score("Covers 3 of the 4 points.\n**Score:** 6"),
+            Some(6.0)
+        );
+        assert_eq!(
+            parse_judge_score("The answer lists 2 causes but misses 1."),
+            None
+        );
+        assert_eq!(parse_judge_score("42"), None);
+        assert_eq!(parse_judge_score("no idea"), None);
+    }
+
+    #[tokio::test]
+    async fn test_suite_runs_models_and_summarizes() {
+        let server = MockServer::start().await;
+        mock_chat(&server, "good/model", "Paris").await;
+        mock_chat(&server, "bad/model", "Lyon").await;
+
+        let chat = chat_for(&server);
+        let suite = EvalSuite::new(vec![
+            EvalCase::prompt("capital", "Capital of France?").with_expected("Paris"),
+            EvalCase::prompt("capital-again", "And again?").with_expected("Paris"),
+        ])
+        .with_scorer(ExactMatch::new())
+        .with_scorer(FnScorer::new("short", |_: &EvalCase, out: &str| {
+            Score::from_bool(out.len() < 10)
+        }))
+        .with_concurrency(2);
+
+        let report = suite
+            .run(&chat, &["good/model", "bad/model"])
+            .await
+            .unwrap();
+        assert_eq!(report.results.len(), 4);
+        assert_eq!(report.results[0].model, "good/model");
+        assert_eq!(report.results[0].case, "capital");
+        assert_eq!(report.results[3].case, "capital-again");
+
+        let good = report.summary("good/model").unwrap();
+        assert_eq!(good.passed, 2);
+        assert!((good.pass_rate() - 1.0).abs() < f64::EPSILON);
+        assert!((good.total_cost - 0.002).abs() < 1e-9);
+
+        let bad = report.summary("bad/model").unwrap();
+        assert_eq!(bad.passed, 0);
+        assert!((bad.mean_score - 0.5).abs() < f64::EPSILON);
+    }
+
+    #[tokio::test]
+    async fn test_llm_judge_scores_with_second_call() {
+        let server = MockServer::start().await;
+        mock_chat(&server, "candidate/model", "Paris").await;
+        mock_chat(&server, "judge/model", "9\nCorrect and concise.").await;
+
+        let chat = Arc::new(chat_for(&server));
+        let suite =
+            EvalSuite::new(vec![EvalCase::prompt("capital", "Capital of France?")]).with_scorer(
+                LlmJudge::new(chat.clone(), "judge/model", "Grade factual accuracy."),
+            );
+
+        let report = suite.run(&chat, &["candidate/model"]).await.unwrap();
+        let result = &report.results[0];
+        assert!(result.passed());
+        assert_eq!(result.scores[0].0, "llm_judge");
+        assert!((result.scores[0].1.value - 0.9).abs() < 1e-9);
+        assert_eq!(
+            result.scores[0].1.reason.as_deref(),
+            Some("9\nCorrect and concise.")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_request_errors_are_recorded() {
+        let server = MockServer::start().await;
+        Mock::given(matchers::method("POST"))
+            .respond_with(ResponseTemplate::new(400).set_body_json(json!({
+                "error": {"code": 400, "message": "bad model"}
+            })))
+            .mount(&server)
+            .await;
+
+        let chat = chat_for(&server);
+        let suite =
+            EvalSuite::new(vec![EvalCase::prompt("c", "hi")]).with_scorer(ExactMatch::new());
+        let report = suite.run(&chat, &["x/y"]).await.unwrap();
+        assert!(report.results[0].error.is_some());
+        assert!(!report.results[0].passed());
+        assert_eq!(report.summary("x/y").unwrap().errors, 1);
+
+        assert!(matches!(
+            EvalSuite::new(vec![]).run(&chat, &["x/y"]).await,
+            Err(Error::ValidationError(_))
+        ));
+    }
+}
@@ -0,0 +1,4 @@
+//! Conversions between this crate's types and other wire formats.
+
+#[cfg(feature = "interop-openai")]
+pub mod openai;
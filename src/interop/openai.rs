@@ -0,0 +1,624 @@
+//! The OpenAI chat completions wire format (`interop-openai` feature).
+//!
+//! The structs here serialize exactly like the requests and responses of
+//! OpenAI's `/v1/chat/completions`, so they can be exchanged with OpenAI
+//! SDKs or code written against them, and convert to and from
+//! [`ChatCompletionRequest`] and [`ChatCompletionResponse`].
+//!
+//! # Field mapping
+//!
+//! Fields OpenAI knows keep their name. OpenRouter-only fields travel in the
+//! `extra_body` / `extra` maps, which are flattened into the JSON like an
+//! OpenAI SDK's `extra_body`:
+//!
+//! | This crate | OpenAI wire format |
+//! |---|---|
+//! | `ChatRole::System` | `system`; `developer` converts to `System` |
+//! | `max_tokens` | `max_tokens`; `max_completion_tokens` is used when `max_tokens` is unset |
+//! | `logit_bias` (token IDs) | `logit_bias` (token IDs as strings) |
+//! | `response_format` | `response_format` of type `json_schema`; `text` converts to `None` |
+//! | `stream` | `stream: true` while streaming |
+//! | `provider`, `models`, `transforms`, `route`, `top_k`, `repetition_penalty`, `min_p`, `top_a`, `verbosity`, `debug`, `plugins`, `reasoning` | `extra_body` |
+//! | `extra_body` | `extra_body`, for keys not listed above |
+//! | `Message::reasoning`, `reasoning_details` | message `extra` |
+//! | `Choice::native_finish_reason` | choice `extra` |
+//! | `Usage::cost`, `is_byok` | usage `extra` |
+//! | `prompt_tokens_details.cached_tokens`, `completion_tokens_details.reasoning_tokens` | same; other details are dropped |
+//!
+//! Converting a request into this crate's type fails for values it can't
+//! represent, such as a `json_object` response format or a malformed
+//! `provider`.
+
+use crate::api::request::ResponseFormatConfig;
+use crate::error::{Error, Result};
+use crate::models::provider_preferences::ProviderPreferences;
+use crate::models::structured::JsonSchemaConfig;
+use crate::models::tool::{Tool, ToolCall, ToolChoice};
+use crate::types::chat::{
+    ChatCompletionRequest, ChatCompletionResponse, ChatRole, DebugConfig, LogProbs, LogitBias,
+    Message, MessageContent, Plugin, PredictionConfig, ReasoningConfig, ReasoningDetail,
+    RouteStrategy, StopSequence, VerbosityLevel,
+};
+use crate::types::ids::ToolCallId;
+use crate::types::status::StreamingStatus;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+/// A chat completions request in OpenAI's format.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct OpenAiChatRequest {
+    pub model: String,
+    pub messages: Vec<OpenAiMessage>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_completion_tokens: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub frequency_penalty: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub presence_penalty: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub n: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stop: Option<StopSequence>,
+    /// Bias by token ID, written as a string.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub logit_bias: Option<HashMap<String, f32>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub logprobs: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub top_logprobs: Option<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tools: Option<Vec<Tool>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_choice: Option<ToolChoice>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parallel_tool_calls: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response_format: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prediction: Option<PredictionConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stream: Option<bool>,
+    /// OpenRouter-only parameters, see the [module docs](self).
+    #[serde(flatten)]
+    pub extra_body: Map<String, Value>,
+}
+
+/// Message roles in OpenAI's format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OpenAiRole {
+    System,
+    Developer,
+    User,
+    Assistant,
+    Tool,
+}
+
+/// A message in OpenAI's format.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OpenAiMessage {
+    pub role: OpenAiRole,
+    /// `None` for assistant messages that only call tools.
+    #[serde(default)]
+    pub content: Option<MessageContent>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_call_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_calls: Option<Vec<ToolCall>>,
+    /// OpenRouter-only fields such as `reasoning`.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// A chat completions response in OpenAI's format.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OpenAiChatResponse {
+    pub id: String,
+    pub object: String,
+    pub created: i64,
+    pub model: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub system_fingerprint: Option<String>,
+    pub choices: Vec<OpenAiChoice>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub usage: Option<OpenAiUsage>,
+    /// Fields OpenAI doesn't define.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// A response choice in OpenAI's format.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OpenAiChoice {
+    pub index: u32,
+    pub message: OpenAiMessage,
+    #[serde(default)]
+    pub finish_reason: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub logprobs: Option<LogProbs>,
+    /// OpenRouter-only fields such as `native_finish_reason`.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// Token usage in OpenAI's format.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct OpenAiUsage {
+    pub prompt_tokens: u32,
+    pub completion_tokens: u32,
+    pub total_tokens: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt_tokens_details: Option<OpenAiPromptTokensDetails>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub completion_tokens_details: Option<OpenAiCompletionTokensDetails>,
+    /// OpenRouter-only fields such as `cost`.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// Breakdown of prompt tokens in OpenAI's format.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct OpenAiPromptTokensDetails {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cached_tokens: Option<u32>,
+}
+
+/// Breakdown of completion tokens in OpenAI's format.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct OpenAiCompletionTokensDetails {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reasoning_tokens: Option<u32>,
+}
+
+/// The request fields carried in `extra_body`; unknown keys stay in `rest`.
+#[derive(Default, Deserialize)]
+struct OpenRouterExtras {
+    provider: Option<ProviderPreferences>,
+    models: Option<Vec<String>>,
+    transforms: Option<Vec<String>>,
+    route: Option<RouteStrategy>,
+    top_k: Option<u32>,
+    repetition_penalty: Option<f32>,
+    min_p: Option<f32>,
+    top_a: Option<f32>,
+    verbosity: Option<VerbosityLevel>,
+    debug: Option<DebugConfig>,
+    plugins: Option<Vec<Plugin>>,
+    reasoning: Option<ReasoningConfig>,
+    #[serde(flatten)]
+    rest: Map<String, Value>,
+}
+
+/// Inserts `value` under `key` unless it is `None` or can't be serialized.
+fn put<T: Serialize>(map: &mut Map<String, Value>, key: &str, value: Option<T>) {
+    if let Some(value) = value.and_then(|value| serde_json::to_value(value).ok()) {
+        map.insert(key.to_string(), value);
+    }
+}
+
+impl From<ChatCompletionRequest> for OpenAiChatRequest {
+    fn from(request: ChatCompletionRequest) -> Self {
+        let mut extra_body = Map::new();
+        put(&mut extra_body, "provider", request.provider);
+        put(&mut extra_body, "models", request.models);
+        put(&mut extra_body, "transforms", request.transforms);
+        put(&mut extra_body, "route", request.route);
+        put(&mut extra_body, "top_k", request.top_k);
+        put(
+            &mut extra_body,
+            "repetition_penalty",
+            request.repetition_penalty,
+        );
+        put(&mut extra_body, "min_p", request.min_p);
+        put(&mut extra_body, "top_a", request.top_a);
+        put(&mut extra_body, "verbosity", request.verbosity);
+        put(&mut extra_body, "debug", request.debug);
+        put(&mut extra_body, "plugins", request.plugins);
+        put(&mut extra_body, "reasoning", request.reasoning);
+        for (key, value) in request.extra_body.into_iter().flatten() {
+            extra_body.entry(key).or_insert(value);
+        }
+
+        Self {
+            model: request.model,
+            messages: request.messages.into_iter().map(Into::into).collect(),
+            max_tokens: request.max_tokens,
+            max_completion_tokens: None,
+            temperature: request.temperature,
+            top_p: request.top_p,
+            frequency_penalty: request.frequency_penalty,
+            presence_penalty: request.presence_penalty,
+            seed: request.seed,
+            n: request.n,
+            stop: request.stop,
+            logit_bias: request.logit_bias.map(|bias| {
+                bias.iter()
+                    .map(|(token, bias)| (token.to_string(), bias))
+                    .collect()
+            }),
+            logprobs: request.logprobs,
+            top_logprobs: request.top_logprobs,
+            user: request.user,
+            tools: request.tools,
+            tool_choice: request.tool_choice,
+            parallel_tool_calls: request.parallel_tool_calls,
+            response_format: request
+                .response_format
+                .and_then(|format| serde_json::to_value(format).ok()),
+            prediction: request.prediction,
+            stream: request
+                .stream
+                .filter(StreamingStatus::as_bool)
+                .map(|_| true),
+            extra_body,
+        }
+    }
+}
+
+impl TryFrom<OpenAiChatRequest> for ChatCompletionRequest {
+    type Error = Error;
+
+    fn try_from(request: OpenAiChatRequest) -> Result<Self> {
+        let extras: OpenRouterExtras = if request.extra_body.is_empty() {
+            OpenRouterExtras::default()
+        } else {
+            serde_json::from_value(Value::Object(request.extra_body))
+                .map_err(|e| Error::ConfigError(format!("Invalid extra_body field: {e}")))?
+        };
+
+        let logit_bias = request
+            .logit_bias
+            .map(|bias| {
+                bias.into_iter()
+                    .try_fold(LogitBias::new(), |bias, (token, value)| {
+                        let token = token.parse().map_err(|_| {
+                            Error::ConfigError(format!(
+                                "logit_bias key '{token}' is not a token ID"
+                            ))
+                        })?;
+                        Ok::<_, Error>(bias.with_token(token, value))
+                    })
+            })
+            .transpose()?;
+
+        Ok(Self {
+            model: request.model,
+            messages: request
+                .messages
+                .into_iter()
+                .map(Message::try_from)
+                .collect::<Result<_>>()?,
+            stream: request
+                .stream
+                .filter(|stream| *stream)
+                .map(|_| StreamingStatus::InProgress),
+            response_format: request
+                .response_format
+                .map(response_format_from_openai)
+                .transpose()?
+                .flatten(),
+            tools: request.tools,
+            tool_choice: request.tool_choice,
+            provider: extras.provider,
+            models: extras.models,
+            transforms: extras.transforms,
+            route: extras.route,
+            user: request.user,
+            max_tokens: request.max_tokens.or(request.max_completion_tokens),
+            temperature: request.temperature,
+            top_p: request.top_p,
+            top_k: extras.top_k,
+            frequency_penalty: request.frequency_penalty,
+            presence_penalty: request.presence_penalty,
+            repetition_penalty: extras.repetition_penalty,
+            min_p: extras.min_p,
+            top_a: extras.top_a,
+            seed: request.seed,
+            n: request.n,
+            stop: request.stop,
+            logit_bias,
+            logprobs: request.logprobs,
+            top_logprobs: request.top_logprobs,
+            prediction: request.prediction,
+            parallel_tool_calls: request.parallel_tool_calls,
+            verbosity: extras.verbosity,
+            debug: extras.debug,
+            plugins: extras.plugins,
+            reasoning: extras.reasoning,
+            extra_body: (!extras.rest.is_empty()).then_some(extras.rest),
+        })
+    }
+}
+
+/// Maps an OpenAI `response_format`; `text` is the default and maps to `None`.
+fn response_format_from_openai(format: Value) -> Result<Option<ResponseFormatConfig>> {
+    match format.get("type").and_then(Value::as_str) {
+        Some("text") => Ok(None),
+        Some("json_schema") => {
+            let schema: JsonSchemaConfig = format
+                .get("json_schema")
+                .cloned()
+                .map(serde_json::from_value)
+                .transpose()
+                .map_err(Error::SerializationError)?
+                .ok_or_else(|| {
+                    Error::ConfigError("response_format 'json_schema' needs a schema".into())
+                })?;
+            Ok(Some(ResponseFormatConfig {
+                format_type: "json_schema".to_string(),
+                json_schema: schema,
+            }))
+        }
+        other => Err(Error::ConfigError(format!(
+            "Unsupported response_format type {}",
+            other.unwrap_or("(missing)")
+        ))),
+    }
+}
+
+impl From<Message> for OpenAiMessage {
+    fn from(message: Message) -> Self {
+        let mut extra = Map::new();
+        put(&mut extra, "reasoning", message.reasoning);
+        put(&mut extra, "reasoning_details", message.reasoning_details);
+
+        let only_calls_tools = message.tool_calls.is_some()
+            && message
+                .content
+                .as_text()
+                .is_some_and(|text| text.is_empty());
+        Self {
+            role: match message.role {
+                ChatRole::System => OpenAiRole::System,
+                ChatRole::User => OpenAiRole::User,
+                ChatRole::Assistant => OpenAiRole::Assistant,
+                ChatRole::Tool => OpenAiRole::Tool,
+            },
+            content: (!only_calls_tools).then_some(message.content),
+            name: message.name,
+            tool_call_id: message.tool_call_id.map(|id| id.as_str().to_string()),
+            tool_calls: message.tool_calls,
+            extra,
+        }
+    }
+}
+
+impl TryFrom<OpenAiMessage> for Message {
+    type Error = Error;
+
+    /// Fails for malformed `reasoning` or `reasoning_details`; other extra
+    /// fields are ignored.
+    fn try_from(mut message: OpenAiMessage) -> Result<Self> {
+        let mut take = |key: &str| message.extra.remove(key);
+        let reasoning: Option<String> = take("reasoning")
+            .map(serde_json::from_value)
+            .transpose()
+            .map_err(Error::SerializationError)?;
+        let reasoning_details: Option<Vec<ReasoningDetail>> = take("reasoning_details")
+            .map(serde_json::from_value)
+            .transpose()
+            .map_err(Error::SerializationError)?;
+
+        Ok(Self {
+            role: match message.role {
+                OpenAiRole::System | OpenAiRole::Developer => ChatRole::System,
+                OpenAiRole::User => ChatRole::User,
+                OpenAiRole::Assistant => ChatRole::Assistant,
+                OpenAiRole::Tool => ChatRole::Tool,
+            },
+            content: message.content.unwrap_or_default(),
+            name: message.name,
+            tool_call_id: message.tool_call_id.map(ToolCallId::new),
+            tool_calls: message.tool_calls,
+            reasoning,
+            reasoning_details,
+        })
+    }
+}
+
+impl From<ChatCompletionResponse> for OpenAiChatResponse {
+    fn from(response: ChatCompletionResponse) -> Self {
+        #[cfg(feature = "preserve-unknown")]
+        let extra = response.extra.into_iter().collect();
+        #[cfg(not(feature = "preserve-unknown"))]
+        let extra = Map::new();
+
+        Self {
+            id: response.id,
+            object: response.object,
+            created: response.created,
+            model: response.model,
+            system_fingerprint: response.system_fingerprint,
+            choices: response
+                .choices
+                .into_iter()
+                .enumerate()
+                .map(|(position, choice)| {
+                    let mut extra = Map::new();
+                    put(
+                        &mut extra,
+                        "native_finish_reason",
+                        choice.native_finish_reason,
+                    );
+                    OpenAiChoice {
+                        index: choice.index.unwrap_or(position as u32),
+                        message: choice.message.into(),
+                        finish_reason: choice.finish_reason,
+                        logprobs: choice.logprobs,
+                        extra,
+                    }
+                })
+                .collect(),
+            usage: response.usage.map(|usage| {
+                let mut extra = Map::new();
+                put(&mut extra, "cost", usage.cost);
+                put(&mut extra, "is_byok", usage.is_byok);
+                OpenAiUsage {
+                    prompt_tokens: usage.prompt_tokens,
+                    completion_tokens: usage.completion_tokens,
+                    total_tokens: usage.total_tokens,
+                    prompt_tokens_details: usage.prompt_tokens_details.map(|details| {
+                        OpenAiPromptTokensDetails {
+                            cached_tokens: details.cached_tokens,
+                        }
+                    }),
+                    completion_tokens_details: usage.completion_tokens_details.map(|details| {
+                        OpenAiCompletionTokensDetails {
+                            reasoning_tokens: details.reasoning_tokens,
+                        }
+                    }),
+                    extra,
+                }
+            }),
+            extra,
+        }
+    }
+}
+
+impl TryFrom<OpenAiChatResponse> for ChatCompletionResponse {
+    type Error = Error;
+
+    /// Reads the response as this crate would read it from the API, so
+    /// OpenRouter fields in the `extra` maps are picked up.
+    fn try_from(response: OpenAiChatResponse) -> Result<Self> {
+        let value = serde_json::to_value(response).map_err(Error::SerializationError)?;
+        serde_json::from_value(value).map_err(Error::SerializationError)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_request_round_trips_with_openrouter_extras() {
+        let request = ChatCompletionRequest::builder("openai/gpt-4o")
+            .message(Message::text(ChatRole::User, "Hi"))
+            .temperature(crate::types::sampling::Temperature::try_from(0.5).unwrap())
+            .top_k(40)
+            .models(vec!["anthropic/claude-3.5-sonnet".into()])
+            .logit_bias(LogitBias::new().with_token(50256, -100.0))
+            .build()
+            .unwrap();
+
+        let openai = OpenAiChatRequest::from(request.clone());
+        let json = serde_json::to_value(&openai).unwrap();
+        assert_eq!(json["temperature"], json!(0.5));
+        assert_eq!(json["top_k"], json!(40));
+        assert_eq!(json["models"], json!(["anthropic/claude-3.5-sonnet"]));
+        assert_eq!(json["logit_bias"], json!({"50256": -100.0}));
+        assert_eq!(json["messages"], json!([{"role": "user", "content": "Hi"}]));
+
+        let parsed: OpenAiChatRequest = serde_json::from_value(json).unwrap();
+        let back = ChatCompletionRequest::try_from(parsed).unwrap();
+        assert_eq!(
+            serde_json::to_value(&back).unwrap(),
+            serde_json::to_value(&request).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_request_from_openai_maps_roles_and_passes_extras_through() {
+        let openai: OpenAiChatRequest = serde_json::from_value(json!({
+            "model": "openai/gpt-4o",
+            "messages": [
+                {"role": "developer", "content": "Be brief."},
+                {"role": "assistant", "content": null, "tool_calls": [{
+                    "id": "call-1", "type": "function",
+                    "function": {"name": "f", "arguments": "{}"}
+                }]},
+                {"role": "tool", "tool_call_id": "call-1", "content": "ok"}
+            ],
+            "max_completion_tokens": 64,
+            "response_format": {"type": "text"}
+        }))
+        .unwrap();
+        let request = ChatCompletionRequest::try_from(openai.clone()).unwrap();
+        assert_eq!(request.messages[0].role, ChatRole::System);
+        assert_eq!(request.messages[1].content.as_text(), Some(""));
+        assert_eq!(
+            request.messages[2].tool_call_id.as_ref().unwrap().as_str(),
+            "call-1"
+        );
+        assert_eq!(request.max_tokens, Some(64));
+        assert!(request.response_format.is_none());
+
+        // Tool-call-only messages go back out with null content.
+        let back = OpenAiChatRequest::from(request);
+        assert!(back.messages[1].content.is_none());
+
+        let mut extras = openai.clone();
+        extras.extra_body.insert("store".into(), json!(true));
+        let request = ChatCompletionRequest::try_from(extras.clone()).unwrap();
+        assert_eq!(request.extra_body.as_ref().unwrap()["store"], json!(true));
+        assert_eq!(
+            OpenAiChatRequest::from(request).extra_body["store"],
+            json!(true)
+        );
+
+        extras.extra_body.insert("top_k".into(), json!("high"));
+        assert!(matches!(
+            ChatCompletionRequest::try_from(extras),
+            Err(Error::ConfigError(_))
+        ));
+
+        let mut json_object = openai;
+        json_object.response_format = Some(json!({"type": "json_object"}));
+        assert!(ChatCompletionRequest::try_from(json_object).is_err());
+    }
+
+    #[test]
+    fn test_response_round_trips_with_openrouter_extras() {
+        let body = json!({
+            "id": "gen-1",
+            "object": "chat.completion",
+            "created": 1,
+            "model": "openai/gpt-4o",
+            "choices": [{
+                "index": 0,
+                "message": {"role": "assistant", "content": "Hi", "reasoning": "Greet back."},
+                "finish_reason": "stop",
+                "native_finish_reason": "end_turn"
+            }],
+            "usage": {
+                "prompt_tokens": 3, "completion_tokens": 1, "total_tokens": 4,
+                "cost": 0.0001,
+                "prompt_tokens_details": {"cached_tokens": 2}
+            }
+        });
+        let response: ChatCompletionResponse = serde_json::from_value(body.clone()).unwrap();
+
+        let openai = OpenAiChatResponse::from(response);
+        assert_eq!(openai.choices[0].extra["native_finish_reason"], "end_turn");
+        assert_eq!(openai.choices[0].message.extra["reasoning"], "Greet back.");
+        let usage = openai.usage.as_ref().unwrap();
+        assert_eq!(usage.extra["cost"], json!(0.0001));
+        assert_eq!(
+            usage.prompt_tokens_details.as_ref().unwrap().cached_tokens,
+            Some(2)
+        );
+        assert_eq!(serde_json::to_value(&openai).unwrap(), body);
+
+        let back = ChatCompletionResponse::try_from(openai).unwrap();
+        assert_eq!(back.text(), Some("Hi"));
+        assert_eq!(
+            back.choices[0].native_finish_reason.as_deref(),
+            Some("end_turn")
+        );
+        assert_eq!(back.usage.unwrap().cost, Some(0.0001));
+    }
+}
@@ -2,11 +2,21 @@
 //!
 //! A Rust client for interfacing with the OpenRouter API.
 
+pub mod agent;
 pub mod api;
 pub mod client;
+pub mod conformance;
 pub mod error;
+pub mod evals;
+#[cfg(feature = "interop-openai")]
+pub mod interop;
 pub mod mcp; // Add the MCP module
 pub mod models;
+#[cfg(feature = "offline")]
+pub mod offline;
+pub mod postprocess;
+pub mod sensitive;
+pub mod tasks;
 #[cfg(test)]
 mod tests;
 pub mod types;
@@ -18,6 +28,8 @@ pub use types::*;
 pub use client::{NoAuth, OpenRouterClient, Ready, Unconfigured};
 pub use mcp::client::MCPClient; // Re-export MCPClient
 pub use mcp::types as mcp_types; // Re-export MCP types
+#[cfg(feature = "macros")]
+pub use openrouter_api_macros::openrouter_tool;
 
 // Ensure TLS features are mutually exclusive
 #[cfg(all(feature = "tls-rustls", feature = "tls-native-tls"))]
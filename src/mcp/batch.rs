@@ -0,0 +1,268 @@
+//! JSON-RPC batching for the MCP client.
+//!
+//! [`McpBatch`] queues several calls and sends them as one JSON-RPC batch
+//! array, saving round-trips on servers that support batching. Each queued
+//! call returns a [`BatchCall`] handle that reads its typed result from the
+//! [`BatchResponse`].
+
+use crate::error::{Error, Result};
+use crate::mcp::client::MCPClient;
+use crate::mcp::types::*;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::marker::PhantomData;
+
+/// Calls queued by [`MCPClient::batch`].
+///
+/// Batches are sent once and are not retried, since they may mix idempotent
+/// and non-idempotent calls.
+///
+/// # Example
+///
+/// ```no_run
+/// use openrouter_api::mcp::types::{GetResourceParams, ListPromptsParams};
+/// use openrouter_api::MCPClient;
+///
+/// # async fn run(mcp: MCPClient) -> openrouter_api::Result<()> {
+/// let mut batch = mcp.batch();
+/// let prompts = batch.list_prompts(ListPromptsParams::default())?;
+/// let readme = batch.get_resource(GetResourceParams {
+///     id: "file:///README.md".into(),
+///     parameters: None,
+/// })?;
+///
+/// let mut response = batch.send().await?;
+/// let prompts = response.take(prompts)?;
+/// let readme = response.take(readme)?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct McpBatch<'a> {
+    client: &'a MCPClient,
+    calls: Vec<QueuedCall>,
+}
+
+struct QueuedCall {
+    method: String,
+    params: Value,
+    capability: Option<&'static str>,
+}
+
+/// Handle to the result of a queued call, redeemed with
+/// [`BatchResponse::take`].
+#[derive(Debug)]
+pub struct BatchCall<T> {
+    index: usize,
+    _result: PhantomData<fn() -> T>,
+}
+
+impl<T> BatchCall<T> {
+    /// Position of the call in the batch.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+}
+
+impl<'a> McpBatch<'a> {
+    pub(crate) fn new(client: &'a MCPClient) -> Self {
+        Self {
+            client,
+            calls: Vec::new(),
+        }
+    }
+
+    /// Queue a call to `method` whose result deserializes to `T`.
+    ///
+    /// Unlike the typed helpers, no server capability is checked.
+    pub fn call<T: DeserializeOwned>(
+        &mut self,
+        method: impl Into<String>,
+        params: impl Serialize,
+    ) -> Result<BatchCall<T>> {
+        self.queue(method.into(), params, None)
+    }
+
+    /// Queue a `getResource` call.
+    pub fn get_resource(
+        &mut self,
+        params: GetResourceParams,
+    ) -> Result<BatchCall<ResourceResponse>> {
+        self.queue("getResource".into(), params, Some("resources"))
+    }
+
+    /// Queue a `toolCall` call.
+    pub fn tool_call(&mut self, params: ToolCallParams) -> Result<BatchCall<ToolCallResponse>> {
+        self.queue("toolCall".into(), params, Some("tools"))
+    }
+
+    /// Queue an `executePrompt` call.
+    pub fn execute_prompt(
+        &mut self,
+        params: ExecutePromptParams,
+    ) -> Result<BatchCall<ExecutePromptResponse>> {
+        self.queue("executePrompt".into(), params, Some("prompts"))
+    }
+
+    /// Queue a `prompts/list` call.
+    pub fn list_prompts(
+        &mut self,
+        params: ListPromptsParams,
+    ) -> Result<BatchCall<ListPromptsResponse>> {
+        self.queue("prompts/list".into(), params, Some("prompts"))
+    }
+
+    /// Queue a `prompts/get` call.
+    pub fn get_prompt(&mut self, params: GetPromptParams) -> Result<BatchCall<GetPromptResponse>> {
+        self.queue("prompts/get".into(), params, Some("prompts"))
+    }
+
+    /// Number of queued calls.
+    pub fn len(&self) -> usize {
+        self.calls.len()
+    }
+
+    /// Whether no calls are queued.
+    pub fn is_empty(&self) -> bool {
+        self.calls.is_empty()
+    }
+
+    fn queue<T>(
+        &mut self,
+        method: String,
+        params: impl Serialize,
+        capability: Option<&'static str>,
+    ) -> Result<BatchCall<T>> {
+        let params = serde_json::to_value(params).map_err(Error::SerializationError)?;
+        self.calls.push(QueuedCall {
+            method,
+            params,
+            capability,
+        });
+        Ok(BatchCall {
+            index: self.calls.len() - 1,
+            _result: PhantomData,
+        })
+    }
+
+    /// Send the queued calls as one JSON-RPC batch.
+    ///
+    /// Fails as a whole if the batch cannot be sent, if a call needs a
+    /// capability the server lacks, or if the server rejects the batch;
+    /// errors of individual calls are reported through
+    /// [`BatchResponse::take`].
+    pub async fn send(self) -> Result<BatchResponse> {
+        if self.calls.is_empty() {
+            return Ok(BatchResponse {
+                results: Vec::new(),
+            });
+        }
+
+        let mut requests = Vec::with_capacity(self.calls.len());
+        for call in self.calls {
+            let protocol_version = match call.capability {
+                Some(capability) => {
+                    self.client
+                        .ensure_capability(capability, &call.method)
+                        .await?
+                }
+                None => self
+                    .client
+                    .protocol_version()
+                    .await
+                    .ok_or_else(|| Error::ConfigError("MCP client not initialized".into()))?,
+            };
+            requests.push(JsonRpcRequest {
+                jsonrpc: "2.0".to_string(),
+                id: MCPClient::generate_id(),
+                method: call.method,
+                params: Some(call.params),
+                protocol_version: Some(protocol_version),
+            });
+        }
+
+        let request_json = serde_json::to_string(&requests).map_err(Error::SerializationError)?;
+        let response_body = self
+            .client
+            .post_json(request_json, Some(requests.len()))
+            .await?;
+        let responses: Vec<JsonRpcResponse> = match serde_json::from_str(&response_body) {
+            Ok(responses) => responses,
+            // Servers answer a batch they cannot process with a single error.
+            Err(e) => {
+                let response: JsonRpcResponse = serde_json::from_str(&response_body)
+                    .map_err(|_| Error::SerializationError(e))?;
+                MCPClient::response_result(response)?;
+                return Err(Error::ConfigError(
+                    "Expected a JSON-RPC batch response array".into(),
+                ));
+            }
+        };
+
+        let mut by_id: HashMap<String, JsonRpcResponse> = responses
+            .into_iter()
+            .map(|response| (response.id.clone(), response))
+            .collect();
+        let results = requests
+            .into_iter()
+            .map(|request| {
+                Some(match by_id.remove(&request.id) {
+                    Some(response) => MCPClient::response_result(response),
+                    None => Err(Error::ConfigError(format!(
+                        "No response to batched {} call",
+                        request.method
+                    ))),
+                })
+            })
+            .collect();
+        Ok(BatchResponse { results })
+    }
+}
+
+/// Results of a sent [`McpBatch`], in the order the calls were queued.
+#[derive(Debug)]
+pub struct BatchResponse {
+    results: Vec<Option<Result<Value>>>,
+}
+
+impl BatchResponse {
+    /// Take the typed result of `call`.
+    pub fn take<T: DeserializeOwned>(&mut self, call: BatchCall<T>) -> Result<T> {
+        let result = self
+            .results
+            .get_mut(call.index)
+            .and_then(Option::take)
+            .ok_or_else(|| {
+                Error::ConfigError(format!("No result for batch call {}", call.index))
+            })?;
+        serde_json::from_value(result?).map_err(Error::SerializationError)
+    }
+
+    /// Number of calls in the batch.
+    pub fn len(&self) -> usize {
+        self.results.len()
+    }
+
+    /// Whether the batch was empty.
+    pub fn is_empty(&self) -> bool {
+        self.results.is_empty()
+    }
+
+    /// The untyped results in call order; results already taken are
+    /// reported as errors.
+    pub fn into_results(self) -> Vec<Result<Value>> {
+        self.results
+            .into_iter()
+            .enumerate()
+            .map(|(index, result)| {
+                result.unwrap_or_else(|| {
+                    Err(Error::ConfigError(format!(
+                        "Result of batch call {} was already taken",
+                        index
+                    )))
+                })
+            })
+            .collect()
+    }
+}
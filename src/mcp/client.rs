@@ -1,11 +1,21 @@
 //! MCP client implementation for connecting to MCP servers.
 
+use futures::StreamExt;
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use std::time::{Duration, Instant};
+use tokio::sync::{broadcast, Mutex};
 use url::Url;
 
+use crate::client::RetryConfig;
 use crate::error::{Error, Result};
+use crate::mcp::batch::McpBatch;
+use crate::mcp::sampling::McpSamplingHandler;
+use crate::mcp::transport::{event_stream, is_event_stream, SseTransport};
 use crate::mcp::types::*;
+use crate::utils::retry::{
+    jittered_backoff_ms, next_backoff, RetryContext, RetryDecision, RetryPolicy, RetryTrigger,
+    StatusCodeRetryPolicy,
+};
 use crate::utils::security::create_safe_error_message;
 
 /// MCP client for connecting to and interacting with MCP servers.
@@ -21,8 +31,23 @@ pub struct MCPClient {
     config: McpConfig,
     /// Semaphore for limiting concurrent requests
     semaphore: Arc<tokio::sync::Semaphore>,
+    /// Fulfills sampling requests from the server
+    sampling_handler: Option<Arc<dyn McpSamplingHandler>>,
+    /// Headers built from `config.auth`
+    auth_headers: reqwest::header::HeaderMap,
+    /// Capabilities sent by the last successful `initialize`
+    client_capabilities: Arc<Mutex<Option<ClientCapabilities>>>,
+    /// Notified of retries and reconnections
+    observer: Option<Arc<dyn McpConnectionObserver>>,
+    /// Streamable HTTP transport, if enabled
+    transport: Option<SseTransport>,
+    /// Notifications received over the transport
+    notifications: broadcast::Sender<JsonRpcNotification>,
 }
 
+/// Notifications buffered per subscriber before the oldest are dropped.
+const NOTIFICATION_BUFFER: usize = 64;
+
 impl MCPClient {
     /// Create a new MCP client for the given server URL with default configuration.
     pub fn new(server_url: impl AsRef<str>) -> Result<Self> {
@@ -34,6 +59,7 @@ impl MCPClient {
         let server_url = Url::parse(server_url.as_ref())
             .map_err(|e| Error::ConfigError(format!("Invalid server URL: {e}")))?;
 
+        let auth_headers = config.auth.to_headers()?;
         let client = reqwest::Client::builder()
             .timeout(config.request_timeout)
             .build()
@@ -45,11 +71,61 @@ impl MCPClient {
             capabilities: Arc::new(Mutex::new(None)),
             config: config.clone(),
             semaphore: Arc::new(tokio::sync::Semaphore::new(config.max_concurrent_requests)),
+            sampling_handler: None,
+            auth_headers,
+            client_capabilities: Arc::new(Mutex::new(None)),
+            observer: None,
+            transport: None,
+            notifications: broadcast::channel(NOTIFICATION_BUFFER).0,
         })
     }
 
+    /// Notify `observer` of retries and reconnections.
+    pub fn with_connection_observer(mut self, observer: Arc<dyn McpConnectionObserver>) -> Self {
+        self.observer = Some(observer);
+        self
+    }
+
+    /// A client that authenticates with `auth` instead, for requests made on
+    /// behalf of another user.
+    ///
+    /// It shares this client's connection pool, concurrency limit, sampling
+    /// handler and initialization state.
+    pub fn with_auth(&self, auth: McpAuth) -> Result<Self> {
+        let mut client = self.clone();
+        client.auth_headers = auth.to_headers()?;
+        client.config.auth = auth;
+        Ok(client)
+    }
+
+    /// Fulfill `sampling/createMessage` requests passed to
+    /// [`handle_server_request`](Self::handle_server_request) with `handler`.
+    ///
+    /// `initialize` then advertises sampling support unless the capabilities
+    /// say otherwise.
+    pub fn with_sampling_handler(mut self, handler: Arc<dyn McpSamplingHandler>) -> Self {
+        self.sampling_handler = Some(handler);
+        self
+    }
+
+    /// Talk to the server over the streamable HTTP transport: keep the
+    /// session it assigns, accept responses as event streams and resume
+    /// dropped streams with `Last-Event-ID`.
+    pub fn with_sse_transport(mut self, transport: SseTransport) -> Self {
+        self.transport = Some(transport);
+        self
+    }
+
+    /// Receive the notifications the server sends over the SSE transport.
+    ///
+    /// A subscriber that falls more than 64 notifications behind misses the
+    /// oldest ones.
+    pub fn subscribe_notifications(&self) -> broadcast::Receiver<JsonRpcNotification> {
+        self.notifications.subscribe()
+    }
+
     /// Generate a unique request ID
-    fn generate_id() -> String {
+    pub(crate) fn generate_id() -> String {
         uuid::Uuid::new_v4().to_string()
     }
 
@@ -58,28 +134,63 @@ impl MCPClient {
         cfg!(test)
     }
 
-    /// Initialize the connection to the MCP server.
+    /// Initialize the connection to the MCP server, negotiating the protocol
+    /// version.
+    ///
+    /// The version in `client_capabilities` is proposed; the server may answer
+    /// with another, which is accepted if it is in
+    /// [`SUPPORTED_PROTOCOL_VERSIONS`] and used for later requests.
     pub async fn initialize(
         &self,
-        client_capabilities: ClientCapabilities,
+        mut client_capabilities: ClientCapabilities,
     ) -> Result<ServerCapabilities> {
-        let request_id = Self::generate_id();
-        let request = JsonRpcRequest {
+        if self.sampling_handler.is_some() {
+            client_capabilities.supports_sampling.get_or_insert(true);
+        }
+        let request = Self::initialize_request(&client_capabilities)?;
+        let request_id = request.id.clone();
+        let response = self.send_with_retry(request, true).await?;
+        let capabilities = self.accept_initialize(response, request_id).await?;
+
+        // Kept to re-initialize after a lost connection
+        *self.client_capabilities.lock().await = Some(client_capabilities);
+
+        Ok(capabilities)
+    }
+
+    fn initialize_request(client_capabilities: &ClientCapabilities) -> Result<JsonRpcRequest> {
+        Ok(JsonRpcRequest {
             jsonrpc: "2.0".to_string(),
-            id: request_id.clone(),
+            id: Self::generate_id(),
             method: "initialize".to_string(),
             params: Some(
                 serde_json::to_value(InitializeParams {
-                    capabilities: client_capabilities,
+                    capabilities: client_capabilities.clone(),
                 })
                 .map_err(Error::SerializationError)?,
             ),
-            protocol_version: Some(MCP_PROTOCOL_VERSION.to_string()),
-        };
+            protocol_version: Some(client_capabilities.protocol_version.clone()),
+        })
+    }
 
-        let response = self.send_request(request).await?;
+    /// Checks the negotiated version and stores the server capabilities.
+    async fn accept_initialize(
+        &self,
+        response: JsonRpcResponse,
+        request_id: String,
+    ) -> Result<ServerCapabilities> {
         let capabilities = self.parse_response::<ServerCapabilities>(response, request_id)?;
 
+        // The server answers with the proposed version or one it prefers;
+        // continue only if it is one this client speaks.
+        if !SUPPORTED_PROTOCOL_VERSIONS.contains(&capabilities.protocol_version.as_str()) {
+            return Err(Error::ConfigError(format!(
+                "MCP server requires unsupported protocol version {} (supported: {})",
+                capabilities.protocol_version,
+                SUPPORTED_PROTOCOL_VERSIONS.join(", ")
+            )));
+        }
+
         // Store the server capabilities
         let mut caps = self.capabilities.lock().await;
         *caps = Some(capabilities.clone());
@@ -89,8 +200,7 @@ impl MCPClient {
 
     /// Get a resource from the server.
     pub async fn get_resource(&self, params: GetResourceParams) -> Result<ResourceResponse> {
-        // Check if initialized
-        self.ensure_initialized().await?;
+        let protocol_version = self.ensure_capability("resources", "getResource").await?;
 
         let request_id = Self::generate_id();
         let request = JsonRpcRequest {
@@ -98,17 +208,16 @@ impl MCPClient {
             id: request_id.clone(),
             method: "getResource".to_string(),
             params: Some(serde_json::to_value(params).map_err(Error::SerializationError)?),
-            protocol_version: Some(MCP_PROTOCOL_VERSION.to_string()),
+            protocol_version: Some(protocol_version),
         };
 
-        let response = self.send_request(request).await?;
+        let response = self.send_with_retry(request, true).await?;
         self.parse_response::<ResourceResponse>(response, request_id)
     }
 
     /// Call a tool on the server.
     pub async fn tool_call(&self, params: ToolCallParams) -> Result<ToolCallResponse> {
-        // Check if initialized
-        self.ensure_initialized().await?;
+        let protocol_version = self.ensure_capability("tools", "toolCall").await?;
 
         let request_id = Self::generate_id();
         let request = JsonRpcRequest {
@@ -116,10 +225,10 @@ impl MCPClient {
             id: request_id.clone(),
             method: "toolCall".to_string(),
             params: Some(serde_json::to_value(params).map_err(Error::SerializationError)?),
-            protocol_version: Some(MCP_PROTOCOL_VERSION.to_string()),
+            protocol_version: Some(protocol_version),
         };
 
-        let response = self.send_request(request).await?;
+        let response = self.send_with_retry(request, false).await?;
         self.parse_response::<ToolCallResponse>(response, request_id)
     }
 
@@ -128,8 +237,7 @@ impl MCPClient {
         &self,
         params: ExecutePromptParams,
     ) -> Result<ExecutePromptResponse> {
-        // Check if initialized
-        self.ensure_initialized().await?;
+        let protocol_version = self.ensure_capability("prompts", "executePrompt").await?;
 
         let request_id = Self::generate_id();
         let request = JsonRpcRequest {
@@ -137,13 +245,50 @@ impl MCPClient {
             id: request_id.clone(),
             method: "executePrompt".to_string(),
             params: Some(serde_json::to_value(params).map_err(Error::SerializationError)?),
-            protocol_version: Some(MCP_PROTOCOL_VERSION.to_string()),
+            protocol_version: Some(protocol_version),
         };
 
-        let response = self.send_request(request).await?;
+        let response = self.send_with_retry(request, false).await?;
         self.parse_response::<ExecutePromptResponse>(response, request_id)
     }
 
+    /// List the prompt templates offered by the server (`prompts/list`).
+    pub async fn list_prompts(&self, params: ListPromptsParams) -> Result<ListPromptsResponse> {
+        let protocol_version = self.ensure_capability("prompts", "prompts/list").await?;
+
+        let request_id = Self::generate_id();
+        let request = JsonRpcRequest {
+            jsonrpc: "2.0".to_string(),
+            id: request_id.clone(),
+            method: "prompts/list".to_string(),
+            params: Some(serde_json::to_value(params).map_err(Error::SerializationError)?),
+            protocol_version: Some(protocol_version),
+        };
+
+        let response = self.send_with_retry(request, true).await?;
+        self.parse_response::<ListPromptsResponse>(response, request_id)
+    }
+
+    /// Get a prompt template with its arguments substituted (`prompts/get`).
+    ///
+    /// Convert the result with `GetPromptResponse::into_messages` to send it
+    /// in a chat completion.
+    pub async fn get_prompt(&self, params: GetPromptParams) -> Result<GetPromptResponse> {
+        let protocol_version = self.ensure_capability("prompts", "prompts/get").await?;
+
+        let request_id = Self::generate_id();
+        let request = JsonRpcRequest {
+            jsonrpc: "2.0".to_string(),
+            id: request_id.clone(),
+            method: "prompts/get".to_string(),
+            params: Some(serde_json::to_value(params).map_err(Error::SerializationError)?),
+            protocol_version: Some(protocol_version),
+        };
+
+        let response = self.send_with_retry(request, true).await?;
+        self.parse_response::<GetPromptResponse>(response, request_id)
+    }
+
     /// Send a sampling response to the server.
     pub async fn respond_to_sampling(&self, id: String, result: SamplingResponse) -> Result<()> {
         // Check if initialized
@@ -159,20 +304,177 @@ impl MCPClient {
         self.send_response(response).await
     }
 
+    /// Answer a request the server sent to the client.
+    ///
+    /// `sampling/createMessage` is fulfilled by the sampling handler; a
+    /// rejection is reported with code -1 and other failures as internal
+    /// errors. Other methods, or sampling without a handler, are answered
+    /// with "method not found".
+    pub async fn handle_server_request(&self, request: JsonRpcRequest) -> Result<()> {
+        let outcome = match (request.method.as_str(), &self.sampling_handler) {
+            ("sampling/createMessage", Some(handler)) => {
+                match serde_json::from_value(request.params.unwrap_or_default()) {
+                    Ok(params) => handler.create_message(params).await.map_err(|e| {
+                        let code = if matches!(e, Error::Cancelled(_)) {
+                            USER_REJECTED
+                        } else {
+                            McpErrorCode::Internal
+                        };
+                        json_rpc_error(code, &e.to_string())
+                    }),
+                    Err(e) => Err(json_rpc_error(
+                        McpErrorCode::InvalidParams,
+                        &format!("Invalid sampling parameters: {e}"),
+                    )),
+                }
+            }
+            (method, _) => Err(json_rpc_error(
+                McpErrorCode::MethodNotFound,
+                &format!("Method not supported: {method}"),
+            )),
+        };
+
+        let response = match outcome {
+            Ok(result) => JsonRpcResponse {
+                jsonrpc: "2.0".to_string(),
+                id: request.id,
+                result: Some(serde_json::to_value(result).map_err(Error::SerializationError)?),
+                error: None,
+            },
+            Err(error) => JsonRpcResponse {
+                jsonrpc: "2.0".to_string(),
+                id: request.id,
+                result: None,
+                error: Some(error),
+            },
+        };
+        self.send_response(response).await
+    }
+
     /// Get the server capabilities.
     pub async fn capabilities(&self) -> Option<ServerCapabilities> {
         self.capabilities.lock().await.clone()
     }
 
+    /// Send a request, retrying under `config.retry`.
+    ///
+    /// Requests that never reached the server (connect failures) are retried
+    /// for every method; other transient failures, including connections
+    /// dropped mid-request, only for `idempotent` ones. After losing the
+    /// connection the client re-initializes, re-syncing the server
+    /// capabilities, before retrying.
+    async fn send_with_retry(
+        &self,
+        mut request: JsonRpcRequest,
+        idempotent: bool,
+    ) -> Result<JsonRpcResponse> {
+        let Some(retry) = &self.config.retry else {
+            return self.send_request(request).await;
+        };
+        let start = Instant::now();
+        let mut rng = fastrand::Rng::new();
+        let mut backoff_ms = retry.initial_backoff_ms;
+        let mut attempt = 0u32;
+        let mut reconnect = false;
+
+        loop {
+            let result = if reconnect {
+                match self.reconnect().await {
+                    Ok(protocol_version) => {
+                        request.protocol_version = Some(protocol_version);
+                        self.send_request(request.clone()).await
+                    }
+                    Err(e) => Err(e),
+                }
+            } else {
+                self.send_request(request.clone()).await
+            };
+            let error = match result {
+                Ok(response) => return Ok(response),
+                Err(error) => error,
+            };
+
+            let remaining = retry.total_timeout.saturating_sub(start.elapsed());
+            let decision = if attempt >= retry.max_retries || remaining.is_zero() {
+                RetryDecision::Stop
+            } else {
+                retry_decision(
+                    retry,
+                    &error,
+                    idempotent,
+                    &RetryContext {
+                        operation: &request.method,
+                        attempt: attempt + 1,
+                        elapsed: start.elapsed(),
+                    },
+                )
+            };
+            let delay = match decision {
+                RetryDecision::Stop => return Err(error),
+                RetryDecision::RetryAfter(delay) => delay.min(remaining),
+                RetryDecision::Retry => {
+                    let delay =
+                        jittered_backoff_ms(backoff_ms, retry.max_backoff_ms, &mut rng, remaining);
+                    backoff_ms = next_backoff(backoff_ms, retry.max_backoff_ms);
+                    Duration::from_millis(delay)
+                }
+            };
+            attempt += 1;
+
+            let lost_connection = is_connection_loss(&error)
+                && request.method != "initialize"
+                && self.client_capabilities.lock().await.is_some();
+            if let Some(observer) = &self.observer {
+                if lost_connection && !reconnect {
+                    observer.on_connection_lost(&error);
+                }
+                observer.on_retry(&request.method, attempt, &error);
+            }
+            reconnect = lost_connection || reconnect;
+            tokio::time::sleep(delay).await;
+        }
+    }
+
+    /// Re-initialize with the last client capabilities, returning the newly
+    /// negotiated protocol version.
+    async fn reconnect(&self) -> Result<String> {
+        let client_capabilities = self
+            .client_capabilities
+            .lock()
+            .await
+            .clone()
+            .ok_or_else(|| Error::ConfigError("MCP client not initialized".into()))?;
+        let request = Self::initialize_request(&client_capabilities)?;
+        let request_id = request.id.clone();
+        let response = self.send_request(request).await?;
+        let capabilities = self.accept_initialize(response, request_id).await?;
+        if let Some(observer) = &self.observer {
+            observer.on_reconnected(&capabilities);
+        }
+        Ok(capabilities.protocol_version)
+    }
+
     /// Send a JSON-RPC request to the server.
     async fn send_request(&self, request: JsonRpcRequest) -> Result<JsonRpcResponse> {
+        let request_json = serde_json::to_string(&request).map_err(Error::SerializationError)?;
+        let response_body = self.post_json(request_json, None).await?;
+        serde_json::from_str(&response_body).map_err(Error::SerializationError)
+    }
+
+    /// POST a serialized JSON-RPC message, or a batch of `batch_len`
+    /// messages, and return the response body, enforcing the concurrency and
+    /// size limits.
+    pub(crate) async fn post_json(
+        &self,
+        request_json: String,
+        batch_len: Option<usize>,
+    ) -> Result<String> {
         // Acquire semaphore permit to limit concurrent requests
-        let _permit = self.semaphore.acquire().await.map_err(|_| {
+        let permit = self.semaphore.acquire().await.map_err(|_| {
             Error::ResourceExhausted("Too many concurrent MCP requests".to_string())
         })?;
 
         // Check request size limit before sending
-        let request_json = serde_json::to_string(&request).map_err(Error::SerializationError)?;
         if request_json.len() > self.config.max_request_size {
             return Err(Error::ResourceExhausted(format!(
                 "Request too large: {} bytes (max: {})",
@@ -181,31 +483,32 @@ impl MCPClient {
             )));
         }
 
-        let response = tokio::time::timeout(
-            self.config.request_timeout,
-            self.client
-                .post(self.server_url.clone())
-                .header("Content-Type", "application/json")
-                .body(request_json)
-                .send(),
-        )
-        .await
-        .map_err(|_| {
-            Error::TimeoutError(format!(
-                "MCP request timeout after {:?}",
-                self.config.request_timeout
-            ))
-        })?
-        .map_err(Error::HttpError)?;
-
-        if !response.status().is_success() {
-            let status_code = response.status().as_u16();
-            let raw_body = response.text().await.unwrap_or_default();
-            return Err(Error::ApiError {
-                code: status_code,
-                message: create_safe_error_message(&raw_body, "MCP server error"),
-                metadata: None,
-            });
+        let response = self
+            .send_http(
+                self.client
+                    .post(self.server_url.clone())
+                    .header("Content-Type", "application/json")
+                    .headers(self.auth_headers.clone())
+                    .headers(
+                        self.transport
+                            .as_ref()
+                            .map(SseTransport::post_headers)
+                            .unwrap_or_default(),
+                    )
+                    .body(request_json),
+            )
+            .await?;
+        let response = self.check_response(response).await?;
+
+        if let Some(transport) = &self.transport {
+            if is_event_stream(response.headers()) {
+                // The stream may carry server requests that must be answered
+                // before the response arrives, so it does not hold a permit.
+                drop(permit);
+                return self
+                    .read_stream_responses(transport, response, batch_len)
+                    .await;
+            }
         }
 
         // Check response size limit from Content-Length header
@@ -218,7 +521,6 @@ impl MCPClient {
         }
 
         // Read body with strict size limit
-        use futures::StreamExt;
         let mut stream = response.bytes_stream();
         let mut body_bytes = Vec::new();
 
@@ -233,15 +535,150 @@ impl MCPClient {
             body_bytes.extend_from_slice(&chunk);
         }
 
-        let response_body = String::from_utf8(body_bytes)
-            .map_err(|e| Error::ConfigError(format!("Invalid UTF-8 in response: {}", e)))?;
+        String::from_utf8(body_bytes)
+            .map_err(|e| Error::ConfigError(format!("Invalid UTF-8 in response: {}", e)))
+    }
 
-        let response: JsonRpcResponse =
-            serde_json::from_str(&response_body).map_err(Error::SerializationError)?;
+    /// Send an HTTP request within `request_timeout`.
+    async fn send_http(&self, request: reqwest::RequestBuilder) -> Result<reqwest::Response> {
+        tokio::time::timeout(self.config.request_timeout, request.send())
+            .await
+            .map_err(|_| {
+                Error::TimeoutError(format!(
+                    "MCP request timeout after {:?}",
+                    self.config.request_timeout
+                ))
+            })?
+            .map_err(Error::HttpError)
+    }
 
+    /// Fail on error statuses, keeping the SSE transport's session in sync.
+    async fn check_response(&self, response: reqwest::Response) -> Result<reqwest::Response> {
+        if let Some(transport) = &self.transport {
+            if response.status() == reqwest::StatusCode::NOT_FOUND
+                && transport.session_id().is_some()
+            {
+                // The session expired; `initialize` starts a new one.
+                transport.reset_session();
+            } else {
+                transport.record_session(response.headers());
+            }
+        }
+
+        if !response.status().is_success() {
+            let status_code = response.status().as_u16();
+            let raw_body = response.text().await.unwrap_or_default();
+            return Err(Error::ApiError {
+                code: status_code,
+                message: create_safe_error_message(&raw_body, "MCP server error"),
+                metadata: None,
+            });
+        }
         Ok(response)
     }
 
+    /// Read the responses to a POST from an event stream, resuming it from
+    /// the last event if it drops.
+    async fn read_stream_responses(
+        &self,
+        transport: &SseTransport,
+        mut response: reqwest::Response,
+        batch_len: Option<usize>,
+    ) -> Result<String> {
+        let mut responses = Vec::new();
+        let mut failures = 0;
+        while let Err(error) = self
+            .consume_event_stream(
+                transport,
+                response,
+                &mut responses,
+                Some(batch_len.unwrap_or(1)),
+            )
+            .await
+        {
+            if !matches!(error, Error::StreamingError(_))
+                || transport.last_event_id().is_none()
+                || failures >= transport.max_reconnects()
+            {
+                return Err(error);
+            }
+            failures += 1;
+            if let Some(observer) = &self.observer {
+                observer.on_connection_lost(&error);
+            }
+            tokio::time::sleep(transport.reconnect_delay()).await;
+            response = self.open_event_stream(transport).await?;
+        }
+
+        let body = match batch_len {
+            Some(_) => serde_json::Value::Array(responses),
+            None => responses.swap_remove(0),
+        };
+        Ok(body.to_string())
+    }
+
+    /// Open the standalone event stream, resuming after the last event
+    /// received if there is one.
+    async fn open_event_stream(&self, transport: &SseTransport) -> Result<reqwest::Response> {
+        let response = self
+            .send_http(
+                self.client
+                    .get(self.server_url.clone())
+                    .headers(self.auth_headers.clone())
+                    .headers(transport.stream_headers()),
+            )
+            .await?;
+        self.check_response(response).await
+    }
+
+    /// Dispatch the messages of an event stream: notifications go to
+    /// subscribers, server requests are answered and responses are collected
+    /// until `expected` arrived. Without `expected`, reads until the server
+    /// closes the stream.
+    async fn consume_event_stream(
+        &self,
+        transport: &SseTransport,
+        response: reqwest::Response,
+        responses: &mut Vec<serde_json::Value>,
+        expected: Option<usize>,
+    ) -> Result<()> {
+        let events = event_stream(response, self.config.max_response_size);
+        let mut events = std::pin::pin!(events);
+        while let Some(event) = events.next().await {
+            let event = event?;
+            transport.record_event(&event);
+            let Ok(message) = serde_json::from_str::<serde_json::Value>(&event.data) else {
+                continue;
+            };
+            let messages = match message {
+                serde_json::Value::Array(messages) => messages,
+                message => vec![message],
+            };
+            for message in messages {
+                if message.get("method").is_none() {
+                    responses.push(message);
+                } else if message.get("id").is_some() {
+                    if let Ok(request) = serde_json::from_value(message) {
+                        self.handle_server_request(request).await?;
+                    }
+                } else if let Ok(notification) = serde_json::from_value(message) {
+                    // Having no subscribers is fine.
+                    let _ = self.notifications.send(notification);
+                }
+            }
+            if expected.is_some_and(|expected| responses.len() >= expected) {
+                return Ok(());
+            }
+        }
+
+        match expected {
+            Some(_) => Err(Error::StreamingError(
+                "Event stream ended before the response".into(),
+            )),
+            None => Ok(()),
+        }
+    }
+
     /// Send a JSON-RPC response to the server with security controls.
     async fn send_response(&self, response: JsonRpcResponse) -> Result<()> {
         // Acquire semaphore permit to limit concurrent requests
@@ -267,6 +704,13 @@ impl MCPClient {
             self.client
                 .post(self.server_url.clone())
                 .header("Content-Type", "application/json")
+                .headers(self.auth_headers.clone())
+                .headers(
+                    self.transport
+                        .as_ref()
+                        .map(SseTransport::session_headers)
+                        .unwrap_or_default(),
+                )
                 .body(response_json)
                 .send(),
         )
@@ -291,20 +735,23 @@ impl MCPClient {
             )));
         }
 
-        // Check for errors
+        let result = Self::response_result(response)?;
+        serde_json::from_value(result).map_err(Error::SerializationError)
+    }
+
+    /// The result of a JSON-RPC response, or its error as `Error::McpError`.
+    pub(crate) fn response_result(response: JsonRpcResponse) -> Result<serde_json::Value> {
         if let Some(error) = response.error {
-            return Err(Error::ApiError {
-                code: error.code.try_into().unwrap_or(500),
+            return Err(Error::McpError {
+                code: error.kind(),
                 message: error.message,
-                metadata: error.data,
+                data: error.data,
             });
         }
 
-        // Parse result
-        match response.result {
-            Some(result) => serde_json::from_value(result).map_err(Error::SerializationError),
-            None => Err(Error::ConfigError("Response contains no result".into())),
-        }
+        response
+            .result
+            .ok_or_else(|| Error::ConfigError("Response contains no result".into()))
     }
 
     /// Ensure the client has been initialized.
@@ -314,11 +761,182 @@ impl MCPClient {
         }
         Ok(())
     }
+
+    /// Ensure the client has been initialized and the server advertised
+    /// `capability`, returning the negotiated protocol version.
+    ///
+    /// Servers that advertise no capabilities at all are assumed to support
+    /// every method.
+    pub(crate) async fn ensure_capability(&self, capability: &str, method: &str) -> Result<String> {
+        let capabilities = self.capabilities.lock().await;
+        let Some(capabilities) = capabilities.as_ref() else {
+            return Err(Error::ConfigError("MCP client not initialized".into()));
+        };
+        if let Some(features) = &capabilities.capabilities {
+            if !features.supports(capability) {
+                return Err(Error::CapabilityNotSupported {
+                    capability: capability.to_string(),
+                    method: method.to_string(),
+                });
+            }
+        }
+        Ok(capabilities.protocol_version.clone())
+    }
+
+    /// The protocol version negotiated by `initialize`.
+    pub async fn protocol_version(&self) -> Option<String> {
+        self.capabilities
+            .lock()
+            .await
+            .as_ref()
+            .map(|capabilities| capabilities.protocol_version.clone())
+    }
+
+    /// Start a JSON-RPC batch; see [`McpBatch`].
+    pub fn batch(&self) -> McpBatch<'_> {
+        McpBatch::new(self)
+    }
+
+    /// Receive the messages the server initiates on its standalone event
+    /// stream until the server closes it: notifications go to
+    /// [`subscribe_notifications`](Self::subscribe_notifications) and
+    /// requests are answered with
+    /// [`handle_server_request`](Self::handle_server_request).
+    ///
+    /// Dropped streams, including those cut off by `request_timeout`, are
+    /// resumed from the last event, giving up after `max_reconnects`
+    /// failures in a row. Requires the SSE transport.
+    pub async fn listen(&self) -> Result<()> {
+        let transport = self
+            .transport
+            .as_ref()
+            .ok_or_else(|| Error::ConfigError("Listening requires the SSE transport".into()))?;
+        let mut failures = 0;
+        loop {
+            let error = match self.open_event_stream(transport).await {
+                Ok(response) => {
+                    failures = 0;
+                    match self
+                        .consume_event_stream(transport, response, &mut Vec::new(), None)
+                        .await
+                    {
+                        Ok(()) => return Ok(()),
+                        Err(error) => error,
+                    }
+                }
+                Err(error) => error,
+            };
+            let resumable = matches!(
+                error,
+                Error::StreamingError(_) | Error::HttpError(_) | Error::TimeoutError(_)
+            );
+            if !resumable || failures >= transport.max_reconnects() {
+                return Err(error);
+            }
+            failures += 1;
+            if let Some(observer) = &self.observer {
+                observer.on_connection_lost(&error);
+            }
+            tokio::time::sleep(transport.reconnect_delay()).await;
+        }
+    }
+
+    /// End the SSE transport's session; `initialize` starts a new one.
+    ///
+    /// Servers that do not let clients end sessions answer 405, and those
+    /// that already ended it 404; neither is an error.
+    pub async fn close_session(&self) -> Result<()> {
+        let Some(transport) = &self.transport else {
+            return Ok(());
+        };
+        if transport.session_id().is_none() {
+            return Ok(());
+        }
+        let response = self
+            .send_http(
+                self.client
+                    .delete(self.server_url.clone())
+                    .headers(self.auth_headers.clone())
+                    .headers(transport.session_headers()),
+            )
+            .await?;
+        let result = match response.status() {
+            reqwest::StatusCode::METHOD_NOT_ALLOWED | reqwest::StatusCode::NOT_FOUND => Ok(()),
+            _ => self.check_response(response).await.map(drop),
+        };
+        transport.reset_session();
+        result
+    }
+}
+
+/// Observes retries and reconnections of an [`MCPClient`]. Every method has
+/// a no-op default.
+pub trait McpConnectionObserver: Send + Sync {
+    /// Called before retry number `attempt` of `method`.
+    fn on_retry(&self, method: &str, attempt: u32, error: &Error) {
+        let _ = (method, attempt, error);
+    }
+
+    /// Called when an initialized client loses its connection; it will
+    /// re-initialize before retrying.
+    fn on_connection_lost(&self, error: &Error) {
+        let _ = error;
+    }
+
+    /// Called after re-initializing, with the re-synced server capabilities.
+    fn on_reconnected(&self, capabilities: &ServerCapabilities) {
+        let _ = capabilities;
+    }
+}
+
+/// Whether the request failed before reaching the server.
+fn never_sent(error: &Error) -> bool {
+    matches!(error, Error::HttpError(e) if e.is_connect())
+}
+
+/// Whether the connection failed or was dropped without a response.
+fn is_connection_loss(error: &Error) -> bool {
+    matches!(error, Error::HttpError(e) if e.is_connect() || e.is_request())
+}
+
+fn retry_decision(
+    retry: &RetryConfig,
+    error: &Error,
+    idempotent: bool,
+    context: &RetryContext<'_>,
+) -> RetryDecision {
+    if !idempotent && !never_sent(error) {
+        return RetryDecision::Stop;
+    }
+    match (error, &retry.policy) {
+        (_, Some(policy)) => policy.should_retry(RetryTrigger::Error(error), context),
+        (Error::ApiError { code, .. }, None) if retry.retry_on_status_codes.contains(code) => {
+            RetryDecision::Retry
+        }
+        (_, None) if is_connection_loss(error) => RetryDecision::Retry,
+        (_, None) => {
+            StatusCodeRetryPolicy::new(Vec::new()).should_retry(RetryTrigger::Error(error), context)
+        }
+    }
+}
+
+/// MCP's error code for a request the user rejected.
+const USER_REJECTED: McpErrorCode = McpErrorCode::ServerDefined(-1);
+
+fn json_rpc_error(code: McpErrorCode, message: &str) -> JsonRpcError {
+    JsonRpcError {
+        code: code.code() as i32,
+        message: message.to_string(),
+        data: None,
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::client::RetryConfig;
+    use crate::mcp::transport::SseTransport;
+    use crate::types::chat::{ChatRole, ContentPart, MessageContent};
     use reqwest::StatusCode;
     use std::time::Duration;
     use wiremock::{matchers, Mock, MockServer, ResponseTemplate};
@@ -329,6 +947,8 @@ mod tests {
             max_response_size: 1024, // 1KB for testing
             max_request_size: 512,   // 512B for testing
             max_concurrent_requests: 2,
+            auth: McpAuth::None,
+            retry: None,
         }
     }
 
@@ -637,4 +1257,703 @@ mod tests {
             _ => panic!("Expected ResourceExhausted error, got: {:?}", error),
         }
     }
+
+    async fn mock_method(server: &MockServer, method: &str, result: serde_json::Value) {
+        Mock::given(matchers::method("POST"))
+            .and(matchers::body_partial_json(
+                serde_json::json!({"method": method}),
+            ))
+            .respond_with(ResponseTemplate::new(StatusCode::OK).set_body_json(
+                serde_json::json!({"jsonrpc": "2.0", "id": "test", "result": result}),
+            ))
+            .mount(server)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn test_list_and_get_prompts() {
+        let mock_server = MockServer::start().await;
+        mock_method(
+            &mock_server,
+            "initialize",
+            serde_json::json!({"protocol_version": "2025-03-26"}),
+        )
+        .await;
+        mock_method(
+            &mock_server,
+            "prompts/list",
+            serde_json::json!({
+                "prompts": [{
+                    "name": "review",
+                    "description": "Review code",
+                    "arguments": [
+                        {"name": "code", "required": true},
+                        {"name": "style"}
+                    ]
+                }],
+                "nextCursor": "page-2"
+            }),
+        )
+        .await;
+        Mock::given(matchers::method("POST"))
+            .and(matchers::body_partial_json(serde_json::json!({
+                "method": "prompts/get",
+                "params": {"name": "review", "arguments": {"code": "fn main() {}"}}
+            })))
+            .respond_with(ResponseTemplate::new(StatusCode::OK).set_body_json(
+                serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "id": "test",
+                    "result": {
+                        "messages": [
+                            {"role": "user", "content": {"type": "text", "text": "Review fn main() {}"}},
+                            {"role": "user", "content": {"type": "image", "data": "aGk=", "mimeType": "image/png"}},
+                            {"role": "assistant", "content": {"type": "resource", "resource": {"uri": "file:///a.rs", "text": "// notes"}}}
+                        ]
+                    }
+                }),
+            ))
+            .mount(&mock_server)
+            .await;
+
+        let client = MCPClient::new(mock_server.uri()).unwrap();
+        client
+            .initialize(ClientCapabilities {
+                protocol_version: MCP_PROTOCOL_VERSION.to_string(),
+                supports_sampling: None,
+            })
+            .await
+            .unwrap();
+
+        let listed = client
+            .list_prompts(ListPromptsParams::default())
+            .await
+            .unwrap();
+        assert_eq!(listed.next_cursor.as_deref(), Some("page-2"));
+        let prompt = &listed.prompts[0];
+        let params = GetPromptParams::new("review");
+        assert_eq!(prompt.missing_arguments(&params.arguments), ["code"]);
+        let params = params.with_argument("code", "fn main() {}");
+        assert!(prompt.missing_arguments(&params.arguments).is_empty());
+
+        let messages = client.get_prompt(params).await.unwrap().into_messages();
+        assert_eq!(messages.len(), 3);
+        assert_eq!(messages[0].content.as_text(), Some("Review fn main() {}"));
+        let MessageContent::Parts(parts) = &messages[1].content else {
+            panic!("expected image part");
+        };
+        assert!(matches!(
+            &parts[0],
+            ContentPart::Image(image) if image.image_url.url == "data:image/png;base64,aGk="
+        ));
+        assert_eq!(messages[2].role, ChatRole::Assistant);
+        assert_eq!(messages[2].content.as_text(), Some("// notes"));
+    }
+
+    struct FixedSampler;
+
+    impl McpSamplingHandler for FixedSampler {
+        fn create_message<'a>(
+            &'a self,
+            params: CreateMessageParams,
+        ) -> crate::mcp::sampling::SamplingFuture<'a> {
+            Box::pin(async move {
+                if params.max_tokens == 0 {
+                    return Err(Error::Cancelled("rejected".into()));
+                }
+                Ok(CreateMessageResult {
+                    role: PromptRole::Assistant,
+                    content: PromptContent::Text {
+                        text: "Paris".into(),
+                    },
+                    model: "openai/gpt-4o".into(),
+                    stop_reason: Some("endTurn".into()),
+                })
+            })
+        }
+    }
+
+    fn sampling_request(id: &str, max_tokens: u32) -> JsonRpcRequest {
+        JsonRpcRequest {
+            jsonrpc: "2.0".into(),
+            id: id.into(),
+            method: "sampling/createMessage".into(),
+            params: Some(serde_json::json!({
+                "messages": [{"role": "user", "content": {"type": "text", "text": "Capital of France?"}}],
+                "maxTokens": max_tokens
+            })),
+            protocol_version: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_handle_sampling_request() {
+        let mock_server = MockServer::start().await;
+        Mock::given(matchers::method("POST"))
+            .and(matchers::body_partial_json(serde_json::json!({
+                "id": "s1",
+                "result": {"role": "assistant", "content": {"type": "text", "text": "Paris"}, "stopReason": "endTurn"}
+            })))
+            .respond_with(ResponseTemplate::new(StatusCode::OK))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(matchers::method("POST"))
+            .and(matchers::body_partial_json(
+                serde_json::json!({"id": "s2", "error": {"code": -1}}),
+            ))
+            .respond_with(ResponseTemplate::new(StatusCode::OK))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(matchers::method("POST"))
+            .and(matchers::body_partial_json(
+                serde_json::json!({"id": "s3", "error": {"code": -32601}}),
+            ))
+            .respond_with(ResponseTemplate::new(StatusCode::OK))
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+
+        let client = MCPClient::new(mock_server.uri())
+            .unwrap()
+            .with_sampling_handler(Arc::new(FixedSampler));
+        client
+            .handle_server_request(sampling_request("s1", 100))
+            .await
+            .unwrap();
+        client
+            .handle_server_request(sampling_request("s2", 0))
+            .await
+            .unwrap();
+        let mut unknown = sampling_request("s3", 100);
+        unknown.method = "roots/list".into();
+        client.handle_server_request(unknown).await.unwrap();
+
+        // Without a handler, sampling is not supported.
+        let client = MCPClient::new(mock_server.uri()).unwrap();
+        client
+            .handle_server_request(sampling_request("s3", 100))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_auth_headers_and_override() {
+        let mock_server = MockServer::start().await;
+        let initialize_result = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": "test",
+            "result": {"protocol_version": "2025-03-26"}
+        });
+        Mock::given(matchers::method("POST"))
+            .and(matchers::header("authorization", "Bearer server-token"))
+            .and(matchers::header("x-tenant", "acme"))
+            .respond_with(ResponseTemplate::new(StatusCode::OK).set_body_json(&initialize_result))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(matchers::method("POST"))
+            .and(matchers::header("x-api-key", "user-key"))
+            .respond_with(ResponseTemplate::new(StatusCode::OK).set_body_json(&initialize_result))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let config = McpConfig {
+            auth: McpAuth::Headers(vec![
+                ("Authorization".into(), "Bearer server-token".into()),
+                ("X-Tenant".into(), "acme".into()),
+            ]),
+            ..Default::default()
+        };
+        let client = MCPClient::new_with_config(mock_server.uri(), config).unwrap();
+        let capabilities = ClientCapabilities {
+            protocol_version: MCP_PROTOCOL_VERSION.to_string(),
+            supports_sampling: None,
+        };
+        client.initialize(capabilities.clone()).await.unwrap();
+        client
+            .with_auth(McpAuth::api_key("X-API-Key", "user-key"))
+            .unwrap()
+            .initialize(capabilities)
+            .await
+            .unwrap();
+    }
+
+    #[test]
+    fn test_auth_validation_and_redaction() {
+        assert!(matches!(
+            MCPClient::new_with_config(
+                "https://mcp.example.com",
+                McpConfig {
+                    auth: McpAuth::bearer("bad\ntoken"),
+                    ..Default::default()
+                },
+            ),
+            Err(Error::ConfigError(_))
+        ));
+        let client = MCPClient::new("https://mcp.example.com").unwrap();
+        assert!(client
+            .with_auth(McpAuth::api_key("bad header", "key"))
+            .is_err());
+
+        let debug = format!("{:?}", McpAuth::api_key("X-API-Key", "secret-key"));
+        assert!(debug.contains("X-API-Key"));
+        assert!(!debug.contains("secret-key"));
+    }
+
+    #[tokio::test]
+    async fn test_version_negotiation_and_capability_gating() {
+        let mock_server = MockServer::start().await;
+        mock_method(
+            &mock_server,
+            "initialize",
+            serde_json::json!({
+                "protocolVersion": "2024-11-05",
+                "capabilities": {"tools": {"listChanged": true}}
+            }),
+        )
+        .await;
+        Mock::given(matchers::method("POST"))
+            .and(matchers::body_partial_json(serde_json::json!({
+                "method": "toolCall",
+                "protocol_version": "2024-11-05"
+            })))
+            .respond_with(ResponseTemplate::new(StatusCode::OK).set_body_json(
+                serde_json::json!({"jsonrpc": "2.0", "id": "test", "result": {"result": 42}}),
+            ))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = MCPClient::new(mock_server.uri()).unwrap();
+        let server = client
+            .initialize(ClientCapabilities {
+                protocol_version: MCP_PROTOCOL_VERSION.to_string(),
+                supports_sampling: None,
+            })
+            .await
+            .unwrap();
+        assert!(server.capabilities.unwrap().supports("tools"));
+        assert_eq!(
+            client.protocol_version().await.as_deref(),
+            Some("2024-11-05")
+        );
+
+        let result = client
+            .tool_call(ToolCallParams {
+                id: "answer".into(),
+                parameters: serde_json::json!({}),
+            })
+            .await
+            .unwrap();
+        assert_eq!(result.result, 42);
+        let error = client
+            .list_prompts(ListPromptsParams::default())
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            &error,
+            Error::CapabilityNotSupported { capability, method }
+                if capability == "prompts" && method == "prompts/list"
+        ));
+        assert_eq!(error.code(), "capability_not_supported");
+    }
+
+    #[tokio::test]
+    async fn test_unsupported_server_version_is_rejected() {
+        let mock_server = MockServer::start().await;
+        mock_method(
+            &mock_server,
+            "initialize",
+            serde_json::json!({"protocolVersion": "2099-01-01"}),
+        )
+        .await;
+
+        let client = MCPClient::new(mock_server.uri()).unwrap();
+        let result = client
+            .initialize(ClientCapabilities {
+                protocol_version: MCP_PROTOCOL_VERSION.to_string(),
+                supports_sampling: None,
+            })
+            .await;
+        assert!(matches!(result, Err(Error::ConfigError(msg)) if msg.contains("2099-01-01")));
+        assert!(client.protocol_version().await.is_none());
+    }
+
+    fn retrying_config() -> McpConfig {
+        McpConfig {
+            retry: Some(RetryConfig {
+                max_retries: 5,
+                initial_backoff_ms: 50,
+                max_backoff_ms: 200,
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[derive(Default)]
+    struct RecordingObserver {
+        events: std::sync::Mutex<Vec<String>>,
+    }
+
+    impl McpConnectionObserver for RecordingObserver {
+        fn on_retry(&self, method: &str, attempt: u32, _error: &Error) {
+            self.events
+                .lock()
+                .unwrap()
+                .push(format!("retry {method} {attempt}"));
+        }
+
+        fn on_connection_lost(&self, _error: &Error) {
+            self.events.lock().unwrap().push("lost".into());
+        }
+
+        fn on_reconnected(&self, capabilities: &ServerCapabilities) {
+            self.events
+                .lock()
+                .unwrap()
+                .push(format!("reconnected {}", capabilities.protocol_version));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retries_only_idempotent_calls_on_server_errors() {
+        let mock_server = MockServer::start().await;
+        mock_method(
+            &mock_server,
+            "initialize",
+            serde_json::json!({"protocol_version": "2025-03-26"}),
+        )
+        .await;
+        Mock::given(matchers::method("POST"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(1)
+            .with_priority(1)
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        mock_method(
+            &mock_server,
+            "prompts/list",
+            serde_json::json!({"prompts": []}),
+        )
+        .await;
+        Mock::given(matchers::method("POST"))
+            .and(matchers::body_partial_json(
+                serde_json::json!({"method": "toolCall"}),
+            ))
+            .respond_with(ResponseTemplate::new(503))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = MCPClient::new_with_config(mock_server.uri(), retrying_config()).unwrap();
+        client
+            .initialize(ClientCapabilities {
+                protocol_version: MCP_PROTOCOL_VERSION.to_string(),
+                supports_sampling: None,
+            })
+            .await
+            .unwrap();
+        let listed = client
+            .list_prompts(ListPromptsParams::default())
+            .await
+            .unwrap();
+        assert!(listed.prompts.is_empty());
+
+        let result = client
+            .tool_call(ToolCallParams {
+                id: "send_email".into(),
+                parameters: serde_json::json!({}),
+            })
+            .await;
+        assert!(matches!(result, Err(Error::ApiError { code: 503, .. })));
+    }
+
+    #[tokio::test]
+    async fn test_reconnects_after_connection_loss() {
+        async fn server_on(listener: std::net::TcpListener, version: &str) -> MockServer {
+            let server = MockServer::builder().listener(listener).start().await;
+            mock_method(
+                &server,
+                "initialize",
+                serde_json::json!({"protocolVersion": version, "capabilities": {"prompts": {}}}),
+            )
+            .await;
+            mock_method(&server, "prompts/list", serde_json::json!({"prompts": []})).await;
+            server
+        }
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let first = server_on(listener, "2025-03-26").await;
+
+        let observer = Arc::new(RecordingObserver::default());
+        let client = MCPClient::new_with_config(first.uri(), retrying_config())
+            .unwrap()
+            .with_connection_observer(observer.clone());
+        client
+            .initialize(ClientCapabilities {
+                protocol_version: MCP_PROTOCOL_VERSION.to_string(),
+                supports_sampling: None,
+            })
+            .await
+            .unwrap();
+
+        // The server restarts on the same address, now speaking an older
+        // protocol version.
+        drop(first);
+        let restart = tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            server_on(std::net::TcpListener::bind(address).unwrap(), "2024-11-05").await
+        });
+
+        client
+            .list_prompts(ListPromptsParams::default())
+            .await
+            .unwrap();
+        let _second = restart.await.unwrap();
+
+        assert_eq!(
+            client.protocol_version().await.as_deref(),
+            Some("2024-11-05")
+        );
+        let events = observer.events.lock().unwrap().clone();
+        assert_eq!(events[0], "lost");
+        assert_eq!(events[1], "retry prompts/list 1");
+        assert_eq!(events.last().unwrap(), "reconnected 2024-11-05");
+    }
+
+    #[tokio::test]
+    async fn test_json_rpc_errors_keep_their_code() {
+        let mock_server = MockServer::start().await;
+        Mock::given(matchers::method("POST"))
+            .respond_with(ResponseTemplate::new(StatusCode::OK).set_body_json(
+                serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "id": "test",
+                    "error": {"code": -32601, "message": "Unknown method", "data": {"method": "initialize"}}
+                }),
+            ))
+            .mount(&mock_server)
+            .await;
+
+        let client = MCPClient::new(mock_server.uri()).unwrap();
+        let error = client
+            .initialize(ClientCapabilities {
+                protocol_version: MCP_PROTOCOL_VERSION.to_string(),
+                supports_sampling: None,
+            })
+            .await
+            .unwrap_err();
+        match error {
+            Error::McpError {
+                code,
+                message,
+                data,
+            } => {
+                assert_eq!(code, McpErrorCode::MethodNotFound);
+                assert_eq!(code.code(), -32601);
+                assert_eq!(message, "Unknown method");
+                assert_eq!(data.unwrap()["method"], "initialize");
+            }
+            other => panic!("Expected McpError, got: {other:?}"),
+        }
+        assert_eq!(
+            McpErrorCode::from(-32099),
+            McpErrorCode::ServerDefined(-32099)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_batch_correlates_responses_by_id() {
+        let mock_server = MockServer::start().await;
+        mock_method(
+            &mock_server,
+            "initialize",
+            serde_json::json!({
+                "protocolVersion": MCP_PROTOCOL_VERSION,
+                "capabilities": {"tools": {}, "prompts": {}}
+            }),
+        )
+        .await;
+        // Answer in reverse order, failing the tool call.
+        Mock::given(matchers::method("POST"))
+            .and(|request: &wiremock::Request| {
+                serde_json::from_slice::<serde_json::Value>(&request.body)
+                    .is_ok_and(|body| body.is_array())
+            })
+            .respond_with(|request: &wiremock::Request| {
+                let calls: Vec<JsonRpcRequest> = serde_json::from_slice(&request.body).unwrap();
+                let responses: Vec<_> = calls
+                    .iter()
+                    .rev()
+                    .map(|call| match call.method.as_str() {
+                        "prompts/list" => serde_json::json!({
+                            "jsonrpc": "2.0",
+                            "id": call.id,
+                            "result": {"prompts": [{"name": "review"}]}
+                        }),
+                        _ => serde_json::json!({
+                            "jsonrpc": "2.0",
+                            "id": call.id,
+                            "error": {"code": -32602, "message": "Unknown tool"}
+                        }),
+                    })
+                    .collect();
+                ResponseTemplate::new(StatusCode::OK).set_body_json(responses)
+            })
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = MCPClient::new(mock_server.uri()).unwrap();
+        assert!(client.batch().send().await.unwrap().is_empty());
+        client
+            .initialize(ClientCapabilities {
+                protocol_version: MCP_PROTOCOL_VERSION.to_string(),
+                supports_sampling: None,
+            })
+            .await
+            .unwrap();
+
+        let mut batch = client.batch();
+        let prompts = batch.list_prompts(ListPromptsParams::default()).unwrap();
+        let tool = batch
+            .tool_call(ToolCallParams {
+                id: "missing".into(),
+                parameters: serde_json::json!({}),
+            })
+            .unwrap();
+        assert_eq!((prompts.index(), tool.index()), (0, 1));
+
+        let mut response = batch.send().await.unwrap();
+        assert_eq!(response.len(), 2);
+        assert_eq!(response.take(prompts).unwrap().prompts[0].name, "review");
+        assert!(matches!(
+            response.take(tool),
+            Err(Error::McpError {
+                code: McpErrorCode::InvalidParams,
+                ..
+            })
+        ));
+
+        let mut batch = client.batch();
+        batch
+            .get_resource(GetResourceParams {
+                id: "file:///README.md".into(),
+                parameters: None,
+            })
+            .unwrap();
+        assert!(matches!(
+            batch.send().await,
+            Err(Error::CapabilityNotSupported { .. })
+        ));
+    }
+
+    fn event_stream_response(body: &str) -> ResponseTemplate {
+        ResponseTemplate::new(StatusCode::OK)
+            .insert_header("Mcp-Session-Id", "session-1")
+            .set_body_raw(body.to_string(), "text/event-stream")
+    }
+
+    #[tokio::test]
+    async fn test_sse_transport_session_notifications_and_resume() {
+        let mock_server = MockServer::start().await;
+        Mock::given(matchers::method("POST"))
+            .and(matchers::body_partial_json(
+                serde_json::json!({"method": "initialize"}),
+            ))
+            .and(matchers::header_regex("Accept", "text/event-stream"))
+            .respond_with(event_stream_response(concat!(
+                ": connected\n\n",
+                "id: 1\n",
+                "data: {\"jsonrpc\": \"2.0\", \"method\": \"notifications/message\",\n",
+                "data:  \"params\": {\"level\": \"info\"}}\n\n",
+                "id: 2\n",
+                "data: {\"jsonrpc\": \"2.0\", \"id\": \"test\", \"result\": {\"protocolVersion\": \"2025-03-26\"}}\n\n",
+            )))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        // The tool call's stream drops after a progress notification...
+        Mock::given(matchers::method("POST"))
+            .and(matchers::body_partial_json(
+                serde_json::json!({"method": "toolCall"}),
+            ))
+            .and(matchers::header("Mcp-Session-Id", "session-1"))
+            .respond_with(event_stream_response(
+                "id: 3\ndata: {\"jsonrpc\": \"2.0\", \"method\": \"notifications/progress\"}\n\n",
+            ))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        // ...and is resumed from the last event.
+        Mock::given(matchers::method("GET"))
+            .and(matchers::header("Last-Event-ID", "3"))
+            .and(matchers::header("Mcp-Session-Id", "session-1"))
+            .respond_with(event_stream_response(
+                "id: 4\ndata: {\"jsonrpc\": \"2.0\", \"id\": \"test\", \"result\": {\"result\": 42}}\n\n",
+            ))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(matchers::method("GET"))
+            .and(matchers::header("Last-Event-ID", "4"))
+            .respond_with(event_stream_response(
+                "id: 5\ndata: {\"jsonrpc\": \"2.0\", \"method\": \"notifications/tools/list_changed\"}\n\n",
+            ))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(matchers::method("DELETE"))
+            .and(matchers::header("Mcp-Session-Id", "session-1"))
+            .respond_with(ResponseTemplate::new(StatusCode::OK))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let transport = SseTransport::new().with_reconnect_delay(Duration::from_millis(10));
+        let client = MCPClient::new(mock_server.uri())
+            .unwrap()
+            .with_sse_transport(transport.clone());
+        let mut notifications = client.subscribe_notifications();
+
+        client
+            .initialize(ClientCapabilities {
+                protocol_version: MCP_PROTOCOL_VERSION.to_string(),
+                supports_sampling: None,
+            })
+            .await
+            .unwrap();
+        assert_eq!(transport.session_id().as_deref(), Some("session-1"));
+
+        let result = client
+            .tool_call(ToolCallParams {
+                id: "answer".into(),
+                parameters: serde_json::json!({}),
+            })
+            .await
+            .unwrap();
+        assert_eq!(result.result, 42);
+        client.listen().await.unwrap();
+
+        let first = notifications.recv().await.unwrap();
+        assert_eq!(first.method, "notifications/message");
+        assert_eq!(first.params.unwrap()["level"], "info");
+        assert_eq!(
+            notifications.recv().await.unwrap().method,
+            "notifications/progress"
+        );
+        assert_eq!(
+            notifications.recv().await.unwrap().method,
+            "notifications/tools/list_changed"
+        );
+
+        client.close_session().await.unwrap();
+        assert_eq!(transport.session_id(), None);
+    }
 }
@@ -5,7 +5,11 @@
 //! applications provide context to LLMs. For more information, see:
 //! <https://modelcontextprotocol.io/>
 
+pub mod batch;
 pub mod client;
+pub mod sampling;
+pub mod transport;
 pub mod types;
 
-pub use types::McpConfig;
+pub use transport::SseTransport;
+pub use types::{McpAuth, McpConfig};
@@ -0,0 +1,312 @@
+//! Acting as the sampling provider for MCP servers.
+//!
+//! MCP servers can ask the client for a completion with
+//! `sampling/createMessage`. Set an [`McpSamplingHandler`] with
+//! `MCPClient::with_sampling_handler` and pass incoming requests to
+//! `MCPClient::handle_server_request`; [`OpenRouterSampler`] fulfills them
+//! through the OpenRouter chat API.
+
+use crate::api::chat::ChatApi;
+use crate::error::{Error, Result};
+use crate::mcp::types::{
+    CreateMessageParams, CreateMessageResult, ModelPreferences, PromptContent, PromptRole,
+};
+use crate::types::chat::{ChatCompletionRequest, ChatRole, Message, StopSequence};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+/// Future returned by [`McpSamplingHandler::create_message`].
+pub type SamplingFuture<'a> =
+    Pin<Box<dyn Future<Output = Result<CreateMessageResult>> + Send + 'a>>;
+
+/// Fulfills `sampling/createMessage` requests from MCP servers.
+///
+/// Return `Error::Cancelled` when the request is rejected, e.g. by the user;
+/// it is reported to the server as a rejection rather than a failure.
+pub trait McpSamplingHandler: Send + Sync {
+    fn create_message<'a>(&'a self, params: CreateMessageParams) -> SamplingFuture<'a>;
+}
+
+/// How a model rates on the axes of [`ModelPreferences`], each from 0 to 1;
+/// higher is cheaper, faster or more capable.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModelProfile {
+    pub model: String,
+    pub cost: f64,
+    pub speed: f64,
+    pub intelligence: f64,
+}
+
+impl ModelProfile {
+    pub fn new(model: impl Into<String>, cost: f64, speed: f64, intelligence: f64) -> Self {
+        Self {
+            model: model.into(),
+            cost,
+            speed,
+            intelligence,
+        }
+    }
+
+    fn score(&self, preferences: &ModelPreferences) -> f64 {
+        preferences.cost_priority.unwrap_or(0.0) * self.cost
+            + preferences.speed_priority.unwrap_or(0.0) * self.speed
+            + preferences.intelligence_priority.unwrap_or(0.0) * self.intelligence
+    }
+}
+
+type ApprovalFn = dyn Fn(&CreateMessageParams, &ChatCompletionRequest) -> bool + Send + Sync;
+
+/// Answers sampling requests with OpenRouter chat completions.
+///
+/// The model is chosen from the server's preferences: the first hint that
+/// matches an alias or a profiled model wins, then a hint that is already an
+/// OpenRouter model ID; otherwise the profile scoring best on the server's
+/// priorities; otherwise the default model.
+///
+/// # Example
+///
+/// ```no_run
+/// use openrouter_api::mcp::sampling::{ModelProfile, OpenRouterSampler};
+/// use openrouter_api::{MCPClient, OpenRouterClient};
+/// use std::sync::Arc;
+///
+/// # fn run() -> openrouter_api::Result<()> {
+/// let client = OpenRouterClient::from_env()?;
+/// let sampler = client
+///     .mcp_sampler("openai/gpt-4o-mini")?
+///     .with_alias("sonnet", "anthropic/claude-sonnet-4")
+///     .with_profile(ModelProfile::new("openai/gpt-4o", 0.3, 0.6, 0.8))
+///     // Human-in-the-loop: let the user veto each request.
+///     .with_approval(|params, request| {
+///         println!("Server wants {} to answer {} messages", request.model, params.messages.len());
+///         true
+///     });
+/// let mcp = MCPClient::new("https://mcp-server.example.com/mcp")?
+///     .with_sampling_handler(Arc::new(sampler));
+/// # Ok(())
+/// # }
+/// ```
+pub struct OpenRouterSampler {
+    chat: Arc<ChatApi>,
+    default_model: String,
+    aliases: Vec<(String, String)>,
+    profiles: Vec<ModelProfile>,
+    approval: Option<Arc<ApprovalFn>>,
+}
+
+impl OpenRouterSampler {
+    /// Creates a sampler that calls `default_model` unless the server's
+    /// preferences select another.
+    pub fn new(chat: Arc<ChatApi>, default_model: impl Into<String>) -> Self {
+        Self {
+            chat,
+            default_model: default_model.into(),
+            aliases: Vec::new(),
+            profiles: Vec::new(),
+            approval: None,
+        }
+    }
+
+    /// Maps the hint `name` (compared case-insensitively) to `model`.
+    pub fn with_alias(mut self, name: impl Into<String>, model: impl Into<String>) -> Self {
+        self.aliases
+            .push((name.into().to_lowercase(), model.into()));
+        self
+    }
+
+    /// Adds a model to choose from by hint or by priorities.
+    pub fn with_profile(mut self, profile: ModelProfile) -> Self {
+        self.profiles.push(profile);
+        self
+    }
+
+    /// Asks `approve` before each completion; returning `false` vetoes the
+    /// request, which fails with `Error::Cancelled`.
+    pub fn with_approval(
+        mut self,
+        approve: impl Fn(&CreateMessageParams, &ChatCompletionRequest) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.approval = Some(Arc::new(approve));
+        self
+    }
+
+    /// The model a request with `preferences` is sent to.
+    pub fn select_model(&self, preferences: Option<&ModelPreferences>) -> String {
+        let Some(preferences) = preferences else {
+            return self.default_model.clone();
+        };
+        for hint in preferences.hints.iter().filter_map(|h| h.name.as_deref()) {
+            let hint = hint.to_lowercase();
+            if let Some((_, model)) = self.aliases.iter().find(|(name, _)| *name == hint) {
+                return model.clone();
+            }
+            if let Some(profile) = self
+                .profiles
+                .iter()
+                .find(|p| p.model.to_lowercase().contains(&hint))
+            {
+                return profile.model.clone();
+            }
+            if hint.contains('/') {
+                return hint;
+            }
+        }
+        let mut best: Option<(&ModelProfile, f64)> = None;
+        for profile in &self.profiles {
+            let score = profile.score(preferences);
+            if best.is_none_or(|(_, best_score)| score > best_score) {
+                best = Some((profile, score));
+            }
+        }
+        match best {
+            Some((profile, score)) if score > 0.0 => profile.model.clone(),
+            _ => self.default_model.clone(),
+        }
+    }
+
+    /// The chat request a sampling request maps to.
+    pub fn to_chat_request(&self, params: &CreateMessageParams) -> ChatCompletionRequest {
+        let mut messages = Vec::with_capacity(params.messages.len() + 1);
+        if let Some(system) = &params.system_prompt {
+            messages.push(Message::text(ChatRole::System, system.clone()));
+        }
+        messages.extend(params.messages.iter().cloned().map(Message::from));
+        ChatCompletionRequest {
+            model: self.select_model(params.model_preferences.as_ref()),
+            messages,
+            max_tokens: Some(params.max_tokens),
+            temperature: params.temperature.map(|t| t as f32),
+            stop: (!params.stop_sequences.is_empty())
+                .then(|| StopSequence::Multiple(params.stop_sequences.clone())),
+            ..Default::default()
+        }
+    }
+}
+
+impl McpSamplingHandler for OpenRouterSampler {
+    fn create_message<'a>(&'a self, params: CreateMessageParams) -> SamplingFuture<'a> {
+        Box::pin(async move {
+            let request = self.to_chat_request(&params);
+            if let Some(approve) = &self.approval {
+                if !approve(&params, &request) {
+                    return Err(Error::Cancelled("Sampling request was rejected".into()));
+                }
+            }
+
+            let response = self.chat.chat_completion(request).await?;
+            let choice = response.first_choice().ok_or_else(|| Error::ApiError {
+                code: 500,
+                message: "API returned no choices".into(),
+                metadata: None,
+            })?;
+            Ok(CreateMessageResult {
+                role: PromptRole::Assistant,
+                content: PromptContent::Text {
+                    text: choice.message.content.to_text(),
+                },
+                model: response.model.clone(),
+                stop_reason: choice.finish_reason.as_deref().map(|reason| {
+                    match reason {
+                        "stop" => "endTurn",
+                        "length" => "maxTokens",
+                        other => other,
+                    }
+                    .to_string()
+                }),
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mcp::types::ModelHint;
+
+    fn sampler() -> OpenRouterSampler {
+        let client = crate::client::OpenRouterClient::<crate::client::Unconfigured>::new()
+            .with_base_url("https://openrouter.ai/api/v1/")
+            .unwrap()
+            .with_api_key("sk-1234567890abcdef1234567890abcdef123456789")
+            .unwrap();
+        OpenRouterSampler::new(Arc::new(client.chat().unwrap()), "openai/gpt-4o-mini")
+            .with_alias("Sonnet", "anthropic/claude-sonnet-4")
+            .with_profile(ModelProfile::new("openai/gpt-4o", 0.3, 0.5, 0.8))
+            .with_profile(ModelProfile::new("google/gemini-2.5-flash", 0.9, 0.9, 0.5))
+    }
+
+    fn hints(names: &[&str]) -> ModelPreferences {
+        ModelPreferences {
+            hints: names
+                .iter()
+                .map(|name| ModelHint {
+                    name: Some(name.to_string()),
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_model_selection() {
+        let sampler = sampler();
+        assert_eq!(sampler.select_model(None), "openai/gpt-4o-mini");
+        assert_eq!(
+            sampler.select_model(Some(&hints(&["claude-3-opus", "sonnet"]))),
+            "anthropic/claude-sonnet-4"
+        );
+        assert_eq!(
+            sampler.select_model(Some(&hints(&["gemini"]))),
+            "google/gemini-2.5-flash"
+        );
+        assert_eq!(
+            sampler.select_model(Some(&hints(&["mistralai/mistral-large"]))),
+            "mistralai/mistral-large"
+        );
+
+        let smart = ModelPreferences {
+            intelligence_priority: Some(1.0),
+            cost_priority: Some(0.2),
+            ..hints(&["unknown"])
+        };
+        assert_eq!(sampler.select_model(Some(&smart)), "openai/gpt-4o");
+        let cheap = ModelPreferences {
+            cost_priority: Some(1.0),
+            ..Default::default()
+        };
+        assert_eq!(
+            sampler.select_model(Some(&cheap)),
+            "google/gemini-2.5-flash"
+        );
+        assert_eq!(
+            sampler.select_model(Some(&hints(&["unknown"]))),
+            "openai/gpt-4o-mini"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_veto_rejects_before_calling_the_api() {
+        let sampler = sampler().with_approval(|params, request| {
+            assert_eq!(request.messages[0].role, ChatRole::System);
+            assert_eq!(request.max_tokens, Some(params.max_tokens));
+            false
+        });
+        let params: CreateMessageParams = serde_json::from_value(serde_json::json!({
+            "messages": [{"role": "user", "content": {"type": "text", "text": "Hi"}}],
+            "systemPrompt": "Be brief",
+            "maxTokens": 50,
+            "stopSequences": ["END"]
+        }))
+        .unwrap();
+        let request = sampler.to_chat_request(&params);
+        assert_eq!(
+            request.stop,
+            Some(StopSequence::Multiple(vec!["END".into()]))
+        );
+        assert!(matches!(
+            sampler.create_message(params).await,
+            Err(Error::Cancelled(_))
+        ));
+    }
+}
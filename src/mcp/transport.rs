@@ -0,0 +1,288 @@
+//! Streamable HTTP transport with server-sent events.
+//!
+//! Servers implementing the streamable HTTP transport may answer a POST with
+//! a `text/event-stream` that carries notifications and requests before the
+//! response, and may offer a standalone stream (GET) for messages the server
+//! initiates. Enable it with `MCPClient::with_sse_transport`; notifications
+//! are delivered to `MCPClient::subscribe_notifications`.
+
+use crate::error::{Error, Result};
+use async_stream::try_stream;
+use futures::stream::Stream;
+use futures::TryStreamExt;
+use reqwest::header::{HeaderMap, ACCEPT, CONTENT_TYPE};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio_util::codec::{FramedRead, LinesCodec};
+use tokio_util::io::StreamReader;
+
+/// Header carrying the session ID assigned by the server.
+pub const SESSION_ID_HEADER: &str = "Mcp-Session-Id";
+
+/// Header naming the last event received, to resume a dropped stream.
+pub const LAST_EVENT_ID_HEADER: &str = "Last-Event-ID";
+
+/// Session state and reconnect settings of the streamable HTTP transport.
+///
+/// Clones share the session, so clients derived with `MCPClient::with_auth`
+/// stay in the same session.
+#[derive(Debug, Clone)]
+pub struct SseTransport {
+    max_reconnects: u32,
+    reconnect_delay: Duration,
+    state: Arc<Mutex<SessionState>>,
+}
+
+#[derive(Debug, Default)]
+struct SessionState {
+    session_id: Option<String>,
+    last_event_id: Option<String>,
+    retry: Option<Duration>,
+}
+
+impl Default for SseTransport {
+    fn default() -> Self {
+        Self {
+            max_reconnects: 3,
+            reconnect_delay: Duration::from_secs(1),
+            state: Arc::default(),
+        }
+    }
+}
+
+impl SseTransport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// How many times in a row a dropped event stream is resumed before
+    /// giving up (default 3).
+    pub fn with_max_reconnects(mut self, max_reconnects: u32) -> Self {
+        self.max_reconnects = max_reconnects;
+        self
+    }
+
+    /// Wait before resuming a dropped stream (default 1s); a `retry` field
+    /// sent by the server takes precedence.
+    pub fn with_reconnect_delay(mut self, delay: Duration) -> Self {
+        self.reconnect_delay = delay;
+        self
+    }
+
+    /// The session ID assigned by the server, if any.
+    pub fn session_id(&self) -> Option<String> {
+        self.state().session_id.clone()
+    }
+
+    /// The ID of the last event received, sent when resuming a stream.
+    pub fn last_event_id(&self) -> Option<String> {
+        self.state().last_event_id.clone()
+    }
+
+    pub(crate) fn max_reconnects(&self) -> u32 {
+        self.max_reconnects
+    }
+
+    pub(crate) fn reconnect_delay(&self) -> Duration {
+        self.state().retry.unwrap_or(self.reconnect_delay)
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, SessionState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Headers for a POST: both response formats are accepted.
+    pub(crate) fn post_headers(&self) -> HeaderMap {
+        let mut headers = self.session_headers();
+        headers.insert(
+            ACCEPT,
+            "application/json, text/event-stream"
+                .parse()
+                .expect("valid header"),
+        );
+        headers
+    }
+
+    /// Headers for opening or resuming a standalone event stream.
+    pub(crate) fn stream_headers(&self) -> HeaderMap {
+        let mut headers = self.session_headers();
+        headers.insert(ACCEPT, "text/event-stream".parse().expect("valid header"));
+        if let Some(value) = self.last_event_id().and_then(|id| id.parse().ok()) {
+            headers.insert(LAST_EVENT_ID_HEADER, value);
+        }
+        headers
+    }
+
+    /// The session header, once the server assigned a session.
+    pub(crate) fn session_headers(&self) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        if let Some(value) = self.session_id().and_then(|id| id.parse().ok()) {
+            headers.insert(SESSION_ID_HEADER, value);
+        }
+        headers
+    }
+
+    /// Adopt the session ID of a response, if it carries one.
+    pub(crate) fn record_session(&self, headers: &HeaderMap) {
+        if let Some(id) = headers
+            .get(SESSION_ID_HEADER)
+            .and_then(|value| value.to_str().ok())
+        {
+            self.state().session_id = Some(id.to_string());
+        }
+    }
+
+    /// Remember the position and retry delay announced by `event`.
+    pub(crate) fn record_event(&self, event: &SseEvent) {
+        let mut state = self.state();
+        if let Some(id) = &event.id {
+            state.last_event_id = Some(id.clone());
+        }
+        if let Some(retry) = event.retry {
+            state.retry = Some(retry);
+        }
+    }
+
+    /// Forget the session, e.g. after the server expired or closed it.
+    pub(crate) fn reset_session(&self) {
+        *self.state() = SessionState::default();
+    }
+}
+
+/// Whether `headers` describe a `text/event-stream` body.
+pub(crate) fn is_event_stream(headers: &HeaderMap) -> bool {
+    headers
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("text/event-stream"))
+}
+
+/// One event of a `text/event-stream`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SseEvent {
+    /// The `id` field, used to resume the stream
+    pub id: Option<String>,
+    /// The `event` field (the event type)
+    pub event: Option<String>,
+    /// The `data` fields, joined with newlines
+    pub data: String,
+    /// The `retry` field: how long to wait before reconnecting
+    pub retry: Option<Duration>,
+}
+
+/// Assembles events from the lines of an event stream.
+#[derive(Debug, Default)]
+pub(crate) struct SseDecoder {
+    event: SseEvent,
+    has_data: bool,
+}
+
+impl SseDecoder {
+    /// Feed one line (without its terminator); returns an event when the
+    /// line completes one. Events with only an `id` or `retry` are returned
+    /// too, with empty data.
+    pub(crate) fn push_line(&mut self, line: &str) -> Option<SseEvent> {
+        if line.is_empty() {
+            let event = std::mem::take(&mut self.event);
+            let has_data = std::mem::take(&mut self.has_data);
+            return (has_data || event.id.is_some() || event.retry.is_some()).then_some(event);
+        }
+        if line.starts_with(':') {
+            return None;
+        }
+
+        let (field, value) = line.split_once(':').unwrap_or((line, ""));
+        let value = value.strip_prefix(' ').unwrap_or(value);
+        match field {
+            "data" => {
+                if self.has_data {
+                    self.event.data.push('\n');
+                }
+                self.event.data.push_str(value);
+                self.has_data = true;
+            }
+            "event" => self.event.event = Some(value.to_string()),
+            "id" if !value.contains('\0') => self.event.id = Some(value.to_string()),
+            "retry" => {
+                if let Ok(ms) = value.parse() {
+                    self.event.retry = Some(Duration::from_millis(ms));
+                }
+            }
+            _ => {}
+        }
+        None
+    }
+}
+
+/// The events of an event-stream response.
+pub(crate) fn event_stream(
+    response: reqwest::Response,
+    max_line_length: usize,
+) -> impl Stream<Item = Result<SseEvent>> + Send {
+    try_stream! {
+        let byte_stream = response.bytes_stream().map_err(std::io::Error::other);
+        let mut lines = FramedRead::new(
+            StreamReader::new(byte_stream),
+            LinesCodec::new_with_max_length(max_line_length),
+        );
+        let mut decoder = SseDecoder::default();
+        while let Some(line) = futures::StreamExt::next(&mut lines).await {
+            let line = line.map_err(|e| {
+                Error::StreamingError(format!("Failed to read event stream: {e}"))
+            })?;
+            if let Some(event) = decoder.push_line(&line) {
+                yield event;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decoder_assembles_events() {
+        let mut decoder = SseDecoder::default();
+        let mut events = Vec::new();
+        for line in [
+            ": keep-alive",
+            "id: 7",
+            "event: message",
+            "data: {\"a\":",
+            "data:1}",
+            "",
+            "retry: 2500",
+            "",
+            "data",
+            "",
+        ] {
+            events.extend(decoder.push_line(line));
+        }
+
+        assert_eq!(
+            events,
+            [
+                SseEvent {
+                    id: Some("7".into()),
+                    event: Some("message".into()),
+                    data: "{\"a\":\n1}".into(),
+                    retry: None,
+                },
+                SseEvent {
+                    retry: Some(Duration::from_millis(2500)),
+                    ..Default::default()
+                },
+                SseEvent::default(),
+            ]
+        );
+
+        let transport = SseTransport::new();
+        events
+            .iter()
+            .for_each(|event| transport.record_event(event));
+        assert_eq!(transport.last_event_id().as_deref(), Some("7"));
+        assert_eq!(transport.reconnect_delay(), Duration::from_millis(2500));
+        assert_eq!(transport.stream_headers()[LAST_EVENT_ID_HEADER], "7");
+    }
+}
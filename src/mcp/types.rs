@@ -1,10 +1,19 @@
 //! Type definitions for the Model Context Protocol.
+use crate::types::chat::{
+    AudioContent, AudioUrl, ChatRole, ContentPart, ContentType, FileContent, FileUrl, ImageContent,
+    ImageUrl, Message, MessageContent,
+};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, AUTHORIZATION};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::time::Duration;
 
 /// The base protocol version
 pub const MCP_PROTOCOL_VERSION: &str = "2025-03-26";
 
+/// Protocol versions the client accepts from a server, newest first.
+pub const SUPPORTED_PROTOCOL_VERSIONS: &[&str] = &[MCP_PROTOCOL_VERSION, "2024-11-05"];
+
 /// Base JSON-RPC request structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct JsonRpcRequest {
@@ -17,6 +26,15 @@ pub struct JsonRpcRequest {
     pub protocol_version: Option<String>,
 }
 
+/// JSON-RPC notification: a message that expects no response
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JsonRpcNotification {
+    pub jsonrpc: String,
+    pub method: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub params: Option<serde_json::Value>,
+}
+
 /// Base JSON-RPC response structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct JsonRpcResponse {
@@ -37,6 +55,71 @@ pub struct JsonRpcError {
     pub data: Option<serde_json::Value>,
 }
 
+/// Meaning of a JSON-RPC error code
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum McpErrorCode {
+    /// -32700: the server could not parse the request
+    ParseError,
+    /// -32600: the request is not a valid JSON-RPC request
+    InvalidRequest,
+    /// -32601: the method does not exist or is not available
+    MethodNotFound,
+    /// -32602: the method's parameters are invalid
+    InvalidParams,
+    /// -32603: the server failed internally
+    Internal,
+    /// Any other code, defined by the server or by MCP (e.g. -1 for a
+    /// request the user rejected)
+    ServerDefined(i64),
+}
+
+impl McpErrorCode {
+    /// The numeric JSON-RPC code.
+    pub fn code(self) -> i64 {
+        match self {
+            McpErrorCode::ParseError => -32700,
+            McpErrorCode::InvalidRequest => -32600,
+            McpErrorCode::MethodNotFound => -32601,
+            McpErrorCode::InvalidParams => -32602,
+            McpErrorCode::Internal => -32603,
+            McpErrorCode::ServerDefined(code) => code,
+        }
+    }
+}
+
+impl From<i64> for McpErrorCode {
+    fn from(code: i64) -> Self {
+        match code {
+            -32700 => McpErrorCode::ParseError,
+            -32600 => McpErrorCode::InvalidRequest,
+            -32601 => McpErrorCode::MethodNotFound,
+            -32602 => McpErrorCode::InvalidParams,
+            -32603 => McpErrorCode::Internal,
+            other => McpErrorCode::ServerDefined(other),
+        }
+    }
+}
+
+impl std::fmt::Display for McpErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            McpErrorCode::ParseError => write!(f, "parse error"),
+            McpErrorCode::InvalidRequest => write!(f, "invalid request"),
+            McpErrorCode::MethodNotFound => write!(f, "method not found"),
+            McpErrorCode::InvalidParams => write!(f, "invalid params"),
+            McpErrorCode::Internal => write!(f, "internal error"),
+            McpErrorCode::ServerDefined(code) => write!(f, "code {code}"),
+        }
+    }
+}
+
+impl JsonRpcError {
+    /// The meaning of `code`.
+    pub fn kind(&self) -> McpErrorCode {
+        McpErrorCode::from(i64::from(self.code))
+    }
+}
+
 /// Initialize parameters sent by the client
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InitializeParams {
@@ -48,10 +131,46 @@ pub struct InitializeParams {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ServerCapabilities {
     /// Protocol version supported by the server
+    #[serde(alias = "protocolVersion")]
     pub protocol_version: String,
     /// Whether server supports sampling
     #[serde(skip_serializing_if = "Option::is_none")]
     pub supports_sampling: Option<bool>,
+    /// Features the server advertised; `None` if it advertised none
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub capabilities: Option<ServerFeatures>,
+}
+
+/// Features advertised by a server. Each is present, with its options, when
+/// supported.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ServerFeatures {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prompts: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resources: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tools: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub logging: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub completions: Option<serde_json::Value>,
+}
+
+impl ServerFeatures {
+    /// Whether the feature named `capability` (e.g. `"prompts"`) was
+    /// advertised.
+    pub fn supports(&self, capability: &str) -> bool {
+        let feature = match capability {
+            "prompts" => &self.prompts,
+            "resources" => &self.resources,
+            "tools" => &self.tools,
+            "logging" => &self.logging,
+            "completions" => &self.completions,
+            _ => return false,
+        };
+        feature.is_some()
+    }
 }
 
 /// Client capabilities
@@ -139,6 +258,277 @@ pub struct ExecutePromptResponse {
     pub result: serde_json::Value,
 }
 
+/// `prompts/list` parameters
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ListPromptsParams {
+    /// Cursor from a previous page's `next_cursor`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+}
+
+/// `prompts/list` response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListPromptsResponse {
+    /// Prompt templates offered by the server
+    pub prompts: Vec<Prompt>,
+    /// Cursor for the next page, if there is one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+/// A prompt template offered by the server
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Prompt {
+    /// Name passed to `prompts/get`
+    pub name: String,
+    /// What the prompt is for
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Arguments substituted into the template
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub arguments: Vec<PromptArgument>,
+}
+
+impl Prompt {
+    /// Names of the required arguments missing from `arguments`.
+    pub fn missing_arguments(&self, arguments: &HashMap<String, String>) -> Vec<&str> {
+        self.arguments
+            .iter()
+            .filter(|argument| argument.required && !arguments.contains_key(&argument.name))
+            .map(|argument| argument.name.as_str())
+            .collect()
+    }
+}
+
+/// An argument of a prompt template
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PromptArgument {
+    /// Argument name
+    pub name: String,
+    /// What the argument is for
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Whether the argument must be provided
+    #[serde(default)]
+    pub required: bool,
+}
+
+/// `prompts/get` parameters
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetPromptParams {
+    /// Name of the prompt template
+    pub name: String,
+    /// Values the server substitutes for the template's arguments
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub arguments: HashMap<String, String>,
+}
+
+impl GetPromptParams {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            arguments: HashMap::new(),
+        }
+    }
+
+    /// Sets the value of an argument.
+    pub fn with_argument(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.arguments.insert(name.into(), value.into());
+        self
+    }
+}
+
+/// `prompts/get` response: the template with its arguments substituted
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetPromptResponse {
+    /// What the prompt is for
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// The prompt's messages
+    pub messages: Vec<PromptMessage>,
+}
+
+impl GetPromptResponse {
+    /// Converts the prompt into chat messages, ready for a
+    /// `ChatCompletionRequest`.
+    pub fn into_messages(self) -> Vec<Message> {
+        self.messages.into_iter().map(Message::from).collect()
+    }
+}
+
+/// Role of a prompt message
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PromptRole {
+    User,
+    Assistant,
+}
+
+/// A message of a prompt template
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PromptMessage {
+    pub role: PromptRole,
+    pub content: PromptContent,
+}
+
+/// Content of a prompt message
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum PromptContent {
+    Text {
+        text: String,
+    },
+    /// Base64 encoded image
+    Image {
+        data: String,
+        #[serde(rename = "mimeType")]
+        mime_type: String,
+    },
+    /// Base64 encoded audio
+    Audio {
+        data: String,
+        #[serde(rename = "mimeType")]
+        mime_type: String,
+    },
+    /// A server resource embedded in the prompt
+    Resource {
+        resource: EmbeddedResource,
+    },
+}
+
+/// A resource embedded in a prompt message
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmbeddedResource {
+    /// URI of the resource
+    pub uri: String,
+    /// MIME type of the content
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
+    /// Text content
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    /// Base64 encoded binary content
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blob: Option<String>,
+}
+
+fn data_url(mime_type: &str, data: &str) -> String {
+    format!("data:{mime_type};base64,{data}")
+}
+
+impl From<PromptMessage> for Message {
+    /// Text and text resources become plain text; images, audio and binary
+    /// resources become a content part with a base64 data URL.
+    fn from(message: PromptMessage) -> Self {
+        let role = match message.role {
+            PromptRole::User => ChatRole::User,
+            PromptRole::Assistant => ChatRole::Assistant,
+        };
+        let part = match message.content {
+            PromptContent::Text { text } => return Message::text(role, text),
+            PromptContent::Image { data, mime_type } => ContentPart::Image(ImageContent {
+                content_type: ContentType::ImageUrl,
+                image_url: ImageUrl {
+                    url: data_url(&mime_type, &data),
+                    detail: None,
+                },
+            }),
+            PromptContent::Audio { data, mime_type } => ContentPart::Audio(AudioContent {
+                content_type: ContentType::AudioUrl,
+                audio_url: AudioUrl {
+                    url: data_url(&mime_type, &data),
+                },
+            }),
+            PromptContent::Resource { resource } => match resource.blob {
+                Some(blob) if resource.text.is_none() => ContentPart::File(FileContent {
+                    content_type: ContentType::FileUrl,
+                    file_url: FileUrl {
+                        url: data_url(
+                            resource
+                                .mime_type
+                                .as_deref()
+                                .unwrap_or("application/octet-stream"),
+                            &blob,
+                        ),
+                    },
+                }),
+                _ => return Message::text(role, resource.text.unwrap_or_default()),
+            },
+        };
+        Message {
+            role,
+            content: MessageContent::Parts(vec![part]),
+            ..Default::default()
+        }
+    }
+}
+
+/// `sampling/createMessage` parameters: a server asking the client for a
+/// completion
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateMessageParams {
+    /// The conversation to complete
+    pub messages: Vec<PromptMessage>,
+    /// The server's model preferences; the client makes the final choice
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model_preferences: Option<ModelPreferences>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub system_prompt: Option<String>,
+    /// Context the server would like included (`none`, `thisServer` or
+    /// `allServers`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub include_context: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f64>,
+    pub max_tokens: u32,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub stop_sequences: Vec<String>,
+    /// Provider-specific metadata
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<serde_json::Value>,
+}
+
+/// A server's model preferences for sampling
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelPreferences {
+    /// Model names or name fragments, most preferred first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hints: Vec<ModelHint>,
+    /// How much cost matters, from 0 to 1
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cost_priority: Option<f64>,
+    /// How much latency matters, from 0 to 1
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub speed_priority: Option<f64>,
+    /// How much capability matters, from 0 to 1
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub intelligence_priority: Option<f64>,
+}
+
+/// A model name or name fragment, e.g. `claude-3-5-sonnet` or `sonnet`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModelHint {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+/// `sampling/createMessage` result
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateMessageResult {
+    pub role: PromptRole,
+    pub content: PromptContent,
+    /// The model that generated the message
+    pub model: String,
+    /// `endTurn`, `stopSequence`, `maxTokens` or a provider-specific reason
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stop_reason: Option<String>,
+}
+
 /// Sampling request parameters
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SamplingParams {
@@ -186,8 +576,82 @@ pub struct SamplingMessage {
     pub name: Option<String>,
 }
 
+/// Authentication sent with every request to an MCP server
+#[derive(Clone, Default, PartialEq, Eq)]
+pub enum McpAuth {
+    /// No authentication
+    #[default]
+    None,
+    /// `Authorization: Bearer <token>`
+    Bearer(String),
+    /// An API key in a custom header, e.g. `X-API-Key`
+    ApiKey { header: String, key: String },
+    /// Arbitrary headers, for servers needing several credentials
+    Headers(Vec<(String, String)>),
+}
+
+impl McpAuth {
+    pub fn bearer(token: impl Into<String>) -> Self {
+        McpAuth::Bearer(token.into())
+    }
+
+    pub fn api_key(header: impl Into<String>, key: impl Into<String>) -> Self {
+        McpAuth::ApiKey {
+            header: header.into(),
+            key: key.into(),
+        }
+    }
+
+    /// The headers to send, marked sensitive so they are never logged.
+    pub(crate) fn to_headers(&self) -> crate::error::Result<HeaderMap> {
+        let pairs: Vec<(&str, String)> = match self {
+            McpAuth::None => Vec::new(),
+            McpAuth::Bearer(token) => vec![(AUTHORIZATION.as_str(), format!("Bearer {token}"))],
+            McpAuth::ApiKey { header, key } => vec![(header.as_str(), key.clone())],
+            McpAuth::Headers(headers) => headers
+                .iter()
+                .map(|(name, value)| (name.as_str(), value.clone()))
+                .collect(),
+        };
+        let mut headers = HeaderMap::new();
+        for (name, value) in pairs {
+            let name = HeaderName::from_bytes(name.as_bytes()).map_err(|_| {
+                crate::error::Error::ConfigError(format!("Invalid MCP auth header name: {name}"))
+            })?;
+            let mut value = HeaderValue::from_str(&value).map_err(|_| {
+                crate::error::Error::ConfigError(format!(
+                    "Invalid value for MCP auth header {name}"
+                ))
+            })?;
+            value.set_sensitive(true);
+            headers.append(name, value);
+        }
+        Ok(headers)
+    }
+}
+
+impl std::fmt::Debug for McpAuth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Never expose credentials in debug output
+        match self {
+            McpAuth::None => f.write_str("None"),
+            McpAuth::Bearer(_) => f.debug_tuple("Bearer").field(&"[REDACTED]").finish(),
+            McpAuth::ApiKey { header, .. } => f
+                .debug_struct("ApiKey")
+                .field("header", header)
+                .field("key", &"[REDACTED]")
+                .finish(),
+            McpAuth::Headers(headers) => f
+                .debug_tuple("Headers")
+                .field(&headers.iter().map(|(name, _)| name).collect::<Vec<_>>())
+                .finish(),
+        }
+    }
+}
+
 /// Configuration for MCP client security and performance limits
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct McpConfig {
     /// Timeout for individual requests
     pub request_timeout: Duration,
@@ -197,6 +661,11 @@ pub struct McpConfig {
     pub max_request_size: usize,
     /// Maximum concurrent requests
     pub max_concurrent_requests: usize,
+    /// Authentication sent with every request
+    pub auth: McpAuth,
+    /// Retries for failed requests; `None` disables them. Status codes and
+    /// policy apply as for OpenRouter requests.
+    pub retry: Option<crate::client::RetryConfig>,
 }
 
 impl Default for McpConfig {
@@ -206,6 +675,22 @@ impl Default for McpConfig {
             max_response_size: 10 * 1024 * 1024, // 10MB
             max_request_size: 1024 * 1024,       // 1MB
             max_concurrent_requests: 10,
+            auth: McpAuth::None,
+            retry: None,
         }
     }
 }
+
+impl McpConfig {
+    /// Sends `auth` with every request.
+    pub fn with_auth(mut self, auth: McpAuth) -> Self {
+        self.auth = auth;
+        self
+    }
+
+    /// Retries failed requests according to `retry`.
+    pub fn with_retry(mut self, retry: crate::client::RetryConfig) -> Self {
+        self.retry = Some(retry);
+        self
+    }
+}
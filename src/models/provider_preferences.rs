@@ -5,10 +5,13 @@
 //! parameter requirements, data collection settings, quantizations and sorting.
 
 use crate::error::Error;
+use crate::types::providers::ProvidersResponse;
 use serde::{Deserialize, Serialize};
+use std::fmt;
 
 /// Defines the data collection policy when selecting providers.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 #[non_exhaustive]
 pub enum DataCollection {
@@ -18,6 +21,7 @@ pub enum DataCollection {
 
 /// Defines provider sort preferences.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 #[non_exhaustive]
 pub enum ProviderSort {
@@ -27,6 +31,7 @@ pub enum ProviderSort {
 
 /// Defines quantization filtering options.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 #[non_exhaustive]
 pub enum Quantization {
@@ -40,9 +45,45 @@ pub enum Quantization {
     Unknown,
 }
 
+/// A provider slug in `ProviderPreferences` that matches no known provider.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownProviderSlug {
+    /// The preference list containing the slug: `"order"`, `"allow"` or
+    /// `"ignore"`.
+    pub field: &'static str,
+    pub slug: String,
+    /// The closest known slug, if any is near enough to be a likely typo.
+    pub suggestion: Option<String>,
+}
+
+impl fmt::Display for UnknownProviderSlug {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "'{}' in {}", self.slug, self.field)?;
+        if let Some(suggestion) = &self.suggestion {
+            write!(f, " (did you mean '{suggestion}'?)")?;
+        }
+        Ok(())
+    }
+}
+
+/// Formats unknown slugs for `Error::UnknownProviderSlugs`.
+pub(crate) fn describe_unknown_slugs(unknown: &[UnknownProviderSlug]) -> String {
+    unknown
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 /// Strongly‑typed provider preferences.
+///
+/// Serializes with the snake_case keys OpenRouter documents
+/// (`allow_fallbacks`, `require_parameters`, `data_collection`); the API
+/// silently ignores the camelCase spellings. The camelCase keys earlier
+/// versions of this crate wrote are still accepted when deserializing.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
 pub struct ProviderPreferences {
     /// Ordered list of provider names to prefer.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -52,13 +93,13 @@ pub struct ProviderPreferences {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub allow: Option<Vec<String>>,
     /// Whether fallback providers are allowed.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(alias = "allowFallbacks", skip_serializing_if = "Option::is_none")]
     pub allow_fallbacks: Option<bool>,
     /// Whether to require providers to support all parameters.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(alias = "requireParameters", skip_serializing_if = "Option::is_none")]
     pub require_parameters: Option<bool>,
     /// Controls data collection for providers ("allow" or "deny").
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(alias = "dataCollection", skip_serializing_if = "Option::is_none")]
     pub data_collection: Option<DataCollection>,
     /// List of provider names to ignore.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -122,6 +163,45 @@ impl ProviderPreferences {
         Ok(())
     }
 
+    /// Checks the slugs in `order`, `allow` and `ignore` against the
+    /// providers OpenRouter lists.
+    ///
+    /// Unknown slugs are otherwise ignored by the API, so a typo silently
+    /// disables the preference. A slug with a variant suffix, such as
+    /// `deepinfra/turbo`, is accepted when its provider is known. Returns
+    /// `Error::UnknownProviderSlugs` listing every unknown slug, each with
+    /// the nearest known slug as a suggestion when one is close.
+    ///
+    /// `ProvidersApi::validate_preferences` runs this against the cached
+    /// providers list.
+    pub fn validate_slugs(&self, providers: &ProvidersResponse) -> Result<(), Error> {
+        let known: Vec<&str> = providers.data.iter().map(|p| p.slug.as_str()).collect();
+        let mut unknown = Vec::new();
+        for (field, slugs) in [
+            ("order", &self.order),
+            ("allow", &self.allow),
+            ("ignore", &self.ignore),
+        ] {
+            for slug in slugs.iter().flatten() {
+                let base = slug.split('/').next().unwrap_or(slug);
+                if known.contains(&base) {
+                    continue;
+                }
+                unknown.push(UnknownProviderSlug {
+                    field,
+                    slug: slug.clone(),
+                    suggestion: nearest_slug(base, &known).map(str::to_string),
+                });
+            }
+        }
+
+        if unknown.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::UnknownProviderSlugs(unknown))
+        }
+    }
+
     /// Sets the order of providers.
     pub fn with_order(mut self, order: Vec<String>) -> Self {
         self.order = Some(order);
@@ -170,3 +250,101 @@ impl ProviderPreferences {
         self
     }
 }
+
+/// Finds the known slug closest to `slug` by edit distance, ignoring case,
+/// if it is within a third of the slug's length (at least 2 edits).
+fn nearest_slug<'a>(slug: &str, known: &[&'a str]) -> Option<&'a str> {
+    let slug = slug.to_lowercase();
+    let max_distance = (slug.chars().count() / 3).max(2);
+    known
+        .iter()
+        .map(|candidate| (edit_distance(&slug, &candidate.to_lowercase()), *candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min()
+        .map(|(_, candidate)| candidate)
+}
+
+/// Levenshtein distance between `a` and `b`, in characters.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != *b_char);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::providers::Provider;
+
+    fn providers() -> ProvidersResponse {
+        ProvidersResponse::new(
+            ["openai", "anthropic", "together", "deepinfra"]
+                .into_iter()
+                .map(|slug| Provider::new(slug.to_string(), slug.to_string(), None, None, None))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_validate_slugs_accepts_known_providers() {
+        let preferences = ProviderPreferences::new()
+            .with_order(vec!["openai".into(), "deepinfra/turbo".into()])
+            .with_ignore(vec!["together".into()]);
+        assert!(preferences.validate_slugs(&providers()).is_ok());
+        assert!(ProviderPreferences::new()
+            .validate_slugs(&providers())
+            .is_ok());
+    }
+
+    #[test]
+    fn test_validate_slugs_reports_unknown_with_suggestions() {
+        let preferences = ProviderPreferences::new()
+            .with_order(vec!["opnai".into(), "anthropic".into()])
+            .with_allow(vec!["OpenAI".into()])
+            .with_ignore(vec!["nonexistent-provider".into()]);
+
+        let Err(Error::UnknownProviderSlugs(unknown)) = preferences.validate_slugs(&providers())
+        else {
+            panic!("expected unknown slugs");
+        };
+        assert_eq!(
+            unknown,
+            [
+                UnknownProviderSlug {
+                    field: "order",
+                    slug: "opnai".into(),
+                    suggestion: Some("openai".into()),
+                },
+                UnknownProviderSlug {
+                    field: "allow",
+                    slug: "OpenAI".into(),
+                    suggestion: Some("openai".into()),
+                },
+                UnknownProviderSlug {
+                    field: "ignore",
+                    slug: "nonexistent-provider".into(),
+                    suggestion: None,
+                },
+            ]
+        );
+        assert_eq!(
+            Error::UnknownProviderSlugs(unknown[..1].to_vec()).to_string(),
+            "Unknown provider slugs: 'opnai' in order (did you mean 'openai'?)"
+        );
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("openai", "openai"), 0);
+    }
+}
@@ -4,6 +4,7 @@ use serde_json::{Map, Value};
 /// A strongly‑typed representation of a JSON Schema definition.
 /// This captures common validation properties.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct JsonSchemaDefinition {
     /// JSON Schema type (typically "object").
@@ -21,6 +22,7 @@ pub struct JsonSchemaDefinition {
 
 /// JSON Schema configuration for requesting structured outputs.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct JsonSchemaConfig {
     /// Name for schema, used to identify output type.
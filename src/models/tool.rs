@@ -9,7 +9,7 @@
    - **Tool:** An enum representing available types of tools. Currently, only function‑type tools are supported.
    - **FunctionCall:** Represents the details of a requested tool call including the function name and JSON‑encoded arguments.
    - **ToolCall:** Captures the tool call details as returned by the API, including a unique identifier and the associated function call details.
-   - **ToolChoice:** Represents the possible outcomes when the model must select a tool (for example, "none", "auto", or a specific function choice).
+   - **ToolChoice:** Controls tool use: "auto", "none", "required", or forcing a specific function.
    - **FunctionName:** A simple structure to represent a function name for tool selection.
 */
 
@@ -20,6 +20,7 @@ use crate::types::ids::ToolCallId;
 
 /// Represents a description for a callable function (tool).
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 pub struct FunctionDescription {
     /// The name of the function.
     pub name: String,
@@ -37,6 +38,7 @@ pub struct FunctionDescription {
 /// Currently, only function‑type tools are supported.
 /// In the future, this enum could be extended for other tool types.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 #[serde(tag = "type", rename_all = "lowercase")]
 pub enum Tool {
     /// A function call tool with an associated [FunctionDescription].
@@ -51,6 +53,7 @@ pub enum Tool {
 /// Currently only function-type tools are supported. This enum makes invalid tool types
 /// unrepresentable at compile time, preventing errors from typos or unknown values.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 pub enum ToolType {
     /// A function call tool.
@@ -69,6 +72,7 @@ impl std::fmt::Display for ToolType {
 ///
 /// The `arguments` field is a JSON‑encoded string that should be parseable into a structured object.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 pub struct FunctionCall {
     /// The name of function to call.
     pub name: String,
@@ -81,6 +85,7 @@ pub struct FunctionCall {
 /// This structure appears in responses when the model indicates that a tool should be invoked.
 /// The `kind` field is a type-safe enum that only allows valid tool types.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 pub struct ToolCall {
     /// A unique identifier for the tool call.
     pub id: ToolCallId,
@@ -94,6 +99,7 @@ pub struct ToolCall {
 
 /// Represents a chunk of a function call as streamed from the API.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 pub struct FunctionCallChunk {
     /// The name of the function to call. Appears in the first chunk for a given function call.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -106,6 +112,7 @@ pub struct FunctionCallChunk {
 /// Represents a chunk of a tool call as streamed from the API.
 /// Fields are optional to accommodate partial data.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 pub struct ToolCallChunk {
     /// The index of the tool call in the list of tool calls.
     pub index: u32,
@@ -121,29 +128,83 @@ pub struct ToolCallChunk {
     pub function: Option<FunctionCallChunk>,
 }
 
-/// Represents a tool selection option when model must choose among available tools.
+/// Controls whether and which tool the model calls.
 ///
-/// This enum covers three cases:
-/// - **None:** No tool is selected (represented by a string, e.g. "none").
-/// - **Auto:** The model automatically selects a tool (represented as "auto").
-/// - **FunctionChoice:** A specific function is selected. The `kind` field uses the type-safe `ToolType` enum.
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(untagged)]
+/// Serializes as `"auto"`, `"none"`, `"required"` or, to force a specific
+/// function, `{"type": "function", "function": {"name": ...}}`. Request
+/// validation rejects a [`Function`](Self::Function) choice that names no
+/// function in the request's tools.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+#[serde(from = "ToolChoiceRepr", into = "ToolChoiceRepr")]
 pub enum ToolChoice {
-    /// No tool is selected.
-    None(String),
-    /// The model automatically selects a tool.
-    Auto(String),
-    /// A specific function is selected.
-    FunctionChoice {
+    /// The model decides whether to call tools.
+    Auto,
+    /// The model must not call tools.
+    None,
+    /// The model must call at least one tool.
+    Required,
+    /// The model must call the named function.
+    Function(String),
+}
+
+impl ToolChoice {
+    /// Forces a call to the function `name`.
+    pub fn function(name: impl Into<String>) -> Self {
+        Self::Function(name.into())
+    }
+}
+
+/// Wire format of [`ToolChoice`].
+#[derive(Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+#[serde(untagged)]
+enum ToolChoiceRepr {
+    Mode(ToolChoiceMode),
+    Function {
         #[serde(rename = "type")]
         kind: ToolType,
         function: FunctionName,
     },
 }
 
+#[derive(Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+enum ToolChoiceMode {
+    Auto,
+    None,
+    Required,
+}
+
+impl From<ToolChoiceRepr> for ToolChoice {
+    fn from(repr: ToolChoiceRepr) -> Self {
+        match repr {
+            ToolChoiceRepr::Mode(ToolChoiceMode::Auto) => Self::Auto,
+            ToolChoiceRepr::Mode(ToolChoiceMode::None) => Self::None,
+            ToolChoiceRepr::Mode(ToolChoiceMode::Required) => Self::Required,
+            ToolChoiceRepr::Function { function, .. } => Self::Function(function.name),
+        }
+    }
+}
+
+impl From<ToolChoice> for ToolChoiceRepr {
+    fn from(choice: ToolChoice) -> Self {
+        match choice {
+            ToolChoice::Auto => Self::Mode(ToolChoiceMode::Auto),
+            ToolChoice::None => Self::Mode(ToolChoiceMode::None),
+            ToolChoice::Required => Self::Mode(ToolChoiceMode::Required),
+            ToolChoice::Function(name) => Self::Function {
+                kind: ToolType::Function,
+                function: FunctionName { name },
+            },
+        }
+    }
+}
+
 /// A simple struct to represent a function name for tool selection.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 pub struct FunctionName {
     /// The name of the function.
     pub name: String,
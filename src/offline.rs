@@ -0,0 +1,87 @@
+//! Bundled model and provider catalog (`offline` feature).
+//!
+//! When the server can't be reached, `ModelsApi::list_models` and
+//! `ProvidersApi::get_providers` (and the helpers built on it, such as
+//! `validate_preferences`) answer from this snapshot instead of failing.
+//! Responses built from it have `stale` set. The snapshot lists a selection
+//! of popular models and providers as of [`SNAPSHOT_DATE`]; prices and
+//! limits may have changed since.
+
+use crate::error::Error;
+use crate::types::models::ModelsResponse;
+use crate::types::providers::ProvidersResponse;
+
+/// When the bundled catalog was taken.
+pub const SNAPSHOT_DATE: &str = "2025-06-01";
+
+const MODELS: &str = include_str!("offline/models.json");
+const PROVIDERS: &str = include_str!("offline/providers.json");
+
+/// The bundled models list, flagged as stale.
+///
+/// Request filters are not applied to it.
+pub fn models() -> ModelsResponse {
+    let mut models: ModelsResponse =
+        serde_json::from_str(MODELS).expect("bundled models snapshot is valid");
+    models.stale = true;
+    models
+}
+
+/// The bundled providers list, flagged as stale.
+pub fn providers() -> ProvidersResponse {
+    let mut providers: ProvidersResponse =
+        serde_json::from_str(PROVIDERS).expect("bundled providers snapshot is valid");
+    providers.stale = true;
+    providers
+}
+
+/// Whether `error` means the request never got a response.
+pub(crate) fn is_unreachable(error: &Error) -> bool {
+    matches!(error, Error::HttpError(_))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshot_parses() {
+        let models = models();
+        assert!(models.stale);
+        assert!(models
+            .data
+            .iter()
+            .any(|model| model.id.as_str() == "openai/gpt-4o"));
+
+        let providers = providers();
+        assert!(providers.stale);
+        assert!(providers.find_by_slug("anthropic").is_some());
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_api_falls_back_to_snapshot() {
+        use crate::api::models::ModelsApi;
+        use crate::api::providers::ProvidersApi;
+
+        let mut config = crate::tests::test_helpers::test_client_config();
+        config.base_url = "http://127.0.0.1:1/".parse().unwrap();
+        config.retry_config.max_retries = 0;
+
+        let models = ModelsApi::new(reqwest::Client::new(), &config)
+            .unwrap()
+            .list_models(None)
+            .await
+            .unwrap();
+        assert!(models.stale);
+
+        let providers = ProvidersApi::new(reqwest::Client::new(), &config).unwrap();
+        assert!(providers.get_providers().await.unwrap().stale);
+        assert!(providers
+            .validate_preferences(
+                &crate::models::provider_preferences::ProviderPreferences::new()
+                    .with_order(vec!["openai".into()])
+            )
+            .await
+            .is_ok());
+    }
+}
@@ -0,0 +1,377 @@
+//! Post-processing of model output text.
+//!
+//! Each [`Processor`] maps output text to new text, so they compose into a
+//! [`Pipeline`]:
+//!
+//! - [`strip_thinking_tags`] removes `<think>…</think>` blocks some models
+//!   inline into their content;
+//! - [`trim_stop_sequences`] cuts the text at a stop sequence the provider
+//!   left in place, such as `<|im_end|>`;
+//! - [`extract_code_blocks`] keeps only the contents of Markdown code blocks;
+//! - [`extract_first_json`] keeps only the first complete JSON value.
+//!
+//! A pipeline applies to plain text, a [`ChatCompletionResponse`], or a
+//! stream assembled with `ChatCompletionStream::collect_full`.
+//!
+//! ```
+//! use openrouter_api::postprocess::{self, Pipeline};
+//!
+//! let pipeline = Pipeline::new()
+//!     .then(postprocess::strip_thinking_tags())
+//!     .then(postprocess::extract_code_blocks(Some("json")))
+//!     .then(postprocess::extract_first_json());
+//!
+//! let output = "<think>The user wants JSON.</think>Here you go:\n```json\n{\"ok\": true}\n```";
+//! assert_eq!(pipeline.apply(output)?, r#"{"ok": true}"#);
+//! # Ok::<(), openrouter_api::Error>(())
+//! ```
+
+use crate::error::{Error, Result};
+use crate::types::chat::ChatCompletionResponse;
+use serde_json::Value;
+use std::fmt;
+use std::sync::Arc;
+
+/// End-of-turn tokens that some providers leave at the end of the output.
+pub const DEFAULT_STOP_SEQUENCES: [&str; 6] = [
+    "<|im_end|>",
+    "<|eot_id|>",
+    "<|endoftext|>",
+    "<|end|>",
+    "</s>",
+    "<end_of_turn>",
+];
+
+/// Transforms model output text.
+///
+/// Implemented for closures taking `&str` and returning `Result<String>`.
+pub trait Processor: Send + Sync {
+    fn process(&self, text: &str) -> Result<String>;
+}
+
+impl<F> Processor for F
+where
+    F: Fn(&str) -> Result<String> + Send + Sync,
+{
+    fn process(&self, text: &str) -> Result<String> {
+        self(text)
+    }
+}
+
+/// Processors applied in order, each to the previous one's output.
+#[derive(Clone, Default)]
+pub struct Pipeline {
+    processors: Vec<Arc<dyn Processor>>,
+}
+
+impl fmt::Debug for Pipeline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Pipeline")
+            .field("processors", &self.processors.len())
+            .finish()
+    }
+}
+
+impl Pipeline {
+    /// Creates an empty pipeline, which returns text unchanged.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a processor.
+    pub fn then(mut self, processor: impl Processor + 'static) -> Self {
+        self.processors.push(Arc::new(processor));
+        self
+    }
+
+    /// Runs the processors over `text`, stopping at the first error.
+    pub fn apply(&self, text: &str) -> Result<String> {
+        let mut output = text.to_string();
+        for processor in &self.processors {
+            output = processor.process(&output)?;
+        }
+        Ok(output)
+    }
+
+    /// Runs the processors over the text of the response's first choice.
+    ///
+    /// Returns `Error::SchemaValidationError` if the response has no text
+    /// content.
+    pub fn apply_response(&self, response: &ChatCompletionResponse) -> Result<String> {
+        let text = response.text().ok_or_else(|| {
+            Error::SchemaValidationError("Response has no text content".to_string())
+        })?;
+        self.apply(text)
+    }
+}
+
+/// A fenced Markdown code block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodeBlock {
+    /// The first word of the info string, e.g. `rust` for ` ```rust `.
+    pub lang: Option<String>,
+    pub code: String,
+}
+
+/// Finds the fenced code blocks in `text`, in order.
+///
+/// A block left open at the end of the text, as in a stream cut short,
+/// runs to the end.
+pub fn code_blocks(text: &str) -> Vec<CodeBlock> {
+    let mut blocks = Vec::new();
+    // The open fence's character and length, the language and the lines so far.
+    let mut open: Option<(char, usize, Option<String>, Vec<&str>)> = None;
+
+    for line in text.lines() {
+        let trimmed = line.trim_start();
+        let fence = trimmed
+            .chars()
+            .next()
+            .filter(|c| *c == '`' || *c == '~')
+            .map(|c| (c, trimmed.chars().take_while(|t| *t == c).count()))
+            .filter(|(_, len)| *len >= 3);
+
+        match (&mut open, fence) {
+            (Some((c, len, _, _)), Some((fence_char, fence_len)))
+                if fence_char == *c
+                    && fence_len >= *len
+                    && trimmed[fence_len..].trim().is_empty() =>
+            {
+                let (_, _, lang, lines) = open.take().expect("block is open");
+                blocks.push(CodeBlock {
+                    lang,
+                    code: lines.join("\n"),
+                });
+            }
+            (Some((_, _, _, lines)), _) => lines.push(line),
+            (None, Some((fence_char, fence_len))) => {
+                let lang = trimmed[fence_len..]
+                    .split_whitespace()
+                    .next()
+                    .map(str::to_string);
+                open = Some((fence_char, fence_len, lang, Vec::new()));
+            }
+            (None, None) => {}
+        }
+    }
+
+    if let Some((_, _, lang, lines)) = open {
+        blocks.push(CodeBlock {
+            lang,
+            code: lines.join("\n"),
+        });
+    }
+    blocks
+}
+
+/// Finds the first complete JSON object or array in `text` and returns it
+/// with the byte range it occupies.
+pub fn first_json(text: &str) -> Option<(Value, std::ops::Range<usize>)> {
+    text.char_indices()
+        .filter(|(_, c)| *c == '{' || *c == '[')
+        .find_map(|(start, _)| {
+            let mut values =
+                serde_json::Deserializer::from_str(&text[start..]).into_iter::<Value>();
+            let value = values.next()?.ok()?;
+            Some((value, start..start + values.byte_offset()))
+        })
+}
+
+/// Keeps only the first complete JSON object or array, as written.
+///
+/// Fails with `Error::SchemaValidationError` if the text contains none.
+pub fn extract_first_json() -> impl Processor {
+    |text: &str| {
+        first_json(text)
+            .map(|(_, range)| text[range].to_string())
+            .ok_or_else(|| Error::SchemaValidationError("No JSON value found in output".into()))
+    }
+}
+
+/// Keeps only the contents of fenced code blocks, joined by newlines.
+///
+/// With `lang` set, only blocks in that language (compared ignoring case)
+/// are kept. Fails with `Error::SchemaValidationError` if no block matches.
+pub fn extract_code_blocks(lang: Option<&str>) -> impl Processor {
+    let lang = lang.map(str::to_string);
+    move |text: &str| {
+        let blocks: Vec<String> = code_blocks(text)
+            .into_iter()
+            .filter(|block| match &lang {
+                Some(lang) => block
+                    .lang
+                    .as_ref()
+                    .is_some_and(|l| l.eq_ignore_ascii_case(lang)),
+                None => true,
+            })
+            .map(|block| block.code)
+            .collect();
+        if blocks.is_empty() {
+            return Err(Error::SchemaValidationError(match &lang {
+                Some(lang) => format!("No {lang} code block found in output"),
+                None => "No code block found in output".to_string(),
+            }));
+        }
+        Ok(blocks.join("\n"))
+    }
+}
+
+/// Removes `<think>…</think>` and `<thinking>…</thinking>` blocks.
+///
+/// An unclosed tag, as in a stream cut short while the model was still
+/// thinking, removes everything after it.
+pub fn strip_thinking_tags() -> impl Processor {
+    |text: &str| {
+        let mut output = String::with_capacity(text.len());
+        let mut rest = text;
+        while let Some((start, open, close)) = ["<think>", "<thinking>"]
+            .into_iter()
+            .zip(["</think>", "</thinking>"])
+            .filter_map(|(open, close)| rest.find(open).map(|start| (start, open, close)))
+            .min_by_key(|(start, _, _)| *start)
+        {
+            output.push_str(&rest[..start]);
+            let after = &rest[start + open.len()..];
+            rest = match after.find(close) {
+                Some(end) => &after[end + close.len()..],
+                None => "",
+            };
+        }
+        output.push_str(rest);
+        Ok(output.trim().to_string())
+    }
+}
+
+/// Cuts the text at the first of [`DEFAULT_STOP_SEQUENCES`] and trims
+/// trailing whitespace; see [`TrimStopSequences::with_sequence`] to add the
+/// request's own stop sequences.
+pub fn trim_stop_sequences() -> TrimStopSequences {
+    TrimStopSequences {
+        sequences: DEFAULT_STOP_SEQUENCES.map(str::to_string).to_vec(),
+    }
+}
+
+/// Processor returned by [`trim_stop_sequences`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrimStopSequences {
+    sequences: Vec<String>,
+}
+
+impl TrimStopSequences {
+    /// Also cuts at `sequence`.
+    pub fn with_sequence(mut self, sequence: impl Into<String>) -> Self {
+        let sequence = sequence.into();
+        if !sequence.is_empty() {
+            self.sequences.push(sequence);
+        }
+        self
+    }
+}
+
+impl Processor for TrimStopSequences {
+    fn process(&self, text: &str) -> Result<String> {
+        let end = self
+            .sequences
+            .iter()
+            .filter_map(|sequence| text.find(sequence.as_str()))
+            .min()
+            .unwrap_or(text.len());
+        Ok(text[..end].trim_end().to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_first_json_skips_prose_and_invalid_brackets() {
+        let text = "Result [see below]: {\"a\": [1, 2]} and {\"b\": 3}";
+        assert_eq!(
+            extract_first_json().process(text).unwrap(),
+            r#"{"a": [1, 2]}"#
+        );
+        let (value, range) = first_json(text).unwrap();
+        assert_eq!(value, serde_json::json!({"a": [1, 2]}));
+        assert_eq!(&text[range], r#"{"a": [1, 2]}"#);
+        assert!(matches!(
+            extract_first_json().process("no json {here"),
+            Err(Error::SchemaValidationError(_))
+        ));
+    }
+
+    #[test]
+    fn test_code_blocks_by_language() {
+        let text = "Intro\n```rust\nfn main() {}\n```\ntext\n~~~ Python extra\nprint(1)\n```\nstill python\n~~~\n```\nunclosed";
+        assert_eq!(
+            code_blocks(text),
+            [
+                CodeBlock {
+                    lang: Some("rust".into()),
+                    code: "fn main() {}".into(),
+                },
+                CodeBlock {
+                    lang: Some("Python".into()),
+                    code: "print(1)\n```\nstill python".into(),
+                },
+                CodeBlock {
+                    lang: None,
+                    code: "unclosed".into(),
+                },
+            ]
+        );
+        assert_eq!(
+            extract_code_blocks(Some("python")).process(text).unwrap(),
+            "print(1)\n```\nstill python"
+        );
+        assert_eq!(
+            extract_code_blocks(Some("go"))
+                .process(text)
+                .unwrap_err()
+                .to_string(),
+            "Schema validation error: No go code block found in output"
+        );
+    }
+
+    #[test]
+    fn test_strip_thinking_tags() {
+        let strip = strip_thinking_tags();
+        assert_eq!(
+            strip
+                .process("<think>hmm</think>\nAnswer <thinking>a</thinking>is 4.")
+                .unwrap(),
+            "Answer is 4."
+        );
+        assert_eq!(
+            strip.process("Partial <think>still going").unwrap(),
+            "Partial"
+        );
+    }
+
+    #[test]
+    fn test_trim_stop_sequences() {
+        let trim = trim_stop_sequences().with_sequence("END");
+        assert_eq!(trim.process("Hello!<|im_end|>\nuser:").unwrap(), "Hello!");
+        assert_eq!(trim.process("Done END </s>").unwrap(), "Done");
+        assert_eq!(trim.process("Untouched  ").unwrap(), "Untouched");
+    }
+
+    #[test]
+    fn test_pipeline_applies_in_order_to_responses() {
+        let response: ChatCompletionResponse = serde_json::from_value(serde_json::json!({
+            "id": "gen-1", "object": "chat.completion", "created": 0, "model": "m",
+            "choices": [{"index": 0, "finish_reason": "stop", "message": {
+                "role": "assistant",
+                "content": "<think>plan</think>```json\n{\"n\": 1}\n```<|eot_id|>"
+            }}]
+        }))
+        .unwrap();
+        let pipeline = Pipeline::new()
+            .then(strip_thinking_tags())
+            .then(trim_stop_sequences())
+            .then(extract_code_blocks(None))
+            .then(|text: &str| Ok(text.to_uppercase()));
+        assert_eq!(pipeline.apply_response(&response).unwrap(), r#"{"N": 1}"#);
+        assert_eq!(Pipeline::new().apply(" as is ").unwrap(), " as is ");
+    }
+}
@@ -0,0 +1,485 @@
+//! Chat sessions for sensitive data.
+//!
+//! A [`SensitiveSession`] keeps message contents in [`SensitiveText`]
+//! buffers, which are zeroed when dropped and redacted from `Debug` output,
+//! and it keeps them out of errors: failed responses are reported by status
+//! alone, without the response body the API sent back.
+//!
+//! The request body and the raw response body are also held in zeroizing
+//! buffers. Copies made inside the HTTP and TLS stack are outside this
+//! crate's control and are not zeroed; neither is text the caller copies out
+//! with [`SensitiveText::expose`].
+
+use crate::api::chat::ChatApi;
+use crate::error::{Error, Result};
+use crate::types::chat::{ChatCompletionRequest, ChatRole, ContentPart, MessageContent, Usage};
+use crate::utils::budget::{self, RequestSize};
+use crate::utils::retry::operations::CHAT_COMPLETION;
+use bytes::Bytes;
+use reqwest::header::{HeaderValue, CONTENT_TYPE};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::io;
+use zeroize::{Zeroize, Zeroizing};
+
+/// Text that is zeroed in memory when dropped and never shown by `Debug`.
+///
+/// Deliberately not `Clone`, `Display` or `Serialize`, so copies are only
+/// made explicitly through [`expose`](Self::expose).
+pub struct SensitiveText(Zeroizing<String>);
+
+impl SensitiveText {
+    /// Takes ownership of `text` without copying it.
+    pub fn new(text: impl Into<String>) -> Self {
+        Self(Zeroizing::new(text.into()))
+    }
+
+    /// Returns the text. Anything derived from it is not zeroed.
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl From<String> for SensitiveText {
+    fn from(text: String) -> Self {
+        Self::new(text)
+    }
+}
+
+impl From<&str> for SensitiveText {
+    fn from(text: &str) -> Self {
+        Self::new(text)
+    }
+}
+
+impl fmt::Debug for SensitiveText {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SensitiveText([REDACTED; {} bytes])", self.len())
+    }
+}
+
+/// A message in a [`SensitiveSession`].
+#[derive(Debug)]
+pub struct SensitiveMessage {
+    pub role: ChatRole,
+    pub content: SensitiveText,
+}
+
+/// A chat session whose message contents are zeroed on drop and kept out of
+/// errors and debug output.
+///
+/// # Example
+///
+/// ```no_run
+/// use openrouter_api::OpenRouterClient;
+///
+/// # async fn run() -> openrouter_api::Result<()> {
+/// let client = OpenRouterClient::from_env()?;
+/// let mut session = client
+///     .sensitive_session("openai/gpt-4o")?
+///     .with_system_prompt("You are a clinical documentation assistant.");
+///
+/// let reply = session.send("Summarize: patient presents with ...").await?;
+/// println!("{}", reply.expose());
+///
+/// // Zeroes every message in the session.
+/// session.clear();
+/// # Ok(())
+/// # }
+/// ```
+pub struct SensitiveSession {
+    chat: ChatApi,
+    model: String,
+    template: ChatCompletionRequest,
+    messages: Vec<SensitiveMessage>,
+    usage: Option<Usage>,
+}
+
+/// Request body borrowing the session's message contents.
+#[derive(Serialize)]
+struct WireRequest<'a> {
+    #[serde(flatten)]
+    parameters: serde_json::Map<String, serde_json::Value>,
+    messages: Vec<WireMessage<'a>>,
+}
+
+#[derive(Serialize)]
+struct WireMessage<'a> {
+    role: &'a ChatRole,
+    content: &'a str,
+}
+
+/// The parts of a chat completion response the session keeps.
+#[derive(Deserialize)]
+struct WireResponse {
+    choices: Vec<WireChoice>,
+    usage: Option<Usage>,
+}
+
+#[derive(Deserialize)]
+struct WireChoice {
+    message: WireReply,
+}
+
+#[derive(Deserialize)]
+struct WireReply {
+    content: Option<MessageContent>,
+}
+
+impl SensitiveSession {
+    /// Creates an empty session with `model`.
+    pub fn new(chat: ChatApi, model: impl Into<String>) -> Self {
+        Self {
+            chat,
+            model: model.into(),
+            template: ChatCompletionRequest::default(),
+            messages: Vec::new(),
+            usage: None,
+        }
+    }
+
+    /// Starts the session with a system message.
+    pub fn with_system_prompt(mut self, prompt: impl Into<SensitiveText>) -> Self {
+        self.push(ChatRole::System, prompt);
+        self
+    }
+
+    /// Sets sampling and other parameters sent with every request. The
+    /// template's `model`, `messages` and `stream` are ignored.
+    pub fn with_request_template(mut self, template: ChatCompletionRequest) -> Self {
+        self.template = template;
+        self
+    }
+
+    /// Appends a message without sending it.
+    pub fn push(&mut self, role: ChatRole, content: impl Into<SensitiveText>) {
+        self.messages.push(SensitiveMessage {
+            role,
+            content: content.into(),
+        });
+    }
+
+    /// The messages so far, oldest first.
+    pub fn messages(&self) -> &[SensitiveMessage] {
+        &self.messages
+    }
+
+    /// Usage reported for the last response.
+    pub fn last_usage(&self) -> Option<&Usage> {
+        self.usage.as_ref()
+    }
+
+    /// Drops (and so zeroes) every message.
+    pub fn clear(&mut self) {
+        self.messages.clear();
+    }
+
+    /// Sends `content` as a user message and returns the assistant's reply,
+    /// which is appended to the session.
+    ///
+    /// On error the user message stays in the session, so the call can be
+    /// retried with [`resend`](Self::resend).
+    pub async fn send(&mut self, content: impl Into<SensitiveText>) -> Result<&SensitiveText> {
+        self.push(ChatRole::User, content);
+        self.resend().await
+    }
+
+    /// Sends the session as it is and appends the assistant's reply.
+    pub async fn resend(&mut self) -> Result<&SensitiveText> {
+        let body = self.request_body()?;
+        // Same 4-bytes-per-token approximation as the validation estimates.
+        let size = RequestSize {
+            prompt_tokens: u32::try_from(body.len() / 4).unwrap_or(u32::MAX),
+            max_tokens: self.template.max_tokens,
+        };
+        let (reply, usage) = budget::guarded(
+            self.chat.config.budget_guard.as_deref(),
+            size,
+            self.post(body),
+            |(_, usage): &(SensitiveText, Option<Usage>)| usage.as_ref().and_then(|u| u.cost),
+        )
+        .await?;
+        self.usage = usage;
+        self.push(ChatRole::Assistant, reply);
+        Ok(&self.messages[self.messages.len() - 1].content)
+    }
+
+    /// Serializes the request into a zeroizing buffer.
+    fn request_body(&self) -> Result<Zeroizing<Vec<u8>>> {
+        let mut template = self.template.clone();
+        template.model.clone_from(&self.model);
+        template.messages.clear();
+        template.stream = None;
+        if template.user.is_none() {
+            template.user.clone_from(&self.chat.config.default_user);
+        }
+        self.chat.config.default_params.apply_to_chat(&mut template);
+        let serde_json::Value::Object(mut parameters) =
+            serde_json::to_value(&template).map_err(Error::SerializationError)?
+        else {
+            return Err(Error::ConfigError(
+                "Request template did not serialize to an object".into(),
+            ));
+        };
+        parameters.remove("messages");
+
+        let request = WireRequest {
+            parameters,
+            messages: self
+                .messages
+                .iter()
+                .map(|message| WireMessage {
+                    role: &message.role,
+                    content: message.content.expose(),
+                })
+                .collect(),
+        };
+        let mut body = Zeroizing::new(Vec::new());
+        serde_json::to_writer(ZeroizingWriter(&mut body), &request).map_err(|_| {
+            Error::ConfigError("Failed to serialize sensitive request (details withheld)".into())
+        })?;
+        Ok(body)
+    }
+
+    /// Posts `body` and reads the reply, keeping every buffer zeroizing and
+    /// every error free of content.
+    async fn post(&self, body: Zeroizing<Vec<u8>>) -> Result<(SensitiveText, Option<Usage>)> {
+        let config = &self.chat.config;
+        let url = config.url_builder().build("chat/completions")?;
+        // Every attempt shares the buffer, which is zeroed once the last
+        // attempt's request drops its reference.
+        let body = Bytes::from_owner(body);
+        let mut response = config
+            .execute(CHAT_COMPLETION, || {
+                self.chat
+                    .client
+                    .post(url.clone())
+                    .headers((*config.headers).clone())
+                    .header(CONTENT_TYPE, HeaderValue::from_static("application/json"))
+                    .body(body.clone())
+            })
+            .await?;
+
+        let status = response.status().as_u16();
+        if !response.status().is_success() {
+            return Err(match status {
+                429 => Error::RateLimitExceeded("response body withheld in sensitive mode".into()),
+                _ => Error::ApiError {
+                    code: status,
+                    message: "Chat completion failed; response body withheld in sensitive mode"
+                        .into(),
+                    metadata: None,
+                },
+            });
+        }
+
+        let expected = response.content_length().unwrap_or(0) as usize;
+        let mut raw = Zeroizing::new(Vec::with_capacity(expected.min(config.max_response_bytes)));
+        while let Some(chunk) = response.chunk().await? {
+            if raw.len() + chunk.len() > config.max_response_bytes {
+                return Err(Error::ResponseTooLarge(
+                    raw.len() + chunk.len(),
+                    config.max_response_bytes,
+                ));
+            }
+            extend_zeroizing(&mut raw, &chunk);
+        }
+
+        let decoded: WireResponse =
+            serde_json::from_slice(&raw).map_err(|_| Error::DeserializationError {
+                message:
+                    "Failed to decode chat completion response (details withheld in sensitive mode)"
+                        .into(),
+                status_code: status,
+                source: None,
+            })?;
+        let reply = decoded
+            .choices
+            .into_iter()
+            .next()
+            .and_then(|choice| choice.message.content)
+            .map(into_sensitive)
+            .unwrap_or_else(|| SensitiveText::new(String::new()));
+        Ok((reply, decoded.usage))
+    }
+}
+
+/// Appends `data` to `buffer`. Instead of letting the `Vec` reallocate, which
+/// frees the old allocation without zeroing it, a full buffer is copied into
+/// a larger zeroizing one and dropped.
+fn extend_zeroizing(buffer: &mut Zeroizing<Vec<u8>>, data: &[u8]) {
+    let needed = buffer.len() + data.len();
+    if needed > buffer.capacity() {
+        let mut grown = Zeroizing::new(Vec::with_capacity(needed.max(buffer.capacity() * 2)));
+        grown.extend_from_slice(buffer);
+        *buffer = grown;
+    }
+    buffer.extend_from_slice(data);
+}
+
+/// `io::Write` into a zeroizing buffer, growing it with [`extend_zeroizing`].
+struct ZeroizingWriter<'a>(&'a mut Zeroizing<Vec<u8>>);
+
+impl io::Write for ZeroizingWriter<'_> {
+    fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+        extend_zeroizing(self.0, data);
+        Ok(data.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Moves `content` into a [`SensitiveText`], zeroing any intermediate text.
+fn into_sensitive(mut content: MessageContent) -> SensitiveText {
+    match &mut content {
+        MessageContent::Text(text) => SensitiveText::new(std::mem::take(text)),
+        MessageContent::Parts(_) => {
+            let text = SensitiveText::new(content.to_text());
+            zeroize_content(&mut content);
+            text
+        }
+    }
+}
+
+fn zeroize_content(content: &mut MessageContent) {
+    match content {
+        MessageContent::Text(text) => text.zeroize(),
+        MessageContent::Parts(parts) => {
+            for part in parts {
+                if let ContentPart::Text(text) = part {
+                    text.text.zeroize();
+                }
+            }
+        }
+    }
+}
+
+impl Drop for SensitiveSession {
+    fn drop(&mut self) {
+        // Message contents zero themselves; the template may hold copies.
+        for message in &mut self.template.messages {
+            zeroize_content(&mut message.content);
+        }
+    }
+}
+
+impl fmt::Debug for SensitiveSession {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SensitiveSession")
+            .field("model", &self.model)
+            .field("messages", &self.messages.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::test_helpers::test_client_config;
+    use serde_json::json;
+    use wiremock::{matchers, Mock, MockServer, ResponseTemplate};
+
+    const SECRET: &str = "patient MRN 448-12-9921";
+
+    async fn session_for(server: &MockServer) -> SensitiveSession {
+        let mut config = test_client_config();
+        config.base_url = format!("{}/", server.uri()).parse().unwrap();
+        SensitiveSession::new(
+            ChatApi::new(reqwest::Client::new(), &config).unwrap(),
+            "openai/gpt-4o",
+        )
+    }
+
+    #[test]
+    fn test_extend_zeroizing_grows_into_fresh_buffers() {
+        let mut buffer = Zeroizing::new(Vec::with_capacity(4));
+        extend_zeroizing(&mut buffer, b"abc");
+        let ptr = buffer.as_ptr();
+        extend_zeroizing(&mut buffer, b"d");
+        assert_eq!(buffer.as_ptr(), ptr, "fits without growing");
+
+        extend_zeroizing(&mut buffer, b"efghij");
+        assert_eq!(buffer.as_slice(), b"abcdefghij");
+        assert!(buffer.capacity() >= 10);
+    }
+
+    #[tokio::test]
+    async fn test_send_round_trip() {
+        let server = MockServer::start().await;
+        Mock::given(matchers::method("POST"))
+            .and(matchers::path("/chat/completions"))
+            .and(matchers::body_json(json!({
+                "model": "openai/gpt-4o",
+                "temperature": 0.0,
+                "messages": [
+                    {"role": "system", "content": "Be terse."},
+                    {"role": "user", "content": SECRET}
+                ]
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": "gen-1",
+                "object": "chat.completion",
+                "created": 0,
+                "model": "openai/gpt-4o",
+                "choices": [{"message": {"role": "assistant", "content": "Noted."}}],
+                "usage": {"prompt_tokens": 12, "completion_tokens": 2, "total_tokens": 14}
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let mut session = session_for(&server)
+            .await
+            .with_system_prompt("Be terse.")
+            .with_request_template(ChatCompletionRequest {
+                temperature: Some(0.0),
+                ..Default::default()
+            });
+        let reply = session.send(SECRET).await.unwrap();
+        assert_eq!(reply.expose(), "Noted.");
+        assert_eq!(session.messages().len(), 3);
+        assert_eq!(session.last_usage().unwrap().total_tokens, 14);
+
+        let debug = format!("{session:?} {:?}", session.messages());
+        assert!(!debug.contains(SECRET));
+        assert!(debug.contains("REDACTED"));
+    }
+
+    #[tokio::test]
+    async fn test_errors_do_not_echo_content() {
+        let server = MockServer::start().await;
+        Mock::given(matchers::method("POST"))
+            .respond_with(ResponseTemplate::new(403).set_body_json(json!({
+                "error": {"code": 403, "message": "Input flagged", "metadata": {"flagged_input": SECRET}}
+            })))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(matchers::method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(format!(
+                r#"{{"choices": [{{"message": {{"content": 42}}}}], "echo": "{SECRET}""#
+            )))
+            .mount(&server)
+            .await;
+
+        let mut session = session_for(&server).await;
+        let error = session.send(SECRET).await.unwrap_err();
+        assert!(matches!(error, Error::ApiError { code: 403, .. }));
+        assert!(!format!("{error} {error:?}").contains(SECRET));
+
+        let error = session.resend().await.unwrap_err();
+        assert!(matches!(error, Error::DeserializationError { .. }));
+        assert!(!format!("{error} {error:?}").contains(SECRET));
+        assert_eq!(session.messages().len(), 1);
+    }
+}
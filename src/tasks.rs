@@ -0,0 +1,356 @@
+//! Bounded background execution of chat requests.
+//!
+//! A [`TaskPool`] runs chat requests as Tokio tasks, at most
+//! `max_concurrency` at a time, and hands back a [`JoinHandle`] per request
+//! (or adds it to a caller's [`JoinSet`]). Every task carries a label that is
+//! passed to `MetricsRecorder::record_task` when it ends.
+//!
+//! [`TaskPool::shutdown`] stops accepting work, lets running tasks finish
+//! within a grace period and then cancels the rest, dropping in-flight
+//! streams and closing their connections.
+
+use crate::api::chat::ChatApi;
+use crate::error::{Error, Result};
+use crate::types::chat::{
+    ChatCompletionAccumulator, ChatCompletionRequest, ChatCompletionResponse,
+};
+use crate::utils::metrics::{MetricsRecorder, TaskOutcome};
+use futures::StreamExt;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Semaphore;
+use tokio::task::{AbortHandle, JoinHandle, JoinSet};
+use tokio_util::sync::CancellationToken;
+use tokio_util::task::TaskTracker;
+
+/// Runs chat requests as background tasks under a concurrency limit.
+///
+/// # Example
+///
+/// ```no_run
+/// use openrouter_api::types::chat::{ChatCompletionRequest, ChatRole, Message};
+/// use openrouter_api::OpenRouterClient;
+/// use std::time::Duration;
+///
+/// # async fn run() -> openrouter_api::Result<()> {
+/// let client = OpenRouterClient::from_env()?;
+/// let pool = client.task_pool(8)?;
+///
+/// let handle = pool.spawn_chat(
+///     "greeting",
+///     ChatCompletionRequest {
+///         model: "openai/gpt-4o".into(),
+///         messages: vec![Message::text(ChatRole::User, "Hello")],
+///         ..Default::default()
+///     },
+/// );
+/// let response = handle.await.expect("task panicked")?;
+///
+/// pool.shutdown(Duration::from_secs(5)).await;
+/// # Ok(())
+/// # }
+/// ```
+pub struct TaskPool {
+    chat: Arc<ChatApi>,
+    permits: Arc<Semaphore>,
+    max_concurrency: usize,
+    tracker: TaskTracker,
+    cancel: CancellationToken,
+    recorder: Option<Arc<dyn MetricsRecorder>>,
+}
+
+impl TaskPool {
+    /// Creates a pool running at most `max_concurrency` requests at once
+    /// (minimum 1). Task metrics go to the `MetricsRecorder` configured on
+    /// `chat`'s client.
+    pub fn new(chat: ChatApi, max_concurrency: usize) -> Self {
+        let max_concurrency = max_concurrency.max(1);
+        let recorder = chat.config.metrics_recorder.clone();
+        Self {
+            chat: Arc::new(chat),
+            permits: Arc::new(Semaphore::new(max_concurrency)),
+            max_concurrency,
+            tracker: TaskTracker::new(),
+            cancel: CancellationToken::new(),
+            recorder,
+        }
+    }
+
+    /// Sends `request` with `ChatApi::chat_completion` in a background task.
+    ///
+    /// The handle resolves to `Error::Cancelled` if the pool shuts down
+    /// before the request completes.
+    pub fn spawn_chat(
+        &self,
+        label: impl Into<String>,
+        request: ChatCompletionRequest,
+    ) -> JoinHandle<Result<ChatCompletionResponse>> {
+        let chat = Arc::clone(&self.chat);
+        let task = self.task(
+            label.into(),
+            async move { chat.chat_completion(request).await },
+        );
+        tokio::spawn(self.tracker.track_future(task))
+    }
+
+    /// Streams `request` in a background task and assembles the chunks into
+    /// a response.
+    ///
+    /// Cancelling the task drops the stream, closing its connection.
+    pub fn spawn_chat_stream(
+        &self,
+        label: impl Into<String>,
+        request: ChatCompletionRequest,
+    ) -> JoinHandle<Result<ChatCompletionResponse>> {
+        let chat = Arc::clone(&self.chat);
+        let task = self.task(label.into(), async move {
+            let mut stream = chat.chat_completion_stream(request);
+            let mut accumulator = ChatCompletionAccumulator::new();
+            while let Some(chunk) = stream.next().await {
+                accumulator.push(chunk?);
+            }
+            accumulator.finish()
+        });
+        tokio::spawn(self.tracker.track_future(task))
+    }
+
+    /// Like [`spawn_chat`](Self::spawn_chat), but adds the task to `set`,
+    /// which yields each task's label with its result.
+    pub fn spawn_chat_on(
+        &self,
+        set: &mut JoinSet<(String, Result<ChatCompletionResponse>)>,
+        label: impl Into<String>,
+        request: ChatCompletionRequest,
+    ) -> AbortHandle {
+        let label = label.into();
+        let chat = Arc::clone(&self.chat);
+        let task = self.task(
+            label.clone(),
+            async move { chat.chat_completion(request).await },
+        );
+        set.spawn(
+            self.tracker
+                .track_future(async move { (label, task.await) }),
+        )
+    }
+
+    /// Number of tasks submitted and not yet finished, queued or running.
+    pub fn in_flight(&self) -> usize {
+        self.tracker.len()
+    }
+
+    /// Maximum number of requests run at once.
+    pub fn max_concurrency(&self) -> usize {
+        self.max_concurrency
+    }
+
+    /// Whether [`shutdown`](Self::shutdown) has been called.
+    pub fn is_shut_down(&self) -> bool {
+        self.tracker.is_closed()
+    }
+
+    /// Stops accepting tasks and waits up to `grace` for submitted tasks to
+    /// finish, then cancels the remainder and waits for them to unwind.
+    ///
+    /// Tasks submitted after shutdown resolve to `Error::Cancelled`
+    /// immediately.
+    pub async fn shutdown(&self, grace: Duration) {
+        self.tracker.close();
+        if tokio::time::timeout(grace, self.tracker.wait())
+            .await
+            .is_err()
+        {
+            self.cancel.cancel();
+            self.tracker.wait().await;
+        }
+    }
+
+    /// Wraps `work` with the concurrency limit, cancellation and metrics.
+    fn task(
+        &self,
+        label: String,
+        work: impl Future<Output = Result<ChatCompletionResponse>> + Send + 'static,
+    ) -> impl Future<Output = Result<ChatCompletionResponse>> + Send + 'static {
+        let permits = Arc::clone(&self.permits);
+        let cancel = self.cancel.clone();
+        let recorder = self.recorder.clone();
+        let closed = self.tracker.is_closed();
+
+        async move {
+            let submitted = Instant::now();
+            let result = if closed {
+                Err(Error::Cancelled(format!(
+                    "task '{label}' submitted after shutdown"
+                )))
+            } else {
+                tokio::select! {
+                    biased;
+                    () = cancel.cancelled() => Err(Error::Cancelled(format!(
+                        "task '{label}' cancelled by shutdown"
+                    ))),
+                    result = async {
+                        let _permit = permits
+                            .acquire_owned()
+                            .await
+                            .map_err(|_| Error::Cancelled("task pool closed".into()))?;
+                        work.await
+                    } => result,
+                }
+            };
+
+            if let Some(recorder) = &recorder {
+                let outcome = match &result {
+                    Ok(_) => TaskOutcome::Succeeded,
+                    Err(Error::Cancelled(_)) => TaskOutcome::Cancelled,
+                    Err(_) => TaskOutcome::Failed,
+                };
+                recorder.record_task(&label, outcome, submitted.elapsed());
+            }
+            result
+        }
+    }
+}
+
+impl std::fmt::Debug for TaskPool {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TaskPool")
+            .field("max_concurrency", &self.max_concurrency)
+            .field("in_flight", &self.in_flight())
+            .field("shut_down", &self.is_shut_down())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::test_helpers::test_client_config;
+    use crate::types::chat::{ChatRole, Message};
+    use serde_json::json;
+    use std::sync::Mutex;
+    use wiremock::{matchers, Mock, MockServer, ResponseTemplate};
+
+    fn chat_response() -> ResponseTemplate {
+        ResponseTemplate::new(200).set_body_json(json!({
+            "id": "gen-1",
+            "choices": [{"message": {"role": "assistant", "content": "hi"}}],
+            "created": 0,
+            "model": "openai/gpt-4o",
+            "object": "chat.completion"
+        }))
+    }
+
+    fn pool_for(server: &MockServer, max_concurrency: usize) -> TaskPool {
+        let mut config = test_client_config();
+        config.base_url = format!("{}/", server.uri()).parse().unwrap();
+        TaskPool::new(
+            ChatApi::new(reqwest::Client::new(), &config).unwrap(),
+            max_concurrency,
+        )
+    }
+
+    fn request() -> ChatCompletionRequest {
+        ChatCompletionRequest {
+            model: "openai/gpt-4o".to_string(),
+            messages: vec![Message::text(ChatRole::User, "hello")],
+            ..Default::default()
+        }
+    }
+
+    #[derive(Debug, Default)]
+    struct Outcomes(Mutex<Vec<(String, TaskOutcome)>>);
+
+    impl MetricsRecorder for Outcomes {
+        fn record_task(&self, label: &str, outcome: TaskOutcome, _elapsed: Duration) {
+            self.0.lock().unwrap().push((label.to_string(), outcome));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_pool_limits_concurrency() {
+        let server = MockServer::start().await;
+        Mock::given(matchers::method("POST"))
+            .respond_with(chat_response().set_delay(Duration::from_millis(200)))
+            .expect(4)
+            .mount(&server)
+            .await;
+
+        let pool = pool_for(&server, 2);
+        let started = Instant::now();
+        let mut set = JoinSet::new();
+        for i in 0..4 {
+            pool.spawn_chat_on(&mut set, format!("task-{i}"), request());
+        }
+        assert_eq!(pool.in_flight(), 4);
+
+        let mut labels = Vec::new();
+        while let Some(joined) = set.join_next().await {
+            let (label, result) = joined.unwrap();
+            result.unwrap();
+            labels.push(label);
+        }
+        labels.sort();
+        assert_eq!(labels, ["task-0", "task-1", "task-2", "task-3"]);
+        assert!(
+            started.elapsed() >= Duration::from_millis(400),
+            "four 200ms requests two at a time should take two rounds"
+        );
+        assert_eq!(pool.in_flight(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_cancels_in_flight_streams() {
+        let server = MockServer::start().await;
+        Mock::given(matchers::method("POST"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-type", "text/event-stream")
+                    .set_body_string("data: [DONE]\n\n")
+                    .set_delay(Duration::from_secs(30)),
+            )
+            .mount(&server)
+            .await;
+
+        let outcomes = Arc::new(Outcomes::default());
+        let mut pool = pool_for(&server, 4);
+        pool.recorder = Some(outcomes.clone());
+
+        let handle = pool.spawn_chat_stream("slow", request());
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        let started = Instant::now();
+        pool.shutdown(Duration::from_millis(100)).await;
+        assert!(started.elapsed() < Duration::from_secs(5));
+        assert!(pool.is_shut_down());
+        assert!(matches!(handle.await.unwrap(), Err(Error::Cancelled(_))));
+
+        let late = pool.spawn_chat("late", request()).await.unwrap();
+        assert!(matches!(late, Err(Error::Cancelled(_))));
+
+        assert_eq!(
+            *outcomes.0.lock().unwrap(),
+            [
+                ("slow".to_string(), TaskOutcome::Cancelled),
+                ("late".to_string(), TaskOutcome::Cancelled),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_waits_for_tasks_within_grace() {
+        let server = MockServer::start().await;
+        Mock::given(matchers::method("POST"))
+            .respond_with(chat_response().set_delay(Duration::from_millis(100)))
+            .mount(&server)
+            .await;
+
+        let pool = pool_for(&server, 1);
+        let first = pool.spawn_chat("first", request());
+        let second = pool.spawn_chat("second", request());
+        pool.shutdown(Duration::from_secs(5)).await;
+
+        assert!(first.await.unwrap().is_ok());
+        assert!(second.await.unwrap().is_ok());
+    }
+}
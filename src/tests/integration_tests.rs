@@ -64,6 +64,7 @@ mod tests {
             min_p: None,
             top_a: None,
             seed: None,
+            n: None,
             stop: None,
             logit_bias: None,
             logprobs: None,
@@ -74,6 +75,7 @@ mod tests {
             debug: None,
             plugins: None,
             reasoning: None,
+            extra_body: None,
         };
 
         // For this integration test we are simulating a response.
@@ -137,29 +139,39 @@ mod tests {
 
         // Create a dummy client in Ready state to call our validation helper.
         let client = OpenRouterClient::<crate::client::Ready> {
-            config: crate::client::ClientConfig {
-                api_key: Some(
-                    crate::client::SecureApiKey::new("sk-1234567890abcdef1234567890abcdef")
-                        .unwrap(),
-                ),
-                base_url: Url::parse("https://dummy/").unwrap(),
-                http_referer: None,
-                site_title: None,
-                user_id: None, // Add this field
-                timeout: std::time::Duration::from_secs(30),
-                retry_config: RetryConfig::default(), // Add this field
-                max_response_bytes: 10 * 1024 * 1024,
-            },
-            http_client: None,
+            inner: std::sync::Arc::new(crate::client::ClientInner {
+                config: crate::client::ClientConfig {
+                    api_key: Some(
+                        crate::client::SecureApiKey::new("sk-1234567890abcdef1234567890abcdef")
+                            .unwrap(),
+                    ),
+                    base_url: Url::parse("https://dummy/").unwrap(),
+                    http_referer: None,
+                    site_title: None,
+                    user_id: None, // Add this field
+                    timeout: std::time::Duration::from_secs(30),
+                    retry_config: RetryConfig::default(), // Add this field
+                    max_response_bytes: 10 * 1024 * 1024,
+                    ..Default::default()
+                },
+                http_client: None,
+                router_config: None,
+                cached_api_config: None,
+                catalog_cache: Default::default(),
+                model_selection: Default::default(),
+                provider_health: Default::default(),
+            }),
             _state: std::marker::PhantomData,
-            router_config: None,
-            cached_api_config: None,
-            providers_cache: None,
         };
 
         // Validate the tool calls – should return Ok.
         client.validate_tool_calls(&response)?;
 
+        assert_eq!(response.text(), Some("Calling tool for weather."));
+        assert_eq!(response.finish_reason(), Some("tool_calls"));
+        assert_eq!(response.tool_calls().len(), 1);
+        assert_eq!(response.tool_calls()[0].function_call.name, "get_weather");
+
         Ok(())
     }
 
@@ -189,6 +201,10 @@ mod tests {
         assert!(!response.choices.is_empty());
         assert_eq!(response.choices[0].finish_reason.as_deref(), Some("stop"));
         assert!(response.choices[0].text.contains("Once upon a time"));
+        assert_eq!(
+            response.text(),
+            Some("Once upon a time, in a land far, far away...")
+        );
 
         Ok(())
     }
@@ -263,12 +279,56 @@ mod tests {
         // Check that the serialized JSON contains the "provider" key with the expected configuration.
         let payload_value: Value = serde_json::from_str(&payload_json)?;
         let provider_config = payload_value.get("provider").expect("provider key missing");
-        assert_eq!(provider_config.get("allowFallbacks").unwrap(), false);
+        assert_eq!(provider_config.get("allow_fallbacks").unwrap(), false);
         assert_eq!(provider_config.get("sort").unwrap(), "throughput");
 
         Ok(())
     }
 
+    #[test]
+    fn test_assistant_prefill_builder_and_stream_assembly() {
+        use crate::api::request::RequestBuilder;
+        use crate::types::chat::{ChatCompletionAccumulator, ChatCompletionChunk};
+
+        let messages = vec![Message::text(ChatRole::User, "Name a color as JSON.")];
+        let builder = RequestBuilder::new("anthropic/claude-sonnet-4", messages.clone(), json!({}))
+            .with_assistant_prefill("[")
+            .unwrap()
+            .with_assistant_prefill("{\"color\":")
+            .unwrap();
+        assert_eq!(builder.assistant_prefill(), Some("{\"color\":"));
+        let payload = builder.build();
+        assert_eq!(payload.messages.len(), 2);
+        assert_eq!(payload.messages[1].role, ChatRole::Assistant);
+        let value = serde_json::to_value(&payload).unwrap();
+        assert_eq!(value["messages"][1]["content"], "{\"color\":");
+        assert!(value.get("assistant_prefill").is_none());
+
+        assert!(matches!(
+            RequestBuilder::new("openai/gpt-4o", messages, json!({})).with_assistant_prefill("{"),
+            Err(crate::error::Error::ConfigError(_))
+        ));
+
+        let mut accumulator =
+            ChatCompletionAccumulator::with_prefill(payload.assistant_prefill.unwrap());
+        for token in [" \"teal\"", "}"] {
+            let chunk: ChatCompletionChunk = serde_json::from_value(json!({
+                "id": "gen-1",
+                "choices": [{"index": 0, "delta": {"content": token}}],
+                "created": 0,
+                "model": "anthropic/claude-sonnet-4",
+                "object": "chat.completion.chunk"
+            }))
+            .unwrap();
+            accumulator.push(chunk);
+        }
+        let mut response = accumulator.finish().unwrap();
+        assert_eq!(response.text(), Some("{\"color\": \"teal\"}"));
+
+        response.prepend_prefill(">");
+        assert_eq!(response.text(), Some(">{\"color\": \"teal\"}"));
+    }
+
     #[tokio::test]
     async fn test_web_search_response_deserialization() -> Result<(), Box<dyn std::error::Error>> {
         // Simulated web search response JSON.
@@ -344,6 +404,7 @@ mod tests {
             min_p: None,
             top_a: None,
             seed: None,
+            n: None,
             stop: None,
             logit_bias: None,
             logprobs: None,
@@ -354,6 +415,7 @@ mod tests {
             debug: None,
             plugins: None,
             reasoning: None,
+            extra_body: None,
         };
 
         // Serialize to JSON to verify the structure
@@ -376,11 +438,11 @@ mod tests {
         assert!(order.is_array(), "Order should be an array");
 
         let allow_fallbacks = provider_field
-            .get("allowFallbacks")
-            .expect("allowFallbacks field should exist");
+            .get("allow_fallbacks")
+            .expect("allow_fallbacks field should exist");
         assert!(
             allow_fallbacks.is_boolean(),
-            "allowFallbacks should be boolean"
+            "allow_fallbacks should be boolean"
         );
 
         Ok(())
@@ -620,6 +682,8 @@ mod tests {
             origin: "api".to_string(),
             usage: 0.01,
             is_byok: false,
+            #[cfg(feature = "preserve-unknown")]
+            extra: Default::default(),
         };
 
         assert_eq!(generation_data.total_tokens(), Some(50));
@@ -663,6 +727,8 @@ mod tests {
             origin: "api".to_string(),
             usage: 0.005,
             is_byok: false,
+            #[cfg(feature = "preserve-unknown")]
+            extra: Default::default(),
         };
 
         assert_eq!(minimal_generation.total_tokens(), None);
@@ -733,6 +799,8 @@ mod tests {
                 origin: "api".to_string(),
                 usage: 0.015,
                 is_byok: false,
+                #[cfg(feature = "preserve-unknown")]
+                extra: Default::default(),
             },
         };
 
@@ -798,6 +866,8 @@ mod tests {
                 origin: "api".to_string(),
                 usage: total_cost,
                 is_byok: false,
+                #[cfg(feature = "preserve-unknown")]
+                extra: Default::default(),
             };
 
             assert!((data.effective_cost() - expected_effective).abs() < f64::EPSILON);
@@ -1601,4 +1671,305 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_response_accessors_handle_empty_and_multimodal_choices(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let empty = deserialize_chat_response(
+            r#"{"id": "gen-1", "choices": [], "created": 0, "model": "m", "object": "chat.completion"}"#,
+        );
+        assert!(empty.first_choice().is_none());
+        assert!(empty.text().is_none());
+        assert!(empty.tool_calls().is_empty());
+        assert!(empty.finish_reason().is_none());
+
+        let multimodal = deserialize_chat_response(
+            r#"{
+                "id": "gen-2",
+                "choices": [{
+                    "message": {
+                        "role": "assistant",
+                        "content": [
+                            {"type": "text", "text": "Hello, "},
+                            {"type": "image_url", "image_url": {"url": "https://example.com/a.png"}},
+                            {"type": "text", "text": "world"}
+                        ]
+                    },
+                    "finish_reason": "stop"
+                }],
+                "created": 0,
+                "model": "m",
+                "object": "chat.completion"
+            }"#,
+        );
+        assert!(multimodal.text().is_none());
+        let content = &multimodal.first_choice().unwrap().message.content;
+        assert_eq!(content.to_text(), "Hello, world");
+
+        let completion: crate::types::completion::CompletionResponse =
+            serde_json::from_str(r#"{"id": null, "choices": []}"#)?;
+        assert!(completion.text().is_none());
+
+        Ok(())
+    }
+
+    #[cfg(feature = "preserve-unknown")]
+    #[test]
+    fn test_unknown_response_fields_are_preserved() {
+        let response: ChatCompletionResponse = serde_json::from_value(json!({
+            "id": "gen-1",
+            "provider": "OpenAI",
+            "choices": [{
+                "index": 0,
+                "message": {"role": "assistant", "content": "Hi"},
+                "finish_reason": "stop",
+                "annotations_v2": [{"kind": "note"}]
+            }],
+            "created": 0,
+            "model": "openai/gpt-4o",
+            "object": "chat.completion",
+            "usage": {"prompt_tokens": 1, "completion_tokens": 1, "total_tokens": 2, "carbon_grams": 0.01}
+        }))
+        .unwrap();
+        assert_eq!(response.extra["provider"], "OpenAI");
+        assert!(!response.extra.contains_key("model"));
+        assert_eq!(
+            response.choices[0].extra["annotations_v2"][0]["kind"],
+            "note"
+        );
+        assert_eq!(response.usage.as_ref().unwrap().extra["carbon_grams"], 0.01);
+
+        let generation: crate::types::generation::GenerationResponse =
+            serde_json::from_value(json!({
+                "data": {
+                    "id": "gen-1", "total_cost": 0.0, "created_at": "2024-01-15T10:30:00Z",
+                    "model": "openai/gpt-4o", "streamed": false, "cancelled": false,
+                    "origin": "api", "usage": 0.0, "is_byok": false, "router": "auto"
+                }
+            }))
+            .unwrap();
+        assert_eq!(generation.data.extra["router"], "auto");
+    }
+
+    #[test]
+    fn test_multiple_choices_are_keyed_by_index() {
+        use crate::types::chat::{ChatCompletionAccumulator, ChatCompletionChunk};
+
+        let choice = |index: Option<u32>, text: &str, logprob: f64| {
+            json!({
+                "index": index,
+                "message": {"role": "assistant", "content": text},
+                "finish_reason": "stop",
+                "logprobs": {"content": [{"token": text, "logprob": logprob}]}
+            })
+        };
+        let response: ChatCompletionResponse = serde_json::from_value(json!({
+            "id": "gen-1",
+            "choices": [choice(Some(2), "c", -0.5), choice(Some(0), "a", -2.0), choice(Some(1), "bb", -0.1)],
+            "created": 0,
+            "model": "openai/gpt-4o",
+            "object": "chat.completion"
+        }))
+        .unwrap();
+        let texts: Vec<_> = response
+            .choices
+            .iter()
+            .filter_map(|c| c.message.content.as_text())
+            .collect();
+        assert_eq!(texts, ["a", "bb", "c"]);
+        assert_eq!(
+            response.choice(2).unwrap().message.content.as_text(),
+            Some("c")
+        );
+        assert!(response.choice(3).is_none());
+
+        let longest = response
+            .best_by(|c| c.message.content.to_text().len())
+            .unwrap();
+        assert_eq!(longest.index, Some(1));
+        let first_of_ties = response.best_by(|_| 1).unwrap();
+        assert_eq!(first_of_ties.index, Some(0));
+        assert!(response.best_by(|_| f64::NAN).is_none());
+        assert_eq!(response.most_likely().unwrap().index, Some(1));
+
+        let positional: ChatCompletionResponse = serde_json::from_value(json!({
+            "id": "gen-2",
+            "choices": [choice(None, "x", -1.0), choice(None, "y", -1.0)],
+            "created": 0,
+            "model": "openai/gpt-4o",
+            "object": "chat.completion"
+        }))
+        .unwrap();
+        assert_eq!(
+            positional.choice(1).unwrap().message.content.as_text(),
+            Some("y")
+        );
+
+        let mut accumulator = ChatCompletionAccumulator::new();
+        for (index, token) in [(1, "B"), (0, "A"), (1, "b"), (0, "a")] {
+            let chunk: ChatCompletionChunk = serde_json::from_value(json!({
+                "id": "gen-3",
+                "choices": [{"index": index, "delta": {"content": token}}],
+                "created": 0,
+                "model": "openai/gpt-4o",
+                "object": "chat.completion.chunk"
+            }))
+            .unwrap();
+            accumulator.push(chunk);
+        }
+        let streamed = accumulator.finish().unwrap();
+        assert_eq!(
+            streamed.choice(0).unwrap().message.content.as_text(),
+            Some("Aa")
+        );
+        assert_eq!(
+            streamed.choice(1).unwrap().message.content.as_text(),
+            Some("Bb")
+        );
+    }
+
+    #[test]
+    fn test_completion_request_typed_fields_serialization() {
+        use crate::types::completion::CompletionRequest;
+
+        let request = CompletionRequest {
+            model: "mistralai/codestral-2501".to_string(),
+            prompt: "fn add(a: i32, b: i32) -> i32 {".to_string(),
+            suffix: Some("\n}".to_string()),
+            n: Some(2),
+            best_of: Some(4),
+            extra_params: json!({"temperature": 0.2}),
+            ..Default::default()
+        };
+        let value = serde_json::to_value(&request).unwrap();
+        assert_eq!(value["suffix"], "\n}");
+        assert_eq!(value["n"], 2);
+        assert_eq!(value["best_of"], 4);
+        assert_eq!(value["temperature"], 0.2);
+        assert!(value.get("echo").is_none());
+    }
+
+    #[test]
+    fn test_logprobs_in_responses_and_streams() {
+        use crate::types::chat::{ChatCompletionAccumulator, ChatCompletionChunk};
+
+        let response = deserialize_chat_response(
+            r#"{
+                "id": "gen-1",
+                "choices": [{
+                    "message": {"role": "assistant", "content": "Hi there"},
+                    "finish_reason": "stop",
+                    "logprobs": {
+                        "content": [
+                            {"token": "Hi", "logprob": -0.1, "bytes": [72, 105],
+                             "top_logprobs": [{"token": "Hi", "logprob": -0.1, "bytes": [72, 105]},
+                                              {"token": "Hello", "logprob": -2.5, "bytes": null}]},
+                            {"token": " there", "logprob": -0.3, "bytes": null, "top_logprobs": []}
+                        ],
+                        "refusal": null
+                    }
+                }],
+                "created": 0,
+                "model": "openai/gpt-4o",
+                "object": "chat.completion"
+            }"#,
+        );
+        let logprobs = response.logprobs().expect("logprobs present");
+        assert_eq!(logprobs.tokens().len(), 2);
+        assert_eq!(logprobs.tokens()[0].bytes.as_deref(), Some(&b"Hi"[..]));
+        assert_eq!(logprobs.tokens()[0].alternatives()[1].token, "Hello");
+        assert!((logprobs.total_logprob() + 0.4).abs() < 1e-6);
+        assert!((logprobs.perplexity().unwrap() - 0.2f64.exp()).abs() < 1e-6);
+        assert!((logprobs.tokens()[1].probability() - (-0.3f64).exp()).abs() < 1e-6);
+        assert!(logprobs.refusal.is_none());
+
+        let mut accumulator = ChatCompletionAccumulator::new();
+        for (token, logprob) in [("Hi", -0.1), (" there", -0.3)] {
+            let chunk: ChatCompletionChunk = serde_json::from_value(json!({
+                "id": "gen-1",
+                "choices": [{
+                    "index": 0,
+                    "delta": {"content": token},
+                    "logprobs": {"content": [{"token": token, "logprob": logprob}]}
+                }],
+                "created": 0,
+                "model": "openai/gpt-4o",
+                "object": "chat.completion.chunk"
+            }))
+            .unwrap();
+            accumulator.push(chunk);
+        }
+        let streamed = accumulator.finish().unwrap();
+        let tokens: Vec<_> = streamed
+            .logprobs()
+            .unwrap()
+            .tokens()
+            .iter()
+            .map(|t| t.token.as_str())
+            .collect();
+        assert_eq!(tokens, ["Hi", " there"]);
+        assert!((streamed.logprobs().unwrap().total_logprob() + 0.4).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_delta_accumulator_merge_rules() {
+        use crate::types::chat::{ChatCompletionChunk, DeltaAccumulator};
+
+        let chunk = |choice: serde_json::Value, usage: Option<serde_json::Value>| {
+            serde_json::from_value::<ChatCompletionChunk>(json!({
+                "id": "gen-1",
+                "choices": [choice],
+                "created": 0,
+                "model": "openai/gpt-4o",
+                "object": "chat.completion.chunk",
+                "usage": usage
+            }))
+            .unwrap()
+        };
+
+        let mut delta = DeltaAccumulator::new();
+        delta.push_chunk(chunk(
+            json!({"index": 0, "delta": {"role": "assistant", "content": "Checking", "reasoning": "Need "}}),
+            None,
+        ));
+        delta.push_chunk(chunk(
+            json!({"index": 0, "delta": {"content": " now", "reasoning": "weather", "tool_calls": [
+                {"index": 1, "id": "call_b", "type": "function", "function": {"name": "time", "arguments": ""}},
+                {"index": 0, "function": {"arguments": "{\"city\":"}}
+            ]}}),
+            None,
+        ));
+        assert!(!delta.is_finished());
+        delta.push_chunk(chunk(
+            json!({"index": 0, "delta": {"tool_calls": [
+                {"index": 0, "id": "call_a", "type": "function", "function": {"name": "weather", "arguments": "\"Paris\"}"}}
+            ]}, "finish_reason": "tool_calls"}),
+            Some(json!({"prompt_tokens": 5, "completion_tokens": 7, "total_tokens": 12})),
+        ));
+
+        assert_eq!(delta.role(), Some("assistant"));
+        assert_eq!(delta.content(), "Checking now");
+        assert_eq!(delta.reasoning(), Some("Need weather"));
+        assert_eq!(delta.finish_reason(), Some("tool_calls"));
+        assert!(delta.is_finished());
+        assert_eq!(delta.usage().unwrap().total_tokens, 12);
+        let ids: Vec<_> = delta.tool_calls().map(|c| c.id.as_deref()).collect();
+        assert_eq!(ids, [Some("call_a"), Some("call_b")]);
+
+        let message = delta.into_message().unwrap();
+        let calls = message.tool_calls.unwrap();
+        assert_eq!(calls[0].function_call.name, "weather");
+        assert_eq!(calls[0].function_call.arguments, r#"{"city":"Paris"}"#);
+        assert_eq!(calls[1].function_call.arguments, "");
+
+        let mut incomplete = DeltaAccumulator::new();
+        incomplete.push_chunk(chunk(
+            json!({"index": 0, "delta": {"tool_calls": [{"index": 0, "function": {"arguments": "{}"}}]}}),
+            None,
+        ));
+        assert!(matches!(
+            incomplete.into_message(),
+            Err(crate::error::Error::StreamingError(_))
+        ));
+    }
 }
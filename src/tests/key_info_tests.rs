@@ -28,6 +28,10 @@ mod tests {
                 limit: None,
                 limit_remaining: None,
                 usage: None,
+                usage_daily: None,
+                usage_weekly: None,
+                usage_monthly: None,
+                limit_reset: None,
                 is_free_tier: None,
                 rate_limit: None,
             },
@@ -43,6 +47,10 @@ mod tests {
                 limit: Some(100.0),
                 limit_remaining: Some(42.5),
                 usage: None,
+                usage_daily: None,
+                usage_weekly: None,
+                usage_monthly: None,
+                limit_reset: None,
                 is_free_tier: None,
                 rate_limit: None,
             },
@@ -86,6 +94,10 @@ mod tests {
                 limit: None,
                 limit_remaining: None,
                 usage: None,
+                usage_daily: None,
+                usage_weekly: None,
+                usage_monthly: None,
+                limit_reset: None,
                 is_free_tier: None,
                 rate_limit: None,
             },
@@ -108,6 +120,10 @@ mod tests {
                 limit: Some(50.0),
                 limit_remaining: Some(25.0),
                 usage: Some(25.0),
+                usage_daily: None,
+                usage_weekly: None,
+                usage_monthly: None,
+                limit_reset: None,
                 is_free_tier: Some(false),
                 rate_limit: None,
             },
@@ -201,6 +217,10 @@ mod tests {
                 limit: Some(100.0),
                 limit_remaining: Some(50.0),
                 usage: Some(50.0),
+                usage_daily: None,
+                usage_weekly: None,
+                usage_monthly: None,
+                limit_reset: None,
                 is_free_tier: Some(false),
                 rate_limit: Some(RateLimitInfo {
                     requests: Some(100),
@@ -296,6 +316,49 @@ mod tests {
         assert!(!response.is_free_tier());
     }
 
+    #[tokio::test]
+    async fn test_current_key_wiremock_happy_path() {
+        use crate::api::key_info::KeyInfoApi;
+        use wiremock::{matchers, Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+
+        let body = serde_json::json!({
+            "data": {
+                "label": "sk-or-v1-abc...xyz",
+                "limit": 20.0,
+                "limit_reset": "monthly",
+                "usage": 12.5,
+                "usage_daily": 0.5,
+                "usage_weekly": 3.0,
+                "usage_monthly": 12.5,
+                "is_free_tier": false,
+                "rate_limit": {"requests": 200, "interval": "10s"}
+            }
+        });
+
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/api/v1/key"))
+            .and(matchers::header_exists("authorization"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&body))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let config = ClientConfig {
+            base_url: Url::parse(&format!("{}/api/v1/", mock_server.uri())).unwrap(),
+            ..test_client_config()
+        };
+
+        let api = KeyInfoApi::new(reqwest::Client::new(), &config).unwrap();
+        let key = api.current_key().await.unwrap();
+
+        assert_eq!(key.label.as_deref(), Some("sk-or-v1-abc...xyz"));
+        assert_eq!(key.usage_weekly, Some(3.0));
+        assert_eq!(key.limit_reset.as_deref(), Some("monthly"));
+        assert_eq!(key.remaining(), Some(7.5));
+    }
+
     #[tokio::test]
     async fn test_get_key_info_wiremock_401_unauthorized() {
         use crate::api::key_info::KeyInfoApi;
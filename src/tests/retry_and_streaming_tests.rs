@@ -54,6 +54,7 @@ mod tests {
             retry_on_status_codes: vec![429],
             total_timeout: Duration::from_secs(10),
             max_retry_interval: Duration::from_secs(30),
+            ..Default::default()
         };
 
         let client = reqwest::Client::new();
@@ -99,6 +100,7 @@ mod tests {
             retry_on_status_codes: vec![429],
             total_timeout: Duration::from_secs(5),
             max_retry_interval: Duration::from_secs(30),
+            ..Default::default()
         };
 
         let client = reqwest::Client::new();
@@ -151,6 +153,7 @@ mod tests {
             retry_on_status_codes: vec![429],
             total_timeout: Duration::from_secs(15),
             max_retry_interval: Duration::from_secs(30),
+            ..Default::default()
         };
 
         let client = reqwest::Client::new();
@@ -204,6 +207,7 @@ mod tests {
             retry_on_status_codes: vec![429],
             total_timeout: Duration::from_secs(5),
             max_retry_interval: Duration::from_secs(30),
+            ..Default::default()
         };
 
         let client = reqwest::Client::new();
@@ -252,6 +256,7 @@ mod tests {
             retry_on_status_codes: vec![429, 500, 502, 503, 504],
             total_timeout: Duration::from_secs(5),
             max_retry_interval: Duration::from_secs(30),
+            ..Default::default()
         };
 
         let client = reqwest::Client::new();
@@ -299,6 +304,7 @@ mod tests {
             retry_on_status_codes: vec![429, 500, 502, 503, 504],
             total_timeout: Duration::from_secs(5),
             max_retry_interval: Duration::from_secs(30),
+            ..Default::default()
         };
 
         let client = reqwest::Client::new();
@@ -342,6 +348,7 @@ mod tests {
             retry_on_status_codes: vec![503],
             total_timeout: Duration::from_secs(10),
             max_retry_interval: Duration::from_secs(30),
+            ..Default::default()
         };
 
         let client = reqwest::Client::new();
@@ -385,6 +392,7 @@ mod tests {
             retry_on_status_codes: vec![500],
             total_timeout: Duration::from_secs(10),
             max_retry_interval: Duration::from_secs(30),
+            ..Default::default()
         };
 
         let client = reqwest::Client::new();
@@ -579,6 +587,7 @@ mod tests {
             user_id: None,
             retry_config: RetryConfig::default(),
             max_response_bytes: 10 * 1024 * 1024,
+            ..Default::default()
         };
 
         let client = reqwest::Client::new();
@@ -606,6 +615,7 @@ mod tests {
             min_p: None,
             top_a: None,
             seed: None,
+            n: None,
             stop: None,
             logit_bias: None,
             logprobs: None,
@@ -616,6 +626,7 @@ mod tests {
             debug: None,
             plugins: None,
             reasoning: None,
+            extra_body: None,
         };
 
         let mut stream = api.chat_completion_stream(request);
@@ -643,6 +654,495 @@ mod tests {
         assert_eq!(chunks[1].choices[0].finish_reason.as_deref(), Some("stop"));
     }
 
+    #[tokio::test]
+    async fn test_streaming_enforces_max_response_bytes() {
+        use crate::api::chat::ChatApi;
+        use crate::types::chat::{ChatCompletionRequest, ChatRole, Message};
+        use futures::StreamExt;
+
+        let mock_server = MockServer::start().await;
+        let chunk = concat!(
+            "data: {\"id\":\"c1\",\"object\":\"chat.completion.chunk\",\"created\":1700000000,",
+            "\"model\":\"openai/gpt-4\",\"choices\":[{\"index\":0,\"delta\":{\"content\":\"Hi\"},",
+            "\"finish_reason\":null}]}\n\n",
+        );
+        Mock::given(matchers::method("POST"))
+            .and(matchers::path("/chat/completions"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-type", "text/event-stream")
+                    .set_body_string(chunk.repeat(4) + "data: [DONE]\n\n"),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let mut config = crate::tests::test_helpers::test_client_config();
+        config.base_url = url::Url::parse(&format!("{}/", mock_server.uri())).unwrap();
+        config.max_response_bytes = chunk.len() * 2;
+        let api = ChatApi::new(reqwest::Client::new(), &config).unwrap();
+
+        let request = ChatCompletionRequest {
+            model: "openai/gpt-4".to_string(),
+            messages: vec![Message::text(ChatRole::User, "hi")],
+            ..Default::default()
+        };
+        let results: Vec<_> = api.chat_completion_stream(request).collect().await;
+
+        assert_eq!(results.iter().filter(|result| result.is_ok()).count(), 2);
+        assert!(matches!(
+            results.last(),
+            Some(Err(crate::error::Error::ResponseTooLarge(_, max))) if *max == chunk.len() * 2
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_streaming_error_keeps_provider_error_details() {
+        use crate::api::chat::ChatApi;
+        use crate::error::Error;
+        use crate::types::chat::{ChatCompletionRequest, ChatRole, Message};
+        use futures::StreamExt;
+        use serde_json::json;
+
+        let mock_server = MockServer::start().await;
+        Mock::given(matchers::method("POST"))
+            .and(matchers::path("/chat/completions"))
+            .and(matchers::body_partial_json(json!({"model": "anthropic/claude-3.5-sonnet"})))
+            .respond_with(ResponseTemplate::new(400).set_body_json(json!({
+                "error": {
+                    "code": 400,
+                    "message": "Provider returned error",
+                    "metadata": {
+                        "provider_name": "Anthropic",
+                        "raw": "{\"type\":\"invalid_request_error\",\"message\":\"max_tokens too large\"}"
+                    }
+                }
+            })))
+            .mount(&mock_server)
+            .await;
+        Mock::given(matchers::method("POST"))
+            .and(matchers::path("/chat/completions"))
+            .and(matchers::body_partial_json(
+                json!({"model": "openai/gpt-4"}),
+            ))
+            .respond_with(ResponseTemplate::new(429).set_body_string("slow down"))
+            .mount(&mock_server)
+            .await;
+
+        let mut config = crate::tests::test_helpers::test_client_config();
+        config.base_url = url::Url::parse(&format!("{}/", mock_server.uri())).unwrap();
+        let api = ChatApi::new(reqwest::Client::new(), &config).unwrap();
+        let request = |model: &str| ChatCompletionRequest {
+            model: model.to_string(),
+            messages: vec![Message::text(ChatRole::User, "hi")],
+            ..Default::default()
+        };
+
+        let results: Vec<_> = api
+            .chat_completion_stream(request("anthropic/claude-3.5-sonnet"))
+            .collect()
+            .await;
+        assert_eq!(results.len(), 1);
+        let error = results.into_iter().next().unwrap().unwrap_err();
+        assert!(matches!(error, Error::ApiError { code: 400, .. }));
+        let provider = error.provider_error().expect("provider details are kept");
+        assert_eq!(provider["provider_name"], "Anthropic");
+        assert_eq!(provider["raw"]["message"], "max_tokens too large");
+        assert!(error.raw_body().is_some());
+
+        let results: Vec<_> = api
+            .chat_completion_stream(request("openai/gpt-4"))
+            .collect()
+            .await;
+        assert!(matches!(
+            results.as_slice(),
+            [Err(Error::RateLimitExceeded(_))]
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_collect_full_assembles_content_tool_calls_and_usage() {
+        use crate::api::chat::{ChatApi, ChatCompletionStream};
+        use crate::types::chat::{ChatCompletionRequest, ChatRole, Message};
+
+        let mock_server = MockServer::start().await;
+        let sse_body = concat!(
+            "data: {\"id\":\"c1\",\"object\":\"chat.completion.chunk\",\"created\":1700000000,",
+            "\"model\":\"openai/gpt-4\",\"choices\":[{\"index\":0,\"delta\":{\"role\":\"assistant\",",
+            "\"content\":\"Checking\",\"tool_calls\":[{\"index\":0,\"id\":\"call-1\",\"type\":\"function\",",
+            "\"function\":{\"name\":\"get_weather\",\"arguments\":\"{\\\"city\\\"\"}}]},",
+            "\"finish_reason\":null}]}\n\n",
+            ": OPENROUTER PROCESSING\n\n",
+            "data: {\"id\":\"c1\",\"object\":\"chat.completion.chunk\",\"created\":1700000000,",
+            "\"model\":\"openai/gpt-4\",\"choices\":[{\"index\":0,\"delta\":{\"content\":\" weather\",",
+            "\"tool_calls\":[{\"index\":0,\"function\":{\"arguments\":\":\\\"Oslo\\\"}\"}}]},",
+            "\"finish_reason\":\"tool_calls\"}]}\n\n",
+            "data: {\"id\":\"c1\",\"object\":\"chat.completion.chunk\",\"created\":1700000000,",
+            "\"model\":\"openai/gpt-4\",\"choices\":[],",
+            "\"usage\":{\"prompt_tokens\":5,\"completion_tokens\":7,\"total_tokens\":12}}\n\n",
+            "data: [DONE]\n\n"
+        );
+
+        Mock::given(matchers::method("POST"))
+            .and(matchers::path("/chat/completions"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-type", "text/event-stream")
+                    .set_body_string(sse_body),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let mut config = crate::tests::test_helpers::test_client_config();
+        config.base_url = url::Url::parse(&format!("{}/", mock_server.uri())).unwrap();
+        let api = ChatApi::new(reqwest::Client::new(), &config).unwrap();
+
+        let request = ChatCompletionRequest {
+            model: "openai/gpt-4".to_string(),
+            messages: vec![Message::text(ChatRole::User, "weather in Oslo?")],
+            ..Default::default()
+        };
+
+        let response = api
+            .chat_completion_stream(request)
+            .collect_full()
+            .await
+            .unwrap();
+
+        assert_eq!(response.id, "c1");
+        assert_eq!(response.object, "chat.completion");
+        assert_eq!(response.text(), Some("Checking weather"));
+        assert_eq!(response.finish_reason(), Some("tool_calls"));
+        assert_eq!(
+            response.first_choice().unwrap().message.role,
+            ChatRole::Assistant
+        );
+
+        let calls = response.tool_calls();
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].id.as_str(), "call-1");
+        assert_eq!(calls[0].function_call.name, "get_weather");
+        assert_eq!(calls[0].function_call.arguments, r#"{"city":"Oslo"}"#);
+
+        assert_eq!(response.usage.as_ref().unwrap().total_tokens, 12);
+    }
+
+    #[tokio::test]
+    async fn test_collect_full_on_empty_stream_is_an_error() {
+        use crate::api::chat::ChatCompletionStream;
+        use crate::error::Error;
+
+        let empty = futures::stream::iter(Vec::<
+            crate::error::Result<crate::types::chat::ChatCompletionChunk>,
+        >::new());
+        assert!(matches!(
+            empty.collect_full().await,
+            Err(Error::StreamingError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_with_stats_emits_chunks_then_summary() {
+        use crate::api::chat::ChatCompletionStream;
+        use crate::types::chat::{ChatCompletionChunk, StreamEvent};
+        use futures::StreamExt;
+
+        let chunk = |value: serde_json::Value| -> crate::error::Result<ChatCompletionChunk> {
+            Ok(serde_json::from_value(value).unwrap())
+        };
+        let chunks = vec![
+            chunk(serde_json::json!({
+                "id": "c1", "object": "chat.completion.chunk", "created": 0, "model": "m",
+                "choices": [{"index": 0, "delta": {"content": "Hello, world"}}]
+            })),
+            chunk(serde_json::json!({
+                "id": "c1", "object": "chat.completion.chunk", "created": 0, "model": "m",
+                "choices": [],
+                "usage": {"prompt_tokens": 5, "completion_tokens": 4, "total_tokens": 9, "cost": 0.002}
+            })),
+        ];
+
+        let events: Vec<_> = futures::stream::iter(chunks).with_stats().collect().await;
+        assert_eq!(events.len(), 3);
+        assert!(matches!(events[0], Ok(StreamEvent::Chunk(_))));
+        assert!(matches!(events[1], Ok(StreamEvent::Chunk(_))));
+        let Ok(StreamEvent::Summary(summary)) = &events[2] else {
+            panic!("expected a summary, got {:?}", events[2]);
+        };
+        assert_eq!(summary.chunks, 2);
+        assert_eq!(summary.estimated_tokens, 3);
+        assert_eq!(summary.prompt_tokens, Some(5));
+        assert_eq!(summary.completion_tokens, Some(4));
+        assert_eq!(summary.cost, Some(0.002));
+    }
+
+    #[tokio::test]
+    async fn test_stream_reasoning_channel_is_summarized_or_stripped() {
+        use crate::api::chat::{ChatApi, ChatCompletionStream};
+        use crate::types::chat::{ChatCompletionRequest, ChatRole, Message, StreamEvent};
+        use futures::TryStreamExt;
+
+        let mock_server = MockServer::start().await;
+        let sse_body = concat!(
+            "data: {\"id\":\"c1\",\"object\":\"chat.completion.chunk\",\"created\":0,",
+            "\"model\":\"deepseek/deepseek-r1\",\"choices\":[{\"index\":0,\"delta\":{",
+            "\"role\":\"assistant\",\"reasoning\":\"Two plus two.\",",
+            "\"reasoning_details\":[{\"type\":\"reasoning.text\",\"text\":\"Two plus two.\"}]}}]}\n\n",
+            "data: {\"id\":\"c1\",\"object\":\"chat.completion.chunk\",\"created\":0,",
+            "\"model\":\"deepseek/deepseek-r1\",\"choices\":[{\"index\":0,\"delta\":{",
+            "\"content\":\"4\"},\"finish_reason\":\"stop\"}]}\n\n",
+            "data: [DONE]\n\n"
+        );
+        Mock::given(matchers::method("POST"))
+            .and(matchers::path("/chat/completions"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-type", "text/event-stream")
+                    .set_body_string(sse_body),
+            )
+            .expect(3)
+            .mount(&mock_server)
+            .await;
+
+        let mut config = crate::tests::test_helpers::test_client_config();
+        config.base_url = url::Url::parse(&format!("{}/", mock_server.uri())).unwrap();
+        let request = ChatCompletionRequest {
+            model: "deepseek/deepseek-r1".to_string(),
+            messages: vec![Message::text(ChatRole::User, "2+2?")],
+            ..Default::default()
+        };
+
+        let api = ChatApi::new(reqwest::Client::new(), &config).unwrap();
+        let response = api
+            .chat_completion_stream(request.clone())
+            .collect_full()
+            .await
+            .unwrap();
+        let message = &response.first_choice().unwrap().message;
+        assert_eq!(message.reasoning.as_deref(), Some("Two plus two."));
+        assert_eq!(message.reasoning_details.as_ref().map(Vec::len), Some(1));
+
+        let events: Vec<_> = api
+            .chat_completion_stream(request.clone())
+            .with_stats()
+            .try_collect()
+            .await
+            .unwrap();
+        let Some(StreamEvent::Summary(summary)) = events.last() else {
+            panic!("expected a summary");
+        };
+        assert_eq!(summary.estimated_reasoning_tokens, 4);
+        assert_eq!(summary.estimated_tokens, 4);
+
+        config.strip_reasoning = true;
+        let api = ChatApi::new(reqwest::Client::new(), &config).unwrap();
+        let response = api
+            .chat_completion_stream(request)
+            .collect_full()
+            .await
+            .unwrap();
+        let message = &response.first_choice().unwrap().message;
+        assert_eq!(message.reasoning, None);
+        assert_eq!(message.reasoning_details, None);
+        assert_eq!(response.text(), Some("4"));
+    }
+
+    #[tokio::test]
+    async fn test_take_until_drops_stream_when_predicate_fires() {
+        use crate::api::chat::ChatCompletionStream;
+        use crate::types::chat::ChatCompletionChunk;
+        use futures::StreamExt;
+        use std::sync::atomic::{AtomicBool, Ordering};
+        use std::sync::Arc;
+
+        struct DropFlag(Arc<AtomicBool>);
+        impl Drop for DropFlag {
+            fn drop(&mut self) {
+                self.0.store(true, Ordering::SeqCst);
+            }
+        }
+
+        let chunk = |content: &str| -> crate::error::Result<ChatCompletionChunk> {
+            Ok(serde_json::from_value(serde_json::json!({
+                "id": "c1", "object": "chat.completion.chunk", "created": 0, "model": "m",
+                "choices": [{"index": 0, "delta": {"content": content}}]
+            }))
+            .unwrap())
+        };
+        let dropped = Arc::new(AtomicBool::new(false));
+        let flag = DropFlag(dropped.clone());
+        let upstream = futures::stream::iter(vec![
+            chunk("{\"answer\": "),
+            chunk("42}"),
+            chunk(" and some trailing prose"),
+        ])
+        .map(move |item| {
+            let _ = &flag;
+            item
+        })
+        .chain(futures::stream::poll_fn(
+            |_| -> std::task::Poll<Option<_>> { panic!("polled after the predicate fired") },
+        ));
+
+        let mut seen = Vec::new();
+        let mut stream = Box::pin(ChatCompletionStream::take_until(upstream, |progress| {
+            seen.push(progress.text.to_string());
+            progress.text.ends_with('}')
+        }));
+        assert!(stream.next().await.unwrap().is_ok());
+        assert!(!dropped.load(Ordering::SeqCst));
+        assert!(stream.next().await.unwrap().is_ok());
+        assert!(dropped.load(Ordering::SeqCst));
+        assert!(stream.next().await.is_none());
+        drop(stream);
+        assert_eq!(seen, ["{\"answer\": ", "{\"answer\": 42}"]);
+    }
+
+    #[tokio::test]
+    async fn test_tool_call_events_finish_calls_when_arguments_complete() {
+        use crate::api::chat::ChatCompletionStream;
+        use crate::types::chat::ChatCompletionChunk;
+        use crate::types::tool_events::ToolStreamEvent;
+        use futures::TryStreamExt;
+
+        let chunk = |delta: serde_json::Value| -> crate::error::Result<ChatCompletionChunk> {
+            Ok(serde_json::from_value(serde_json::json!({
+                "id": "c1", "object": "chat.completion.chunk", "created": 0, "model": "m",
+                "choices": [{"index": 0, "delta": delta}]
+            }))
+            .unwrap())
+        };
+        let chunks = vec![
+            chunk(serde_json::json!({"content": "Checking."})),
+            chunk(serde_json::json!({"tool_calls": [{
+                "index": 0, "id": "call-1", "type": "function",
+                "function": {"name": "lookup", "arguments": "{\"q\": \"ru"}
+            }]})),
+            chunk(serde_json::json!({"tool_calls": [{
+                "index": 0, "function": {"arguments": "st\"}"}
+            }]})),
+        ];
+
+        let events: Vec<_> = futures::stream::iter(chunks)
+            .tool_call_events()
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(events.len(), 5);
+        assert_eq!(
+            events[0],
+            ToolStreamEvent::Content {
+                choice: 0,
+                text: "Checking.".into()
+            }
+        );
+        assert!(
+            matches!(&events[1], ToolStreamEvent::ToolCallStarted { name, .. } if name == "lookup")
+        );
+        assert_eq!(
+            events[4],
+            ToolStreamEvent::ToolCallFinished {
+                id: "call-1".into(),
+                arguments: serde_json::json!({"q": "rust"})
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_with_stats_stops_on_error_without_summary() {
+        use crate::api::chat::ChatCompletionStream;
+        use crate::error::Error;
+        use futures::StreamExt;
+
+        let failing =
+            futures::stream::iter(vec![Err::<crate::types::chat::ChatCompletionChunk, _>(
+                Error::StreamingError("boom".into()),
+            )]);
+        let events: Vec<_> = failing.with_stats().collect().await;
+        assert_eq!(events.len(), 1);
+        assert!(matches!(events[0], Err(Error::StreamingError(_))));
+    }
+
+    #[test]
+    fn test_latency_percentiles_nearest_rank() {
+        use crate::types::chat::LatencyPercentiles;
+
+        assert_eq!(LatencyPercentiles::from_samples(&[]), None);
+
+        let samples: Vec<_> = (1..=10).rev().map(Duration::from_millis).collect();
+        let percentiles = LatencyPercentiles::from_samples(&samples).unwrap();
+        assert_eq!(percentiles.p50, Duration::from_millis(5));
+        assert_eq!(percentiles.p90, Duration::from_millis(9));
+        assert_eq!(percentiles.p99, Duration::from_millis(10));
+        assert_eq!(percentiles.max, Duration::from_millis(10));
+    }
+
+    #[tokio::test]
+    async fn test_metrics_recorder_receives_stream_summary() {
+        use crate::types::chat::StreamSummary;
+        use crate::utils::metrics::MetricsRecorder;
+        use futures::StreamExt;
+        use std::sync::Mutex;
+
+        #[derive(Debug, Default)]
+        struct Recorded(Mutex<Vec<(String, StreamSummary)>>);
+
+        impl MetricsRecorder for Recorded {
+            fn record_stream(&self, model: &str, summary: &StreamSummary) {
+                self.0
+                    .lock()
+                    .unwrap()
+                    .push((model.to_string(), summary.clone()));
+            }
+        }
+
+        let mock_server = MockServer::start().await;
+        let sse_body = concat!(
+            "data: {\"id\":\"c1\",\"object\":\"chat.completion.chunk\",\"created\":0,",
+            "\"model\":\"openai/gpt-4\",\"choices\":[{\"index\":0,\"delta\":{\"content\":\"Hello\"}}]}\n\n",
+            "data: {\"id\":\"c1\",\"object\":\"chat.completion.chunk\",\"created\":0,",
+            "\"model\":\"openai/gpt-4\",\"choices\":[{\"index\":0,\"delta\":{\"content\":\" world\"}}],",
+            "\"usage\":{\"prompt_tokens\":3,\"completion_tokens\":2,\"total_tokens\":5}}\n\n",
+            "data: [DONE]\n\n"
+        );
+        Mock::given(matchers::method("POST"))
+            .and(matchers::path("/api/v1/chat/completions"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-type", "text/event-stream")
+                    .set_body_string(sse_body),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let recorder = Arc::new(Recorded::default());
+        let mut config = crate::tests::test_helpers::test_client_config();
+        config.base_url = url::Url::parse(&format!("{}/api/v1/", mock_server.uri())).unwrap();
+        config.metrics_recorder = Some(recorder.clone());
+        let api = crate::api::chat::ChatApi::new(reqwest::Client::new(), &config).unwrap();
+
+        let request = crate::types::chat::ChatCompletionRequest {
+            model: "openai/gpt-4".to_string(),
+            messages: vec![crate::types::chat::Message::text(
+                crate::types::chat::ChatRole::User,
+                "hi",
+            )],
+            ..Default::default()
+        };
+        let chunks: Vec<_> = api.chat_completion_stream(request).collect().await;
+        assert_eq!(chunks.len(), 2);
+
+        let recorded = recorder.0.lock().unwrap();
+        assert_eq!(recorded.len(), 1);
+        let (model, summary) = &recorded[0];
+        assert_eq!(model, "openai/gpt-4");
+        assert_eq!(summary.chunks, 2);
+        assert_eq!(summary.completion_tokens, Some(2));
+        assert!(summary.time_to_first_token.is_some());
+        assert!(summary.inter_token_latency.is_some());
+        assert!(summary.elapsed >= summary.time_to_first_token.unwrap());
+    }
+
     #[tokio::test]
     async fn test_streaming_validation_error_before_network_call() {
         use crate::api::chat::ChatApi;
@@ -660,6 +1160,7 @@ mod tests {
             user_id: None,
             retry_config: RetryConfig::default(),
             max_response_bytes: 10 * 1024 * 1024,
+            ..Default::default()
         };
 
         let client = reqwest::Client::new();
@@ -688,6 +1189,7 @@ mod tests {
             min_p: None,
             top_a: None,
             seed: None,
+            n: None,
             stop: None,
             logit_bias: None,
             logprobs: None,
@@ -698,6 +1200,7 @@ mod tests {
             debug: None,
             plugins: None,
             reasoning: None,
+            extra_body: None,
         };
 
         let mut stream = api.chat_completion_stream(request);
@@ -758,6 +1261,7 @@ mod tests {
             user_id: None,
             retry_config: RetryConfig::default(),
             max_response_bytes: 10 * 1024 * 1024,
+            ..Default::default()
         };
 
         let client = reqwest::Client::new();
@@ -810,6 +1314,7 @@ mod tests {
             user_id: None,
             retry_config: RetryConfig::default(),
             max_response_bytes: 10 * 1024 * 1024,
+            ..Default::default()
         };
 
         let client = reqwest::Client::new();
@@ -849,6 +1354,7 @@ mod tests {
             user_id: None,
             retry_config: RetryConfig::default(),
             max_response_bytes: 10 * 1024 * 1024,
+            ..Default::default()
         };
 
         let client = reqwest::Client::new();
@@ -886,6 +1392,7 @@ mod tests {
             user_id: None,
             retry_config: RetryConfig::default(),
             max_response_bytes: 10 * 1024 * 1024,
+            ..Default::default()
         };
 
         let client = reqwest::Client::new();
@@ -908,4 +1415,138 @@ mod tests {
             ),
         }
     }
+
+    // =========================================================================
+    // Per-request deadlines via RequestOptions
+    // =========================================================================
+
+    fn deadline_test_api(mock_server: &MockServer) -> crate::api::chat::ChatApi {
+        let mut config = crate::tests::test_helpers::test_client_config();
+        config.base_url = url::Url::parse(&format!("{}/api/v1/", mock_server.uri())).unwrap();
+        config.retry_config.initial_backoff_ms = 1;
+        config.retry_config.max_backoff_ms = 1;
+        crate::api::chat::ChatApi::new(reqwest::Client::new(), &config).unwrap()
+    }
+
+    fn deadline_test_request() -> crate::types::chat::ChatCompletionRequest {
+        crate::types::chat::ChatCompletionRequest {
+            model: "openai/gpt-4".to_string(),
+            messages: vec![crate::types::chat::Message::text(
+                crate::types::chat::ChatRole::User,
+                "hi",
+            )],
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_request_timeout_override_bounds_call_and_retries() {
+        use crate::api::RequestOptions;
+
+        let mock_server = MockServer::start().await;
+        Mock::given(matchers::method("POST"))
+            .and(matchers::path("/api/v1/chat/completions"))
+            .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_secs(5)))
+            .mount(&mock_server)
+            .await;
+
+        let api = deadline_test_api(&mock_server);
+        let options = RequestOptions::new().with_timeout(Duration::from_millis(200));
+        let started = Instant::now();
+        let result = api
+            .chat_completion_with_options(deadline_test_request(), &options)
+            .await;
+
+        assert!(
+            matches!(result, Err(crate::error::Error::TimeoutError(_))),
+            "{result:?}"
+        );
+        assert!(started.elapsed() < Duration::from_secs(2));
+    }
+
+    #[tokio::test]
+    async fn test_stream_first_token_timeout() {
+        use crate::api::RequestOptions;
+        use futures::StreamExt;
+
+        let mock_server = MockServer::start().await;
+        Mock::given(matchers::method("POST"))
+            .and(matchers::path("/api/v1/chat/completions"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-type", "text/event-stream")
+                    .set_body_string("data: [DONE]\n\n")
+                    .set_delay(Duration::from_secs(5)),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let api = deadline_test_api(&mock_server);
+        for options in [
+            RequestOptions::new().with_first_token_timeout(Duration::from_millis(200)),
+            RequestOptions::new()
+                .with_first_token_timeout(Duration::from_secs(30))
+                .with_timeout(Duration::from_millis(200)),
+        ] {
+            let started = Instant::now();
+            let mut stream =
+                api.chat_completion_stream_with_options(deadline_test_request(), &options);
+            let first = stream.next().await.expect("stream yields an error");
+            assert!(
+                matches!(first, Err(crate::error::Error::TimeoutError(_))),
+                "{first:?}"
+            );
+            assert!(stream.next().await.is_none());
+            assert!(started.elapsed() < Duration::from_secs(2));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_stream_options_reject_reserved_headers() {
+        use crate::api::RequestOptions;
+        use futures::StreamExt;
+
+        let mock_server = MockServer::start().await;
+        let api = deadline_test_api(&mock_server);
+        let options = RequestOptions::new().with_header("Content-Type", "text/plain");
+        let mut stream = api.chat_completion_stream_with_options(deadline_test_request(), &options);
+        assert!(matches!(
+            stream.next().await,
+            Some(Err(crate::error::Error::ConfigError(_)))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_retries_reuse_generated_idempotency_key() {
+        let mock_server = MockServer::start().await;
+        Mock::given(matchers::method("POST"))
+            .and(matchers::path("/api/v1/chat/completions"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(matchers::method("POST"))
+            .and(matchers::path("/api/v1/chat/completions"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": "gen-1",
+                "choices": [{"message": {"role": "assistant", "content": "hi"}}],
+                "object": "chat.completion",
+                "created": 0,
+                "model": "openai/gpt-4"
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let api = deadline_test_api(&mock_server);
+        api.chat_completion(deadline_test_request()).await.unwrap();
+
+        let requests = mock_server.received_requests().await.unwrap();
+        assert_eq!(requests.len(), 2);
+        let keys: Vec<_> = requests
+            .iter()
+            .map(|r| r.headers.get("idempotency-key").cloned())
+            .collect();
+        assert!(keys[0].is_some());
+        assert_eq!(keys[0], keys[1]);
+    }
 }
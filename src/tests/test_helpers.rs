@@ -1,6 +1,6 @@
 //! Shared test helper utilities to reduce copy-pasted test setup code.
 
-use crate::client::{ApiConfig, ClientConfig, RetryConfig, SecureApiKey};
+use crate::client::{ApiConfig, ClientConfig, ConnectionConfig, RetryConfig, SecureApiKey};
 use std::time::Duration;
 use url::Url;
 
@@ -15,12 +15,29 @@ pub fn test_client_config() -> ClientConfig {
     ClientConfig {
         api_key: Some(SecureApiKey::new(TEST_API_KEY).unwrap()),
         base_url: Url::parse(TEST_BASE_URL).unwrap(),
+        fallback_base_urls: Vec::new(),
+        failover: Default::default(),
         timeout: Duration::from_secs(30),
         http_referer: None,
         site_title: None,
         user_id: None,
+        default_user: None,
+        default_params: Default::default(),
         retry_config: RetryConfig::default(),
         max_response_bytes: 10 * 1024 * 1024,
+        budget_guard: None,
+        cost_tracking: None,
+        connection: ConnectionConfig::default(),
+        compression: Default::default(),
+        default_headers: reqwest::header::HeaderMap::new(),
+        key_provider: None,
+        metrics_recorder: None,
+        diagnostics: None,
+        truncation: None,
+        message_normalization: false,
+        strip_reasoning: false,
+        validation_policy: Default::default(),
+        validation_mode: Default::default(),
     }
 }
 
@@ -30,12 +47,29 @@ pub fn test_client_config_with_key(api_key: &str) -> ClientConfig {
     ClientConfig {
         api_key: Some(SecureApiKey::new(api_key).unwrap()),
         base_url: Url::parse(TEST_BASE_URL).unwrap(),
+        fallback_base_urls: Vec::new(),
+        failover: Default::default(),
         timeout: Duration::from_secs(30),
         http_referer: None,
         site_title: None,
         user_id: None,
+        default_user: None,
+        default_params: Default::default(),
         retry_config: RetryConfig::default(),
         max_response_bytes: 10 * 1024 * 1024,
+        budget_guard: None,
+        cost_tracking: None,
+        connection: ConnectionConfig::default(),
+        compression: Default::default(),
+        default_headers: reqwest::header::HeaderMap::new(),
+        key_provider: None,
+        metrics_recorder: None,
+        diagnostics: None,
+        truncation: None,
+        message_normalization: false,
+        strip_reasoning: false,
+        validation_policy: Default::default(),
+        validation_mode: Default::default(),
     }
 }
 
@@ -1,6 +1,7 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::io::Write;
 
 use crate::types::ids::ActivityId;
 use crate::types::status::{CancellationStatus, StreamingStatus};
@@ -182,6 +183,107 @@ impl ActivityData {
     }
 }
 
+/// Column order used when exporting activity data to CSV.
+///
+/// This ordering is part of the public contract: new columns are only ever
+/// appended, so downstream spreadsheets and BI pipelines can rely on it.
+pub const ACTIVITY_CSV_COLUMNS: &[&str] = &[
+    "id",
+    "created_at",
+    "model",
+    "provider",
+    "origin",
+    "user_id",
+    "total_cost",
+    "effective_cost",
+    "cache_discount",
+    "tokens_prompt",
+    "tokens_completion",
+    "total_tokens",
+    "latency",
+    "generation_time",
+    "moderation_latency",
+    "streamed",
+    "cancelled",
+    "web_search",
+    "media",
+    "reasoning",
+    "finish_reason",
+    "native_finish_reason",
+    "upstream_id",
+    "http_referer",
+];
+
+impl ActivityData {
+    /// Returns the CSV field values for this entry in `ACTIVITY_CSV_COLUMNS` order.
+    pub fn csv_record(&self) -> Vec<String> {
+        fn opt<T: ToString>(value: &Option<T>) -> String {
+            value.as_ref().map(ToString::to_string).unwrap_or_default()
+        }
+
+        vec![
+            self.id.to_string(),
+            self.created_at.to_rfc3339(),
+            self.model.clone(),
+            opt(&self.provider),
+            opt(&self.origin),
+            opt(&self.user_id),
+            opt(&self.total_cost),
+            opt(&self.effective_cost),
+            opt(&self.cache_discount),
+            opt(&self.tokens_prompt),
+            opt(&self.tokens_completion),
+            opt(&self.total_tokens),
+            opt(&self.latency),
+            opt(&self.generation_time),
+            opt(&self.moderation_latency),
+            self.streamed.to_string(),
+            self.cancelled.to_string(),
+            opt(&self.web_search),
+            opt(&self.media),
+            opt(&self.reasoning),
+            opt(&self.finish_reason),
+            opt(&self.native_finish_reason),
+            opt(&self.upstream_id),
+            opt(&self.http_referer),
+        ]
+    }
+
+    /// Writes this entry as a single CSV row (including the trailing newline).
+    pub fn write_csv_row<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        write_csv_line(writer, self.csv_record().iter().map(String::as_str))
+    }
+
+    /// Writes this entry as a single JSON line (including the trailing newline).
+    pub fn write_jsonl_row<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        serde_json::to_writer(&mut *writer, self).map_err(std::io::Error::other)?;
+        writer.write_all(b"\n")
+    }
+}
+
+/// Writes the CSV header row for activity exports.
+pub fn write_activity_csv_header<W: Write>(writer: &mut W) -> std::io::Result<()> {
+    write_csv_line(writer, ACTIVITY_CSV_COLUMNS.iter().copied())
+}
+
+/// Writes one RFC 4180 CSV line, quoting fields that need it.
+fn write_csv_line<'a, W: Write>(
+    writer: &mut W,
+    fields: impl Iterator<Item = &'a str>,
+) -> std::io::Result<()> {
+    for (index, field) in fields.enumerate() {
+        if index > 0 {
+            writer.write_all(b",")?;
+        }
+        if field.contains([',', '"', '\n', '\r']) {
+            write!(writer, "\"{}\"", field.replace('"', "\"\""))?;
+        } else {
+            writer.write_all(field.as_bytes())?;
+        }
+    }
+    writer.write_all(b"\r\n")
+}
+
 /// Request parameters for activity data retrieval
 #[derive(Debug, Clone, Serialize, Default)]
 pub struct ActivityRequest {
@@ -400,6 +502,62 @@ impl ActivityResponse {
         groups
     }
 
+    /// Groups activity data by the `user` the request was tagged with.
+    ///
+    /// Entries without a user are omitted; see
+    /// [`cost_attribution`](Self::cost_attribution) for the unattributed total.
+    pub fn group_by_user(&self) -> HashMap<String, Vec<&ActivityData>> {
+        let mut groups: HashMap<String, Vec<&ActivityData>> = HashMap::new();
+        for activity in &self.data {
+            if let Some(user_id) = &activity.user_id {
+                groups.entry(user_id.clone()).or_default().push(activity);
+            }
+        }
+        groups
+    }
+
+    /// Attributes spend to individual users, most expensive first.
+    pub fn cost_attribution(&self) -> CostAttribution {
+        let total_cost = self.total_cost();
+        let mut users: Vec<UserCost> = self
+            .group_by_user()
+            .into_iter()
+            .map(|(user_id, activities)| {
+                let cost: f64 = activities.iter().filter_map(|d| d.final_cost()).sum();
+                UserCost {
+                    user_id,
+                    request_count: activities.len(),
+                    total_cost: cost,
+                    total_tokens: activities
+                        .iter()
+                        .filter_map(|d| d.total_tokens)
+                        .map(u64::from)
+                        .sum(),
+                    share_of_total: if total_cost > 0.0 {
+                        cost / total_cost * 100.0
+                    } else {
+                        0.0
+                    },
+                }
+            })
+            .collect();
+        users.sort_by(|a, b| {
+            b.total_cost
+                .total_cmp(&a.total_cost)
+                .then_with(|| a.user_id.cmp(&b.user_id))
+        });
+
+        let unattributed: Vec<&ActivityData> =
+            self.data.iter().filter(|d| d.user_id.is_none()).collect();
+
+        CostAttribution {
+            users,
+            unattributed_requests: unattributed.len(),
+            unattributed_cost: unattributed.iter().filter_map(|d| d.final_cost()).sum(),
+            total_cost,
+        }
+    }
+
     /// Returns usage statistics for a specific model
     pub fn model_stats(&self, model: &str) -> ModelUsageStats {
         let model_activities: Vec<&ActivityData> =
@@ -475,6 +633,46 @@ impl ActivityResponse {
         }
     }
 
+    /// Writes all entries as CSV, preceded by a header row.
+    ///
+    /// Columns follow `ACTIVITY_CSV_COLUMNS`; missing optional values are
+    /// written as empty fields.
+    pub fn write_csv<W: Write>(&self, mut writer: W) -> std::io::Result<()> {
+        write_activity_csv_header(&mut writer)?;
+        for activity in &self.data {
+            activity.write_csv_row(&mut writer)?;
+        }
+        writer.flush()
+    }
+
+    /// Writes all entries as JSON Lines (one JSON object per line).
+    pub fn write_jsonl<W: Write>(&self, mut writer: W) -> std::io::Result<()> {
+        for activity in &self.data {
+            activity.write_jsonl_row(&mut writer)?;
+        }
+        writer.flush()
+    }
+
+    /// Aggregates entries into time buckets of the given granularity.
+    pub fn bucket_by(&self, granularity: BucketGranularity) -> Vec<UsageBucket> {
+        bucket_activity(&self.data, granularity)
+    }
+
+    /// Aggregates entries into calendar-day buckets (UTC).
+    pub fn bucket_by_day(&self) -> Vec<UsageBucket> {
+        self.bucket_by(BucketGranularity::Day)
+    }
+
+    /// Aggregates entries into ISO-week buckets starting on Monday (UTC).
+    pub fn bucket_by_week(&self) -> Vec<UsageBucket> {
+        self.bucket_by(BucketGranularity::Week)
+    }
+
+    /// Aggregates entries into calendar-month buckets (UTC).
+    pub fn bucket_by_month(&self) -> Vec<UsageBucket> {
+        self.bucket_by(BucketGranularity::Month)
+    }
+
     /// Returns the percentage of requests that used each feature
     pub fn feature_usage_percentages(&self) -> FeatureUsagePercentages {
         if self.data.is_empty() {
@@ -522,6 +720,39 @@ pub struct ProviderUsageStats {
     pub success_rate: f64,
 }
 
+/// Spend attributed to a single user
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct UserCost {
+    pub user_id: String,
+    pub request_count: usize,
+    pub total_cost: f64,
+    pub total_tokens: u64,
+    /// Percentage of the overall cost attributed to this user
+    pub share_of_total: f64,
+}
+
+/// Per-user cost attribution report
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct CostAttribution {
+    /// Users sorted by descending cost
+    pub users: Vec<UserCost>,
+    /// Number of requests without a user
+    pub unattributed_requests: usize,
+    /// Cost of requests without a user
+    pub unattributed_cost: f64,
+    /// Total cost across all requests
+    pub total_cost: f64,
+}
+
+impl CostAttribution {
+    /// Returns the attribution entry for a specific user
+    pub fn user(&self, user_id: &str) -> Option<&UserCost> {
+        self.users.iter().find(|u| u.user_id == user_id)
+    }
+}
+
 /// Feature usage percentages
 #[derive(Debug, Clone)]
 #[non_exhaustive]
@@ -532,6 +763,110 @@ pub struct FeatureUsagePercentages {
     pub streaming: f64,
 }
 
+/// Time period size used when bucketing activity data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BucketGranularity {
+    /// One bucket per calendar day.
+    Day,
+    /// One bucket per ISO week (Monday through Sunday).
+    Week,
+    /// One bucket per calendar month.
+    Month,
+}
+
+impl BucketGranularity {
+    /// Returns the first day of the period containing `date`.
+    pub fn period_start(&self, date: NaiveDate) -> NaiveDate {
+        match self {
+            BucketGranularity::Day => date,
+            BucketGranularity::Week => {
+                date - chrono::Duration::days(date.weekday().num_days_from_monday() as i64)
+            }
+            BucketGranularity::Month => date.with_day(1).unwrap_or(date),
+        }
+    }
+}
+
+/// Aggregated usage for a single time period.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct UsageBucket {
+    /// First day of the period (UTC).
+    pub period: NaiveDate,
+    /// Total cost in USD (effective cost where available).
+    pub cost: f64,
+    /// Total tokens used.
+    pub tokens: u64,
+    /// Number of requests in the period.
+    pub request_count: usize,
+    /// Percentage of non-cancelled requests.
+    pub success_rate: f64,
+}
+
+/// Aggregates activity entries into time buckets one at a time, so pages
+/// can be folded in as they arrive.
+#[derive(Debug, Clone)]
+pub struct UsageBucketer {
+    granularity: BucketGranularity,
+    // period -> (cost, tokens, requests, successful)
+    buckets: BTreeMap<NaiveDate, (f64, u64, usize, usize)>,
+}
+
+impl UsageBucketer {
+    /// Creates an empty bucketer for `granularity`.
+    pub fn new(granularity: BucketGranularity) -> Self {
+        Self {
+            granularity,
+            buckets: BTreeMap::new(),
+        }
+    }
+
+    /// Adds one activity entry to its period's bucket.
+    pub fn add(&mut self, activity: &ActivityData) {
+        let period = self
+            .granularity
+            .period_start(activity.created_at.date_naive());
+        let entry = self.buckets.entry(period).or_default();
+        entry.0 += activity.final_cost().unwrap_or(0.0);
+        entry.1 += u64::from(activity.total_tokens.unwrap_or(0));
+        entry.2 += 1;
+        if activity.is_successful() {
+            entry.3 += 1;
+        }
+    }
+
+    /// Returns the buckets, sorted by period. Periods with no activity are
+    /// omitted.
+    pub fn finish(self) -> Vec<UsageBucket> {
+        self.buckets
+            .into_iter()
+            .map(
+                |(period, (cost, tokens, request_count, successful))| UsageBucket {
+                    period,
+                    cost,
+                    tokens,
+                    request_count,
+                    success_rate: successful as f64 / request_count as f64 * 100.0,
+                },
+            )
+            .collect()
+    }
+}
+
+/// Aggregates activity entries into time buckets, sorted by period.
+///
+/// Periods with no activity are omitted.
+pub fn bucket_activity<'a>(
+    activities: impl IntoIterator<Item = &'a ActivityData>,
+    granularity: BucketGranularity,
+) -> Vec<UsageBucket> {
+    let mut bucketer = UsageBucketer::new(granularity);
+    for activity in activities {
+        bucketer.add(activity);
+    }
+    bucketer.finish()
+}
+
 /// Validates date format (YYYY-MM-DD) with proper calendar validation
 fn is_valid_date_format(date: &str) -> bool {
     if date.len() != constants::DATE_FORMAT_LENGTH {
@@ -941,4 +1276,166 @@ mod tests {
 
         assert_eq!(set.len(), 2); // Should only have 2 unique IDs
     }
+
+    #[test]
+    fn test_write_csv_stable_columns_and_escaping() {
+        let response = ActivityResponse {
+            data: vec![ActivityData {
+                id: ActivityId::new("act-1"),
+                model: "vendor/model, \"large\"".to_string(),
+                total_cost: Some(0.5),
+                total_tokens: Some(42),
+                ..Default::default()
+            }],
+            total_count: Some(1),
+            has_more: Some(false),
+        };
+
+        let mut out = Vec::new();
+        response.write_csv(&mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        let mut lines = text.split("\r\n");
+
+        assert_eq!(lines.next().unwrap(), ACTIVITY_CSV_COLUMNS.join(","));
+        let row = lines.next().unwrap();
+        assert!(row.starts_with("act-1,2024-01-01T00:00:00+00:00,\"vendor/model, \"\"large\"\"\","));
+        assert!(row.contains(",0.5,"));
+        assert!(row.contains(",42,"));
+        assert_eq!(lines.next(), Some(""));
+        assert_eq!(
+            response.data[0].csv_record().len(),
+            ACTIVITY_CSV_COLUMNS.len()
+        );
+    }
+
+    #[test]
+    fn test_write_jsonl_one_object_per_line() {
+        let response = ActivityResponse {
+            data: vec![
+                ActivityData {
+                    id: ActivityId::new("act-1"),
+                    ..Default::default()
+                },
+                ActivityData {
+                    id: ActivityId::new("act-2"),
+                    ..Default::default()
+                },
+            ],
+            total_count: Some(2),
+            has_more: Some(false),
+        };
+
+        let mut out = Vec::new();
+        response.write_jsonl(&mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+
+        assert_eq!(lines.len(), 2);
+        let parsed: ActivityData = serde_json::from_str(lines[1]).unwrap();
+        assert_eq!(parsed.id.as_str(), "act-2");
+    }
+
+    #[test]
+    fn test_bucket_by_day_week_month() {
+        let at = |ts: &str| {
+            DateTime::parse_from_rfc3339(ts)
+                .unwrap()
+                .with_timezone(&Utc)
+        };
+        let response = ActivityResponse {
+            data: vec![
+                // Wednesday 2024-01-03
+                ActivityData {
+                    created_at: at("2024-01-03T10:00:00Z"),
+                    total_cost: Some(1.0),
+                    total_tokens: Some(100),
+                    ..Default::default()
+                },
+                ActivityData {
+                    created_at: at("2024-01-03T23:59:59Z"),
+                    total_cost: Some(2.0),
+                    total_tokens: Some(50),
+                    cancelled: CancellationStatus::Completed,
+                    ..Default::default()
+                },
+                // Monday 2024-01-08, next ISO week
+                ActivityData {
+                    created_at: at("2024-01-08T00:00:00Z"),
+                    total_cost: Some(4.0),
+                    ..Default::default()
+                },
+                ActivityData {
+                    created_at: at("2024-02-01T12:00:00Z"),
+                    effective_cost: Some(8.0),
+                    total_cost: Some(9.0),
+                    ..Default::default()
+                },
+            ],
+            total_count: Some(4),
+            has_more: Some(false),
+        };
+
+        let date = |s: &str| NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap();
+
+        let daily = response.bucket_by_day();
+        assert_eq!(daily.len(), 3);
+        assert_eq!(daily[0].period, date("2024-01-03"));
+        assert_eq!(daily[0].cost, 3.0);
+        assert_eq!(daily[0].tokens, 150);
+        assert_eq!(daily[0].request_count, 2);
+        assert_eq!(daily[0].success_rate, 50.0);
+
+        let weekly = response.bucket_by_week();
+        let periods: Vec<NaiveDate> = weekly.iter().map(|b| b.period).collect();
+        assert_eq!(
+            periods,
+            vec![date("2024-01-01"), date("2024-01-08"), date("2024-01-29")]
+        );
+
+        let monthly = response.bucket_by_month();
+        assert_eq!(monthly.len(), 2);
+        assert_eq!(monthly[0].period, date("2024-01-01"));
+        assert_eq!(monthly[0].cost, 7.0);
+        assert_eq!(monthly[1].cost, 8.0);
+        assert_eq!(monthly[1].success_rate, 100.0);
+    }
+
+    #[test]
+    fn test_group_by_user_and_cost_attribution() {
+        let entry = |user: Option<&str>, cost: f64| ActivityData {
+            total_cost: Some(cost),
+            total_tokens: Some(10),
+            user_id: user.map(str::to_string),
+            ..Default::default()
+        };
+        let response = ActivityResponse {
+            data: vec![
+                entry(Some("alice"), 1.0),
+                entry(Some("bob"), 3.0),
+                entry(Some("alice"), 2.0),
+                entry(None, 4.0),
+            ],
+            total_count: Some(4),
+            has_more: Some(false),
+        };
+
+        let groups = response.group_by_user();
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups["alice"].len(), 2);
+
+        let report = response.cost_attribution();
+        assert_eq!(report.total_cost, 10.0);
+        assert_eq!(report.unattributed_requests, 1);
+        assert_eq!(report.unattributed_cost, 4.0);
+        // alice and bob tie on cost; ties are broken by user id
+        let order: Vec<&str> = report.users.iter().map(|u| u.user_id.as_str()).collect();
+        assert_eq!(order, vec!["alice", "bob"]);
+
+        let alice = report.user("alice").unwrap();
+        assert_eq!(alice.request_count, 2);
+        assert_eq!(alice.total_cost, 3.0);
+        assert_eq!(alice.total_tokens, 20);
+        assert_eq!(alice.share_of_total, 30.0);
+        assert!(report.user("carol").is_none());
+    }
 }
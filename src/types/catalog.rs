@@ -0,0 +1,320 @@
+//! Change detection for the model and provider catalogs.
+//!
+//! A [`ModelsSnapshot`] or [`ProvidersSnapshot`] records the catalog at one
+//! point in time and can be stored as JSON. Comparing two snapshots with
+//! `diff(old, new)` lists entries that were added, removed or changed;
+//! `OpenRouterClient::watch_models` does this periodically and yields a
+//! [`ModelsDiff`] whenever the model catalog changes.
+
+use crate::types::models::{ModelInfo, ModelsResponse};
+use crate::types::providers::{Provider, ProvidersResponse};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// The model catalog at a point in time, keyed by model ID.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelsSnapshot {
+    pub taken_at: DateTime<Utc>,
+    pub models: BTreeMap<String, ModelInfo>,
+}
+
+impl ModelsSnapshot {
+    /// Records `models`, timestamped now.
+    pub fn new(models: impl IntoIterator<Item = ModelInfo>) -> Self {
+        Self {
+            taken_at: Utc::now(),
+            models: models
+                .into_iter()
+                .map(|model| (model.id.to_string(), model))
+                .collect(),
+        }
+    }
+
+    /// Compares two snapshots of the catalog.
+    pub fn diff(old: &ModelsSnapshot, new: &ModelsSnapshot) -> ModelsDiff {
+        let mut diff = ModelsDiff {
+            from: old.taken_at,
+            to: new.taken_at,
+            ..Default::default()
+        };
+        for (id, model) in &new.models {
+            match old.models.get(id) {
+                None => diff.added.push(model.clone()),
+                Some(previous) => {
+                    let changes = model_changes(previous, model);
+                    if !changes.is_empty() {
+                        diff.changed.push(ModelUpdate {
+                            id: id.clone(),
+                            changes,
+                        });
+                    }
+                }
+            }
+        }
+        diff.removed = old
+            .models
+            .iter()
+            .filter(|(id, _)| !new.models.contains_key(*id))
+            .map(|(_, model)| model.clone())
+            .collect();
+        diff
+    }
+}
+
+impl From<ModelsResponse> for ModelsSnapshot {
+    fn from(response: ModelsResponse) -> Self {
+        Self::new(response.data)
+    }
+}
+
+/// A change to one attribute of a model.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ModelChange {
+    /// Price per prompt token.
+    PromptPrice {
+        old: f64,
+        new: f64,
+    },
+    /// Price per completion token.
+    CompletionPrice {
+        old: f64,
+        new: f64,
+    },
+    ContextLength {
+        old: u32,
+        new: u32,
+    },
+    MaxCompletionTokens {
+        old: Option<u32>,
+        new: Option<u32>,
+    },
+}
+
+/// The changes to a model present in both snapshots.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModelUpdate {
+    pub id: String,
+    pub changes: Vec<ModelChange>,
+}
+
+/// Differences between two [`ModelsSnapshot`]s, in model ID order.
+#[derive(Debug, Clone, Default)]
+#[non_exhaustive]
+pub struct ModelsDiff {
+    /// When the older snapshot was taken.
+    pub from: DateTime<Utc>,
+    /// When the newer snapshot was taken.
+    pub to: DateTime<Utc>,
+    pub added: Vec<ModelInfo>,
+    pub removed: Vec<ModelInfo>,
+    pub changed: Vec<ModelUpdate>,
+}
+
+impl ModelsDiff {
+    /// Whether the snapshots describe the same catalog.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+fn model_changes(old: &ModelInfo, new: &ModelInfo) -> Vec<ModelChange> {
+    let mut changes = Vec::new();
+    let (old_prompt, new_prompt) = (old.pricing.prompt_price(), new.pricing.prompt_price());
+    if old_prompt != new_prompt {
+        changes.push(ModelChange::PromptPrice {
+            old: old_prompt,
+            new: new_prompt,
+        });
+    }
+    let (old_completion, new_completion) = (
+        old.pricing.completion_price(),
+        new.pricing.completion_price(),
+    );
+    if old_completion != new_completion {
+        changes.push(ModelChange::CompletionPrice {
+            old: old_completion,
+            new: new_completion,
+        });
+    }
+    if old.context_length != new.context_length {
+        changes.push(ModelChange::ContextLength {
+            old: old.context_length,
+            new: new.context_length,
+        });
+    }
+    let (old_max, new_max) = (
+        old.top_provider.max_completion_tokens,
+        new.top_provider.max_completion_tokens,
+    );
+    if old_max != new_max {
+        changes.push(ModelChange::MaxCompletionTokens {
+            old: old_max,
+            new: new_max,
+        });
+    }
+    changes
+}
+
+/// The provider list at a point in time, keyed by slug.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProvidersSnapshot {
+    pub taken_at: DateTime<Utc>,
+    pub providers: BTreeMap<String, Provider>,
+}
+
+impl ProvidersSnapshot {
+    /// Records `providers`, timestamped now.
+    pub fn new(providers: impl IntoIterator<Item = Provider>) -> Self {
+        Self {
+            taken_at: Utc::now(),
+            providers: providers
+                .into_iter()
+                .map(|provider| (provider.slug.clone(), provider))
+                .collect(),
+        }
+    }
+
+    /// Compares two snapshots of the provider list.
+    pub fn diff(old: &ProvidersSnapshot, new: &ProvidersSnapshot) -> ProvidersDiff {
+        let mut diff = ProvidersDiff {
+            from: old.taken_at,
+            to: new.taken_at,
+            ..Default::default()
+        };
+        for (slug, provider) in &new.providers {
+            match old.providers.get(slug) {
+                None => diff.added.push(provider.clone()),
+                Some(previous) if previous != provider => diff.changed.push(ProviderUpdate {
+                    old: previous.clone(),
+                    new: provider.clone(),
+                }),
+                Some(_) => {}
+            }
+        }
+        diff.removed = old
+            .providers
+            .iter()
+            .filter(|(slug, _)| !new.providers.contains_key(*slug))
+            .map(|(_, provider)| provider.clone())
+            .collect();
+        diff
+    }
+}
+
+impl From<ProvidersResponse> for ProvidersSnapshot {
+    fn from(response: ProvidersResponse) -> Self {
+        Self::new(response.data)
+    }
+}
+
+/// A provider whose details (name or policy URLs) changed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProviderUpdate {
+    pub old: Provider,
+    pub new: Provider,
+}
+
+/// Differences between two [`ProvidersSnapshot`]s, in slug order.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[non_exhaustive]
+pub struct ProvidersDiff {
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    pub added: Vec<Provider>,
+    pub removed: Vec<Provider>,
+    pub changed: Vec<ProviderUpdate>,
+}
+
+impl ProvidersDiff {
+    /// Whether the snapshots describe the same provider list.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn model(id: &str, prompt: &str, context_length: u32) -> ModelInfo {
+        serde_json::from_value(json!({
+            "id": id,
+            "name": id,
+            "context_length": context_length,
+            "created": 0,
+            "architecture": {
+                "modality": "text->text",
+                "input_modalities": ["text"],
+                "output_modalities": ["text"],
+                "tokenizer": "GPT"
+            },
+            "pricing": {"prompt": prompt, "completion": "0.00001"},
+            "top_provider": {"context_length": context_length, "max_completion_tokens": 4096, "is_moderated": false},
+            "per_request_limits": null
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_models_diff_reports_added_removed_and_changed() {
+        let old = ModelsSnapshot::new([
+            model("openai/gpt-4o", "0.000005", 128000),
+            model("openai/gpt-3.5-turbo", "0.0000005", 16385),
+            model("anthropic/claude-3-haiku", "0.00000025", 200000),
+        ]);
+        let new = ModelsSnapshot::new([
+            model("openai/gpt-4o", "0.0000025", 128000),
+            model("anthropic/claude-3-haiku", "0.00000025", 200000),
+            model("google/gemini-pro", "0.000001", 32000),
+        ]);
+
+        let diff = ModelsSnapshot::diff(&old, &new);
+        assert!(!diff.is_empty());
+        assert_eq!(diff.added[0].id.to_string(), "google/gemini-pro");
+        assert_eq!(diff.removed[0].id.to_string(), "openai/gpt-3.5-turbo");
+        assert_eq!(
+            diff.changed,
+            [ModelUpdate {
+                id: "openai/gpt-4o".into(),
+                changes: vec![ModelChange::PromptPrice {
+                    old: 0.000005,
+                    new: 0.0000025
+                }],
+            }]
+        );
+        assert!(ModelsSnapshot::diff(&new, &new).is_empty());
+
+        // Stored snapshots compare the same as fresh ones.
+        let stored: ModelsSnapshot =
+            serde_json::from_str(&serde_json::to_string(&old).unwrap()).unwrap();
+        assert!(ModelsSnapshot::diff(&stored, &old).is_empty());
+    }
+
+    #[test]
+    fn test_providers_diff() {
+        let provider = |slug: &str, status: Option<&str>| {
+            Provider::new(
+                slug.to_string(),
+                slug.to_string(),
+                None,
+                None,
+                status.map(str::to_string),
+            )
+        };
+        let old = ProvidersSnapshot::new([provider("openai", None), provider("mancer", None)]);
+        let new = ProvidersSnapshot::new([
+            provider("openai", Some("https://status.openai.com")),
+            provider("together", None),
+        ]);
+
+        let diff = ProvidersSnapshot::diff(&old, &new);
+        assert_eq!(diff.added, [provider("together", None)]);
+        assert_eq!(diff.removed, [provider("mancer", None)]);
+        assert_eq!(
+            diff.changed[0].new.status_page_url.as_deref(),
+            Some("https://status.openai.com")
+        );
+    }
+}
@@ -12,6 +12,7 @@ use std::collections::HashMap;
 /// such as text, images, audio, or files. Using an enum with serde tagging makes
 /// invalid content types unrepresentable at compile time.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 #[non_exhaustive]
 pub enum ContentType {
@@ -38,6 +39,7 @@ impl std::fmt::Display for ContentType {
 
 /// Defines the role of a chat message (user, assistant, or system).
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 #[non_exhaustive]
 pub enum ChatRole {
@@ -58,16 +60,189 @@ impl std::fmt::Display for ChatRole {
     }
 }
 
+/// Most stop sequences accepted in one request.
+pub const MAX_STOP_SEQUENCES: usize = 4;
+
+/// Longest stop sequence accepted, in bytes.
+pub const MAX_STOP_SEQUENCE_LENGTH: usize = 100;
+
 /// Stop sequence for chat completion - can be a string or array of strings.
+///
+/// Build one with `From` (`"\n".into()`, `["END", "STOP"].into()`) or,
+/// to validate up front, [`StopSequence::new`]. `validate_chat_request`
+/// rejects more than [`MAX_STOP_SEQUENCES`] sequences and empty sequences or
+/// ones longer than [`MAX_STOP_SEQUENCE_LENGTH`].
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum StopSequence {
     Single(String),
     Multiple(Vec<String>),
 }
 
+impl StopSequence {
+    /// Creates stop sequences, checking the provider limits.
+    pub fn new<I, S>(sequences: I) -> crate::error::Result<Self>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let stop = Self::Multiple(sequences.into_iter().map(Into::into).collect());
+        crate::utils::validation::chat::validate_stop_sequences(&stop)?;
+        Ok(stop)
+    }
+
+    /// The sequences, whether one or several.
+    pub fn sequences(&self) -> &[String] {
+        match self {
+            StopSequence::Single(sequence) => std::slice::from_ref(sequence),
+            StopSequence::Multiple(sequences) => sequences,
+        }
+    }
+}
+
+impl From<&str> for StopSequence {
+    fn from(sequence: &str) -> Self {
+        StopSequence::Single(sequence.to_string())
+    }
+}
+
+impl From<String> for StopSequence {
+    fn from(sequence: String) -> Self {
+        StopSequence::Single(sequence)
+    }
+}
+
+impl From<Vec<String>> for StopSequence {
+    fn from(sequences: Vec<String>) -> Self {
+        StopSequence::Multiple(sequences)
+    }
+}
+
+impl From<Vec<&str>> for StopSequence {
+    fn from(sequences: Vec<&str>) -> Self {
+        StopSequence::Multiple(sequences.into_iter().map(str::to_string).collect())
+    }
+}
+
+impl<const N: usize> From<[&str; N]> for StopSequence {
+    fn from(sequences: [&str; N]) -> Self {
+        StopSequence::Multiple(sequences.into_iter().map(str::to_string).collect())
+    }
+}
+
+/// Lowest bias accepted for a token; effectively bans it.
+pub const LOGIT_BIAS_MIN: f32 = -100.0;
+
+/// Highest bias accepted for a token; effectively forces it.
+pub const LOGIT_BIAS_MAX: f32 = 100.0;
+
+/// Converts text to the model's token IDs, for building a [`LogitBias`] from
+/// strings.
+///
+/// No tokenizer is bundled; implement this for the tokenizer matching your
+/// model, or pass a closure `Fn(&str) -> Vec<u32>`.
+pub trait Tokenizer {
+    fn encode(&self, text: &str) -> Vec<u32>;
+}
+
+impl<F: Fn(&str) -> Vec<u32>> Tokenizer for F {
+    fn encode(&self, text: &str) -> Vec<u32> {
+        self(text)
+    }
+}
+
+/// Per-token adjustments to the model's logits, keyed by token ID.
+///
+/// Biases set through [`insert`](Self::insert) and the builders are clamped
+/// to [`LOGIT_BIAS_MIN`]..=[`LOGIT_BIAS_MAX`]; maps converted with `From`
+/// are checked by `validate_chat_request` instead.
+///
+/// ```
+/// use openrouter_api::types::chat::LogitBias;
+///
+/// let tokenizer = |text: &str| text.bytes().map(u32::from).collect::<Vec<_>>();
+/// let bias = LogitBias::new()
+///     .with_token(1734, 5.0)
+///     .ban_text(&tokenizer, "ab");
+/// assert_eq!(bias.get(97), Some(-100.0));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
+#[serde(transparent)]
+pub struct LogitBias(HashMap<u32, f32>);
+
+impl LogitBias {
+    /// Creates an empty bias map.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the bias for `token`, clamped to the accepted range.
+    pub fn insert(&mut self, token: u32, bias: f32) {
+        self.0
+            .insert(token, bias.clamp(LOGIT_BIAS_MIN, LOGIT_BIAS_MAX));
+    }
+
+    /// Sets the bias for `token`.
+    pub fn with_token(mut self, token: u32, bias: f32) -> Self {
+        self.insert(token, bias);
+        self
+    }
+
+    /// Sets `bias` for every token `text` encodes to.
+    pub fn with_text(mut self, tokenizer: &impl Tokenizer, text: &str, bias: f32) -> Self {
+        for token in tokenizer.encode(text) {
+            self.insert(token, bias);
+        }
+        self
+    }
+
+    /// Bans every token `text` encodes to.
+    pub fn ban_text(self, tokenizer: &impl Tokenizer, text: &str) -> Self {
+        self.with_text(tokenizer, text, LOGIT_BIAS_MIN)
+    }
+
+    /// Returns the bias for `token`.
+    pub fn get(&self, token: u32) -> Option<f32> {
+        self.0.get(&token).copied()
+    }
+
+    /// Iterates over token IDs and their biases.
+    pub fn iter(&self) -> impl Iterator<Item = (u32, f32)> + '_ {
+        self.0.iter().map(|(token, bias)| (*token, *bias))
+    }
+
+    /// Number of biased tokens.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Whether no token is biased.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl From<HashMap<u32, f32>> for LogitBias {
+    fn from(map: HashMap<u32, f32>) -> Self {
+        Self(map)
+    }
+}
+
+impl FromIterator<(u32, f32)> for LogitBias {
+    fn from_iter<I: IntoIterator<Item = (u32, f32)>>(iter: I) -> Self {
+        let mut bias = Self::new();
+        for (token, value) in iter {
+            bias.insert(token, value);
+        }
+        bias
+    }
+}
+
 /// Prediction configuration for latency optimization.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 pub struct PredictionConfig {
     #[serde(rename = "type")]
     pub prediction_type: String, // "content"
@@ -76,6 +251,7 @@ pub struct PredictionConfig {
 
 /// Verbosity level for model responses.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 #[non_exhaustive]
 pub enum VerbosityLevel {
@@ -86,6 +262,7 @@ pub enum VerbosityLevel {
 
 /// Route strategy for model routing.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 #[non_exhaustive]
 pub enum RouteStrategy {
@@ -94,6 +271,7 @@ pub enum RouteStrategy {
 
 /// Image detail level for vision models.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 #[non_exhaustive]
 pub enum ImageDetail {
@@ -104,6 +282,7 @@ pub enum ImageDetail {
 
 /// Text content part for multimodal messages.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 pub struct TextContent {
     #[serde(rename = "type")]
     pub content_type: ContentType,
@@ -112,6 +291,7 @@ pub struct TextContent {
 
 /// Image URL content for multimodal messages.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 pub struct ImageUrl {
     pub url: String, // URL or base64 encoded image data
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -120,6 +300,7 @@ pub struct ImageUrl {
 
 /// Image content part for multimodal messages.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 pub struct ImageContent {
     #[serde(rename = "type")]
     pub content_type: ContentType,
@@ -128,12 +309,14 @@ pub struct ImageContent {
 
 /// Audio URL content for multimodal messages.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 pub struct AudioUrl {
     pub url: String,
 }
 
 /// Audio content part for multimodal messages.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 pub struct AudioContent {
     #[serde(rename = "type")]
     pub content_type: ContentType,
@@ -142,12 +325,14 @@ pub struct AudioContent {
 
 /// File URL content for multimodal messages (e.g. PDFs).
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 pub struct FileUrl {
     pub url: String,
 }
 
 /// File content part for multimodal messages.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 pub struct FileContent {
     #[serde(rename = "type")]
     pub content_type: ContentType,
@@ -156,6 +341,7 @@ pub struct FileContent {
 
 /// Content parts for multimodal messages (user role only).
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum ContentPart {
     Text(TextContent),
@@ -166,16 +352,125 @@ pub enum ContentPart {
 
 /// Enhanced message content supporting both string and multimodal content.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum MessageContent {
     Text(String),
     Parts(Vec<ContentPart>),
 }
 
+impl Default for MessageContent {
+    fn default() -> Self {
+        MessageContent::Text(String::new())
+    }
+}
+
+impl MessageContent {
+    /// Returns the content as a string slice when it is plain text.
+    pub fn as_text(&self) -> Option<&str> {
+        match self {
+            MessageContent::Text(text) => Some(text),
+            MessageContent::Parts(_) => None,
+        }
+    }
+
+    /// Returns all text in the content, concatenating the text parts of
+    /// multimodal content and skipping non-text parts.
+    pub fn to_text(&self) -> String {
+        match self {
+            MessageContent::Text(text) => text.clone(),
+            MessageContent::Parts(parts) => parts
+                .iter()
+                .filter_map(|part| match part {
+                    ContentPart::Text(text) => Some(text.text.as_str()),
+                    _ => None,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Output of a tool call, as sent back to the model with
+/// [`Message::tool_result`].
+///
+/// Text is sent as is and JSON as its serialized text. Images are sent as an
+/// `image_url` content part, which providers that accept images in tool
+/// results (e.g. Anthropic models) pass to the model.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ToolResult {
+    Text(String),
+    Json(serde_json::Value),
+    /// An image by URL or base64 data URL.
+    Image(ImageUrl),
+}
+
+impl ToolResult {
+    /// An image at `url`, which may be a `data:` URL.
+    pub fn image_url(url: impl Into<String>) -> Self {
+        Self::Image(ImageUrl {
+            url: url.into(),
+            detail: None,
+        })
+    }
+
+    /// An image from base64-encoded `data` of the given MIME type.
+    pub fn image_base64(mime_type: &str, data: &str) -> Self {
+        Self::image_url(format!("data:{mime_type};base64,{data}"))
+    }
+
+    /// The tool message content carrying this result.
+    pub fn into_content(self) -> MessageContent {
+        match self {
+            ToolResult::Text(text) => MessageContent::Text(text),
+            ToolResult::Json(value) => MessageContent::Text(value.to_string()),
+            ToolResult::Image(image_url) => {
+                MessageContent::Parts(vec![ContentPart::Image(ImageContent {
+                    content_type: ContentType::ImageUrl,
+                    image_url,
+                })])
+            }
+        }
+    }
+}
+
+impl From<String> for ToolResult {
+    fn from(text: String) -> Self {
+        Self::Text(text)
+    }
+}
+
+impl From<&str> for ToolResult {
+    fn from(text: &str) -> Self {
+        Self::Text(text.to_string())
+    }
+}
+
+impl From<serde_json::Value> for ToolResult {
+    /// Strings become text and an image content part
+    /// (`{"type": "image_url", "image_url": {"url": ...}}`) becomes an
+    /// image; any other value is JSON.
+    fn from(value: serde_json::Value) -> Self {
+        match value {
+            serde_json::Value::String(text) => Self::Text(text),
+            value if value.get("type").and_then(|t| t.as_str()) == Some("image_url") => {
+                match serde_json::from_value::<ImageContent>(value.clone()) {
+                    Ok(image) => Self::Image(image.image_url),
+                    Err(_) => Self::Json(value),
+                }
+            }
+            value => Self::Json(value),
+        }
+    }
+}
+
 /// Represents a chat message with a role and content.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 pub struct Message {
     pub role: ChatRole,
+    /// Assistant messages that only call tools may omit the content or send
+    /// `null`; both deserialize as empty text.
+    #[serde(default, deserialize_with = "deserialize_nullable_content")]
     pub content: MessageContent,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
@@ -192,6 +487,13 @@ pub struct Message {
     pub reasoning_details: Option<Vec<ReasoningDetail>>,
 }
 
+fn deserialize_nullable_content<'de, D>(deserializer: D) -> Result<MessageContent, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    Ok(Option::<MessageContent>::deserialize(deserializer)?.unwrap_or_default())
+}
+
 impl Default for Message {
     fn default() -> Self {
         Self {
@@ -263,6 +565,17 @@ impl Message {
         }
     }
 
+    /// Create a tool message from a [`ToolResult`], which may be JSON or an
+    /// image as well as text.
+    pub fn tool_result(result: impl Into<ToolResult>, tool_call_id: impl Into<ToolCallId>) -> Self {
+        Self {
+            role: ChatRole::Tool,
+            content: result.into().into_content(),
+            tool_call_id: Some(tool_call_id.into()),
+            ..Default::default()
+        }
+    }
+
     /// Create an assistant message with tool calls.
     pub fn assistant_with_tools(
         content: Option<impl Into<String>>,
@@ -284,6 +597,7 @@ impl Message {
 
 /// Debug configuration for request inspection.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 pub struct DebugConfig {
     /// When true, the transformed upstream request body is echoed as the first streaming chunk.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -395,6 +709,27 @@ impl<'de> Deserialize<'de> for Plugin {
     }
 }
 
+/// Plugins serialize as an object with `id`, an optional `enabled` flag and
+/// any configuration keys flattened alongside them.
+#[cfg(feature = "schema-export")]
+impl schemars::JsonSchema for Plugin {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "Plugin".into()
+    }
+
+    fn json_schema(generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "type": "object",
+            "properties": {
+                "id": generator.subschema_for::<String>(),
+                "enabled": generator.subschema_for::<bool>(),
+            },
+            "required": ["id"],
+            "additionalProperties": true,
+        })
+    }
+}
+
 impl Plugin {
     /// Create a plugin by id.
     pub fn new(id: impl Into<String>) -> Self {
@@ -440,6 +775,7 @@ impl Plugin {
 
 /// Effort level for reasoning models (o1, o3, Claude extended thinking, DeepSeek R1).
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 #[non_exhaustive]
 pub enum ReasoningEffort {
@@ -453,6 +789,7 @@ pub enum ReasoningEffort {
 
 /// Summary verbosity for reasoning models that expose summarized thinking output.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 #[non_exhaustive]
 pub enum ReasoningSummary {
@@ -463,6 +800,7 @@ pub enum ReasoningSummary {
 
 /// Reasoning configuration for models that support extended thinking.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 pub struct ReasoningConfig {
     /// Constrains effort on reasoning-capable models.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -510,6 +848,7 @@ impl ReasoningConfig {
 
 /// Structured reasoning detail item returned by some reasoning-capable models.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 #[serde(tag = "type")]
 pub enum ReasoningDetail {
     #[serde(rename = "reasoning.summary")]
@@ -549,6 +888,7 @@ pub enum ReasoningDetail {
 
 /// Chat completion request matching the OpenRouter API schema.
 #[derive(Debug, Serialize, Clone, Default)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 pub struct ChatCompletionRequest {
     /// The model ID to use.
     pub model: String,
@@ -565,7 +905,7 @@ pub struct ChatCompletionRequest {
     pub tools: Option<Vec<crate::models::tool::Tool>>,
     /// (Optional) Tool choice configuration.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub tool_choice: Option<serde_json::Value>,
+    pub tool_choice: Option<crate::models::tool::ToolChoice>,
     /// (Optional) Provider preferences for routing and fallback configuration.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub provider: Option<crate::models::provider_preferences::ProviderPreferences>,
@@ -613,6 +953,9 @@ pub struct ChatCompletionRequest {
     /// (Optional) Seed for deterministic sampling.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub seed: Option<u64>,
+    /// (Optional) Number of choices to generate (1 to 128).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub n: Option<u32>,
     /// (Optional) Stop sequences.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub stop: Option<StopSequence>,
@@ -620,7 +963,7 @@ pub struct ChatCompletionRequest {
     // Advanced parameters
     /// (Optional) Logit bias for token selection.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub logit_bias: Option<HashMap<u32, f32>>,
+    pub logit_bias: Option<LogitBias>,
     /// (Optional) Whether to return log probabilities.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub logprobs: Option<bool>,
@@ -646,10 +989,16 @@ pub struct ChatCompletionRequest {
     /// (o1, o3, Claude extended thinking, DeepSeek R1).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub reasoning: Option<ReasoningConfig>,
+    /// (Optional) Provider-specific parameters the typed fields don't cover,
+    /// e.g. `thinking` or `safe_prompt`, merged into the top level of the
+    /// payload. Keys that collide with a field set above fail validation.
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    pub extra_body: Option<serde_json::Map<String, serde_json::Value>>,
 }
 
 /// A choice returned by the chat API.
 #[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 #[non_exhaustive]
 pub struct Choice {
     pub message: Message,
@@ -658,36 +1007,97 @@ pub struct Choice {
     pub native_finish_reason: Option<String>,
     pub index: Option<u32>,
     pub logprobs: Option<LogProbs>,
+    /// Fields not modeled by this version of the crate (`preserve-unknown`
+    /// feature).
+    #[cfg(feature = "preserve-unknown")]
+    #[serde(flatten)]
+    pub extra: std::collections::HashMap<String, serde_json::Value>,
 }
 
-/// Log probabilities information.
-#[derive(Debug, Deserialize)]
+/// Log probabilities of the generated tokens, returned when the request sets
+/// `logprobs: true`.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 #[non_exhaustive]
 pub struct LogProbs {
+    /// One entry per content token, in order.
+    #[serde(default)]
     pub content: Option<Vec<TokenLogProb>>,
+    /// One entry per refusal token, for models that refuse in a separate
+    /// channel.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refusal: Option<Vec<TokenLogProb>>,
 }
 
-/// Token log probability information.
-#[derive(Debug, Deserialize)]
+impl LogProbs {
+    /// The content tokens, or an empty slice.
+    pub fn tokens(&self) -> &[TokenLogProb] {
+        self.content.as_deref().unwrap_or_default()
+    }
+
+    /// Sum of the content tokens' log probabilities: the log probability of
+    /// the whole completion.
+    pub fn total_logprob(&self) -> f64 {
+        self.tokens().iter().map(|t| f64::from(t.logprob)).sum()
+    }
+
+    /// Perplexity of the completion, `exp(-mean logprob)`. `None` without
+    /// content tokens.
+    pub fn perplexity(&self) -> Option<f64> {
+        let count = self.tokens().len();
+        (count > 0).then(|| (-self.total_logprob() / count as f64).exp())
+    }
+}
+
+/// Log probability of one generated token.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 #[non_exhaustive]
 pub struct TokenLogProb {
     pub token: String,
     pub logprob: f32,
+    /// UTF-8 bytes of the token, for tokens that are not valid UTF-8 alone.
+    #[serde(default)]
     pub bytes: Option<Vec<u8>>,
+    /// The most likely alternatives at this position, up to the request's
+    /// `top_logprobs`.
+    #[serde(default)]
     pub top_logprobs: Option<Vec<TopLogProb>>,
 }
 
-/// Top log probability information.
-#[derive(Debug, Deserialize)]
+impl TokenLogProb {
+    /// The token's probability, `exp(logprob)`.
+    pub fn probability(&self) -> f64 {
+        f64::from(self.logprob).exp()
+    }
+
+    /// The alternatives at this position, or an empty slice.
+    pub fn alternatives(&self) -> &[TopLogProb] {
+        self.top_logprobs.as_deref().unwrap_or_default()
+    }
+}
+
+/// Log probability of an alternative token.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 #[non_exhaustive]
 pub struct TopLogProb {
     pub token: String,
     pub logprob: f32,
+    #[serde(default)]
     pub bytes: Option<Vec<u8>>,
 }
 
+impl TopLogProb {
+    /// The token's probability, `exp(logprob)`.
+    pub fn probability(&self) -> f64 {
+        f64::from(self.logprob).exp()
+    }
+}
+
 /// Server-side tool usage counts (e.g., web search requests).
 #[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 #[non_exhaustive]
 pub struct ServerToolUse {
     /// Number of web search requests made by the server.
@@ -700,6 +1110,7 @@ pub struct ServerToolUse {
 
 /// Usage data returned from the API.
 #[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 #[non_exhaustive]
 pub struct Usage {
     pub prompt_tokens: u32,
@@ -713,10 +1124,16 @@ pub struct Usage {
     pub server_tool_use: Option<ServerToolUse>,
     pub prompt_tokens_details: Option<PromptTokensDetails>,
     pub completion_tokens_details: Option<CompletionTokensDetails>,
+    /// Fields not modeled by this version of the crate (`preserve-unknown`
+    /// feature).
+    #[cfg(feature = "preserve-unknown")]
+    #[serde(flatten)]
+    pub extra: std::collections::HashMap<String, serde_json::Value>,
 }
 
 /// Details about prompt token usage.
 #[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 #[non_exhaustive]
 pub struct PromptTokensDetails {
     pub cached_tokens: Option<u32>,
@@ -731,6 +1148,7 @@ pub struct PromptTokensDetails {
 
 /// Details about completion token usage.
 #[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 #[non_exhaustive]
 pub struct CompletionTokensDetails {
     pub reasoning_tokens: Option<u32>,
@@ -741,9 +1159,13 @@ pub struct CompletionTokensDetails {
 
 /// Chat completion response.
 #[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 #[non_exhaustive]
 pub struct ChatCompletionResponse {
     pub id: String,
+    /// Choices ordered by index. A choice without an index is given its
+    /// position in the response.
+    #[serde(deserialize_with = "deserialize_choices")]
     pub choices: Vec<Choice>,
     pub created: i64,
     pub model: String,
@@ -751,13 +1173,145 @@ pub struct ChatCompletionResponse {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub system_fingerprint: Option<String>,
     pub usage: Option<Usage>,
+    /// Set by the client when the request was truncated to fit the model's
+    /// context window.
+    #[serde(skip)]
+    pub truncation: Option<crate::types::truncation::TruncationReport>,
+    /// Fields not modeled by this version of the crate (`preserve-unknown`
+    /// feature).
+    #[cfg(feature = "preserve-unknown")]
+    #[serde(flatten)]
+    pub extra: std::collections::HashMap<String, serde_json::Value>,
+}
+
+impl ChatCompletionResponse {
+    /// Returns the first choice, if the API returned any.
+    pub fn first_choice(&self) -> Option<&Choice> {
+        self.choices.first()
+    }
+
+    /// Removes reasoning text and details from every choice.
+    pub fn strip_reasoning(&mut self) {
+        for choice in &mut self.choices {
+            choice.message.reasoning = None;
+            choice.message.reasoning_details = None;
+        }
+    }
+
+    /// Returns the text of the first choice.
+    ///
+    /// Returns `None` if there are no choices or the message content is
+    /// multimodal; use `MessageContent::to_text` for the latter.
+    pub fn text(&self) -> Option<&str> {
+        self.first_choice()?.message.content.as_text()
+    }
+
+    /// Returns the first choice's log probabilities, present when the
+    /// request set `logprobs: true`.
+    pub fn logprobs(&self) -> Option<&LogProbs> {
+        self.first_choice()?.logprobs.as_ref()
+    }
+
+    /// Returns the tool calls requested in the first choice, or an empty
+    /// slice if there are none.
+    pub fn tool_calls(&self) -> &[ToolCall] {
+        self.first_choice()
+            .and_then(|choice| choice.message.tool_calls.as_deref())
+            .unwrap_or_default()
+    }
+
+    /// Returns the finish reason of the first choice.
+    pub fn finish_reason(&self) -> Option<&str> {
+        self.first_choice()?.finish_reason.as_deref()
+    }
+
+    /// Prepends an assistant prefill to the text of every choice, since the
+    /// response only contains the continuation.
+    pub fn prepend_prefill(&mut self, prefill: &str) {
+        for choice in &mut self.choices {
+            if let MessageContent::Text(text) = &mut choice.message.content {
+                text.insert_str(0, prefill);
+            }
+        }
+    }
+
+    /// Returns the choice with the given index.
+    pub fn choice(&self, index: u32) -> Option<&Choice> {
+        self.choices
+            .iter()
+            .find(|choice| choice.index == Some(index))
+    }
+
+    /// Returns the choice with the highest score, for reranking the
+    /// candidates of a request with `n > 1`. Ties go to the lowest index;
+    /// incomparable scores such as `NaN` never win.
+    ///
+    /// ```
+    /// # use openrouter_api::types::chat::ChatCompletionResponse;
+    /// # fn pick(response: &ChatCompletionResponse) -> Option<&str> {
+    /// // Prefer the shortest answer that finished normally.
+    /// response
+    ///     .best_by(|choice| {
+    ///         let length = choice.message.content.to_text().len();
+    ///         (choice.finish_reason.as_deref() == Some("stop"), -(length as i64))
+    ///     })
+    ///     .and_then(|choice| choice.message.content.as_text())
+    /// # }
+    /// ```
+    pub fn best_by<K, F>(&self, mut score: F) -> Option<&Choice>
+    where
+        K: PartialOrd,
+        F: FnMut(&Choice) -> K,
+    {
+        let mut best: Option<(&Choice, K)> = None;
+        for choice in &self.choices {
+            let key = score(choice);
+            let better = match &best {
+                None => key.partial_cmp(&key).is_some(),
+                Some((_, best_key)) => {
+                    key.partial_cmp(best_key) == Some(std::cmp::Ordering::Greater)
+                }
+            };
+            if better {
+                best = Some((choice, key));
+            }
+        }
+        best.map(|(choice, _)| choice)
+    }
+
+    /// Returns the choice the model was most confident in: the lowest
+    /// perplexity among choices that returned log probabilities.
+    pub fn most_likely(&self) -> Option<&Choice> {
+        self.choices
+            .iter()
+            .filter_map(|choice| Some((choice, choice.logprobs.as_ref()?.perplexity()?)))
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(choice, _)| choice)
+    }
+}
+
+/// Deserializes choices sorted by index, filling in missing indices from
+/// their position.
+fn deserialize_choices<'de, D>(deserializer: D) -> std::result::Result<Vec<Choice>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let mut choices = Vec::<Choice>::deserialize(deserializer)?;
+    for (position, choice) in choices.iter_mut().enumerate() {
+        choice.index.get_or_insert(position as u32);
+    }
+    choices.sort_by_key(|choice| choice.index);
+    Ok(choices)
 }
 
 /// A choice returned by the streaming chat API.
 /// Different from regular Choice as it contains deltas instead of complete messages.
 #[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 #[non_exhaustive]
 pub struct ChoiceStream {
+    /// Defaults to 0 for providers that omit it on single-choice streams.
+    #[serde(default)]
     pub index: u32,
     pub delta: StreamDelta,
     pub finish_reason: Option<String>,
@@ -768,6 +1322,7 @@ pub struct ChoiceStream {
 
 /// Delta content for streaming responses.
 #[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 #[non_exhaustive]
 pub struct StreamDelta {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -786,6 +1341,7 @@ pub struct StreamDelta {
 
 /// A streaming chunk for chat completions.
 #[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 #[non_exhaustive]
 pub struct ChatCompletionChunk {
     pub id: String,
@@ -798,4 +1354,570 @@ pub struct ChatCompletionChunk {
     pub usage: Option<Usage>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub system_fingerprint: Option<String>,
+    /// Set on the first chunk when the client truncated the request to fit
+    /// the context window; never sent by the API.
+    #[serde(skip)]
+    pub truncation: Option<crate::types::truncation::TruncationReport>,
+}
+
+impl ChatCompletionChunk {
+    /// Removes reasoning text and details from every choice's delta.
+    pub fn strip_reasoning(&mut self) {
+        for choice in &mut self.choices {
+            choice.delta.reasoning = None;
+            choice.delta.reasoning_details = None;
+        }
+    }
+}
+
+/// Assembles streamed [`ChatCompletionChunk`]s into a [`ChatCompletionResponse`].
+///
+/// Each choice is merged by a [`DeltaAccumulator`]; the last reported usage
+/// wins. This lets code that streams for latency share post-processing with
+/// non-streaming code paths.
+#[derive(Debug, Default)]
+pub struct ChatCompletionAccumulator {
+    id: Option<String>,
+    created: i64,
+    model: String,
+    system_fingerprint: Option<String>,
+    usage: Option<Usage>,
+    choices: std::collections::BTreeMap<u32, DeltaAccumulator>,
+    prefill: Option<String>,
+    truncation: Option<crate::types::truncation::TruncationReport>,
+}
+
+impl ChatCompletionAccumulator {
+    /// Creates an empty accumulator.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an accumulator that starts every choice's content with an
+    /// assistant prefill, which the stream itself does not repeat.
+    pub fn with_prefill(prefill: impl Into<String>) -> Self {
+        Self {
+            prefill: Some(prefill.into()),
+            ..Self::default()
+        }
+    }
+
+    /// Folds a chunk into the accumulated response.
+    pub fn push(&mut self, chunk: ChatCompletionChunk) {
+        if self.id.is_none() {
+            self.id = Some(chunk.id);
+            self.created = chunk.created;
+            self.model = chunk.model;
+        }
+        if chunk.system_fingerprint.is_some() {
+            self.system_fingerprint = chunk.system_fingerprint;
+        }
+        if chunk.usage.is_some() {
+            self.usage = chunk.usage;
+        }
+        if chunk.truncation.is_some() {
+            self.truncation = chunk.truncation;
+        }
+        for choice in chunk.choices {
+            self.choices
+                .entry(choice.index)
+                .or_insert_with(|| match &self.prefill {
+                    Some(prefill) => DeltaAccumulator::with_prefill(prefill.clone()),
+                    None => DeltaAccumulator::new(),
+                })
+                .push_choice(choice);
+        }
+    }
+
+    /// Produces the synthesized response.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::StreamingError` if no chunks were received or a
+    /// streamed tool call never reported its ID or function name.
+    pub fn finish(self) -> crate::error::Result<ChatCompletionResponse> {
+        let id = self.id.ok_or_else(|| {
+            crate::error::Error::StreamingError("Stream ended without any chunks".into())
+        })?;
+
+        let choices = self
+            .choices
+            .into_iter()
+            .map(|(index, acc)| acc.into_choice(index))
+            .collect::<crate::error::Result<Vec<_>>>()?;
+
+        Ok(ChatCompletionResponse {
+            id,
+            choices,
+            created: self.created,
+            model: self.model,
+            object: "chat.completion".to_string(),
+            system_fingerprint: self.system_fingerprint,
+            usage: self.usage,
+            truncation: self.truncation,
+            #[cfg(feature = "preserve-unknown")]
+            extra: Default::default(),
+        })
+    }
+}
+
+/// Merges the streamed deltas of one choice.
+///
+/// The merge rules:
+///
+/// - content and reasoning fragments are appended in order;
+/// - tool-call fragments are grouped by their `index`; the ID, type and
+///   function name are taken from whichever fragment carries them (the last
+///   one wins) and argument fragments are appended;
+/// - role, finish reasons and usage keep the last value reported;
+/// - log probabilities and reasoning details are appended.
+///
+/// # Example
+///
+/// ```
+/// use futures::StreamExt;
+/// use openrouter_api::types::chat::DeltaAccumulator;
+///
+/// # async fn run(api: openrouter_api::api::chat::ChatApi, request: openrouter_api::types::chat::ChatCompletionRequest) -> openrouter_api::Result<()> {
+/// let mut stream = api.chat_completion_stream(request);
+/// let mut delta = DeltaAccumulator::new();
+/// while let Some(chunk) = stream.next().await {
+///     delta.push_chunk(chunk?);
+///     print!("\r{}", delta.content());
+/// }
+/// let message = delta.into_message()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default)]
+pub struct DeltaAccumulator {
+    role: Option<String>,
+    content: String,
+    reasoning: Option<String>,
+    reasoning_details: Vec<ReasoningDetail>,
+    tool_calls: std::collections::BTreeMap<u32, ToolCallChunk>,
+    finish_reason: Option<String>,
+    native_finish_reason: Option<String>,
+    logprobs: Option<Vec<TokenLogProb>>,
+    refusal_logprobs: Option<Vec<TokenLogProb>>,
+    usage: Option<Usage>,
+}
+
+impl DeltaAccumulator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an accumulator whose content starts with an assistant
+    /// prefill, which the stream itself does not repeat.
+    pub fn with_prefill(prefill: impl Into<String>) -> Self {
+        Self {
+            content: prefill.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Merges the first choice of `chunk` and records its usage.
+    ///
+    /// Meant for single-choice streams; use [`ChatCompletionAccumulator`]
+    /// when requesting several choices.
+    pub fn push_chunk(&mut self, chunk: ChatCompletionChunk) {
+        if chunk.usage.is_some() {
+            self.usage = chunk.usage;
+        }
+        if let Some(choice) = chunk.choices.into_iter().next() {
+            self.push_choice(choice);
+        }
+    }
+
+    /// Merges a streamed choice: its delta, finish reasons and log
+    /// probabilities.
+    pub fn push_choice(&mut self, choice: ChoiceStream) {
+        self.push_delta(choice.delta);
+        if choice.finish_reason.is_some() {
+            self.finish_reason = choice.finish_reason;
+        }
+        if choice.native_finish_reason.is_some() {
+            self.native_finish_reason = choice.native_finish_reason;
+        }
+        if let Some(logprobs) = choice.logprobs {
+            if let Some(content) = logprobs.content {
+                self.logprobs.get_or_insert_with(Vec::new).extend(content);
+            }
+            if let Some(refusal) = logprobs.refusal {
+                self.refusal_logprobs
+                    .get_or_insert_with(Vec::new)
+                    .extend(refusal);
+            }
+        }
+    }
+
+    /// Merges a delta's role, content, reasoning and tool-call fragments.
+    pub fn push_delta(&mut self, delta: StreamDelta) {
+        if delta.role.is_some() {
+            self.role = delta.role;
+        }
+        if let Some(content) = delta.content {
+            self.content.push_str(&content.to_text());
+        }
+        if let Some(reasoning) = delta.reasoning {
+            self.reasoning
+                .get_or_insert_with(String::new)
+                .push_str(&reasoning);
+        }
+        if let Some(details) = delta.reasoning_details {
+            self.reasoning_details.extend(details);
+        }
+        for call in delta.tool_calls.unwrap_or_default() {
+            self.push_tool_call(call);
+        }
+    }
+
+    /// Merges one tool-call fragment into the call with the same index.
+    pub fn push_tool_call(&mut self, call: ToolCallChunk) {
+        let existing = match self.tool_calls.entry(call.index) {
+            std::collections::btree_map::Entry::Vacant(entry) => {
+                entry.insert(call);
+                return;
+            }
+            std::collections::btree_map::Entry::Occupied(entry) => entry.into_mut(),
+        };
+        if call.id.is_some() {
+            existing.id = call.id;
+        }
+        if call.kind.is_some() {
+            existing.kind = call.kind;
+        }
+        if let Some(function) = call.function {
+            let target = existing.function.get_or_insert_with(Default::default);
+            if function.name.is_some() {
+                target.name = function.name;
+            }
+            if let Some(arguments) = function.arguments {
+                target
+                    .arguments
+                    .get_or_insert_with(String::new)
+                    .push_str(&arguments);
+            }
+        }
+    }
+
+    /// The last role reported, if any.
+    pub fn role(&self) -> Option<&str> {
+        self.role.as_deref()
+    }
+
+    /// The text streamed so far.
+    pub fn content(&self) -> &str {
+        &self.content
+    }
+
+    pub fn reasoning(&self) -> Option<&str> {
+        self.reasoning.as_deref()
+    }
+
+    /// Tool calls merged so far, by index. Arguments may still be partial.
+    pub fn tool_calls(&self) -> impl Iterator<Item = &ToolCallChunk> + '_ {
+        self.tool_calls.values()
+    }
+
+    pub fn finish_reason(&self) -> Option<&str> {
+        self.finish_reason.as_deref()
+    }
+
+    /// The last usage reported by a chunk passed to
+    /// [`push_chunk`](Self::push_chunk).
+    pub fn usage(&self) -> Option<&Usage> {
+        self.usage.as_ref()
+    }
+
+    /// Whether a finish reason has been reported.
+    pub fn is_finished(&self) -> bool {
+        self.finish_reason.is_some()
+    }
+
+    /// Builds the assistant message streamed so far.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::StreamingError` if a tool call never reported its ID
+    /// or function name.
+    pub fn into_message(self) -> crate::error::Result<Message> {
+        Ok(self.into_choice(0)?.message)
+    }
+
+    /// Builds the choice at `index` streamed so far.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::StreamingError` if a tool call never reported its ID
+    /// or function name.
+    pub fn into_choice(self, index: u32) -> crate::error::Result<Choice> {
+        let role = self
+            .role
+            .and_then(|role| serde_json::from_value(serde_json::Value::String(role)).ok())
+            .unwrap_or(ChatRole::Assistant);
+
+        let tool_calls = self
+            .tool_calls
+            .into_values()
+            .map(|call| {
+                let function = call.function.unwrap_or_default();
+                match (call.id, function.name) {
+                    (Some(id), Some(name)) => Ok(ToolCall {
+                        id: ToolCallId::new(id),
+                        kind: call.kind.unwrap_or(crate::models::tool::ToolType::Function),
+                        function_call: crate::models::tool::FunctionCall {
+                            name,
+                            arguments: function.arguments.unwrap_or_default(),
+                        },
+                    }),
+                    _ => Err(crate::error::Error::StreamingError(format!(
+                        "Streamed tool call {} is missing its id or function name",
+                        call.index
+                    ))),
+                }
+            })
+            .collect::<crate::error::Result<Vec<_>>>()?;
+
+        Ok(Choice {
+            message: Message {
+                role,
+                content: MessageContent::Text(self.content),
+                tool_calls: (!tool_calls.is_empty()).then_some(tool_calls),
+                reasoning: self.reasoning,
+                reasoning_details: (!self.reasoning_details.is_empty())
+                    .then_some(self.reasoning_details),
+                ..Default::default()
+            },
+            finish_reason: self.finish_reason,
+            native_finish_reason: self.native_finish_reason,
+            index: Some(index),
+            logprobs: (self.logprobs.is_some() || self.refusal_logprobs.is_some()).then_some(
+                LogProbs {
+                    content: self.logprobs,
+                    refusal: self.refusal_logprobs,
+                },
+            ),
+            #[cfg(feature = "preserve-unknown")]
+            extra: Default::default(),
+        })
+    }
+}
+
+/// Running statistics for a chat completion stream.
+///
+/// Until the final chunk reports usage, completion tokens are estimated from
+/// the streamed text at roughly four bytes per token. Latency is measured
+/// between chunks that carry text, reasoning or tool-call arguments.
+#[derive(Debug, Clone)]
+pub struct StreamStats {
+    started: std::time::Instant,
+    chunks: usize,
+    streamed_bytes: usize,
+    reasoning_bytes: usize,
+    first_token_at: Option<std::time::Duration>,
+    last_token_at: Option<std::time::Duration>,
+    token_gaps: Vec<std::time::Duration>,
+    prompt_tokens: Option<u32>,
+    completion_tokens: Option<u32>,
+    cost: Option<f64>,
+}
+
+impl Default for StreamStats {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StreamStats {
+    /// Creates empty statistics; the elapsed-time clock starts now.
+    pub fn new() -> Self {
+        Self {
+            started: std::time::Instant::now(),
+            chunks: 0,
+            streamed_bytes: 0,
+            reasoning_bytes: 0,
+            first_token_at: None,
+            last_token_at: None,
+            token_gaps: Vec::new(),
+            prompt_tokens: None,
+            completion_tokens: None,
+            cost: None,
+        }
+    }
+
+    /// Records a chunk's text, reasoning and tool-call arguments, and its
+    /// usage if present.
+    pub fn push(&mut self, chunk: &ChatCompletionChunk) {
+        self.chunks += 1;
+        let bytes_before = self.streamed_bytes;
+        for choice in &chunk.choices {
+            let delta = &choice.delta;
+            if let Some(content) = &delta.content {
+                self.streamed_bytes += content.to_text().len();
+            }
+            if let Some(reasoning) = &delta.reasoning {
+                self.streamed_bytes += reasoning.len();
+                self.reasoning_bytes += reasoning.len();
+            }
+            for call in delta.tool_calls.iter().flatten() {
+                if let Some(arguments) = call.function.as_ref().and_then(|f| f.arguments.as_ref()) {
+                    self.streamed_bytes += arguments.len();
+                }
+            }
+        }
+        if self.streamed_bytes > bytes_before {
+            let now = self.started.elapsed();
+            match self.last_token_at {
+                Some(last) => self.token_gaps.push(now.saturating_sub(last)),
+                None => self.first_token_at = Some(now),
+            }
+            self.last_token_at = Some(now);
+        }
+        if let Some(usage) = &chunk.usage {
+            self.prompt_tokens = Some(usage.prompt_tokens);
+            self.completion_tokens = Some(usage.completion_tokens);
+            if usage.cost.is_some() {
+                self.cost = usage.cost;
+            }
+        }
+    }
+
+    /// Number of chunks received so far.
+    pub fn chunks(&self) -> usize {
+        self.chunks
+    }
+
+    /// Time from creation to the first chunk carrying output.
+    pub fn time_to_first_token(&self) -> Option<std::time::Duration> {
+        self.first_token_at
+    }
+
+    /// Percentiles of the gaps between chunks carrying output, or `None`
+    /// until at least two such chunks have arrived.
+    pub fn inter_token_latency(&self) -> Option<LatencyPercentiles> {
+        LatencyPercentiles::from_samples(&self.token_gaps)
+    }
+
+    /// Completion tokens estimated from the text streamed so far.
+    pub fn estimated_tokens(&self) -> u32 {
+        self.streamed_bytes.div_ceil(4) as u32
+    }
+
+    /// The part of `estimated_tokens` streamed on the reasoning channel.
+    pub fn estimated_reasoning_tokens(&self) -> u32 {
+        self.reasoning_bytes.div_ceil(4) as u32
+    }
+
+    /// Completion tokens reported by the API, falling back to the estimate.
+    pub fn completion_tokens(&self) -> u32 {
+        self.completion_tokens
+            .unwrap_or_else(|| self.estimated_tokens())
+    }
+
+    /// Time since the statistics were created.
+    pub fn elapsed(&self) -> std::time::Duration {
+        self.started.elapsed()
+    }
+
+    /// Completion tokens per second over the elapsed time.
+    pub fn tokens_per_second(&self) -> f64 {
+        let seconds = self.elapsed().as_secs_f64();
+        if seconds > 0.0 {
+            f64::from(self.completion_tokens()) / seconds
+        } else {
+            0.0
+        }
+    }
+
+    /// Snapshot of the statistics.
+    pub fn summary(&self) -> StreamSummary {
+        StreamSummary {
+            chunks: self.chunks,
+            elapsed: self.elapsed(),
+            estimated_tokens: self.estimated_tokens(),
+            estimated_reasoning_tokens: self.estimated_reasoning_tokens(),
+            tokens_per_second: self.tokens_per_second(),
+            time_to_first_token: self.time_to_first_token(),
+            inter_token_latency: self.inter_token_latency(),
+            prompt_tokens: self.prompt_tokens,
+            completion_tokens: self.completion_tokens,
+            cost: self.cost,
+        }
+    }
+}
+
+/// Final statistics for a chat completion stream.
+///
+/// `prompt_tokens`, `completion_tokens` and `cost` are `None` when the
+/// stream ended without a usage chunk.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct StreamSummary {
+    pub chunks: usize,
+    pub elapsed: std::time::Duration,
+    pub estimated_tokens: u32,
+    /// The part of `estimated_tokens` streamed as `delta.reasoning`.
+    pub estimated_reasoning_tokens: u32,
+    /// Based on actual completion tokens when reported, else the estimate.
+    pub tokens_per_second: f64,
+    /// `None` if no chunk carried output.
+    pub time_to_first_token: Option<std::time::Duration>,
+    /// `None` if fewer than two chunks carried output.
+    pub inter_token_latency: Option<LatencyPercentiles>,
+    pub prompt_tokens: Option<u32>,
+    pub completion_tokens: Option<u32>,
+    pub cost: Option<f64>,
+}
+
+/// Nearest-rank percentiles of a set of latency samples.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct LatencyPercentiles {
+    pub p50: std::time::Duration,
+    pub p90: std::time::Duration,
+    pub p99: std::time::Duration,
+    pub max: std::time::Duration,
+}
+
+impl LatencyPercentiles {
+    /// Computes the percentiles, or `None` for an empty sample set.
+    pub fn from_samples(samples: &[std::time::Duration]) -> Option<Self> {
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+        let max = *sorted.last()?;
+        let rank = |percentile: usize| {
+            let index = (sorted.len() * percentile).div_ceil(100).max(1) - 1;
+            sorted[index]
+        };
+        Some(Self {
+            p50: rank(50),
+            p90: rank(90),
+            p99: rank(99),
+            max,
+        })
+    }
+}
+
+/// A stream's output so far, passed to `ChatCompletionStream::take_until`.
+#[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
+pub struct StreamProgress<'a> {
+    /// The chunk just received.
+    pub chunk: &'a ChatCompletionChunk,
+    /// Content of choice 0 streamed so far, including this chunk.
+    pub text: &'a str,
+    /// Completion tokens estimated from the output so far, as in
+    /// [`StreamStats::estimated_tokens`].
+    pub estimated_tokens: u32,
+}
+
+/// An item of a chat completion stream with statistics.
+#[derive(Debug)]
+pub enum StreamEvent {
+    /// A chunk from the underlying stream.
+    Chunk(ChatCompletionChunk),
+    /// Emitted once after the last chunk.
+    Summary(StreamSummary),
 }
@@ -0,0 +1,488 @@
+//! Builder for `ChatCompletionRequest`.
+
+use crate::api::request::ResponseFormatConfig;
+use crate::error::{Error, Result};
+use crate::models::provider_preferences::ProviderPreferences;
+use crate::models::tool::{Tool, ToolChoice};
+use crate::types::chat::{
+    ChatCompletionRequest, DebugConfig, LogitBias, Message, Plugin, PredictionConfig,
+    ReasoningConfig, RouteStrategy, StopSequence, VerbosityLevel,
+};
+use crate::types::sampling::{PenaltyValue, Temperature, TopP};
+use crate::utils::validation::{validate_chat_request_all, ValidationReport};
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+/// Builds a [`ChatCompletionRequest`] field by field, checking the result
+/// in [`build`](Self::build).
+///
+/// Temperature, top-p and penalty setters take the range-checked
+/// [`Temperature`], [`TopP`] and [`PenaltyValue`]; create them from floats
+/// with `try_from`, which fails on out-of-range values.
+///
+/// # Example
+///
+/// ```
+/// use openrouter_api::types::chat::{ChatCompletionRequest, ChatRole, Message};
+/// use openrouter_api::types::sampling::Temperature;
+///
+/// let request = ChatCompletionRequest::builder("openai/gpt-4o")
+///     .message(Message::text(ChatRole::User, "Hello!"))
+///     .temperature(Temperature::try_from(0.2)?)
+///     .max_tokens(256)
+///     .build()?;
+/// assert_eq!(request.max_tokens, Some(256));
+/// # Ok::<(), openrouter_api::Error>(())
+/// ```
+#[derive(Debug, Clone, Default)]
+#[must_use = "call build() to get the request"]
+pub struct ChatCompletionRequestBuilder {
+    request: ChatCompletionRequest,
+}
+
+impl ChatCompletionRequest {
+    /// Starts building a request for `model`.
+    pub fn builder(model: impl Into<String>) -> ChatCompletionRequestBuilder {
+        ChatCompletionRequestBuilder::new(model)
+    }
+}
+
+/// Setters that store their argument in the optional field of the same name.
+macro_rules! setters {
+    ($($(#[$doc:meta])* $field:ident: $ty:ty,)*) => {
+        $(
+            $(#[$doc])*
+            pub fn $field(mut self, $field: $ty) -> Self {
+                self.request.$field = Some($field);
+                self
+            }
+        )*
+    };
+}
+
+impl ChatCompletionRequestBuilder {
+    pub fn new(model: impl Into<String>) -> Self {
+        Self {
+            request: ChatCompletionRequest {
+                model: model.into(),
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Replaces the model.
+    pub fn model(mut self, model: impl Into<String>) -> Self {
+        self.request.model = model.into();
+        self
+    }
+
+    /// Replaces the messages.
+    pub fn messages(mut self, messages: Vec<Message>) -> Self {
+        self.request.messages = messages;
+        self
+    }
+
+    /// Appends a message.
+    pub fn message(mut self, message: Message) -> Self {
+        self.request.messages.push(message);
+        self
+    }
+
+    /// Appends a tool.
+    pub fn tool(mut self, tool: Tool) -> Self {
+        self.request.tools.get_or_insert_with(Vec::new).push(tool);
+        self
+    }
+
+    /// Sets the end-user identifier.
+    pub fn user(mut self, user: impl Into<String>) -> Self {
+        self.request.user = Some(user.into());
+        self
+    }
+
+    /// Adds a provider-specific parameter to the top level of the payload;
+    /// see [`ChatCompletionRequest::extra_body`]. A later call with the same
+    /// key replaces the value.
+    pub fn extra_param(mut self, key: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.request
+            .extra_body
+            .get_or_insert_with(Map::new)
+            .insert(key.into(), value.into());
+        self
+    }
+
+    /// Sets the stop sequences, e.g. from a string or an array of strings.
+    pub fn stop(mut self, stop: impl Into<StopSequence>) -> Self {
+        self.request.stop = Some(stop.into());
+        self
+    }
+
+    /// Sets the sampling temperature, see [`Temperature`].
+    pub fn temperature(mut self, temperature: Temperature) -> Self {
+        self.request.temperature = Some(temperature.as_f32());
+        self
+    }
+
+    /// Sets the nucleus sampling threshold, see [`TopP`].
+    pub fn top_p(mut self, top_p: TopP) -> Self {
+        self.request.top_p = Some(top_p.as_f32());
+        self
+    }
+
+    /// Sets the frequency penalty, see [`PenaltyValue`].
+    pub fn frequency_penalty(mut self, penalty: PenaltyValue) -> Self {
+        self.request.frequency_penalty = Some(penalty.as_f32());
+        self
+    }
+
+    /// Sets the presence penalty, see [`PenaltyValue`].
+    pub fn presence_penalty(mut self, penalty: PenaltyValue) -> Self {
+        self.request.presence_penalty = Some(penalty.as_f32());
+        self
+    }
+
+    setters! {
+        /// Sets the response format for structured outputs.
+        response_format: ResponseFormatConfig,
+        /// Replaces the tools.
+        tools: Vec<Tool>,
+        /// Sets the tool choice, e.g. `ToolChoice::Auto` or a specific function; needs tools.
+        tool_choice: ToolChoice,
+        /// Sets provider preferences for routing and fallbacks.
+        provider: ProviderPreferences,
+        /// Sets the fallback models.
+        models: Vec<String>,
+        /// Sets the message transforms.
+        transforms: Vec<String>,
+        /// Sets the route strategy.
+        route: RouteStrategy,
+        /// Sets the maximum number of tokens to generate.
+        max_tokens: u32,
+        /// Sets top-k sampling (0 disables).
+        top_k: u32,
+        /// Sets the repetition penalty (0.0 exclusive to 2.0).
+        repetition_penalty: f32,
+        /// Sets the minimum probability threshold (0.0 to 1.0).
+        min_p: f32,
+        /// Sets the Top-A threshold (0.0 to 1.0).
+        top_a: f32,
+        /// Sets the seed for deterministic sampling.
+        seed: u64,
+        /// Sets the number of choices to generate (1 to 128).
+        n: u32,
+        /// Sets the logit bias.
+        logit_bias: LogitBias,
+        /// Sets whether to return log probabilities.
+        logprobs: bool,
+        /// Sets the number of top log probabilities (0 to 20); needs `logprobs(true)`.
+        top_logprobs: u8,
+        /// Sets the predicted output.
+        prediction: PredictionConfig,
+        /// Sets whether tool calls may run in parallel; needs tools.
+        parallel_tool_calls: bool,
+        /// Sets the response verbosity.
+        verbosity: VerbosityLevel,
+        /// Sets the debug configuration.
+        debug: DebugConfig,
+        /// Sets the plugins.
+        plugins: Vec<Plugin>,
+        /// Sets the reasoning configuration.
+        reasoning: ReasoningConfig,
+    }
+
+    /// Checks the request and returns it.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::ConfigError` describing the first problem if the
+    /// request fails `validate_chat_request` or sets fields that need
+    /// another one: `top_logprobs` without `logprobs`, or `tool_choice` or
+    /// `parallel_tool_calls` without tools. Use [`check`](Self::check) to
+    /// get every problem.
+    pub fn build(self) -> Result<ChatCompletionRequest> {
+        self.check().map_err(ValidationReport::into_error)?;
+        Ok(self.request)
+    }
+
+    /// Reports every problem `build` would reject.
+    pub fn check(&self) -> std::result::Result<(), ValidationReport> {
+        let request = &self.request;
+        let mut report = match validate_chat_request_all(request) {
+            Ok(()) => ValidationReport::default(),
+            Err(report) => report,
+        };
+
+        if request.top_logprobs.is_some() && request.logprobs != Some(true) {
+            report.push(
+                "top_logprobs",
+                "requires_field",
+                "top_logprobs requires logprobs to be enabled",
+            );
+        }
+        let has_tools = request
+            .tools
+            .as_ref()
+            .is_some_and(|tools| !tools.is_empty());
+        if request.tool_choice.is_some() && !has_tools {
+            report.push(
+                "tool_choice",
+                "requires_field",
+                "tool_choice requires tools",
+            );
+        }
+        if request.parallel_tool_calls.is_some() && !has_tools {
+            report.push(
+                "parallel_tool_calls",
+                "requires_field",
+                "parallel_tool_calls requires tools",
+            );
+        }
+        report.into_result()
+    }
+}
+
+/// Parameters of a `RequestBuilder`'s extra params that map onto
+/// `ChatCompletionRequest` fields; the rest go to `extra_body`.
+#[derive(Deserialize, Default)]
+struct ExtraParams {
+    tool_choice: Option<ToolChoice>,
+    provider: Option<ProviderPreferences>,
+    models: Option<Vec<String>>,
+    transforms: Option<Vec<String>>,
+    route: Option<RouteStrategy>,
+    user: Option<String>,
+    max_tokens: Option<u32>,
+    temperature: Option<f32>,
+    top_p: Option<f32>,
+    top_k: Option<u32>,
+    frequency_penalty: Option<f32>,
+    presence_penalty: Option<f32>,
+    repetition_penalty: Option<f32>,
+    min_p: Option<f32>,
+    top_a: Option<f32>,
+    seed: Option<u64>,
+    n: Option<u32>,
+    stop: Option<StopSequence>,
+    logit_bias: Option<LogitBias>,
+    logprobs: Option<bool>,
+    top_logprobs: Option<u8>,
+    prediction: Option<PredictionConfig>,
+    parallel_tool_calls: Option<bool>,
+    verbosity: Option<VerbosityLevel>,
+    debug: Option<DebugConfig>,
+    reasoning: Option<ReasoningConfig>,
+    #[serde(flatten)]
+    extra_body: Map<String, Value>,
+}
+
+impl<T: serde::Serialize> TryFrom<crate::api::request::RequestBuilder<T>>
+    for ChatCompletionRequestBuilder
+{
+    type Error = Error;
+
+    /// Carries over the model, messages (including any assistant prefill),
+    /// structured output and tools, and the extra params that name request
+    /// fields. Any other extra param is passed through in `extra_body`.
+    fn try_from(builder: crate::api::request::RequestBuilder<T>) -> Result<Self> {
+        let payload = builder.build();
+        let extra =
+            match serde_json::to_value(&payload.extra_params).map_err(Error::SerializationError)? {
+                Value::Null => ExtraParams::default(),
+                value => serde_json::from_value(value)
+                    .map_err(|e| Error::ConfigError(format!("Invalid extra parameter: {e}")))?,
+            };
+
+        Ok(Self {
+            request: ChatCompletionRequest {
+                model: payload.model,
+                messages: payload.messages,
+                response_format: payload.response_format,
+                tools: payload.tools,
+                tool_choice: extra.tool_choice,
+                provider: extra.provider,
+                models: extra.models,
+                transforms: extra.transforms,
+                route: extra.route,
+                user: extra.user,
+                max_tokens: extra.max_tokens,
+                temperature: extra.temperature,
+                top_p: extra.top_p,
+                top_k: extra.top_k,
+                frequency_penalty: extra.frequency_penalty,
+                presence_penalty: extra.presence_penalty,
+                repetition_penalty: extra.repetition_penalty,
+                min_p: extra.min_p,
+                top_a: extra.top_a,
+                seed: extra.seed,
+                n: extra.n,
+                stop: extra.stop,
+                logit_bias: extra.logit_bias,
+                logprobs: extra.logprobs,
+                top_logprobs: extra.top_logprobs,
+                prediction: extra.prediction,
+                parallel_tool_calls: extra.parallel_tool_calls,
+                verbosity: extra.verbosity,
+                debug: extra.debug,
+                reasoning: extra.reasoning,
+                extra_body: (!extra.extra_body.is_empty()).then_some(extra.extra_body),
+                stream: None,
+                plugins: None,
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::request::RequestBuilder;
+    use crate::types::chat::ChatRole;
+    use serde_json::json;
+
+    #[test]
+    fn test_builder_sets_fields_and_checks_cross_field_rules() {
+        let request = ChatCompletionRequest::builder("openai/gpt-4o")
+            .message(Message::text(ChatRole::User, "Hi"))
+            .temperature(Temperature::try_from(0.5).unwrap())
+            .stop("END")
+            .logprobs(true)
+            .top_logprobs(5)
+            .build()
+            .unwrap();
+        assert_eq!(request.temperature, Some(0.5));
+        assert_eq!(request.stop, Some(StopSequence::from("END")));
+        assert_eq!(request.top_logprobs, Some(5));
+
+        let report = ChatCompletionRequest::builder("openai/gpt-4o")
+            .message(Message::text(ChatRole::User, "Hi"))
+            .n(0)
+            .top_logprobs(5)
+            .tool_choice(ToolChoice::Auto)
+            .check()
+            .unwrap_err();
+        let fields: Vec<_> = report.violations.iter().map(|v| v.field.as_str()).collect();
+        assert_eq!(fields, ["n", "top_logprobs", "tool_choice"]);
+
+        assert!(matches!(
+            ChatCompletionRequest::builder("openai/gpt-4o").build(),
+            Err(Error::ConfigError(_))
+        ));
+    }
+
+    #[test]
+    fn test_tool_choice_serializes_and_must_name_a_declared_function() {
+        for (choice, wire) in [
+            (ToolChoice::Auto, json!("auto")),
+            (ToolChoice::None, json!("none")),
+            (ToolChoice::Required, json!("required")),
+            (
+                ToolChoice::function("get_weather"),
+                json!({"type": "function", "function": {"name": "get_weather"}}),
+            ),
+        ] {
+            assert_eq!(serde_json::to_value(&choice).unwrap(), wire);
+            assert_eq!(serde_json::from_value::<ToolChoice>(wire).unwrap(), choice);
+        }
+        assert!(serde_json::from_value::<ToolChoice>(json!("sometimes")).is_err());
+
+        let weather = Tool::Function {
+            function: crate::models::tool::FunctionDescription {
+                name: "get_weather".into(),
+                description: None,
+                parameters: json!({"type": "object"}),
+                strict: None,
+            },
+        };
+        let builder = ChatCompletionRequest::builder("openai/gpt-4o")
+            .message(Message::text(ChatRole::User, "Weather?"))
+            .tools(vec![weather]);
+        let request = builder
+            .clone()
+            .tool_choice(ToolChoice::function("get_weather"))
+            .build()
+            .unwrap();
+        assert_eq!(
+            serde_json::to_value(&request).unwrap()["tool_choice"]["function"]["name"],
+            "get_weather"
+        );
+
+        let report = builder
+            .tool_choice(ToolChoice::function("get_time"))
+            .check()
+            .unwrap_err();
+        assert_eq!(report.violations.len(), 1);
+        assert_eq!(report.violations[0].code, "unknown_function");
+    }
+
+    #[test]
+    fn test_builder_from_request_builder() {
+        let messages = vec![Message::text(ChatRole::User, "Hi")];
+        let builder = RequestBuilder::new(
+            "openai/gpt-4o",
+            messages,
+            json!({"temperature": 0.3, "max_tokens": 64, "stop": ["a", "b"]}),
+        );
+        let request = ChatCompletionRequestBuilder::try_from(builder)
+            .unwrap()
+            .seed(7)
+            .build()
+            .unwrap();
+        assert_eq!(request.model, "openai/gpt-4o");
+        assert_eq!(request.messages.len(), 1);
+        assert_eq!(request.temperature, Some(0.3));
+        assert_eq!(request.max_tokens, Some(64));
+        assert_eq!(request.seed, Some(7));
+
+        let builder = RequestBuilder::new(
+            "openai/gpt-4o",
+            vec![],
+            json!({"top_k": 40, "safe_prompt": true}),
+        );
+        let request = ChatCompletionRequestBuilder::try_from(builder).unwrap();
+        assert_eq!(request.request.top_k, Some(40));
+        assert_eq!(
+            request.request.extra_body,
+            Some(json!({"safe_prompt": true}).as_object().unwrap().clone())
+        );
+
+        let builder = RequestBuilder::new("openai/gpt-4o", vec![], json!({"top_k": "high"}));
+        assert!(ChatCompletionRequestBuilder::try_from(builder).is_err());
+    }
+
+    #[test]
+    fn test_extra_params_merge_into_payload_and_conflicts_fail() {
+        let request = ChatCompletionRequest::builder("mistralai/mistral-large")
+            .message(Message::text(ChatRole::User, "Hi"))
+            .extra_param("safe_prompt", true)
+            .extra_param("thinking", json!({"type": "enabled"}))
+            .build()
+            .unwrap();
+        let value = serde_json::to_value(&request).unwrap();
+        assert_eq!(value["safe_prompt"], json!(true));
+        assert_eq!(value["thinking"]["type"], "enabled");
+        assert!(value.get("extra_body").is_none());
+
+        let report = ChatCompletionRequest::builder("openai/gpt-4o")
+            .message(Message::text(ChatRole::User, "Hi"))
+            .temperature(Temperature::try_from(0.5).unwrap())
+            .extra_param("temperature", 1.0)
+            .extra_param("messages", json!([]))
+            .check()
+            .unwrap_err();
+        let fields: Vec<_> = report
+            .violations
+            .iter()
+            .map(|violation| violation.field.as_str())
+            .collect();
+        assert_eq!(fields, ["extra_body.messages", "extra_body.temperature"]);
+
+        let builder = RequestBuilder::new("openai/gpt-4o", vec![], json!({}))
+            .with_extra_param("safe_prompt", true)
+            .unwrap();
+        assert_eq!(builder.build().extra_params["safe_prompt"], json!(true));
+        let builder = RequestBuilder::new("openai/gpt-4o", vec![], json!({"seed": 1}));
+        assert!(builder.with_extra_param("seed", 2).is_err());
+        let builder = RequestBuilder::new("openai/gpt-4o", vec![], json!({}));
+        assert!(builder.with_extra_param("tools", json!([])).is_err());
+    }
+}
@@ -0,0 +1,261 @@
+//! Results of running one request against several models side by side.
+
+use crate::error::Error;
+use crate::types::chat::ChatCompletionResponse;
+use std::time::Duration;
+
+/// The outcome of sending the comparison request to a single model.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct ModelRun {
+    /// The model the request was sent to.
+    pub model: String,
+    /// The response, or the error the call failed with.
+    pub result: Result<ChatCompletionResponse, Error>,
+    /// Wall-clock time from sending the request to receiving the response.
+    pub latency: Duration,
+    /// Cost estimated from the model's catalog pricing and the reported
+    /// token counts, set when the response carried no cost.
+    pub estimated_cost: Option<f64>,
+}
+
+impl ModelRun {
+    /// Returns the response if the call succeeded.
+    pub fn response(&self) -> Option<&ChatCompletionResponse> {
+        self.result.as_ref().ok()
+    }
+
+    /// Whether the call succeeded.
+    pub fn is_success(&self) -> bool {
+        self.result.is_ok()
+    }
+
+    /// Cost reported by OpenRouter in the response usage, falling back to
+    /// [`estimated_cost`](Self::estimated_cost).
+    pub fn cost(&self) -> Option<f64> {
+        self.reported_cost().or(self.estimated_cost)
+    }
+
+    /// Cost reported by OpenRouter in the response usage, if any.
+    pub fn reported_cost(&self) -> Option<f64> {
+        self.response()?.usage.as_ref()?.cost
+    }
+
+    /// Total tokens reported in the response usage, if any.
+    pub fn total_tokens(&self) -> Option<u32> {
+        self.response()?
+            .usage
+            .as_ref()
+            .map(|usage| usage.total_tokens)
+    }
+}
+
+/// Side-by-side results of one request fanned out to several models.
+///
+/// Runs are kept in the order the models were given.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct ModelComparison {
+    pub runs: Vec<ModelRun>,
+}
+
+impl ModelComparison {
+    /// Returns the run for `model`, if it was part of the comparison.
+    pub fn get(&self, model: &str) -> Option<&ModelRun> {
+        self.runs.iter().find(|run| run.model == model)
+    }
+
+    /// Iterates over the runs that succeeded.
+    pub fn successful(&self) -> impl Iterator<Item = &ModelRun> {
+        self.runs.iter().filter(|run| run.is_success())
+    }
+
+    /// Iterates over the runs that failed.
+    pub fn failed(&self) -> impl Iterator<Item = &ModelRun> {
+        self.runs.iter().filter(|run| !run.is_success())
+    }
+
+    /// The successful run with the lowest latency.
+    pub fn fastest(&self) -> Option<&ModelRun> {
+        self.successful().min_by_key(|run| run.latency)
+    }
+
+    /// The successful run with the lowest cost, reported or estimated.
+    ///
+    /// Runs without either are ignored.
+    pub fn cheapest(&self) -> Option<&ModelRun> {
+        self.successful()
+            .filter_map(|run| run.cost().map(|cost| (run, cost)))
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(run, _)| run)
+    }
+
+    /// Sum of the reported or estimated costs across all runs.
+    pub fn total_cost(&self) -> f64 {
+        self.runs.iter().filter_map(ModelRun::cost).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::chat::ChatApi;
+    use crate::error::Error;
+    use crate::tests::test_helpers::test_client_config;
+    use crate::types::chat::{ChatCompletionRequest, ChatRole, Message};
+    use serde_json::json;
+    use wiremock::{matchers, Mock, MockServer, ResponseTemplate};
+
+    fn response_for(model: &str, cost: f64) -> serde_json::Value {
+        json!({
+            "id": format!("gen-{model}"),
+            "choices": [{
+                "message": {"role": "assistant", "content": format!("from {model}")},
+                "finish_reason": "stop"
+            }],
+            "created": 0,
+            "model": model,
+            "object": "chat.completion",
+            "usage": {"prompt_tokens": 3, "completion_tokens": 4, "total_tokens": 7, "cost": cost}
+        })
+    }
+
+    #[tokio::test]
+    async fn test_compare_models_fans_out_and_records_failures() {
+        let mock_server = MockServer::start().await;
+        for (model, cost) in [("openai/gpt-4o", 0.003), ("openai/gpt-4o-mini", 0.0004)] {
+            Mock::given(matchers::method("POST"))
+                .and(matchers::path("/chat/completions"))
+                .and(matchers::body_partial_json(json!({"model": model})))
+                .respond_with(ResponseTemplate::new(200).set_body_json(response_for(model, cost)))
+                .expect(1)
+                .mount(&mock_server)
+                .await;
+        }
+        Mock::given(matchers::method("POST"))
+            .and(matchers::path("/chat/completions"))
+            .and(matchers::body_partial_json(
+                json!({"model": "broken/model"}),
+            ))
+            .respond_with(ResponseTemplate::new(400).set_body_json(json!({
+                "error": {"code": 400, "message": "unknown model"}
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let mut config = test_client_config();
+        config.base_url = format!("{}/", mock_server.uri()).parse().unwrap();
+        let chat = ChatApi::new(reqwest::Client::new(), &config).unwrap();
+
+        let request = ChatCompletionRequest {
+            model: "ignored".to_string(),
+            messages: vec![Message::text(ChatRole::User, "hello")],
+            models: Some(vec!["fallback/model".to_string()]),
+            ..Default::default()
+        };
+        let comparison = chat
+            .compare_models(
+                request,
+                &["openai/gpt-4o", "openai/gpt-4o-mini", "broken/model"],
+            )
+            .await
+            .unwrap();
+
+        let models: Vec<_> = comparison.runs.iter().map(|r| r.model.as_str()).collect();
+        assert_eq!(
+            models,
+            vec!["openai/gpt-4o", "openai/gpt-4o-mini", "broken/model"]
+        );
+        assert_eq!(comparison.successful().count(), 2);
+        assert_eq!(comparison.failed().count(), 1);
+        assert_eq!(
+            comparison
+                .get("openai/gpt-4o")
+                .unwrap()
+                .response()
+                .unwrap()
+                .text(),
+            Some("from openai/gpt-4o")
+        );
+        assert_eq!(comparison.cheapest().unwrap().model, "openai/gpt-4o-mini");
+        assert!(comparison.fastest().is_some());
+        assert!((comparison.total_cost() - 0.0034).abs() < 1e-9);
+        assert_eq!(
+            comparison.get("openai/gpt-4o").unwrap().total_tokens(),
+            Some(7)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_compare_models_estimates_missing_costs_from_pricing() {
+        let mock_server = MockServer::start().await;
+        let mut body = response_for("openai/gpt-4o", 0.0);
+        body["usage"].as_object_mut().unwrap().remove("cost");
+        Mock::given(matchers::method("POST"))
+            .and(matchers::path("/chat/completions"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(body))
+            .mount(&mock_server)
+            .await;
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/models"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "data": [{
+                    "id": "openai/gpt-4o",
+                    "name": "GPT-4o",
+                    "description": null,
+                    "created": 0,
+                    "context_length": 128000,
+                    "architecture": {
+                        "modality": "text->text",
+                        "input_modalities": ["text"],
+                        "output_modalities": ["text"],
+                        "tokenizer": "GPT",
+                        "instruct_type": null
+                    },
+                    "pricing": {"prompt": "0.001", "completion": "0.002"},
+                    "top_provider": {
+                        "context_length": 128000,
+                        "max_completion_tokens": null,
+                        "is_moderated": false
+                    },
+                    "per_request_limits": null
+                }]
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let mut config = test_client_config();
+        config.base_url = format!("{}/", mock_server.uri()).parse().unwrap();
+        let chat = ChatApi::new(reqwest::Client::new(), &config).unwrap();
+
+        let request = ChatCompletionRequest {
+            model: "ignored".to_string(),
+            messages: vec![Message::text(ChatRole::User, "hello")],
+            ..Default::default()
+        };
+        let comparison = chat
+            .compare_models(request, &["openai/gpt-4o"])
+            .await
+            .unwrap();
+
+        let run = comparison.get("openai/gpt-4o").unwrap();
+        assert_eq!(run.reported_cost(), None);
+        // 3 prompt tokens at 0.001 plus 4 completion tokens at 0.002.
+        assert!((run.cost().unwrap() - 0.011).abs() < 1e-9);
+        assert_eq!(comparison.cheapest().unwrap().model, "openai/gpt-4o");
+    }
+
+    #[tokio::test]
+    async fn test_compare_models_requires_models() {
+        let chat = ChatApi::new(reqwest::Client::new(), &test_client_config()).unwrap();
+        let request = ChatCompletionRequest {
+            model: "openai/gpt-4o".to_string(),
+            messages: vec![Message::text(ChatRole::User, "hello")],
+            ..Default::default()
+        };
+        assert!(matches!(
+            chat.compare_models(request, &[]).await,
+            Err(Error::ValidationError(_))
+        ));
+    }
+}
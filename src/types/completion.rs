@@ -1,3 +1,8 @@
+use crate::error::{Error, Result};
+use crate::models::provider_preferences::ProviderPreferences;
+use crate::types::chat::{LogitBias, StopSequence};
+use crate::types::sampling::{PenaltyValue, Temperature, TopP};
+use crate::utils::validation::validate_completion_request;
 use serde::{Deserialize, Serialize};
 
 /// Represents a text completion request. It minimally contains:
@@ -6,16 +11,228 @@ use serde::{Deserialize, Serialize};
 ///
 /// Any extra parameters (e.g., `temperature`, `top_p`, etc.) can also be provided and will be flattened
 /// into the resulting JSON.
-#[derive(Debug, Serialize, PartialEq, Eq)]
+#[derive(Debug, Default, Serialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 pub struct CompletionRequest {
     pub model: String,
     pub prompt: String,
+    /// Prepends the prompt to each returned completion.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub echo: Option<bool>,
+    /// Text that follows the completion, for fill-in-the-middle models.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suffix: Option<String>,
+    /// Generates this many completions server-side and returns the best `n`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub best_of: Option<u32>,
+    /// Number of completions to return.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub n: Option<u32>,
     #[serde(flatten)]
     pub extra_params: serde_json::Value,
 }
 
+impl CompletionRequest {
+    /// Starts building a request for `model` and `prompt`.
+    pub fn builder(
+        model: impl Into<String>,
+        prompt: impl Into<String>,
+    ) -> CompletionRequestBuilder {
+        CompletionRequestBuilder::new(model, prompt)
+    }
+}
+
+/// Builds a [`CompletionRequest`], mirroring `ChatCompletionRequestBuilder`.
+///
+/// Parameters without a field on `CompletionRequest` are collected into its
+/// `extra_params`. [`build`](Self::build) runs `validate_completion_request`.
+///
+/// # Example
+///
+/// ```
+/// use openrouter_api::types::completion::CompletionRequest;
+/// use openrouter_api::types::sampling::Temperature;
+///
+/// let request = CompletionRequest::builder("openai/gpt-3.5-turbo-instruct", "Once upon a time")
+///     .temperature(Temperature::try_from(0.7)?)
+///     .max_tokens(64)
+///     .stop("\n\n")
+///     .build()?;
+/// assert_eq!(request.extra_params["max_tokens"], 64);
+/// # Ok::<(), openrouter_api::Error>(())
+/// ```
+#[derive(Debug, Default)]
+#[must_use = "call build() to get the request"]
+pub struct CompletionRequestBuilder {
+    request: CompletionRequest,
+    params: serde_json::Map<String, serde_json::Value>,
+    stop: Option<StopSequence>,
+    logit_bias: Option<LogitBias>,
+    provider: Option<ProviderPreferences>,
+}
+
+impl CompletionRequestBuilder {
+    pub fn new(model: impl Into<String>, prompt: impl Into<String>) -> Self {
+        Self {
+            request: CompletionRequest {
+                model: model.into(),
+                prompt: prompt.into(),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    /// Replaces the model.
+    pub fn model(mut self, model: impl Into<String>) -> Self {
+        self.request.model = model.into();
+        self
+    }
+
+    /// Replaces the prompt.
+    pub fn prompt(mut self, prompt: impl Into<String>) -> Self {
+        self.request.prompt = prompt.into();
+        self
+    }
+
+    /// Prepends the prompt to each returned completion.
+    pub fn echo(mut self, echo: bool) -> Self {
+        self.request.echo = Some(echo);
+        self
+    }
+
+    /// Sets the text following the completion, for fill-in-the-middle models.
+    pub fn suffix(mut self, suffix: impl Into<String>) -> Self {
+        self.request.suffix = Some(suffix.into());
+        self
+    }
+
+    /// Generates `best_of` completions server-side and returns the best `n`.
+    pub fn best_of(mut self, best_of: u32) -> Self {
+        self.request.best_of = Some(best_of);
+        self
+    }
+
+    /// Sets the number of completions to return (1 to 128).
+    pub fn n(mut self, n: u32) -> Self {
+        self.request.n = Some(n);
+        self
+    }
+
+    /// Sets the stop sequences, e.g. from a string or an array of strings.
+    pub fn stop(mut self, stop: impl Into<StopSequence>) -> Self {
+        self.stop = Some(stop.into());
+        self
+    }
+
+    /// Sets the logit bias.
+    pub fn logit_bias(mut self, logit_bias: LogitBias) -> Self {
+        self.logit_bias = Some(logit_bias);
+        self
+    }
+
+    /// Sets provider preferences for routing and fallbacks; they are
+    /// validated by `build`.
+    pub fn provider(mut self, provider: ProviderPreferences) -> Self {
+        self.provider = Some(provider);
+        self
+    }
+
+    /// Sets the end-user identifier.
+    pub fn user(self, user: impl Into<String>) -> Self {
+        self.param("user", user.into())
+    }
+
+    /// Sets the maximum number of tokens to generate.
+    pub fn max_tokens(self, max_tokens: u32) -> Self {
+        self.param("max_tokens", max_tokens)
+    }
+
+    /// Sets the sampling temperature, see [`Temperature`].
+    pub fn temperature(self, temperature: Temperature) -> Self {
+        self.param("temperature", temperature.as_f64())
+    }
+
+    /// Sets the nucleus sampling threshold, see [`TopP`].
+    pub fn top_p(self, top_p: TopP) -> Self {
+        self.param("top_p", top_p.as_f64())
+    }
+
+    /// Sets top-k sampling (0 disables).
+    pub fn top_k(self, top_k: u32) -> Self {
+        self.param("top_k", top_k)
+    }
+
+    /// Sets the frequency penalty, see [`PenaltyValue`].
+    pub fn frequency_penalty(self, penalty: PenaltyValue) -> Self {
+        self.param("frequency_penalty", penalty.as_f64())
+    }
+
+    /// Sets the presence penalty, see [`PenaltyValue`].
+    pub fn presence_penalty(self, penalty: PenaltyValue) -> Self {
+        self.param("presence_penalty", penalty.as_f64())
+    }
+
+    /// Sets the repetition penalty (0.0 exclusive to 2.0).
+    pub fn repetition_penalty(self, repetition_penalty: f64) -> Self {
+        self.param("repetition_penalty", repetition_penalty)
+    }
+
+    /// Sets the seed for deterministic sampling.
+    pub fn seed(self, seed: u64) -> Self {
+        self.param("seed", seed)
+    }
+
+    /// Sets the number of log probabilities to return (0 to 5).
+    pub fn logprobs(self, logprobs: u8) -> Self {
+        self.param("logprobs", logprobs)
+    }
+
+    /// Sets any other parameter of the completions endpoint.
+    pub fn param(mut self, name: impl Into<String>, value: impl Into<serde_json::Value>) -> Self {
+        self.params.insert(name.into(), value.into());
+        self
+    }
+
+    /// Checks the request and returns it.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::ConfigError` if the request fails
+    /// `validate_completion_request` or the provider preferences are invalid.
+    pub fn build(mut self) -> Result<CompletionRequest> {
+        if let Some(provider) = self.provider {
+            provider.validate()?;
+            self.params.insert(
+                "provider".into(),
+                serde_json::to_value(provider).map_err(Error::SerializationError)?,
+            );
+        }
+        if let Some(stop) = self.stop {
+            self.params.insert(
+                "stop".into(),
+                serde_json::to_value(stop).map_err(Error::SerializationError)?,
+            );
+        }
+        if let Some(logit_bias) = self.logit_bias {
+            self.params.insert(
+                "logit_bias".into(),
+                serde_json::to_value(logit_bias).map_err(Error::SerializationError)?,
+            );
+        }
+
+        let mut request = self.request;
+        if !self.params.is_empty() {
+            request.extra_params = serde_json::Value::Object(self.params);
+        }
+        validate_completion_request(&request)?;
+        Ok(request)
+    }
+}
+
 /// Represents a choice returned by the completions endpoint.
 #[derive(Debug, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 pub struct CompletionChoice {
     pub text: String,
     pub index: Option<u32>,
@@ -27,7 +244,60 @@ pub struct CompletionChoice {
 /// - an optional `id` for the request
 /// - a list of choices with the completed text
 #[derive(Debug, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 pub struct CompletionResponse {
     pub id: Option<String>,
     pub choices: Vec<CompletionChoice>,
 }
+
+impl CompletionResponse {
+    /// Returns the text of the first choice, or `None` if there are no choices.
+    pub fn text(&self) -> Option<&str> {
+        self.choices.first().map(|choice| choice.text.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::provider_preferences::ProviderSort;
+
+    #[test]
+    fn test_completion_builder_collects_and_validates_params() {
+        let request = CompletionRequest::builder("mistralai/codestral-2501", "fn main() {")
+            .suffix("}")
+            .n(2)
+            .best_of(3)
+            .temperature(Temperature::try_from(0.5).unwrap())
+            .stop(["\n\n", "}"])
+            .provider(ProviderPreferences {
+                sort: Some(ProviderSort::Price),
+                ..Default::default()
+            })
+            .param("transforms", serde_json::json!(["middle-out"]))
+            .build()
+            .unwrap();
+        assert_eq!(request.suffix.as_deref(), Some("}"));
+        assert_eq!(request.extra_params["temperature"], 0.5);
+        assert_eq!(
+            request.extra_params["stop"],
+            serde_json::json!(["\n\n", "}"])
+        );
+        assert_eq!(request.extra_params["provider"]["sort"], "price");
+
+        for builder in [
+            CompletionRequest::builder("openai/gpt-3.5-turbo-instruct", "Hi")
+                .param("temperature", 3.0),
+            CompletionRequest::builder("openai/gpt-3.5-turbo-instruct", "Hi").stop(""),
+            CompletionRequest::builder("openai/gpt-3.5-turbo-instruct", "Hi")
+                .suffix("!")
+                .echo(true),
+            CompletionRequest::builder("openai/gpt-3.5-turbo-instruct", "Hi")
+                .n(3)
+                .best_of(2),
+            CompletionRequest::builder("openai/gpt-3.5-turbo-instruct", ""),
+        ] {
+            assert!(builder.build().is_err());
+        }
+    }
+}
@@ -0,0 +1,251 @@
+//! Chat sessions that can be saved and restored.
+//!
+//! A [`Conversation`] keeps the message history of a chat session, including
+//! tool calls and tool results, together with running usage totals. It is
+//! persisted as JSON with a `version` field so files written by older
+//! releases keep loading:
+//!
+//! ```json
+//! {"version": 1, "messages": [...], "usage": {"prompt_tokens": 12, ...}}
+//! ```
+//!
+//! Version history:
+//!
+//! - unversioned: a bare JSON array of messages, i.e. a serialized
+//!   `Vec<Message>`. Loaded with zeroed usage totals.
+//! - `1`: messages plus usage totals.
+
+use crate::error::{Error, Result};
+use crate::types::chat::{ChatCompletionRequest, ChatCompletionResponse, Message};
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+
+/// Schema version written by [`Conversation::save`].
+pub const CONVERSATION_SCHEMA_VERSION: u32 = 1;
+
+/// Token and cost totals accumulated over a conversation.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ConversationUsage {
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+    pub total_tokens: u64,
+    /// Cost in credits, summed over responses that reported one.
+    pub cost: f64,
+}
+
+/// The message history and usage of a chat session.
+///
+/// # Example
+///
+/// ```
+/// use openrouter_api::types::chat::{ChatRole, Message};
+/// use openrouter_api::types::conversation::Conversation;
+///
+/// let mut conversation = Conversation::new();
+/// conversation.push(Message::text(ChatRole::User, "Hello"));
+///
+/// let mut saved = Vec::new();
+/// conversation.save(&mut saved)?;
+/// let restored = Conversation::load(saved.as_slice())?;
+/// assert_eq!(restored, conversation);
+/// # Ok::<(), openrouter_api::error::Error>(())
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Conversation {
+    messages: Vec<Message>,
+    #[serde(default)]
+    usage: ConversationUsage,
+}
+
+/// On-disk layout: the conversation tagged with its schema version.
+#[derive(Serialize)]
+struct VersionedRef<'a> {
+    version: u32,
+    #[serde(flatten)]
+    conversation: &'a Conversation,
+}
+
+impl Conversation {
+    /// Creates an empty conversation.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts a conversation from existing messages.
+    pub fn from_messages(messages: Vec<Message>) -> Self {
+        Self {
+            messages,
+            usage: ConversationUsage::default(),
+        }
+    }
+
+    /// Appends a message.
+    pub fn push(&mut self, message: Message) {
+        self.messages.push(message);
+    }
+
+    /// Appends the response's first choice and adds its usage to the totals.
+    pub fn record_response(&mut self, response: &ChatCompletionResponse) {
+        if let Some(choice) = response.first_choice() {
+            self.messages.push(choice.message.clone());
+        }
+        if let Some(usage) = &response.usage {
+            self.usage.prompt_tokens += u64::from(usage.prompt_tokens);
+            self.usage.completion_tokens += u64::from(usage.completion_tokens);
+            self.usage.total_tokens += u64::from(usage.total_tokens);
+            self.usage.cost += usage.cost.unwrap_or(0.0);
+        }
+    }
+
+    /// The messages so far, oldest first.
+    pub fn messages(&self) -> &[Message] {
+        &self.messages
+    }
+
+    /// Usage totals over the recorded responses.
+    pub fn usage(&self) -> &ConversationUsage {
+        &self.usage
+    }
+
+    /// Builds a request continuing the conversation with `model`.
+    pub fn to_request(&self, model: impl Into<String>) -> ChatCompletionRequest {
+        ChatCompletionRequest {
+            model: model.into(),
+            messages: self.messages.clone(),
+            ..Default::default()
+        }
+    }
+
+    /// Writes the conversation as JSON in the current schema version.
+    pub fn save<W: Write>(&self, writer: W) -> Result<()> {
+        serde_json::to_writer(
+            writer,
+            &VersionedRef {
+                version: CONVERSATION_SCHEMA_VERSION,
+                conversation: self,
+            },
+        )
+        .map_err(Error::SerializationError)
+    }
+
+    /// Reads a conversation written by [`save`](Self::save) in this or any
+    /// earlier schema version.
+    ///
+    /// Returns `Error::ConfigError` for versions newer than this release
+    /// understands, and `Error::SerializationError` for malformed JSON.
+    pub fn load<R: Read>(reader: R) -> Result<Self> {
+        let value: serde_json::Value =
+            serde_json::from_reader(reader).map_err(Error::SerializationError)?;
+        Self::migrate(value)
+    }
+
+    /// Upgrades a stored conversation of any known version.
+    fn migrate(mut value: serde_json::Value) -> Result<Self> {
+        if value.is_array() {
+            let messages = serde_json::from_value(value).map_err(Error::SerializationError)?;
+            return Ok(Self::from_messages(messages));
+        }
+
+        let version = value
+            .as_object_mut()
+            .and_then(|object| object.remove("version"))
+            .and_then(|version| version.as_u64())
+            .ok_or_else(|| {
+                Error::ConfigError("Conversation JSON is missing a numeric 'version'".into())
+            })?;
+        match version {
+            1 => serde_json::from_value(value).map_err(Error::SerializationError),
+            _ => Err(Error::ConfigError(format!(
+                "Unsupported conversation schema version {version} (newest supported: {CONVERSATION_SCHEMA_VERSION})"
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::tool::{FunctionCall, ToolCall, ToolType};
+    use crate::types::chat::ChatRole;
+    use crate::types::ids::ToolCallId;
+
+    fn conversation_with_tool_call() -> Conversation {
+        let mut conversation = Conversation::new();
+        conversation.push(Message::text(ChatRole::User, "Weather in Oslo?"));
+        conversation.push(Message {
+            role: ChatRole::Assistant,
+            tool_calls: Some(vec![ToolCall {
+                id: ToolCallId::new("call-1"),
+                kind: ToolType::Function,
+                function_call: FunctionCall {
+                    name: "get_weather".into(),
+                    arguments: r#"{"city":"Oslo"}"#.into(),
+                },
+            }]),
+            ..Default::default()
+        });
+        conversation.push(Message {
+            role: ChatRole::Tool,
+            tool_call_id: Some(ToolCallId::new("call-1")),
+            ..Message::text(ChatRole::Tool, "Sunny")
+        });
+        conversation
+    }
+
+    #[test]
+    fn test_save_and_load_round_trip() {
+        let mut conversation = conversation_with_tool_call();
+        let response: ChatCompletionResponse = serde_json::from_value(serde_json::json!({
+            "id": "gen-1",
+            "object": "chat.completion",
+            "created": 0,
+            "model": "openai/gpt-4o",
+            "choices": [{"message": {"role": "assistant", "content": "It is sunny."}}],
+            "usage": {"prompt_tokens": 20, "completion_tokens": 5, "total_tokens": 25, "cost": 0.001}
+        }))
+        .unwrap();
+        conversation.record_response(&response);
+        assert_eq!(conversation.messages().len(), 4);
+        assert_eq!(conversation.usage().total_tokens, 25);
+
+        let mut saved = Vec::new();
+        conversation.save(&mut saved).unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&saved).unwrap();
+        assert_eq!(json["version"], CONVERSATION_SCHEMA_VERSION);
+
+        let restored = Conversation::load(saved.as_slice()).unwrap();
+        assert_eq!(restored, conversation);
+        assert_eq!(
+            restored.messages()[1].tool_calls.as_ref().unwrap()[0]
+                .function_call
+                .name,
+            "get_weather"
+        );
+    }
+
+    #[test]
+    fn test_load_migrates_unversioned_message_array() {
+        let legacy = serde_json::to_vec(conversation_with_tool_call().messages()).unwrap();
+        let restored = Conversation::load(legacy.as_slice()).unwrap();
+        assert_eq!(
+            restored.messages(),
+            conversation_with_tool_call().messages()
+        );
+        assert_eq!(restored.usage(), &ConversationUsage::default());
+    }
+
+    #[test]
+    fn test_load_rejects_unknown_versions() {
+        for json in [r#"{"version": 99, "messages": []}"#, r#"{"messages": []}"#] {
+            assert!(matches!(
+                Conversation::load(json.as_bytes()),
+                Err(Error::ConfigError(_))
+            ));
+        }
+        assert!(matches!(
+            Conversation::load("not json".as_bytes()),
+            Err(Error::SerializationError(_))
+        ));
+    }
+}
@@ -81,6 +81,62 @@ impl CreditsResponse {
     }
 }
 
+/// Well-known EVM chain IDs accepted by the Coinbase credits endpoint.
+pub mod chains {
+    /// Ethereum mainnet.
+    pub const ETHEREUM: u64 = 1;
+    /// Polygon PoS.
+    pub const POLYGON: u64 = 137;
+    /// Base mainnet.
+    pub const BASE: u64 = 8453;
+}
+
+/// Request body for creating a Coinbase (crypto) credit purchase intent.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CoinbaseChargeRequest {
+    /// Amount of credits to purchase, in USD.
+    pub amount: f64,
+    /// Wallet address the payment will be sent from.
+    pub sender: String,
+    /// EVM chain ID the payment will be made on (see [`chains`]).
+    pub chain_id: u64,
+}
+
+impl CoinbaseChargeRequest {
+    /// Creates a new Coinbase charge request.
+    #[must_use]
+    pub fn new(amount: f64, sender: impl Into<String>, chain_id: u64) -> Self {
+        Self {
+            amount,
+            sender: sender.into(),
+            chain_id,
+        }
+    }
+}
+
+/// Purchase intent returned by the Coinbase credits endpoint.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[non_exhaustive]
+pub struct CoinbaseChargeData {
+    /// Charge identifier.
+    pub id: String,
+    /// ISO 8601 creation timestamp.
+    pub created_at: String,
+    /// ISO 8601 timestamp after which the intent can no longer be paid.
+    pub expires_at: String,
+    /// On-chain transfer intent (contract call data and metadata).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub web3_data: Option<serde_json::Value>,
+}
+
+/// Response from the Coinbase charge creation endpoint.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[non_exhaustive]
+pub struct CoinbaseChargeResponse {
+    /// Charge data
+    pub data: CoinbaseChargeData,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -168,4 +224,14 @@ mod tests {
         let parsed: CreditsResponse = serde_json::from_str(&json).unwrap();
         assert_eq!(response, parsed);
     }
+
+    #[test]
+    fn test_coinbase_charge_request_serialization() {
+        let request = CoinbaseChargeRequest::new(25.0, "0xabc", chains::BASE);
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({"amount": 25.0, "sender": "0xabc", "chain_id": 8453})
+        );
+    }
 }
@@ -0,0 +1,222 @@
+//! Helpers for reproducible requests and regression checks.
+//!
+//! [`DeterministicRequest`] pins the sampling parameters of a request.
+//! Responses are captured as [`ResponseSnapshot`]s, which can be stored (they
+//! serialize to JSON) and compared later with [`verify_reproducibility`].
+//!
+//! Seeded sampling is best-effort: providers only promise identical output for
+//! the same seed and parameters while the backend, identified by
+//! `system_fingerprint`, is unchanged. The report distinguishes output drift
+//! from a backend change so tests can decide which one to fail on.
+
+use crate::types::chat::{ChatCompletionRequest, ChatCompletionResponse};
+use serde::{Deserialize, Serialize};
+
+/// Seed used by [`DeterministicRequest::default`].
+pub const DEFAULT_SEED: u64 = 42;
+
+/// Sampling settings that make a request as repeatable as the provider allows.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeterministicRequest {
+    pub seed: u64,
+    /// Defaults to `0.0`, i.e. greedy decoding.
+    pub temperature: f32,
+}
+
+impl Default for DeterministicRequest {
+    fn default() -> Self {
+        Self::new(DEFAULT_SEED)
+    }
+}
+
+impl DeterministicRequest {
+    /// Creates settings with `seed` and temperature `0.0`.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            temperature: 0.0,
+        }
+    }
+
+    /// Sets the temperature, for checking seeded sampling above zero.
+    pub fn with_temperature(mut self, temperature: f32) -> Self {
+        self.temperature = temperature;
+        self
+    }
+
+    /// Sets the seed and temperature on `request`, clearing `top_p`, `top_k`
+    /// and `min_p` so the provider's defaults don't vary between runs.
+    pub fn apply(&self, mut request: ChatCompletionRequest) -> ChatCompletionRequest {
+        request.seed = Some(self.seed);
+        request.temperature = Some(self.temperature);
+        request.top_p = None;
+        request.top_k = None;
+        request.min_p = None;
+        request
+    }
+
+    /// Captures the parts of `response` that matter for reproducibility.
+    pub fn capture(&self, response: &ChatCompletionResponse) -> ResponseSnapshot {
+        ResponseSnapshot {
+            model: response.model.clone(),
+            seed: Some(self.seed),
+            system_fingerprint: response.system_fingerprint.clone(),
+            output: response
+                .first_choice()
+                .map(|choice| choice.message.content.to_text())
+                .unwrap_or_default(),
+            finish_reason: response.finish_reason().map(str::to_string),
+        }
+    }
+}
+
+/// The reproducibility-relevant parts of a response.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResponseSnapshot {
+    pub model: String,
+    pub seed: Option<u64>,
+    /// Identifies the backend configuration that served the request.
+    pub system_fingerprint: Option<String>,
+    /// Text of the first choice.
+    pub output: String,
+    pub finish_reason: Option<String>,
+}
+
+impl From<&ChatCompletionResponse> for ResponseSnapshot {
+    /// Captures a response sent without [`DeterministicRequest`] settings.
+    fn from(response: &ChatCompletionResponse) -> Self {
+        Self {
+            seed: None,
+            ..DeterministicRequest::default().capture(response)
+        }
+    }
+}
+
+/// How two snapshots of the same request compare.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct ReproducibilityReport {
+    pub same_model: bool,
+    pub same_seed: bool,
+    /// `None` when either response lacks a `system_fingerprint`.
+    pub same_fingerprint: Option<bool>,
+    pub identical_output: bool,
+    /// Byte offset in the first output where the outputs diverge.
+    pub first_difference: Option<usize>,
+}
+
+impl ReproducibilityReport {
+    /// Whether both runs produced the same output.
+    pub fn is_reproducible(&self) -> bool {
+        self.identical_output
+    }
+
+    /// Whether the outputs differ even though the model, seed and backend
+    /// fingerprint match — i.e. a regression rather than a backend update.
+    pub fn is_unexplained_drift(&self) -> bool {
+        !self.identical_output
+            && self.same_model
+            && self.same_seed
+            && self.same_fingerprint == Some(true)
+    }
+}
+
+/// Compares two snapshots of the same request.
+pub fn verify_reproducibility(a: &ResponseSnapshot, b: &ResponseSnapshot) -> ReproducibilityReport {
+    let same_fingerprint = match (&a.system_fingerprint, &b.system_fingerprint) {
+        (Some(x), Some(y)) => Some(x == y),
+        _ => None,
+    };
+    let first_difference = (a.output != b.output).then(|| {
+        a.output
+            .char_indices()
+            .zip(b.output.chars())
+            .find(|((_, x), y)| x != y)
+            .map_or_else(
+                || a.output.len().min(b.output.len()),
+                |((index, _), _)| index,
+            )
+    });
+
+    ReproducibilityReport {
+        same_model: a.model == b.model,
+        same_seed: a.seed == b.seed,
+        same_fingerprint,
+        identical_output: first_difference.is_none(),
+        first_difference,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(output: &str, fingerprint: Option<&str>) -> ChatCompletionResponse {
+        serde_json::from_value(serde_json::json!({
+            "id": "gen-1",
+            "object": "chat.completion",
+            "created": 0,
+            "model": "openai/gpt-4o",
+            "system_fingerprint": fingerprint,
+            "choices": [{"message": {"role": "assistant", "content": output}, "finish_reason": "stop"}]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_apply_pins_sampling_parameters() {
+        let request = ChatCompletionRequest {
+            top_p: Some(0.9),
+            top_k: Some(40),
+            ..Default::default()
+        };
+        let request = DeterministicRequest::new(7).apply(request);
+        assert_eq!(request.seed, Some(7));
+        assert_eq!(request.temperature, Some(0.0));
+        assert_eq!(request.top_p, None);
+        assert_eq!(request.top_k, None);
+    }
+
+    #[test]
+    fn test_identical_runs_are_reproducible() {
+        let settings = DeterministicRequest::default();
+        let a = settings.capture(&response("Paris", Some("fp_1")));
+        let b = settings.capture(&response("Paris", Some("fp_1")));
+        let report = verify_reproducibility(&a, &b);
+        assert!(report.is_reproducible());
+        assert_eq!(report.same_fingerprint, Some(true));
+        assert_eq!(report.first_difference, None);
+
+        // Snapshots survive a round trip through storage.
+        let stored = serde_json::to_string(&a).unwrap();
+        assert_eq!(
+            serde_json::from_str::<ResponseSnapshot>(&stored).unwrap(),
+            a
+        );
+    }
+
+    #[test]
+    fn test_drift_is_attributed_to_backend_changes() {
+        let settings = DeterministicRequest::default();
+        let baseline = settings.capture(&response("The capital is Paris.", Some("fp_1")));
+
+        let drifted = settings.capture(&response("The capital is Lyon.", Some("fp_1")));
+        let report = verify_reproducibility(&baseline, &drifted);
+        assert!(!report.is_reproducible());
+        assert!(report.is_unexplained_drift());
+        assert_eq!(report.first_difference, Some(15));
+
+        let updated = settings.capture(&response("The capital is Paris!", Some("fp_2")));
+        let report = verify_reproducibility(&baseline, &updated);
+        assert_eq!(report.same_fingerprint, Some(false));
+        assert!(!report.is_unexplained_drift());
+
+        let prefix = settings.capture(&response("The capital", None));
+        let report = verify_reproducibility(&baseline, &prefix);
+        assert_eq!(report.first_difference, Some(11));
+        assert_eq!(report.same_fingerprint, None);
+
+        let unseeded = ResponseSnapshot::from(&response("The capital is Paris.", Some("fp_1")));
+        assert!(!verify_reproducibility(&baseline, &unseeded).same_seed);
+    }
+}
@@ -4,6 +4,7 @@ use serde::{Deserialize, Serialize};
 
 /// Input for an embedding request — single string, batch of strings, or multimodal content.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum EmbeddingInput {
     /// A single string to embed.
@@ -14,6 +15,7 @@ pub enum EmbeddingInput {
 
 /// Encoding format for the embedding output.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 pub enum EncodingFormat {
     Float,
@@ -22,6 +24,7 @@ pub enum EncodingFormat {
 
 /// Request body for `POST /api/v1/embeddings`.
 #[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 pub struct EmbeddingRequest {
     /// The model to use for embeddings.
     pub model: String,
@@ -37,6 +40,7 @@ pub struct EmbeddingRequest {
 
 /// A single embedding result.
 #[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 #[non_exhaustive]
 pub struct EmbeddingData {
     /// The embedding vector.
@@ -50,6 +54,7 @@ pub struct EmbeddingData {
 
 /// Usage information for an embedding request.
 #[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 #[non_exhaustive]
 pub struct EmbeddingUsage {
     /// Number of tokens in the input.
@@ -60,6 +65,7 @@ pub struct EmbeddingUsage {
 
 /// Response from `POST /api/v1/embeddings`.
 #[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 #[non_exhaustive]
 pub struct EmbeddingResponse {
     /// Object type — "list".
@@ -63,6 +63,11 @@ pub struct GenerationData {
     pub usage: f64,
     /// Whether this is a BYOK (Bring Your Own Key) generation
     pub is_byok: bool,
+    /// Fields not modeled by this version of the crate (`preserve-unknown`
+    /// feature).
+    #[cfg(feature = "preserve-unknown")]
+    #[serde(flatten)]
+    pub extra: std::collections::HashMap<String, serde_json::Value>,
 }
 
 impl GenerationData {
@@ -249,6 +254,8 @@ mod tests {
             origin: "api".to_string(),
             usage: 0.025,
             is_byok: false,
+            #[cfg(feature = "preserve-unknown")]
+            extra: Default::default(),
         }
     }
 
@@ -342,6 +349,8 @@ mod tests {
             origin: "api".to_string(),
             usage: 0.01,
             is_byok: false,
+            #[cfg(feature = "preserve-unknown")]
+            extra: Default::default(),
         };
 
         assert_eq!(minimal_data.total_tokens(), None);
@@ -403,6 +412,8 @@ mod tests {
             origin: "api".to_string(),
             usage: 0.025,
             is_byok: false,
+            #[cfg(feature = "preserve-unknown")]
+            extra: Default::default(),
         };
 
         // Test that GenerationId serializes as a plain string
@@ -11,6 +11,7 @@ use std::hash::Hash;
 ///
 /// Prevents accidental mixing of model IDs with other entity IDs.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 #[serde(transparent)]
 pub struct ModelId(String);
 
@@ -65,6 +66,7 @@ impl From<ModelId> for String {
 ///
 /// Prevents accidental mixing of generation IDs with other entity IDs.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 #[serde(transparent)]
 pub struct GenerationId(String);
 
@@ -119,6 +121,7 @@ impl From<GenerationId> for String {
 ///
 /// Prevents accidental mixing of activity IDs with other entity IDs.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 #[serde(transparent)]
 pub struct ActivityId(String);
 
@@ -173,6 +176,7 @@ impl From<ActivityId> for String {
 ///
 /// Prevents accidental mixing of tool call IDs with other entity IDs.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 #[serde(transparent)]
 pub struct ToolCallId(String);
 
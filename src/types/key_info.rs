@@ -13,9 +13,9 @@ pub struct RateLimitInfo {
     pub interval: Option<String>,
 }
 
-/// Key info data returned by the OpenRouter API.
+/// The current API key's label, usage, limit and rate limit.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-pub struct KeyInfoData {
+pub struct KeyInfo {
     /// Human-readable label for the API key.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub label: Option<String>,
@@ -28,6 +28,18 @@ pub struct KeyInfoData {
     /// Total usage in credits.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub usage: Option<f64>,
+    /// Usage in credits for the current UTC day.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub usage_daily: Option<f64>,
+    /// Usage in credits for the current UTC week.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub usage_weekly: Option<f64>,
+    /// Usage in credits for the current UTC month.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub usage_monthly: Option<f64>,
+    /// How often the limit resets (e.g. "daily"); None if it never does.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit_reset: Option<String>,
     /// Whether the key is on the free tier.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub is_free_tier: Option<bool>,
@@ -36,11 +48,26 @@ pub struct KeyInfoData {
     pub rate_limit: Option<RateLimitInfo>,
 }
 
-/// Response from the key info endpoint (`GET /api/v1/auth/key`).
+impl KeyInfo {
+    /// Credits left before the key's limit: `limit_remaining`, else `limit`
+    /// minus `usage`. None means unlimited.
+    pub fn remaining(&self) -> Option<f64> {
+        self.limit_remaining.or_else(|| {
+            self.limit
+                .map(|limit| (limit - self.usage.unwrap_or(0.0)).max(0.0))
+        })
+    }
+}
+
+/// Former name of [`KeyInfo`].
+pub type KeyInfoData = KeyInfo;
+
+/// Response from the key info endpoints (`GET /api/v1/key` and
+/// `GET /api/v1/auth/key`).
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct KeyInfoResponse {
     /// Key info data.
-    pub data: KeyInfoData,
+    pub data: KeyInfo,
 }
 
 impl KeyInfoResponse {
@@ -105,6 +132,16 @@ mod tests {
         assert!(!response.is_free_tier()); // defaults to false via unwrap_or
     }
 
+    #[test]
+    fn test_key_info_remaining_falls_back_to_limit_minus_usage() {
+        let info: KeyInfo =
+            serde_json::from_str(r#"{"limit": 10.0, "usage": 4.0, "usage_daily": 1.5}"#).unwrap();
+        assert_eq!(info.remaining(), Some(6.0));
+        assert_eq!(info.usage_daily, Some(1.5));
+        let unlimited: KeyInfo = serde_json::from_str(r#"{"usage": 4.0}"#).unwrap();
+        assert_eq!(unlimited.remaining(), None);
+    }
+
     #[test]
     fn test_key_info_free_tier() {
         let json = r#"{"data": {"is_free_tier": true, "usage": 0.0}}"#;
@@ -120,6 +157,10 @@ mod tests {
                 limit: Some(50.0),
                 limit_remaining: Some(25.0),
                 usage: Some(25.0),
+                usage_daily: Some(5.0),
+                usage_weekly: None,
+                usage_monthly: None,
+                limit_reset: Some("monthly".to_string()),
                 is_free_tier: Some(false),
                 rate_limit: Some(RateLimitInfo {
                     requests: Some(100),
@@ -0,0 +1,223 @@
+//! Pluggable persistence for chat message histories.
+//!
+//! A [`MessageStore`] keeps the messages of any number of sessions, keyed by
+//! a session ID. [`InMemoryMessageStore`] is provided, as is
+//! `SqliteMessageStore` with the `sqlite` feature; implement the trait to
+//! keep histories in Redis, Postgres or any other backend, then restore them
+//! with [`Conversation::from_store`] or [`Conversation::from_store_window`].
+
+use crate::error::Result;
+use crate::types::chat::{ChatRole, Message};
+use crate::types::conversation::Conversation;
+use crate::utils::validation::chat::estimate_message_tokens;
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Mutex;
+
+#[cfg(feature = "sqlite")]
+mod sqlite;
+#[cfg(feature = "sqlite")]
+pub use sqlite::SqliteMessageStore;
+
+/// Future returned by [`MessageStore`] methods.
+pub type StoreFuture<'a, T> = Pin<Box<dyn Future<Output = Result<T>> + Send + 'a>>;
+
+/// Storage for the message histories of chat sessions.
+pub trait MessageStore: Send + Sync {
+    /// Appends `messages` to the history of `session`, creating it if needed.
+    fn append<'a>(&'a self, session: &'a str, messages: &'a [Message]) -> StoreFuture<'a, ()>;
+
+    /// Returns the history of `session`, oldest first. Unknown sessions have
+    /// an empty history.
+    fn load<'a>(&'a self, session: &'a str) -> StoreFuture<'a, Vec<Message>>;
+
+    /// Deletes the history of `session`.
+    fn clear<'a>(&'a self, session: &'a str) -> StoreFuture<'a, ()>;
+
+    /// Returns the most recent part of the history that fits `max_tokens`,
+    /// as selected by [`window_messages`].
+    ///
+    /// The default implementation loads the whole history; stores that can
+    /// read newest-first may override it.
+    fn window<'a>(&'a self, session: &'a str, max_tokens: u32) -> StoreFuture<'a, Vec<Message>> {
+        Box::pin(async move { Ok(window_messages(self.load(session).await?, max_tokens)) })
+    }
+}
+
+/// Keeps the system messages and as many of the newest other messages as fit
+/// `max_tokens` together, by the crate's token estimate.
+///
+/// Tool results at the start of the kept messages are dropped as well, since
+/// the call they answer was not kept.
+pub fn window_messages(messages: Vec<Message>, max_tokens: u32) -> Vec<Message> {
+    let system_tokens: u32 = messages
+        .iter()
+        .filter(|message| message.role == ChatRole::System)
+        .map(estimate_message_tokens)
+        .sum();
+    let mut budget = max_tokens.saturating_sub(system_tokens);
+
+    let mut first_kept = messages.len();
+    for (index, message) in messages.iter().enumerate().rev() {
+        if message.role == ChatRole::System {
+            continue;
+        }
+        let tokens = estimate_message_tokens(message);
+        if tokens > budget {
+            break;
+        }
+        budget -= tokens;
+        first_kept = index;
+    }
+    while messages
+        .get(first_kept)
+        .is_some_and(|message| message.role == ChatRole::Tool)
+    {
+        first_kept += 1;
+    }
+
+    messages
+        .into_iter()
+        .enumerate()
+        .filter(|(index, message)| *index >= first_kept || message.role == ChatRole::System)
+        .map(|(_, message)| message)
+        .collect()
+}
+
+/// A [`MessageStore`] that keeps histories in memory.
+#[derive(Debug, Default)]
+pub struct InMemoryMessageStore {
+    sessions: Mutex<HashMap<String, Vec<Message>>>,
+}
+
+impl InMemoryMessageStore {
+    /// Creates an empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn sessions(&self) -> std::sync::MutexGuard<'_, HashMap<String, Vec<Message>>> {
+        self.sessions.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl MessageStore for InMemoryMessageStore {
+    fn append<'a>(&'a self, session: &'a str, messages: &'a [Message]) -> StoreFuture<'a, ()> {
+        self.sessions()
+            .entry(session.to_string())
+            .or_default()
+            .extend_from_slice(messages);
+        Box::pin(async { Ok(()) })
+    }
+
+    fn load<'a>(&'a self, session: &'a str) -> StoreFuture<'a, Vec<Message>> {
+        let messages = self.sessions().get(session).cloned().unwrap_or_default();
+        Box::pin(async { Ok(messages) })
+    }
+
+    fn clear<'a>(&'a self, session: &'a str) -> StoreFuture<'a, ()> {
+        self.sessions().remove(session);
+        Box::pin(async { Ok(()) })
+    }
+}
+
+impl Conversation {
+    /// Restores the full history of `session` from `store`.
+    ///
+    /// Usage totals are not stored and start at zero.
+    pub async fn from_store(store: &dyn MessageStore, session: &str) -> Result<Self> {
+        Ok(Self::from_messages(store.load(session).await?))
+    }
+
+    /// Restores the most recent part of `session` that fits `max_tokens`; see
+    /// [`MessageStore::window`].
+    pub async fn from_store_window(
+        store: &dyn MessageStore,
+        session: &str,
+        max_tokens: u32,
+    ) -> Result<Self> {
+        Ok(Self::from_messages(
+            store.window(session, max_tokens).await?,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::tool::{FunctionCall, ToolCall, ToolType};
+    use crate::types::ids::ToolCallId;
+
+    fn text(role: ChatRole, bytes: usize) -> Message {
+        Message::text(role, "x".repeat(bytes))
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_store_appends_loads_and_clears() {
+        let store = InMemoryMessageStore::new();
+        store
+            .append("a", &[Message::text(ChatRole::User, "Hi")])
+            .await
+            .unwrap();
+        store
+            .append("a", &[Message::text(ChatRole::Assistant, "Hello")])
+            .await
+            .unwrap();
+        store
+            .append("b", &[Message::text(ChatRole::User, "Other")])
+            .await
+            .unwrap();
+
+        let conversation = Conversation::from_store(&store, "a").await.unwrap();
+        assert_eq!(conversation.messages().len(), 2);
+        assert_eq!(conversation.messages()[1].content.as_text(), Some("Hello"));
+
+        store.clear("a").await.unwrap();
+        assert!(store.load("a").await.unwrap().is_empty());
+        assert_eq!(store.load("b").await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_window_keeps_system_and_newest_messages() {
+        let store = InMemoryMessageStore::new();
+        let history = vec![
+            text(ChatRole::System, 40),
+            text(ChatRole::User, 400),
+            text(ChatRole::Assistant, 40),
+            text(ChatRole::User, 40),
+        ];
+        store.append("s", &history).await.unwrap();
+
+        // 13 tokens each with the role, except the 103-token first user message.
+        let window = Conversation::from_store_window(&store, "s", 40)
+            .await
+            .unwrap();
+        assert_eq!(
+            window.messages(),
+            &[history[0].clone(), history[2].clone(), history[3].clone()]
+        );
+
+        let everything = store.window("s", 1000).await.unwrap();
+        assert_eq!(everything, history);
+    }
+
+    #[test]
+    fn test_window_drops_orphaned_tool_results() {
+        let mut call = text(ChatRole::Assistant, 400);
+        call.tool_calls = Some(vec![ToolCall {
+            id: ToolCallId::new("call-1"),
+            kind: ToolType::Function,
+            function_call: FunctionCall {
+                name: "f".to_string(),
+                arguments: "{}".to_string(),
+            },
+        }]);
+        let mut result = text(ChatRole::Tool, 4);
+        result.tool_call_id = Some(ToolCallId::new("call-1"));
+        let answer = text(ChatRole::Assistant, 4);
+
+        let window = window_messages(vec![call, result, answer.clone()], 10);
+        assert_eq!(window, vec![answer]);
+    }
+}
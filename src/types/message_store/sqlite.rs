@@ -0,0 +1,160 @@
+//! A [`MessageStore`] backed by SQLite, behind the `sqlite` feature.
+
+use super::{MessageStore, StoreFuture};
+use crate::error::{Error, Result};
+use crate::types::chat::Message;
+use rusqlite::{params, Connection};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+/// Table the histories are kept in, one row per message.
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS openrouter_messages (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        session TEXT NOT NULL,
+        message TEXT NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS openrouter_messages_session
+        ON openrouter_messages (session, id);
+";
+
+/// A [`MessageStore`] that keeps histories in a SQLite database.
+///
+/// Messages are stored as JSON in the `openrouter_messages` table, which is
+/// created when the store is opened. Queries run on tokio's blocking thread
+/// pool; clones share the connection.
+#[derive(Debug, Clone)]
+pub struct SqliteMessageStore {
+    connection: Arc<Mutex<Connection>>,
+}
+
+impl SqliteMessageStore {
+    /// Opens (or creates) the database at `path`.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        Self::from_connection(Connection::open(path).map_err(storage_error)?)
+    }
+
+    /// Creates a store in a private in-memory database.
+    pub fn open_in_memory() -> Result<Self> {
+        Self::from_connection(Connection::open_in_memory().map_err(storage_error)?)
+    }
+
+    /// Uses an existing connection, creating the table if needed.
+    pub fn from_connection(connection: Connection) -> Result<Self> {
+        connection.execute_batch(SCHEMA).map_err(storage_error)?;
+        Ok(Self {
+            connection: Arc::new(Mutex::new(connection)),
+        })
+    }
+
+    /// Runs `query` on the blocking thread pool.
+    fn run<'a, T, F>(&self, query: F) -> StoreFuture<'a, T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut Connection) -> Result<T> + Send + 'static,
+    {
+        let connection = Arc::clone(&self.connection);
+        Box::pin(async move {
+            tokio::task::spawn_blocking(move || {
+                let mut connection = connection.lock().unwrap_or_else(|e| e.into_inner());
+                query(&mut connection)
+            })
+            .await
+            .map_err(|e| Error::IoError(std::io::Error::other(e)))?
+        })
+    }
+}
+
+impl MessageStore for SqliteMessageStore {
+    fn append<'a>(&'a self, session: &'a str, messages: &'a [Message]) -> StoreFuture<'a, ()> {
+        let session = session.to_string();
+        let rows = messages
+            .iter()
+            .map(serde_json::to_string)
+            .collect::<serde_json::Result<Vec<_>>>();
+        self.run(move |connection| {
+            let rows = rows?;
+            let transaction = connection.transaction().map_err(storage_error)?;
+            {
+                let mut insert = transaction
+                    .prepare_cached(
+                        "INSERT INTO openrouter_messages (session, message) VALUES (?1, ?2)",
+                    )
+                    .map_err(storage_error)?;
+                for row in &rows {
+                    insert
+                        .execute(params![session, row])
+                        .map_err(storage_error)?;
+                }
+            }
+            transaction.commit().map_err(storage_error)
+        })
+    }
+
+    fn load<'a>(&'a self, session: &'a str) -> StoreFuture<'a, Vec<Message>> {
+        let session = session.to_string();
+        self.run(move |connection| {
+            let mut select = connection
+                .prepare_cached(
+                    "SELECT message FROM openrouter_messages WHERE session = ?1 ORDER BY id",
+                )
+                .map_err(storage_error)?;
+            let rows = select
+                .query_map(params![session], |row| row.get::<_, String>(0))
+                .map_err(storage_error)?;
+            rows.map(|row| Ok(serde_json::from_str(&row.map_err(storage_error)?)?))
+                .collect()
+        })
+    }
+
+    fn clear<'a>(&'a self, session: &'a str) -> StoreFuture<'a, ()> {
+        let session = session.to_string();
+        self.run(move |connection| {
+            connection
+                .execute(
+                    "DELETE FROM openrouter_messages WHERE session = ?1",
+                    params![session],
+                )
+                .map(|_| ())
+                .map_err(storage_error)
+        })
+    }
+}
+
+fn storage_error(error: rusqlite::Error) -> Error {
+    Error::IoError(std::io::Error::other(error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::chat::ChatRole;
+    use crate::types::conversation::Conversation;
+
+    #[tokio::test]
+    async fn test_sqlite_store_persists_histories() {
+        let path = std::env::temp_dir().join(format!("messages-{}.db", uuid::Uuid::new_v4()));
+        let history = vec![
+            Message::text(ChatRole::System, "Be terse."),
+            Message::text(ChatRole::User, "Hi"),
+        ];
+
+        let store = SqliteMessageStore::open(&path).unwrap();
+        store.append("a", &history).await.unwrap();
+        store
+            .append("b", &[Message::text(ChatRole::User, "Other")])
+            .await
+            .unwrap();
+        drop(store);
+
+        let store = SqliteMessageStore::open(&path).unwrap();
+        let conversation = Conversation::from_store(&store, "a").await.unwrap();
+        assert_eq!(conversation.messages(), history.as_slice());
+
+        store.clear("a").await.unwrap();
+        assert!(store.load("a").await.unwrap().is_empty());
+        assert_eq!(store.load("b").await.unwrap().len(), 1);
+        drop(store);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
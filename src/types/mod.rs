@@ -1,31 +1,65 @@
 pub mod analytics;
+pub mod catalog;
 pub mod chat;
+pub mod chat_builder;
+pub mod comparison;
 pub mod completion;
+pub mod conversation;
 pub mod credits;
+pub mod determinism;
 pub mod embeddings;
 pub mod generation;
 pub mod guardrails;
 pub mod ids;
 pub mod key_info;
+pub mod message_store;
 pub mod models;
+pub mod normalization;
+pub mod prompts;
 pub mod providers;
+pub mod response_meta;
 pub mod routing;
+pub mod sampling;
+#[cfg(feature = "schema-export")]
+pub mod schema;
+pub mod search_answer;
 pub mod status;
+pub mod tool_events;
+pub mod trace;
+pub mod transcript;
 pub mod transform;
+pub mod truncation;
 pub mod web_search;
 
 // Re-export common types
 pub use analytics::*;
+pub use catalog::*;
 pub use chat::*;
+pub use chat_builder::*;
+pub use comparison::*;
 pub use completion::*;
+pub use conversation::*;
 pub use credits::*;
+pub use determinism::*;
 pub use embeddings::*;
 pub use generation::*;
 pub use guardrails::*;
 pub use ids::*;
 pub use key_info::*;
+pub use message_store::*;
 pub use models::*;
+pub use normalization::*;
+pub use prompts::*;
 pub use providers::*;
+pub use response_meta::*;
 pub use routing::*;
+pub use sampling::*;
+#[cfg(feature = "schema-export")]
+pub use schema::*;
+pub use search_answer::*;
 pub use status::*;
+pub use tool_events::*;
+pub use trace::*;
+pub use transcript::*;
 pub use transform::*;
+pub use truncation::*;
@@ -61,6 +61,23 @@ impl PricingInfo {
     pub fn completion_price(&self) -> f64 {
         self.completion.as_f64()
     }
+
+    /// Estimates the cost of a request with the given token counts, including
+    /// any per-request price.
+    ///
+    /// Returns `None` when a price is negative, which the API uses to mark
+    /// unknown pricing.
+    pub fn estimate_cost(&self, prompt_tokens: u32, completion_tokens: u32) -> Option<f64> {
+        let request = self.request.as_ref().map_or(0.0, Price::as_f64);
+        if self.prompt_price() < 0.0 || self.completion_price() < 0.0 || request < 0.0 {
+            return None;
+        }
+        Some(
+            f64::from(prompt_tokens) * self.prompt_price()
+                + f64::from(completion_tokens) * self.completion_price()
+                + request,
+        )
+    }
 }
 
 /// Nested structure for top provider details within ModelInfo.
@@ -90,6 +107,11 @@ pub struct ModelInfo {
     pub per_request_limits: Option<Value>, // Can be null, structure can vary
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub supported_parameters: Option<Vec<String>>, // Can be null or a list
+    /// Fields not modeled by this version of the crate (`preserve-unknown`
+    /// feature).
+    #[cfg(feature = "preserve-unknown")]
+    #[serde(flatten)]
+    pub extra: std::collections::HashMap<String, serde_json::Value>,
 }
 
 /// Request to list available models.
@@ -103,10 +125,56 @@ pub struct ModelsRequest {
 }
 
 /// Response containing available models.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct ModelsResponse {
     /// A list of available models.
     pub data: Vec<ModelInfo>,
+    /// Set when the list comes from the bundled snapshot (`offline` feature)
+    /// rather than the API.
+    #[serde(skip)]
+    pub stale: bool,
+}
+
+/// A provider endpoint serving a model, with its limits and live health.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelEndpoint {
+    pub name: String,
+    pub provider_name: String,
+    /// Provider slug usable in `ProviderPreferences::order`.
+    #[serde(default)]
+    pub tag: Option<String>,
+    #[serde(default)]
+    pub context_length: Option<u32>,
+    pub pricing: PricingInfo,
+    #[serde(default)]
+    pub quantization: Option<String>,
+    #[serde(default)]
+    pub max_completion_tokens: Option<u32>,
+    #[serde(default)]
+    pub max_prompt_tokens: Option<u32>,
+    #[serde(default)]
+    pub supported_parameters: Option<Vec<String>>,
+    /// Endpoint status; `0` is healthy and negative values are degraded.
+    #[serde(default)]
+    pub status: Option<i32>,
+    /// Percentage of successful requests over the last 30 minutes.
+    #[serde(default)]
+    pub uptime_last_30m: Option<f64>,
+}
+
+/// A model and the provider endpoints serving it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelEndpoints {
+    pub id: ModelId,
+    pub name: String,
+    #[serde(default)]
+    pub endpoints: Vec<ModelEndpoint>,
+}
+
+/// Response from the model endpoints endpoint.
+#[derive(Debug, Deserialize)]
+pub struct ModelEndpointsResponse {
+    pub data: ModelEndpoints,
 }
 
 #[cfg(test)]
@@ -258,6 +326,44 @@ mod tests {
         assert_eq!(models_response.data[0].id, "openai/gpt-4o".into());
     }
 
+    #[test]
+    fn test_deserialize_model_endpoints_response() {
+        let json_data = r#"
+        {
+            "data": {
+                "id": "openai/gpt-4o",
+                "name": "OpenAI: GPT-4o",
+                "endpoints": [
+                    {
+                        "name": "OpenAI | openai/gpt-4o",
+                        "provider_name": "OpenAI",
+                        "tag": "openai",
+                        "context_length": 128000,
+                        "pricing": {"prompt": "0.0000025", "completion": "0.00001"},
+                        "quantization": null,
+                        "max_completion_tokens": 16384,
+                        "max_prompt_tokens": null,
+                        "supported_parameters": ["tools", "max_tokens"],
+                        "status": 0,
+                        "uptime_last_30m": 99.8
+                    },
+                    {
+                        "name": "Azure | openai/gpt-4o",
+                        "provider_name": "Azure",
+                        "pricing": {"prompt": "0.0000025", "completion": "0.00001"}
+                    }
+                ]
+            }
+        }
+        "#;
+        let response: ModelEndpointsResponse = serde_json::from_str(json_data).unwrap();
+        assert_eq!(response.data.id, "openai/gpt-4o".into());
+        assert_eq!(response.data.endpoints.len(), 2);
+        assert_eq!(response.data.endpoints[0].tag.as_deref(), Some("openai"));
+        assert_eq!(response.data.endpoints[0].uptime_last_30m, Some(99.8));
+        assert_eq!(response.data.endpoints[1].status, None);
+    }
+
     #[test]
     fn test_deserialize_all_models_from_api() {
         // Construct the path to the test data file relative to the crate root
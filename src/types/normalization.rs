@@ -0,0 +1,317 @@
+//! Message normalization for providers with stricter input rules.
+//!
+//! Some providers reject conversations that others accept: Anthropic and
+//! Google require alternating roles and non-empty content, and some
+//! OpenAI-compatible providers only accept string content.
+//! [`normalize_messages`] rewrites a conversation to satisfy such rules.
+//!
+//! Normalization is opt-in: call [`normalize_messages`] directly, or enable
+//! `OpenRouterClient::with_message_normalization` to apply
+//! [`NormalizationRules::for_model`] to every chat request.
+
+use crate::types::chat::{
+    ChatRole, ContentPart, ContentType, Message, MessageContent, TextContent,
+};
+
+/// Separator placed between the texts of merged messages and flattened parts.
+const TEXT_SEPARATOR: &str = "\n\n";
+
+/// Which normalizations [`normalize_messages`] applies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct NormalizationRules {
+    /// Removes messages with no content, unless they carry tool calls or
+    /// answer one.
+    pub strip_empty: bool,
+    /// Merges consecutive system, user or assistant messages that share a
+    /// name. Tool results are never merged.
+    pub merge_consecutive: bool,
+    /// Converts content made only of text parts into plain text.
+    pub flatten_text_parts: bool,
+}
+
+impl NormalizationRules {
+    /// Applies no normalization.
+    pub fn none() -> Self {
+        Self::default()
+    }
+
+    /// Applies every normalization.
+    pub fn all() -> Self {
+        Self {
+            strip_empty: true,
+            merge_consecutive: true,
+            flatten_text_parts: true,
+        }
+    }
+
+    /// The rules required by the provider serving `model`, judged by its
+    /// vendor prefix. Unknown vendors get no normalization.
+    pub fn for_model(model: &str) -> Self {
+        let vendor = model.split('/').next().unwrap_or_default();
+        match vendor.to_ascii_lowercase().as_str() {
+            "anthropic" | "google" => Self {
+                strip_empty: true,
+                merge_consecutive: true,
+                flatten_text_parts: false,
+            },
+            "deepseek" => Self::all(),
+            "mistralai" => Self {
+                strip_empty: true,
+                merge_consecutive: false,
+                flatten_text_parts: true,
+            },
+            _ => Self::none(),
+        }
+    }
+
+    /// Whether any normalization is enabled.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::none()
+    }
+}
+
+/// Rewrites `messages` according to `rules`.
+///
+/// Empty messages are stripped first, so that the messages around them can
+/// be merged; text parts are flattened last, including those produced by a
+/// merge.
+///
+/// # Example
+///
+/// ```
+/// use openrouter_api::types::chat::{ChatRole, Message};
+/// use openrouter_api::types::normalization::{normalize_messages, NormalizationRules};
+///
+/// let mut messages = vec![
+///     Message::text(ChatRole::User, "Hello"),
+///     Message::text(ChatRole::Assistant, ""),
+///     Message::text(ChatRole::User, "Are you there?"),
+/// ];
+/// normalize_messages(&mut messages, NormalizationRules::all());
+/// assert_eq!(messages.len(), 1);
+/// assert_eq!(messages[0].content.as_text(), Some("Hello\n\nAre you there?"));
+/// ```
+pub fn normalize_messages(messages: &mut Vec<Message>, rules: NormalizationRules) {
+    if rules.strip_empty {
+        messages.retain(|message| !is_empty(message));
+    }
+    if rules.merge_consecutive {
+        let mut merged: Vec<Message> = Vec::with_capacity(messages.len());
+        for message in messages.drain(..) {
+            match merged.last_mut() {
+                Some(previous) if can_merge(previous, &message) => merge_into(previous, message),
+                _ => merged.push(message),
+            }
+        }
+        *messages = merged;
+    }
+    if rules.flatten_text_parts {
+        for message in messages.iter_mut() {
+            flatten_text_parts(&mut message.content);
+        }
+    }
+}
+
+fn is_empty(message: &Message) -> bool {
+    if message.role == ChatRole::Tool || message.tool_calls.is_some() {
+        return false;
+    }
+    match &message.content {
+        MessageContent::Text(text) => text.trim().is_empty(),
+        MessageContent::Parts(parts) => parts.iter().all(|part| match part {
+            ContentPart::Text(text) => text.text.trim().is_empty(),
+            _ => false,
+        }),
+    }
+}
+
+fn can_merge(previous: &Message, next: &Message) -> bool {
+    previous.role == next.role
+        && previous.role != ChatRole::Tool
+        && previous.name == next.name
+        // A tool call must stay last in its message, directly before the
+        // results that answer it.
+        && previous.tool_calls.is_none()
+}
+
+fn merge_into(target: &mut Message, source: Message) {
+    target.content = match (std::mem::take(&mut target.content), source.content) {
+        (MessageContent::Text(a), MessageContent::Text(b)) => {
+            MessageContent::Text(join_texts([a, b]))
+        }
+        (a, b) => {
+            let mut parts = into_parts(a);
+            parts.extend(into_parts(b));
+            MessageContent::Parts(parts)
+        }
+    };
+    if let Some(calls) = source.tool_calls {
+        target.tool_calls = Some(calls);
+    }
+    target.reasoning = match (target.reasoning.take(), source.reasoning) {
+        (Some(a), Some(b)) => Some(join_texts([a, b])),
+        (a, b) => a.or(b),
+    };
+    if let Some(details) = source.reasoning_details {
+        target
+            .reasoning_details
+            .get_or_insert_with(Vec::new)
+            .extend(details);
+    }
+}
+
+fn into_parts(content: MessageContent) -> Vec<ContentPart> {
+    match content {
+        MessageContent::Parts(parts) => parts,
+        MessageContent::Text(text) if text.is_empty() => Vec::new(),
+        MessageContent::Text(text) => vec![ContentPart::Text(TextContent {
+            content_type: ContentType::Text,
+            text,
+        })],
+    }
+}
+
+fn flatten_text_parts(content: &mut MessageContent) {
+    let MessageContent::Parts(parts) = content else {
+        return;
+    };
+    let texts: Option<Vec<String>> = parts
+        .iter()
+        .map(|part| match part {
+            ContentPart::Text(text) => Some(text.text.clone()),
+            _ => None,
+        })
+        .collect();
+    if let Some(texts) = texts {
+        *content = MessageContent::Text(join_texts(texts));
+    }
+}
+
+fn join_texts(texts: impl IntoIterator<Item = String>) -> String {
+    texts
+        .into_iter()
+        .filter(|text| !text.is_empty())
+        .collect::<Vec<_>>()
+        .join(TEXT_SEPARATOR)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::tool::{FunctionCall, ToolCall, ToolType};
+    use crate::types::chat::{ImageContent, ImageUrl};
+    use crate::types::ids::ToolCallId;
+
+    fn text_part(text: &str) -> ContentPart {
+        ContentPart::Text(TextContent {
+            content_type: ContentType::Text,
+            text: text.to_string(),
+        })
+    }
+
+    fn tool_call(id: &str) -> ToolCall {
+        ToolCall {
+            id: ToolCallId::new(id),
+            kind: ToolType::Function,
+            function_call: FunctionCall {
+                name: "lookup".to_string(),
+                arguments: "{}".to_string(),
+            },
+        }
+    }
+
+    #[test]
+    fn test_strip_empty_keeps_tool_messages() {
+        let mut messages = vec![
+            Message::text(ChatRole::User, "Hi"),
+            Message::text(ChatRole::Assistant, "  "),
+            Message {
+                role: ChatRole::Assistant,
+                tool_calls: Some(vec![tool_call("call_1")]),
+                ..Default::default()
+            },
+            Message {
+                role: ChatRole::Tool,
+                tool_call_id: Some(ToolCallId::new("call_1")),
+                ..Default::default()
+            },
+            Message {
+                role: ChatRole::User,
+                content: MessageContent::Parts(vec![text_part("")]),
+                ..Default::default()
+            },
+        ];
+        normalize_messages(
+            &mut messages,
+            NormalizationRules {
+                strip_empty: true,
+                ..NormalizationRules::none()
+            },
+        );
+        let roles: Vec<_> = messages.iter().map(|m| m.role.clone()).collect();
+        assert_eq!(roles, [ChatRole::User, ChatRole::Assistant, ChatRole::Tool]);
+    }
+
+    #[test]
+    fn test_merge_consecutive_respects_names_and_tool_calls() {
+        let image = ContentPart::Image(ImageContent {
+            content_type: ContentType::ImageUrl,
+            image_url: ImageUrl {
+                url: "https://example.com/a.png".to_string(),
+                detail: None,
+            },
+        });
+        let mut messages = vec![
+            Message::text(ChatRole::User, "Look:"),
+            Message {
+                role: ChatRole::User,
+                content: MessageContent::Parts(vec![image.clone()]),
+                ..Default::default()
+            },
+            Message::text_with_name(ChatRole::User, "Me too", "bob"),
+            Message {
+                role: ChatRole::Assistant,
+                content: MessageContent::Text("Checking".to_string()),
+                tool_calls: Some(vec![tool_call("call_1")]),
+                ..Default::default()
+            },
+            Message::text(ChatRole::Assistant, "Done"),
+        ];
+        normalize_messages(
+            &mut messages,
+            NormalizationRules {
+                merge_consecutive: true,
+                ..NormalizationRules::none()
+            },
+        );
+        assert_eq!(messages.len(), 4);
+        assert_eq!(
+            messages[0].content,
+            MessageContent::Parts(vec![text_part("Look:"), image])
+        );
+        assert_eq!(messages[1].name.as_deref(), Some("bob"));
+        assert_eq!(messages[3].content.as_text(), Some("Done"));
+    }
+
+    #[test]
+    fn test_flatten_text_parts_and_provider_rules() {
+        let mut messages = vec![Message {
+            role: ChatRole::User,
+            content: MessageContent::Parts(vec![text_part("a"), text_part("b")]),
+            ..Default::default()
+        }];
+        normalize_messages(
+            &mut messages,
+            NormalizationRules::for_model("openai/gpt-4o"),
+        );
+        assert!(matches!(messages[0].content, MessageContent::Parts(_)));
+        normalize_messages(
+            &mut messages,
+            NormalizationRules::for_model("mistralai/mistral-large"),
+        );
+        assert_eq!(messages[0].content.as_text(), Some("a\n\nb"));
+
+        assert!(NormalizationRules::for_model("openai/gpt-4o").is_empty());
+        assert!(NormalizationRules::for_model("anthropic/claude-sonnet-4").merge_consecutive);
+    }
+}
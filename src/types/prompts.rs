@@ -0,0 +1,770 @@
+//! Prompt templates with named placeholders.
+//!
+//! A [`PromptTemplate`] is an ordered list of message scaffolds whose text may
+//! contain placeholders. Rendering substitutes variables and produces the
+//! `Vec<Message>` expected by `ChatCompletionRequest`.
+//!
+//! Template syntax:
+//!
+//! - `{{name}}` is replaced with the variable `name` (surrounding whitespace
+//!   inside the braces is ignored).
+//! - `{{> partial}}` is replaced with the rendered partial registered under
+//!   `partial`, which is handy for sharing system prompts between templates.
+//! - `\{{` produces a literal `{{`; no other escapes are interpreted.
+//!
+//! A [`PromptLibrary`] loads versioned [`PromptDefinition`]s from a directory
+//! so prompts can be iterated on without recompiling.
+
+use crate::error::{Error, Result};
+use crate::types::chat::{ChatCompletionRequest, ChatRole, Message, StopSequence};
+use serde::Deserialize;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::Path;
+
+/// Maximum nesting depth for partials, guarding against include cycles.
+const MAX_PARTIAL_DEPTH: usize = 8;
+
+/// Variables used when rendering a [`PromptTemplate`].
+pub type PromptVars = HashMap<String, String>;
+
+/// A reusable set of chat messages with named placeholders.
+///
+/// # Example
+///
+/// ```
+/// use openrouter_api::types::prompts::{PromptTemplate, PromptVars};
+///
+/// let template = PromptTemplate::new()
+///     .with_partial("persona", "You are a helpful {{domain}} assistant.")
+///     .system("{{> persona}}")
+///     .user("Explain {{topic}} in one paragraph.");
+///
+/// let mut vars = PromptVars::new();
+/// vars.insert("domain".into(), "physics".into());
+/// vars.insert("topic".into(), "entropy".into());
+///
+/// let messages = template.render_messages(&vars).unwrap();
+/// assert_eq!(messages.len(), 2);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PromptTemplate {
+    messages: Vec<(ChatRole, String)>,
+    partials: HashMap<String, String>,
+}
+
+impl PromptTemplate {
+    /// Creates an empty template.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a message scaffold with the given role.
+    pub fn with_message(mut self, role: ChatRole, template: impl Into<String>) -> Self {
+        self.messages.push((role, template.into()));
+        self
+    }
+
+    /// Appends a system message scaffold.
+    pub fn system(self, template: impl Into<String>) -> Self {
+        self.with_message(ChatRole::System, template)
+    }
+
+    /// Appends a user message scaffold.
+    pub fn user(self, template: impl Into<String>) -> Self {
+        self.with_message(ChatRole::User, template)
+    }
+
+    /// Appends an assistant message scaffold.
+    pub fn assistant(self, template: impl Into<String>) -> Self {
+        self.with_message(ChatRole::Assistant, template)
+    }
+
+    /// Registers a partial that can be included with `{{> name}}`.
+    pub fn with_partial(mut self, name: impl Into<String>, template: impl Into<String>) -> Self {
+        self.partials.insert(name.into(), template.into());
+        self
+    }
+
+    /// Returns the message scaffolds in order.
+    pub fn messages(&self) -> &[(ChatRole, String)] {
+        &self.messages
+    }
+
+    /// Returns the names of all variables referenced by the template,
+    /// including those inside partials.
+    pub fn placeholders(&self) -> Result<BTreeSet<String>> {
+        let mut names = BTreeSet::new();
+        for (_, template) in &self.messages {
+            self.collect_placeholders(template, 0, &mut names)?;
+        }
+        Ok(names)
+    }
+
+    /// Renders a single template string against `vars`, resolving partials.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::ValidationError` if a variable or partial is missing,
+    /// a placeholder is unterminated or empty, or partials nest too deeply.
+    pub fn render_str(&self, template: &str, vars: &PromptVars) -> Result<String> {
+        self.render_at_depth(template, vars, 0)
+    }
+
+    /// Renders every message scaffold into a chat message.
+    ///
+    /// # Errors
+    ///
+    /// See [`PromptTemplate::render_str`].
+    pub fn render_messages(&self, vars: &PromptVars) -> Result<Vec<Message>> {
+        self.messages
+            .iter()
+            .map(|(role, template)| {
+                let text = self.render_str(template, vars)?;
+                Ok(Message::text(role.clone(), text))
+            })
+            .collect()
+    }
+
+    fn render_at_depth(&self, template: &str, vars: &PromptVars, depth: usize) -> Result<String> {
+        let mut out = String::with_capacity(template.len());
+        for token in tokenize(template)? {
+            match token {
+                Token::Text(text) => out.push_str(&text),
+                Token::Var(name) => match vars.get(name) {
+                    Some(value) => out.push_str(value),
+                    None => {
+                        return Err(Error::ValidationError(format!(
+                            "Missing prompt variable '{name}'"
+                        )))
+                    }
+                },
+                Token::Partial(name) => {
+                    let partial = self.partial(name, depth)?;
+                    out.push_str(&self.render_at_depth(partial, vars, depth + 1)?);
+                }
+            }
+        }
+        Ok(out)
+    }
+
+    fn collect_placeholders(
+        &self,
+        template: &str,
+        depth: usize,
+        names: &mut BTreeSet<String>,
+    ) -> Result<()> {
+        for token in tokenize(template)? {
+            match token {
+                Token::Text(_) => {}
+                Token::Var(name) => {
+                    names.insert(name.to_string());
+                }
+                Token::Partial(name) => {
+                    let partial = self.partial(name, depth)?;
+                    self.collect_placeholders(partial, depth + 1, names)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn partial(&self, name: &str, depth: usize) -> Result<&str> {
+        if depth >= MAX_PARTIAL_DEPTH {
+            return Err(Error::ValidationError(format!(
+                "Prompt partials nested deeper than {MAX_PARTIAL_DEPTH} levels (cycle at '{name}'?)"
+            )));
+        }
+        self.partials
+            .get(name)
+            .map(String::as_str)
+            .ok_or_else(|| Error::ValidationError(format!("Unknown prompt partial '{name}'")))
+    }
+}
+
+/// Sampling parameters a prompt definition applies to its requests.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PromptParameters {
+    pub max_tokens: Option<u32>,
+    pub temperature: Option<f32>,
+    pub top_p: Option<f32>,
+    pub top_k: Option<u32>,
+    pub frequency_penalty: Option<f32>,
+    pub presence_penalty: Option<f32>,
+    pub repetition_penalty: Option<f32>,
+    pub seed: Option<u64>,
+    pub stop: Option<StopSequence>,
+}
+
+impl PromptParameters {
+    /// Copies every parameter that is set onto `request`.
+    pub fn apply(&self, request: &mut ChatCompletionRequest) {
+        macro_rules! copy {
+            ($($field:ident),*) => {
+                $(if self.$field.is_some() {
+                    request.$field = self.$field.clone();
+                })*
+            };
+        }
+        copy!(
+            max_tokens,
+            temperature,
+            top_p,
+            top_k,
+            frequency_penalty,
+            presence_penalty,
+            repetition_penalty,
+            seed,
+            stop
+        );
+    }
+}
+
+/// A single message scaffold in a prompt definition file.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct PromptMessage {
+    pub role: ChatRole,
+    pub content: String,
+}
+
+/// A versioned prompt loaded from a JSON, YAML or TOML file.
+///
+/// ```yaml
+/// name: summarize
+/// version: 2
+/// model: openai/gpt-4o-mini
+/// parameters:
+///   temperature: 0.2
+/// partials:
+///   persona: You are a concise editor.
+/// messages:
+///   - role: system
+///     content: "{{> persona}}"
+///   - role: user
+///     content: "Summarize: {{text}}"
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct PromptDefinition {
+    pub name: String,
+    #[serde(default = "default_prompt_version")]
+    pub version: u32,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Preferred model for this prompt.
+    #[serde(default)]
+    pub model: Option<String>,
+    /// Fallback models, sent as `models` on the request.
+    #[serde(default)]
+    pub models: Option<Vec<String>>,
+    #[serde(default)]
+    pub parameters: PromptParameters,
+    #[serde(default)]
+    pub partials: HashMap<String, String>,
+    pub messages: Vec<PromptMessage>,
+}
+
+fn default_prompt_version() -> u32 {
+    1
+}
+
+impl PromptDefinition {
+    /// Parses a prompt definition from JSON.
+    pub fn from_json_str(source: &str) -> Result<Self> {
+        serde_json::from_str(source)
+            .map_err(|e| Error::ConfigError(format!("Invalid JSON prompt definition: {e}")))
+    }
+
+    /// Parses a prompt definition from YAML.
+    #[cfg(feature = "prompts-yaml")]
+    pub fn from_yaml_str(source: &str) -> Result<Self> {
+        serde_yaml_ng::from_str(source)
+            .map_err(|e| Error::ConfigError(format!("Invalid YAML prompt definition: {e}")))
+    }
+
+    /// Parses a prompt definition from TOML.
+    #[cfg(feature = "prompts-toml")]
+    pub fn from_toml_str(source: &str) -> Result<Self> {
+        toml::from_str(source)
+            .map_err(|e| Error::ConfigError(format!("Invalid TOML prompt definition: {e}")))
+    }
+
+    /// Builds the [`PromptTemplate`] described by this definition.
+    pub fn template(&self) -> PromptTemplate {
+        let template = self
+            .partials
+            .iter()
+            .fold(PromptTemplate::new(), |t, (name, partial)| {
+                t.with_partial(name.clone(), partial.clone())
+            });
+        self.messages.iter().fold(template, |t, message| {
+            t.with_message(message.role.clone(), message.content.clone())
+        })
+    }
+
+    /// Renders a ready-to-send request using the definition's model hint.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::ValidationError` if the definition has no `model` or
+    /// rendering fails.
+    pub fn to_request(&self, vars: &PromptVars) -> Result<ChatCompletionRequest> {
+        let model = self.model.as_deref().ok_or_else(|| {
+            Error::ValidationError(format!(
+                "Prompt '{}' v{} does not specify a model",
+                self.name, self.version
+            ))
+        })?;
+        self.to_request_for_model(model, vars)
+    }
+
+    /// Renders a ready-to-send request for an explicit model.
+    pub fn to_request_for_model(
+        &self,
+        model: &str,
+        vars: &PromptVars,
+    ) -> Result<ChatCompletionRequest> {
+        let mut request = ChatCompletionRequest {
+            model: model.to_string(),
+            messages: self.template().render_messages(vars)?,
+            models: self.models.clone(),
+            ..Default::default()
+        };
+        self.parameters.apply(&mut request);
+        Ok(request)
+    }
+}
+
+/// A collection of versioned prompt definitions, usually loaded from disk.
+///
+/// Prompts are keyed by name; each name may have several versions, and
+/// [`PromptLibrary::get`] returns the highest one.
+#[derive(Debug, Clone, Default)]
+pub struct PromptLibrary {
+    prompts: HashMap<String, BTreeMap<u32, PromptDefinition>>,
+}
+
+impl PromptLibrary {
+    /// Creates an empty library.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads every prompt file in `dir` (non-recursively).
+    ///
+    /// `.json` files are always read. `.yaml`/`.yml` files require the
+    /// `prompts-yaml` feature and `.toml` files the `prompts-toml` feature;
+    /// without them such files are rejected rather than silently skipped.
+    /// Files with any other extension are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::IoError` if the directory cannot be read and
+    /// `Error::ConfigError` for unparsable files or duplicate versions.
+    pub fn from_path(dir: impl AsRef<Path>) -> Result<Self> {
+        let mut paths = std::fs::read_dir(dir.as_ref())?
+            .map(|entry| entry.map(|e| e.path()))
+            .collect::<std::io::Result<Vec<_>>>()?;
+        paths.sort();
+
+        let mut library = Self::new();
+        for path in paths.into_iter().filter(|p| p.is_file()) {
+            if let Some(definition) = load_definition(&path)? {
+                library.insert(definition).map_err(|e| match e {
+                    Error::ConfigError(msg) => {
+                        Error::ConfigError(format!("{}: {msg}", path.display()))
+                    }
+                    other => other,
+                })?;
+            }
+        }
+        Ok(library)
+    }
+
+    /// Adds a definition, rejecting duplicate name/version pairs.
+    pub fn insert(&mut self, definition: PromptDefinition) -> Result<()> {
+        let versions = self.prompts.entry(definition.name.clone()).or_default();
+        if versions.contains_key(&definition.version) {
+            return Err(Error::ConfigError(format!(
+                "Duplicate prompt '{}' version {}",
+                definition.name, definition.version
+            )));
+        }
+        versions.insert(definition.version, definition);
+        Ok(())
+    }
+
+    /// Returns the latest version of the named prompt.
+    pub fn get(&self, name: &str) -> Option<&PromptDefinition> {
+        self.prompts
+            .get(name)
+            .and_then(|versions| versions.values().next_back())
+    }
+
+    /// Returns a specific version of the named prompt.
+    pub fn get_version(&self, name: &str, version: u32) -> Option<&PromptDefinition> {
+        self.prompts.get(name)?.get(&version)
+    }
+
+    /// Returns the prompt names in sorted order.
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<_> = self.prompts.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
+
+    /// Number of distinct prompt names.
+    pub fn len(&self) -> usize {
+        self.prompts.len()
+    }
+
+    /// Whether the library has no prompts.
+    pub fn is_empty(&self) -> bool {
+        self.prompts.is_empty()
+    }
+
+    /// Renders the latest version of `name` into a ready-to-send request.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::ValidationError` if the prompt does not exist or
+    /// cannot be rendered.
+    pub fn request(&self, name: &str, vars: &PromptVars) -> Result<ChatCompletionRequest> {
+        self.get(name)
+            .ok_or_else(|| Error::ValidationError(format!("Unknown prompt '{name}'")))?
+            .to_request(vars)
+    }
+}
+
+fn load_definition(path: &Path) -> Result<Option<PromptDefinition>> {
+    let extension = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(str::to_ascii_lowercase);
+    let parse: fn(&str) -> Result<PromptDefinition> = match extension.as_deref() {
+        Some("json") => PromptDefinition::from_json_str,
+        #[cfg(feature = "prompts-yaml")]
+        Some("yaml" | "yml") => PromptDefinition::from_yaml_str,
+        #[cfg(not(feature = "prompts-yaml"))]
+        Some("yaml" | "yml") => {
+            return Err(Error::ConfigError(format!(
+                "{}: YAML prompt files require the `prompts-yaml` feature",
+                path.display()
+            )))
+        }
+        #[cfg(feature = "prompts-toml")]
+        Some("toml") => PromptDefinition::from_toml_str,
+        #[cfg(not(feature = "prompts-toml"))]
+        Some("toml") => {
+            return Err(Error::ConfigError(format!(
+                "{}: TOML prompt files require the `prompts-toml` feature",
+                path.display()
+            )))
+        }
+        _ => return Ok(None),
+    };
+
+    let source = std::fs::read_to_string(path)?;
+    parse(&source)
+        .map(Some)
+        .map_err(|e| Error::ConfigError(format!("{}: {e}", path.display())))
+}
+
+#[derive(Debug, PartialEq)]
+enum Token<'a> {
+    Text(String),
+    Var(&'a str),
+    Partial(&'a str),
+}
+
+fn tokenize(template: &str) -> Result<Vec<Token<'_>>> {
+    let mut tokens = Vec::new();
+    let mut text = String::new();
+    let mut rest = template;
+
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix("\\{{") {
+            text.push_str("{{");
+            rest = after;
+        } else if let Some(after) = rest.strip_prefix("{{") {
+            let end = after.find("}}").ok_or_else(|| {
+                Error::ValidationError("Unterminated placeholder in prompt template".to_string())
+            })?;
+            let inner = after[..end].trim();
+            let token = match inner.strip_prefix('>') {
+                Some(partial) => Token::Partial(validate_name(partial.trim())?),
+                None => Token::Var(validate_name(inner)?),
+            };
+            if !text.is_empty() {
+                tokens.push(Token::Text(std::mem::take(&mut text)));
+            }
+            tokens.push(token);
+            rest = &after[end + 2..];
+        } else {
+            let ch = rest.chars().next().expect("rest is non-empty");
+            text.push(ch);
+            rest = &rest[ch.len_utf8()..];
+        }
+    }
+
+    if !text.is_empty() {
+        tokens.push(Token::Text(text));
+    }
+    Ok(tokens)
+}
+
+fn validate_name(name: &str) -> Result<&str> {
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'));
+    if valid {
+        Ok(name)
+    } else {
+        Err(Error::ValidationError(format!(
+            "Invalid prompt placeholder name '{name}'"
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::chat::MessageContent;
+
+    fn vars(pairs: &[(&str, &str)]) -> PromptVars {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    fn text_of(message: &Message) -> &str {
+        match &message.content {
+            MessageContent::Text(text) => text,
+            other => panic!("expected text content, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_render_messages_substitutes_variables_and_partials() {
+        let template = PromptTemplate::new()
+            .with_partial("persona", "You are a {{ tone }} assistant.")
+            .system("{{> persona}} Answer in {{lang}}.")
+            .user("Summarize: {{text}}");
+
+        let messages = template
+            .render_messages(&vars(&[
+                ("tone", "terse"),
+                ("lang", "French"),
+                ("text", "hello"),
+            ]))
+            .unwrap();
+
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].role, ChatRole::System);
+        assert_eq!(
+            text_of(&messages[0]),
+            "You are a terse assistant. Answer in French."
+        );
+        assert_eq!(messages[1].role, ChatRole::User);
+        assert_eq!(text_of(&messages[1]), "Summarize: hello");
+    }
+
+    #[test]
+    fn test_escaped_braces_and_values_are_not_reinterpreted() {
+        let template = PromptTemplate::new();
+        let rendered = template
+            .render_str(r"Use \{{name}} for {{x}}", &vars(&[("x", "{{y}}")]))
+            .unwrap();
+        assert_eq!(rendered, "Use {{name}} for {{y}}");
+    }
+
+    #[test]
+    fn test_render_errors() {
+        let template = PromptTemplate::new()
+            .with_partial("a", "{{> b}}")
+            .with_partial("b", "{{> a}}");
+        let empty = PromptVars::new();
+
+        for source in [
+            "{{missing}}",
+            "{{> nope}}",
+            "{{open",
+            "{{}}",
+            "{{bad name}}",
+        ] {
+            let result = template.render_str(source, &empty);
+            assert!(
+                matches!(result, Err(Error::ValidationError(_))),
+                "{source} should fail"
+            );
+        }
+        assert!(matches!(
+            template.render_str("{{> a}}", &empty),
+            Err(Error::ValidationError(_))
+        ));
+    }
+
+    #[test]
+    fn test_placeholders_include_partials() {
+        let template = PromptTemplate::new()
+            .with_partial("sys", "{{role}}")
+            .system("{{> sys}}")
+            .user("{{question}} {{role}}");
+
+        let names: Vec<_> = template.placeholders().unwrap().into_iter().collect();
+        assert_eq!(names, vec!["question".to_string(), "role".to_string()]);
+    }
+
+    struct TempDir(std::path::PathBuf);
+
+    impl TempDir {
+        fn new() -> Self {
+            let path = std::env::temp_dir().join(format!("prompts-{}", uuid::Uuid::new_v4()));
+            std::fs::create_dir_all(&path).unwrap();
+            Self(path)
+        }
+
+        fn write(&self, name: &str, contents: &str) {
+            std::fs::write(self.0.join(name), contents).unwrap();
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    const SUMMARIZE_V1: &str = r#"{
+        "name": "summarize",
+        "model": "openai/gpt-4o-mini",
+        "messages": [{"role": "user", "content": "Summarize: {{text}}"}]
+    }"#;
+
+    const SUMMARIZE_V2: &str = r#"{
+        "name": "summarize",
+        "version": 2,
+        "model": "openai/gpt-4o",
+        "models": ["anthropic/claude-3.5-sonnet"],
+        "parameters": {"temperature": 0.2, "max_tokens": 200, "stop": ["END"]},
+        "partials": {"persona": "You are a concise editor."},
+        "messages": [
+            {"role": "system", "content": "{{> persona}}"},
+            {"role": "user", "content": "Summarize: {{text}}"}
+        ]
+    }"#;
+
+    #[test]
+    fn test_library_loads_latest_version_and_builds_request() {
+        let dir = TempDir::new();
+        dir.write("summarize.v1.json", SUMMARIZE_V1);
+        dir.write("summarize.v2.json", SUMMARIZE_V2);
+        dir.write("README.md", "ignored");
+
+        let library = PromptLibrary::from_path(&dir.0).unwrap();
+        assert_eq!(library.names(), vec!["summarize"]);
+        assert_eq!(library.get("summarize").unwrap().version, 2);
+        assert_eq!(
+            library
+                .get_version("summarize", 1)
+                .unwrap()
+                .model
+                .as_deref(),
+            Some("openai/gpt-4o-mini")
+        );
+
+        let request = library
+            .request("summarize", &vars(&[("text", "a long article")]))
+            .unwrap();
+        assert_eq!(request.model, "openai/gpt-4o");
+        assert_eq!(
+            request.models,
+            Some(vec!["anthropic/claude-3.5-sonnet".to_string()])
+        );
+        assert_eq!(request.temperature, Some(0.2));
+        assert_eq!(request.max_tokens, Some(200));
+        assert!(matches!(request.stop, Some(StopSequence::Multiple(_))));
+        assert_eq!(request.messages.len(), 2);
+        assert_eq!(text_of(&request.messages[0]), "You are a concise editor.");
+        assert_eq!(text_of(&request.messages[1]), "Summarize: a long article");
+    }
+
+    #[test]
+    fn test_library_rejects_duplicates_and_bad_files() {
+        let dir = TempDir::new();
+        dir.write("a.json", SUMMARIZE_V1);
+        dir.write("b.json", SUMMARIZE_V1);
+        assert!(matches!(
+            PromptLibrary::from_path(&dir.0),
+            Err(Error::ConfigError(msg)) if msg.contains("Duplicate")
+        ));
+
+        let dir = TempDir::new();
+        dir.write("broken.json", "{\"name\": ");
+        assert!(matches!(
+            PromptLibrary::from_path(&dir.0),
+            Err(Error::ConfigError(_))
+        ));
+
+        assert!(matches!(
+            PromptLibrary::from_path(dir.0.join("missing")),
+            Err(Error::IoError(_))
+        ));
+    }
+
+    #[test]
+    fn test_definition_without_model_requires_explicit_model() {
+        let mut definition = PromptDefinition::from_json_str(SUMMARIZE_V1).unwrap();
+        definition.model = None;
+        let vars = vars(&[("text", "x")]);
+
+        assert!(matches!(
+            definition.to_request(&vars),
+            Err(Error::ValidationError(_))
+        ));
+        let request = definition
+            .to_request_for_model("openai/gpt-4o", &vars)
+            .unwrap();
+        assert_eq!(request.model, "openai/gpt-4o");
+    }
+
+    #[cfg(not(feature = "prompts-yaml"))]
+    #[test]
+    fn test_yaml_files_require_feature() {
+        let dir = TempDir::new();
+        dir.write("p.yaml", "name: p");
+        assert!(matches!(
+            PromptLibrary::from_path(&dir.0),
+            Err(Error::ConfigError(msg)) if msg.contains("prompts-yaml")
+        ));
+    }
+
+    #[cfg(feature = "prompts-yaml")]
+    #[test]
+    fn test_library_loads_yaml() {
+        let dir = TempDir::new();
+        dir.write(
+            "p.yml",
+            "name: greet\nmodel: openai/gpt-4o\nparameters:\n  temperature: 0.5\nmessages:\n  - role: user\n    content: \"Hi {{who}}\"\n",
+        );
+        let library = PromptLibrary::from_path(&dir.0).unwrap();
+        let request = library.request("greet", &vars(&[("who", "Ada")])).unwrap();
+        assert_eq!(request.temperature, Some(0.5));
+        assert_eq!(text_of(&request.messages[0]), "Hi Ada");
+    }
+
+    #[cfg(feature = "prompts-toml")]
+    #[test]
+    fn test_library_loads_toml() {
+        let dir = TempDir::new();
+        dir.write(
+            "p.toml",
+            "name = \"greet\"\nversion = 3\nmodel = \"openai/gpt-4o\"\n\n[[messages]]\nrole = \"user\"\ncontent = \"Hi {{who}}\"\n",
+        );
+        let library = PromptLibrary::from_path(&dir.0).unwrap();
+        assert_eq!(library.get("greet").unwrap().version, 3);
+        let request = library.request("greet", &vars(&[("who", "Ada")])).unwrap();
+        assert_eq!(text_of(&request.messages[0]), "Hi Ada");
+    }
+}
@@ -92,12 +92,16 @@ impl Provider {
 pub struct ProvidersResponse {
     /// List of available providers
     pub data: Vec<Provider>,
+    /// Set when the list comes from the bundled snapshot (`offline` feature)
+    /// rather than the API.
+    #[serde(skip)]
+    pub stale: bool,
 }
 
 impl ProvidersResponse {
     /// Creates a new ProvidersResponse
     pub fn new(data: Vec<Provider>) -> Self {
-        Self { data }
+        Self { data, stale: false }
     }
 
     /// Returns the number of providers
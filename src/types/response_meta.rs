@@ -0,0 +1,111 @@
+//! Metadata read from the headers of API responses.
+
+use reqwest::header::HeaderMap;
+
+/// Rate limit state reported in the `x-ratelimit-*` response headers.
+///
+/// This is the live state after a request; the limits configured for a key
+/// are in [`RateLimitInfo`](crate::types::key_info::RateLimitInfo).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RateLimitStatus {
+    /// Requests allowed in the current window (`x-ratelimit-limit`).
+    pub limit: Option<u64>,
+    /// Requests left in the current window (`x-ratelimit-remaining`).
+    pub remaining: Option<u64>,
+    /// When the window resets, in Unix milliseconds (`x-ratelimit-reset`).
+    pub reset_ms: Option<u64>,
+}
+
+/// Metadata from the headers of a response, for correlating a call with
+/// OpenRouter support or logs.
+#[derive(Debug, Clone, Default)]
+pub struct ResponseMeta {
+    /// The request ID (`x-request-id`), if returned.
+    pub request_id: Option<String>,
+    /// Time the server spent on the request, in milliseconds
+    /// (`x-processing-ms` or `openai-processing-ms`).
+    pub processing_ms: Option<u64>,
+    /// Rate limit state, if any `x-ratelimit-*` header was returned.
+    pub rate_limit: Option<RateLimitStatus>,
+    /// All response headers.
+    pub headers: HeaderMap,
+}
+
+impl ResponseMeta {
+    /// Reads the metadata from response headers.
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        let text = |name: &str| {
+            headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+        };
+        let number = |name: &str| text(name).and_then(|value| value.parse::<u64>().ok());
+
+        let rate_limit = RateLimitStatus {
+            limit: number("x-ratelimit-limit"),
+            remaining: number("x-ratelimit-remaining"),
+            reset_ms: number("x-ratelimit-reset"),
+        };
+        Self {
+            request_id: text("x-request-id").map(str::to_string),
+            processing_ms: number("x-processing-ms").or_else(|| number("openai-processing-ms")),
+            rate_limit: (rate_limit != RateLimitStatus::default()).then_some(rate_limit),
+            headers: headers.clone(),
+        }
+    }
+}
+
+/// A response paired with the metadata from its headers.
+///
+/// Returned by the `*_with_meta` methods on `ChatApi` and `CompletionApi`.
+#[derive(Debug)]
+pub struct WithMeta<T> {
+    /// The API response.
+    pub response: T,
+    /// Metadata from the response headers.
+    pub meta: ResponseMeta,
+}
+
+impl<T> WithMeta<T> {
+    /// Returns the response, dropping the metadata.
+    pub fn into_response(self) -> T {
+        self.response
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn test_meta_reads_request_id_timing_and_rate_limit() {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-request-id", HeaderValue::from_static("req-123"));
+        headers.insert("openai-processing-ms", HeaderValue::from_static("250"));
+        headers.insert("x-ratelimit-remaining", HeaderValue::from_static("19"));
+        headers.insert(
+            "x-ratelimit-reset",
+            HeaderValue::from_static("1760000000000"),
+        );
+        headers.insert("x-ratelimit-limit", HeaderValue::from_static("n/a"));
+
+        let meta = ResponseMeta::from_headers(&headers);
+        assert_eq!(meta.request_id.as_deref(), Some("req-123"));
+        assert_eq!(meta.processing_ms, Some(250));
+        assert_eq!(
+            meta.rate_limit,
+            Some(RateLimitStatus {
+                limit: None,
+                remaining: Some(19),
+                reset_ms: Some(1_760_000_000_000),
+            })
+        );
+        assert_eq!(meta.headers.len(), 5);
+
+        let meta = ResponseMeta::from_headers(&HeaderMap::new());
+        assert!(meta.request_id.is_none() && meta.rate_limit.is_none());
+    }
+}
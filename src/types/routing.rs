@@ -1,5 +1,13 @@
+use crate::types::models::{ModelEndpoint, ModelInfo};
+use crate::utils::cache::Cache;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// How long fetched model and endpoint data is reused by `select_models`.
+const MODEL_CANDIDATE_TTL: Duration = Duration::from_secs(300);
 
 /// Model Coverage Profile (MCP) for ensuring consistent model availability
 /// while optimizing for quality, cost, and reliability.
@@ -48,6 +56,7 @@ pub enum PredefinedModelCoverageProfile {
 
 /// Router configuration for model selection and fallback behavior
 #[derive(Debug, Serialize)]
+#[non_exhaustive]
 pub struct RouterConfig {
     /// The model coverage profile to use
     pub profile: PredefinedModelCoverageProfile,
@@ -55,6 +64,243 @@ pub struct RouterConfig {
     /// Additional provider preferences
     #[serde(skip_serializing_if = "Option::is_none")]
     pub provider_preferences: Option<crate::models::provider_preferences::ProviderPreferences>,
+
+    /// User-defined ranking that overrides the profile once
+    /// `OpenRouterClient::select_models` has run.
+    #[serde(skip)]
+    pub selector: Option<Arc<dyn ModelSelector>>,
+
+    /// Reorders `provider.order` by live endpoint uptime.
+    #[serde(skip)]
+    pub uptime_routing: Option<UptimeRouting>,
+}
+
+impl RouterConfig {
+    /// Creates a configuration for `profile` with no preferences, selector
+    /// or uptime routing.
+    pub fn new(profile: PredefinedModelCoverageProfile) -> Self {
+        Self {
+            profile,
+            provider_preferences: None,
+            selector: None,
+            uptime_routing: None,
+        }
+    }
+}
+
+/// A model considered by a [`ModelSelector`], with its provider endpoints.
+#[derive(Debug, Clone)]
+pub struct ModelCandidate {
+    pub model: ModelInfo,
+    pub endpoints: Vec<ModelEndpoint>,
+}
+
+/// Ranks models for routing, e.g. trading price against latency or quality.
+///
+/// `OpenRouterClient::select_models` fetches the model and endpoint data for
+/// the selector's candidates (reusing it for five minutes), asks the selector
+/// to rank them, and remembers the ranking. Requests built with
+/// `chat_request_builder` then use the top model as primary and the rest as
+/// fallbacks.
+pub trait ModelSelector: fmt::Debug + Send + Sync {
+    /// IDs of the models to consider, e.g. `"openai/gpt-4o"`.
+    fn candidates(&self) -> Vec<String>;
+
+    /// Returns model IDs best first. Models left out are not used.
+    fn rank(&self, candidates: &[ModelCandidate]) -> Vec<String>;
+}
+
+/// Cached candidate data and the latest ranking from a [`ModelSelector`].
+#[derive(Debug)]
+pub(crate) struct ModelSelection {
+    pub(crate) candidates: Cache<String, ModelCandidate>,
+    pub(crate) ranking: Option<Vec<String>>,
+}
+
+impl Default for ModelSelection {
+    fn default() -> Self {
+        Self {
+            candidates: Cache::new(MODEL_CANDIDATE_TTL),
+            ranking: None,
+        }
+    }
+}
+
+/// Settings for routing away from providers with degraded uptime.
+///
+/// A provider is demoted to the end of `provider.order` when its uptime over
+/// the last 30 minutes drops below `degrade_below` or its endpoint reports a
+/// negative status. It is only promoted again once uptime reaches
+/// `recover_above`, so a provider hovering around one threshold doesn't flap
+/// between positions.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UptimeRouting {
+    /// Models whose endpoints are refreshed. When empty, the model picked by
+    /// `chat_request_builder` is monitored.
+    pub models: Vec<String>,
+    /// How long endpoint stats are used before being fetched again.
+    pub refresh_interval: Duration,
+    /// Uptime percentage below which a provider is demoted.
+    pub degrade_below: f64,
+    /// Uptime percentage a demoted provider must reach to be promoted.
+    pub recover_above: f64,
+}
+
+impl Default for UptimeRouting {
+    fn default() -> Self {
+        Self {
+            models: Vec::new(),
+            refresh_interval: Duration::from_secs(60),
+            degrade_below: 90.0,
+            recover_above: 97.0,
+        }
+    }
+}
+
+impl UptimeRouting {
+    /// Creates uptime routing with the default thresholds.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the models to monitor.
+    pub fn with_models<I, S>(mut self, models: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.models = models.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Sets how often endpoint stats are refreshed.
+    pub fn with_refresh_interval(mut self, interval: Duration) -> Self {
+        self.refresh_interval = interval;
+        self
+    }
+
+    /// Sets the demotion and promotion thresholds, in percent.
+    pub fn with_thresholds(mut self, degrade_below: f64, recover_above: f64) -> Self {
+        self.degrade_below = degrade_below;
+        self.recover_above = recover_above.max(degrade_below);
+        self
+    }
+}
+
+/// Uptime of one provider serving a model.
+#[derive(Debug, Clone)]
+struct ProviderUptime {
+    provider: String,
+    uptime: Option<f64>,
+    status: Option<i32>,
+}
+
+/// The last endpoint stats fetched for a model.
+#[derive(Debug)]
+struct ModelHealth {
+    refreshed_at: Instant,
+    providers: Vec<ProviderUptime>,
+    degraded: HashSet<String>,
+}
+
+/// Provider health per model, as tracked by uptime routing.
+#[derive(Debug, Default)]
+pub(crate) struct ProviderHealth {
+    models: HashMap<String, ModelHealth>,
+}
+
+impl ProviderHealth {
+    /// Whether `model` has no stats or stats older than the refresh interval.
+    pub(crate) fn is_stale(&self, model: &str, routing: &UptimeRouting) -> bool {
+        self.models
+            .get(model)
+            .is_none_or(|health| health.refreshed_at.elapsed() >= routing.refresh_interval)
+    }
+
+    /// Records fresh endpoint stats, demoting and promoting providers with
+    /// hysteresis.
+    pub(crate) fn update(
+        &mut self,
+        model: &str,
+        endpoints: &[ModelEndpoint],
+        routing: &UptimeRouting,
+    ) {
+        let mut providers: Vec<_> = endpoints
+            .iter()
+            .map(|endpoint| ProviderUptime {
+                provider: endpoint
+                    .tag
+                    .clone()
+                    .unwrap_or_else(|| endpoint.provider_name.clone()),
+                uptime: endpoint.uptime_last_30m,
+                status: endpoint.status,
+            })
+            .collect();
+        providers.sort_by(|a, b| {
+            b.uptime
+                .unwrap_or(100.0)
+                .total_cmp(&a.uptime.unwrap_or(100.0))
+        });
+
+        let previous = self
+            .models
+            .remove(model)
+            .map(|health| health.degraded)
+            .unwrap_or_default();
+        let degraded = providers
+            .iter()
+            .filter(|p| {
+                let failing = p.status.is_some_and(|status| status < 0);
+                let uptime = p.uptime.unwrap_or(100.0);
+                if previous.contains(&p.provider) {
+                    failing || uptime < routing.recover_above
+                } else {
+                    failing || uptime < routing.degrade_below
+                }
+            })
+            .map(|p| p.provider.clone())
+            .collect();
+
+        self.models.insert(
+            model.to_string(),
+            ModelHealth {
+                refreshed_at: Instant::now(),
+                providers,
+                degraded,
+            },
+        );
+    }
+
+    /// Providers currently demoted for `model`, in no particular order.
+    pub(crate) fn degraded(&self, model: &str) -> Vec<String> {
+        self.models
+            .get(model)
+            .map(|health| health.degraded.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// The provider order for `model`: healthy providers first, demoted
+    /// providers last.
+    ///
+    /// A `preferred` order is kept apart from moving demoted providers to the
+    /// end; otherwise healthy providers are sorted by uptime. Returns `None`
+    /// when there are no stats for the model.
+    pub(crate) fn order(&self, model: &str, preferred: Option<&[String]>) -> Option<Vec<String>> {
+        let health = self.models.get(model)?;
+        let providers: Vec<String> = match preferred {
+            Some(order) => order.to_vec(),
+            None => health
+                .providers
+                .iter()
+                .map(|p| p.provider.clone())
+                .collect(),
+        };
+        let (mut healthy, degraded): (Vec<_>, Vec<_>) = providers
+            .into_iter()
+            .partition(|provider| !health.degraded.contains(provider));
+        healthy.extend(degraded);
+        Some(healthy)
+    }
 }
 
 /// Predefined model groups for common tasks
@@ -143,6 +389,8 @@ mod tests {
         let cfg = RouterConfig {
             profile: PredefinedModelCoverageProfile::LowestLatency,
             provider_preferences: None,
+            selector: None,
+            uptime_routing: None,
         };
         let json = serde_json::to_value(&cfg).unwrap();
         assert_eq!(json["profile"], "lowest_latency");
@@ -153,6 +401,8 @@ mod tests {
         let cfg = RouterConfig {
             profile: PredefinedModelCoverageProfile::Custom(ModelGroups::general()),
             provider_preferences: None,
+            selector: None,
+            uptime_routing: None,
         };
         let json = serde_json::to_value(&cfg).unwrap();
         // Custom variant carries data, so it serializes as { "custom": {…} }
@@ -163,4 +413,60 @@ mod tests {
         );
         assert_eq!(json["profile"]["custom"]["primary"], "openai/gpt-4o");
     }
+
+    fn endpoint(tag: &str, uptime: f64) -> ModelEndpoint {
+        serde_json::from_value(serde_json::json!({
+            "name": tag,
+            "provider_name": tag,
+            "tag": tag,
+            "pricing": {"prompt": "0", "completion": "0"},
+            "status": 0,
+            "uptime_last_30m": uptime
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_provider_health_orders_degraded_providers_last() {
+        let routing = UptimeRouting::new();
+        let mut health = ProviderHealth::default();
+        assert!(health.is_stale("m", &routing));
+        assert_eq!(health.order("m", None), None);
+
+        health.update(
+            "m",
+            &[
+                endpoint("slow", 80.0),
+                endpoint("fast", 99.0),
+                endpoint("ok", 95.0),
+            ],
+            &routing,
+        );
+        assert!(!health.is_stale("m", &routing));
+        assert_eq!(health.degraded("m"), ["slow"]);
+        assert_eq!(health.order("m", None).unwrap(), ["fast", "ok", "slow"]);
+
+        let preferred = ["slow".to_string(), "ok".to_string()];
+        assert_eq!(health.order("m", Some(&preferred)).unwrap(), ["ok", "slow"]);
+    }
+
+    #[test]
+    fn test_provider_health_recovery_uses_hysteresis() {
+        let routing = UptimeRouting::new().with_thresholds(90.0, 97.0);
+        let mut health = ProviderHealth::default();
+
+        health.update("m", &[endpoint("a", 85.0)], &routing);
+        assert_eq!(health.degraded("m"), ["a"]);
+
+        // Above the demotion threshold, but not yet recovered.
+        health.update("m", &[endpoint("a", 93.0)], &routing);
+        assert_eq!(health.degraded("m"), ["a"]);
+
+        health.update("m", &[endpoint("a", 98.0)], &routing);
+        assert!(health.degraded("m").is_empty());
+
+        // A healthy provider at the same uptime stays healthy.
+        health.update("m", &[endpoint("a", 93.0)], &routing);
+        assert!(health.degraded("m").is_empty());
+    }
 }
@@ -0,0 +1,163 @@
+//! Range-checked sampling parameters.
+//!
+//! Like `Price`, these wrap a number and serialize as it, but they can only
+//! hold values the API accepts. Floats convert with `TryFrom`, failing with
+//! `Error::ConfigError` when out of range.
+
+use crate::error::Error;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+macro_rules! sampling_value {
+    (
+        $(#[$doc:meta])*
+        $name:ident, $what:literal, $range:literal, |$v:ident| $valid:expr
+    ) => {
+        $(#[$doc])*
+        #[derive(Debug, Clone, Copy, Serialize, PartialEq, PartialOrd)]
+        #[serde(transparent)]
+        pub struct $name(f64);
+
+        impl $name {
+            #[doc = concat!("Creates a ", $what, ", or `None` outside ", $range, ".")]
+            pub fn new(value: impl Into<f64>) -> Option<Self> {
+                let $v = value.into();
+                ($v.is_finite() && $valid).then_some(Self($v))
+            }
+
+            #[doc = concat!("Creates a ", $what, ", panicking outside ", $range, ".")]
+            ///
+            /// # Panics
+            /// Panics if the value is out of range or NaN.
+            pub fn new_unchecked(value: impl Into<f64>) -> Self {
+                let value = value.into();
+                Self::new(value).unwrap_or_else(|| {
+                    panic!(concat!($what, " must be in ", $range, ", got {}"), value)
+                })
+            }
+
+            /// Returns the underlying value.
+            pub fn as_f64(&self) -> f64 {
+                self.0
+            }
+
+            /// Returns the value as the `f32` used by request fields.
+            pub fn as_f32(&self) -> f32 {
+                self.0 as f32
+            }
+        }
+
+        impl TryFrom<f64> for $name {
+            type Error = Error;
+
+            fn try_from(value: f64) -> Result<Self, Error> {
+                Self::new(value).ok_or_else(|| {
+                    Error::ConfigError(format!(
+                        concat!($what, " must be in ", $range, ", got {}"),
+                        value
+                    ))
+                })
+            }
+        }
+
+        impl TryFrom<f32> for $name {
+            type Error = Error;
+
+            fn try_from(value: f32) -> Result<Self, Error> {
+                Self::try_from(f32_to_f64(value))
+            }
+        }
+
+        impl From<$name> for f64 {
+            fn from(value: $name) -> Self {
+                value.0
+            }
+        }
+
+        impl From<$name> for f32 {
+            fn from(value: $name) -> Self {
+                value.as_f32()
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{}", self.0)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                let value = f64::deserialize(deserializer)?;
+                Self::new(value).ok_or_else(|| {
+                    serde::de::Error::custom(format!(
+                        concat!($what, " must be in ", $range, ", got {}"),
+                        value
+                    ))
+                })
+            }
+        }
+    };
+}
+
+/// Widens through the decimal form, so 0.7f32 becomes 0.7 rather than
+/// 0.699999988...
+pub(crate) fn f32_to_f64(value: f32) -> f64 {
+    value.to_string().parse().unwrap_or(f64::from(value))
+}
+
+sampling_value! {
+    /// Sampling temperature, in `[0.0, 2.0]`.
+    Temperature, "temperature", "[0.0, 2.0]", |v| (0.0..=2.0).contains(&v)
+}
+
+sampling_value! {
+    /// Nucleus sampling threshold, in `(0.0, 1.0]`.
+    TopP, "top_p", "(0.0, 1.0]", |v| v > 0.0 && v <= 1.0
+}
+
+sampling_value! {
+    /// Frequency or presence penalty, in `[-2.0, 2.0]`.
+    PenaltyValue, "penalty", "[-2.0, 2.0]", |v| (-2.0..=2.0).contains(&v)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sampling_values_check_ranges() {
+        assert_eq!(Temperature::new(0.7).unwrap().as_f64(), 0.7);
+        assert_eq!(Temperature::try_from(0.7f32).unwrap().as_f64(), 0.7);
+        assert!(Temperature::new(2.5).is_none());
+        assert!(Temperature::new(f64::NAN).is_none());
+        assert!(TopP::new(0.0).is_none());
+        assert!(TopP::new(1.0).is_some());
+        assert!(PenaltyValue::new(-2.0).is_some());
+        assert!(PenaltyValue::new(2.1).is_none());
+
+        assert_eq!(
+            serde_json::to_string(&TopP::try_from(0.9).unwrap()).unwrap(),
+            "0.9"
+        );
+        assert_eq!(
+            serde_json::from_str::<PenaltyValue>("-1.5").unwrap(),
+            PenaltyValue::try_from(-1.5).unwrap()
+        );
+        assert!(serde_json::from_str::<Temperature>("3").is_err());
+    }
+
+    #[test]
+    fn test_sampling_value_try_from_out_of_range_fails() {
+        match Temperature::try_from(5.0) {
+            Err(Error::ConfigError(message)) => {
+                assert_eq!(message, "temperature must be in [0.0, 2.0], got 5")
+            }
+            other => panic!("expected ConfigError, got {other:?}"),
+        }
+        assert!(TopP::try_from(f32::NAN).is_err());
+    }
+}
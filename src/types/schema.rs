@@ -0,0 +1,42 @@
+//! JSON Schemas for the request and response types (`schema-export` feature).
+//!
+//! Services that exchange payloads with an application built on this crate,
+//! but aren't written in Rust, can validate them against the same
+//! definitions. The schemas follow the serde representation, so they
+//! describe exactly what the crate sends and accepts.
+
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+macro_rules! schemas {
+    ($($ty:ty),* $(,)?) => {
+        BTreeMap::from([
+            $((stringify!($ty), schemars::schema_for!($ty).to_value()),)*
+        ])
+    };
+}
+
+/// Returns a JSON Schema (draft 2020-12) for each request and response type,
+/// keyed by type name. Every schema is self-contained, with the types it
+/// references under `$defs`.
+///
+/// ```
+/// let schemas = openrouter_api::types::schema();
+/// let request = &schemas["ChatCompletionRequest"];
+/// assert_eq!(request["required"], serde_json::json!(["model", "messages"]));
+/// ```
+pub fn schema() -> BTreeMap<&'static str, Value> {
+    use crate::types::chat::{ChatCompletionChunk, ChatCompletionRequest, ChatCompletionResponse};
+    use crate::types::completion::{CompletionRequest, CompletionResponse};
+    use crate::types::embeddings::{EmbeddingRequest, EmbeddingResponse};
+
+    schemas![
+        ChatCompletionRequest,
+        ChatCompletionResponse,
+        ChatCompletionChunk,
+        CompletionRequest,
+        CompletionResponse,
+        EmbeddingRequest,
+        EmbeddingResponse,
+    ]
+}
@@ -0,0 +1,234 @@
+//! Answering a question from web search results with citations.
+//!
+//! `OpenRouterClient::search_and_answer` runs a web search, numbers the top
+//! results in a prompt that asks the model to cite them as `[1]`, `[2]`, …,
+//! and maps the markers in the answer back to the results as [`Citation`]s.
+
+use crate::types::chat::{ChatCompletionRequest, ChatCompletionResponse, ChatRole, Message};
+use crate::types::web_search::WebSearchResult;
+
+/// Number of search results given to the model by default.
+pub const DEFAULT_MAX_SOURCES: usize = 5;
+
+/// Instructions placed before the numbered sources.
+const CITATION_INSTRUCTIONS: &str = "Answer the user's question using only the numbered search \
+results below. After each claim, cite the result that supports it by its number in square \
+brackets, for example [1] or [2][3]. Do not cite anything else. If the results do not contain \
+the answer, say so.";
+
+/// Options for `OpenRouterClient::search_and_answer`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SummarizeOptions {
+    /// Model that writes the answer.
+    pub model: String,
+    /// How many of the top search results to include in the prompt. Also
+    /// sent as the search's `num_results` (at most 100).
+    pub max_sources: usize,
+    /// Replaces the default citation instructions. The numbered sources are
+    /// appended after it.
+    pub instructions: Option<String>,
+    pub max_tokens: Option<u32>,
+    pub temperature: Option<f32>,
+}
+
+impl SummarizeOptions {
+    /// Creates options answering with `model` from the top
+    /// [`DEFAULT_MAX_SOURCES`] results.
+    pub fn new(model: impl Into<String>) -> Self {
+        Self {
+            model: model.into(),
+            max_sources: DEFAULT_MAX_SOURCES,
+            instructions: None,
+            max_tokens: None,
+            temperature: None,
+        }
+    }
+
+    /// Sets how many results are included in the prompt (minimum 1).
+    pub fn with_max_sources(mut self, max_sources: usize) -> Self {
+        self.max_sources = max_sources.max(1);
+        self
+    }
+
+    /// Replaces the default citation instructions.
+    pub fn with_instructions(mut self, instructions: impl Into<String>) -> Self {
+        self.instructions = Some(instructions.into());
+        self
+    }
+
+    pub fn with_max_tokens(mut self, max_tokens: u32) -> Self {
+        self.max_tokens = Some(max_tokens);
+        self
+    }
+
+    pub fn with_temperature(mut self, temperature: f32) -> Self {
+        self.temperature = Some(temperature);
+        self
+    }
+}
+
+/// A search result the answer cites.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Citation {
+    /// The marker number used in the answer, e.g. `2` for `[2]`.
+    pub index: usize,
+    pub title: String,
+    pub url: String,
+    pub snippet: Option<String>,
+}
+
+/// An answer written from search results.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct SearchAnswer {
+    /// The answer text, with citation markers left in place.
+    pub answer: String,
+    /// Sources cited in the answer, in order of first citation.
+    pub citations: Vec<Citation>,
+    /// Every result given to the model; `sources[i]` is marker `[i + 1]`.
+    pub sources: Vec<WebSearchResult>,
+    /// The underlying chat response, for usage and metadata.
+    pub response: ChatCompletionResponse,
+}
+
+impl SearchAnswer {
+    pub(crate) fn new(sources: Vec<WebSearchResult>, response: ChatCompletionResponse) -> Self {
+        let answer = response
+            .first_choice()
+            .map(|choice| choice.message.content.to_text())
+            .unwrap_or_default();
+        let citations = extract_citations(&answer, &sources);
+        Self {
+            answer,
+            citations,
+            sources,
+            response,
+        }
+    }
+
+    /// Sources given to the model but not cited in the answer.
+    pub fn uncited_sources(&self) -> impl Iterator<Item = &WebSearchResult> + '_ {
+        self.sources.iter().enumerate().filter_map(|(i, source)| {
+            (!self.citations.iter().any(|c| c.index == i + 1)).then_some(source)
+        })
+    }
+}
+
+/// Builds the chat request asking `options.model` to answer `query` from
+/// `sources`.
+pub(crate) fn build_request(
+    query: &str,
+    sources: &[WebSearchResult],
+    options: &SummarizeOptions,
+) -> ChatCompletionRequest {
+    let mut system = options
+        .instructions
+        .clone()
+        .unwrap_or_else(|| CITATION_INSTRUCTIONS.to_string());
+    system.push_str("\n\nSearch results:");
+    if sources.is_empty() {
+        system.push_str("\n\n(no results)");
+    }
+    for (i, source) in sources.iter().enumerate() {
+        system.push_str(&format!(
+            "\n\n[{}] {}\nURL: {}",
+            i + 1,
+            source.title,
+            source.url
+        ));
+        if let Some(snippet) = &source.snippet {
+            system.push('\n');
+            system.push_str(snippet);
+        }
+    }
+
+    ChatCompletionRequest {
+        model: options.model.clone(),
+        messages: vec![
+            Message::text(ChatRole::System, system),
+            Message::text(ChatRole::User, query),
+        ],
+        max_tokens: options.max_tokens,
+        temperature: options.temperature,
+        ..Default::default()
+    }
+}
+
+/// Finds `[n]` markers in `answer` that refer to one of `sources`.
+///
+/// Markers outside `1..=sources.len()` are ignored; each source is listed
+/// once, at its first citation.
+pub(crate) fn extract_citations(answer: &str, sources: &[WebSearchResult]) -> Vec<Citation> {
+    let mut citations: Vec<Citation> = Vec::new();
+    let mut rest = answer;
+    while let Some(open) = rest.find('[') {
+        rest = &rest[open + 1..];
+        let Some(close) = rest.find(']') else { break };
+        // Markers may list several sources, e.g. `[1, 3]`.
+        for part in rest[..close].split(',') {
+            let Ok(index) = part.trim().parse::<usize>() else {
+                continue;
+            };
+            let Some(source) = index.checked_sub(1).and_then(|i| sources.get(i)) else {
+                continue;
+            };
+            if citations.iter().all(|citation| citation.index != index) {
+                citations.push(Citation {
+                    index,
+                    title: source.title.clone(),
+                    url: source.url.clone(),
+                    snippet: source.snippet.clone(),
+                });
+            }
+        }
+    }
+    citations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn source(name: &str) -> WebSearchResult {
+        WebSearchResult {
+            title: name.to_string(),
+            url: format!("https://{name}.example.com"),
+            snippet: Some(format!("About {name}")),
+        }
+    }
+
+    #[test]
+    fn test_build_request_numbers_sources() {
+        let sources = [source("tokio"), source("smol")];
+        let request = build_request(
+            "Which async runtime?",
+            &sources,
+            &SummarizeOptions::new("openai/gpt-4o").with_max_tokens(200),
+        );
+
+        assert_eq!(request.model, "openai/gpt-4o");
+        assert_eq!(request.max_tokens, Some(200));
+        let system = request.messages[0].content.to_text();
+        assert!(system.starts_with(CITATION_INSTRUCTIONS));
+        assert!(system.contains("[1] tokio\nURL: https://tokio.example.com\nAbout tokio"));
+        assert!(system.contains("[2] smol"));
+        assert_eq!(
+            request.messages[1].content.to_text(),
+            "Which async runtime?"
+        );
+    }
+
+    #[test]
+    fn test_extract_citations_maps_markers_to_sources() {
+        let sources = [source("a"), source("b"), source("c")];
+        let citations = extract_citations(
+            "Use c [3]. It is fast [3][1, 2]; see [7], [x] and [].",
+            &sources,
+        );
+
+        let indices: Vec<_> = citations.iter().map(|c| c.index).collect();
+        assert_eq!(indices, [3, 1, 2]);
+        assert_eq!(citations[0].url, "https://c.example.com");
+        assert!(extract_citations("No citations [", &sources).is_empty());
+    }
+}
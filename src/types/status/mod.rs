@@ -11,6 +11,7 @@ use serde::{Deserialize, Serialize};
 /// Replaces `Option<bool>` to make invalid states unrepresentable.
 /// Supports multiple serialization formats for API compatibility.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Default)]
+#[cfg_attr(feature = "schema-export", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 pub enum StreamingStatus {
     /// Streaming has not started
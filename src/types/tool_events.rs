@@ -0,0 +1,285 @@
+//! Typed events for tool calls in a chat completion stream.
+//!
+//! Streamed tool calls arrive as `delta.tool_calls[*]` fragments: the ID and
+//! name in the first one, then pieces of the JSON arguments string.
+//! [`ToolCallTracker`] turns those fragments into [`ToolStreamEvent`]s, and
+//! parses each call's arguments as they arrive so it can report the call as
+//! finished as soon as its JSON is complete.
+
+use crate::error::{Error, Result};
+use crate::models::tool::ToolCallChunk;
+use crate::types::chat::ChatCompletionChunk;
+use crate::utils::partial_json::PartialJsonParser;
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// An event from [`ToolCallTracker`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum ToolStreamEvent {
+    /// Text content streamed for the choice at `choice`.
+    Content { choice: u32, text: String },
+    /// A tool call's ID and function name are known.
+    ToolCallStarted { id: String, name: String },
+    /// The next piece of a tool call's arguments string.
+    ToolCallArgumentsDelta { id: String, chunk: String },
+    /// A tool call's arguments are complete. Calls streamed without
+    /// arguments finish with an empty object.
+    ToolCallFinished { id: String, arguments: Value },
+}
+
+#[derive(Debug, Default)]
+struct PendingCall {
+    id: Option<String>,
+    name: Option<String>,
+    /// Arguments received before the ID and name were both known.
+    buffered: String,
+    received_arguments: bool,
+    parser: PartialJsonParser,
+    started: bool,
+    finished: bool,
+}
+
+impl PendingCall {
+    fn push(&mut self, chunk: ToolCallChunk, events: &mut Vec<ToolStreamEvent>) -> Result<()> {
+        if self.id.is_none() {
+            self.id = chunk.id;
+        }
+        let (name, arguments) = chunk
+            .function
+            .map(|function| (function.name, function.arguments))
+            .unwrap_or_default();
+        if let Some(name) = name {
+            self.name.get_or_insert_with(String::new).push_str(&name);
+        }
+        if let Some(arguments) = arguments.filter(|arguments| !arguments.is_empty()) {
+            self.buffered.push_str(&arguments);
+        }
+        self.flush(events)
+    }
+
+    /// Emits whatever the call has ready once its ID and name are known.
+    fn flush(&mut self, events: &mut Vec<ToolStreamEvent>) -> Result<()> {
+        let (Some(id), Some(name)) = (&self.id, &self.name) else {
+            return Ok(());
+        };
+        if !self.started {
+            self.started = true;
+            events.push(ToolStreamEvent::ToolCallStarted {
+                id: id.clone(),
+                name: name.clone(),
+            });
+        }
+        if self.buffered.is_empty() {
+            return Ok(());
+        }
+        let chunk = std::mem::take(&mut self.buffered);
+        self.received_arguments = true;
+        events.push(ToolStreamEvent::ToolCallArgumentsDelta {
+            id: id.clone(),
+            chunk: chunk.clone(),
+        });
+        if !self.finished {
+            self.parser.push(&chunk)?;
+            if self.parser.is_complete() {
+                self.finished = true;
+                events.push(ToolStreamEvent::ToolCallFinished {
+                    id: id.clone(),
+                    arguments: self.parser.value().cloned().unwrap_or(Value::Null),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Finishes a call whose stream ended, e.g. with empty arguments.
+    fn close(mut self, events: &mut Vec<ToolStreamEvent>) -> Result<()> {
+        if self.finished {
+            return Ok(());
+        }
+        let Some(id) = self.id.clone() else {
+            return Err(Error::SchemaValidationError(
+                "Streamed tool call ended without an ID".into(),
+            ));
+        };
+        self.name.get_or_insert_with(String::new);
+        self.flush(events)?;
+        if self.finished {
+            return Ok(());
+        }
+        let arguments = if self.received_arguments {
+            self.parser.finish()?
+        } else {
+            Value::Object(Default::default())
+        };
+        events.push(ToolStreamEvent::ToolCallFinished { id, arguments });
+        Ok(())
+    }
+}
+
+/// Turns the tool call fragments of streamed chunks into
+/// [`ToolStreamEvent`]s.
+///
+/// Feed every chunk to [`push`](Self::push), then call
+/// [`finish`](Self::finish) when the stream ends. A call is reported as
+/// finished when its arguments form a complete JSON value, or otherwise when
+/// its choice reports a finish reason or the stream ends.
+#[derive(Debug, Default)]
+pub struct ToolCallTracker {
+    calls: BTreeMap<(u32, u32), PendingCall>,
+}
+
+impl ToolCallTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the events for `chunk`, in stream order.
+    ///
+    /// Returns `Error::SchemaValidationError` if a call's arguments are not
+    /// JSON.
+    pub fn push(&mut self, chunk: ChatCompletionChunk) -> Result<Vec<ToolStreamEvent>> {
+        let mut events = Vec::new();
+        for choice in chunk.choices {
+            let delta = choice.delta;
+            if let Some(text) = delta.content.map(|content| content.to_text()) {
+                if !text.is_empty() {
+                    events.push(ToolStreamEvent::Content {
+                        choice: choice.index,
+                        text,
+                    });
+                }
+            }
+            for call in delta.tool_calls.unwrap_or_default() {
+                self.calls
+                    .entry((choice.index, call.index))
+                    .or_default()
+                    .push(call, &mut events)?;
+            }
+            if choice.finish_reason.is_some() {
+                self.close_choice(choice.index, &mut events)?;
+            }
+        }
+        Ok(events)
+    }
+
+    /// Finishes the calls still open when the stream ended.
+    pub fn finish(self) -> Result<Vec<ToolStreamEvent>> {
+        let mut events = Vec::new();
+        for call in self.calls.into_values() {
+            call.close(&mut events)?;
+        }
+        Ok(events)
+    }
+
+    fn close_choice(&mut self, choice: u32, events: &mut Vec<ToolStreamEvent>) -> Result<()> {
+        let keys: Vec<_> = self
+            .calls
+            .range((choice, 0)..=(choice, u32::MAX))
+            .map(|(key, _)| *key)
+            .collect();
+        for key in keys {
+            if let Some(call) = self.calls.remove(&key) {
+                call.close(events)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn chunk(choice: Value) -> ChatCompletionChunk {
+        serde_json::from_value(json!({
+            "id": "gen-1",
+            "object": "chat.completion.chunk",
+            "created": 0,
+            "model": "openai/gpt-4o",
+            "choices": [choice]
+        }))
+        .unwrap()
+    }
+
+    fn call(
+        index: u32,
+        id: Option<&str>,
+        name: Option<&str>,
+        arguments: &str,
+    ) -> ChatCompletionChunk {
+        chunk(json!({
+            "index": 0,
+            "delta": {"tool_calls": [{
+                "index": index,
+                "id": id,
+                "type": "function",
+                "function": {"name": name, "arguments": arguments}
+            }]}
+        }))
+    }
+
+    #[test]
+    fn test_tracker_emits_started_deltas_and_finished() {
+        let mut tracker = ToolCallTracker::new();
+        let mut events = Vec::new();
+        for chunk in [
+            call(0, Some("call-1"), Some("get_weather"), ""),
+            call(0, None, None, "{\"city\":"),
+            call(1, Some("call-2"), Some("get_time"), ""),
+            call(0, None, None, " \"Paris\"}"),
+        ] {
+            events.extend(tracker.push(chunk).unwrap());
+        }
+        events.extend(
+            tracker
+                .push(chunk(
+                    json!({"index": 0, "delta": {}, "finish_reason": "tool_calls"}),
+                ))
+                .unwrap(),
+        );
+        assert!(tracker.finish().unwrap().is_empty());
+
+        assert_eq!(
+            events,
+            vec![
+                ToolStreamEvent::ToolCallStarted {
+                    id: "call-1".into(),
+                    name: "get_weather".into()
+                },
+                ToolStreamEvent::ToolCallArgumentsDelta {
+                    id: "call-1".into(),
+                    chunk: "{\"city\":".into()
+                },
+                ToolStreamEvent::ToolCallStarted {
+                    id: "call-2".into(),
+                    name: "get_time".into()
+                },
+                ToolStreamEvent::ToolCallArgumentsDelta {
+                    id: "call-1".into(),
+                    chunk: " \"Paris\"}".into()
+                },
+                ToolStreamEvent::ToolCallFinished {
+                    id: "call-1".into(),
+                    arguments: json!({"city": "Paris"})
+                },
+                ToolStreamEvent::ToolCallFinished {
+                    id: "call-2".into(),
+                    arguments: json!({})
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tracker_fails_on_truncated_arguments() {
+        let mut tracker = ToolCallTracker::new();
+        tracker
+            .push(call(0, Some("call-1"), Some("f"), "{\"a\": [1,"))
+            .unwrap();
+        assert!(matches!(
+            tracker.finish(),
+            Err(Error::SchemaValidationError(_))
+        ));
+    }
+}
@@ -0,0 +1,303 @@
+//! Joining generation records with activity records.
+//!
+//! The generation endpoint and the activity endpoint describe the same
+//! requests from different angles: generations carry native token counts and
+//! detailed timings, activity rows carry the user and referer and are what
+//! billing exports are built from. [`correlate`] joins the two by ID, falling
+//! back to the upstream provider ID, into one [`RequestTrace`] per request.
+
+use crate::types::analytics::ActivityData;
+use crate::types::generation::{GenerationData, GenerationResponse};
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+
+/// Everything known about one request, from its generation record, its
+/// activity record, or both.
+///
+/// Values come from the generation record when it has them and from the
+/// activity record otherwise; both records are kept for anything else.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct RequestTrace {
+    pub id: String,
+    pub upstream_id: Option<String>,
+    pub model: String,
+    pub provider: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
+    /// Cost in credits before discounts.
+    pub total_cost: Option<f64>,
+    pub cache_discount: Option<f64>,
+    pub prompt_tokens: Option<u64>,
+    pub completion_tokens: Option<u64>,
+    pub reasoning_tokens: Option<u64>,
+    /// Time to first token, in milliseconds.
+    pub latency_ms: Option<u64>,
+    pub generation_time_ms: Option<u64>,
+    pub finish_reason: Option<String>,
+    pub streamed: bool,
+    pub cancelled: bool,
+    pub generation: Option<GenerationData>,
+    pub activity: Option<ActivityData>,
+}
+
+impl RequestTrace {
+    /// Combines the records of one request. Either may be missing, but not
+    /// both.
+    fn join(generation: Option<GenerationData>, activity: Option<ActivityData>) -> Self {
+        let (g, a) = (generation.as_ref(), activity.as_ref());
+        let non_negative = |value: i64| u64::try_from(value).ok();
+        Self {
+            id: g
+                .map(|g| g.id.to_string())
+                .or_else(|| a.map(|a| a.id.to_string()))
+                .unwrap_or_default(),
+            upstream_id: g
+                .and_then(|g| g.upstream_id.clone())
+                .or_else(|| a.and_then(|a| a.upstream_id.clone())),
+            model: g
+                .map(|g| g.model.clone())
+                .or_else(|| a.map(|a| a.model.clone()))
+                .unwrap_or_default(),
+            provider: g
+                .and_then(|g| g.provider_name.clone())
+                .or_else(|| a.and_then(|a| a.provider.clone())),
+            created_at: a.map(|a| a.created_at).or_else(|| {
+                g.and_then(|g| DateTime::parse_from_rfc3339(&g.created_at).ok())
+                    .map(|time| time.with_timezone(&Utc))
+            }),
+            total_cost: g
+                .map(|g| g.total_cost)
+                .or_else(|| a.and_then(|a| a.total_cost)),
+            cache_discount: g
+                .and_then(|g| g.cache_discount)
+                .or_else(|| a.and_then(|a| a.cache_discount)),
+            prompt_tokens: g
+                .and_then(|g| g.tokens_prompt.and_then(non_negative))
+                .or_else(|| a.and_then(|a| a.tokens_prompt.map(u64::from))),
+            completion_tokens: g
+                .and_then(|g| g.tokens_completion.and_then(non_negative))
+                .or_else(|| a.and_then(|a| a.tokens_completion.map(u64::from))),
+            reasoning_tokens: g.and_then(|g| g.native_tokens_reasoning.and_then(non_negative)),
+            latency_ms: g
+                .and_then(|g| g.latency.and_then(non_negative))
+                .or_else(|| a.and_then(|a| a.latency)),
+            generation_time_ms: g
+                .and_then(|g| g.generation_time.and_then(non_negative))
+                .or_else(|| a.and_then(|a| a.generation_time)),
+            finish_reason: g
+                .and_then(|g| g.finish_reason.clone())
+                .or_else(|| a.and_then(|a| a.finish_reason.clone())),
+            streamed: g.map_or_else(|| a.is_some_and(|a| a.was_streamed()), |g| g.was_streamed()),
+            cancelled: g.map_or_else(
+                || a.is_some_and(|a| !a.is_successful()),
+                |g| g.was_cancelled(),
+            ),
+            generation,
+            activity,
+        }
+    }
+
+    /// Whether both a generation and an activity record were found.
+    pub fn is_matched(&self) -> bool {
+        self.generation.is_some() && self.activity.is_some()
+    }
+
+    /// Cost after the cache discount.
+    pub fn effective_cost(&self) -> Option<f64> {
+        self.total_cost
+            .map(|cost| cost - self.cache_discount.unwrap_or(0.0))
+    }
+
+    pub fn total_tokens(&self) -> Option<u64> {
+        match (self.prompt_tokens, self.completion_tokens) {
+            (None, None) => None,
+            (prompt, completion) => Some(prompt.unwrap_or(0) + completion.unwrap_or(0)),
+        }
+    }
+
+    /// Generation cost minus activity cost, when both records report one.
+    ///
+    /// Non-zero values point at records to look at during reconciliation.
+    pub fn cost_discrepancy(&self) -> Option<f64> {
+        let generation = self.generation.as_ref()?.total_cost;
+        let activity = self.activity.as_ref()?.total_cost?;
+        Some(generation - activity)
+    }
+}
+
+impl From<GenerationResponse> for GenerationData {
+    fn from(response: GenerationResponse) -> Self {
+        response.data
+    }
+}
+
+/// Joins generation records with activity records.
+///
+/// A generation matches the activity record with the same ID, or else one
+/// with the same `upstream_id`; each activity record is matched at most once.
+/// Traces come in generation order, followed by activity records no
+/// generation matched.
+///
+/// # Example
+///
+/// ```no_run
+/// use futures::TryStreamExt;
+/// use openrouter_api::types::analytics::ActivityRequest;
+/// use openrouter_api::types::trace::correlate;
+/// use openrouter_api::OpenRouterClient;
+///
+/// # async fn run(generation_ids: Vec<String>) -> Result<(), Box<dyn std::error::Error>> {
+/// let client = OpenRouterClient::from_env()?;
+/// let mut generations = Vec::new();
+/// for id in &generation_ids {
+///     generations.push(client.generation()?.get_generation(id).await?);
+/// }
+/// let activity: Vec<_> = client
+///     .analytics()?
+///     .get_activity_paged(ActivityRequest::new().with_start_date("2024-01-01"))
+///     .try_collect()
+///     .await?;
+///
+/// for trace in correlate(generations, activity) {
+///     if let Some(delta) = trace.cost_discrepancy().filter(|d| d.abs() > 1e-9) {
+///         println!("{}: billed cost differs by {delta}", trace.id);
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub fn correlate<G>(
+    generations: impl IntoIterator<Item = G>,
+    activity: impl IntoIterator<Item = ActivityData>,
+) -> Vec<RequestTrace>
+where
+    G: Into<GenerationData>,
+{
+    let activity: Vec<ActivityData> = activity.into_iter().collect();
+    // Reversed so the first of several records with the same key wins.
+    let mut by_id = HashMap::new();
+    let mut by_upstream_id = HashMap::new();
+    for (index, record) in activity.iter().enumerate().rev() {
+        by_id.insert(record.id.to_string(), index);
+        if let Some(upstream_id) = &record.upstream_id {
+            by_upstream_id.insert(upstream_id.clone(), index);
+        }
+    }
+    let mut activity: Vec<Option<ActivityData>> = activity.into_iter().map(Some).collect();
+
+    let mut traces: Vec<RequestTrace> = generations
+        .into_iter()
+        .map(|generation| {
+            let generation = generation.into();
+            let matched = [
+                by_id.get(generation.id.as_str()),
+                generation
+                    .upstream_id
+                    .as_ref()
+                    .and_then(|upstream_id| by_upstream_id.get(upstream_id)),
+            ]
+            .into_iter()
+            .flatten()
+            .find_map(|&index| activity[index].take());
+            RequestTrace::join(Some(generation), matched)
+        })
+        .collect();
+    traces.extend(
+        activity
+            .into_iter()
+            .flatten()
+            .map(|record| RequestTrace::join(None, Some(record))),
+    );
+    traces
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn generation(id: &str, upstream_id: Option<&str>, cost: f64) -> GenerationResponse {
+        serde_json::from_value(json!({"data": {
+            "id": id,
+            "upstream_id": upstream_id,
+            "total_cost": cost,
+            "created_at": "2024-03-01T12:00:00Z",
+            "model": "openai/gpt-4o",
+            "streamed": true,
+            "cancelled": false,
+            "provider_name": "OpenAI",
+            "latency": 420,
+            "tokens_prompt": 100,
+            "tokens_completion": 20,
+            "native_tokens_reasoning": 5,
+            "origin": "",
+            "usage": cost,
+            "is_byok": false
+        }}))
+        .unwrap()
+    }
+
+    fn activity(id: &str, upstream_id: Option<&str>, cost: f64) -> ActivityData {
+        serde_json::from_value(json!({
+            "id": id,
+            "created_at": "2024-03-01T12:00:01Z",
+            "model": "openai/gpt-4o",
+            "total_cost": cost,
+            "tokens_prompt": 100,
+            "tokens_completion": 20,
+            "streamed": true,
+            "cancelled": false,
+            "upstream_id": upstream_id,
+            "user_id": "user-1"
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_correlate_joins_by_id_then_upstream_id() {
+        let traces = correlate(
+            [
+                generation("gen-1", None, 0.002),
+                generation("gen-2", Some("chatcmpl-2"), 0.003),
+                generation("gen-3", None, 0.004),
+            ],
+            [
+                activity("act-2", Some("chatcmpl-2"), 0.003),
+                activity("gen-1", None, 0.0025),
+                activity("act-9", None, 0.001),
+            ],
+        );
+
+        let ids: Vec<_> = traces.iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(ids, ["gen-1", "gen-2", "gen-3", "act-9"]);
+        let matched: Vec<_> = traces.iter().map(RequestTrace::is_matched).collect();
+        assert_eq!(matched, [true, true, false, false]);
+
+        let first = &traces[0];
+        assert_eq!(first.provider.as_deref(), Some("OpenAI"));
+        assert_eq!(first.latency_ms, Some(420));
+        assert_eq!(first.total_tokens(), Some(120));
+        assert_eq!(first.reasoning_tokens, Some(5));
+        assert_eq!(
+            first.activity.as_ref().unwrap().user_id.as_deref(),
+            Some("user-1")
+        );
+        assert!((first.cost_discrepancy().unwrap() + 0.0005).abs() < 1e-12);
+        assert_eq!(traces[1].cost_discrepancy(), Some(0.0));
+    }
+
+    #[test]
+    fn test_unmatched_activity_fills_trace_fields() {
+        let traces = correlate(
+            Vec::<GenerationData>::new(),
+            [activity("act-1", None, 0.01)],
+        );
+        let trace = &traces[0];
+        assert_eq!(trace.id, "act-1");
+        assert_eq!(trace.total_cost, Some(0.01));
+        assert_eq!(trace.effective_cost(), Some(0.01));
+        assert!(trace.streamed);
+        assert!(trace.created_at.is_some());
+        assert_eq!(trace.cost_discrepancy(), None);
+    }
+}
@@ -0,0 +1,442 @@
+//! Markdown and HTML renderings of a [`Conversation`].
+//!
+//! Each message becomes a section headed by its role. Code fences in message
+//! text are kept as they are, tool calls are listed with their arguments and
+//! tool results are shown as code. Reasoning is left out. Attachments are
+//! linked only when their URL is `http` or `https`.
+
+use crate::types::chat::{ChatRole, ContentPart, Message, MessageContent};
+use crate::types::conversation::{Conversation, ConversationUsage};
+
+/// What to include in a rendered transcript.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TranscriptOptions {
+    /// Ends the transcript with the usage totals, if any were recorded.
+    pub include_usage: bool,
+}
+
+impl Default for TranscriptOptions {
+    fn default() -> Self {
+        Self {
+            include_usage: true,
+        }
+    }
+}
+
+impl TranscriptOptions {
+    /// Sets whether the usage footer is rendered.
+    pub fn with_usage(mut self, include_usage: bool) -> Self {
+        self.include_usage = include_usage;
+        self
+    }
+}
+
+impl Conversation {
+    /// Renders the conversation as Markdown with the default options.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use openrouter_api::types::chat::{ChatRole, Message};
+    /// use openrouter_api::types::conversation::Conversation;
+    ///
+    /// let conversation = Conversation::from_messages(vec![
+    ///     Message::text(ChatRole::User, "Hello"),
+    ///     Message::text(ChatRole::Assistant, "Hi there!"),
+    /// ]);
+    /// assert_eq!(
+    ///     conversation.to_markdown(),
+    ///     "## User\n\nHello\n\n## Assistant\n\nHi there!\n"
+    /// );
+    /// ```
+    pub fn to_markdown(&self) -> String {
+        self.to_markdown_with(TranscriptOptions::default())
+    }
+
+    /// Renders the conversation as Markdown.
+    pub fn to_markdown_with(&self, options: TranscriptOptions) -> String {
+        let mut sections: Vec<String> = self.messages().iter().map(markdown_message).collect();
+        if let Some(footer) = usage_footer(self.usage(), options) {
+            sections.push(format!("---\n\n{footer}"));
+        }
+        let mut markdown = sections.join("\n\n");
+        markdown.push('\n');
+        markdown
+    }
+
+    /// Renders the conversation as an HTML fragment with the default
+    /// options.
+    ///
+    /// All text is escaped and only `http`/`https` attachments are linked.
+    /// Sections carry the classes `message` and the
+    /// role, e.g. `message assistant`, for styling.
+    pub fn to_html(&self) -> String {
+        self.to_html_with(TranscriptOptions::default())
+    }
+
+    /// Renders the conversation as an HTML fragment.
+    pub fn to_html_with(&self, options: TranscriptOptions) -> String {
+        let mut html = String::from("<article class=\"transcript\">\n");
+        for message in self.messages() {
+            html.push_str(&html_message(message));
+        }
+        if let Some(footer) = usage_footer(self.usage(), options) {
+            html.push_str(&format!(
+                "<footer class=\"usage\">{}</footer>\n",
+                escape_html(&footer)
+            ));
+        }
+        html.push_str("</article>\n");
+        html
+    }
+}
+
+fn role_title(role: &ChatRole) -> &'static str {
+    match role {
+        ChatRole::User => "User",
+        ChatRole::Assistant => "Assistant",
+        ChatRole::System => "System",
+        ChatRole::Tool => "Tool",
+    }
+}
+
+/// The section heading: the role, the sender's name and the answered call.
+fn heading(message: &Message) -> String {
+    let mut heading = role_title(&message.role).to_string();
+    if let Some(name) = &message.name {
+        heading.push_str(&format!(" ({name})"));
+    }
+    if let Some(id) = &message.tool_call_id {
+        heading.push_str(&format!(" · result of {id}"));
+    }
+    heading
+}
+
+/// The non-text content parts, as their kind and URL.
+fn attachments(content: &MessageContent) -> Vec<(&'static str, &str)> {
+    let MessageContent::Parts(parts) = content else {
+        return Vec::new();
+    };
+    parts
+        .iter()
+        .filter_map(|part| match part {
+            ContentPart::Text(_) => None,
+            ContentPart::Image(image) => Some(("image", image.image_url.url.as_str())),
+            ContentPart::Audio(audio) => Some(("audio", audio.audio_url.url.as_str())),
+            ContentPart::File(file) => Some(("file", file.file_url.url.as_str())),
+        })
+        .collect()
+}
+
+/// The link for an attachment, if it is an `http` or `https` URL.
+///
+/// Other schemes, such as `javascript:`, are never linked. Parentheses are
+/// percent-encoded so the URL cannot end a Markdown link early.
+fn attachment_target(url: &str) -> Option<String> {
+    let url = url::Url::parse(url).ok()?;
+    matches!(url.scheme(), "http" | "https")
+        .then(|| url.as_str().replace('(', "%28").replace(')', "%29"))
+}
+
+/// Text shown instead of an attachment that is not linked. Inline data is
+/// replaced by its kind so transcripts stay readable.
+fn attachment_placeholder(kind: &str, url: &str) -> String {
+    if url.starts_with("data:") {
+        format!("[inline {kind}]")
+    } else {
+        format!("[{kind}]")
+    }
+}
+
+fn usage_footer(usage: &ConversationUsage, options: TranscriptOptions) -> Option<String> {
+    if !options.include_usage || *usage == ConversationUsage::default() {
+        return None;
+    }
+    let mut footer = format!(
+        "Usage: {} prompt + {} completion = {} tokens",
+        usage.prompt_tokens, usage.completion_tokens, usage.total_tokens
+    );
+    if usage.cost > 0.0 {
+        footer.push_str(&format!(", cost {}", usage.cost));
+    }
+    Some(footer)
+}
+
+/// A code fence longer than any backtick run in `text`.
+fn fence_for(text: &str) -> String {
+    let longest = text.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+    "`".repeat(longest.max(2) + 1)
+}
+
+fn markdown_code(text: &str, language: &str) -> String {
+    let fence = fence_for(text);
+    format!("{fence}{language}\n{text}\n{fence}")
+}
+
+fn markdown_message(message: &Message) -> String {
+    let mut blocks = vec![format!("## {}", heading(message))];
+    let text = message.content.to_text();
+    if !text.is_empty() {
+        if message.role == ChatRole::Tool {
+            blocks.push(markdown_code(&text, ""));
+        } else {
+            blocks.push(text);
+        }
+    }
+    for (kind, url) in attachments(&message.content) {
+        blocks.push(match attachment_target(url) {
+            Some(url) if kind == "image" => format!("![image]({url})"),
+            Some(url) => format!("[{kind}]({url})"),
+            None => format!("*{}*", attachment_placeholder(kind, url)),
+        });
+    }
+    for call in message.tool_calls.iter().flatten() {
+        blocks.push(format!(
+            "### Tool call: {} (`{}`)",
+            call.function_call.name, call.id
+        ));
+        blocks.push(markdown_code(&call.function_call.arguments, "json"));
+    }
+    blocks.join("\n\n")
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn html_code(text: &str, language: &str) -> String {
+    let class = if language.is_empty() {
+        String::new()
+    } else {
+        format!(" class=\"language-{}\"", escape_html(language))
+    };
+    format!("<pre><code{class}>{}</code></pre>\n", escape_html(text))
+}
+
+fn html_paragraphs(text: &str) -> String {
+    text.split("\n\n")
+        .map(str::trim)
+        .filter(|paragraph| !paragraph.is_empty())
+        .map(|paragraph| {
+            format!(
+                "<p>{}</p>\n",
+                escape_html(paragraph).replace('\n', "<br>\n")
+            )
+        })
+        .collect()
+}
+
+/// Renders message text, turning fenced code blocks into `<pre>` blocks and
+/// the rest into paragraphs. An unclosed fence runs to the end.
+fn html_text(text: &str) -> String {
+    let mut html = String::new();
+    let mut prose = String::new();
+    let mut fence: Option<(usize, String, Vec<&str>)> = None;
+    for line in text.lines() {
+        let trimmed = line.trim_start();
+        let ticks = trimmed.len() - trimmed.trim_start_matches('`').len();
+        match &mut fence {
+            Some((open, language, code)) => {
+                if ticks >= *open && trimmed[ticks..].trim().is_empty() {
+                    html.push_str(&html_code(&code.join("\n"), language));
+                    fence = None;
+                } else {
+                    code.push(line);
+                }
+            }
+            None if ticks >= 3 => {
+                html.push_str(&html_paragraphs(&prose));
+                prose.clear();
+                let language = trimmed[ticks..].trim().to_string();
+                fence = Some((ticks, language, Vec::new()));
+            }
+            None => {
+                prose.push_str(line);
+                prose.push('\n');
+            }
+        }
+    }
+    if let Some((_, language, code)) = fence {
+        html.push_str(&html_code(&code.join("\n"), &language));
+    }
+    html.push_str(&html_paragraphs(&prose));
+    html
+}
+
+fn html_message(message: &Message) -> String {
+    let role = message.role.to_string();
+    let mut html = format!(
+        "<section class=\"message {role}\">\n<h2>{}</h2>\n",
+        escape_html(&heading(message))
+    );
+    let text = message.content.to_text();
+    if !text.is_empty() {
+        if message.role == ChatRole::Tool {
+            html.push_str(&html_code(&text, ""));
+        } else {
+            html.push_str(&html_text(&text));
+        }
+    }
+    for (kind, url) in attachments(&message.content) {
+        html.push_str(&match attachment_target(url) {
+            Some(url) if kind == "image" => {
+                format!("<p><img src=\"{}\" alt=\"image\"></p>\n", escape_html(&url))
+            }
+            Some(url) => format!("<p><a href=\"{}\">{kind}</a></p>\n", escape_html(&url)),
+            None => format!(
+                "<p><em>{}</em></p>\n",
+                escape_html(&attachment_placeholder(kind, url))
+            ),
+        });
+    }
+    for call in message.tool_calls.iter().flatten() {
+        html.push_str(&format!(
+            "<h3>Tool call: {} (<code>{}</code>)</h3>\n",
+            escape_html(&call.function_call.name),
+            escape_html(call.id.as_str())
+        ));
+        html.push_str(&html_code(&call.function_call.arguments, "json"));
+    }
+    html.push_str("</section>\n");
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::tool::{FunctionCall, ToolCall, ToolType};
+    use crate::types::chat::{ChatCompletionResponse, ToolResult};
+    use crate::types::ids::ToolCallId;
+
+    fn conversation() -> Conversation {
+        let mut conversation = Conversation::from_messages(vec![
+            Message::text(ChatRole::User, "Show a loop & check Oslo's weather"),
+            Message {
+                role: ChatRole::Assistant,
+                tool_calls: Some(vec![ToolCall {
+                    id: ToolCallId::new("call-1"),
+                    kind: ToolType::Function,
+                    function_call: FunctionCall {
+                        name: "get_weather".into(),
+                        arguments: r#"{"city":"Oslo"}"#.into(),
+                    },
+                }]),
+                ..Message::text(ChatRole::Assistant, "")
+            },
+            Message {
+                tool_call_id: Some(ToolCallId::new("call-1")),
+                ..Message::text(ChatRole::Tool, "Sunny")
+            },
+        ]);
+        let response: ChatCompletionResponse = serde_json::from_value(serde_json::json!({
+            "id": "gen-1",
+            "object": "chat.completion",
+            "created": 0,
+            "model": "openai/gpt-4o",
+            "choices": [{"message": {
+                "role": "assistant",
+                "content": "It is sunny.\n\n```rust\nfor i in 0..3 {\n    println!(\"{i}\");\n}\n```"
+            }}],
+            "usage": {"prompt_tokens": 20, "completion_tokens": 5, "total_tokens": 25}
+        }))
+        .unwrap();
+        conversation.record_response(&response);
+        conversation
+    }
+
+    #[test]
+    fn test_to_markdown() {
+        assert_eq!(
+            conversation().to_markdown(),
+            r#"## User
+
+Show a loop & check Oslo's weather
+
+## Assistant
+
+### Tool call: get_weather (`call-1`)
+
+```json
+{"city":"Oslo"}
+```
+
+## Tool · result of call-1
+
+```
+Sunny
+```
+
+## Assistant
+
+It is sunny.
+
+```rust
+for i in 0..3 {
+    println!("{i}");
+}
+```
+
+---
+
+Usage: 20 prompt + 5 completion = 25 tokens
+"#
+        );
+
+        let markdown =
+            conversation().to_markdown_with(TranscriptOptions::default().with_usage(false));
+        assert!(!markdown.contains("Usage:"));
+        assert_eq!(fence_for("a ```` b"), "`````");
+    }
+
+    #[test]
+    fn test_to_html() {
+        let html = conversation().to_html();
+        assert!(html.starts_with("<article class=\"transcript\">\n"));
+        assert!(html.contains("<section class=\"message user\">\n<h2>User</h2>\n<p>Show a loop &amp; check Oslo&#39;s weather</p>\n"));
+        assert!(html.contains("<h3>Tool call: get_weather (<code>call-1</code>)</h3>\n<pre><code class=\"language-json\">{&quot;city&quot;:&quot;Oslo&quot;}</code></pre>\n"));
+        assert!(html.contains("<h2>Tool · result of call-1</h2>\n<pre><code>Sunny</code></pre>\n"));
+        assert!(html.contains("<p>It is sunny.</p>\n<pre><code class=\"language-rust\">for i in 0..3 {\n    println!(&quot;{i}&quot;);\n}</code></pre>\n"));
+        assert!(html.ends_with("<footer class=\"usage\">Usage: 20 prompt + 5 completion = 25 tokens</footer>\n</article>\n"));
+    }
+
+    #[test]
+    fn test_only_http_attachments_are_linked() {
+        let parts = [
+            "javascript:alert(document.cookie)",
+            "https://example.com/cat (1).png",
+            "data:image/png;base64,AAAA",
+        ]
+        .into_iter()
+        .flat_map(|url| match ToolResult::image_url(url).into_content() {
+            MessageContent::Parts(parts) => parts,
+            MessageContent::Text(_) => unreachable!(),
+        })
+        .collect();
+        let conversation = Conversation::from_messages(vec![Message {
+            content: MessageContent::Parts(parts),
+            ..Message::text(ChatRole::User, "")
+        }]);
+
+        let html = conversation.to_html();
+        assert!(!html.contains("javascript:"));
+        assert!(html.contains("<p><em>[image]</em></p>\n"));
+        assert!(html.contains("<img src=\"https://example.com/cat%20%281%29.png\" alt=\"image\">"));
+        assert!(html.contains("<p><em>[inline image]</em></p>\n"));
+
+        let markdown = conversation.to_markdown();
+        assert!(!markdown.contains("javascript:"));
+        assert!(markdown.contains("*[image]*"));
+        assert!(markdown.contains("![image](https://example.com/cat%20%281%29.png)"));
+    }
+}
@@ -0,0 +1,231 @@
+//! Prompt truncation for chat requests that exceed a model's context window.
+//!
+//! Truncation is opt-in: configure a [`TruncationConfig`] on the client with
+//! `OpenRouterClient::with_truncation` or per call with
+//! `RequestOptions::with_truncation`. When a request's estimated size exceeds
+//! the context window (less any `max_tokens` reserved for the answer), the
+//! policy shortens it and the response's `truncation` field reports what was
+//! removed.
+
+use crate::types::chat::{ChatCompletionRequest, ChatRole, Message, MessageContent};
+use crate::utils::validation::chat::estimate_request_tokens;
+
+/// Marker inserted where `TruncationPolicy::TruncateMiddle` removed text.
+pub const TRUNCATION_MARKER: &str = "\n[... truncated ...]\n";
+
+/// How a chat request that doesn't fit the context window is shortened.
+///
+/// System messages and the final message are always kept.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum TruncationPolicy {
+    /// Send the request unchanged and let the API reject it.
+    #[default]
+    None,
+    /// Drop the oldest messages (with the tool results that answer them).
+    DropOldestMessages,
+    /// Replace the oldest messages with a summary written by `model`,
+    /// typically a small, cheap model.
+    SummarizeOldest { model: String },
+    /// Cut text from the middle of the longest text messages.
+    TruncateMiddle,
+}
+
+/// Truncation settings for a client or a single call.
+///
+/// Applied by `ChatApi::chat_completion`, `ChatApi::chat_completion_stream`
+/// and their variants. Streams report it on the first chunk.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub struct TruncationConfig {
+    pub policy: TruncationPolicy,
+    /// Context window in tokens. When `None`, the model's `context_length`
+    /// is fetched from the models endpoint and cached.
+    pub context_window: Option<u32>,
+}
+
+impl TruncationConfig {
+    /// Creates a configuration that looks up the model's context window.
+    pub fn new(policy: TruncationPolicy) -> Self {
+        Self {
+            policy,
+            context_window: None,
+        }
+    }
+
+    /// Uses a fixed context window instead of looking it up.
+    pub fn with_context_window(mut self, tokens: u32) -> Self {
+        self.context_window = Some(tokens);
+        self
+    }
+}
+
+/// What truncation removed from a request.
+///
+/// Token counts are the client's estimates, not the provider's.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[non_exhaustive]
+pub struct TruncationReport {
+    pub estimated_tokens_before: u32,
+    pub estimated_tokens_after: u32,
+    /// Messages dropped or summarized, oldest first.
+    pub removed_messages: Vec<Message>,
+    /// The summary that replaced `removed_messages`, for
+    /// `TruncationPolicy::SummarizeOldest`.
+    pub summary: Option<String>,
+    /// Bytes of text cut by `TruncationPolicy::TruncateMiddle`.
+    pub truncated_bytes: usize,
+}
+
+/// Tokens available for the prompt once the answer's `max_tokens` is reserved.
+pub(crate) fn prompt_budget(request: &ChatCompletionRequest, context_window: u32) -> u32 {
+    context_window.saturating_sub(request.max_tokens.unwrap_or(0))
+}
+
+/// Removes the oldest non-system messages, except the last message, until
+/// the request fits `budget`.
+///
+/// Tool results directly following a removed message go with it, so no
+/// result is left without its call. Returns the index the first message was
+/// removed from and the removed messages.
+pub(crate) fn drop_oldest(
+    request: &mut ChatCompletionRequest,
+    budget: u32,
+) -> (usize, Vec<Message>) {
+    let mut removed = Vec::new();
+    let mut first_index = None;
+    while estimate_request_tokens(request) > budget {
+        let last = request.messages.len().saturating_sub(1);
+        let Some(index) = request.messages[..last]
+            .iter()
+            .position(|message| message.role != ChatRole::System)
+        else {
+            break;
+        };
+        first_index.get_or_insert(index);
+        removed.push(request.messages.remove(index));
+        while index + 1 < request.messages.len() && request.messages[index].role == ChatRole::Tool {
+            removed.push(request.messages.remove(index));
+        }
+    }
+    (first_index.unwrap_or(0), removed)
+}
+
+/// Cuts text from the middle of the longest text messages until the request
+/// fits `budget`, returning the number of bytes removed.
+pub(crate) fn truncate_middle(request: &mut ChatCompletionRequest, budget: u32) -> usize {
+    let mut removed = 0;
+    loop {
+        let over = estimate_request_tokens(request).saturating_sub(budget) as usize;
+        if over == 0 {
+            return removed;
+        }
+        let Some(text) = request
+            .messages
+            .iter_mut()
+            .filter_map(|message| match &mut message.content {
+                MessageContent::Text(text) => Some(text),
+                MessageContent::Parts(_) => None,
+            })
+            .max_by_key(|text| text.len())
+        else {
+            return removed;
+        };
+
+        // Four bytes per token, matching the estimator.
+        let cut = (over * 4 + TRUNCATION_MARKER.len()).min(text.len());
+        let mut start = (text.len() - cut) / 2;
+        while !text.is_char_boundary(start) {
+            start -= 1;
+        }
+        let mut end = start + cut;
+        while !text.is_char_boundary(end) {
+            end += 1;
+        }
+        if end - start <= TRUNCATION_MARKER.len() {
+            return removed;
+        }
+        text.replace_range(start..end, TRUNCATION_MARKER);
+        removed += end - start - TRUNCATION_MARKER.len();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(messages: Vec<Message>) -> ChatCompletionRequest {
+        ChatCompletionRequest {
+            model: "openai/gpt-4o".to_string(),
+            messages,
+            ..Default::default()
+        }
+    }
+
+    fn text(role: ChatRole, bytes: usize) -> Message {
+        Message::text(role, "x".repeat(bytes))
+    }
+
+    #[test]
+    fn test_prompt_budget_reserves_max_tokens() {
+        let mut req = request(vec![]);
+        assert_eq!(prompt_budget(&req, 1000), 1000);
+        req.max_tokens = Some(300);
+        assert_eq!(prompt_budget(&req, 1000), 700);
+        req.max_tokens = Some(3000);
+        assert_eq!(prompt_budget(&req, 1000), 0);
+    }
+
+    #[test]
+    fn test_drop_oldest_keeps_system_and_last_message() {
+        let mut req = request(vec![
+            text(ChatRole::System, 40),
+            text(ChatRole::User, 400),
+            text(ChatRole::Assistant, 400),
+            text(ChatRole::User, 40),
+        ]);
+        let (index, removed) = drop_oldest(&mut req, 100);
+
+        assert_eq!(index, 1);
+        assert_eq!(removed.len(), 2);
+        assert_eq!(removed[0].role, ChatRole::User);
+        assert_eq!(removed[1].role, ChatRole::Assistant);
+        assert_eq!(req.messages.len(), 2);
+        assert_eq!(req.messages[0].role, ChatRole::System);
+        assert!(estimate_request_tokens(&req) <= 100);
+    }
+
+    #[test]
+    fn test_drop_oldest_removes_tool_results_with_their_call() {
+        let mut req = request(vec![
+            text(ChatRole::Assistant, 400),
+            text(ChatRole::Tool, 40),
+            text(ChatRole::User, 40),
+        ]);
+        let (_, removed) = drop_oldest(&mut req, 100);
+
+        assert_eq!(removed.len(), 2);
+        assert_eq!(req.messages.len(), 1);
+        assert_eq!(req.messages[0].role, ChatRole::User);
+    }
+
+    #[test]
+    fn test_truncate_middle_keeps_both_ends() {
+        let body = format!("{}{}{}", "a".repeat(500), "b".repeat(2000), "c".repeat(500));
+        let mut req = request(vec![Message::text(ChatRole::User, body)]);
+        let removed = truncate_middle(&mut req, 400);
+
+        assert!(removed > 0);
+        assert!(estimate_request_tokens(&req) <= 400);
+        let text = req.messages[0].content.as_text().unwrap();
+        assert!(text.starts_with('a'));
+        assert!(text.ends_with('c'));
+        assert!(text.contains(TRUNCATION_MARKER));
+    }
+
+    #[test]
+    fn test_truncate_middle_respects_char_boundaries() {
+        let mut req = request(vec![Message::text(ChatRole::User, "é".repeat(1000))]);
+        truncate_middle(&mut req, 100);
+        assert!(estimate_request_tokens(&req) <= 100);
+    }
+}
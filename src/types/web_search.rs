@@ -1,19 +1,157 @@
 /// Web search request and response types
 use serde::{Deserialize, Serialize};
 
+/// Safe-search filtering level for web search results.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SafeSearch {
+    Off,
+    Moderate,
+    Strict,
+}
+
 /// Request type for performing a web search.
-#[derive(Debug, Serialize, PartialEq, Eq)]
+///
+/// # Example
+///
+/// ```
+/// use openrouter_api::types::web_search::{SafeSearch, WebSearchRequest};
+///
+/// let request = WebSearchRequest::new("rust async runtimes")
+///     .with_num_results(5)
+///     .with_included_domains(["docs.rs", "github.com"])
+///     .with_date_range("2024-01-01", "2024-12-31")
+///     .with_country("US")
+///     .with_safe_search(SafeSearch::Strict);
+/// ```
+#[derive(Debug, Clone, Default, Serialize, PartialEq, Eq)]
 pub struct WebSearchRequest {
     /// The search query string.
     pub query: String,
     /// Optionally specify the number of results to return.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub num_results: Option<u32>,
-    // Additional parameters can be added here in the future.
+    /// Only return results from these domains, e.g. `"docs.rs"`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub include_domains: Option<Vec<String>>,
+    /// Never return results from these domains.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exclude_domains: Option<Vec<String>>,
+    /// Earliest publication date, in YYYY-MM-DD format. Checked to be a real
+    /// calendar date no later than `end_date` before the request is sent.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_date: Option<String>,
+    /// Latest publication date, in YYYY-MM-DD format.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_date: Option<String>,
+    /// ISO 3166-1 alpha-2 country code to localize results for, e.g. `"US"`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub country: Option<String>,
+    /// Language tag for results, e.g. `"en"` or `"pt-BR"`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    /// Safe-search filtering level; the provider's default when unset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub safe_search: Option<SafeSearch>,
+    /// Resumes a search from a previous response's `next_cursor`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+}
+
+impl WebSearchRequest {
+    /// Creates a request for `query` with the provider's default options.
+    pub fn new(query: impl Into<String>) -> Self {
+        Self {
+            query: query.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Asks for `num_results` results (1 to 100).
+    pub fn with_num_results(mut self, num_results: u32) -> Self {
+        self.num_results = Some(num_results);
+        self
+    }
+
+    /// Restricts results to `domains`.
+    pub fn with_included_domains<I, S>(mut self, domains: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.include_domains = Some(domains.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Excludes results from `domains`.
+    pub fn with_excluded_domains<I, S>(mut self, domains: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.exclude_domains = Some(domains.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Only returns results published from `start` to `end` (YYYY-MM-DD,
+    /// inclusive).
+    pub fn with_date_range(mut self, start: impl Into<String>, end: impl Into<String>) -> Self {
+        self.start_date = Some(start.into());
+        self.end_date = Some(end.into());
+        self
+    }
+
+    /// Only returns results published on or after `date` (YYYY-MM-DD).
+    pub fn with_start_date(mut self, date: impl Into<String>) -> Self {
+        self.start_date = Some(date.into());
+        self
+    }
+
+    /// Only returns results published on or before `date` (YYYY-MM-DD).
+    pub fn with_end_date(mut self, date: impl Into<String>) -> Self {
+        self.end_date = Some(date.into());
+        self
+    }
+
+    /// Localizes results for a two-letter ISO 3166-1 `country` code.
+    pub fn with_country(mut self, country: impl Into<String>) -> Self {
+        self.country = Some(country.into());
+        self
+    }
+
+    /// Prefers results in `language`, a tag such as `"en"` or `"pt-BR"`.
+    pub fn with_language(mut self, language: impl Into<String>) -> Self {
+        self.language = Some(language.into());
+        self
+    }
+
+    /// Sets the safe-search filtering level.
+    pub fn with_safe_search(mut self, safe_search: SafeSearch) -> Self {
+        self.safe_search = Some(safe_search);
+        self
+    }
+
+    /// Requests the page following the response that returned `cursor`.
+    pub fn with_cursor(mut self, cursor: impl Into<String>) -> Self {
+        self.cursor = Some(cursor.into());
+        self
+    }
+}
+
+impl From<&str> for WebSearchRequest {
+    fn from(query: &str) -> Self {
+        Self::new(query)
+    }
+}
+
+impl From<String> for WebSearchRequest {
+    fn from(query: String) -> Self {
+        Self::new(query)
+    }
 }
 
 /// A single search result.
-#[derive(Debug, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
 pub struct WebSearchResult {
     /// The title of the search result.
     pub title: String,
@@ -33,4 +171,7 @@ pub struct WebSearchResponse {
     pub results: Vec<WebSearchResult>,
     /// The total number of results available.
     pub total_results: u32,
+    /// Cursor for the next page of results; `None` on the last page.
+    #[serde(default)]
+    pub next_cursor: Option<String>,
 }
@@ -3,6 +3,12 @@
 use crate::client::SecureApiKey;
 use crate::error::{Error, Result};
 use std::env;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Environment variable naming a file that holds the API key.
+pub const API_KEY_FILE_ENV: &str = "OPENROUTER_API_KEY_FILE";
 
 /// Attempts to load an API key from environment variables.
 /// Checks for OPENROUTER_API_KEY and OR_API_KEY.
@@ -25,6 +31,152 @@ pub fn load_api_key_from_env() -> Result<String> {
     ))
 }
 
+/// Reads an API key from a file, trimming surrounding whitespace.
+///
+/// On Unix the file must not be readable or writable by group or others
+/// (e.g. mode `0600`); otherwise a `ConfigError` is returned so that a
+/// world-readable secret is noticed rather than silently used.
+pub fn load_api_key_from_file(path: impl AsRef<Path>) -> Result<String> {
+    let path = path.as_ref();
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        let mode = std::fs::metadata(path)?.permissions().mode();
+        if mode & 0o077 != 0 {
+            return Err(Error::ConfigError(format!(
+                "API key file {} is accessible by group or others (mode {:o}); \
+                 restrict it with `chmod 600`",
+                path.display(),
+                mode & 0o777
+            )));
+        }
+    }
+
+    let key = std::fs::read_to_string(path)?.trim().to_string();
+    if key.is_empty() {
+        return Err(Error::MissingCredential(format!(
+            "API key file {} is empty",
+            path.display()
+        )));
+    }
+    Ok(key)
+}
+
+/// Runs a shell command (e.g. a secret-manager CLI) and uses its trimmed
+/// standard output as the API key.
+///
+/// The command runs through `sh -c` (`cmd /C` on Windows). A non-zero exit
+/// status or empty output is an error; the command's stderr is not included
+/// in the error since it may echo secrets.
+pub fn load_api_key_from_command(cmd: &str) -> Result<String> {
+    #[cfg(windows)]
+    let output = Command::new("cmd").args(["/C", cmd]).output()?;
+    #[cfg(not(windows))]
+    let output = Command::new("sh").args(["-c", cmd]).output()?;
+
+    if !output.status.success() {
+        return Err(Error::ConfigError(format!(
+            "API key command failed with {}",
+            output.status
+        )));
+    }
+    let key = String::from_utf8(output.stdout)
+        .map_err(|_| Error::ConfigError("API key command printed invalid UTF-8".into()))?
+        .trim()
+        .to_string();
+    if key.is_empty() {
+        return Err(Error::MissingCredential(
+            "API key command printed nothing".into(),
+        ));
+    }
+    Ok(key)
+}
+
+/// A place an API key can be loaded from.
+///
+/// `from_env`-style constructors try [`ApiKeySource::default_chain`] in
+/// order and use the first source that is configured:
+///
+/// 1. the `OPENROUTER_API_KEY` environment variable,
+/// 2. the `OR_API_KEY` environment variable,
+/// 3. the file named by `OPENROUTER_API_KEY_FILE`.
+///
+/// [`ApiKeySource::Command`] is never part of the default chain, so no
+/// environment variable can make the library spawn a process; pass it to
+/// `OpenRouterClient::from_key_sources` explicitly to use one.
+///
+/// An unset or blank environment variable is skipped, but a configured file
+/// or command that fails is an error rather than a fallthrough.
+#[derive(Clone, PartialEq, Eq)]
+pub enum ApiKeySource {
+    /// An environment variable holding the key.
+    Env(String),
+    /// A file holding the key; see [`load_api_key_from_file`].
+    File(PathBuf),
+    /// A command printing the key; see [`load_api_key_from_command`].
+    Command(String),
+}
+
+impl ApiKeySource {
+    /// The sources used by `from_env`-style constructors, in precedence order.
+    pub fn default_chain() -> Vec<Self> {
+        let mut sources = vec![
+            Self::Env("OPENROUTER_API_KEY".into()),
+            Self::Env("OR_API_KEY".into()),
+        ];
+        if let Some(path) = env::var_os(API_KEY_FILE_ENV).filter(|path| !path.is_empty()) {
+            sources.push(Self::File(path.into()));
+        }
+        sources
+    }
+
+    /// Loads the key, returning `Ok(None)` for an unset environment variable.
+    pub fn load(&self) -> Result<Option<String>> {
+        match self {
+            Self::Env(var) => Ok(env::var(var).ok().filter(|key| !key.trim().is_empty())),
+            Self::File(path) => load_api_key_from_file(path).map(Some),
+            Self::Command(cmd) => load_api_key_from_command(cmd).map(Some),
+        }
+    }
+
+    /// Loads the key from the first configured source.
+    pub fn load_first(sources: &[ApiKeySource]) -> Result<String> {
+        for source in sources {
+            if let Some(key) = source.load()? {
+                return Ok(key);
+            }
+        }
+        let tried: Vec<String> = sources.iter().map(ToString::to_string).collect();
+        Err(Error::MissingCredential(format!(
+            "API key not found; tried {}",
+            tried.join(", ")
+        )))
+    }
+}
+
+impl fmt::Display for ApiKeySource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Env(var) => write!(f, "environment variable {var}"),
+            Self::File(path) => write!(f, "file {}", path.display()),
+            // The command line may embed secrets, so it is not printed.
+            Self::Command(_) => f.write_str("key command"),
+        }
+    }
+}
+
+impl fmt::Debug for ApiKeySource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Env(var) => f.debug_tuple("Env").field(var).finish(),
+            Self::File(path) => f.debug_tuple("File").field(path).finish(),
+            Self::Command(_) => f.debug_tuple("Command").field(&"[REDACTED]").finish(),
+        }
+    }
+}
+
 /// Validates an API key format.
 /// Basic validation to check if the key is non-empty and has a reasonable length.
 pub fn validate_api_key(key: &str) -> Result<()> {
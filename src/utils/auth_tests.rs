@@ -2,8 +2,10 @@
 
 #[cfg(test)]
 mod tests {
+    use crate::error::Error;
     use crate::utils::auth::{
-        load_api_key_from_env, load_secure_api_key_from_env, validate_api_key,
+        load_api_key_from_command, load_api_key_from_env, load_api_key_from_file,
+        load_secure_api_key_from_env, validate_api_key, ApiKeySource, API_KEY_FILE_ENV,
     };
     use serial_test::serial;
     use std::env;
@@ -300,4 +302,121 @@ mod tests {
         env::remove_var("OPENROUTER_API_KEY");
         env::remove_var("OR_API_KEY");
     }
+
+    fn temp_key_file(contents: &str, mode: u32) -> std::path::PathBuf {
+        let path = env::temp_dir().join(format!("openrouter-key-{}", uuid::Uuid::new_v4()));
+        std::fs::write(&path, contents).unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(mode)).unwrap();
+        }
+        #[cfg(not(unix))]
+        let _ = mode;
+        path
+    }
+
+    #[test]
+    fn test_load_api_key_from_file() {
+        let path = temp_key_file("  sk-test1234567890abcdef1234567890abcdef\n", 0o600);
+        assert_eq!(
+            load_api_key_from_file(&path).unwrap(),
+            "sk-test1234567890abcdef1234567890abcdef"
+        );
+        std::fs::remove_file(&path).unwrap();
+
+        let empty = temp_key_file("\n", 0o600);
+        assert!(matches!(
+            load_api_key_from_file(&empty),
+            Err(Error::MissingCredential(_))
+        ));
+        std::fs::remove_file(&empty).unwrap();
+
+        assert!(matches!(
+            load_api_key_from_file(env::temp_dir().join("openrouter-key-does-not-exist")),
+            Err(Error::IoError(_))
+        ));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_load_api_key_from_file_rejects_loose_permissions() {
+        let path = temp_key_file("sk-test1234567890abcdef1234567890abcdef", 0o644);
+        let result = load_api_key_from_file(&path);
+        std::fs::remove_file(&path).unwrap();
+        match result {
+            Err(Error::ConfigError(message)) => assert!(message.contains("644"), "{message}"),
+            other => panic!("expected ConfigError, got {other:?}"),
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_load_api_key_from_command() {
+        assert_eq!(
+            load_api_key_from_command("echo sk-test1234567890abcdef1234567890abcdef").unwrap(),
+            "sk-test1234567890abcdef1234567890abcdef"
+        );
+        assert!(matches!(
+            load_api_key_from_command("echo oops >&2; exit 3"),
+            Err(Error::ConfigError(message)) if !message.contains("oops")
+        ));
+        assert!(matches!(
+            load_api_key_from_command("true"),
+            Err(Error::MissingCredential(_))
+        ));
+    }
+
+    #[test]
+    #[serial]
+    fn test_api_key_source_precedence() {
+        env::remove_var("OPENROUTER_API_KEY");
+        env::remove_var("OR_API_KEY");
+        env::remove_var(API_KEY_FILE_ENV);
+
+        let path = temp_key_file("sk-fromfile1234567890abcdef1234567890", 0o600);
+        env::set_var(API_KEY_FILE_ENV, &path);
+        // A command in the environment is never run implicitly.
+        env::set_var(
+            "OPENROUTER_API_KEY_COMMAND",
+            "echo sk-fromcommand1234567890abcdef123456",
+        );
+
+        let chain = ApiKeySource::default_chain();
+        assert_eq!(
+            chain,
+            vec![
+                ApiKeySource::Env("OPENROUTER_API_KEY".into()),
+                ApiKeySource::Env("OR_API_KEY".into()),
+                ApiKeySource::File(path.clone()),
+            ]
+        );
+        assert_eq!(
+            ApiKeySource::load_first(&chain).unwrap(),
+            "sk-fromfile1234567890abcdef1234567890"
+        );
+
+        env::set_var("OR_API_KEY", "sk-fromenv1234567890abcdef1234567890ab");
+        assert_eq!(
+            ApiKeySource::load_first(&ApiKeySource::default_chain()).unwrap(),
+            "sk-fromenv1234567890abcdef1234567890ab"
+        );
+
+        env::remove_var("OR_API_KEY");
+        env::remove_var(API_KEY_FILE_ENV);
+        env::remove_var("OPENROUTER_API_KEY_COMMAND");
+        std::fs::remove_file(&path).unwrap();
+
+        let result = ApiKeySource::load_first(&ApiKeySource::default_chain());
+        match result {
+            Err(Error::MissingCredential(message)) => {
+                assert!(message.contains("OR_API_KEY"), "{message}")
+            }
+            other => panic!("expected MissingCredential, got {other:?}"),
+        }
+        assert_eq!(
+            format!("{:?}", ApiKeySource::Command("vault read secret".into())),
+            "Command(\"[REDACTED]\")"
+        );
+    }
 }
@@ -0,0 +1,661 @@
+//! Client-side spending caps for unattended workloads.
+//!
+//! A [`BudgetGuard`] keeps a rolling ledger of request costs. Before a request
+//! is sent, its estimated cost is reserved against every configured window;
+//! once the response arrives the reservation is settled with the actual cost
+//! reported in `usage.cost` (or left at the estimate when the provider does
+//! not report one). Requests that fail before producing output release their
+//! reservation; a stream dropped or failing after its first chunk keeps the
+//! estimate, since the tokens generated so far are billed. Costs that only become known later, e.g. from
+//! the generation endpoint, can be added with [`BudgetGuard::record_cost`].
+
+use crate::error::{Error, Result};
+use crate::types::chat::ChatCompletionRequest;
+use crate::types::completion::CompletionRequest;
+use crate::utils::validation::chat::estimate_request_tokens;
+use crate::utils::validation::completion::estimate_prompt_tokens;
+use std::collections::VecDeque;
+use std::fmt;
+use std::ops::Deref;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Rolling time window a spending cap applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BudgetWindow {
+    /// The last 60 minutes.
+    Hour,
+    /// The last 24 hours.
+    Day,
+    /// The last 30 days.
+    Month,
+}
+
+impl BudgetWindow {
+    /// Returns the length of the window.
+    pub fn duration(&self) -> Duration {
+        match self {
+            BudgetWindow::Hour => Duration::from_secs(60 * 60),
+            BudgetWindow::Day => Duration::from_secs(24 * 60 * 60),
+            BudgetWindow::Month => Duration::from_secs(30 * 24 * 60 * 60),
+        }
+    }
+
+    const ALL: [BudgetWindow; 3] = [BudgetWindow::Hour, BudgetWindow::Day, BudgetWindow::Month];
+}
+
+impl fmt::Display for BudgetWindow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BudgetWindow::Hour => write!(f, "hourly"),
+            BudgetWindow::Day => write!(f, "daily"),
+            BudgetWindow::Month => write!(f, "monthly"),
+        }
+    }
+}
+
+/// Size of a request, used to scale its reservation with
+/// [`BudgetGuard::with_token_prices`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RequestSize {
+    /// Estimated prompt tokens.
+    pub prompt_tokens: u32,
+    /// Most tokens the response may generate, if the request caps it.
+    pub max_tokens: Option<u32>,
+}
+
+impl RequestSize {
+    /// Estimates the size of a chat request from its messages and tools.
+    pub fn of_chat(request: &ChatCompletionRequest) -> Self {
+        Self {
+            prompt_tokens: estimate_request_tokens(request),
+            max_tokens: request.max_tokens,
+        }
+    }
+
+    /// Estimates the size of a text completion request from its prompt.
+    pub fn of_completion(request: &CompletionRequest) -> Self {
+        Self {
+            prompt_tokens: estimate_prompt_tokens(&request.prompt),
+            max_tokens: request
+                .extra_params
+                .get("max_tokens")
+                .and_then(|v| v.as_u64())
+                .map(|v| u32::try_from(v).unwrap_or(u32::MAX)),
+        }
+    }
+}
+
+/// Handle for a cost reserved before a request is sent.
+///
+/// Pass it back to [`BudgetGuard::settle`] once the actual cost is known, or
+/// to [`BudgetGuard::cancel`] if the request failed.
+#[derive(Debug)]
+#[must_use = "reservations should be settled or cancelled"]
+pub struct BudgetReservation {
+    id: u64,
+}
+
+#[derive(Debug)]
+struct LedgerEntry {
+    id: u64,
+    at: Instant,
+    cost: f64,
+}
+
+#[derive(Debug, Default)]
+struct Ledger {
+    next_id: u64,
+    entries: VecDeque<LedgerEntry>,
+}
+
+impl Ledger {
+    fn prune(&mut self, now: Instant) {
+        let horizon = BudgetWindow::Month.duration();
+        while self
+            .entries
+            .front()
+            .is_some_and(|e| now.duration_since(e.at) > horizon)
+        {
+            self.entries.pop_front();
+        }
+    }
+
+    fn spent(&self, window: BudgetWindow, now: Instant) -> f64 {
+        let span = window.duration();
+        self.entries
+            .iter()
+            .filter(|e| now.duration_since(e.at) <= span)
+            .map(|e| e.cost)
+            .sum()
+    }
+
+    fn push(&mut self, at: Instant, cost: f64) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.entries.push_back(LedgerEntry { id, at, cost });
+        id
+    }
+}
+
+/// Hard spending caps enforced before requests are sent.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use openrouter_api::utils::budget::BudgetGuard;
+/// use openrouter_api::OpenRouterClient;
+///
+/// let guard = BudgetGuard::new()
+///     .with_hourly_limit(1.0)
+///     .with_daily_limit(10.0)
+///     .with_request_estimate(0.01)
+///     // $3 / $15 per million prompt / completion tokens
+///     .with_token_prices(0.000_003, 0.000_015);
+///
+/// let client = OpenRouterClient::new()
+///     .skip_url_configuration()
+///     .with_budget_guard(guard)
+///     .with_api_key("sk-your-api-key")?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Default)]
+pub struct BudgetGuard {
+    hourly_limit: Option<f64>,
+    daily_limit: Option<f64>,
+    monthly_limit: Option<f64>,
+    request_estimate: f64,
+    prompt_token_usd: f64,
+    completion_token_usd: f64,
+    ledger: Mutex<Ledger>,
+}
+
+impl BudgetGuard {
+    /// Creates a guard with no caps configured.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Caps spend over the last hour, in USD.
+    pub fn with_hourly_limit(mut self, usd: f64) -> Self {
+        self.hourly_limit = Some(usd);
+        self
+    }
+
+    /// Caps spend over the last 24 hours, in USD.
+    pub fn with_daily_limit(mut self, usd: f64) -> Self {
+        self.daily_limit = Some(usd);
+        self
+    }
+
+    /// Caps spend over the last 30 days, in USD.
+    pub fn with_monthly_limit(mut self, usd: f64) -> Self {
+        self.monthly_limit = Some(usd);
+        self
+    }
+
+    /// Sets the cost reserved for each outgoing request until its actual cost
+    /// is known. Defaults to zero; see also [`with_token_prices`](Self::with_token_prices).
+    pub fn with_request_estimate(mut self, usd: f64) -> Self {
+        self.request_estimate = usd.max(0.0);
+        self
+    }
+
+    /// Adds a size-dependent part to each reservation: the estimated prompt
+    /// tokens at `prompt_usd` each, plus the request's `max_tokens` at
+    /// `completion_usd` each. Requests without `max_tokens` only reserve
+    /// their prompt.
+    pub fn with_token_prices(mut self, prompt_usd: f64, completion_usd: f64) -> Self {
+        self.prompt_token_usd = prompt_usd.max(0.0);
+        self.completion_token_usd = completion_usd.max(0.0);
+        self
+    }
+
+    /// Returns the configured cap for a window, if any.
+    pub fn limit(&self, window: BudgetWindow) -> Option<f64> {
+        match window {
+            BudgetWindow::Hour => self.hourly_limit,
+            BudgetWindow::Day => self.daily_limit,
+            BudgetWindow::Month => self.monthly_limit,
+        }
+    }
+
+    /// Returns the flat per-request estimate.
+    pub fn request_estimate(&self) -> f64 {
+        self.request_estimate
+    }
+
+    /// Returns the cost reserved for a request of the given size: the flat
+    /// estimate plus its tokens at the configured prices.
+    pub fn estimate(&self, size: RequestSize) -> f64 {
+        self.request_estimate
+            + f64::from(size.prompt_tokens) * self.prompt_token_usd
+            + f64::from(size.max_tokens.unwrap_or(0)) * self.completion_token_usd
+    }
+
+    /// Returns the amount spent (including outstanding reservations) in a window.
+    pub fn spent(&self, window: BudgetWindow) -> f64 {
+        self.lock().spent(window, Instant::now())
+    }
+
+    /// Returns the amount left in a window, or `None` if it is uncapped.
+    pub fn remaining(&self, window: BudgetWindow) -> Option<f64> {
+        self.limit(window)
+            .map(|limit| (limit - self.spent(window)).max(0.0))
+    }
+
+    /// Reserves `estimated_usd` against every configured cap.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::BudgetExceeded` if any window would go over its cap.
+    /// Nothing is reserved in that case.
+    pub fn reserve(&self, estimated_usd: f64) -> Result<BudgetReservation> {
+        let estimated_usd = estimated_usd.max(0.0);
+        let now = Instant::now();
+        let mut ledger = self.lock();
+        ledger.prune(now);
+
+        for window in BudgetWindow::ALL {
+            if let Some(limit) = self.limit(window) {
+                let spent = ledger.spent(window, now);
+                // A zero-cost reservation is still rejected once the cap is hit.
+                if spent >= limit || spent + estimated_usd > limit {
+                    return Err(Error::BudgetExceeded {
+                        window,
+                        limit,
+                        spent,
+                    });
+                }
+            }
+        }
+
+        Ok(BudgetReservation {
+            id: ledger.push(now, estimated_usd),
+        })
+    }
+
+    /// Replaces a reservation's estimate with the actual cost, if known.
+    /// Costs that are not finite are ignored.
+    pub fn settle(&self, reservation: BudgetReservation, actual_usd: Option<f64>) {
+        let Some(actual) = actual_usd.filter(|usd| usd.is_finite()) else {
+            return;
+        };
+        let mut ledger = self.lock();
+        if let Some(entry) = ledger.entries.iter_mut().find(|e| e.id == reservation.id) {
+            entry.cost = actual.max(0.0);
+        }
+    }
+
+    /// Releases a reservation for a request that did not complete.
+    pub fn cancel(&self, reservation: BudgetReservation) {
+        self.lock().entries.retain(|e| e.id != reservation.id);
+    }
+
+    /// Records a cost that was not reserved up front, such as a correction
+    /// from the generation endpoint.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::ConfigError` if `usd` is negative or not finite.
+    pub fn record_cost(&self, usd: f64) -> Result<()> {
+        if !usd.is_finite() || usd < 0.0 {
+            return Err(Error::ConfigError(format!(
+                "Recorded cost must be a finite, non-negative amount, got {usd}"
+            )));
+        }
+        let now = Instant::now();
+        let mut ledger = self.lock();
+        ledger.prune(now);
+        ledger.push(now, usd);
+        Ok(())
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Ledger> {
+        // The ledger holds plain numbers, so a poisoned lock is still usable.
+        self.ledger.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// A reservation that is resolved when dropped unless it was settled.
+///
+/// A request that fails or is abandoned before anything was generated has
+/// its reservation released. Once output has started arriving the tokens are
+/// billed regardless, so the estimate is kept instead.
+pub(crate) struct PendingReservation<G: Deref<Target = BudgetGuard>> {
+    guard: G,
+    reservation: Option<BudgetReservation>,
+    billable: bool,
+}
+
+impl<G: Deref<Target = BudgetGuard>> PendingReservation<G> {
+    /// Reserves the estimate for a request of `size`.
+    pub(crate) fn reserve(guard: G, size: RequestSize) -> Result<Self> {
+        let reservation = guard.reserve(guard.estimate(size))?;
+        Ok(Self {
+            guard,
+            reservation: Some(reservation),
+            billable: false,
+        })
+    }
+
+    /// Records that output has been generated, so dropping the reservation
+    /// keeps its estimate rather than releasing it.
+    pub(crate) fn mark_billable(&mut self) {
+        self.billable = true;
+    }
+
+    /// Settles the reservation; see [`BudgetGuard::settle`].
+    pub(crate) fn settle(mut self, actual_usd: Option<f64>) {
+        if let Some(reservation) = self.reservation.take() {
+            self.guard.settle(reservation, actual_usd);
+        }
+    }
+}
+
+impl<G: Deref<Target = BudgetGuard>> Drop for PendingReservation<G> {
+    fn drop(&mut self) {
+        if let Some(reservation) = self.reservation.take() {
+            if self.billable {
+                self.guard.settle(reservation, None);
+            } else {
+                self.guard.cancel(reservation);
+            }
+        }
+    }
+}
+
+/// Runs `request` under the guard (if any), reserving the estimate for a
+/// request of `size` up front and settling it with the cost extracted from
+/// the result. The reservation is released if the request fails or the
+/// future is dropped.
+pub(crate) async fn guarded<T, F>(
+    guard: Option<&BudgetGuard>,
+    size: RequestSize,
+    request: F,
+    actual_cost: impl FnOnce(&T) -> Option<f64>,
+) -> Result<T>
+where
+    F: std::future::Future<Output = Result<T>>,
+{
+    let Some(guard) = guard else {
+        return request.await;
+    };
+
+    let pending = PendingReservation::reserve(guard, size)?;
+    let value = request.await?;
+    pending.settle(actual_cost(&value));
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reserve_rejects_when_cap_would_be_exceeded() {
+        let guard = BudgetGuard::new().with_hourly_limit(1.0);
+
+        let first = guard.reserve(0.6).unwrap();
+        assert!((guard.spent(BudgetWindow::Hour) - 0.6).abs() < f64::EPSILON);
+
+        let err = guard.reserve(0.5).unwrap_err();
+        assert!(matches!(
+            err,
+            Error::BudgetExceeded {
+                window: BudgetWindow::Hour,
+                ..
+            }
+        ));
+
+        guard.cancel(first);
+        assert_eq!(guard.spent(BudgetWindow::Hour), 0.0);
+        assert!(guard.reserve(0.5).is_ok());
+    }
+
+    #[test]
+    fn test_settle_replaces_estimate_with_actual_cost() {
+        let guard = BudgetGuard::new().with_daily_limit(1.0);
+
+        let reservation = guard.reserve(0.5).unwrap();
+        guard.settle(reservation, Some(0.1));
+        assert!((guard.spent(BudgetWindow::Day) - 0.1).abs() < 1e-9);
+
+        let reservation = guard.reserve(0.2).unwrap();
+        guard.settle(reservation, None);
+        assert!((guard.spent(BudgetWindow::Day) - 0.3).abs() < 1e-9);
+        assert!((guard.remaining(BudgetWindow::Day).unwrap() - 0.7).abs() < 1e-9);
+        assert_eq!(guard.remaining(BudgetWindow::Hour), None);
+    }
+
+    #[test]
+    fn test_recorded_costs_count_towards_every_window() {
+        let guard = BudgetGuard::new()
+            .with_hourly_limit(5.0)
+            .with_monthly_limit(1.0);
+
+        guard.record_cost(1.0).unwrap();
+        let err = guard.reserve(0.0).unwrap_err();
+        assert!(matches!(
+            err,
+            Error::BudgetExceeded {
+                window: BudgetWindow::Month,
+                ..
+            }
+        ));
+        assert_eq!(
+            err.to_string(),
+            "Budget exceeded: monthly spend $1.0000 has reached the $1.0000 cap"
+        );
+    }
+
+    #[test]
+    fn test_invalid_costs_are_rejected() {
+        let guard = BudgetGuard::new().with_daily_limit(1.0);
+        for usd in [f64::NAN, f64::INFINITY, -0.5] {
+            assert!(matches!(guard.record_cost(usd), Err(Error::ConfigError(_))));
+        }
+
+        let reservation = guard.reserve(0.2).unwrap();
+        guard.settle(reservation, Some(f64::NAN));
+        assert!((guard.spent(BudgetWindow::Day) - 0.2).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_estimate_scales_with_request_size() {
+        let guard = BudgetGuard::new()
+            .with_request_estimate(0.01)
+            .with_token_prices(0.000_001, 0.000_004);
+        let size = RequestSize {
+            prompt_tokens: 1000,
+            max_tokens: Some(500),
+        };
+        assert!((guard.estimate(size) - 0.013).abs() < 1e-12);
+        assert!(
+            (guard.estimate(RequestSize {
+                max_tokens: None,
+                ..size
+            }) - 0.011)
+                .abs()
+                < 1e-12
+        );
+    }
+
+    #[tokio::test]
+    async fn test_reservations_are_released_only_before_output() {
+        let guard = BudgetGuard::new()
+            .with_hourly_limit(1.0)
+            .with_request_estimate(0.4);
+
+        let result: Result<()> = guarded(
+            Some(&guard),
+            RequestSize::default(),
+            async { Err(Error::ConfigError("failed".to_string())) },
+            |_| None,
+        )
+        .await;
+        assert!(result.is_err());
+        assert_eq!(guard.spent(BudgetWindow::Hour), 0.0);
+
+        let pending = PendingReservation::reserve(&guard, RequestSize::default()).unwrap();
+        assert!((guard.spent(BudgetWindow::Hour) - 0.4).abs() < 1e-9);
+        drop(pending);
+        assert_eq!(guard.spent(BudgetWindow::Hour), 0.0);
+
+        PendingReservation::reserve(&guard, RequestSize::default())
+            .unwrap()
+            .settle(None);
+        assert!((guard.spent(BudgetWindow::Hour) - 0.4).abs() < 1e-9);
+
+        // Output already generated is billed, so the estimate is kept.
+        let mut pending = PendingReservation::reserve(&guard, RequestSize::default()).unwrap();
+        pending.mark_billable();
+        drop(pending);
+        assert!((guard.spent(BudgetWindow::Hour) - 0.8).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_chat_completion_is_rejected_once_budget_is_spent() {
+        use crate::api::chat::ChatApi;
+        use crate::types::chat::{ChatCompletionRequest, ChatRole, Message};
+        use serde_json::json;
+        use std::sync::Arc;
+        use wiremock::{matchers, Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+        Mock::given(matchers::method("POST"))
+            .and(matchers::path("/chat/completions"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": "gen-1",
+                "object": "chat.completion",
+                "created": 0,
+                "model": "openai/gpt-4o",
+                "choices": [{
+                    "message": {"role": "assistant", "content": "hi"},
+                    "finish_reason": "stop"
+                }],
+                "usage": {
+                    "prompt_tokens": 1,
+                    "completion_tokens": 1,
+                    "total_tokens": 2,
+                    "cost": 0.75
+                }
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let guard = Arc::new(
+            BudgetGuard::new()
+                .with_hourly_limit(1.0)
+                .with_request_estimate(0.1),
+        );
+        let mut config = crate::tests::test_helpers::test_client_config();
+        config.base_url = format!("{}/", mock_server.uri()).parse().unwrap();
+        config.budget_guard = Some(Arc::clone(&guard));
+        let chat = ChatApi::new(reqwest::Client::new(), &config).unwrap();
+
+        let request = ChatCompletionRequest {
+            model: "openai/gpt-4o".to_string(),
+            messages: vec![Message::text(ChatRole::User, "hello")],
+            ..Default::default()
+        };
+
+        chat.chat_completion(request.clone()).await.unwrap();
+        assert!((guard.spent(BudgetWindow::Hour) - 0.75).abs() < 1e-9);
+
+        guard.record_cost(0.25).unwrap();
+        let err = chat.chat_completion(request).await.unwrap_err();
+        assert!(matches!(err, Error::BudgetExceeded { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_stream_keeps_its_estimate_once_output_has_arrived() {
+        use crate::api::chat::ChatApi;
+        use crate::types::chat::{ChatCompletionRequest, ChatRole, Message};
+        use futures::StreamExt;
+        use std::sync::Arc;
+        use wiremock::{matchers, Mock, MockServer, ResponseTemplate};
+
+        let chunk = concat!(
+            "{\"id\":\"c1\",\"object\":\"chat.completion.chunk\",\"created\":0,",
+            "\"model\":\"openai/gpt-4o\",\"choices\":[{\"index\":0,",
+            "\"delta\":{\"content\":\"hi\"},\"finish_reason\":null}]"
+        );
+        let usage = concat!(
+            ",\"usage\":{\"prompt_tokens\":1,\"completion_tokens\":1,",
+            "\"total_tokens\":2,\"cost\":0.3}"
+        );
+        let mock_server = MockServer::start().await;
+        Mock::given(matchers::method("POST"))
+            .and(matchers::path("/chat/completions"))
+            .and(matchers::body_partial_json(
+                serde_json::json!({"model": "sse/model"}),
+            ))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-type", "text/event-stream")
+                    .set_body_string(format!(
+                        "data: {chunk}}}\n\ndata: {chunk}}}\n\ndata: [DONE]\n\n"
+                    )),
+            )
+            .mount(&mock_server)
+            .await;
+        Mock::given(matchers::method("POST"))
+            .and(matchers::path("/chat/completions"))
+            .and(matchers::body_partial_json(
+                serde_json::json!({"model": "json/model"}),
+            ))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string(format!("{chunk}}}\n{chunk}{usage}}}\n")),
+            )
+            .mount(&mock_server)
+            .await;
+        Mock::given(matchers::method("POST"))
+            .and(matchers::path("/chat/completions"))
+            .and(matchers::body_partial_json(
+                serde_json::json!({"model": "broken/model"}),
+            ))
+            .respond_with(ResponseTemplate::new(400))
+            .mount(&mock_server)
+            .await;
+
+        let guard = Arc::new(
+            BudgetGuard::new()
+                .with_hourly_limit(10.0)
+                .with_request_estimate(0.1),
+        );
+        let mut config = crate::tests::test_helpers::test_client_config();
+        config.base_url = format!("{}/", mock_server.uri()).parse().unwrap();
+        config.budget_guard = Some(Arc::clone(&guard));
+        let chat = ChatApi::new(reqwest::Client::new(), &config).unwrap();
+        let request = |model: &str| ChatCompletionRequest {
+            model: model.to_string(),
+            messages: vec![Message::text(ChatRole::User, "hello")],
+            ..Default::default()
+        };
+
+        // Failing before any output releases the reservation.
+        let chunks: Vec<_> = chat
+            .chat_completion_stream(request("broken/model"))
+            .collect()
+            .await;
+        assert!(chunks[0].is_err());
+        assert_eq!(guard.spent(BudgetWindow::Hour), 0.0);
+
+        // Abandoning the stream after a chunk keeps the estimate.
+        let mut stream = chat.chat_completion_stream(request("sse/model"));
+        stream.next().await.unwrap().unwrap();
+        drop(stream);
+        assert!((guard.spent(BudgetWindow::Hour) - 0.1).abs() < 1e-9);
+
+        // Usage on a plain JSON line settles the actual cost.
+        let chunks: Vec<_> = chat
+            .chat_completion_stream(request("json/model"))
+            .collect()
+            .await;
+        assert_eq!(chunks.len(), 2);
+        assert!((guard.spent(BudgetWindow::Hour) - 0.4).abs() < 1e-9);
+    }
+}
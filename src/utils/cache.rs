@@ -1,4 +1,6 @@
+use std::any::Any;
 use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
 /// A simple in-memory cache with TTL support
@@ -95,6 +97,74 @@ where
     }
 }
 
+/// How long catalog responses are reused.
+pub const CATALOG_CACHE_TTL: Duration = Duration::from_secs(300);
+
+/// Responses of the catalog endpoints (models, providers), keyed by request
+/// URL.
+///
+/// Every API handle created by a client shares one cache, so the router,
+/// `ModelsApi` and `ProvidersApi` see the same catalog. Clones share the
+/// entries.
+#[derive(Debug, Clone)]
+pub struct CatalogCache {
+    entries: Arc<Mutex<Cache<String, Arc<dyn Any + Send + Sync>>>>,
+}
+
+impl Default for CatalogCache {
+    fn default() -> Self {
+        Self::new(CATALOG_CACHE_TTL)
+    }
+}
+
+impl CatalogCache {
+    /// Creates an empty cache whose entries live for `ttl`.
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            entries: Arc::new(Mutex::new(Cache::new(ttl))),
+        }
+    }
+
+    /// The fresh response cached for `key`, if it has type `T`.
+    pub(crate) fn get<T: Clone + 'static>(&self, key: &str) -> Option<T> {
+        let entry = self.entries.lock().ok()?.get(&key.to_string())?;
+        entry.downcast_ref::<T>().cloned()
+    }
+
+    /// Whether a fresh response is cached for `key`.
+    pub(crate) fn contains(&self, key: &str) -> bool {
+        self.entries
+            .lock()
+            .is_ok_and(|mut entries| entries.get(&key.to_string()).is_some())
+    }
+
+    pub(crate) fn insert<T: Send + Sync + 'static>(&self, key: String, value: T) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.insert(key, Arc::new(value));
+        }
+    }
+
+    /// Drops every cached response, so the next calls fetch a fresh catalog.
+    pub fn clear(&self) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.clear();
+        }
+    }
+
+    /// Number of cached responses, including expired ones.
+    pub fn len(&self) -> usize {
+        self.entries
+            .lock()
+            .map(|entries| entries.len())
+            .unwrap_or(0)
+    }
+
+    /// Returns true if nothing is cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -158,4 +228,18 @@ mod tests {
         cache.cleanup_expired();
         assert_eq!(cache.len(), 0);
     }
+
+    #[test]
+    fn test_catalog_cache_is_shared_and_typed() {
+        let cache = CatalogCache::default();
+        let clone = cache.clone();
+        clone.insert("models".to_string(), vec![1u32, 2]);
+
+        assert_eq!(cache.get::<Vec<u32>>("models"), Some(vec![1, 2]));
+        assert_eq!(cache.get::<String>("models"), None);
+        assert_eq!(cache.len(), 1);
+
+        cache.clear();
+        assert!(clone.is_empty());
+    }
 }
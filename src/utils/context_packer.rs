@@ -0,0 +1,329 @@
+//! Packing retrieved documents into a model's context window.
+//!
+//! A [`ContextPacker`] divides a context window between the system prompt,
+//! retrieved context and the answer, then fills the context share with the
+//! highest-scoring [`DocumentChunk`]s that fit. Chunks are ranked by score
+//! (ties broken by ID) so the same input always packs the same way, and every
+//! chunk left out is reported in [`PackedContext::dropped`].
+//!
+//! Token counts use the crate's estimator (about four bytes per token), not
+//! the model's tokenizer, so leave some headroom in the split.
+
+use crate::error::{Error, Result};
+use crate::types::chat::{ChatCompletionRequest, ChatRole, Message};
+use crate::utils::validation::chat::estimate_message_tokens;
+
+/// Per-request overhead added by `estimate_request_tokens`.
+const REQUEST_OVERHEAD_TOKENS: u32 = 10;
+
+/// Separator placed between chunks in the context message.
+const CHUNK_SEPARATOR: &str = "\n\n---\n\n";
+
+/// A scored piece of a retrieved document.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DocumentChunk {
+    /// Identifies the chunk; also breaks ties between equal scores.
+    pub id: String,
+    pub text: String,
+    /// Relevance score; higher is packed first.
+    pub score: f32,
+    /// Where the chunk came from, e.g. a URL or file name. Shown to the
+    /// model in place of the ID when set.
+    pub source: Option<String>,
+}
+
+impl DocumentChunk {
+    pub fn new(id: impl Into<String>, text: impl Into<String>, score: f32) -> Self {
+        Self {
+            id: id.into(),
+            text: text.into(),
+            score,
+            source: None,
+        }
+    }
+
+    pub fn with_source(mut self, source: impl Into<String>) -> Self {
+        self.source = Some(source.into());
+        self
+    }
+
+    /// The chunk as it appears in the context message.
+    fn render(&self) -> String {
+        format!(
+            "[{}]\n{}",
+            self.source.as_deref().unwrap_or(&self.id),
+            self.text
+        )
+    }
+}
+
+/// Fractions of the context window reserved for each part of the request.
+///
+/// The fractions must be non-negative and sum to at most 1.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BudgetSplit {
+    pub system: f32,
+    pub context: f32,
+    /// Reserved for the answer and sent as `max_tokens`.
+    pub answer: f32,
+}
+
+impl Default for BudgetSplit {
+    /// 10% system prompt, 70% context, 20% answer.
+    fn default() -> Self {
+        Self {
+            system: 0.1,
+            context: 0.7,
+            answer: 0.2,
+        }
+    }
+}
+
+impl BudgetSplit {
+    pub fn new(system: f32, context: f32, answer: f32) -> Self {
+        Self {
+            system,
+            context,
+            answer,
+        }
+    }
+
+    fn validate(&self) -> Result<()> {
+        let parts = [self.system, self.context, self.answer];
+        if parts.iter().any(|part| !part.is_finite() || *part < 0.0)
+            || parts.iter().sum::<f32>() > 1.0 + f32::EPSILON
+        {
+            return Err(Error::ConfigError(format!(
+                "Budget split fractions must be non-negative and sum to at most 1, got {:?}",
+                self
+            )));
+        }
+        Ok(())
+    }
+
+    fn tokens(fraction: f32, context_length: u32) -> u32 {
+        (fraction * context_length as f32).floor() as u32
+    }
+}
+
+/// A chunk that was not packed, with its estimated size.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DroppedChunk {
+    pub chunk: DocumentChunk,
+    pub estimated_tokens: u32,
+}
+
+/// The result of [`ContextPacker::pack`].
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct PackedContext {
+    /// The system prompt followed by a user message holding the packed
+    /// chunks and the question.
+    pub messages: Vec<Message>,
+    /// Chunks included, in the order they appear.
+    pub included: Vec<DocumentChunk>,
+    /// Chunks left out for lack of room, highest score first.
+    pub dropped: Vec<DroppedChunk>,
+    /// Estimated prompt size of `messages`.
+    pub estimated_tokens: u32,
+    /// Tokens reserved for the answer.
+    pub answer_tokens: u32,
+}
+
+impl PackedContext {
+    /// Builds a request for `model` from the packed messages, with
+    /// `max_tokens` set to the answer reservation.
+    pub fn to_request(&self, model: impl Into<String>) -> ChatCompletionRequest {
+        ChatCompletionRequest {
+            model: model.into(),
+            messages: self.messages.clone(),
+            max_tokens: Some(self.answer_tokens),
+            ..Default::default()
+        }
+    }
+}
+
+/// Fits as many document chunks as possible into a context window.
+///
+/// # Example
+///
+/// ```
+/// use openrouter_api::utils::context_packer::{BudgetSplit, ContextPacker, DocumentChunk};
+///
+/// let chunks = vec![
+///     DocumentChunk::new("a", "Tokio is an async runtime.", 0.9),
+///     DocumentChunk::new("b", "x".repeat(10_000), 0.8),
+/// ];
+/// let packed = ContextPacker::new(1_000)
+///     .with_split(BudgetSplit::new(0.1, 0.6, 0.3))
+///     .pack("Answer from the context.", "What is Tokio?", chunks)?;
+///
+/// assert_eq!(packed.included.len(), 1);
+/// assert_eq!(packed.dropped[0].chunk.id, "b");
+/// # Ok::<(), openrouter_api::Error>(())
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContextPacker {
+    context_length: u32,
+    split: BudgetSplit,
+}
+
+impl ContextPacker {
+    /// Creates a packer for a model with `context_length` tokens, using the
+    /// default [`BudgetSplit`].
+    pub fn new(context_length: u32) -> Self {
+        Self {
+            context_length,
+            split: BudgetSplit::default(),
+        }
+    }
+
+    pub fn with_split(mut self, split: BudgetSplit) -> Self {
+        self.split = split;
+        self
+    }
+
+    /// Packs the highest-scoring `chunks` that fit alongside `system_prompt`
+    /// and `query`.
+    ///
+    /// System-prompt tokens left unused are added to the context share. A
+    /// chunk too large for the remaining room is skipped, and smaller,
+    /// lower-scoring chunks may still be packed after it.
+    ///
+    /// Returns `Error::ConfigError` for an invalid split and
+    /// `Error::ValidationError` when the system prompt exceeds its share.
+    pub fn pack(
+        &self,
+        system_prompt: &str,
+        query: &str,
+        mut chunks: Vec<DocumentChunk>,
+    ) -> Result<PackedContext> {
+        self.split.validate()?;
+        let system_budget = BudgetSplit::tokens(self.split.system, self.context_length);
+        let context_budget = BudgetSplit::tokens(self.split.context, self.context_length);
+        let answer_tokens = BudgetSplit::tokens(self.split.answer, self.context_length);
+
+        let system = Message::text(ChatRole::System, system_prompt);
+        let system_tokens = estimate_message_tokens(&system);
+        if system_tokens > system_budget {
+            return Err(Error::ValidationError(format!(
+                "System prompt needs about {} tokens but its budget is {}",
+                system_tokens, system_budget
+            )));
+        }
+
+        let question = format!("Question: {query}");
+        let fixed_tokens =
+            estimate_message_tokens(&Message::text(ChatRole::User, question.as_str()))
+                + REQUEST_OVERHEAD_TOKENS;
+        let mut remaining =
+            (context_budget + system_budget - system_tokens).saturating_sub(fixed_tokens);
+
+        chunks.sort_by(|a, b| {
+            b.score
+                .partial_cmp(&a.score)
+                .unwrap_or_else(|| a.score.is_nan().cmp(&b.score.is_nan()))
+                .then_with(|| a.id.cmp(&b.id))
+        });
+
+        let mut included = Vec::new();
+        let mut dropped = Vec::new();
+        let mut body = String::new();
+        for chunk in chunks {
+            // Rounded up per chunk so the total never exceeds the estimate
+            // of the joined text.
+            let rendered = chunk.render();
+            let estimated_tokens = (rendered.len() + CHUNK_SEPARATOR.len()).div_ceil(4) as u32;
+            if estimated_tokens > remaining {
+                dropped.push(DroppedChunk {
+                    chunk,
+                    estimated_tokens,
+                });
+                continue;
+            }
+            remaining -= estimated_tokens;
+            body.push_str(&rendered);
+            body.push_str(CHUNK_SEPARATOR);
+            included.push(chunk);
+        }
+        body.push_str(&question);
+
+        let messages = vec![system, Message::text(ChatRole::User, body)];
+        let estimated_tokens =
+            messages.iter().map(estimate_message_tokens).sum::<u32>() + REQUEST_OVERHEAD_TOKENS;
+        Ok(PackedContext {
+            messages,
+            included,
+            dropped,
+            estimated_tokens,
+            answer_tokens,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::validation::chat::estimate_request_tokens;
+
+    fn chunk(id: &str, bytes: usize, score: f32) -> DocumentChunk {
+        DocumentChunk::new(id, "x".repeat(bytes), score)
+    }
+
+    #[test]
+    fn test_pack_prefers_high_scores_and_fills_gaps() {
+        let packer = ContextPacker::new(1000).with_split(BudgetSplit::new(0.1, 0.5, 0.4));
+        let chunks = vec![
+            chunk("small-low", 200, 0.1),
+            chunk("large-mid", 1600, 0.5),
+            chunk("medium-high", 1200, 0.9),
+        ];
+        let packed = packer.pack("Be brief.", "What?", chunks).unwrap();
+
+        let included: Vec<_> = packed.included.iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(included, ["medium-high", "small-low"]);
+        assert_eq!(packed.dropped.len(), 1);
+        assert_eq!(packed.dropped[0].chunk.id, "large-mid");
+
+        let request = packed.to_request("openai/gpt-4o");
+        assert_eq!(request.max_tokens, Some(400));
+        assert_eq!(estimate_request_tokens(&request), packed.estimated_tokens);
+        assert!(packed.estimated_tokens <= 600);
+        let body = request.messages[1].content.to_text();
+        assert!(body.starts_with("[medium-high]\n"));
+        assert!(body.ends_with("Question: What?"));
+    }
+
+    #[test]
+    fn test_pack_is_deterministic_for_equal_scores() {
+        let packer = ContextPacker::new(400);
+        let forward = vec![
+            chunk("b", 400, 0.5),
+            chunk("a", 400, 0.5),
+            chunk("c", 400, f32::NAN),
+        ];
+        let mut reversed = forward.clone();
+        reversed.reverse();
+
+        let first = packer.pack("", "q", forward).unwrap();
+        let second = packer.pack("", "q", reversed).unwrap();
+        assert_eq!(first.included[0].id, "a");
+        assert_eq!(first.dropped.last().unwrap().chunk.id, "c");
+        assert_eq!(first.messages, second.messages);
+    }
+
+    #[test]
+    fn test_pack_rejects_bad_budgets() {
+        let packer = ContextPacker::new(100);
+        assert!(matches!(
+            packer.pack(&"x".repeat(400), "q", vec![]),
+            Err(Error::ValidationError(_))
+        ));
+
+        let packer = packer.with_split(BudgetSplit::new(0.5, 0.5, 0.5));
+        assert!(matches!(
+            packer.pack("", "q", vec![]),
+            Err(Error::ConfigError(_))
+        ));
+    }
+}
@@ -0,0 +1,209 @@
+//! Diagnostics reported by the client, for logging without `tracing`.
+//!
+//! The client reports retries, model fallbacks, skipped values and catalog
+//! cache lookups to a [`DiagnosticsSink`]. Without one, they are logged with
+//! `tracing` when that feature is enabled and dropped otherwise.
+//! [`StderrDiagnostics`] prints them instead.
+
+use std::fmt;
+use std::time::Duration;
+
+/// How noteworthy a [`Diagnostic`] is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum DiagnosticLevel {
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+/// Something the client noticed while handling a request.
+#[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
+pub enum Diagnostic<'a> {
+    /// A failed attempt is retried after `delay`. `status` is `None` when
+    /// no response arrived.
+    Retry {
+        operation: &'a str,
+        attempt: u32,
+        status: Option<u16>,
+        delay: Duration,
+    },
+    /// The retry policy allowed a retry but the shared `RetryBudget` had
+    /// none left.
+    RetryBudgetExhausted { operation: &'a str },
+    /// The body of a response being retried could not be drained.
+    RetryBodyDiscardFailed { operation: &'a str, error: &'a str },
+    /// A request was served by a fallback model instead of `requested`.
+    ModelFallback { requested: &'a str, served: &'a str },
+    /// Requests moved to another base URL (`client::failover`).
+    BaseUrlFailover { from: &'a str, to: &'a str },
+    /// A value could not be serialized and was left out of a request.
+    SerializationSkipped { field: &'a str, error: &'a str },
+    /// A streamed chunk could not be parsed and was skipped.
+    StreamChunkSkipped { operation: &'a str, error: &'a str },
+    /// A request that failed validation is sent anyway
+    /// (`ValidationMode::Lenient`).
+    ValidationBypassed { operation: &'a str, error: &'a str },
+    /// A request sets a parameter `model` is not known to support; it is
+    /// sent as is.
+    UnsupportedParameter { model: &'a str, parameter: &'a str },
+    /// A catalog response was answered from the cache.
+    CacheHit { key: &'a str },
+    /// A catalog response was not cached and is fetched.
+    CacheMiss { key: &'a str },
+}
+
+impl Diagnostic<'_> {
+    pub fn level(&self) -> DiagnosticLevel {
+        match self {
+            Diagnostic::CacheHit { .. } | Diagnostic::CacheMiss { .. } => DiagnosticLevel::Debug,
+            Diagnostic::Retry { .. } | Diagnostic::ModelFallback { .. } => DiagnosticLevel::Info,
+            Diagnostic::StreamChunkSkipped { .. } => DiagnosticLevel::Error,
+            _ => DiagnosticLevel::Warn,
+        }
+    }
+}
+
+impl fmt::Display for Diagnostic<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Diagnostic::Retry {
+                operation,
+                attempt,
+                status: Some(status),
+                delay,
+            } => write!(
+                f,
+                "Retrying {operation} after status {status} (attempt {attempt}) in {delay:?}"
+            ),
+            Diagnostic::Retry {
+                operation,
+                attempt,
+                status: None,
+                delay,
+            } => write!(
+                f,
+                "Retrying {operation} after a network error (attempt {attempt}) in {delay:?}"
+            ),
+            Diagnostic::RetryBudgetExhausted { operation } => {
+                write!(f, "Retry budget exhausted; not retrying {operation}")
+            }
+            Diagnostic::RetryBodyDiscardFailed { operation, error } => write!(
+                f,
+                "Failed to consume response body during retry for {operation}: {error}"
+            ),
+            Diagnostic::ModelFallback { requested, served } => {
+                write!(f, "Request for {requested} was served by fallback {served}")
+            }
+            Diagnostic::BaseUrlFailover { from, to } => {
+                write!(f, "Switched base URL from {from} to {to}")
+            }
+            Diagnostic::SerializationSkipped { field, error } => {
+                write!(f, "Left {field} out of the request: {error}")
+            }
+            Diagnostic::StreamChunkSkipped { operation, error } => {
+                write!(f, "Skipped unparseable {operation} stream chunk: {error}")
+            }
+            Diagnostic::ValidationBypassed { operation, error } => {
+                write!(
+                    f,
+                    "Sending {operation} request that failed validation: {error}"
+                )
+            }
+            Diagnostic::UnsupportedParameter { model, parameter } => write!(
+                f,
+                "Model {model} is not known to support '{parameter}'; sending it anyway"
+            ),
+            Diagnostic::CacheHit { key } => write!(f, "Catalog cache hit for {key}"),
+            Diagnostic::CacheMiss { key } => write!(f, "Catalog cache miss for {key}"),
+        }
+    }
+}
+
+/// Receives the client's diagnostics.
+pub trait DiagnosticsSink: fmt::Debug + Send + Sync {
+    fn emit(&self, diagnostic: &Diagnostic<'_>);
+}
+
+/// Drops every diagnostic.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoopDiagnostics;
+
+impl DiagnosticsSink for NoopDiagnostics {
+    fn emit(&self, _diagnostic: &Diagnostic<'_>) {}
+}
+
+/// Prints diagnostics at or above a level to stderr.
+#[derive(Debug, Clone, Copy)]
+pub struct StderrDiagnostics {
+    pub min_level: DiagnosticLevel,
+}
+
+impl Default for StderrDiagnostics {
+    /// Prints warnings and errors.
+    fn default() -> Self {
+        Self {
+            min_level: DiagnosticLevel::Warn,
+        }
+    }
+}
+
+impl DiagnosticsSink for StderrDiagnostics {
+    fn emit(&self, diagnostic: &Diagnostic<'_>) {
+        if diagnostic.level() >= self.min_level {
+            eprintln!("openrouter_api: {diagnostic}");
+        }
+    }
+}
+
+/// Logs diagnostics as `tracing` events at their level.
+#[cfg(feature = "tracing")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TracingDiagnostics;
+
+#[cfg(feature = "tracing")]
+impl DiagnosticsSink for TracingDiagnostics {
+    fn emit(&self, diagnostic: &Diagnostic<'_>) {
+        match diagnostic.level() {
+            DiagnosticLevel::Debug => tracing::debug!("{diagnostic}"),
+            DiagnosticLevel::Info => tracing::info!("{diagnostic}"),
+            DiagnosticLevel::Warn => tracing::warn!("{diagnostic}"),
+            DiagnosticLevel::Error => tracing::error!("{diagnostic}"),
+        }
+    }
+}
+
+/// The sink used when none is configured: `tracing` with that feature,
+/// otherwise none.
+pub(crate) fn default_diagnostics() -> &'static dyn DiagnosticsSink {
+    #[cfg(feature = "tracing")]
+    return &TracingDiagnostics;
+    #[cfg(not(feature = "tracing"))]
+    &NoopDiagnostics
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_levels_and_messages() {
+        let retry = Diagnostic::Retry {
+            operation: "chat_completion",
+            attempt: 2,
+            status: Some(503),
+            delay: Duration::from_millis(500),
+        };
+        assert_eq!(retry.level(), DiagnosticLevel::Info);
+        assert_eq!(
+            retry.to_string(),
+            "Retrying chat_completion after status 503 (attempt 2) in 500ms"
+        );
+        assert_eq!(
+            Diagnostic::CacheMiss { key: "models" }.level(),
+            DiagnosticLevel::Debug
+        );
+        assert!(DiagnosticLevel::Warn > DiagnosticLevel::Info);
+    }
+}
@@ -0,0 +1,43 @@
+//! Hook for exporting client metrics to a monitoring system.
+//!
+//! Install a [`MetricsRecorder`] with `OpenRouterClient::with_metrics_recorder`
+//! to observe every call made through the client without wrapping its
+//! streams or futures.
+
+use crate::client::CompressionStats;
+use crate::types::chat::StreamSummary;
+use std::fmt;
+use std::time::Duration;
+
+/// How a `TaskPool` task ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskOutcome {
+    Succeeded,
+    Failed,
+    /// Cancelled by `TaskPool::shutdown`, or submitted after it.
+    Cancelled,
+}
+
+/// Receives measurements from API calls.
+///
+/// Every method has a no-op default, so implementations only override the
+/// measurements they export.
+pub trait MetricsRecorder: fmt::Debug + Send + Sync {
+    /// Called when a chat completion stream for `model` ends successfully,
+    /// with its latency, throughput and usage.
+    fn record_stream(&self, model: &str, summary: &StreamSummary) {
+        let _ = (model, summary);
+    }
+
+    /// Called when a `TaskPool` task labelled `label` ends, with the time
+    /// since it was submitted.
+    fn record_task(&self, label: &str, outcome: TaskOutcome, elapsed: Duration) {
+        let _ = (label, outcome, elapsed);
+    }
+
+    /// Called for each request sent with a compressed body, labelled with
+    /// its operation (e.g. `chat_completion`).
+    fn record_request_compression(&self, operation: &str, stats: &CompressionStats) {
+        let _ = (operation, stats);
+    }
+}
@@ -1,18 +1,37 @@
 pub mod auth;
+pub mod budget;
 pub mod cache;
+pub mod context_packer;
+pub mod diagnostics;
 pub mod https;
+pub mod metrics;
+pub mod partial_json;
 pub mod retry;
 pub mod security;
 pub mod url_builder;
 pub mod validation;
 
 // Re-export commonly used utilities
-pub use auth::load_api_key_from_env;
-pub use cache::Cache;
-pub use retry::{execute_with_retry_builder, handle_response_json, handle_response_text};
-pub use security::{create_safe_error_message, redact_sensitive_content};
+pub use auth::{
+    load_api_key_from_command, load_api_key_from_env, load_api_key_from_file, ApiKeySource,
+};
+pub use budget::{BudgetGuard, BudgetWindow, RequestSize};
+pub use cache::{Cache, CatalogCache};
+pub use context_packer::{BudgetSplit, ContextPacker, DocumentChunk};
+#[cfg(feature = "tracing")]
+pub use diagnostics::TracingDiagnostics;
+pub use diagnostics::{
+    Diagnostic, DiagnosticLevel, DiagnosticsSink, NoopDiagnostics, StderrDiagnostics,
+};
+pub use metrics::{MetricsRecorder, TaskOutcome};
+pub use partial_json::{PartialJsonParser, PartialValue};
+pub use retry::{
+    execute_with_retry_builder, handle_response_json, handle_response_json_limited,
+    handle_response_text, RetryBudget, RetryDecision, RetryPolicy, StatusCodeRetryPolicy,
+};
+pub use security::{create_safe_error_message, redact_sensitive_content, Redactor};
 pub use url_builder::UrlBuilder;
 pub use validation::{
     check_prompt_token_limits, check_token_limits, validate_chat_request,
-    validate_completion_request, validate_web_search_request,
+    validate_completion_request, validate_web_search_request, ValidationMode,
 };
@@ -0,0 +1,372 @@
+//! Incremental parsing of JSON that arrives in pieces.
+//!
+//! Structured outputs streamed from a chat completion arrive as fragments of
+//! one JSON document. [`PartialJsonParser`] scans each fragment once and,
+//! whenever values inside the document complete, yields a [`PartialValue`]
+//! holding everything complete so far, so a UI can render fields as they
+//! arrive instead of waiting for the closing brace.
+//!
+//! Values are only included once they are complete: a string still being
+//! written, or a number that may have more digits, is left out until it ends.
+
+use crate::error::{Error, Result};
+use serde_json::Value;
+
+/// The document parsed so far.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PartialValue {
+    /// Every complete value so far, inside the containers still open.
+    pub value: Value,
+    /// JSON Pointers (e.g. `/items/0/name`) of the values completed by the
+    /// latest fragment, innermost first. The root is `""`.
+    pub completed: Vec<String>,
+    /// Whether the whole document is complete.
+    pub is_complete: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Container {
+    Object,
+    Array,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Expect {
+    /// Just after `{`.
+    KeyOrEnd,
+    /// Just after `[`.
+    ValueOrEnd,
+    /// After a `,` in an object.
+    Key,
+    Colon,
+    Value,
+    CommaOrEnd,
+}
+
+#[derive(Debug)]
+struct Frame {
+    container: Container,
+    expect: Expect,
+    /// Key of the object member being read.
+    key: String,
+    /// Index of the array element being read.
+    index: usize,
+}
+
+impl Frame {
+    fn segment(&self) -> String {
+        match self.container {
+            Container::Object => self.key.replace('~', "~0").replace('/', "~1"),
+            Container::Array => self.index.to_string(),
+        }
+    }
+
+    fn closer(&self) -> char {
+        match self.container {
+            Container::Object => '}',
+            Container::Array => ']',
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+enum Token {
+    #[default]
+    None,
+    Key,
+    String,
+    /// A number, `true`, `false` or `null`.
+    Literal,
+}
+
+/// Parses a JSON object or array fed in fragments.
+///
+/// Anything before the first `{` or `[` (such as a Markdown code fence) and
+/// anything after the document ends is ignored.
+///
+/// # Example
+///
+/// ```
+/// use openrouter_api::utils::partial_json::PartialJsonParser;
+/// use serde_json::json;
+///
+/// let mut parser = PartialJsonParser::new();
+/// assert!(parser.push(r#"{"title": "Rust", "ta"#)?.is_some());
+/// assert_eq!(parser.value(), Some(&json!({"title": "Rust"})));
+///
+/// let update = parser.push(r#"gs": ["async"]}"#)?.unwrap();
+/// assert!(update.is_complete);
+/// assert_eq!(update.completed, ["/tags/0", "/tags", ""]);
+/// assert_eq!(parser.finish()?, json!({"title": "Rust", "tags": ["async"]}));
+/// # Ok::<(), openrouter_api::Error>(())
+/// ```
+#[derive(Debug, Default)]
+pub struct PartialJsonParser {
+    buffer: String,
+    /// Where the document starts in `buffer`, once its first bracket is seen.
+    start: Option<usize>,
+    stack: Vec<Frame>,
+    token: Token,
+    token_start: usize,
+    escaped: bool,
+    /// End of the last complete value, and the brackets that close the
+    /// containers open at that point.
+    safe_end: usize,
+    closers: String,
+    done: bool,
+    completed: Vec<String>,
+    latest: Option<Value>,
+}
+
+impl PartialJsonParser {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feeds the next fragment.
+    ///
+    /// Returns the document so far when the fragment completed at least one
+    /// value, and `Error::SchemaValidationError` when the input is not JSON.
+    pub fn push(&mut self, fragment: &str) -> Result<Option<PartialValue>> {
+        let from = self.buffer.len();
+        self.buffer.push_str(fragment);
+        for i in from..self.buffer.len() {
+            if self.done {
+                break;
+            }
+            // JSON's structural characters are ASCII, so scanning bytes never
+            // splits a character that matters.
+            self.step(i, self.buffer.as_bytes()[i])?;
+        }
+        if self.completed.is_empty() {
+            return Ok(None);
+        }
+
+        let value = self.repaired()?;
+        self.latest = Some(value.clone());
+        Ok(Some(PartialValue {
+            value,
+            completed: std::mem::take(&mut self.completed),
+            is_complete: self.done,
+        }))
+    }
+
+    /// The document as of the last update, if any value has completed.
+    pub fn value(&self) -> Option<&Value> {
+        self.latest.as_ref()
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.done
+    }
+
+    /// Returns the complete document, or an error if the input ended early.
+    pub fn finish(self) -> Result<Value> {
+        if !self.done {
+            return Err(Error::SchemaValidationError(
+                "Streamed JSON ended before the document was complete".into(),
+            ));
+        }
+        match self.latest {
+            Some(value) => Ok(value),
+            None => self.repaired(),
+        }
+    }
+
+    fn step(&mut self, i: usize, byte: u8) -> Result<()> {
+        match self.token {
+            Token::Key | Token::String => {
+                if self.escaped {
+                    self.escaped = false;
+                } else if byte == b'\\' {
+                    self.escaped = true;
+                } else if byte == b'"' {
+                    self.end_string(i)?;
+                }
+                return Ok(());
+            }
+            Token::Literal if is_delimiter(byte) => {
+                self.token = Token::None;
+                self.complete_value(i);
+            }
+            Token::Literal => return Ok(()),
+            Token::None => {}
+        }
+
+        if self.start.is_none() {
+            if matches!(byte, b'{' | b'[') {
+                self.start = Some(i);
+                self.open(byte);
+            }
+            return Ok(());
+        }
+        if byte.is_ascii_whitespace() {
+            return Ok(());
+        }
+
+        let Some(frame) = self.stack.last_mut() else {
+            return Ok(());
+        };
+        match (frame.container, frame.expect, byte) {
+            (_, Expect::KeyOrEnd | Expect::Key, b'"') => {
+                self.token = Token::Key;
+                self.token_start = i;
+            }
+            (Container::Object, Expect::KeyOrEnd | Expect::CommaOrEnd, b'}')
+            | (Container::Array, Expect::ValueOrEnd | Expect::CommaOrEnd, b']') => {
+                self.stack.pop();
+                self.complete_value(i + 1);
+            }
+            (_, Expect::Colon, b':') => frame.expect = Expect::Value,
+            (Container::Object, Expect::CommaOrEnd, b',') => frame.expect = Expect::Key,
+            (Container::Array, Expect::CommaOrEnd, b',') => {
+                frame.index += 1;
+                frame.expect = Expect::Value;
+            }
+            (_, Expect::Value | Expect::ValueOrEnd, _) => self.start_value(i, byte)?,
+            _ => return Err(malformed(i)),
+        }
+        Ok(())
+    }
+
+    fn start_value(&mut self, i: usize, byte: u8) -> Result<()> {
+        match byte {
+            b'{' | b'[' => self.open(byte),
+            b'"' => self.token = Token::String,
+            b'-' | b'0'..=b'9' | b't' | b'f' | b'n' => self.token = Token::Literal,
+            _ => return Err(malformed(i)),
+        }
+        self.token_start = i;
+        Ok(())
+    }
+
+    fn open(&mut self, byte: u8) {
+        let (container, expect) = if byte == b'{' {
+            (Container::Object, Expect::KeyOrEnd)
+        } else {
+            (Container::Array, Expect::ValueOrEnd)
+        };
+        self.stack.push(Frame {
+            container,
+            expect,
+            key: String::new(),
+            index: 0,
+        });
+    }
+
+    fn end_string(&mut self, i: usize) -> Result<()> {
+        let token = std::mem::replace(&mut self.token, Token::None);
+        if token == Token::String {
+            self.complete_value(i + 1);
+            return Ok(());
+        }
+        let key: String = serde_json::from_str(&self.buffer[self.token_start..=i])
+            .map_err(|_| malformed(self.token_start))?;
+        if let Some(frame) = self.stack.last_mut() {
+            frame.key = key;
+            frame.expect = Expect::Colon;
+        }
+        Ok(())
+    }
+
+    /// Records that a value ended just before `end`.
+    fn complete_value(&mut self, end: usize) {
+        let pointer: String = self
+            .stack
+            .iter()
+            .map(|frame| format!("/{}", frame.segment()))
+            .collect();
+        self.completed.push(pointer);
+        self.safe_end = end;
+        self.closers = self.stack.iter().rev().map(Frame::closer).collect();
+        match self.stack.last_mut() {
+            Some(frame) => frame.expect = Expect::CommaOrEnd,
+            None => self.done = true,
+        }
+    }
+
+    /// Parses the complete prefix with its open containers closed.
+    fn repaired(&self) -> Result<Value> {
+        let start = self.start.unwrap_or(0);
+        let text = format!("{}{}", &self.buffer[start..self.safe_end], self.closers);
+        serde_json::from_str(&text).map_err(|e| {
+            Error::SchemaValidationError(format!("Malformed JSON in streamed output: {e}"))
+        })
+    }
+}
+
+fn is_delimiter(byte: u8) -> bool {
+    matches!(byte, b',' | b'}' | b']') || byte.is_ascii_whitespace()
+}
+
+fn malformed(offset: usize) -> Error {
+    Error::SchemaValidationError(format!(
+        "Malformed JSON in streamed output at byte {offset}"
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_char_by_char_updates_track_completed_fields() {
+        let document = r#"```json
+{"name": "Ada \"the\" Countess", "born": 1815, "tags": ["math", {"a/b": true}], "x": null}
+```"#;
+        let mut parser = PartialJsonParser::new();
+        let mut updates = Vec::new();
+        for c in document.chars() {
+            if let Some(update) = parser.push(&c.to_string()).unwrap() {
+                updates.push(update);
+            }
+        }
+
+        let completed: Vec<_> = updates.iter().flat_map(|u| u.completed.clone()).collect();
+        assert_eq!(
+            completed,
+            [
+                "/name",
+                "/born",
+                "/tags/0",
+                "/tags/1/a~1b",
+                "/tags/1",
+                "/tags",
+                "/x",
+                ""
+            ]
+        );
+        assert_eq!(updates[0].value, json!({"name": "Ada \"the\" Countess"}));
+        assert_eq!(
+            updates[3].value,
+            json!({"name": "Ada \"the\" Countess", "born": 1815, "tags": ["math", {"a/b": true}]})
+        );
+        assert!(updates.last().unwrap().is_complete);
+        assert!(updates[..updates.len() - 1].iter().all(|u| !u.is_complete));
+        assert_eq!(parser.finish().unwrap()["x"], Value::Null);
+    }
+
+    #[test]
+    fn test_incomplete_values_are_left_out() {
+        let mut parser = PartialJsonParser::new();
+        assert!(parser.push(r#"{"count": 12"#).unwrap().is_none());
+        let update = parser.push(r#"3, "text": "partial"#).unwrap().unwrap();
+        assert_eq!(update.value, json!({"count": 123}));
+        assert!(matches!(
+            parser.finish(),
+            Err(Error::SchemaValidationError(_))
+        ));
+    }
+
+    #[test]
+    fn test_malformed_input_is_an_error() {
+        for input in [r#"{"a" 1}"#, r#"{"a": x}"#, r#"{"a": tru}"#, r#"[1 2]"#] {
+            let mut parser = PartialJsonParser::new();
+            assert!(
+                matches!(parser.push(input), Err(Error::SchemaValidationError(_))),
+                "{input}"
+            );
+        }
+    }
+}
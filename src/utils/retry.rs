@@ -2,6 +2,7 @@
 
 use crate::client::RetryConfig;
 use crate::error::{Error, Result};
+use crate::utils::diagnostics::{default_diagnostics, Diagnostic, DiagnosticsSink};
 use fastrand::Rng;
 use reqwest::{
     header::{HeaderMap, RETRY_AFTER},
@@ -15,7 +16,9 @@ pub mod operations {
     pub const TEXT_COMPLETION: &str = "text_completion";
     pub const WEB_SEARCH: &str = "web_search";
     pub const LIST_MODELS: &str = "list_models";
+    pub const LIST_MODEL_ENDPOINTS: &str = "list_model_endpoints";
     pub const GET_BALANCE: &str = "get_balance";
+    pub const CREATE_COINBASE_CHARGE: &str = "create_coinbase_charge";
     pub const GET_ACTIVITY: &str = "get_activity";
     pub const GET_PROVIDERS: &str = "get_providers";
     pub const GET_GENERATION: &str = "get_generation";
@@ -25,23 +28,168 @@ pub mod operations {
     pub const GET_EMBEDDINGS: &str = "get_embeddings";
 }
 
+/// What prompted a retry decision.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum RetryTrigger<'a> {
+    /// The server answered with a non-success status.
+    Response(&'a Response),
+    /// No response arrived: a network error or the attempt timed out.
+    Error(&'a Error),
+}
+
+/// Information about the failed attempt passed to a [`RetryPolicy`].
+#[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
+pub struct RetryContext<'a> {
+    /// Operation name, one of the constants in [`operations`].
+    pub operation: &'a str,
+    /// 1-based number of the attempt that just failed.
+    pub attempt: u32,
+    /// Time spent on the operation so far, including earlier attempts.
+    pub elapsed: Duration,
+}
+
+/// The outcome of a [`RetryPolicy`] check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetryDecision {
+    /// Retry using the configured backoff (or the server's `Retry-After`).
+    Retry,
+    /// Retry after exactly this delay.
+    RetryAfter(Duration),
+    /// Give up and surface the response or error.
+    Stop,
+}
+
+/// Decides whether a failed attempt should be retried.
+///
+/// Policies are consulted for non-success responses and for requests that
+/// produced no response. `RetryConfig::max_retries` and
+/// `RetryConfig::total_timeout` still cap the number and duration of retries
+/// regardless of what the policy returns.
+pub trait RetryPolicy: std::fmt::Debug + Send + Sync {
+    fn should_retry(&self, trigger: RetryTrigger<'_>, context: &RetryContext<'_>) -> RetryDecision;
+}
+
+/// The default policy: retry listed status codes and transient network errors.
+///
+/// Connect errors and timeouts are retried; other failures are not.
+#[derive(Debug, Clone)]
+pub struct StatusCodeRetryPolicy {
+    pub retry_on_status_codes: Vec<u16>,
+}
+
+impl StatusCodeRetryPolicy {
+    /// Creates a policy retrying the given HTTP status codes.
+    pub fn new(retry_on_status_codes: Vec<u16>) -> Self {
+        Self {
+            retry_on_status_codes,
+        }
+    }
+}
+
+impl RetryPolicy for StatusCodeRetryPolicy {
+    fn should_retry(
+        &self,
+        trigger: RetryTrigger<'_>,
+        _context: &RetryContext<'_>,
+    ) -> RetryDecision {
+        let retry = match trigger {
+            RetryTrigger::Response(response) => self
+                .retry_on_status_codes
+                .contains(&response.status().as_u16()),
+            RetryTrigger::Error(Error::HttpError(e)) => is_retryable_reqwest_error(e),
+            RetryTrigger::Error(Error::TimeoutError(_)) => true,
+            RetryTrigger::Error(_) => false,
+        };
+        if retry {
+            RetryDecision::Retry
+        } else {
+            RetryDecision::Stop
+        }
+    }
+}
+
 /// Executes an HTTP request with retry logic using a closure for request building
 ///
 /// This version recreates the request for each retry attempt, avoiding request builder consumption.
-/// Retries are performed on selected HTTP status codes **and** transient network errors/timeouts.
+/// Whether a failed attempt is retried is decided by `config.policy`, falling back to a
+/// [`StatusCodeRetryPolicy`] built from `config.retry_on_status_codes`, which retries the
+/// selected HTTP status codes **and** transient network errors/timeouts.
 pub async fn execute_with_retry_builder<F>(
     config: &RetryConfig,
     operation_name: &str,
+    request_builder: F,
+) -> Result<Response>
+where
+    F: FnMut() -> RequestBuilder,
+{
+    execute_with_retry_observed(
+        config,
+        operation_name,
+        default_diagnostics(),
+        request_builder,
+        |_| {},
+    )
+    .await
+}
+
+/// Like [`execute_with_retry_builder`], calling `observe` with the outcome of
+/// every attempt, including the ones that trigger a retry.
+pub(crate) async fn execute_with_retry_observed<F, O>(
+    config: &RetryConfig,
+    operation_name: &str,
+    diagnostics: &dyn DiagnosticsSink,
     mut request_builder: F,
+    mut observe: O,
 ) -> Result<Response>
 where
     F: FnMut() -> RequestBuilder,
+    O: FnMut(RetryTrigger<'_>),
 {
     let mut retry_count = 0usize;
     let mut backoff_ms = config.initial_backoff_ms;
     let mut rng = Rng::new();
     let start_time = Instant::now();
 
+    let default_policy;
+    let policy: &dyn RetryPolicy = match &config.policy {
+        Some(policy) => policy.as_ref(),
+        None => {
+            default_policy = StatusCodeRetryPolicy::new(config.retry_on_status_codes.clone());
+            &default_policy
+        }
+    };
+    let budget = config.budget.as_deref();
+    if let Some(budget) = budget {
+        budget.record_request();
+    }
+    let decide = |trigger: RetryTrigger<'_>, retry_count: usize| {
+        if retry_count >= config.max_retries as usize {
+            return RetryDecision::Stop;
+        }
+        let context = RetryContext {
+            operation: operation_name,
+            attempt: retry_count as u32 + 1,
+            elapsed: start_time.elapsed(),
+        };
+        match policy.should_retry(trigger, &context) {
+            RetryDecision::Stop => RetryDecision::Stop,
+            _ if budget.is_some_and(|budget| !budget.try_retry()) => {
+                diagnostics.emit(&Diagnostic::RetryBudgetExhausted {
+                    operation: operation_name,
+                });
+                RetryDecision::Stop
+            }
+            decision => decision,
+        }
+    };
+    // Exponential backoff, stretched by the budget's recent failure rate.
+    let adaptive = |backoff_ms: u64| match budget {
+        Some(budget) => (backoff_ms as f64 * budget.backoff_multiplier()) as u64,
+        None => backoff_ms,
+    };
+
     loop {
         // Remaining time against the overall cap.
         let remaining = config.total_timeout.saturating_sub(start_time.elapsed());
@@ -57,94 +205,250 @@ where
         let send_fut = request_builder().send();
 
         // We use the remaining time as the timeout for this attempt
-        match timeout(remaining, send_fut).await {
+        let error = match timeout(remaining, send_fut).await {
             // Outer timeout (this single attempt took too long relative to global timeout)
-            Err(_) => {
-                if retry_count < config.max_retries as usize {
-                    retry_count += 1;
-
-                    // Wait with jitter, but never sleep past the remaining overall time.
-                    let sleep_ms =
-                        jittered_backoff_ms(backoff_ms, config.max_backoff_ms, &mut rng, remaining);
-                    sleep(Duration::from_millis(sleep_ms)).await;
-
-                    // Exponential step for next time.
-                    backoff_ms = next_backoff(backoff_ms, config.max_backoff_ms);
-                    continue;
-                } else {
-                    return Err(Error::TimeoutError(format!(
-                        "Request timeout for {} after {:?}",
-                        operation_name, config.total_timeout
-                    )));
-                }
-            }
+            Err(_) => Error::TimeoutError(format!(
+                "Request timeout for {} after {:?}",
+                operation_name, config.total_timeout
+            )),
 
-            // The send completed; now check whether it succeeded or failed with a network error.
-            Ok(Err(e)) => {
-                // Treat transient network failures as retryable (connect/timeouts).
-                if is_retryable_reqwest_error(&e) && retry_count < config.max_retries as usize {
-                    retry_count += 1;
+            // The send completed but failed with a network error.
+            Ok(Err(e)) => e.into(),
 
-                    let sleep_ms =
-                        jittered_backoff_ms(backoff_ms, config.max_backoff_ms, &mut rng, remaining);
-                    sleep(Duration::from_millis(sleep_ms)).await;
+            Ok(Ok(response)) => {
+                observe(RetryTrigger::Response(&response));
+                if let Some(budget) = budget {
+                    budget.record_attempt(is_overload_status(response.status()));
+                }
+                if response.status().is_success() {
+                    return Ok(response);
+                }
 
-                    backoff_ms = next_backoff(backoff_ms, config.max_backoff_ms);
-                    continue;
+                let decision = decide(RetryTrigger::Response(&response), retry_count);
+                if decision == RetryDecision::Stop {
+                    // Non-retryable status or out of retries (return as-is).
+                    return Ok(response);
                 }
+                retry_count += 1;
 
-                // Non-retryable or out of retries.
-                return Err(e.into());
-            }
+                // Parse Retry-After (delta-seconds or HTTP date), capped to 1 hour.
+                let retry_after_ms = parse_retry_after_ms(response.headers());
 
-            Ok(Ok(response)) => {
-                let status = response.status();
-                let status_code = status.as_u16();
-
-                // HTTP status-based retries.
-                if config.retry_on_status_codes.contains(&status_code)
-                    && retry_count < config.max_retries as usize
-                {
-                    retry_count += 1;
-
-                    // Parse Retry-After (delta-seconds or HTTP date), capped to 1 hour.
-                    let retry_after_ms = parse_retry_after_ms(response.headers());
-
-                    // Consume body to free the connection.
-                    if let Err(e) = response.bytes().await {
-                        #[cfg(feature = "tracing")]
-                        tracing::warn!(
-                            operation = operation_name,
-                            error = %e,
-                            "Failed to consume response body during retry"
-                        );
-                        #[cfg(not(feature = "tracing"))]
-                        eprintln!(
-                            "Warning: Failed to consume response body during retry for {}: {}",
-                            operation_name, e
-                        );
-                        // Continue anyway since we're retrying
-                    }
+                let status = response.status().as_u16();
 
-                    // Decide sleep time: prefer Retry-After, else exponential.
-                    let base_ms = retry_after_ms.unwrap_or(backoff_ms);
-                    let sleep_ms =
-                        jittered_backoff_ms(base_ms, config.max_backoff_ms, &mut rng, remaining);
+                // Consume body to free the connection; retry regardless.
+                if let Err(e) = response.bytes().await {
+                    diagnostics.emit(&Diagnostic::RetryBodyDiscardFailed {
+                        operation: operation_name,
+                        error: &e.to_string(),
+                    });
+                }
 
-                    sleep(Duration::from_millis(sleep_ms)).await;
+                let delay = match decision {
+                    RetryDecision::RetryAfter(delay) => delay.min(remaining),
+                    _ => {
+                        // Decide sleep time: prefer Retry-After, else exponential.
+                        let base_ms = retry_after_ms.unwrap_or_else(|| adaptive(backoff_ms));
+                        let sleep_ms = jittered_backoff_ms(
+                            base_ms,
+                            config.max_backoff_ms,
+                            &mut rng,
+                            remaining,
+                        );
 
-                    // Only grow exponential backoff if we didn't use Retry-After.
-                    if retry_after_ms.is_none() {
-                        backoff_ms = next_backoff(backoff_ms, config.max_backoff_ms);
+                        // Only grow exponential backoff if we didn't use Retry-After.
+                        if retry_after_ms.is_none() {
+                            backoff_ms = next_backoff(backoff_ms, config.max_backoff_ms);
+                        }
+                        Duration::from_millis(sleep_ms)
                     }
-                    continue;
-                }
+                };
+                diagnostics.emit(&Diagnostic::Retry {
+                    operation: operation_name,
+                    attempt: retry_count as u32,
+                    status: Some(status),
+                    delay,
+                });
+                sleep(delay).await;
+                continue;
+            }
+        };
+
+        // No response: retry transient failures while the policy allows it.
+        observe(RetryTrigger::Error(&error));
+        if let Some(budget) = budget {
+            budget.record_attempt(true);
+        }
+        let delay = match decide(RetryTrigger::Error(&error), retry_count) {
+            RetryDecision::Stop => return Err(error),
+            RetryDecision::RetryAfter(delay) => delay.min(remaining),
+            RetryDecision::Retry => {
+                // Wait with jitter, but never sleep past the remaining overall time.
+                let sleep_ms = jittered_backoff_ms(
+                    adaptive(backoff_ms),
+                    config.max_backoff_ms,
+                    &mut rng,
+                    remaining,
+                );
 
-                // Either success, or a non-retryable status (return as-is).
-                return Ok(response);
+                // Exponential step for next time.
+                backoff_ms = next_backoff(backoff_ms, config.max_backoff_ms);
+                Duration::from_millis(sleep_ms)
             }
+        };
+        retry_count += 1;
+        diagnostics.emit(&Diagnostic::Retry {
+            operation: operation_name,
+            attempt: retry_count as u32,
+            status: None,
+            delay,
+        });
+        sleep(delay).await;
+    }
+}
+
+/// A retry allowance shared by every request that uses it, so retries
+/// cannot multiply the load on a struggling API.
+///
+/// Retries are allowed while they stay within `retry_ratio` of the requests
+/// started in the last two windows, or below `min_retries` per window,
+/// whichever is more. With adaptive backoff on, the computed backoff grows
+/// with the share of recent attempts that failed with 429, 5xx or a network
+/// error, up to `max_backoff_multiplier` times. A server's `Retry-After` is
+/// used as-is.
+///
+/// Share one budget across clients with
+/// `RetryConfig::with_budget(Arc::clone(&budget))`.
+#[derive(Debug)]
+pub struct RetryBudget {
+    retry_ratio: f64,
+    min_retries: u32,
+    window: Duration,
+    max_backoff_multiplier: f64,
+    state: std::sync::Mutex<RetryBudgetState>,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct RetryWindow {
+    requests: u32,
+    retries: u32,
+    attempts: u32,
+    failures: u32,
+}
+
+#[derive(Debug)]
+struct RetryBudgetState {
+    started: Instant,
+    current: RetryWindow,
+    previous: RetryWindow,
+}
+
+impl RetryBudget {
+    /// Allows retries for up to `retry_ratio` of requests (e.g. `0.2`), with
+    /// a floor of 10 retries per 10-second window and adaptive backoff of up
+    /// to 4x.
+    pub fn new(retry_ratio: f64) -> Self {
+        Self {
+            retry_ratio: retry_ratio.max(0.0),
+            min_retries: 10,
+            window: Duration::from_secs(10),
+            max_backoff_multiplier: 4.0,
+            state: std::sync::Mutex::new(RetryBudgetState {
+                started: Instant::now(),
+                current: RetryWindow::default(),
+                previous: RetryWindow::default(),
+            }),
         }
     }
+
+    /// Sets the retries always allowed per window.
+    pub fn with_min_retries(mut self, min_retries: u32) -> Self {
+        self.min_retries = min_retries;
+        self
+    }
+
+    /// Sets the length of the accounting window.
+    pub fn with_window(mut self, window: Duration) -> Self {
+        self.window = window.max(Duration::from_millis(1));
+        self
+    }
+
+    /// Sets how much the backoff may grow when every recent attempt failed;
+    /// `1.0` turns adaptive backoff off.
+    pub fn with_max_backoff_multiplier(mut self, multiplier: f64) -> Self {
+        self.max_backoff_multiplier = multiplier.max(1.0);
+        self
+    }
+
+    /// Share of the attempts in the last two windows that failed with 429,
+    /// 5xx or a network error.
+    pub fn failure_rate(&self) -> f64 {
+        let state = self.state();
+        let attempts = state.current.attempts + state.previous.attempts;
+        if attempts == 0 {
+            0.0
+        } else {
+            f64::from(state.current.failures + state.previous.failures) / f64::from(attempts)
+        }
+    }
+
+    /// Retries still allowed right now.
+    pub fn remaining(&self) -> u32 {
+        let state = self.state();
+        self.allowance(&state)
+            .saturating_sub(state.current.retries + state.previous.retries)
+    }
+
+    /// Factor applied to the computed backoff.
+    pub fn backoff_multiplier(&self) -> f64 {
+        1.0 + (self.max_backoff_multiplier - 1.0) * self.failure_rate()
+    }
+
+    pub(crate) fn record_request(&self) {
+        self.state().current.requests += 1;
+    }
+
+    pub(crate) fn record_attempt(&self, failed: bool) {
+        let mut state = self.state();
+        state.current.attempts += 1;
+        state.current.failures += u32::from(failed);
+    }
+
+    /// Takes one retry from the budget, or returns false if none is left.
+    pub(crate) fn try_retry(&self) -> bool {
+        let mut state = self.state();
+        if state.current.retries + state.previous.retries >= self.allowance(&state) {
+            return false;
+        }
+        state.current.retries += 1;
+        true
+    }
+
+    fn allowance(&self, state: &RetryBudgetState) -> u32 {
+        let requests = f64::from(state.current.requests + state.previous.requests);
+        ((requests * self.retry_ratio) as u32).max(self.min_retries)
+    }
+
+    /// Locks the state, rolling the windows forward first.
+    fn state(&self) -> std::sync::MutexGuard<'_, RetryBudgetState> {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let elapsed = state.started.elapsed();
+        if elapsed >= self.window * 2 {
+            state.previous = RetryWindow::default();
+            state.current = RetryWindow::default();
+            state.started = Instant::now();
+        } else if elapsed >= self.window {
+            state.previous = state.current;
+            state.current = RetryWindow::default();
+            state.started += self.window;
+        }
+        state
+    }
+}
+
+/// Whether an attempt's status counts as a failure for [`RetryBudget`].
+fn is_overload_status(status: reqwest::StatusCode) -> bool {
+    status == reqwest::StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
 }
 
 /// Robust Retry-After parsing with single assignment and 1h cap.
@@ -181,7 +485,7 @@ fn is_retryable_reqwest_error(e: &reqwest::Error) -> bool {
 
 /// Jittered backoff capped by both config.max_backoff_ms and remaining overall time.
 /// Also safety-caps any single sleep to ≤5 minutes.
-fn jittered_backoff_ms(
+pub(crate) fn jittered_backoff_ms(
     base_ms: u64,
     max_backoff_ms: u64,
     rng: &mut Rng,
@@ -203,7 +507,7 @@ fn jittered_backoff_ms(
 }
 
 /// Next exponential backoff step with both config and safety cap.
-fn next_backoff(current_ms: u64, max_backoff_ms: u64) -> u64 {
+pub(crate) fn next_backoff(current_ms: u64, max_backoff_ms: u64) -> u64 {
     let doubled = current_ms.saturating_mul(2);
     doubled.min(max_backoff_ms).min(300_000) // ≤ 5 minutes
 }
@@ -238,11 +542,54 @@ pub async fn handle_response_json<T: serde::de::DeserializeOwned>(
     operation_name: &str,
 ) -> Result<T> {
     let status = response.status();
-    let status_code = status.as_u16();
     let body = response.text().await?;
+    decode_json_body(status, &body, operation_name)
+}
+
+/// Like [`handle_response_json`], reading at most `max_bytes` of the body
+/// (see `ClientConfig::max_response_bytes`).
+pub async fn handle_response_json_limited<T: serde::de::DeserializeOwned>(
+    response: Response,
+    operation_name: &str,
+    max_bytes: usize,
+) -> Result<T> {
+    let status = response.status();
+    let body = read_body_limited(response, max_bytes).await?;
+    decode_json_body(status, &body, operation_name)
+}
+
+/// Reads a response body chunk by chunk, failing with
+/// `Error::ResponseTooLarge` as soon as it exceeds `max_bytes`.
+pub(crate) async fn read_body_limited(mut response: Response, max_bytes: usize) -> Result<String> {
+    if let Some(length) = response.content_length() {
+        if length > max_bytes as u64 {
+            return Err(Error::ResponseTooLarge(
+                usize::try_from(length).unwrap_or(usize::MAX),
+                max_bytes,
+            ));
+        }
+    }
+
+    let mut body = Vec::new();
+    while let Some(chunk) = response.chunk().await? {
+        if body.len() + chunk.len() > max_bytes {
+            return Err(Error::ResponseTooLarge(body.len() + chunk.len(), max_bytes));
+        }
+        body.extend_from_slice(&chunk);
+    }
+    Ok(String::from_utf8_lossy(&body).into_owned())
+}
+
+/// Decodes a response body read with `status`, with consistent error parsing.
+pub(crate) fn decode_json_body<T: serde::de::DeserializeOwned>(
+    status: reqwest::StatusCode,
+    body: &str,
+    operation_name: &str,
+) -> Result<T> {
+    let status_code = status.as_u16();
 
     if !status.is_success() {
-        let err = Error::from_response_text(status_code, &body);
+        let err = Error::from_response_text(status_code, body);
         return Err(err);
     }
 
@@ -255,17 +602,18 @@ pub async fn handle_response_json<T: serde::de::DeserializeOwned>(
     }
 
     // Decode JSON with a safe error message.
-    serde_json::from_str::<T>(&body).map_err(|e| Error::DeserializationError {
+    serde_json::from_str::<T>(body).map_err(|e| Error::DeserializationError {
         status_code,
         message: crate::utils::security::create_safe_error_message(
             &format!(
                 "Failed to decode JSON response for {}: {}. Body (elided) was: {}",
                 operation_name,
                 e,
-                elide(&body, 2_000)
+                elide(body, 2_000)
             ),
             &format!("{} JSON parsing error", operation_name),
         ),
+        source: Some(e),
     })
 }
 
@@ -387,6 +735,7 @@ mod tests {
             retry_on_status_codes: vec![429, 500, 502, 503, 504],
             total_timeout: Duration::from_millis(200), // Very short timeout
             max_retry_interval: Duration::from_secs(30),
+            ..Default::default()
         };
 
         let client = reqwest::Client::new();
@@ -435,6 +784,7 @@ mod tests {
             retry_on_status_codes: vec![500],
             total_timeout: Duration::from_secs(10), // Generous timeout
             max_retry_interval: Duration::from_secs(30),
+            ..Default::default()
         };
 
         let start_time = std::time::Instant::now();
@@ -501,6 +851,7 @@ mod tests {
             retry_on_status_codes: vec![500],
             total_timeout: Duration::from_secs(5),
             max_retry_interval: Duration::from_secs(30),
+            ..Default::default()
         };
 
         let config = Arc::new(config);
@@ -595,6 +946,7 @@ mod tests {
             retry_on_status_codes: vec![429],
             total_timeout: Duration::from_secs(5),
             max_retry_interval: Duration::from_secs(30),
+            ..Default::default()
         };
 
         let start_time = std::time::Instant::now();
@@ -638,4 +990,136 @@ mod tests {
             elapsed
         );
     }
+
+    #[tokio::test]
+    async fn test_custom_retry_policy_controls_retries() {
+        use std::sync::atomic::{AtomicU32, Ordering};
+        use std::sync::Arc;
+        use wiremock::{matchers, Mock, MockServer, ResponseTemplate};
+
+        /// Retries 418 with a fixed delay and stops after two consecutive
+        /// failures, like a tiny circuit breaker.
+        #[derive(Debug, Default)]
+        struct TeapotPolicy {
+            failures: AtomicU32,
+        }
+
+        impl RetryPolicy for TeapotPolicy {
+            fn should_retry(
+                &self,
+                trigger: RetryTrigger<'_>,
+                context: &RetryContext<'_>,
+            ) -> RetryDecision {
+                assert_eq!(context.operation, "teapot");
+                let failures = self.failures.fetch_add(1, Ordering::SeqCst) + 1;
+                match trigger {
+                    RetryTrigger::Response(r) if r.status().as_u16() == 418 && failures < 2 => {
+                        RetryDecision::RetryAfter(Duration::from_millis(10))
+                    }
+                    _ => RetryDecision::Stop,
+                }
+            }
+        }
+
+        let mock_server = MockServer::start().await;
+        Mock::given(matchers::method("GET"))
+            .respond_with(ResponseTemplate::new(418))
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+
+        let policy = Arc::new(TeapotPolicy::default());
+        let config = RetryConfig {
+            max_retries: 5,
+            policy: Some(policy.clone()),
+            ..Default::default()
+        };
+        let client = reqwest::Client::new();
+        let response =
+            execute_with_retry_builder(&config, "teapot", || client.get(mock_server.uri()))
+                .await
+                .unwrap();
+
+        assert_eq!(response.status().as_u16(), 418);
+        assert_eq!(policy.failures.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_policy_replaces_status_code_list() {
+        use wiremock::{matchers, Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+        Mock::given(matchers::method("GET"))
+            .respond_with(ResponseTemplate::new(503))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        // 503 is in the default status list, but the policy only retries 429.
+        let config = RetryConfig::default().with_policy(StatusCodeRetryPolicy::new(vec![429]));
+        let client = reqwest::Client::new();
+        let response =
+            execute_with_retry_builder(&config, "no_retry", || client.get(mock_server.uri()))
+                .await
+                .unwrap();
+        assert_eq!(response.status().as_u16(), 503);
+    }
+
+    #[tokio::test]
+    async fn test_retry_budget_caps_retries_across_requests() {
+        use std::sync::Arc;
+        use wiremock::{matchers, Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+        Mock::given(matchers::method("GET"))
+            .respond_with(ResponseTemplate::new(503))
+            .mount(&mock_server)
+            .await;
+
+        // Two retries in total for four requests of up to three retries each.
+        let budget = Arc::new(
+            RetryBudget::new(0.0)
+                .with_min_retries(2)
+                .with_window(Duration::from_secs(60)),
+        );
+        let config = RetryConfig {
+            initial_backoff_ms: 1,
+            max_backoff_ms: 5,
+            ..RetryConfig::default()
+        }
+        .with_budget(Arc::clone(&budget));
+        let client = reqwest::Client::new();
+        for _ in 0..4 {
+            let response =
+                execute_with_retry_builder(&config, "budgeted", || client.get(mock_server.uri()))
+                    .await
+                    .unwrap();
+            assert_eq!(response.status().as_u16(), 503);
+        }
+
+        assert_eq!(mock_server.received_requests().await.unwrap().len(), 6);
+        assert_eq!(budget.remaining(), 0);
+        assert_eq!(budget.failure_rate(), 1.0);
+        assert_eq!(budget.backoff_multiplier(), 4.0);
+    }
+
+    #[test]
+    fn test_retry_budget_allows_ratio_of_requests_and_recovers() {
+        let budget = RetryBudget::new(0.2)
+            .with_min_retries(0)
+            .with_window(Duration::from_millis(20));
+        for _ in 0..10 {
+            budget.record_request();
+            budget.record_attempt(false);
+        }
+        assert!(budget.try_retry());
+        assert!(budget.try_retry());
+        assert!(!budget.try_retry());
+        assert_eq!(budget.backoff_multiplier(), 1.0);
+
+        std::thread::sleep(Duration::from_millis(45));
+        assert_eq!(budget.remaining(), 0);
+        budget.record_request();
+        assert_eq!(budget.failure_rate(), 0.0);
+    }
 }
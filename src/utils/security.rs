@@ -121,6 +121,192 @@ pub fn create_safe_error_message(error_content: &str, fallback_message: &str) ->
     }
 }
 
+/// Field names whose values are always masked by a [`Redactor`].
+const SECRET_FIELDS: [&str; 12] = [
+    "api_key",
+    "apikey",
+    "authorization",
+    "password",
+    "secret",
+    "token",
+    "access_token",
+    "refresh_token",
+    "private_key",
+    "credential",
+    "cookie",
+    "session_id",
+];
+
+/// Fields holding user or model text (messages, completion prompts,
+/// embedding input, content parts and reasoning).
+const CONTENT_FIELDS: [&str; 5] = ["content", "prompt", "input", "text", "reasoning"];
+
+/// Masks sensitive fields in request/response JSON before it is logged.
+///
+/// Secret fields (`api_key`, `authorization`, tokens, ...) and API keys
+/// embedded in any string are always masked. Message content, images
+/// (image URLs and `data:` URLs) and tool call arguments are masked by
+/// default and can be kept with the `mask_*` toggles. Masked strings are
+/// replaced with a marker recording their length, so logs still show the
+/// shape of a request.
+///
+/// ```
+/// use openrouter_api::utils::security::Redactor;
+/// use serde_json::json;
+///
+/// let redactor = Redactor::new().with_max_excerpt_len(200);
+/// let body = json!({"model": "openai/gpt-4o", "messages": [{"role": "user", "content": "hi"}]});
+/// let logged = redactor.excerpt(&body);
+/// assert!(logged.contains("openai/gpt-4o"));
+/// assert!(!logged.contains("\"hi\""));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Redactor {
+    fields: Vec<String>,
+    mask_content: bool,
+    mask_images: bool,
+    mask_tool_arguments: bool,
+    max_excerpt_len: usize,
+}
+
+impl Default for Redactor {
+    fn default() -> Self {
+        Self {
+            fields: SECRET_FIELDS
+                .iter()
+                .map(|field| field.to_string())
+                .collect(),
+            mask_content: true,
+            mask_images: true,
+            mask_tool_arguments: true,
+            max_excerpt_len: 1000,
+        }
+    }
+}
+
+impl Redactor {
+    /// Creates a redactor masking secrets, content, images and tool arguments.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Additionally masks every field with this name (case-insensitive).
+    pub fn with_field(mut self, name: impl Into<String>) -> Self {
+        self.fields.push(name.into().to_ascii_lowercase());
+        self
+    }
+
+    /// Whether message content, prompts and embedding input are masked.
+    pub fn mask_message_content(mut self, mask: bool) -> Self {
+        self.mask_content = mask;
+        self
+    }
+
+    /// Whether image URLs and `data:` URLs are masked.
+    pub fn mask_images(mut self, mask: bool) -> Self {
+        self.mask_images = mask;
+        self
+    }
+
+    /// Whether tool call arguments are masked.
+    pub fn mask_tool_arguments(mut self, mask: bool) -> Self {
+        self.mask_tool_arguments = mask;
+        self
+    }
+
+    /// Sets the maximum length in bytes of [`Redactor::excerpt`] output.
+    pub fn with_max_excerpt_len(mut self, max_len: usize) -> Self {
+        self.max_excerpt_len = max_len;
+        self
+    }
+
+    /// Returns a copy of `value` with the configured fields masked.
+    pub fn redact(&self, value: &serde_json::Value) -> serde_json::Value {
+        let mut value = value.clone();
+        self.redact_in_place(&mut value);
+        value
+    }
+
+    /// Masks the configured fields of `value` in place.
+    pub fn redact_in_place(&self, value: &mut serde_json::Value) {
+        self.walk(value, None);
+    }
+
+    /// Redacts `value` and serializes it, truncated to the excerpt length.
+    pub fn excerpt(&self, value: &serde_json::Value) -> String {
+        truncate_excerpt(self.redact(value).to_string(), self.max_excerpt_len)
+    }
+
+    /// Like [`Redactor::excerpt`] for a raw body. Bodies that are not JSON
+    /// fall back to [`redact_sensitive_content`].
+    pub fn excerpt_str(&self, body: &str) -> String {
+        match serde_json::from_str::<serde_json::Value>(body) {
+            Ok(value) => self.excerpt(&value),
+            Err(_) => truncate_excerpt(redact_sensitive_content(body), self.max_excerpt_len),
+        }
+    }
+
+    fn walk(&self, value: &mut serde_json::Value, key: Option<&str>) {
+        use serde_json::Value;
+
+        let key = key.map(str::to_ascii_lowercase);
+        let key = key.as_deref();
+        if key.is_some_and(|key| self.fields.iter().any(|field| field == key)) {
+            *value = Value::String("***REDACTED***".into());
+            return;
+        }
+        let masked = match key {
+            Some(key) if CONTENT_FIELDS.contains(&key) => self.mask_content,
+            Some("arguments") => self.mask_tool_arguments,
+            Some("image_url") => self.mask_images,
+            _ => false,
+        };
+
+        match value {
+            Value::String(text) if masked => *text = format!("[REDACTED {} chars]", text.len()),
+            Value::String(text) if self.mask_images && text.starts_with("data:") => {
+                *text = format!("[REDACTED data URL, {} bytes]", text.len());
+            }
+            Value::String(text) => {
+                if API_KEY_REGEX.is_match(text) {
+                    *text = API_KEY_REGEX
+                        .replace_all(text, "***REDACTED***")
+                        .into_owned();
+                }
+            }
+            // `{"image_url": {"url": ..., "detail": ...}}`
+            Value::Object(map) if masked && key == Some("image_url") => {
+                if let Some(Value::String(url)) = map.get_mut("url") {
+                    *url = format!("[REDACTED {} chars]", url.len());
+                }
+            }
+            Value::Array(items) => {
+                for item in items {
+                    self.walk(item, key);
+                }
+            }
+            Value::Object(map) => {
+                for (field, item) in map.iter_mut() {
+                    self.walk(item, Some(field));
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Cuts `text` to at most `max_len` bytes on a char boundary.
+fn truncate_excerpt(text: String, max_len: usize) -> String {
+    if text.len() <= max_len {
+        return text;
+    }
+    let mut end = max_len;
+    while end > 0 && !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}...[truncated {} bytes]", &text[..end], text.len() - end)
+}
+
 #[cfg(test)]
 pub mod security_tests {
     use super::*;
@@ -205,4 +391,85 @@ pub mod security_tests {
         // Verify it starts with 1000 'A's (the threshold matches the cut point)
         assert!(redacted.starts_with(&"A".repeat(1000)));
     }
+
+    fn chat_body() -> serde_json::Value {
+        serde_json::json!({
+            "model": "openai/gpt-4o",
+            "api_key": "whatever",
+            "messages": [
+                {"role": "system", "content": "You are helpful"},
+                {"role": "user", "content": [
+                    {"type": "text", "text": "Describe this"},
+                    {"type": "image_url", "image_url": {"url": "data:image/png;base64,AAAA"}}
+                ]},
+                {"role": "assistant", "content": null, "tool_calls": [{
+                    "id": "call_1",
+                    "type": "function",
+                    "function": {"name": "lookup", "arguments": "{\"ssn\":\"123\"}"}
+                }]}
+            ],
+            "metadata": {"note": "key sk-or-v1-abcdefghijkl0123"}
+        })
+    }
+
+    #[test]
+    pub fn test_redactor_masks_content_images_and_tool_arguments() {
+        let redacted = Redactor::new().redact(&chat_body());
+        let messages = &redacted["messages"];
+
+        assert_eq!(redacted["model"], "openai/gpt-4o");
+        assert_eq!(redacted["api_key"], "***REDACTED***");
+        assert_eq!(messages[0]["role"], "system");
+        assert_eq!(messages[0]["content"], "[REDACTED 15 chars]");
+        assert_eq!(messages[1]["content"][0]["type"], "text");
+        assert_eq!(messages[1]["content"][0]["text"], "[REDACTED 13 chars]");
+        assert_eq!(
+            messages[1]["content"][1]["image_url"]["url"],
+            "[REDACTED 26 chars]"
+        );
+        assert!(messages[2]["content"].is_null());
+        let function = &messages[2]["tool_calls"][0]["function"];
+        assert_eq!(function["name"], "lookup");
+        assert_eq!(function["arguments"], "[REDACTED 13 chars]");
+        assert_eq!(redacted["metadata"]["note"], "key ***REDACTED***");
+    }
+
+    #[test]
+    pub fn test_redactor_toggles_and_custom_fields() {
+        let redactor = Redactor::new()
+            .mask_message_content(false)
+            .mask_tool_arguments(false)
+            .with_field("Model");
+        let redacted = redactor.redact(&chat_body());
+
+        assert_eq!(redacted["model"], "***REDACTED***");
+        assert_eq!(redacted["messages"][0]["content"], "You are helpful");
+        assert_eq!(
+            redacted["messages"][2]["tool_calls"][0]["function"]["arguments"],
+            "{\"ssn\":\"123\"}"
+        );
+        // Images are still masked, including bare data URLs.
+        assert_eq!(
+            redacted["messages"][1]["content"][1]["image_url"]["url"],
+            "[REDACTED 26 chars]"
+        );
+        let data_url =
+            redactor.redact(&serde_json::json!({"file": "data:application/pdf;base64,AA"}));
+        assert_eq!(data_url["file"], "[REDACTED data URL, 30 bytes]");
+    }
+
+    #[test]
+    pub fn test_redactor_excerpt_is_size_bounded() {
+        let redactor = Redactor::new()
+            .mask_message_content(false)
+            .with_max_excerpt_len(40);
+        let body = serde_json::json!({"content": "é".repeat(100)});
+
+        let excerpt = redactor.excerpt(&body);
+        assert!(excerpt.contains("...[truncated"));
+        assert!(excerpt.len() < 80);
+
+        let plain = redactor.excerpt_str("not json, key sk-or-v1-abcdefghijkl0123");
+        assert_eq!(plain, "not json, key ***REDACTED***");
+    }
 }
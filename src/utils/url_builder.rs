@@ -1,19 +1,37 @@
 use crate::error::{Error, Result};
+use serde::Serialize;
+use serde_json::Value;
 use url::Url;
 
 /// Utility for building API URLs with consistent path handling
+///
+/// Paths are resolved below the base URL, path parameters are percent-encoded
+/// as single segments and query parameters are encoded from any serializable
+/// value, so API modules never format URLs by hand.
+#[derive(Debug, Clone)]
 pub struct UrlBuilder {
     base_url: Url,
 }
 
 impl UrlBuilder {
     /// Creates a new UrlBuilder with the given base URL
-    pub fn new(base_url: Url) -> Self {
+    ///
+    /// A base URL without a trailing '/' gets one, so that paths are appended
+    /// to its last segment rather than replacing it.
+    pub fn new(mut base_url: Url) -> Self {
+        if !base_url.path().ends_with('/') {
+            let path = format!("{}/", base_url.path());
+            base_url.set_path(&path);
+        }
         Self { base_url }
     }
 
+    /// The normalized base URL
+    pub fn base_url(&self) -> &Url {
+        &self.base_url
+    }
+
     /// Builds a URL by appending the given path to the base URL
-    /// Handles base URLs that may or may not end with '/'
     pub fn build(&self, path: &str) -> Result<Url> {
         // Ensure path doesn't start with '/' to avoid double slashes
         let clean_path = path.trim_start_matches('/');
@@ -25,12 +43,105 @@ impl UrlBuilder {
         })
     }
 
+    /// Builds a URL from a path template, see [`expand`](Self::expand)
+    pub fn build_path(&self, template: &str, params: &[(&str, &str)]) -> Result<Url> {
+        self.build(&Self::expand(template, params)?)
+    }
+
     /// Builds a URL with query parameters
-    pub fn build_with_query(&self, path: &str) -> Result<Url> {
-        self.build(path)
+    ///
+    /// `query` is a struct, map or list of pairs; `None` fields are omitted
+    /// and sequences become repeated keys.
+    pub fn build_with_query<Q: Serialize + ?Sized>(&self, path: &str, query: &Q) -> Result<Url> {
+        let mut url = self.build(path)?;
+        let pairs = query_pairs(query)?;
+        if !pairs.is_empty() {
+            url.query_pairs_mut().extend_pairs(pairs);
+        }
+        Ok(url)
+    }
+
+    /// Substitutes each `{name}` segment of `template` with the
+    /// percent-encoded value of `name`, so a value can never add segments.
+    ///
+    /// Empty values and the relative segments `.` and `..` are rejected.
+    pub fn expand(template: &str, params: &[(&str, &str)]) -> Result<String> {
+        let segments = template
+            .split('/')
+            .map(|segment| {
+                let Some(name) = segment
+                    .strip_prefix('{')
+                    .and_then(|rest| rest.strip_suffix('}'))
+                else {
+                    return Ok(segment.to_string());
+                };
+                let value = params
+                    .iter()
+                    .find(|(param, _)| *param == name)
+                    .map(|(_, value)| *value)
+                    .ok_or_else(|| {
+                        Error::ValidationError(format!(
+                            "Missing path parameter '{name}' for '{template}'"
+                        ))
+                    })?;
+                if value.is_empty() || value == "." || value == ".." {
+                    return Err(Error::ValidationError(format!(
+                        "Invalid value '{value}' for path parameter '{name}'"
+                    )));
+                }
+                Ok(urlencoding::encode(value).into_owned())
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(segments.join("/"))
     }
 }
 
+/// Flattens `query` into key/value pairs.
+fn query_pairs<Q: Serialize + ?Sized>(query: &Q) -> Result<Vec<(String, String)>> {
+    let invalid = |detail: &str| Error::ApiError {
+        code: 400,
+        message: format!("Invalid query parameters: {detail}"),
+        metadata: None,
+    };
+    let entries: Vec<(String, Value)> =
+        match serde_json::to_value(query).map_err(Error::SerializationError)? {
+            Value::Null => Vec::new(),
+            Value::Object(map) => map.into_iter().collect(),
+            Value::Array(items) => items
+                .into_iter()
+                .map(|item| match item {
+                    Value::Array(pair) if pair.len() == 2 => match &pair[0] {
+                        Value::String(key) => Ok((key.clone(), pair[1].clone())),
+                        _ => Err(invalid("keys must be strings")),
+                    },
+                    _ => Err(invalid("expected key/value pairs")),
+                })
+                .collect::<Result<_>>()?,
+            _ => return Err(invalid("expected a struct, map or list of pairs")),
+        };
+
+    let mut pairs = Vec::new();
+    for (key, value) in entries {
+        let values = match value {
+            Value::Array(values) => values,
+            value => vec![value],
+        };
+        for value in values {
+            let value = match value {
+                Value::Null => continue,
+                Value::String(value) => value,
+                Value::Bool(value) => value.to_string(),
+                Value::Number(value) => value.to_string(),
+                Value::Array(_) | Value::Object(_) => {
+                    return Err(invalid(&format!("'{key}' is not a scalar")))
+                }
+            };
+            pairs.push((key.clone(), value));
+        }
+    }
+    Ok(pairs)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -50,8 +161,8 @@ mod tests {
         let builder = UrlBuilder::new(base_url);
 
         let url = builder.build("activity").unwrap();
-        // When base URL doesn't end with '/', join replaces the last segment
-        assert_eq!(url.as_str(), "https://api.example.com/activity");
+        // The base URL is normalized, so the last segment is kept
+        assert_eq!(url.as_str(), "https://api.example.com/v1/activity");
     }
 
     #[test]
@@ -73,4 +184,78 @@ mod tests {
         // The result should still be a valid URL, just might not be what we expect
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_url_builder_path_templates() {
+        let builder = UrlBuilder::new(Url::parse("https://api.example.com/v1").unwrap());
+
+        let url = builder
+            .build_path(
+                "models/{author}/{slug}/endpoints",
+                &[("author", "openai"), ("slug", "gpt-4o:free")],
+            )
+            .unwrap();
+        assert_eq!(
+            url.as_str(),
+            "https://api.example.com/v1/models/openai/gpt-4o%3Afree/endpoints"
+        );
+
+        let url = builder
+            .build_path("guardrails/{id}", &[("id", "../keys")])
+            .unwrap();
+        assert_eq!(
+            url.as_str(),
+            "https://api.example.com/v1/guardrails/..%2Fkeys"
+        );
+
+        for value in ["", ".", ".."] {
+            assert!(matches!(
+                UrlBuilder::expand("guardrails/{id}", &[("id", value)]),
+                Err(Error::ValidationError(_))
+            ));
+        }
+        assert!(UrlBuilder::expand("guardrails/{id}", &[]).is_err());
+    }
+
+    #[test]
+    fn test_url_builder_query_parameters() {
+        #[derive(Serialize)]
+        struct Query {
+            limit: u32,
+            model: Option<&'static str>,
+            provider: Option<&'static str>,
+            tags: Vec<&'static str>,
+        }
+
+        let builder = UrlBuilder::new(Url::parse("https://api.example.com/v1/").unwrap());
+        let url = builder
+            .build_with_query(
+                "activity",
+                &Query {
+                    limit: 10,
+                    model: Some("a b&c"),
+                    provider: None,
+                    tags: vec!["x", "y"],
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            url.as_str(),
+            "https://api.example.com/v1/activity?limit=10&model=a+b%26c&tags=x&tags=y"
+        );
+
+        let url = builder
+            .build_with_query("generation", &[("id", "gen-1")])
+            .unwrap();
+        assert_eq!(url.query(), Some("id=gen-1"));
+
+        let url = builder
+            .build_with_query("models", &Option::<Query>::None)
+            .unwrap();
+        assert_eq!(url.query(), None);
+
+        assert!(builder
+            .build_with_query("models", &serde_json::json!({"nested": {"a": 1}}))
+            .is_err());
+    }
 }
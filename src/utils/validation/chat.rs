@@ -1,139 +1,392 @@
 //! Validation utilities for chat completion requests
 
 use crate::error::{Error, Result};
-use crate::models::tool::Tool;
-
-use crate::types::chat::{ChatCompletionRequest, ContentPart, Message, MessageContent};
+use crate::models::tool::{Tool, ToolChoice};
+
+use crate::types::chat::{
+    ChatCompletionRequest, ChatRole, ContentPart, Message, MessageContent, StopSequence,
+    LOGIT_BIAS_MAX, LOGIT_BIAS_MIN, MAX_STOP_SEQUENCES, MAX_STOP_SEQUENCE_LENGTH,
+};
+use crate::utils::validation::common::{
+    validate_collection_size, validate_string_length, ValidationReport,
+};
 use std::collections::HashSet;
 
 /// Maximum allowed tokens in a chat completion request
 const MAX_TOKENS: u32 = 1_000_000;
 
+/// Maximum number of choices per request (`n`)
+const MAX_CHOICES: u32 = 128;
+
+/// How requests with more than one system message are handled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SystemMessagePolicy {
+    /// Send any number of system messages.
+    #[default]
+    Allow,
+    /// Merge all system messages into the first one before sending.
+    Merge,
+    /// Reject requests with more than one system message.
+    Reject,
+}
+
+/// Client-wide rules applied on top of the standard request validation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ValidationPolicy {
+    pub system_messages: SystemMessagePolicy,
+}
+
+impl ValidationPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_system_messages(mut self, policy: SystemMessagePolicy) -> Self {
+        self.system_messages = policy;
+        self
+    }
+}
+
 /// Validates a chat completion request for common errors.
 pub fn validate_chat_request(request: &ChatCompletionRequest) -> Result<()> {
+    validate_chat_request_with_policy(request, &ValidationPolicy::default())
+}
+
+/// Repairs what `policy` allows to be repaired, such as merging system
+/// messages under `SystemMessagePolicy::Merge`.
+pub fn repair_chat_request(request: &mut ChatCompletionRequest, policy: &ValidationPolicy) {
+    if policy.system_messages == SystemMessagePolicy::Merge {
+        merge_system_messages(&mut request.messages);
+    }
+}
+
+/// Merges every system message into the first one, joining their text
+/// with blank lines. Returns the number of messages removed.
+pub fn merge_system_messages(messages: &mut Vec<Message>) -> usize {
+    let Some(first) = messages.iter().position(|m| m.role == ChatRole::System) else {
+        return 0;
+    };
+    let before = messages.len();
+    let mut texts = Vec::new();
+    let mut index = 0;
+    messages.retain(|message| {
+        let keep = message.role != ChatRole::System || index == first;
+        if message.role == ChatRole::System {
+            texts.push(message.content.to_text());
+        }
+        index += 1;
+        keep
+    });
+    let removed = before - messages.len();
+    if removed > 0 {
+        messages[first].content = MessageContent::Text(
+            texts
+                .into_iter()
+                .filter(|text| !text.is_empty())
+                .collect::<Vec<_>>()
+                .join("\n\n"),
+        );
+    }
+    removed
+}
+
+/// Validates a chat completion request under a client's `ValidationPolicy`.
+///
+/// A request that `repair_chat_request` would change is an error here, so
+/// repair before validating.
+pub fn validate_chat_request_with_policy(
+    request: &ChatCompletionRequest,
+    policy: &ValidationPolicy,
+) -> Result<()> {
+    validate_chat_request_all_with_policy(request, policy).map_err(ValidationReport::into_error)
+}
+
+/// Validates a chat completion request, reporting every problem instead of
+/// only the first.
+pub fn validate_chat_request_all(
+    request: &ChatCompletionRequest,
+) -> std::result::Result<(), ValidationReport> {
+    validate_chat_request_all_with_policy(request, &ValidationPolicy::default())
+}
+
+/// Like `validate_chat_request_all`, under a client's `ValidationPolicy`.
+pub fn validate_chat_request_all_with_policy(
+    request: &ChatCompletionRequest,
+    policy: &ValidationPolicy,
+) -> std::result::Result<(), ValidationReport> {
+    let mut report = ValidationReport::default();
+
     // Validate model is not empty
     if request.model.trim().is_empty() {
-        return Err(Error::ConfigError("Model ID cannot be empty".into()));
+        report.push("model", "empty", "Model ID cannot be empty");
     }
 
     // Validate messages are present
     if request.messages.is_empty() {
-        return Err(Error::ConfigError("Messages array cannot be empty".into()));
+        report.push("messages", "empty", "Messages array cannot be empty");
     }
 
     // Validate sampling parameters
-    validate_sampling_parameters(request)?;
+    check_sampling_parameters(request, &mut report);
 
     // Validate message roles
     for (i, msg) in request.messages.iter().enumerate() {
-        validate_message(msg, i)?;
+        check_message(msg, i, &mut report);
     }
 
     // Validate tools if present
     if let Some(tools) = &request.tools {
-        validate_tools(tools)?;
+        check_tools(tools, &mut report);
+    }
+
+    if let Some(ToolChoice::Function(name)) = &request.tool_choice {
+        let declared = request.tools.iter().flatten().any(|tool| match tool {
+            Tool::Function { function } => function.name == *name,
+        });
+        if !declared {
+            report.push(
+                "tool_choice",
+                "unknown_function",
+                format!("tool_choice names function '{name}', which is not in tools"),
+            );
+        }
     }
 
+    if let Some(stop) = &request.stop {
+        check_stop_sequences(stop, &mut report);
+    }
+
+    if let Some(extra_body) = request
+        .extra_body
+        .as_ref()
+        .filter(|extra| !extra.is_empty())
+    {
+        check_extra_body(request, extra_body, &mut report);
+    }
+
+    if policy.system_messages != SystemMessagePolicy::Allow {
+        let count = request
+            .messages
+            .iter()
+            .filter(|message| message.role == ChatRole::System)
+            .count();
+        if count > 1 {
+            report.push(
+                "messages",
+                "too_many_system_messages",
+                format!(
+                    "Request has {} system messages but the validation policy allows only one",
+                    count
+                ),
+            );
+        }
+    }
+
+    report.into_result()
+}
+
+/// Model ID prefixes of providers that continue a trailing assistant message
+/// instead of answering after it.
+const ASSISTANT_PREFILL_MODELS: &[&str] = &[
+    "anthropic/",
+    "deepseek/",
+    "mistralai/",
+    "meta-llama/",
+    "qwen/",
+];
+
+/// Returns true if the model is known to continue a partial assistant
+/// message (assistant prefill).
+pub fn supports_assistant_prefill(model: &str) -> bool {
+    let model = model.to_ascii_lowercase();
+    ASSISTANT_PREFILL_MODELS
+        .iter()
+        .any(|prefix| model.starts_with(prefix))
+}
+
+/// Validates an assistant prefill for the target model.
+///
+/// The prefill must be non-empty and must not end with whitespace, which
+/// Anthropic rejects.
+pub fn validate_assistant_prefill(model: &str, prefill: &str) -> Result<()> {
+    if !supports_assistant_prefill(model) {
+        return Err(Error::ConfigError(format!(
+            "Model '{}' does not support assistant prefill",
+            model
+        )));
+    }
+    if prefill.is_empty() {
+        return Err(Error::ConfigError(
+            "Assistant prefill cannot be empty".into(),
+        ));
+    }
+    if prefill.ends_with(char::is_whitespace) {
+        return Err(Error::ConfigError(
+            "Assistant prefill cannot end with whitespace".into(),
+        ));
+    }
     Ok(())
 }
 
-/// Validates sampling parameters for valid ranges.
-fn validate_sampling_parameters(request: &ChatCompletionRequest) -> Result<()> {
+/// Validates stop sequences against provider limits: at most
+/// `MAX_STOP_SEQUENCES`, each non-empty and at most
+/// `MAX_STOP_SEQUENCE_LENGTH` bytes.
+pub fn validate_stop_sequences(stop: &StopSequence) -> Result<()> {
+    let mut report = ValidationReport::default();
+    check_stop_sequences(stop, &mut report);
+    report.into_result().map_err(ValidationReport::into_error)
+}
+
+fn check_stop_sequences(stop: &StopSequence, report: &mut ValidationReport) {
+    let sequences = stop.sequences();
+    if let Err(e) = validate_collection_size(sequences, "stop", 1, MAX_STOP_SEQUENCES) {
+        report.push_error("stop", "invalid_length", e);
+    }
+    for (index, sequence) in sequences.iter().enumerate() {
+        let field = format!("stop[{index}]");
+        if let Err(e) = validate_string_length(sequence, &field, 1, MAX_STOP_SEQUENCE_LENGTH) {
+            report.push_error(field, "invalid_length", e);
+        }
+    }
+}
+
+/// Checks sampling parameters for valid ranges.
+fn check_sampling_parameters(request: &ChatCompletionRequest, report: &mut ValidationReport) {
     // Temperature: [0.0, 2.0]
     if let Some(temp) = request.temperature {
         if !(0.0..=2.0).contains(&temp) {
-            return Err(Error::ConfigError(format!(
-                "Temperature must be between 0.0 and 2.0, got {}",
-                temp
-            )));
+            report.push(
+                "temperature",
+                "out_of_range",
+                format!("Temperature must be between 0.0 and 2.0, got {}", temp),
+            );
         }
     }
 
     // Top P: (0.0, 1.0]
     if let Some(top_p) = request.top_p {
         if top_p <= 0.0 || top_p > 1.0 {
-            return Err(Error::ConfigError(format!(
-                "Top P must be between 0.0 (exclusive) and 1.0 (inclusive), got {}",
-                top_p
-            )));
+            report.push(
+                "top_p",
+                "out_of_range",
+                format!(
+                    "Top P must be between 0.0 (exclusive) and 1.0 (inclusive), got {}",
+                    top_p
+                ),
+            );
         }
     }
 
     // Top K: [1, ∞) or 0 (disabled)
     if let Some(top_k) = request.top_k {
         if top_k != 0 && top_k < 1 {
-            return Err(Error::ConfigError(format!(
-                "Top K must be 0 (disabled) or >= 1, got {}",
-                top_k
-            )));
+            report.push(
+                "top_k",
+                "out_of_range",
+                format!("Top K must be 0 (disabled) or >= 1, got {}", top_k),
+            );
         }
     }
 
     // Frequency Penalty: [-2.0, 2.0]
     if let Some(fp) = request.frequency_penalty {
         if !(-2.0..=2.0).contains(&fp) {
-            return Err(Error::ConfigError(format!(
-                "Frequency penalty must be between -2.0 and 2.0, got {}",
-                fp
-            )));
+            report.push(
+                "frequency_penalty",
+                "out_of_range",
+                format!("Frequency penalty must be between -2.0 and 2.0, got {}", fp),
+            );
         }
     }
 
     // Presence Penalty: [-2.0, 2.0]
     if let Some(pp) = request.presence_penalty {
         if !(-2.0..=2.0).contains(&pp) {
-            return Err(Error::ConfigError(format!(
-                "Presence penalty must be between -2.0 and 2.0, got {}",
-                pp
-            )));
+            report.push(
+                "presence_penalty",
+                "out_of_range",
+                format!("Presence penalty must be between -2.0 and 2.0, got {}", pp),
+            );
         }
     }
 
     // Repetition Penalty: (0.0, 2.0]
     if let Some(rp) = request.repetition_penalty {
         if rp <= 0.0 || rp > 2.0 {
-            return Err(Error::ConfigError(format!(
-                "Repetition penalty must be between 0.0 (exclusive) and 2.0 (inclusive), got {}",
-                rp
-            )));
+            report.push(
+                "repetition_penalty",
+                "out_of_range",
+                format!(
+                    "Repetition penalty must be between 0.0 (exclusive) and 2.0 (inclusive), got {}",
+                    rp
+                ),
+            );
         }
     }
 
     // Min P: [0.0, 1.0]
     if let Some(min_p) = request.min_p {
         if !(0.0..=1.0).contains(&min_p) {
-            return Err(Error::ConfigError(format!(
-                "Min P must be between 0.0 and 1.0, got {}",
-                min_p
-            )));
+            report.push(
+                "min_p",
+                "out_of_range",
+                format!("Min P must be between 0.0 and 1.0, got {}", min_p),
+            );
         }
     }
 
     // Top A: [0.0, 1.0]
     if let Some(top_a) = request.top_a {
         if !(0.0..=1.0).contains(&top_a) {
-            return Err(Error::ConfigError(format!(
-                "Top A must be between 0.0 and 1.0, got {}",
-                top_a
-            )));
+            report.push(
+                "top_a",
+                "out_of_range",
+                format!("Top A must be between 0.0 and 1.0, got {}", top_a),
+            );
+        }
+    }
+
+    // N: [1, 128]
+    if let Some(n) = request.n {
+        if !(1..=MAX_CHOICES).contains(&n) {
+            report.push(
+                "n",
+                "out_of_range",
+                format!("N must be between 1 and {}, got {}", MAX_CHOICES, n),
+            );
         }
     }
 
     // Top Logprobs: [0, 20]
     if let Some(tlp) = request.top_logprobs {
         if tlp > 20 {
-            return Err(Error::ConfigError(format!(
-                "Top logprobs must be <= 20, got {}",
-                tlp
-            )));
+            report.push(
+                "top_logprobs",
+                "out_of_range",
+                format!("Top logprobs must be <= 20, got {}", tlp),
+            );
         }
     }
 
-    Ok(())
+    // Logit bias: [-100, 100] per token
+    if let Some(logit_bias) = &request.logit_bias {
+        for (token, bias) in logit_bias.iter() {
+            if !(LOGIT_BIAS_MIN..=LOGIT_BIAS_MAX).contains(&bias) {
+                report.push(
+                    format!("logit_bias.{token}"),
+                    "out_of_range",
+                    format!(
+                        "Logit bias for token {} must be between {} and {}, got {}",
+                        token, LOGIT_BIAS_MIN, LOGIT_BIAS_MAX, bias
+                    ),
+                );
+            }
+        }
+    }
 }
 
-/// Validates a single message for errors.
-fn validate_message(message: &Message, index: usize) -> Result<()> {
+/// Checks a single message for errors.
+fn check_message(message: &Message, index: usize, report: &mut ValidationReport) {
     // Role validation - ChatRole enum ensures only valid values
     match message.role {
         crate::types::chat::ChatRole::User
@@ -143,36 +396,51 @@ fn validate_message(message: &Message, index: usize) -> Result<()> {
     }
 
     // Content validation based on role
-    validate_message_content(message, index)?;
+    check_message_content(message, index, report);
 
     // Tool calls validation for assistant messages
     if let Some(tool_calls) = &message.tool_calls {
         if message.role != crate::types::chat::ChatRole::Assistant {
-            return Err(Error::ConfigError(format!(
-                "Message at index {} has tool_calls but role is '{}', not 'assistant'",
-                index, message.role
-            )));
+            report.push(
+                format!("messages[{index}].tool_calls"),
+                "invalid_role",
+                format!(
+                    "Message at index {} has tool_calls but role is '{}', not 'assistant'",
+                    index, message.role
+                ),
+            );
         }
 
         // Validate each tool call
         for (tc_idx, tc) in tool_calls.iter().enumerate() {
+            let field = format!("messages[{index}].tool_calls[{tc_idx}]");
             if tc.id.is_empty() {
-                return Err(Error::ConfigError(format!(
-                    "Tool call {tc_idx} at message {index} has empty id"
-                )));
+                report.push(
+                    format!("{field}.id"),
+                    "empty",
+                    format!("Tool call {tc_idx} at message {index} has empty id"),
+                );
             }
 
             if tc.kind != crate::models::tool::ToolType::Function {
-                return Err(Error::ConfigError(format!(
-                    "Tool call {} at message {} has invalid type: '{}'. Must be 'function'",
-                    tc_idx, index, tc.kind
-                )));
+                report.push(
+                    format!("{field}.type"),
+                    "invalid_type",
+                    format!(
+                        "Tool call {} at message {} has invalid type: '{}'. Must be 'function'",
+                        tc_idx, index, tc.kind
+                    ),
+                );
             }
 
             if tc.function_call.name.trim().is_empty() {
-                return Err(Error::ConfigError(format!(
-                    "Function name in tool call {tc_idx} at message {index} cannot be empty"
-                )));
+                report.push(
+                    format!("{field}.function.name"),
+                    "empty",
+                    format!(
+                        "Function name in tool call {tc_idx} at message {index} cannot be empty"
+                    ),
+                );
             }
         }
     }
@@ -181,17 +449,20 @@ fn validate_message(message: &Message, index: usize) -> Result<()> {
     if message.role == crate::types::chat::ChatRole::Tool
         && (message.tool_call_id.is_none() || message.tool_call_id.as_ref().unwrap().is_empty())
     {
-        return Err(Error::ConfigError(format!(
-            "Tool message at index {} must have a non-empty tool_call_id",
-            index
-        )));
+        report.push(
+            format!("messages[{index}].tool_call_id"),
+            "empty",
+            format!(
+                "Tool message at index {} must have a non-empty tool_call_id",
+                index
+            ),
+        );
     }
-
-    Ok(())
 }
 
-/// Validates message content based on role and content type.
-fn validate_message_content(message: &Message, index: usize) -> Result<()> {
+/// Checks message content based on role and content type.
+fn check_message_content(message: &Message, index: usize, report: &mut ValidationReport) {
+    let field = format!("messages[{index}].content");
     match &message.content {
         MessageContent::Text(text) => {
             // For tool messages, content can be empty (some providers allow empty results)
@@ -199,97 +470,134 @@ fn validate_message_content(message: &Message, index: usize) -> Result<()> {
                 && text.trim().is_empty()
                 && message.tool_calls.is_none()
             {
-                return Err(Error::ConfigError(format!(
-                    "Message at index {} must have either non-empty content or tool_calls",
-                    index
-                )));
+                report.push(
+                    field,
+                    "empty",
+                    format!(
+                        "Message at index {} must have either non-empty content or tool_calls",
+                        index
+                    ),
+                );
             }
         }
         MessageContent::Parts(parts) => {
-            // Multimodal content is only allowed for user messages
-            if message.role != crate::types::chat::ChatRole::User {
-                return Err(Error::ConfigError(format!(
-                    "Multimodal content (ContentParts) is only allowed for user messages, got role '{}' at index {}",
-                    message.role, index
-                )));
+            // Multimodal content is only allowed for user messages and tool
+            // results
+            if !matches!(
+                message.role,
+                crate::types::chat::ChatRole::User | crate::types::chat::ChatRole::Tool
+            ) {
+                report.push(
+                    field.clone(),
+                    "invalid_role",
+                    format!(
+                        "Multimodal content (ContentParts) is only allowed for user and tool messages, got role '{}' at index {}",
+                        message.role, index
+                    ),
+                );
             }
 
             // Validate each content part
             if parts.is_empty() {
-                return Err(Error::ConfigError(format!(
-                    "Content parts array cannot be empty for message at index {}",
-                    index
-                )));
+                report.push(
+                    field.clone(),
+                    "empty",
+                    format!(
+                        "Content parts array cannot be empty for message at index {}",
+                        index
+                    ),
+                );
             }
 
             for (part_idx, part) in parts.iter().enumerate() {
-                validate_content_part(part, index, part_idx)?;
+                check_content_part(
+                    part,
+                    &format!("{field}[{part_idx}]"),
+                    index,
+                    part_idx,
+                    report,
+                );
             }
         }
     }
-
-    Ok(())
 }
 
-/// Validates a single content part.
-fn validate_content_part(part: &ContentPart, msg_index: usize, part_index: usize) -> Result<()> {
+/// Checks a single content part.
+fn check_content_part(
+    part: &ContentPart,
+    field: &str,
+    msg_index: usize,
+    part_index: usize,
+    report: &mut ValidationReport,
+) {
     match part {
         ContentPart::Text(text_content) => {
             if text_content.text.trim().is_empty() {
-                return Err(Error::ConfigError(format!(
-                    "Text content part {} at message {} cannot be empty",
-                    part_index, msg_index
-                )));
+                report.push(
+                    format!("{field}.text"),
+                    "empty",
+                    format!(
+                        "Text content part {} at message {} cannot be empty",
+                        part_index, msg_index
+                    ),
+                );
             }
         }
         ContentPart::Image(image_content) => {
-            // Validate image URL
-            if image_content.image_url.url.trim().is_empty() {
-                return Err(Error::ConfigError(format!(
-                    "Image URL cannot be empty for image part {} at message {}",
-                    part_index, msg_index
-                )));
-            }
-
-            // Basic URL validation - should start with http://, https://, or data:image/
             let url = &image_content.image_url.url;
-            if !(url.starts_with("http://")
+            // Basic URL validation - should start with http://, https://, or data:image/
+            if url.trim().is_empty() {
+                report.push(
+                    format!("{field}.image_url.url"),
+                    "empty",
+                    format!(
+                        "Image URL cannot be empty for image part {} at message {}",
+                        part_index, msg_index
+                    ),
+                );
+            } else if !(url.starts_with("http://")
                 || url.starts_with("https://")
                 || url.starts_with("data:image/"))
             {
-                return Err(Error::ConfigError(format!(
-                    "Image URL must be a valid HTTP(S) URL or base64 data URI for image part {} at message {}",
-                    part_index, msg_index
-                )));
+                report.push(
+                    format!("{field}.image_url.url"),
+                    "invalid_url",
+                    format!(
+                        "Image URL must be a valid HTTP(S) URL or base64 data URI for image part {} at message {}",
+                        part_index, msg_index
+                    ),
+                );
             }
         }
         ContentPart::Audio(audio_content) => {
             if audio_content.audio_url.url.trim().is_empty() {
-                return Err(Error::ConfigError(format!(
-                    "Audio URL cannot be empty for audio part {} at message {}",
-                    part_index, msg_index
-                )));
+                report.push(
+                    format!("{field}.audio_url.url"),
+                    "empty",
+                    format!(
+                        "Audio URL cannot be empty for audio part {} at message {}",
+                        part_index, msg_index
+                    ),
+                );
             }
         }
         ContentPart::File(file_content) => {
             if file_content.file_url.url.trim().is_empty() {
-                return Err(Error::ConfigError(format!(
-                    "File URL cannot be empty for file part {} at message {}",
-                    part_index, msg_index
-                )));
+                report.push(
+                    format!("{field}.file_url.url"),
+                    "empty",
+                    format!(
+                        "File URL cannot be empty for file part {} at message {}",
+                        part_index, msg_index
+                    ),
+                );
             }
         }
     }
-
-    Ok(())
 }
 
-/// Validates tools in a request.
-fn validate_tools(tools: &[Tool]) -> Result<()> {
-    if tools.is_empty() {
-        return Ok(());
-    }
-
+/// Checks tools in a request.
+fn check_tools(tools: &[Tool], report: &mut ValidationReport) {
     // Check for duplicate function names
     let mut function_names = HashSet::new();
 
@@ -297,30 +605,57 @@ fn validate_tools(tools: &[Tool]) -> Result<()> {
         match tool {
             Tool::Function { function } => {
                 if function.name.trim().is_empty() {
-                    return Err(Error::ConfigError(format!(
-                        "Function name in tool[{i}] cannot be empty"
-                    )));
-                }
-
-                if !function_names.insert(&function.name) {
-                    return Err(Error::ConfigError(format!(
-                        "Duplicate function name '{}' in tools",
-                        function.name
-                    )));
+                    report.push(
+                        format!("tools[{i}].function.name"),
+                        "empty",
+                        format!("Function name in tool[{i}] cannot be empty"),
+                    );
+                } else if !function_names.insert(&function.name) {
+                    report.push(
+                        format!("tools[{i}].function.name"),
+                        "duplicate",
+                        format!("Duplicate function name '{}' in tools", function.name),
+                    );
                 }
 
                 // Validate parameters schema
                 if !function.parameters.is_object() {
-                    return Err(Error::ConfigError(format!(
-                        "Parameters for function '{}' must be a JSON object",
-                        function.name
-                    )));
+                    report.push(
+                        format!("tools[{i}].function.parameters"),
+                        "invalid_type",
+                        format!(
+                            "Parameters for function '{}' must be a JSON object",
+                            function.name
+                        ),
+                    );
                 }
             }
         }
     }
+}
 
-    Ok(())
+/// Flags `extra_body` keys that would collide with `messages` or a typed
+/// field the request sets.
+fn check_extra_body(
+    request: &ChatCompletionRequest,
+    extra_body: &serde_json::Map<String, serde_json::Value>,
+    report: &mut ValidationReport,
+) {
+    let typed = serde_json::to_value(ChatCompletionRequest {
+        messages: Vec::new(),
+        extra_body: None,
+        ..request.clone()
+    })
+    .unwrap_or_default();
+    for key in extra_body.keys() {
+        if typed.get(key).is_some() {
+            report.push(
+                format!("extra_body.{key}"),
+                "conflict",
+                format!("extra_body key '{key}' conflicts with a field set on the request"),
+            );
+        }
+    }
 }
 
 /// Estimates token count for a message (rough approximation).
@@ -460,6 +795,7 @@ mod tests {
             min_p: None,
             top_a: None,
             seed: None,
+            n: None,
             stop: None,
             logit_bias: None,
             logprobs: None,
@@ -470,6 +806,7 @@ mod tests {
             debug: None,
             plugins: None,
             reasoning: None,
+            extra_body: None,
         }
     }
 
@@ -496,6 +833,84 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_system_message_policy() {
+        let mut request = create_valid_chat_request();
+        request.messages = vec![
+            Message::text(ChatRole::System, "Be brief."),
+            Message::text(ChatRole::User, "Hi"),
+            Message::text(ChatRole::System, "Answer in French."),
+        ];
+        assert!(validate_chat_request(&request).is_ok());
+
+        let reject = ValidationPolicy::new().with_system_messages(SystemMessagePolicy::Reject);
+        repair_chat_request(&mut request, &reject);
+        assert!(validate_chat_request_with_policy(&request, &reject).is_err());
+
+        let merge = ValidationPolicy::new().with_system_messages(SystemMessagePolicy::Merge);
+        repair_chat_request(&mut request, &merge);
+        assert!(validate_chat_request_with_policy(&request, &merge).is_ok());
+        assert_eq!(request.messages.len(), 2);
+        assert_eq!(
+            request.messages[0].content.as_text(),
+            Some("Be brief.\n\nAnswer in French.")
+        );
+        assert_eq!(merge_system_messages(&mut request.messages), 0);
+    }
+
+    #[test]
+    fn test_validate_chat_request_all_reports_every_violation() {
+        let mut request = create_valid_chat_request();
+        assert!(validate_chat_request_all(&request).is_ok());
+
+        request.model = " ".to_string();
+        request.temperature = Some(3.0);
+        request.messages.push(Message::text(ChatRole::Tool, ""));
+        request.stop = Some(StopSequence::from(vec!["ok", ""]));
+
+        let report = validate_chat_request_all(&request).unwrap_err();
+        let found: Vec<_> = report
+            .violations
+            .iter()
+            .map(|violation| (violation.field.as_str(), violation.code))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("model", "empty"),
+                ("temperature", "out_of_range"),
+                ("messages[1].tool_call_id", "empty"),
+                ("stop[1]", "invalid_length"),
+            ]
+        );
+        assert!(report
+            .to_string()
+            .starts_with("model: Model ID cannot be empty (empty)"));
+
+        // The single-error validator reports the first violation.
+        assert!(matches!(
+            validate_chat_request(&request),
+            Err(Error::ConfigError(message)) if message == "Model ID cannot be empty"
+        ));
+    }
+
+    #[test]
+    fn test_validate_assistant_prefill() {
+        assert!(validate_assistant_prefill("anthropic/claude-sonnet-4", "{\"name\":").is_ok());
+        assert!(validate_assistant_prefill("openai/gpt-4o", "{").is_err());
+        assert!(validate_assistant_prefill("anthropic/claude-sonnet-4", "").is_err());
+        assert!(validate_assistant_prefill("anthropic/claude-sonnet-4", "Sure, ").is_err());
+    }
+
+    #[test]
+    fn test_validate_chat_request_n() {
+        let mut request = create_valid_chat_request();
+        for (n, valid) in [(1, true), (128, true), (0, false), (129, false)] {
+            request.n = Some(n);
+            assert_eq!(validate_chat_request(&request).is_ok(), valid, "n = {n}");
+        }
+    }
+
     #[test]
     fn test_check_token_limits_moderate_content() {
         let mut request = create_valid_chat_request();
@@ -505,4 +920,64 @@ mod tests {
         let result = check_token_limits(&request);
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_validate_chat_request_stop_sequences() {
+        let mut request = create_valid_chat_request();
+        request.stop = Some("\n\n".into());
+        assert!(validate_chat_request(&request).is_ok());
+
+        request.stop = Some(["END", "STOP", "DONE", "###"].into());
+        assert!(validate_chat_request(&request).is_ok());
+
+        for invalid in [
+            StopSequence::from(["a", "b", "c", "d", "e"]),
+            StopSequence::from(""),
+            StopSequence::from(vec!["ok", ""]),
+            StopSequence::from(Vec::<String>::new()),
+            StopSequence::from("x".repeat(MAX_STOP_SEQUENCE_LENGTH + 1)),
+        ] {
+            request.stop = Some(invalid);
+            assert!(matches!(
+                validate_chat_request(&request),
+                Err(Error::ConfigError(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_stop_sequence_new_validates() {
+        let stop = StopSequence::new(["END", "STOP"]).unwrap();
+        assert_eq!(stop.sequences(), ["END", "STOP"]);
+        assert_eq!(
+            serde_json::to_value(&stop).unwrap(),
+            serde_json::json!(["END", "STOP"])
+        );
+        assert_eq!(StopSequence::from("\n").sequences(), ["\n"]);
+        assert!(StopSequence::new(["a", "b", "c", "d", "e"]).is_err());
+        assert!(StopSequence::new(Vec::<String>::new()).is_err());
+    }
+
+    #[test]
+    fn test_validate_chat_request_logit_bias() {
+        use crate::types::chat::LogitBias;
+        use std::collections::HashMap;
+
+        let mut request = create_valid_chat_request();
+        request.logit_bias = Some(
+            LogitBias::new()
+                .with_token(50256, -100.0)
+                .with_token(1, 250.0),
+        );
+        assert!(validate_chat_request(&request).is_ok());
+        assert_eq!(request.logit_bias.as_ref().unwrap().get(1), Some(100.0));
+
+        request.logit_bias = Some(HashMap::from([(7, -100.5)]).into());
+        assert!(matches!(
+            validate_chat_request(&request),
+            Err(Error::ConfigError(_))
+        ));
+        request.logit_bias = Some(HashMap::from([(7, f32::NAN)]).into());
+        assert!(validate_chat_request(&request).is_err());
+    }
 }
@@ -1,8 +1,147 @@
 //! Common validation utilities used across all API endpoints
 
 use crate::error::{Error, Result};
+use crate::utils::diagnostics::{default_diagnostics, Diagnostic, DiagnosticsSink};
+use serde::Serialize;
 use std::collections::HashSet;
 
+/// One problem found while validating a request.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ValidationViolation {
+    /// Path of the offending field, e.g. `messages[2].content`
+    pub field: String,
+    /// Machine-readable kind of problem, e.g. `empty` or `out_of_range`
+    pub code: &'static str,
+    /// Human-readable description
+    pub message: String,
+}
+
+/// Every problem found while validating a request, in request order.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ValidationReport {
+    pub violations: Vec<ValidationViolation>,
+}
+
+impl ValidationReport {
+    /// Records a violation.
+    pub fn push(
+        &mut self,
+        field: impl Into<String>,
+        code: &'static str,
+        message: impl Into<String>,
+    ) {
+        self.violations.push(ValidationViolation {
+            field: field.into(),
+            code,
+            message: message.into(),
+        });
+    }
+
+    /// Records the failure of one of the single-error validators.
+    pub(crate) fn push_error(
+        &mut self,
+        field: impl Into<String>,
+        code: &'static str,
+        error: Error,
+    ) {
+        let message = match error {
+            Error::ConfigError(message) | Error::ValidationError(message) => message,
+            error => error.to_string(),
+        };
+        self.push(field, code, message);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.violations.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.violations.len()
+    }
+
+    /// `Ok` if nothing was reported.
+    pub fn into_result(self) -> std::result::Result<(), Self> {
+        if self.is_empty() {
+            Ok(())
+        } else {
+            Err(self)
+        }
+    }
+
+    /// The first violation as an `Error::ConfigError`, as returned by the
+    /// validators that stop at the first problem.
+    pub fn into_error(self) -> Error {
+        let message = self
+            .violations
+            .into_iter()
+            .next()
+            .map(|violation| violation.message)
+            .unwrap_or_else(|| "Validation failed".into());
+        Error::ConfigError(message)
+    }
+}
+
+impl std::fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, violation) in self.violations.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(
+                f,
+                "{}: {} ({})",
+                violation.field, violation.message, violation.code
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ValidationReport {}
+
+/// Whether requests are validated before they are sent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ValidationMode {
+    /// Invalid requests fail without being sent.
+    #[default]
+    Strict,
+    /// Validation failures are reported as
+    /// `Diagnostic::ValidationBypassed` and the request is sent anyway.
+    Lenient,
+    /// No validation runs.
+    Off,
+}
+
+impl ValidationMode {
+    /// Runs `check` as this mode prescribes.
+    pub fn apply(self, operation: &str, check: impl FnOnce() -> Result<()>) -> Result<()> {
+        self.apply_with(operation, default_diagnostics(), check)
+    }
+
+    /// Like [`apply`](Self::apply), reporting bypassed failures to
+    /// `diagnostics`.
+    pub(crate) fn apply_with(
+        self,
+        operation: &str,
+        diagnostics: &dyn DiagnosticsSink,
+        check: impl FnOnce() -> Result<()>,
+    ) -> Result<()> {
+        match self {
+            ValidationMode::Strict => check(),
+            ValidationMode::Lenient => {
+                if let Err(e) = check() {
+                    diagnostics.emit(&Diagnostic::ValidationBypassed {
+                        operation,
+                        error: &e.to_string(),
+                    });
+                }
+                Ok(())
+            }
+            ValidationMode::Off => Ok(()),
+        }
+    }
+}
+
 /// Validates a required string field
 pub fn validate_required_string<'a>(
     value: &'a Option<String>,
@@ -2,11 +2,44 @@
 
 use super::common::*;
 use crate::error::{Error, Result};
+use crate::types::chat::{MAX_STOP_SEQUENCES, MAX_STOP_SEQUENCE_LENGTH};
 use crate::types::completion::CompletionRequest;
 
 /// Maximum allowed prompt length for completions
 const MAX_PROMPT_LENGTH: usize = 1_000_000;
 
+/// Maximum number of completions per request (`n`)
+const MAX_COMPLETION_CHOICES: u32 = 128;
+
+/// Maximum number of server-side candidates (`best_of`)
+const MAX_BEST_OF: u32 = 20;
+
+/// Maximum allowed suffix length
+const MAX_SUFFIX_LENGTH: usize = 1000;
+
+/// Model ID prefixes of models known to accept `suffix` for
+/// fill-in-the-middle completion
+const FILL_IN_THE_MIDDLE_MODELS: &[&str] = &[
+    "mistralai/codestral",
+    "deepseek/deepseek-coder",
+    "qwen/qwen-2.5-coder",
+    "qwen/qwen2.5-coder",
+    "bigcode/starcoder",
+    "meta-llama/codellama",
+];
+
+/// Returns true if the model is known to support fill-in-the-middle
+/// completion with a `suffix`.
+///
+/// The list is not exhaustive, so other models are not rejected; the client
+/// reports `Diagnostic::UnsupportedParameter` for them instead.
+pub fn supports_suffix(model: &str) -> bool {
+    let model = model.to_ascii_lowercase();
+    FILL_IN_THE_MIDDLE_MODELS
+        .iter()
+        .any(|prefix| model.starts_with(prefix))
+}
+
 /// Validates a completion request for common errors
 pub fn validate_completion_request(request: &CompletionRequest) -> Result<()> {
     // Validate model
@@ -19,8 +52,75 @@ pub fn validate_completion_request(request: &CompletionRequest) -> Result<()> {
     // Validate extra parameters if present
     if let serde_json::Value::Object(params) = &request.extra_params {
         validate_extra_params(params)?;
+
+        // Typed fields would be serialized alongside the flattened copy
+        for (key, set) in [
+            ("echo", request.echo.is_some()),
+            ("suffix", request.suffix.is_some()),
+            ("best_of", request.best_of.is_some()),
+            ("n", request.n.is_some()),
+        ] {
+            if set && params.contains_key(key) {
+                return Err(Error::ConfigError(format!(
+                    "Parameter '{}' is set both as a field and in extra_params",
+                    key
+                )));
+            }
+        }
+    }
+
+    validate_choice_counts(request)?;
+    validate_suffix(request)?;
+
+    Ok(())
+}
+
+/// Validates `n` and `best_of`, from the typed fields or extra parameters
+fn validate_choice_counts(request: &CompletionRequest) -> Result<()> {
+    let n = request
+        .n
+        .map(u64::from)
+        .or_else(|| request.extra_params.get("n").and_then(|v| v.as_u64()));
+    let best_of = request
+        .best_of
+        .map(u64::from)
+        .or_else(|| request.extra_params.get("best_of").and_then(|v| v.as_u64()));
+
+    if let Some(n) = n {
+        validate_numeric_range(n, "n", 1, u64::from(MAX_COMPLETION_CHOICES))?;
+    }
+    if let Some(best_of) = best_of {
+        validate_numeric_range(best_of, "best_of", 1, u64::from(MAX_BEST_OF))?;
+        if best_of < n.unwrap_or(1) {
+            return Err(Error::ConfigError(format!(
+                "best_of ({}) must be greater than or equal to n ({})",
+                best_of,
+                n.unwrap_or(1)
+            )));
+        }
     }
+    Ok(())
+}
 
+/// Validates `suffix`, from the typed field or extra parameters
+fn validate_suffix(request: &CompletionRequest) -> Result<()> {
+    let suffix = request
+        .suffix
+        .as_deref()
+        .or_else(|| request.extra_params.get("suffix").and_then(|v| v.as_str()));
+    let Some(suffix) = suffix else {
+        return Ok(());
+    };
+
+    validate_string_length(suffix, "suffix", 0, MAX_SUFFIX_LENGTH)?;
+    let echo = request
+        .echo
+        .or_else(|| request.extra_params.get("echo").and_then(|v| v.as_bool()));
+    if echo == Some(true) {
+        return Err(Error::ConfigError(
+            "Parameter 'echo' cannot be combined with 'suffix'".to_string(),
+        ));
+    }
     Ok(())
 }
 
@@ -88,9 +188,7 @@ fn validate_extra_params(params: &serde_json::Map<String, serde_json::Value>) ->
 
     // Validate suffix parameter if present
     if let Some(value) = params.get("suffix") {
-        if let Some(suffix) = value.as_str() {
-            validate_string_length(suffix, "suffix", 0, 1000)?;
-        } else if !value.is_null() {
+        if !value.is_string() && !value.is_null() {
             return Err(Error::ConfigError(
                 "Parameter 'suffix' must be a string or null".to_string(),
             ));
@@ -127,15 +225,20 @@ fn validate_stop_sequence(value: &serde_json::Value) -> Result<()> {
     match value {
         serde_json::Value::String(stop) => {
             // Single stop sequence
-            validate_string_length(stop, "stop", 1, 100)?;
+            validate_string_length(stop, "stop", 1, MAX_STOP_SEQUENCE_LENGTH)?;
         }
         serde_json::Value::Array(stops) => {
             // Multiple stop sequences
-            validate_collection_size(stops, "stop", 1, 4)?;
+            validate_collection_size(stops, "stop", 1, MAX_STOP_SEQUENCES)?;
 
             for (index, stop_val) in stops.iter().enumerate() {
                 if let Some(stop_str) = stop_val.as_str() {
-                    validate_string_length(stop_str, &format!("stop[{}]", index), 1, 100)?;
+                    validate_string_length(
+                        stop_str,
+                        &format!("stop[{}]", index),
+                        1,
+                        MAX_STOP_SEQUENCE_LENGTH,
+                    )?;
                 } else {
                     return Err(Error::ConfigError(format!(
                         "Stop sequence at index {} must be a string",
@@ -229,6 +332,7 @@ mod tests {
             model: "openai/gpt-4".to_string(),
             prompt: "Once upon a time,".to_string(),
             extra_params: serde_json::json!({}),
+            ..Default::default()
         }
     }
 
@@ -492,6 +596,47 @@ mod tests {
         assert!(validate_completion_request(&request).is_err());
     }
 
+    #[test]
+    fn test_validate_typed_suffix() {
+        let mut request = create_valid_completion_request();
+        request.suffix = Some("\n}".to_string());
+        assert!(validate_completion_request(&request).is_ok());
+        assert!(!supports_suffix(&request.model));
+
+        request.model = "mistralai/codestral-2501".to_string();
+        assert!(validate_completion_request(&request).is_ok());
+        assert!(supports_suffix(&request.model));
+
+        request.echo = Some(true);
+        assert!(validate_completion_request(&request).is_err());
+
+        request.echo = None;
+        request.extra_params = json!({"suffix": "}"});
+        assert!(validate_completion_request(&request).is_err());
+    }
+
+    #[test]
+    fn test_validate_typed_choice_counts() {
+        let mut request = create_valid_completion_request();
+        request.n = Some(3);
+        request.best_of = Some(5);
+        assert!(validate_completion_request(&request).is_ok());
+
+        request.best_of = Some(2);
+        assert!(validate_completion_request(&request).is_err());
+
+        request.best_of = None;
+        request.n = Some(0);
+        assert!(validate_completion_request(&request).is_err());
+
+        request.n = Some(129);
+        assert!(validate_completion_request(&request).is_err());
+
+        request.n = Some(4);
+        request.extra_params = json!({"best_of": 2});
+        assert!(validate_completion_request(&request).is_err());
+    }
+
     #[test]
     fn test_validate_best_of_parameter() {
         let test_cases = [
@@ -41,11 +41,17 @@ pub mod completion;
 pub mod web_search;
 
 // Re-export commonly used validation functions for convenience
-pub use chat::{check_token_limits, validate_chat_request};
+pub use chat::{
+    check_token_limits, merge_system_messages, repair_chat_request, supports_assistant_prefill,
+    validate_assistant_prefill, validate_chat_request, validate_chat_request_all,
+    validate_chat_request_all_with_policy, validate_chat_request_with_policy, SystemMessagePolicy,
+    ValidationPolicy,
+};
 pub use common::{
     validate_date_format, validate_date_range, validate_enum_value, validate_model_id,
     validate_non_empty_collection, validate_non_empty_string, validate_numeric_range,
-    validate_sampling_parameters, validate_string_length, validate_url,
+    validate_sampling_parameters, validate_string_length, validate_url, ValidationMode,
+    ValidationReport, ValidationViolation,
 };
 pub use completion::{check_prompt_token_limits, validate_completion_request};
 pub use web_search::{
@@ -72,6 +72,7 @@ mod validation_tests {
             min_p: None,
             top_a: None,
             seed: None,
+            n: None,
             stop: None,
             logit_bias: None,
             logprobs: None,
@@ -82,6 +83,7 @@ mod validation_tests {
             debug: None,
             plugins: None,
             reasoning: None,
+            extra_body: None,
         };
 
         assert!(validate_chat_request(&request).is_ok());
@@ -94,6 +96,7 @@ mod validation_tests {
             model: "openai/gpt-4".to_string(),
             prompt: "Once upon a time,".to_string(),
             extra_params: serde_json::json!({"temperature": 0.7}),
+            ..Default::default()
         };
 
         assert!(validate_completion_request(&request).is_ok());
@@ -105,6 +108,7 @@ mod validation_tests {
         let request = WebSearchRequest {
             query: "rust programming language".to_string(),
             num_results: Some(10),
+            ..Default::default()
         };
 
         assert!(validate_web_search_request(&request).is_ok());
@@ -156,6 +160,7 @@ mod validation_tests {
                 min_p: None,
                 top_a: None,
                 seed: None,
+                n: None,
                 stop: None,
                 logit_bias: None,
                 logprobs: None,
@@ -166,6 +171,7 @@ mod validation_tests {
                 debug: None,
                 plugins: None,
                 reasoning: None,
+                extra_body: None,
             };
             let _ = validate_chat_request(&request);
         }
@@ -206,6 +212,7 @@ mod validation_tests {
             model: model.to_string(),
             prompt: "Hello".to_string(),
             extra_params: serde_json::json!({}),
+            ..Default::default()
         };
         assert!(validate_completion_request(&completion_request).is_ok());
 
@@ -213,6 +220,7 @@ mod validation_tests {
             model: "invalid".to_string(),
             prompt: "Hello".to_string(),
             extra_params: serde_json::json!({}),
+            ..Default::default()
         };
         assert!(validate_completion_request(&invalid_completion_request).is_err());
     }
@@ -11,11 +11,17 @@ const MAX_QUERY_LENGTH: usize = 1000;
 const MIN_QUERY_LENGTH: usize = 1;
 
 /// Maximum number of results that can be requested
-const MAX_RESULTS: u32 = 100;
+pub(crate) const MAX_RESULTS: u32 = 100;
 
 /// Minimum number of results that can be requested
 const MIN_RESULTS: u32 = 1;
 
+/// Maximum number of domains in each of the include and exclude lists
+const MAX_DOMAIN_FILTERS: usize = 50;
+
+/// Maximum length of a domain name
+const MAX_DOMAIN_LENGTH: usize = 253;
+
 /// Validates a web search request for common errors
 pub fn validate_web_search_request(request: &WebSearchRequest) -> Result<()> {
     // Validate query
@@ -30,6 +36,104 @@ pub fn validate_web_search_request(request: &WebSearchRequest) -> Result<()> {
         validate_numeric_range(num_results, "num_results", MIN_RESULTS, MAX_RESULTS)?;
     }
 
+    validate_domain_filters(request)?;
+    validate_date_restrictions(request)?;
+    validate_locale(request)?;
+
+    Ok(())
+}
+
+/// Validates the include and exclude domain lists
+fn validate_domain_filters(request: &WebSearchRequest) -> Result<()> {
+    for (domains, field_name) in [
+        (&request.include_domains, "include_domains"),
+        (&request.exclude_domains, "exclude_domains"),
+    ] {
+        let Some(domains) = domains else { continue };
+        validate_collection_size(domains, field_name, 1, MAX_DOMAIN_FILTERS)?;
+        for domain in domains {
+            validate_domain(domain, field_name)?;
+        }
+    }
+
+    if let (Some(include), Some(exclude)) = (&request.include_domains, &request.exclude_domains) {
+        if let Some(domain) = include.iter().find(|domain| {
+            exclude
+                .iter()
+                .any(|other| other.eq_ignore_ascii_case(domain))
+        }) {
+            return Err(Error::ConfigError(format!(
+                "Domain '{}' cannot be both included and excluded",
+                domain
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// Validates a bare domain name such as `docs.rs` (no scheme or path)
+fn validate_domain(domain: &str, field_name: &str) -> Result<()> {
+    let valid = !domain.is_empty()
+        && domain.len() <= MAX_DOMAIN_LENGTH
+        && domain.contains('.')
+        && domain.split('.').all(|label| {
+            !label.is_empty()
+                && label.len() <= 63
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label.chars().all(|c| c.is_alphanumeric() || c == '-')
+        });
+    if !valid {
+        return Err(Error::ConfigError(format!(
+            "Field '{}' contains invalid domain '{}'; use a bare domain like 'example.com' without scheme or path",
+            field_name, domain
+        )));
+    }
+    Ok(())
+}
+
+/// Validates the publication date restrictions
+fn validate_date_restrictions(request: &WebSearchRequest) -> Result<()> {
+    if let Some(start_date) = &request.start_date {
+        validate_date_format(start_date, "start_date")?;
+    }
+    if let Some(end_date) = &request.end_date {
+        validate_date_format(end_date, "end_date")?;
+    }
+    if let (Some(start_date), Some(end_date)) = (&request.start_date, &request.end_date) {
+        validate_date_range(start_date, end_date)?;
+    }
+    Ok(())
+}
+
+/// Validates the country code and language tag
+fn validate_locale(request: &WebSearchRequest) -> Result<()> {
+    if let Some(country) = &request.country {
+        if country.len() != 2 || !country.chars().all(|c| c.is_ascii_alphabetic()) {
+            return Err(Error::ConfigError(format!(
+                "Field 'country' must be a two-letter ISO 3166-1 code, got '{}'",
+                country
+            )));
+        }
+    }
+
+    if let Some(language) = &request.language {
+        let mut subtags = language.split(['-', '_']);
+        let primary_ok = subtags.next().is_some_and(|tag| {
+            (2..=3).contains(&tag.len()) && tag.chars().all(|c| c.is_ascii_alphabetic())
+        });
+        let rest_ok = subtags.all(|tag| {
+            (2..=8).contains(&tag.len()) && tag.chars().all(|c| c.is_ascii_alphanumeric())
+        });
+        if !primary_ok || !rest_ok {
+            return Err(Error::ConfigError(format!(
+                "Field 'language' must be a language tag like 'en' or 'pt-BR', got '{}'",
+                language
+            )));
+        }
+    }
+
     Ok(())
 }
 
@@ -145,10 +249,7 @@ pub fn looks_like_url_injection(query: &str) -> bool {
 
 /// Validates search query format and provides suggestions for improvement
 pub fn validate_and_suggest_query_improvement(query: &str) -> Result<Vec<String>> {
-    validate_web_search_request(&WebSearchRequest {
-        query: query.to_string(),
-        num_results: None,
-    })?;
+    validate_web_search_request(&WebSearchRequest::new(query))?;
 
     let mut suggestions = Vec::new();
 
@@ -243,6 +344,7 @@ mod tests {
         WebSearchRequest {
             query: "rust programming language".to_string(),
             num_results: Some(10),
+            ..Default::default()
         }
     }
 
@@ -529,6 +631,7 @@ mod tests {
         let simple_request = WebSearchRequest {
             query: "simple search".to_string(),
             num_results: Some(100),
+            ..Default::default()
         };
         assert!(validate_results_for_complexity(&simple_request).is_ok());
 
@@ -536,6 +639,7 @@ mod tests {
         let moderate_request = WebSearchRequest {
             query: "\"exact phrase\" search".to_string(),
             num_results: Some(50),
+            ..Default::default()
         };
         assert!(validate_results_for_complexity(&moderate_request).is_ok());
 
@@ -543,6 +647,7 @@ mod tests {
         let complex_request = WebSearchRequest {
             query: "AND:complex OR:query site:example.com".to_string(),
             num_results: Some(25),
+            ..Default::default()
         };
         assert!(validate_results_for_complexity(&complex_request).is_ok());
 
@@ -551,6 +656,7 @@ mod tests {
             query: "AND:very OR:complex AND:query OR:with AND:multiple site:test.com filetype:pdf \"phrase\""
                 .to_string(),
             num_results: Some(15),
+            ..Default::default()
         };
         assert!(validate_results_for_complexity(&very_complex_request).is_err());
 
@@ -559,6 +665,7 @@ mod tests {
             query: "AND:complex OR:query site:example.com filetype:pdf \"exact phrase\""
                 .to_string(),
             num_results: Some(50),
+            ..Default::default()
         };
         assert!(validate_results_for_complexity(&too_many_results_request).is_err());
     }
@@ -643,4 +750,68 @@ mod tests {
         let suggestions = validate_and_suggest_query_improvement(&request.query).unwrap();
         assert_eq!(suggestions.len(), 0); // Good query, no suggestions
     }
+
+    #[test]
+    fn test_validate_web_search_request_domain_filters() {
+        let request = create_valid_web_search_request()
+            .with_included_domains(["docs.rs", "github.com"])
+            .with_excluded_domains(["example.com"]);
+        assert!(validate_web_search_request(&request).is_ok());
+
+        for domain in [
+            "https://docs.rs",
+            "docs.rs/tokio",
+            "localhost",
+            "-bad.com",
+            "",
+        ] {
+            let request = create_valid_web_search_request().with_included_domains([domain]);
+            assert!(
+                validate_web_search_request(&request).is_err(),
+                "domain '{}' should be rejected",
+                domain
+            );
+        }
+
+        let request = create_valid_web_search_request().with_included_domains(Vec::<String>::new());
+        assert!(validate_web_search_request(&request).is_err());
+
+        let request = create_valid_web_search_request()
+            .with_included_domains(["docs.rs"])
+            .with_excluded_domains(["DOCS.rs"]);
+        assert!(validate_web_search_request(&request).is_err());
+    }
+
+    #[test]
+    fn test_validate_web_search_request_date_restrictions() {
+        let request = create_valid_web_search_request().with_date_range("2024-01-01", "2024-06-30");
+        assert!(validate_web_search_request(&request).is_ok());
+        let request = create_valid_web_search_request().with_start_date("2024-01-01");
+        assert!(validate_web_search_request(&request).is_ok());
+
+        let request = create_valid_web_search_request().with_date_range("2024-06-30", "2024-01-01");
+        assert!(validate_web_search_request(&request).is_err());
+        let request = create_valid_web_search_request().with_end_date("06/30/2024");
+        assert!(validate_web_search_request(&request).is_err());
+        let request = create_valid_web_search_request().with_start_date("2024-02-30");
+        assert!(validate_web_search_request(&request).is_err());
+    }
+
+    #[test]
+    fn test_validate_web_search_request_locale() {
+        let request = create_valid_web_search_request()
+            .with_country("US")
+            .with_language("pt-BR")
+            .with_safe_search(crate::types::web_search::SafeSearch::Moderate);
+        assert!(validate_web_search_request(&request).is_ok());
+
+        for country in ["USA", "u", "1A"] {
+            let request = create_valid_web_search_request().with_country(country);
+            assert!(validate_web_search_request(&request).is_err());
+        }
+        for language in ["english", "e", "en-", "en_US!"] {
+            let request = create_valid_web_search_request().with_language(language);
+            assert!(validate_web_search_request(&request).is_err());
+        }
+    }
 }
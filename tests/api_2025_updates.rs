@@ -60,7 +60,7 @@ fn test_policy_controls() {
 
     let json_prefs = serde_json::to_value(&prefs).unwrap();
     assert_eq!(json_prefs["allow"][0], "OpenAI");
-    assert_eq!(json_prefs["dataCollection"], "deny");
+    assert_eq!(json_prefs["data_collection"], "deny");
 }
 
 #[test]
@@ -0,0 +1,24 @@
+// Runs the conformance suite over the captured payloads in
+// `tests/data/conformance`. Add a capture there when the API starts
+// returning a new shape, so a later change cannot stop parsing it.
+
+use openrouter_api::conformance::{check_dir, PayloadKind};
+use std::path::PathBuf;
+
+#[test]
+fn test_captured_payloads_conform() {
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/data/conformance");
+    let report = check_dir(dir).unwrap();
+
+    let failures: Vec<_> = report
+        .failures()
+        .map(|f| format!("{}: {}", f.path.display(), f.error.as_deref().unwrap_or("")))
+        .collect();
+    assert!(
+        failures.is_empty(),
+        "non-conforming payloads: {failures:#?}"
+    );
+    for kind in PayloadKind::ALL {
+        assert!(report.count(kind) > 0, "no {kind} captures");
+    }
+}
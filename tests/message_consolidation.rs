@@ -10,11 +10,13 @@
 use openrouter_api::models::tool::ToolType;
 use openrouter_api::types::chat::{
     AudioContent, AudioUrl, ChatRole, ContentPart, ContentType, FileContent, FileUrl, ImageContent,
-    ImageUrl, Message, MessageContent, TextContent,
+    ImageUrl, Message, MessageContent, TextContent, ToolResult,
 };
 
+use openrouter_api::types::chat::ChatCompletionRequest;
 use openrouter_api::types::ids::ToolCallId;
-use serde_json::{from_str, from_value, to_value};
+use openrouter_api::utils::validation::validate_chat_request;
+use serde_json::{from_str, from_value, json, to_value};
 
 /// Test that Message uses ChatRole enum, not String
 #[test]
@@ -404,3 +406,42 @@ fn test_message_traits() {
     // Test PartialEq
     assert_eq!(msg, msg_clone);
 }
+
+/// Test tool results carrying text, JSON and images
+#[test]
+fn test_tool_result_content_shapes() {
+    let text = Message::tool_result("Sunny", "call-1");
+    assert_eq!(text.content, MessageContent::Text("Sunny".into()));
+
+    let data = Message::tool_result(ToolResult::Json(json!({"temp": 21})), "call-2");
+    assert_eq!(to_value(&data).unwrap()["content"], r#"{"temp":21}"#);
+
+    let image = Message::tool_result(ToolResult::image_base64("image/png", "aGk="), "call-3");
+    assert_eq!(
+        to_value(&image).unwrap(),
+        json!({
+            "role": "tool",
+            "content": [{"type": "image_url", "image_url": {"url": "data:image/png;base64,aGk="}}],
+            "tool_call_id": "call-3"
+        })
+    );
+
+    // Tool output values convert by shape.
+    assert_eq!(ToolResult::from(json!("ok")), ToolResult::Text("ok".into()));
+    assert_eq!(
+        ToolResult::from(json!({"type": "image_url", "image_url": {"url": "https://x/y.png"}})),
+        ToolResult::image_url("https://x/y.png")
+    );
+    assert_eq!(
+        ToolResult::from(json!({"type": "image_url"})),
+        ToolResult::Json(json!({"type": "image_url"}))
+    );
+
+    // Image results pass request validation.
+    let request = ChatCompletionRequest {
+        model: "anthropic/claude-sonnet-4".into(),
+        messages: vec![Message::text(ChatRole::User, "Draw"), image],
+        ..Default::default()
+    };
+    assert!(validate_chat_request(&request).is_ok());
+}
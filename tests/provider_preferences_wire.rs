@@ -0,0 +1,65 @@
+// Wire-format tests for `ProviderPreferences` against recorded request bodies.
+//
+// OpenRouter ignores provider preference keys it does not recognise, so a
+// key-casing regression silently drops the user's routing settings instead
+// of failing. The fixtures under `tests/data/wire` are request bodies in the
+// format the API documents; compare against them rather than against the
+// crate's own output.
+
+use openrouter_api::models::provider_preferences::{
+    DataCollection, ProviderPreferences, ProviderSort, Quantization,
+};
+use openrouter_api::types::chat::{ChatCompletionRequest, ChatRole, Message};
+use serde_json::Value;
+use std::path::PathBuf;
+
+fn fixture(name: &str) -> Value {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/data/wire")
+        .join(name);
+    serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+}
+
+#[test]
+fn test_provider_preferences_serialize_as_recorded() {
+    let recorded = fixture("provider_preferences.json");
+    let preferences = ProviderPreferences::new()
+        .with_order(vec!["together".into(), "deepinfra/turbo".into()])
+        .with_allow_fallbacks(false)
+        .with_require_parameters(true)
+        .with_data_collection(DataCollection::Deny)
+        .with_ignore(vec!["azure".into()])
+        .with_quantizations(vec![Quantization::Fp8, Quantization::Bf16])
+        .with_sort(ProviderSort::Throughput);
+    let request = ChatCompletionRequest {
+        model: "meta-llama/llama-3.1-70b-instruct".to_string(),
+        messages: vec![Message::text(ChatRole::User, "Hello")],
+        provider: Some(preferences),
+        ..Default::default()
+    };
+
+    let actual = serde_json::to_value(&request).unwrap();
+    assert_eq!(actual["provider"], recorded["provider"]);
+}
+
+#[test]
+fn test_provider_preferences_round_trip_recorded() {
+    let recorded = fixture("provider_preferences.json")["provider"].clone();
+    let preferences: ProviderPreferences = serde_json::from_value(recorded.clone()).unwrap();
+    assert_eq!(preferences.allow_fallbacks, Some(false));
+    assert_eq!(preferences.data_collection, Some(DataCollection::Deny));
+    assert_eq!(serde_json::to_value(&preferences).unwrap(), recorded);
+}
+
+#[test]
+fn test_provider_preferences_accept_legacy_camel_case() {
+    let legacy: ProviderPreferences =
+        serde_json::from_value(fixture("provider_preferences_legacy.json")).unwrap();
+    assert_eq!(legacy.allow_fallbacks, Some(false));
+    assert_eq!(legacy.require_parameters, Some(true));
+    assert_eq!(legacy.data_collection, Some(DataCollection::Deny));
+
+    let rewritten = serde_json::to_value(&legacy).unwrap();
+    assert_eq!(rewritten["allow_fallbacks"], false);
+    assert!(rewritten.get("allowFallbacks").is_none());
+}
@@ -0,0 +1,54 @@
+// Snapshot tests for the exported JSON Schemas.
+//
+// The schemas are a public contract for services outside Rust, so any change
+// to them must be deliberate. Regenerate the snapshots with
+// `UPDATE_SCHEMA_SNAPSHOTS=1 cargo test --features schema-export --test schema_export`
+// and review the diff.
+//
+// `preserve-unknown` opens the response schemas to extra fields, so the
+// snapshots are only checked without it.
+#![cfg(all(feature = "schema-export", not(feature = "preserve-unknown")))]
+
+use std::path::PathBuf;
+
+fn snapshot_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/data/schemas")
+        .join(format!("{name}.json"))
+}
+
+#[test]
+fn test_schemas_match_snapshots() {
+    let update = std::env::var_os("UPDATE_SCHEMA_SNAPSHOTS").is_some();
+    let mut mismatched = Vec::new();
+    for (name, schema) in openrouter_api::types::schema() {
+        let actual = serde_json::to_string_pretty(&schema).unwrap() + "\n";
+        let path = snapshot_path(name);
+        if update {
+            std::fs::write(&path, &actual).unwrap();
+        } else if std::fs::read_to_string(&path).ok().as_deref() != Some(actual.as_str()) {
+            mismatched.push(name);
+        }
+    }
+    assert!(
+        mismatched.is_empty(),
+        "schemas changed for {mismatched:?}; set UPDATE_SCHEMA_SNAPSHOTS=1 to accept"
+    );
+}
+
+#[test]
+fn test_schemas_follow_serde_representation() {
+    let schemas = openrouter_api::types::schema();
+
+    let request = &schemas["ChatCompletionRequest"];
+    let stop = &request["$defs"]["StopSequence"]["anyOf"];
+    assert_eq!(stop[0]["type"], "string");
+    assert_eq!(stop[1]["type"], "array");
+    let plugin = &request["$defs"]["Plugin"];
+    assert_eq!(plugin["required"], serde_json::json!(["id"]));
+
+    // Fields the client fills in locally are not part of the wire format.
+    let response = &schemas["ChatCompletionResponse"];
+    assert!(response["properties"].get("truncation").is_none());
+    assert!(response["properties"].get("choices").is_some());
+}
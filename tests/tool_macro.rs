@@ -0,0 +1,78 @@
+// Tools generated by `#[openrouter_tool]`.
+#![cfg(feature = "macros")]
+
+use openrouter_api::agent::AgentTool;
+use openrouter_api::{openrouter_tool, Error};
+use serde_json::json;
+
+/// Looks up the current weather.
+///
+/// Covers cities worldwide.
+///
+/// # Arguments
+///
+/// - `city`: The city name.
+/// - `days`: Forecast days, defaults to 1.
+#[openrouter_tool]
+async fn get_weather(city: String, days: Option<u32>) -> openrouter_api::Result<String> {
+    Ok(format!("Sunny in {city} for {} day(s)", days.unwrap_or(1)))
+}
+
+#[openrouter_tool]
+fn sum(values: Vec<f64>) -> Result<f64, Error> {
+    Ok(values.iter().sum())
+}
+
+#[test]
+fn test_definition_is_generated_from_signature_and_docs() {
+    let tool = get_weather_tool();
+    let definition = tool.definition();
+    assert_eq!(definition.name, "get_weather");
+    assert_eq!(
+        definition.description.as_deref(),
+        Some("Looks up the current weather.\n\nCovers cities worldwide.")
+    );
+    assert_eq!(
+        definition.parameters,
+        json!({
+            "type": "object",
+            "properties": {
+                "city": {"type": "string", "description": "The city name."},
+                "days": {"type": "integer", "description": "Forecast days, defaults to 1."}
+            },
+            "required": ["city"]
+        })
+    );
+
+    let definition = sum_tool().definition().clone();
+    assert_eq!(definition.description, None);
+    assert_eq!(
+        definition.parameters["properties"]["values"],
+        json!({"type": "array", "items": {"type": "number"}})
+    );
+}
+
+#[tokio::test]
+async fn test_generated_tool_calls_the_function() {
+    let tool = get_weather_tool();
+    assert_eq!(
+        tool.call(json!({"city": "Oslo"})).await.unwrap(),
+        json!("Sunny in Oslo for 1 day(s)")
+    );
+    assert_eq!(
+        tool.call(json!({"city": "Oslo", "days": 3})).await.unwrap(),
+        json!("Sunny in Oslo for 3 day(s)")
+    );
+    assert!(matches!(
+        tool.call(json!({"days": 3})).await,
+        Err(Error::ValidationError(_))
+    ));
+
+    assert_eq!(
+        sum_tool()
+            .call(json!({"values": [1.5, 2.5]}))
+            .await
+            .unwrap(),
+        json!(4.0)
+    );
+}